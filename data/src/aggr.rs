@@ -1,3 +1,4 @@
+pub mod bricks;
 pub mod ticks;
 pub mod time;
 
@@ -27,3 +28,101 @@ impl std::fmt::Display for TickCount {
         write!(f, "{}T", self.0)
     }
 }
+
+/// Height of a Renko brick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RenkoBrick {
+    /// In ticks of the chart's price step
+    Fixed(u16),
+    /// Average true range of one minute bars over this many minutes, taken once that much
+    /// trading has been seen
+    Atr(u16),
+}
+
+/// Bars closed by the move of the price or the amount traded instead of time or trade count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BarKind {
+    Renko(RenkoBrick),
+    /// Columns of X's and O's, each box `box_ticks` ticks high. A column turns after the
+    /// price moves `reversal` boxes against it
+    PointFigure {
+        box_ticks: u16,
+        reversal: u8,
+    },
+    /// Closes before its high and low would be more than this many ticks apart
+    Range(u16),
+    /// Closes once this much has traded, in the size unit volumes are shown in
+    Volume(u32),
+    /// Closes once this much quote currency value has traded
+    Dollar(u32),
+}
+
+impl BarKind {
+    pub const PRESETS: [BarKind; 19] = [
+        BarKind::Range(10),
+        BarKind::Range(25),
+        BarKind::Range(50),
+        BarKind::Range(100),
+        BarKind::Volume(100),
+        BarKind::Volume(1_000),
+        BarKind::Volume(10_000),
+        BarKind::Volume(100_000),
+        BarKind::Dollar(100_000),
+        BarKind::Dollar(1_000_000),
+        BarKind::Dollar(10_000_000),
+        BarKind::Renko(RenkoBrick::Fixed(5)),
+        BarKind::Renko(RenkoBrick::Fixed(10)),
+        BarKind::Renko(RenkoBrick::Fixed(25)),
+        BarKind::Renko(RenkoBrick::Atr(14)),
+        BarKind::PointFigure {
+            box_ticks: 5,
+            reversal: 3,
+        },
+        BarKind::PointFigure {
+            box_ticks: 10,
+            reversal: 3,
+        },
+        BarKind::PointFigure {
+            box_ticks: 25,
+            reversal: 3,
+        },
+        BarKind::PointFigure {
+            box_ticks: 10,
+            reversal: 1,
+        },
+    ];
+
+    /// Whether the bars keep the candles and footprints of their trades, Renko bricks and
+    /// point & figure columns are drawn in place of them
+    pub fn keeps_candles(&self) -> bool {
+        matches!(
+            self,
+            BarKind::Range(_) | BarKind::Volume(_) | BarKind::Dollar(_)
+        )
+    }
+}
+
+impl std::fmt::Display for BarKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BarKind::Renko(RenkoBrick::Fixed(ticks)) => write!(f, "Renko {ticks}t"),
+            BarKind::Renko(RenkoBrick::Atr(period)) => write!(f, "Renko ATR{period}"),
+            BarKind::PointFigure {
+                box_ticks,
+                reversal,
+            } => write!(f, "P&F {box_ticks}×{reversal}"),
+            BarKind::Range(ticks) => write!(f, "Range {ticks}t"),
+            BarKind::Volume(qty) => write!(f, "Vol {}", thousands(*qty)),
+            BarKind::Dollar(value) => write!(f, "${}", thousands(*value)),
+        }
+    }
+}
+
+/// Round amounts the way they're picked, 1000 as "1K"
+fn thousands(amount: u32) -> String {
+    match amount {
+        n if n >= 1_000_000 && n % 1_000_000 == 0 => format!("{}M", n / 1_000_000),
+        n if n >= 1_000 && n % 1_000 == 0 => format!("{}K", n / 1_000),
+        n => n.to_string(),
+    }
+}
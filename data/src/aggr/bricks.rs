@@ -0,0 +1,257 @@
+//! Renko bricks and point & figure columns, built trade by trade out of the same
+//! [`TickAccumulation`]s as tick charts.
+//!
+//! The last datapoint is always the one still forming, it takes the trades until the price
+//! closes a brick or turns the column.
+
+use super::ticks::TickAccumulation;
+use super::{BarKind, RenkoBrick};
+use crate::chart::oscillator;
+
+use exchange::Trade;
+use exchange::unit::price::{Price, PriceStep};
+
+const MINUTE_MS: u64 = 60_000;
+
+#[derive(Debug, Clone)]
+pub struct BrickBuilder {
+    kind: BarKind,
+    /// Brick or box height in price units, unknown until an ATR brick has seen enough trading
+    size: Option<i64>,
+    /// Held back while the ATR brick size is worked out
+    pending: Vec<Trade>,
+    /// Minute, high, low and close of the minutes seen while working out the ATR
+    minutes: Vec<(u64, f64, f64, f64)>,
+    /// Renko: open and close of the last brick, the starting level twice before the first.
+    /// Point & figure: first and last box of the current column
+    levels: (i64, i64),
+    /// Direction of the last brick or the current column, `None` before the price has moved
+    rising: Option<bool>,
+}
+
+impl BrickBuilder {
+    pub fn new(kind: BarKind, step: PriceStep) -> Self {
+        let step = step.units.max(1);
+        let size = match kind {
+            BarKind::Renko(RenkoBrick::Fixed(ticks)) => Some(i64::from(ticks.max(1)) * step),
+            BarKind::Renko(RenkoBrick::Atr(_)) => None,
+            BarKind::PointFigure { box_ticks, .. } => Some(i64::from(box_ticks.max(1)) * step),
+            // Keep their candles, see `TickAggr::bars`
+            BarKind::Range(_) | BarKind::Volume(_) | BarKind::Dollar(_) => None,
+        };
+
+        Self {
+            kind,
+            size,
+            pending: vec![],
+            minutes: vec![],
+            levels: (0, 0),
+            rising: None,
+        }
+    }
+
+    pub fn kind(&self) -> BarKind {
+        self.kind
+    }
+
+    /// Adds the trade to `datapoints`, pushing the indices of those it changed to `touched`
+    pub fn insert(
+        &mut self,
+        trade: &Trade,
+        step: PriceStep,
+        datapoints: &mut Vec<TickAccumulation>,
+        touched: &mut Vec<usize>,
+    ) {
+        if let Some(size) = self.size {
+            self.add(trade, size, step, datapoints, touched);
+            return;
+        }
+
+        self.pending.push(*trade);
+        self.track_minute(trade);
+
+        if let Some(size) = self.atr_size(step) {
+            self.size = Some(size);
+            self.minutes.clear();
+
+            for trade in std::mem::take(&mut self.pending) {
+                self.add(&trade, size, step, datapoints, touched);
+            }
+        }
+    }
+
+    fn add(
+        &mut self,
+        trade: &Trade,
+        size: i64,
+        step: PriceStep,
+        datapoints: &mut Vec<TickAccumulation>,
+        touched: &mut Vec<usize>,
+    ) {
+        match self.kind {
+            BarKind::Renko(_) => self.add_renko(trade, size, step, datapoints, touched),
+            BarKind::PointFigure { reversal, .. } => {
+                self.add_point_figure(trade, size, reversal, step, datapoints, touched);
+            }
+            BarKind::Range(_) | BarKind::Volume(_) | BarKind::Dollar(_) => {}
+        }
+    }
+
+    fn add_renko(
+        &mut self,
+        trade: &Trade,
+        size: i64,
+        step: PriceStep,
+        datapoints: &mut Vec<TickAccumulation>,
+        touched: &mut Vec<usize>,
+    ) {
+        let price = trade.price.units;
+
+        match datapoints.last_mut() {
+            Some(forming) => forming.update_with_trade(trade, step),
+            None => {
+                let start = floor_to(price, size);
+                self.levels = (start, start);
+                datapoints.push(TickAccumulation::new(trade, step));
+            }
+        }
+
+        loop {
+            let (open, close) = self.levels;
+            let (top, bottom) = (open.max(close), open.min(close));
+
+            let brick = if price >= top + size {
+                (top, top + size)
+            } else if price <= bottom - size {
+                (bottom, bottom - size)
+            } else {
+                break;
+            };
+
+            let index = datapoints.len() - 1;
+            datapoints[index].set_body(Price::from_units(brick.0), Price::from_units(brick.1));
+            touched.push(index);
+
+            self.levels = brick;
+            self.rising = Some(brick.1 > brick.0);
+            datapoints.push(TickAccumulation::empty(
+                trade.time,
+                Price::from_units(brick.1),
+            ));
+        }
+
+        // Drawn from the last brick's close to the price, a brick in the making
+        let index = datapoints.len() - 1;
+        datapoints[index].set_body(Price::from_units(self.levels.1), trade.price);
+        touched.push(index);
+    }
+
+    fn add_point_figure(
+        &mut self,
+        trade: &Trade,
+        size: i64,
+        reversal: u8,
+        step: PriceStep,
+        datapoints: &mut Vec<TickAccumulation>,
+        touched: &mut Vec<usize>,
+    ) {
+        let price = trade.price.units;
+        let turn = i64::from(reversal.max(1)) * size;
+        let (first, last) = self.levels;
+
+        if datapoints.is_empty() {
+            let start = floor_to(price, size);
+            self.levels = (start, start);
+            datapoints.push(TickAccumulation::new(trade, step));
+        } else {
+            let turned = match self.rising {
+                None if price >= first + size => {
+                    self.rising = Some(true);
+                    self.levels.1 = floor_to(price, size);
+                    false
+                }
+                None if price <= first - size => {
+                    self.rising = Some(false);
+                    self.levels.1 = ceil_to(price, size);
+                    false
+                }
+                Some(true) if price >= last + size => {
+                    self.levels.1 = floor_to(price, size);
+                    false
+                }
+                Some(false) if price <= last - size => {
+                    self.levels.1 = ceil_to(price, size);
+                    false
+                }
+                // A new column starts a box away from where the last one ended
+                Some(true) if price <= last - turn => {
+                    self.rising = Some(false);
+                    self.levels = (last - size, ceil_to(price, size));
+                    true
+                }
+                Some(false) if price >= last + turn => {
+                    self.rising = Some(true);
+                    self.levels = (last + size, floor_to(price, size));
+                    true
+                }
+                _ => false,
+            };
+
+            if turned {
+                datapoints.push(TickAccumulation::new(trade, step));
+            } else if let Some(column) = datapoints.last_mut() {
+                column.update_with_trade(trade, step);
+            }
+        }
+
+        let index = datapoints.len() - 1;
+        let (first, last) = self.levels;
+        datapoints[index].set_body(Price::from_units(first), Price::from_units(last));
+        touched.push(index);
+    }
+
+    fn track_minute(&mut self, trade: &Trade) {
+        let minute = trade.time.as_u64() / MINUTE_MS;
+        let price = trade.price.to_f64();
+
+        match self.minutes.last_mut() {
+            Some((last, high, low, close)) if *last == minute => {
+                *high = high.max(price);
+                *low = low.min(price);
+                *close = price;
+            }
+            _ => self.minutes.push((minute, price, price, price)),
+        }
+    }
+
+    /// ATR of the minutes that have closed, rounded to the price step, once there are enough
+    fn atr_size(&self, step: PriceStep) -> Option<i64> {
+        let BarKind::Renko(RenkoBrick::Atr(period)) = self.kind else {
+            return None;
+        };
+        let closed = self.minutes.split_last()?.1;
+        if closed.len() < usize::from(period.max(1)) {
+            return None;
+        }
+
+        let highs = closed.iter().map(|m| m.1).collect::<Vec<_>>();
+        let lows = closed.iter().map(|m| m.2).collect::<Vec<_>>();
+        let closes = closed.iter().map(|m| m.3).collect::<Vec<_>>();
+        let atr = oscillator::atr(&highs, &lows, &closes, period)
+            .last()
+            .copied()
+            .flatten()?;
+
+        let step = step.units.max(1);
+        let size = Price::from_f64(atr).units;
+        Some(((size + step / 2) / step * step).max(step))
+    }
+}
+
+fn floor_to(units: i64, size: i64) -> i64 {
+    units.div_euclid(size) * size
+}
+
+fn ceil_to(units: i64, size: i64) -> i64 {
+    -((-units).div_euclid(size) * size)
+}
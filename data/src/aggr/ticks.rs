@@ -1,8 +1,11 @@
 use crate::aggr;
+use crate::aggr::bricks::BrickBuilder;
 use crate::chart::kline::{ClusterKind, KlineTrades, NPoc};
+use exchange::adapter::MarketKind;
 use exchange::unit::Qty;
 use exchange::unit::price::{Price, PriceStep};
-use exchange::{Kline, Trade, Volume};
+use exchange::unit::qty::{SizeUnit, volume_size_unit};
+use exchange::{Kline, Trade, UnixMs, Volume};
 
 use std::collections::BTreeMap;
 
@@ -34,6 +37,31 @@ impl TickAccumulation {
         }
     }
 
+    /// A bar with no trades yet, opened at `price`
+    pub fn empty(time: UnixMs, price: Price) -> Self {
+        Self {
+            tick_count: 0,
+            kline: Kline {
+                time,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: Volume::empty_buy_sell(),
+            },
+            footprint: KlineTrades::new(),
+        }
+    }
+
+    /// Reshapes the candle to a body from `open` to `close` without wicks, the way bricks
+    /// and columns are drawn
+    pub fn set_body(&mut self, open: Price, close: Price) {
+        self.kline.open = open;
+        self.kline.close = close;
+        self.kline.high = open.max(close);
+        self.kline.low = open.min(close);
+    }
+
     pub fn update_with_trade(&mut self, trade: &Trade, step: PriceStep) {
         self.tick_count += 1;
         self.kline.high = self.kline.high.max(trade.price);
@@ -89,17 +117,113 @@ impl TickAccumulation {
     }
 }
 
+/// What closes a bar and starts the next
+#[derive(Debug, Clone)]
+enum BarClose {
+    Count(aggr::TickCount),
+    Bricks(BrickBuilder),
+    Activity(ActivityClose),
+}
+
+impl BarClose {
+    /// Whether `trade` opens a new bar instead of going into `forming`
+    fn starts_bar(
+        &mut self,
+        forming: Option<&TickAccumulation>,
+        trade: &Trade,
+        step: PriceStep,
+    ) -> bool {
+        match self {
+            BarClose::Count(interval) => forming.is_none_or(|dp| dp.is_full(*interval)),
+            BarClose::Activity(close) => close.starts_bar(forming, trade, step),
+            // Bricks place their own trades
+            BarClose::Bricks(_) => false,
+        }
+    }
+}
+
+/// Range, volume and dollar bars, closed by how far the price ranged or how much traded
+#[derive(Debug, Clone)]
+struct ActivityClose {
+    kind: aggr::BarKind,
+    market: MarketKind,
+    /// Volume or quote value the forming bar has taken
+    filled: f64,
+}
+
+impl ActivityClose {
+    fn starts_bar(
+        &mut self,
+        forming: Option<&TickAccumulation>,
+        trade: &Trade,
+        step: PriceStep,
+    ) -> bool {
+        let (amount, threshold) = match self.kind {
+            aggr::BarKind::Range(ticks) => {
+                let Some(forming) = forming else {
+                    return true;
+                };
+                let high = forming.kline.high.max(trade.price);
+                let low = forming.kline.low.min(trade.price);
+
+                return high.units - low.units > i64::from(ticks.max(1)) * step.units;
+            }
+            aggr::BarKind::Volume(threshold) => (trade.qty.to_f64(), threshold),
+            aggr::BarKind::Dollar(threshold) => {
+                let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+                let value =
+                    self.market
+                        .qty_in_quote_value(trade.qty, trade.price, size_in_quote_ccy);
+                (value, threshold)
+            }
+            aggr::BarKind::Renko(_) | aggr::BarKind::PointFigure { .. } => return false,
+        };
+
+        let starts_bar = forming.is_none() || self.filled >= f64::from(threshold);
+        if starts_bar {
+            self.filled = amount;
+        } else {
+            self.filled += amount;
+        }
+        starts_bar
+    }
+}
+
 pub struct TickAggr {
     pub datapoints: Vec<TickAccumulation>,
-    pub interval: aggr::TickCount,
+    close: BarClose,
     pub tick_size: PriceStep,
 }
 
 impl TickAggr {
     pub fn new(interval: aggr::TickCount, tick_size: PriceStep, raw_trades: &[Trade]) -> Self {
+        Self::with_close(BarClose::Count(interval), tick_size, raw_trades)
+    }
+
+    /// Bars closed by price or traded amount instead of a fixed number of trades
+    pub fn bars(
+        kind: aggr::BarKind,
+        market: MarketKind,
+        tick_size: PriceStep,
+        raw_trades: &[Trade],
+    ) -> Self {
+        let close = if kind.keeps_candles() {
+            BarClose::Activity(ActivityClose {
+                kind,
+                market,
+                filled: 0.0,
+            })
+        } else {
+            BarClose::Bricks(BrickBuilder::new(kind, tick_size))
+        };
+
+        Self::with_close(close, tick_size, raw_trades)
+    }
+
+    fn with_close(close: BarClose, tick_size: PriceStep, raw_trades: &[Trade]) -> Self {
         let mut tick_aggr = Self {
             datapoints: Vec::new(),
-            interval,
+            close,
             tick_size,
         };
 
@@ -114,6 +238,11 @@ impl TickAggr {
         self.tick_size = tick_size;
 
         self.datapoints.clear();
+        match &mut self.close {
+            BarClose::Bricks(builder) => *builder = BrickBuilder::new(builder.kind(), tick_size),
+            BarClose::Activity(close) => close.filled = 0.0,
+            BarClose::Count(_) => {}
+        }
 
         if !raw_trades.is_empty() {
             self.insert_trades(raw_trades);
@@ -134,27 +263,43 @@ impl TickAggr {
     pub fn insert_trades(&mut self, buffer: &[Trade]) {
         let mut updated_indices = Vec::new();
 
+        if let BarClose::Bricks(builder) = &mut self.close {
+            for trade in buffer {
+                builder.insert(
+                    trade,
+                    self.tick_size,
+                    &mut self.datapoints,
+                    &mut updated_indices,
+                );
+            }
+            updated_indices.dedup();
+
+            self.calculate_pocs(updated_indices);
+            return;
+        }
+
         for trade in buffer {
-            if self.datapoints.is_empty() {
+            if self
+                .close
+                .starts_bar(self.datapoints.last(), trade, self.tick_size)
+            {
                 self.datapoints
                     .push(TickAccumulation::new(trade, self.tick_size));
-                updated_indices.push(0);
+                updated_indices.push(self.datapoints.len() - 1);
             } else {
                 let last_idx = self.datapoints.len() - 1;
 
-                if self.datapoints[last_idx].is_full(self.interval) {
-                    self.datapoints
-                        .push(TickAccumulation::new(trade, self.tick_size));
-                    updated_indices.push(self.datapoints.len() - 1);
-                } else {
-                    self.datapoints[last_idx].update_with_trade(trade, self.tick_size);
-                    if !updated_indices.contains(&last_idx) {
-                        updated_indices.push(last_idx);
-                    }
+                self.datapoints[last_idx].update_with_trade(trade, self.tick_size);
+                if !updated_indices.contains(&last_idx) {
+                    updated_indices.push(last_idx);
                 }
             }
         }
 
+        self.calculate_pocs(updated_indices);
+    }
+
+    fn calculate_pocs(&mut self, updated_indices: Vec<usize>) {
         for idx in updated_indices {
             if idx < self.datapoints.len() {
                 self.datapoints[idx].calculate_poc();
@@ -175,30 +320,54 @@ impl TickAggr {
         let total_points = self.datapoints.len();
 
         for (current_idx, poc_price) in updates {
-            let mut npoc = NPoc::default();
-
-            for next_idx in (current_idx + 1)..total_points {
-                let next_dp = &self.datapoints[next_idx];
-
-                let next_dp_low = next_dp.kline.low.round_to_side_step(true, self.tick_size);
-                let next_dp_high = next_dp.kline.high.round_to_side_step(false, self.tick_size);
-
-                if next_dp_low <= poc_price && next_dp_high >= poc_price {
-                    // on render we reverse the order of the points
-                    // as it is easier to just take the idx=0 as latest candle for coords
-                    let reversed_idx = (total_points - 1) - next_idx;
-                    npoc.filled(reversed_idx as u64);
-                    break;
-                } else {
-                    npoc.unfilled();
-                }
-            }
+            let npoc = self.revisit_status(current_idx, poc_price);
 
             if current_idx < total_points {
                 let data_point = &mut self.datapoints[current_idx];
                 data_point.set_poc_status(npoc);
             }
         }
+
+        for current_idx in 0..total_points {
+            let statuses = self.datapoints[current_idx]
+                .footprint
+                .unfinished_auctions
+                .iter()
+                .map(|auction| self.revisit_status(current_idx, auction.price))
+                .collect::<Vec<_>>();
+
+            self.datapoints[current_idx]
+                .footprint
+                .unfinished_auctions
+                .iter_mut()
+                .zip(statuses)
+                .for_each(|(auction, status)| auction.status = status);
+        }
+    }
+
+    /// Whether a datapoint after `current_idx` traded back at `price`, and the first that did
+    fn revisit_status(&self, current_idx: usize, price: Price) -> NPoc {
+        let total_points = self.datapoints.len();
+        let mut status = NPoc::default();
+
+        for next_idx in (current_idx + 1)..total_points {
+            let next_dp = &self.datapoints[next_idx];
+
+            let next_dp_low = next_dp.kline.low.round_to_side_step(true, self.tick_size);
+            let next_dp_high = next_dp.kline.high.round_to_side_step(false, self.tick_size);
+
+            if next_dp_low <= price && next_dp_high >= price {
+                // on render we reverse the order of the points
+                // as it is easier to just take the idx=0 as latest candle for coords
+                let reversed_idx = (total_points - 1) - next_idx;
+                status.filled(reversed_idx as u64);
+                break;
+            } else {
+                status.unfilled();
+            }
+        }
+
+        status
     }
 
     pub fn min_max_price_in_range_prices(
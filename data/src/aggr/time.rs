@@ -29,6 +29,8 @@ pub struct TimeSeries<D: DataPoint> {
     pub datapoints: BTreeMap<UnixMs, D>,
     pub interval: Timeframe,
     pub tick_size: PriceStep,
+    /// Lower timeframe klines a custom interval is resampled from, empty otherwise
+    source_klines: BTreeMap<UnixMs, Kline>,
 }
 
 impl<D: DataPoint> TimeSeries<D> {
@@ -225,6 +227,7 @@ impl TimeSeries<KlineDataPoint> {
             datapoints: BTreeMap::new(),
             interval,
             tick_size,
+            source_klines: BTreeMap::new(),
         };
 
         timeseries.insert_klines(klines);
@@ -236,6 +239,7 @@ impl TimeSeries<KlineDataPoint> {
             datapoints: self.datapoints.clone(),
             interval: self.interval,
             tick_size: self.tick_size,
+            source_klines: self.source_klines.clone(),
         };
 
         new_series.insert_trades_or_create_bucket(trades);
@@ -243,6 +247,11 @@ impl TimeSeries<KlineDataPoint> {
     }
 
     pub fn insert_klines(&mut self, klines: &[Kline]) {
+        if self.interval.is_custom() {
+            self.insert_resampled_klines(klines);
+            return;
+        }
+
         for kline in klines {
             let entry = self
                 .datapoints
@@ -258,6 +267,65 @@ impl TimeSeries<KlineDataPoint> {
         self.update_poc_status();
     }
 
+    /// Rebuilds the candles the lower timeframe klines fall into. The latest one is streamed
+    /// over and over as it forms, so they're kept rather than added up
+    fn insert_resampled_klines(&mut self, klines: &[Kline]) {
+        let mut updated_times = Vec::new();
+
+        for kline in klines {
+            self.source_klines.insert(kline.time, *kline);
+
+            let rounded_time = kline.time.floor_to(self.interval);
+            if !updated_times.contains(&rounded_time) {
+                updated_times.push(rounded_time);
+            }
+        }
+
+        for time in updated_times {
+            let end = time.offset_by_timeframe(self.interval, 1);
+            let mut sources = self.source_klines.range(time..end).map(|(_, kline)| kline);
+
+            let Some(first) = sources.next() else {
+                continue;
+            };
+            let resampled = sources.fold(Kline { time, ..*first }, |acc, kline| Kline {
+                high: acc.high.max(kline.high),
+                low: acc.low.min(kline.low),
+                close: kline.close,
+                volume: merge_volume(acc.volume, kline.volume),
+                ..acc
+            });
+
+            self.datapoints
+                .entry(time)
+                .or_insert_with(|| KlineDataPoint {
+                    kline: resampled,
+                    footprint: KlineTrades::new(),
+                })
+                .kline = resampled;
+        }
+
+        self.update_poc_status();
+    }
+
+    /// The candles `klines` went into, the same klines unless the interval is resampled
+    pub fn resampled(&self, klines: &[Kline]) -> Vec<Kline> {
+        if !self.interval.is_custom() {
+            return klines.to_vec();
+        }
+
+        let mut times = klines
+            .iter()
+            .map(|kline| kline.time.floor_to(self.interval))
+            .collect::<Vec<_>>();
+        times.dedup();
+
+        times
+            .into_iter()
+            .filter_map(|time| self.datapoints.get(&time).map(|dp| dp.kline))
+            .collect()
+    }
+
     pub fn insert_trades_or_create_bucket(&mut self, buffer: &[Trade]) {
         if buffer.is_empty() {
             return;
@@ -296,6 +364,52 @@ impl TimeSeries<KlineDataPoint> {
         }
     }
 
+    /// Like [`Self::insert_trades_or_create_bucket`], but the trades also make up the candles,
+    /// for timeframes the venue has no klines for
+    pub fn insert_trades_as_klines(&mut self, buffer: &[Trade]) {
+        if buffer.is_empty() {
+            return;
+        }
+        let mut updated_times = Vec::new();
+
+        buffer.iter().for_each(|trade| {
+            let rounded_time = trade.time.floor_to(self.interval);
+
+            if !updated_times.contains(&rounded_time) {
+                updated_times.push(rounded_time);
+            }
+
+            let entry = self
+                .datapoints
+                .entry(rounded_time)
+                .or_insert_with(|| KlineDataPoint {
+                    kline: Kline {
+                        time: rounded_time,
+                        open: trade.price,
+                        high: trade.price,
+                        low: trade.price,
+                        close: trade.price,
+                        volume: Volume::empty_buy_sell(),
+                    },
+                    footprint: KlineTrades::new(),
+                });
+
+            let kline = &mut entry.kline;
+            kline.high = kline.high.max(trade.price);
+            kline.low = kline.low.min(trade.price);
+            kline.close = trade.price;
+            kline.volume = kline.volume.add_trade_qty(trade.is_sell, trade.qty);
+
+            entry.add_trade(trade, self.tick_size);
+        });
+
+        for time in updated_times {
+            if let Some(data_point) = self.datapoints.get_mut(&time) {
+                data_point.calculate_poc();
+            }
+        }
+    }
+
     pub fn insert_trades_existing_buckets(&mut self, buffer: &[Trade]) {
         if buffer.is_empty() {
             return;
@@ -338,26 +452,59 @@ impl TimeSeries<KlineDataPoint> {
             .collect::<Vec<_>>();
 
         for (current_time, poc_price) in updates {
-            let mut npoc = NPoc::default();
+            let npoc = self.revisit_status(current_time, poc_price);
 
-            for (&next_time, next_dp) in self.datapoints.range(current_time.saturating_add(1)..) {
-                let next_dp_low = next_dp.kline.low.round_to_side_step(true, self.tick_size);
-                let next_dp_high = next_dp.kline.high.round_to_side_step(false, self.tick_size);
-
-                if next_dp_low <= poc_price && next_dp_high >= poc_price {
-                    npoc.filled(next_time.as_u64());
-                    break;
-                } else {
-                    npoc.unfilled();
-                }
+            if let Some(data_point) = self.datapoints.get_mut(&current_time) {
+                data_point.set_poc_status(npoc);
             }
+        }
+
+        let auctions = self
+            .datapoints
+            .iter()
+            .filter(|(_, dp)| !dp.footprint.unfinished_auctions.is_empty())
+            .map(|(&time, dp)| {
+                let statuses = dp
+                    .footprint
+                    .unfinished_auctions
+                    .iter()
+                    .map(|auction| self.revisit_status(time, auction.price))
+                    .collect::<Vec<_>>();
+                (time, statuses)
+            })
+            .collect::<Vec<_>>();
 
+        for (current_time, statuses) in auctions {
             if let Some(data_point) = self.datapoints.get_mut(&current_time) {
-                data_point.set_poc_status(npoc);
+                data_point
+                    .footprint
+                    .unfinished_auctions
+                    .iter_mut()
+                    .zip(statuses)
+                    .for_each(|(auction, status)| auction.status = status);
             }
         }
     }
 
+    /// Whether a candle after `time` traded back at `price`, and the first that did
+    fn revisit_status(&self, time: UnixMs, price: Price) -> NPoc {
+        let mut status = NPoc::default();
+
+        for (&next_time, next_dp) in self.datapoints.range(time.saturating_add(1)..) {
+            let next_dp_low = next_dp.kline.low.round_to_side_step(true, self.tick_size);
+            let next_dp_high = next_dp.kline.high.round_to_side_step(false, self.tick_size);
+
+            if next_dp_low <= price && next_dp_high >= price {
+                status.filled(next_time.as_u64());
+                break;
+            } else {
+                status.unfilled();
+            }
+        }
+
+        status
+    }
+
     pub fn min_max_footprint_price_in_range(
         &self,
         earliest: UnixMs,
@@ -478,15 +625,11 @@ impl TimeSeries<KlineDataPoint> {
 
 impl TimeSeries<HeatmapDataPoint> {
     pub fn new(basis: Basis, tick_size: PriceStep) -> Self {
-        let timeframe = match basis {
-            Basis::Time(interval) => interval,
-            Basis::Tick(_) => unimplemented!(),
-        };
-
         Self {
             datapoints: BTreeMap::new(),
-            interval: timeframe,
+            interval: basis.heatmap_interval(),
             tick_size,
+            source_klines: BTreeMap::new(),
         }
     }
 
@@ -549,3 +692,12 @@ impl From<&TimeSeries<KlineDataPoint>> for BTreeMap<UnixMs, exchange::Volume> {
             .collect()
     }
 }
+
+fn merge_volume(a: Volume, b: Volume) -> Volume {
+    match (a.buy_sell(), b.buy_sell()) {
+        (Some((a_buy, a_sell)), Some((b_buy, b_sell))) => {
+            Volume::BuySell(a_buy + b_buy, a_sell + b_sell)
+        }
+        _ => Volume::TotalOnly(a.total() + b.total()),
+    }
+}
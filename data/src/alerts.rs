@@ -0,0 +1,467 @@
+//! Price and volume alerts set per ticker, checked against the live market streams.
+//!
+//! Alerts are persisted with the saved state, the trackers used to evaluate them are
+//! rebuilt from the streams after every launch. An alert is only evaluated while a pane
+//! streams its ticker: trades drive every condition, klines only the price based ones.
+
+pub mod delivery;
+
+use exchange::adapter::{Event, StreamKind};
+use exchange::{Kline, Ticker, Trade, UnixMs};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub const MAX_ALERTS: usize = 64;
+
+/// Finished volume windows a spike is compared against
+const VOLUME_HISTORY: usize = 20;
+/// Finished volume windows needed before a spike can fire
+const MIN_VOLUME_HISTORY: usize = 3;
+/// Price samples kept per alert, old ones are dropped first
+const MAX_PRICE_SAMPLES: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum Condition {
+    /// Price trades through the level, from either side
+    PriceCross { price: f64 },
+    /// Price moves at least `pct` percent, up or down, within `window_secs`
+    PercentChange { pct: f32, window_secs: u32 },
+    /// Volume traded within `window_secs` reaches `multiple` times the average of the
+    /// previous windows
+    VolumeSpike { multiple: f32, window_secs: u32 },
+}
+
+impl Condition {
+    /// A starting point for `kind`
+    pub fn default_for(kind: ConditionKind) -> Self {
+        match kind {
+            ConditionKind::PriceCross => Condition::PriceCross { price: 0.0 },
+            ConditionKind::PercentChange => Condition::PercentChange {
+                pct: 1.0,
+                window_secs: 300,
+            },
+            ConditionKind::VolumeSpike => Condition::VolumeSpike {
+                multiple: 3.0,
+                window_secs: 60,
+            },
+        }
+    }
+
+    pub fn kind(&self) -> ConditionKind {
+        match self {
+            Condition::PriceCross { .. } => ConditionKind::PriceCross,
+            Condition::PercentChange { .. } => ConditionKind::PercentChange,
+            Condition::VolumeSpike { .. } => ConditionKind::VolumeSpike,
+        }
+    }
+
+    pub fn needs_trades(&self) -> bool {
+        matches!(self, Condition::VolumeSpike { .. })
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::PriceCross { price } => write!(f, "Crosses {price}"),
+            Condition::PercentChange { pct, window_secs } => {
+                write!(f, "Moves ±{pct}% in {}", format_window(*window_secs))
+            }
+            Condition::VolumeSpike {
+                multiple,
+                window_secs,
+            } => write!(
+                f,
+                "Volume ≥ {multiple}x average per {}",
+                format_window(*window_secs)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionKind {
+    PriceCross,
+    PercentChange,
+    VolumeSpike,
+}
+
+impl ConditionKind {
+    pub const ALL: [ConditionKind; 3] = [
+        ConditionKind::PriceCross,
+        ConditionKind::PercentChange,
+        ConditionKind::VolumeSpike,
+    ];
+}
+
+impl std::fmt::Display for ConditionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionKind::PriceCross => write!(f, "Price cross"),
+            ConditionKind::PercentChange => write!(f, "% change"),
+            ConditionKind::VolumeSpike => write!(f, "Volume spike"),
+        }
+    }
+}
+
+/// Window lengths offered for the windowed conditions, in seconds
+pub const WINDOW_OPTIONS: [u32; 5] = [60, 300, 900, 3600, 14400];
+
+pub fn format_window(secs: u32) -> String {
+    match secs {
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Alert {
+    pub id: u32,
+    pub ticker: Ticker,
+    pub condition: Condition,
+    pub enabled: bool,
+    /// Stays enabled after firing, otherwise it's disabled until turned back on
+    pub repeat: bool,
+    #[serde(default)]
+    pub last_fired: Option<UnixMs>,
+}
+
+impl Alert {
+    /// Whether updates of `stream` are checked against this alert
+    pub fn is_evaluated_by(&self, stream: &StreamKind) -> bool {
+        match stream {
+            StreamKind::Trades { ticker_info } => ticker_info.ticker == self.ticker,
+            StreamKind::Kline { ticker_info, .. } => {
+                ticker_info.ticker == self.ticker && !self.condition.needs_trades()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A price cross alert placed, dragged or removed from a chart's price axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LevelEdit {
+    Place(f64),
+    Move(u32, f64),
+    Remove(u32),
+}
+
+/// An alert that just fired
+#[derive(Debug, Clone)]
+pub struct Fired {
+    pub id: u32,
+    pub ticker: Ticker,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// Also show fired alerts as desktop notifications, besides the in-app toast
+    pub os_notifications: bool,
+    pub delivery: delivery::DeliveryConfig,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            os_notifications: true,
+            delivery: delivery::DeliveryConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Alerts {
+    pub config: AlertsConfig,
+    alerts: Vec<Alert>,
+    next_id: u32,
+    #[serde(skip)]
+    trackers: FxHashMap<u32, Tracker>,
+}
+
+impl Alerts {
+    pub fn iter(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty()
+    }
+
+    /// Adds an enabled alert, `None` if [`MAX_ALERTS`] are already set
+    pub fn add(&mut self, ticker: Ticker, condition: Condition) -> Option<u32> {
+        if self.alerts.len() >= MAX_ALERTS {
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+
+        self.alerts.push(Alert {
+            id,
+            ticker,
+            condition,
+            enabled: true,
+            repeat: false,
+            last_fired: None,
+        });
+
+        Some(id)
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.alerts.retain(|alert| alert.id != id);
+        self.trackers.remove(&id);
+    }
+
+    pub fn set_enabled(&mut self, id: u32, enabled: bool) {
+        if let Some(alert) = self.alerts.iter_mut().find(|alert| alert.id == id) {
+            alert.enabled = enabled;
+            self.trackers.remove(&id);
+        }
+    }
+
+    pub fn set_repeat(&mut self, id: u32, repeat: bool) {
+        if let Some(alert) = self.alerts.iter_mut().find(|alert| alert.id == id) {
+            alert.repeat = repeat;
+        }
+    }
+
+    pub fn set_condition(&mut self, id: u32, condition: Condition) {
+        if let Some(alert) = self.alerts.iter_mut().find(|alert| alert.id == id) {
+            alert.condition = condition;
+            self.trackers.remove(&id);
+        }
+    }
+
+    /// Levels of the enabled price cross alerts, what charts draw as alert lines
+    pub fn price_levels(&self) -> FxHashMap<Ticker, Vec<(u32, f64)>> {
+        let mut levels = FxHashMap::<Ticker, Vec<(u32, f64)>>::default();
+
+        for alert in self.alerts.iter().filter(|alert| alert.enabled) {
+            if let Condition::PriceCross { price } = alert.condition {
+                levels
+                    .entry(alert.ticker)
+                    .or_default()
+                    .push((alert.id, price));
+            }
+        }
+
+        levels
+    }
+
+    /// Applies an edit made on a chart of `ticker`, `false` if [`MAX_ALERTS`] are already set
+    pub fn edit_level(&mut self, ticker: Ticker, edit: LevelEdit) -> bool {
+        match edit {
+            LevelEdit::Place(price) => {
+                return self.add(ticker, Condition::PriceCross { price }).is_some();
+            }
+            LevelEdit::Move(id, price) => {
+                self.set_condition(id, Condition::PriceCross { price });
+            }
+            LevelEdit::Remove(id) => self.remove(id),
+        }
+        true
+    }
+
+    /// Checks the enabled alerts of the event's ticker, returning the ones that fired
+    pub fn evaluate(&mut self, event: &Event) -> Vec<Fired> {
+        match event {
+            Event::TradesReceived(StreamKind::Trades { ticker_info }, _, trades) => self
+                .observe(ticker_info.ticker, |tracker, condition| {
+                    tracker.on_trades(condition, trades)
+                }),
+            Event::KlineReceived(StreamKind::Kline { ticker_info, .. }, kline) => self
+                .observe(ticker_info.ticker, |tracker, condition| {
+                    tracker.on_kline(condition, kline)
+                }),
+            _ => vec![],
+        }
+    }
+
+    fn observe(
+        &mut self,
+        ticker: Ticker,
+        mut check: impl FnMut(&mut Tracker, &Condition) -> Option<String>,
+    ) -> Vec<Fired> {
+        let mut fired = vec![];
+
+        for alert in self
+            .alerts
+            .iter_mut()
+            .filter(|alert| alert.enabled && alert.ticker == ticker)
+        {
+            let tracker = self.trackers.entry(alert.id).or_default();
+
+            if let Some(message) = check(tracker, &alert.condition) {
+                alert.last_fired = Some(UnixMs::now());
+                if !alert.repeat {
+                    alert.enabled = false;
+                    self.trackers.remove(&alert.id);
+                }
+
+                fired.push(Fired {
+                    id: alert.id,
+                    ticker,
+                    message: format!("{}: {message}", ticker.symbol_and_exchange_string()),
+                });
+            }
+        }
+
+        fired
+    }
+}
+
+/// Evaluation state of one alert
+#[derive(Debug, Default, Clone)]
+struct Tracker {
+    last_price: Option<f64>,
+    /// `(time, price)` samples within the percent change window
+    prices: VecDeque<(u64, f64)>,
+    /// `(window start, volume)` of the running and finished volume windows
+    volumes: VecDeque<(u64, f64)>,
+    /// Start of the volume window that already fired
+    spiked_window: Option<u64>,
+}
+
+impl Tracker {
+    fn on_trades(&mut self, condition: &Condition, trades: &[Trade]) -> Option<String> {
+        match *condition {
+            Condition::VolumeSpike {
+                multiple,
+                window_secs,
+            } => {
+                let window_ms = u64::from(window_secs.max(1)) * 1000;
+
+                for trade in trades {
+                    let start = trade.time.as_u64() - trade.time.as_u64() % window_ms;
+
+                    match self.volumes.back_mut() {
+                        Some((current, volume)) if *current == start => {
+                            *volume += trade.qty.to_f64();
+                        }
+                        Some((current, _)) if *current > start => {}
+                        _ => {
+                            self.volumes.push_back((start, trade.qty.to_f64()));
+                            if self.volumes.len() > VOLUME_HISTORY + 1 {
+                                self.volumes.pop_front();
+                            }
+                        }
+                    }
+                }
+
+                self.check_volume(multiple)
+            }
+            _ => trades.iter().find_map(|trade| {
+                self.on_price(condition, trade.time.as_u64(), trade.price.to_f64())
+            }),
+        }
+    }
+
+    fn on_kline(&mut self, condition: &Condition, kline: &Kline) -> Option<String> {
+        if condition.needs_trades() {
+            return None;
+        }
+
+        // Kline times are the open of the candle, the update itself is current
+        self.on_price(condition, UnixMs::now().as_u64(), kline.close.to_f64())
+    }
+
+    fn on_price(&mut self, condition: &Condition, time: u64, price: f64) -> Option<String> {
+        let previous = self.last_price.replace(price);
+
+        match *condition {
+            Condition::PriceCross { price: level } => {
+                let previous = previous?;
+                let crossed_up = previous < level && price >= level;
+                let crossed_down = previous > level && price <= level;
+
+                if crossed_up {
+                    Some(format!("crossed above {level}"))
+                } else if crossed_down {
+                    Some(format!("crossed below {level}"))
+                } else {
+                    None
+                }
+            }
+            Condition::PercentChange { pct, window_secs } => {
+                let window_ms = u64::from(window_secs) * 1000;
+
+                while self
+                    .prices
+                    .front()
+                    .is_some_and(|(sampled, _)| *sampled + window_ms < time)
+                    || self.prices.len() >= MAX_PRICE_SAMPLES
+                {
+                    self.prices.pop_front();
+                }
+                self.prices.push_back((time, price));
+
+                let (low, high) = self
+                    .prices
+                    .iter()
+                    .fold((f64::MAX, f64::MIN), |(low, high), (_, p)| {
+                        (low.min(*p), high.max(*p))
+                    });
+
+                let rise = (price - low) / low * 100.0;
+                let fall = (high - price) / high * 100.0;
+                let threshold = f64::from(pct);
+                let window = format_window(window_secs);
+
+                let message = if rise >= threshold {
+                    Some(format!("up {rise:.2}% in {window}, now {price}"))
+                } else if fall >= threshold {
+                    Some(format!("down {fall:.2}% in {window}, now {price}"))
+                } else {
+                    None
+                };
+
+                if message.is_some() {
+                    // Start over, so one move doesn't fire on every following trade
+                    self.prices.clear();
+                    self.prices.push_back((time, price));
+                }
+
+                message
+            }
+            Condition::VolumeSpike { .. } => None,
+        }
+    }
+
+    fn check_volume(&mut self, multiple: f32) -> Option<String> {
+        let (current_start, current) = *self.volumes.back()?;
+        let finished = self.volumes.len() - 1;
+
+        if finished < MIN_VOLUME_HISTORY || self.spiked_window == Some(current_start) {
+            return None;
+        }
+
+        let average = self
+            .volumes
+            .iter()
+            .take(finished)
+            .map(|(_, volume)| volume)
+            .sum::<f64>()
+            / finished as f64;
+        if average <= 0.0 {
+            return None;
+        }
+
+        let ratio = current / average;
+        if ratio < f64::from(multiple) {
+            return None;
+        }
+
+        self.spiked_window = Some(current_start);
+        Some(format!(
+            "volume {ratio:.1}x the average of the last {finished} windows"
+        ))
+    }
+}
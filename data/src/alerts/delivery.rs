@@ -0,0 +1,135 @@
+//! Delivery of fired alerts outside the app, to a generic HTTP webhook or a Telegram bot.
+//!
+//! Which channels are enabled is saved with the state. The webhook URL and bot token grant
+//! access to the receiving end, so they're kept in the OS keychain instead.
+
+use super::Fired;
+use exchange::UnixMs;
+
+use serde::{Deserialize, Serialize};
+
+const KEYCHAIN_SERVICE: &str = "flowsurface.alerts";
+const KEYCHAIN_KEY: &str = "delivery";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Webhook,
+    Telegram,
+}
+
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Channel::Webhook => write!(f, "Webhook"),
+            Channel::Telegram => write!(f, "Telegram"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DeliveryConfig {
+    pub webhook: bool,
+    pub telegram: bool,
+    /// Chat, group or channel the bot posts to, e.g. `123456789` or `@channel`
+    pub telegram_chat_id: String,
+}
+
+impl DeliveryConfig {
+    /// Requests delivering `fired` through every enabled channel that's fully set up
+    pub fn requests(&self, secrets: &Secrets, fired: &Fired) -> Vec<Request> {
+        let ticker = fired.ticker.symbol_and_exchange_string();
+        let mut requests = vec![];
+
+        if self.webhook && !secrets.webhook_url.trim().is_empty() {
+            // `text` and `content` are what Slack and Discord webhooks display
+            requests.push(Request {
+                channel: Channel::Webhook,
+                url: secrets.webhook_url.trim().to_string(),
+                body: serde_json::json!({
+                    "source": "flowsurface",
+                    "alert_id": fired.id,
+                    "ticker": ticker,
+                    "message": fired.message,
+                    "time": UnixMs::now().as_u64(),
+                    "text": fired.message,
+                    "content": fired.message,
+                }),
+            });
+        }
+
+        if self.telegram
+            && !secrets.telegram_token.trim().is_empty()
+            && !self.telegram_chat_id.trim().is_empty()
+        {
+            requests.push(Request {
+                channel: Channel::Telegram,
+                url: format!(
+                    "https://api.telegram.org/bot{}/sendMessage",
+                    secrets.telegram_token.trim()
+                ),
+                body: serde_json::json!({
+                    "chat_id": self.telegram_chat_id.trim(),
+                    "text": format!("Flowsurface alert\n{}", fired.message),
+                }),
+            });
+        }
+
+        requests
+    }
+}
+
+/// A webhook or bot request, the URL may carry credentials and shouldn't be logged
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub channel: Channel,
+    pub url: String,
+    pub body: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Secrets {
+    pub webhook_url: String,
+    pub telegram_token: String,
+}
+
+impl Secrets {
+    pub fn load() -> Self {
+        let entry = match keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_KEY) {
+            Ok(entry) => entry,
+            Err(err) => {
+                log::warn!("Keychain entry init failed for service={KEYCHAIN_SERVICE}: {err}");
+                return Self::default();
+            }
+        };
+
+        match entry.get_password() {
+            Ok(secret) => serde_json::from_str(&secret).unwrap_or_else(|err| {
+                log::warn!("Alert delivery secrets in keychain are invalid JSON: {err}");
+                Self::default()
+            }),
+            Err(err) => {
+                log::info!("No alert delivery secrets in keychain: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_KEY)
+            .map_err(|err| format!("Keychain unavailable: {err}"))?;
+
+        if *self == Self::default() {
+            return match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(err) => Err(format!("Failed to clear keychain entry: {err}")),
+            };
+        }
+
+        let secret = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        entry
+            .set_password(&secret)
+            .map_err(|err| format!("Failed to store in keychain: {err}"))
+    }
+}
@@ -1,9 +1,11 @@
 use crate::util::ok_or_default;
-use exchange::SerTicker;
-use exchange::unit::qty::Qty;
+use exchange::adapter::MarketKind;
+use exchange::unit::qty::{Qty, volume_size_unit};
+use exchange::{SerTicker, SizeUnit, Trade};
 
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum Threshold {
@@ -43,3 +45,98 @@ pub struct AudioStream {
     #[serde(deserialize_with = "ok_or_default")]
     pub volume: Option<f32>,
 }
+
+/// One side's notional within this window counts towards the same burst
+const BURST_WINDOW_MS: u64 = 1_000;
+
+/// Sounds for outsized prints on a Time&Sales or Heatmap pane
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TradeSound {
+    pub enabled: bool,
+    /// Notional of a single trade that plays a sound
+    pub trade_notional: f32,
+    /// Notional taken by one side within a second that plays the harder sound.
+    ///
+    /// Liquidations aren't streamed, so a burst like this is what a liquidation
+    /// cluster looks like on the tape.
+    pub burst_notional: Option<f32>,
+}
+
+impl Default for TradeSound {
+    fn default() -> Self {
+        TradeSound {
+            enabled: false,
+            trade_notional: 100_000.0,
+            burst_notional: Some(1_000_000.0),
+        }
+    }
+}
+
+/// Bursts outrank single trades and footprint order flow alerts outrank both, so the
+/// strongest hit of a batch can be picked with `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TradeSoundHit {
+    Trade { is_sell: bool },
+    Burst { is_sell: bool },
+    FlowSignal { is_sell: bool },
+}
+
+/// Rolling one-second notional per side, for [`TradeSound::burst_notional`]
+#[derive(Debug, Default)]
+pub struct BurstTracker {
+    buys: VecDeque<(u64, f64)>,
+    sells: VecDeque<(u64, f64)>,
+}
+
+impl BurstTracker {
+    pub fn check(
+        &mut self,
+        cfg: &TradeSound,
+        market: MarketKind,
+        trades: &[Trade],
+    ) -> Option<TradeSoundHit> {
+        if !cfg.enabled {
+            return None;
+        }
+
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut hit = None;
+
+        for trade in trades {
+            let notional = market.qty_in_quote_value(trade.qty, trade.price, size_in_quote_ccy);
+            let is_sell = trade.is_sell;
+
+            if notional >= f64::from(cfg.trade_notional) {
+                hit = hit.max(Some(TradeSoundHit::Trade { is_sell }));
+            }
+
+            let Some(burst_notional) = cfg.burst_notional else {
+                continue;
+            };
+
+            let time = trade.time.as_u64();
+            let side = if is_sell {
+                &mut self.sells
+            } else {
+                &mut self.buys
+            };
+
+            side.push_back((time, notional));
+            while side
+                .front()
+                .is_some_and(|(t, _)| t + BURST_WINDOW_MS < time)
+            {
+                side.pop_front();
+            }
+
+            if side.iter().map(|(_, n)| n).sum::<f64>() >= f64::from(burst_notional) {
+                // Start over so a single burst doesn't keep firing on every trade after it
+                side.clear();
+                hit = hit.max(Some(TradeSoundHit::Burst { is_sell }));
+            }
+        }
+
+        hit
+    }
+}
@@ -0,0 +1,124 @@
+//! Market data re-broadcast to local websocket clients, one JSON object per message, e.g.
+//!
+//! ```json
+//! {"type":"trades","exchange":"BinanceLinear","ticker":"BTCUSDT","time":1718000000000,"trades":[{"time":1718000000000,"price":64210.5,"qty":0.25,"side":"sell"}]}
+//! {"type":"depth","exchange":"BinanceLinear","ticker":"BTCUSDT","time":1718000000000,"bids":[[64210.4,1.2]],"asks":[[64210.5,0.8]]}
+//! {"type":"kline","exchange":"BinanceLinear","ticker":"BTCUSDT","timeframe":"1m","time":1717999980000,"open":64200.0,"high":64215.0,"low":64190.5,"close":64210.5,"volume":120.4,"buy_volume":70.1,"sell_volume":50.3}
+//! ```
+
+use exchange::adapter::{Event, StreamKind};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+pub const DEFAULT_PORT: u16 = 7432;
+
+/// Levels sent per side of a depth message, nearest to the spread first
+pub const MAX_DEPTH_LEVELS: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BroadcastConfig {
+    pub enabled: bool,
+    /// Bound on loopback only
+    pub port: u16,
+    /// Web pages allowed to connect, e.g. `http://localhost:3000`. Clients that send no
+    /// `Origin`, i.e. anything but a browser, are always accepted
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            allowed_origins: vec![],
+        }
+    }
+}
+
+/// JSON message for a market data event, `None` for connection status events
+pub fn encode(event: &Event) -> Option<String> {
+    let message = match event {
+        Event::TradesReceived(stream, update_t, trades) => {
+            let trades = trades
+                .iter()
+                .map(|trade| {
+                    json!({
+                        "time": trade.time.as_u64(),
+                        "price": trade.price.to_f64(),
+                        "qty": trade.qty.to_f64(),
+                        "side": if trade.is_sell { "sell" } else { "buy" },
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut message = header("trades", stream);
+            message["time"] = json!(update_t.as_u64());
+            message["trades"] = json!(trades);
+            message
+        }
+        Event::DepthReceived(stream, update_t, depth) => {
+            let bids = depth
+                .bids
+                .iter()
+                .rev()
+                .take(MAX_DEPTH_LEVELS)
+                .map(|(price, qty)| [price.to_f64(), qty.to_f64()])
+                .collect::<Vec<_>>();
+            let asks = depth
+                .asks
+                .iter()
+                .take(MAX_DEPTH_LEVELS)
+                .map(|(price, qty)| [price.to_f64(), qty.to_f64()])
+                .collect::<Vec<_>>();
+
+            let mut message = header("depth", stream);
+            message["time"] = json!(update_t.as_u64());
+            message["bids"] = json!(bids);
+            message["asks"] = json!(asks);
+            message
+        }
+        Event::KlineReceived(stream, kline) => {
+            let mut message = header("kline", stream);
+            if let StreamKind::Kline { timeframe, .. } = stream {
+                message["timeframe"] = json!(timeframe.to_string());
+            }
+            message["time"] = json!(kline.time.as_u64());
+            message["open"] = json!(kline.open.to_f64());
+            message["high"] = json!(kline.high.to_f64());
+            message["low"] = json!(kline.low.to_f64());
+            message["close"] = json!(kline.close.to_f64());
+            message["volume"] = json!(kline.volume.total().to_f64());
+            if let Some((buy, sell)) = kline.volume.buy_sell() {
+                message["buy_volume"] = json!(buy.to_f64());
+                message["sell_volume"] = json!(sell.to_f64());
+            }
+            message
+        }
+        Event::MarkPriceReceived(stream, mark_price) => {
+            let mut message = header("mark_price", stream);
+            message["time"] = json!(mark_price.time.as_u64());
+            message["mark"] = json!(mark_price.mark.to_f64());
+            message["index"] = json!(mark_price.index.map(|index| index.to_f64()));
+            message
+        }
+        Event::Connected(_)
+        | Event::Disconnected(..)
+        | Event::Resumed(..)
+        | Event::Reconnecting(..)
+        | Event::TickerStatsReceived(..) => return None,
+    };
+
+    Some(message.to_string())
+}
+
+fn header(kind: &str, stream: &StreamKind) -> serde_json::Value {
+    let ticker_info = stream.ticker_info();
+
+    json!({
+        "type": kind,
+        "exchange": ticker_info.exchange(),
+        "ticker": ticker_info.ticker.to_string(),
+    })
+}
@@ -1,7 +1,13 @@
 pub mod comparison;
+pub mod drawing;
 pub mod heatmap;
 pub mod indicator;
 pub mod kline;
+pub mod liquidation;
+pub mod oscillator;
+pub mod script;
+pub mod spread;
+pub mod vwap;
 
 use exchange::UnixMs;
 use exchange::{Timeframe, unit::Price};
@@ -63,6 +69,43 @@ impl<D: DataPoint> PlotData<D> {
             }
         }
     }
+
+    /// Like [`Self::visible_price_range`], but ignores the most extreme `clip` fraction
+    /// of highs and lows so a few spikes don't decide the whole range
+    pub fn visible_price_range_clipped(
+        &self,
+        start_interval: u64,
+        end_interval: u64,
+        clip: f32,
+    ) -> Option<(f32, f32)> {
+        let (mut lows, mut highs): (Vec<Price>, Vec<Price>) = match self {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .range(UnixMs::new(start_interval)..=UnixMs::new(end_interval))
+                .map(|(_, dp)| (dp.value_low(), dp.value_high()))
+                .unzip(),
+            PlotData::TickBased(tick_aggr) => tick_aggr
+                .datapoints
+                .iter()
+                .rev()
+                .enumerate()
+                .filter(|(idx, _)| *idx as u64 >= start_interval && *idx as u64 <= end_interval)
+                .map(|(_, dp)| (dp.kline.low, dp.kline.high))
+                .unzip(),
+        };
+
+        if lows.is_empty() {
+            return None;
+        }
+
+        // Always keep most of the datapoints, small ranges are left untouched
+        let skipped = ((lows.len() as f32 * clip).ceil() as usize).min(lows.len() / 10);
+
+        lows.sort_unstable();
+        highs.sort_unstable_by(|a, b| b.cmp(a));
+
+        Some((lows[skipped].to_f32_lossy(), highs[skipped].to_f32_lossy()))
+    }
 }
 
 impl PlotData<KlineDataPoint> {
@@ -88,6 +131,10 @@ pub enum Basis {
 
     /// Trade-based aggregation where each datapoint represents a fixed number of trades.
     Tick(aggr::TickCount),
+
+    /// Price-based aggregation where a datapoint closes once the price has moved far enough,
+    /// built from trades like tick bars.
+    Bars(aggr::BarKind),
 }
 
 impl Basis {
@@ -95,6 +142,15 @@ impl Basis {
         matches!(self, Basis::Time(_))
     }
 
+    /// How far apart consecutive datapoints are on an index based x-axis, the trade count of
+    /// tick bars
+    pub fn index_step(&self) -> u64 {
+        match self {
+            Basis::Tick(count) => u64::from(count.0),
+            Basis::Time(_) | Basis::Bars(_) => 1,
+        }
+    }
+
     pub fn default_kline_time(
         ticker_info: Option<exchange::TickerInfo>,
         fallback: Timeframe,
@@ -130,6 +186,18 @@ impl Basis {
 
         interval.into()
     }
+
+    /// Column width of a heatmap, which only aggregates by time. Anything else is a bug
+    /// upstream, logged and replaced by the default interval rather than panicking
+    pub fn heatmap_interval(self) -> Timeframe {
+        match self {
+            Basis::Time(interval) => interval,
+            Basis::Tick(_) | Basis::Bars(_) => {
+                log::error!("Heatmaps only aggregate by time, got {self:?}");
+                Timeframe::MS500
+            }
+        }
+    }
 }
 
 /// Lightweight basis-aware keyed projection of values.
@@ -188,6 +256,7 @@ impl std::fmt::Display for Basis {
         match self {
             Basis::Time(timeframe) => write!(f, "{timeframe}"),
             Basis::Tick(count) => write!(f, "{count}"),
+            Basis::Bars(kind) => write!(f, "{kind}"),
         }
     }
 }
@@ -1,8 +1,217 @@
-use exchange::SerTicker;
+use super::heatmap::EXPORTS_DIR;
+use super::spread::SpreadMode;
+
+use exchange::{SerTicker, UnixMs};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Bars the correlation is taken over, unless set otherwise
+pub const DEFAULT_CORRELATION_WINDOW: u16 = 50;
+pub const CORRELATION_WINDOW_RANGE: std::ops::RangeInclusive<u16> = 10..=500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub colors: Vec<(SerTicker, iced_core::Color)>,
     pub names: Vec<(SerTicker, String)>,
+    #[serde(default)]
+    pub mode: ComparisonMode,
+    #[serde(default)]
+    pub normalization: Normalization,
+    #[serde(default = "default_correlation_window")]
+    pub correlation_window: u16,
+    #[serde(default)]
+    pub derived: Vec<DerivedSeries>,
+    /// Series the y axis reads the price of, while the lines stay in `normalization`
+    #[serde(default)]
+    pub price_axis: Option<SerTicker>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            colors: vec![],
+            names: vec![],
+            mode: ComparisonMode::default(),
+            normalization: Normalization::default(),
+            correlation_window: DEFAULT_CORRELATION_WINDOW,
+            derived: vec![],
+            price_axis: None,
+        }
+    }
+}
+
+fn default_correlation_window() -> u16 {
+    DEFAULT_CORRELATION_WINDOW
+}
+
+/// A line computed from two of the compared tickers, `a op b`, plotted alongside them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DerivedSeries {
+    pub a: SerTicker,
+    pub b: SerTicker,
+    pub op: SpreadMode,
+    pub color: iced_core::Color,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ComparisonMode {
+    /// Each series as a line, in percent change from the first visible point
+    #[default]
+    Lines,
+    /// Correlation of every pair of series, as a matrix
+    Correlation,
+}
+
+impl ComparisonMode {
+    pub const ALL: [ComparisonMode; 2] = [ComparisonMode::Lines, ComparisonMode::Correlation];
+}
+
+impl std::fmt::Display for ComparisonMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComparisonMode::Lines => write!(f, "Lines"),
+            ComparisonMode::Correlation => write!(f, "Correlation"),
+        }
+    }
+}
+
+/// How the lines are scaled so series of different prices can be compared
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Normalization {
+    /// Percent change from the rebase point
+    #[default]
+    Percent,
+    /// Percent change from the rebase point on a log scale
+    Log,
+    /// Standard deviations from the mean of the visible window
+    ZScore,
+}
+
+impl Normalization {
+    pub const ALL: [Normalization; 3] = [
+        Normalization::Percent,
+        Normalization::Log,
+        Normalization::ZScore,
+    ];
+}
+
+impl std::fmt::Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Normalization::Percent => write!(f, "% change"),
+            Normalization::Log => write!(f, "Log % change"),
+            Normalization::ZScore => write!(f, "Z-score"),
+        }
+    }
+}
+
+/// Pearson correlation of the returns of two `(time, close)` series over the last `window`
+/// bars they share, `None` with fewer than 3 returns or a flat series
+pub fn correlation(a: &[(u64, f32)], b: &[(u64, f32)], window: usize) -> Option<f32> {
+    // Closes at the timestamps both series have, walked from the latest one
+    let mut shared = Vec::with_capacity(window + 1);
+    let (mut i, mut j) = (a.len(), b.len());
+
+    while i > 0 && j > 0 && shared.len() <= window {
+        let ((ta, pa), (tb, pb)) = (a[i - 1], b[j - 1]);
+        match ta.cmp(&tb) {
+            std::cmp::Ordering::Equal => {
+                shared.push((f64::from(pa), f64::from(pb)));
+                i -= 1;
+                j -= 1;
+            }
+            std::cmp::Ordering::Greater => i -= 1,
+            std::cmp::Ordering::Less => j -= 1,
+        }
+    }
+
+    let returns = shared
+        .windows(2)
+        .filter_map(|pair| {
+            let ((a1, b1), (a0, b0)) = (pair[0], pair[1]);
+            (a0 > 0.0 && b0 > 0.0 && a1 > 0.0 && b1 > 0.0).then(|| ((a1 / a0).ln(), (b1 / b0).ln()))
+        })
+        .collect::<Vec<_>>();
+
+    if returns.len() < 3 {
+        return None;
+    }
+
+    let n = returns.len() as f64;
+    let mean_a = returns.iter().map(|(ra, _)| ra).sum::<f64>() / n;
+    let mean_b = returns.iter().map(|(_, rb)| rb).sum::<f64>() / n;
+
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (ra, rb) in &returns {
+        let (da, db) = (ra - mean_a, rb - mean_b);
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+
+    if var_a <= f64::EPSILON || var_b <= f64::EPSILON {
+        return None;
+    }
+
+    Some((cov / (var_a * var_b).sqrt()).clamp(-1.0, 1.0) as f32)
+}
+
+/// Writes the `(name, points)` columns to a CSV with a row for every timestamp any of them
+/// has. Each column carries its last value forward and stays empty before its first point
+pub fn write_csv(columns: &[(String, &[(u64, f32)])]) -> std::io::Result<PathBuf> {
+    let dir = crate::data_path(Some(EXPORTS_DIR));
+    std::fs::create_dir_all(&dir)?;
+
+    let now = UnixMs::now();
+    let file_name = format!(
+        "comparison_{}.csv",
+        now.as_datetime_utc()
+            .map(|dt| dt.format("%Y%m%d-%H%M%S").to_string())
+            .unwrap_or_else(|| now.as_u64().to_string()),
+    );
+    let path = dir.join(file_name);
+
+    let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+
+    write!(out, "time")?;
+    for (name, _) in columns {
+        write!(out, ",{}", csv_field(name))?;
+    }
+    writeln!(out)?;
+
+    let times = columns
+        .iter()
+        .flat_map(|(_, points)| points.iter().map(|(t, _)| *t))
+        .collect::<BTreeSet<_>>();
+    let mut cursors = vec![0usize; columns.len()];
+
+    for time in times {
+        write!(out, "{time}")?;
+        for ((_, points), cursor) in columns.iter().zip(cursors.iter_mut()) {
+            while *cursor < points.len() && points[*cursor].0 <= time {
+                *cursor += 1;
+            }
+            match cursor.checked_sub(1).map(|i| points[i].1) {
+                Some(value) => write!(out, ",{value}")?,
+                None => write!(out, ",")?,
+            }
+        }
+        writeln!(out)?;
+    }
+    out.flush()?;
+
+    Ok(path)
+}
+
+/// Quoted when it holds a separator, series can be renamed to anything
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
 }
@@ -0,0 +1,253 @@
+use exchange::unit::Price;
+use exchange::{SerTicker, UnixMs};
+use serde::{Deserialize, Serialize};
+
+/// Past this many drawings, or notes, on a ticker, the oldest ones are dropped
+const MAX_PER_TICKER: usize = 200;
+pub const MAX_NOTE_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Tool {
+    TrendLine,
+    Ray,
+    HorizontalLine,
+    VerticalLine,
+    Rectangle,
+    FibRetracement,
+    FibExtension,
+    /// Price, percent and time change between its anchors, with the volume traded in between
+    Measure,
+    /// Places a [`Note`] rather than a drawing
+    Note,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 9] = [
+        Tool::TrendLine,
+        Tool::Ray,
+        Tool::HorizontalLine,
+        Tool::VerticalLine,
+        Tool::Rectangle,
+        Tool::FibRetracement,
+        Tool::FibExtension,
+        Tool::Measure,
+        Tool::Note,
+    ];
+
+    /// Clicks it takes to place one
+    pub fn anchors(self) -> usize {
+        match self {
+            Tool::HorizontalLine | Tool::VerticalLine | Tool::Note => 1,
+            Tool::TrendLine
+            | Tool::Ray
+            | Tool::Rectangle
+            | Tool::FibRetracement
+            | Tool::FibExtension
+            | Tool::Measure => 2,
+        }
+    }
+
+    /// Drawn as the box its anchors span rather than as a line through them
+    pub fn is_boxed(self) -> bool {
+        matches!(
+            self,
+            Tool::Rectangle | Tool::FibRetracement | Tool::FibExtension | Tool::Measure
+        )
+    }
+
+    /// Ratios of the move between the anchors the levels are drawn at, empty for the other tools
+    pub fn fib_levels(self) -> &'static [f64] {
+        match self {
+            Tool::FibRetracement => &[0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0],
+            Tool::FibExtension => &[0.0, 0.618, 1.0, 1.272, 1.618, 2.0, 2.618],
+            _ => &[],
+        }
+    }
+}
+
+impl std::fmt::Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tool::TrendLine => write!(f, "Trend line"),
+            Tool::Ray => write!(f, "Ray"),
+            Tool::HorizontalLine => write!(f, "Horizontal line"),
+            Tool::VerticalLine => write!(f, "Vertical line"),
+            Tool::Rectangle => write!(f, "Rectangle"),
+            Tool::FibRetracement => write!(f, "Fib retracement"),
+            Tool::FibExtension => write!(f, "Fib extension"),
+            Tool::Measure => write!(f, "Measure"),
+            Tool::Note => write!(f, "Note"),
+        }
+    }
+}
+
+/// What the anchors of drawings and notes are pulled onto as they're placed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Snap {
+    #[default]
+    Off,
+    /// Open, high, low or close of the candle, whichever is closest. Heatmaps snap to
+    /// the tick size, having no candles
+    Ohlc,
+    /// Prices aligned to the chart's tick size
+    Tick,
+}
+
+impl Snap {
+    pub const ALL: [Snap; 3] = [Snap::Off, Snap::Ohlc, Snap::Tick];
+}
+
+impl std::fmt::Display for Snap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Snap::Off => write!(f, "Off"),
+            Snap::Ohlc => write!(f, "OHLC"),
+            Snap::Tick => write!(f, "Tick"),
+        }
+    }
+}
+
+/// A point on a time based chart, the open time of a candle and a price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Anchor {
+    pub time: UnixMs,
+    pub price: Price,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Drawing {
+    pub tool: Tool,
+    pub from: Anchor,
+    /// Same as `from` for the tools placed with a single click
+    pub to: Anchor,
+}
+
+impl Drawing {
+    /// Earliest and latest candle, lowest and highest price the anchors span
+    pub fn bounds(&self) -> (UnixMs, UnixMs, Price, Price) {
+        let (from, to) = (self.from, self.to);
+        (
+            from.time.min(to.time),
+            from.time.max(to.time),
+            from.price.min(to.price),
+            from.price.max(to.price),
+        )
+    }
+
+    /// Price of a fib level. Retracements are measured back from `to`, a full one landing on
+    /// `from`, extensions onward from `from` with `1.0` on `to`
+    pub fn fib_price(&self, level: f64) -> Price {
+        let (from, to) = (self.from.price.to_f64(), self.to.price.to_f64());
+
+        match self.tool {
+            Tool::FibRetracement => Price::from_f64(to + (from - to) * level),
+            _ => Price::from_f64(from + (to - from) * level),
+        }
+    }
+}
+
+/// A marker with an optional line of text, pinned to a candle and price
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Note {
+    pub at: Anchor,
+    pub marker: Marker,
+    #[serde(default)]
+    pub text: String,
+}
+
+impl Note {
+    pub fn new(at: Anchor) -> Self {
+        Self {
+            at,
+            marker: Marker::default(),
+            text: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Marker {
+    #[default]
+    Pin,
+    Flag,
+    Star,
+    Warning,
+    Eyes,
+    Rocket,
+}
+
+impl Marker {
+    pub const ALL: [Marker; 6] = [
+        Marker::Pin,
+        Marker::Flag,
+        Marker::Star,
+        Marker::Warning,
+        Marker::Eyes,
+        Marker::Rocket,
+    ];
+
+    /// `None` for the pin, which is drawn as a shape so it shows without an emoji font
+    pub fn emoji(self) -> Option<&'static str> {
+        match self {
+            Marker::Pin => None,
+            Marker::Flag => Some("\u{1f6a9}"),
+            Marker::Star => Some("\u{2b50}"),
+            Marker::Warning => Some("\u{26a0}"),
+            Marker::Eyes => Some("\u{1f440}"),
+            Marker::Rocket => Some("\u{1f680}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Marker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.emoji() {
+            Some(emoji) => write!(f, "{emoji}"),
+            None => write!(f, "Pin"),
+        }
+    }
+}
+
+pub type Drawings = ByTicker<Drawing>;
+pub type Notes = ByTicker<Note>;
+
+/// Drawings, or notes, of every ticker the pane has shown
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ByTicker<T>(Vec<(SerTicker, Vec<T>)>);
+
+impl<T> Default for ByTicker<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> ByTicker<T> {
+    pub fn of(&self, ticker: &SerTicker) -> &[T] {
+        self.0
+            .iter()
+            .find(|(t, _)| t == ticker)
+            .map_or(&[][..], |(_, items)| items.as_slice())
+    }
+
+    /// Applies `f` to the items of `ticker`, dropping the entry once it has none left
+    pub fn edit(&mut self, ticker: SerTicker, f: impl FnOnce(&mut Vec<T>)) {
+        let index = match self.0.iter().position(|(t, _)| *t == ticker) {
+            Some(index) => index,
+            None => {
+                self.0.push((ticker, vec![]));
+                self.0.len() - 1
+            }
+        };
+
+        let items = &mut self.0[index].1;
+        f(items);
+
+        if items.len() > MAX_PER_TICKER {
+            let excess = items.len() - MAX_PER_TICKER;
+            items.drain(..excess);
+        }
+        if items.is_empty() {
+            self.0.remove(index);
+        }
+    }
+}
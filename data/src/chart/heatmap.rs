@@ -1,15 +1,25 @@
 use super::Basis;
 use super::aggr::time::DataPoint;
-use exchange::unit::MinQtySize;
+use crate::audio::TradeSound;
+use crate::snapshot::TimelapseConfig;
 use exchange::unit::price::{Price, PriceStep};
 use exchange::unit::qty::{Qty, SizeUnit, volume_size_unit};
-use exchange::{Timeframe, UnixMs, adapter::MarketKind, depth::Depth};
+use exchange::unit::{MinQtySize, MinTicksize};
+use exchange::{TickerInfo, Timeframe, UnixMs, adapter::MarketKind, depth::Depth};
 
+use iced_core::Color;
 use rustc_hash::{FxBuildHasher, FxHashMap};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
 
 pub const CLEANUP_THRESHOLD: usize = 4800;
+/// How many columns are merged into one once they're in the older half of a limited history
+const DOWNSAMPLE_FACTOR: u64 = 8;
+
+/// Sub-directory of the data path for user-triggered exports
+pub const EXPORTS_DIR: &str = "exports";
 
 #[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Config {
@@ -17,6 +27,28 @@ pub struct Config {
     pub order_size_filter: f32,
     pub trade_size_scale: Option<i32>,
     pub coalescing: Option<CoalesceKind>,
+    #[serde(default)]
+    pub export: DepthExport,
+    /// Draws what circle sizes and depth shades stand for in the corner of the chart
+    #[serde(default)]
+    pub show_legend: bool,
+    #[serde(default)]
+    pub timelapse: TimelapseConfig,
+    #[serde(default)]
+    pub sound: TradeSound,
+    #[serde(default)]
+    pub color_map: ColorMap,
+    #[serde(default)]
+    pub intensity: IntensityScale,
+    /// Draws the volume traded over the visible window next to the resting depth on the right
+    #[serde(default)]
+    pub traded_profile: bool,
+    #[serde(default)]
+    pub tooltip: TooltipKind,
+    #[serde(default)]
+    pub depth_mode: DepthMode,
+    #[serde(default)]
+    pub history: HistoryLimit,
 }
 
 impl Default for Config {
@@ -26,16 +58,305 @@ impl Default for Config {
             order_size_filter: 0.0,
             trade_size_scale: Some(100),
             coalescing: Some(CoalesceKind::Average(0.15)),
+            export: DepthExport::default(),
+            show_legend: false,
+            timelapse: TimelapseConfig::default(),
+            sound: TradeSound::default(),
+            color_map: ColorMap::default(),
+            intensity: IntensityScale::default(),
+            traded_profile: false,
+            tooltip: TooltipKind::default(),
+            depth_mode: DepthMode::default(),
+            history: HistoryLimit::default(),
+        }
+    }
+}
+
+/// Colors the resting depth is shaded with, from the thinnest levels to the deepest
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum ColorMap {
+    /// Bids and asks in the theme's colors, fading in with size
+    #[default]
+    Theme,
+    Viridis,
+    Magma,
+    Monochrome,
+    Gradient {
+        low: Color,
+        high: Color,
+    },
+}
+
+impl ColorMap {
+    pub const DEFAULT_GRADIENT: ColorMap = ColorMap::Gradient {
+        low: Color::from_rgb(0.10, 0.14, 0.40),
+        high: Color::from_rgb(1.0, 0.86, 0.32),
+    };
+
+    /// Every kind of map, `current` standing in for the gradient when it is one
+    pub fn options(current: ColorMap) -> [ColorMap; 5] {
+        let gradient = match current {
+            ColorMap::Gradient { .. } => current,
+            _ => Self::DEFAULT_GRADIENT,
+        };
+
+        [
+            ColorMap::Theme,
+            ColorMap::Viridis,
+            ColorMap::Magma,
+            ColorMap::Monochrome,
+            gradient,
+        ]
+    }
+}
+
+impl std::fmt::Display for ColorMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorMap::Theme => write!(f, "Theme"),
+            ColorMap::Viridis => write!(f, "Viridis"),
+            ColorMap::Magma => write!(f, "Magma"),
+            ColorMap::Monochrome => write!(f, "Monochrome"),
+            ColorMap::Gradient { .. } => write!(f, "Custom gradient"),
+        }
+    }
+}
+
+/// How a level's size maps to a shade
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum IntensityScale {
+    /// Relative to the largest level in view
+    #[default]
+    Linear,
+    /// Logarithmic, so thinner levels stay apart instead of all fading out
+    Log,
+    /// Relative to the 95th percentile of the levels in view, the few walls above it
+    /// all get the deepest shade
+    Percentile,
+}
+
+impl IntensityScale {
+    pub const ALL: [IntensityScale; 3] = [
+        IntensityScale::Linear,
+        IntensityScale::Log,
+        IntensityScale::Percentile,
+    ];
+
+    /// Share of the levels in view the percentile scale tops out above
+    pub const PERCENTILE: f32 = 0.95;
+}
+
+impl std::fmt::Display for IntensityScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntensityScale::Linear => write!(f, "Linear"),
+            IntensityScale::Log => write!(f, "Log"),
+            IntensityScale::Percentile => write!(f, "Percentile"),
+        }
+    }
+}
+
+/// What the cursor shows over the depth
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TooltipKind {
+    /// Resting sizes of the cells around the cursor, in a grid
+    #[default]
+    Neighborhood,
+    /// Resting size and traded volume of the hovered cell, next to the crosshair
+    Cell,
+}
+
+impl TooltipKind {
+    pub const ALL: [TooltipKind; 2] = [TooltipKind::Neighborhood, TooltipKind::Cell];
+}
+
+impl std::fmt::Display for TooltipKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TooltipKind::Neighborhood => write!(f, "Nearby cells"),
+            TooltipKind::Cell => write!(f, "Hovered cell"),
         }
     }
 }
 
+/// What the cells of the depth stand for
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DepthMode {
+    /// Size resting at the level
+    #[default]
+    Size,
+    /// Size added to or pulled from the level since the column before, so orders that
+    /// come and go without trading stand out
+    Delta,
+}
+
+impl DepthMode {
+    pub const ALL: [DepthMode; 2] = [DepthMode::Size, DepthMode::Delta];
+}
+
+impl std::fmt::Display for DepthMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepthMode::Size => write!(f, "Resting size"),
+            DepthMode::Delta => write!(f, "Pulled & stacked"),
+        }
+    }
+}
+
+/// How much depth history a heatmap keeps. Columns past the older half of it are merged
+/// into coarser ones before they're dropped
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HistoryLimit {
+    /// About as much as the chart can show
+    #[default]
+    Auto,
+    Minutes(u16),
+    Megabytes(u16),
+}
+
+impl HistoryLimit {
+    pub const DEFAULT_MINUTES: u16 = 60;
+    pub const DEFAULT_MEGABYTES: u16 = 256;
+
+    /// Every kind of limit, `current` standing in for its own kind
+    pub fn options(current: HistoryLimit) -> [HistoryLimit; 3] {
+        let minutes = match current {
+            HistoryLimit::Minutes(_) => current,
+            _ => HistoryLimit::Minutes(Self::DEFAULT_MINUTES),
+        };
+        let megabytes = match current {
+            HistoryLimit::Megabytes(_) => current,
+            _ => HistoryLimit::Megabytes(Self::DEFAULT_MEGABYTES),
+        };
+
+        [HistoryLimit::Auto, minutes, megabytes]
+    }
+}
+
+impl std::fmt::Display for HistoryLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryLimit::Auto => write!(f, "Automatic"),
+            HistoryLimit::Minutes(_) => write!(f, "Duration"),
+            HistoryLimit::Megabytes(_) => write!(f, "Memory"),
+        }
+    }
+}
+
+/// Rough footprint of a depth history
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryUsage {
+    pub bytes: usize,
+    /// From the oldest run kept to the latest snapshot
+    pub span_ms: u64,
+}
+
+/// Settings for dumping the depth history as a price × time matrix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DepthExport {
+    /// How far back from the latest snapshot to export
+    pub window_mins: u16,
+    /// One column per this many seconds
+    pub interval_secs: u16,
+}
+
+impl Default for DepthExport {
+    fn default() -> Self {
+        Self {
+            window_mins: 15,
+            interval_secs: 5,
+        }
+    }
+}
+
+impl DepthExport {
+    /// Writes a CSV with one row per price level (ascending) and one column per sample time
+    /// in unix ms. Bid sizes are positive, ask sizes negative, empty cells had no resting size.
+    pub fn write_csv(
+        self,
+        depth: &HistoricalDepth,
+        ticker_info: &TickerInfo,
+    ) -> std::io::Result<PathBuf> {
+        let Some(latest) = depth.last_snapshot_time else {
+            return Err(std::io::Error::other("no depth recorded yet"));
+        };
+
+        let earliest = latest.saturating_sub(u64::from(self.window_mins) * 60_000);
+        let interval_ms = (u64::from(self.interval_secs) * 1000).max(depth.aggr_time_ms());
+        let samples = depth.sample(earliest, latest, interval_ms);
+
+        let dir = crate::data_path(Some(EXPORTS_DIR));
+        std::fs::create_dir_all(&dir)?;
+
+        let file_name = format!(
+            "depth_{}_{}_{}.csv",
+            ticker_info.exchange().to_string().replace(' ', "-"),
+            ticker_info.ticker,
+            latest
+                .as_datetime_utc()
+                .map(|dt| dt.format("%Y%m%d-%H%M%S").to_string())
+                .unwrap_or_else(|| latest.as_u64().to_string()),
+        );
+        let path = dir.join(file_name);
+
+        let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        samples.write_csv(&mut out, ticker_info.min_ticksize)?;
+        out.flush()?;
+
+        Ok(path)
+    }
+}
+
+/// Size resting at a level at one sampled time, and whether it was a bid
+pub type DepthCell = Option<(Qty, bool)>;
+
+/// Order book resampled at fixed times from [`HistoricalDepth`]
+pub struct DepthSamples {
+    pub times: Vec<UnixMs>,
+    /// Ascending by price, each row holding one cell per entry of `times`
+    pub levels: Vec<(Price, Vec<DepthCell>)>,
+}
+
+impl DepthSamples {
+    fn write_csv(&self, out: &mut impl Write, precision: MinTicksize) -> std::io::Result<()> {
+        write!(out, "price")?;
+        for time in &self.times {
+            write!(out, ",{}", time.as_u64())?;
+        }
+        writeln!(out)?;
+
+        for (price, cells) in &self.levels {
+            write!(out, "{}", price.to_string(precision))?;
+            for cell in cells {
+                match cell {
+                    Some((qty, true)) => write!(out, ",{}", qty.to_f64())?,
+                    Some((qty, false)) => write!(out, ",-{}", qty.to_f64())?,
+                    None => write!(out, ",")?,
+                }
+            }
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Default)]
 pub struct HeatmapDataPoint {
     pub grouped_trades: Box<[GroupedTrade]>,
     pub buy_sell: (Qty, Qty),
 }
 
+impl HeatmapDataPoint {
+    /// Traded between `low` and `high`, both sides
+    pub fn volume_within(&self, low: Price, high: Price) -> Qty {
+        self.grouped_trades
+            .iter()
+            .filter(|trade| (low..=high).contains(&trade.price))
+            .fold(Qty::ZERO, |acc, trade| acc + trade.qty)
+    }
+}
+
 impl DataPoint for HeatmapDataPoint {
     fn add_trade(&mut self, trade: &exchange::Trade, step: PriceStep) {
         let grouped_price: Price = trade.price.round_to_side_step(trade.is_sell, step);
@@ -140,19 +461,22 @@ pub struct HistoricalDepth {
     tick_size: PriceStep,
     min_order_qty: MinQtySize,
     last_snapshot_time: Option<UnixMs>,
+    /// Runs ending before this have been merged into coarser columns already
+    downsampled_until: UnixMs,
+    /// Where the duration limit last cut the history
+    pruned_until: UnixMs,
 }
 
 impl HistoricalDepth {
     pub fn new(min_order_qty: MinQtySize, tick_size: PriceStep, basis: Basis) -> Self {
         Self {
             price_levels: BTreeMap::new(),
-            aggr_time: match basis {
-                Basis::Time(interval) => interval,
-                Basis::Tick(_) => unimplemented!(),
-            },
+            aggr_time: basis.heatmap_interval(),
             tick_size,
             min_order_qty,
             last_snapshot_time: None,
+            downsampled_until: UnixMs::ZERO,
+            pruned_until: UnixMs::ZERO,
         }
     }
 
@@ -293,9 +617,195 @@ impl HistoricalDepth {
             })
     }
 
+    /// The run resting at each price level at `time`, the book as it stood then
+    pub fn order_runs_at(
+        &self,
+        highest: Price,
+        lowest: Price,
+        time: UnixMs,
+    ) -> impl Iterator<Item = (&Price, &OrderRun)> {
+        self.price_levels
+            .range(lowest..=highest)
+            .filter_map(move |(price, runs)| {
+                runs.iter()
+                    .find(|run| run.start_time <= time && run.until_time > time)
+                    .map(|run| (price, run))
+            })
+    }
+
+    /// Resting size at every price level at `earliest`, `earliest + interval_ms`, ... up to `latest`
+    pub fn sample(&self, earliest: UnixMs, latest: UnixMs, interval_ms: u64) -> DepthSamples {
+        let interval_ms = interval_ms.max(1);
+        let times = (earliest.as_u64()..=latest.as_u64())
+            .step_by(interval_ms as usize)
+            .map(UnixMs::new)
+            .collect::<Vec<_>>();
+
+        let levels = self
+            .price_levels
+            .iter()
+            .filter_map(|(price, runs)| {
+                // Runs of a level are appended in time order, so one cursor walks both lists
+                let mut cursor = 0;
+                let mut any = false;
+
+                let cells = times
+                    .iter()
+                    .map(|&time| {
+                        while runs.get(cursor).is_some_and(|run| run.until_time <= time) {
+                            cursor += 1;
+                        }
+
+                        let run = runs.get(cursor).filter(|run| run.start_time <= time)?;
+                        any = true;
+                        Some((run.qty, run.is_bid))
+                    })
+                    .collect::<Vec<_>>();
+
+                any.then_some((*price, cells))
+            })
+            .collect();
+
+        DepthSamples { times, levels }
+    }
+
+    pub fn usage(&self) -> HistoryUsage {
+        let runs = self.price_levels.values().map(Vec::len).sum::<usize>();
+        let bytes = runs * std::mem::size_of::<OrderRun>()
+            + self.price_levels.len()
+                * (std::mem::size_of::<Price>() + std::mem::size_of::<Vec<OrderRun>>());
+
+        let oldest = self
+            .price_levels
+            .values()
+            .filter_map(|runs| runs.first())
+            .map(|run| run.start_time)
+            .min();
+        let span_ms = match (oldest, self.last_snapshot_time) {
+            (Some(oldest), Some(latest)) => latest.as_u64().saturating_sub(oldest.as_u64()),
+            _ => 0,
+        };
+
+        HistoryUsage { bytes, span_ms }
+    }
+
+    /// Merges the runs that ended before `time` into columns `factor` times as wide, each
+    /// taking the time weighted average size of the runs it replaced
+    pub fn downsample_before(&mut self, time: UnixMs, factor: u64) {
+        let coarse_ms = self.aggr_time_ms().saturating_mul(factor.max(1)).max(1);
+        // Whole coarse columns only, a later call picks up where this one stopped
+        let time = UnixMs::new(time.as_u64() / coarse_ms * coarse_ms);
+        if time <= self.downsampled_until {
+            return;
+        }
+        let from = self.downsampled_until;
+        let column = |time: UnixMs| time.as_u64() / coarse_ms;
+
+        for runs in self.price_levels.values_mut() {
+            let first = runs
+                .partition_point(|run| run.until_time < from)
+                .saturating_sub(1);
+            if runs.get(first).is_none_or(|run| run.start_time >= time) {
+                continue;
+            }
+
+            let mut kept: Vec<OrderRun> = Vec::with_capacity(runs.len());
+            kept.extend_from_slice(&runs[..first]);
+
+            for run in &runs[first..] {
+                if let Some(last) = kept.last_mut()
+                    && run.until_time <= time
+                    && last.is_bid == run.is_bid
+                    && last.until_time >= run.start_time
+                    && column(last.start_time) == column(run.start_time)
+                {
+                    let last_ms = last.until_time.as_u64() - last.start_time.as_u64();
+                    let run_ms = run
+                        .until_time
+                        .as_u64()
+                        .saturating_sub(run.start_time.as_u64());
+                    let total_ms = last_ms + run_ms;
+                    if total_ms > 0 {
+                        last.qty = Qty::from_f64(
+                            (last.qty.to_f64() * last_ms as f64 + run.qty.to_f64() * run_ms as f64)
+                                / total_ms as f64,
+                        );
+                    }
+                    last.until_time = last.until_time.max(run.until_time);
+                    continue;
+                }
+                kept.push(*run);
+            }
+
+            *runs = kept;
+        }
+
+        self.downsampled_until = time;
+    }
+
+    /// Coarsens and then drops the oldest runs until the history fits `limit`, returns the
+    /// time everything older than was dropped. `Auto` is left to the chart
+    pub fn enforce_limit(&mut self, limit: HistoryLimit) -> Option<UnixMs> {
+        let latest = self.last_snapshot_time?;
+
+        match limit {
+            HistoryLimit::Auto => None,
+            HistoryLimit::Minutes(minutes) => {
+                let span_ms = u64::from(minutes.max(1)) * 60_000;
+                let cutoff = latest.saturating_sub(span_ms);
+                // Cut in steps of a twentieth of the span, not on every snapshot
+                if cutoff.as_u64() < self.pruned_until.as_u64().saturating_add(span_ms / 20) {
+                    return None;
+                }
+
+                self.downsample_before(latest.saturating_sub(span_ms / 2), DOWNSAMPLE_FACTOR);
+                self.cleanup_old_price_levels(cutoff);
+                self.pruned_until = cutoff;
+                Some(cutoff)
+            }
+            HistoryLimit::Megabytes(megabytes) => {
+                let cap = usize::from(megabytes.max(1)) * 1024 * 1024;
+
+                let usage = self.usage();
+                if usage.bytes <= cap {
+                    return None;
+                }
+                self.downsample_before(latest.saturating_sub(usage.span_ms / 2), DOWNSAMPLE_FACTOR);
+
+                let usage = self.usage();
+                // A tenth of the span at a time, the oldest first. The cut only ever moves
+                // forward, a level resting since the first snapshot keeps the span from shrinking
+                let step = (usage.span_ms / 10).max(1);
+                let mut next = latest.saturating_sub(usage.span_ms);
+                let mut bytes = usage.bytes;
+
+                let mut cutoff = None;
+                while bytes > cap && next < latest {
+                    next = next.saturating_add(step).min(latest);
+                    self.cleanup_old_price_levels(next);
+                    cutoff = Some(next);
+
+                    let freed = bytes.saturating_sub(self.usage().bytes);
+                    if freed == 0 {
+                        break;
+                    }
+                    bytes -= freed;
+                }
+                cutoff
+            }
+        }
+    }
+
+    /// Drops the runs that ended before `oldest_time` and trims the ones straddling it
     pub fn cleanup_old_price_levels(&mut self, oldest_time: UnixMs) {
         self.price_levels.iter_mut().for_each(|(_, runs)| {
             runs.retain(|run| run.until_time >= oldest_time);
+            for run in runs.iter_mut() {
+                if run.start_time >= oldest_time {
+                    break;
+                }
+                run.start_time = oldest_time;
+            }
         });
 
         self.price_levels.retain(|_, runs| !runs.is_empty());
@@ -374,6 +884,80 @@ impl HistoricalDepth {
         result_runs
     }
 
+    /// Runs in range merged into price levels `factor` steps tall, keyed by the lowest price
+    /// of each. A merged level holds the summed size of the levels it covers at every moment,
+    /// on whichever side has more of it
+    pub fn rebucketed_runs(
+        &self,
+        earliest: UnixMs,
+        latest: UnixMs,
+        highest: Price,
+        lowest: Price,
+        factor: i64,
+        market_type: MarketKind,
+        order_size_filter: f32,
+    ) -> Vec<(Price, OrderRun)> {
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let bucket_units = self.tick_size.units.max(1) * factor.max(1);
+
+        // Bid and ask size each run adds at its start and takes away at its end
+        let mut buckets: BTreeMap<i64, Vec<(UnixMs, f64, f64)>> = BTreeMap::new();
+
+        for (price, runs) in self.iter_time_filtered(earliest, latest, highest, lowest) {
+            let events = buckets
+                .entry(price.units.div_euclid(bucket_units))
+                .or_default();
+
+            for run in runs.iter().filter(|run| {
+                run.until_time >= earliest
+                    && run.start_time <= latest
+                    && market_type.qty_in_quote_value(run.qty, *price, size_in_quote_ccy) as f32
+                        > order_size_filter
+            }) {
+                let qty = run.qty.to_f64();
+                let (bid, ask) = if run.is_bid { (qty, 0.0) } else { (0.0, qty) };
+                events.push((run.start_time, bid, ask));
+                events.push((run.until_time, -bid, -ask));
+            }
+        }
+
+        let mut merged = vec![];
+
+        for (bucket, mut events) in buckets {
+            events.sort_by_key(|(time, ..)| *time);
+            let price = Price::from_units(bucket * bucket_units);
+
+            let (mut bids, mut asks) = (0.0_f64, 0.0_f64);
+            let mut index = 0;
+            while index < events.len() {
+                let time = events[index].0;
+                while let Some((_, bid, ask)) = events.get(index).filter(|e| e.0 == time) {
+                    bids += bid;
+                    asks += ask;
+                    index += 1;
+                }
+
+                let Some((until_time, ..)) = events.get(index) else {
+                    break;
+                };
+                let qty = bids.max(asks);
+                if qty > f64::EPSILON {
+                    merged.push((
+                        price,
+                        OrderRun {
+                            start_time: time,
+                            until_time: *until_time,
+                            qty: Qty::from_f64(qty),
+                            is_bid: bids >= asks,
+                        },
+                    ));
+                }
+            }
+        }
+
+        merged
+    }
+
     pub fn query_grid_qtys(
         &self,
         center_time: UnixMs,
@@ -480,6 +1064,112 @@ impl HistoricalDepth {
         max_qty
     }
 
+    /// Size the given share of the runs in range stay at or below, skipping those under
+    /// the order size filter
+    pub fn percentile_qty_in_range(
+        &self,
+        earliest: UnixMs,
+        latest: UnixMs,
+        highest: Price,
+        lowest: Price,
+        market_type: MarketKind,
+        order_size_filter: f32,
+        percentile: f32,
+    ) -> Qty {
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+        let mut qtys = self
+            .price_levels
+            .range(lowest..=highest)
+            .flat_map(|(price, runs)| runs.iter().map(move |run| (price, run)))
+            .filter(|(price, run)| {
+                run.until_time >= earliest
+                    && run.start_time <= latest
+                    && market_type.qty_in_quote_value(run.qty, **price, size_in_quote_ccy) as f32
+                        > order_size_filter
+            })
+            .map(|(_, run)| run.qty)
+            .collect::<Vec<_>>();
+
+        if qtys.is_empty() {
+            return Qty::ZERO;
+        }
+
+        let index = ((qtys.len() - 1) as f32 * percentile.clamp(0.0, 1.0)).round() as usize;
+        *qtys.select_nth_unstable(index).1
+    }
+
+    /// Change in resting size at each price level from one snapshot to the next, positive
+    /// where liquidity was stacked and negative where it was pulled. Runs under the order
+    /// size filter count as empty, and a level that turned to the other side was traded
+    /// through rather than pulled, so it's left out
+    pub fn depth_deltas(
+        &self,
+        earliest: UnixMs,
+        latest: UnixMs,
+        highest: Price,
+        lowest: Price,
+        market_type: MarketKind,
+        order_size_filter: f32,
+    ) -> Vec<(Price, UnixMs, f64)> {
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        // Runs reaching this far are still resting, they haven't been pulled yet
+        let live_until = self
+            .last_snapshot_time
+            .map(|time| time.saturating_add(self.aggr_time_ms()));
+        let in_window = |time: UnixMs| time >= earliest && time <= latest;
+
+        let mut deltas = vec![];
+
+        for (price, runs) in self.price_levels.range(lowest..=highest) {
+            let shown = |run: &OrderRun| {
+                if market_type.qty_in_quote_value(run.qty, *price, size_in_quote_ccy) as f32
+                    > order_size_filter
+                {
+                    run.qty.to_f64()
+                } else {
+                    0.0
+                }
+            };
+
+            for (index, run) in runs.iter().enumerate() {
+                if run.until_time < earliest || run.start_time > latest {
+                    continue;
+                }
+
+                if in_window(run.start_time) {
+                    let prev = index
+                        .checked_sub(1)
+                        .map(|prev| &runs[prev])
+                        .filter(|prev| prev.until_time >= run.start_time);
+
+                    let delta = match prev {
+                        Some(prev) if prev.is_bid != run.is_bid => 0.0,
+                        Some(prev) => shown(run) - shown(prev),
+                        None => shown(run),
+                    };
+                    if delta != 0.0 {
+                        deltas.push((*price, run.start_time, delta));
+                    }
+                }
+
+                let replaced = runs
+                    .get(index + 1)
+                    .is_some_and(|next| next.start_time <= run.until_time);
+                let resting = live_until.is_none_or(|live| run.until_time >= live);
+
+                if !replaced && !resting && in_window(run.until_time) {
+                    let delta = shown(run);
+                    if delta != 0.0 {
+                        deltas.push((*price, run.until_time, -delta));
+                    }
+                }
+            }
+        }
+
+        deltas
+    }
+
     pub fn max_depth_qty_in_range(
         &self,
         earliest: UnixMs,
@@ -658,10 +1348,44 @@ impl GroupedTrade {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum HeatmapStudy {
     VolumeProfile(ProfileKind),
+    DevelopingValueArea {
+        value_area_pct: usize,
+    },
+    /// Estimated from open interest, see [`super::liquidation`]
+    LiquidationLevels {
+        max_leverage: u16,
+    },
 }
 
 impl HeatmapStudy {
-    pub const ALL: [HeatmapStudy; 1] = [HeatmapStudy::VolumeProfile(ProfileKind::VisibleRange)];
+    pub const ALL: [HeatmapStudy; 3] = [
+        HeatmapStudy::VolumeProfile(ProfileKind::VisibleRange),
+        HeatmapStudy::DevelopingValueArea { value_area_pct: 70 },
+        HeatmapStudy::LiquidationLevels { max_leverage: 100 },
+    ];
+
+    pub fn volume_profile(&self) -> Option<&ProfileKind> {
+        match self {
+            HeatmapStudy::VolumeProfile(kind) => Some(kind),
+            HeatmapStudy::DevelopingValueArea { .. } | HeatmapStudy::LiquidationLevels { .. } => {
+                None
+            }
+        }
+    }
+
+    pub fn value_area_pct(&self) -> Option<usize> {
+        match self {
+            HeatmapStudy::DevelopingValueArea { value_area_pct } => Some(*value_area_pct),
+            HeatmapStudy::VolumeProfile(_) | HeatmapStudy::LiquidationLevels { .. } => None,
+        }
+    }
+
+    pub fn max_leverage(&self) -> Option<u16> {
+        match self {
+            HeatmapStudy::LiquidationLevels { max_leverage } => Some(*max_leverage),
+            HeatmapStudy::VolumeProfile(_) | HeatmapStudy::DevelopingValueArea { .. } => None,
+        }
+    }
 }
 
 impl std::fmt::Display for HeatmapStudy {
@@ -670,6 +1394,8 @@ impl std::fmt::Display for HeatmapStudy {
             HeatmapStudy::VolumeProfile(kind) => {
                 write!(f, "Volume Profile ({})", kind)
             }
+            HeatmapStudy::DevelopingValueArea { .. } => write!(f, "Developing Value Area"),
+            HeatmapStudy::LiquidationLevels { .. } => write!(f, "Liquidation Levels"),
         }
     }
 }
@@ -689,3 +1415,204 @@ impl std::fmt::Display for ProfileKind {
         }
     }
 }
+
+/// Point of control and value area bounds as they stood at one datapoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueArea {
+    pub poc: Price,
+    pub vah: Price,
+    pub val: Price,
+}
+
+/// Session volume-at-price built up datapoint by datapoint from executed trades, keeping the
+/// value area as it developed over time. Sessions roll over at 00:00 UTC.
+#[derive(Debug, Clone)]
+pub struct DevelopingValueArea {
+    value_area_pct: usize,
+    session: Option<UnixMs>,
+    volume_at_price: BTreeMap<Price, f64>,
+    /// The newest datapoint keeps receiving trades until the next one opens,
+    /// so its contribution is taken back out before it gets applied again
+    open: Option<(UnixMs, Vec<(Price, f64)>)>,
+    pub levels: BTreeMap<UnixMs, ValueArea>,
+}
+
+impl DevelopingValueArea {
+    const SESSION: Timeframe = Timeframe::D1;
+
+    pub fn new(value_area_pct: usize) -> Self {
+        Self {
+            value_area_pct: value_area_pct.clamp(1, 100),
+            session: None,
+            volume_at_price: BTreeMap::new(),
+            open: None,
+            levels: BTreeMap::new(),
+        }
+    }
+
+    /// Creates, rebuilds or drops `current` so it matches the enabled studies
+    pub fn sync(
+        current: &mut Option<Self>,
+        studies: &[HeatmapStudy],
+        datapoints: &BTreeMap<UnixMs, HeatmapDataPoint>,
+    ) {
+        match studies.iter().find_map(HeatmapStudy::value_area_pct) {
+            Some(pct) if current.as_ref().is_some_and(|va| va.value_area_pct == pct) => {}
+            Some(pct) => {
+                let mut value_area = Self::new(pct);
+                value_area.update(datapoints);
+                *current = Some(value_area);
+            }
+            None => *current = None,
+        }
+    }
+
+    pub fn is_same_session(a: UnixMs, b: UnixMs) -> bool {
+        a.floor_to(Self::SESSION) == b.floor_to(Self::SESSION)
+    }
+
+    /// Applies every datapoint from the currently open one onwards
+    pub fn update(&mut self, datapoints: &BTreeMap<UnixMs, HeatmapDataPoint>) {
+        let from = self.open.as_ref().map_or(UnixMs::ZERO, |(time, _)| *time);
+
+        for (time, dp) in datapoints.range(from..) {
+            self.apply(*time, dp);
+        }
+    }
+
+    pub fn prune_before(&mut self, time: UnixMs) {
+        self.levels = self.levels.split_off(&time);
+    }
+
+    fn apply(&mut self, time: UnixMs, dp: &HeatmapDataPoint) {
+        if let Some((open_time, contribution)) = self.open.take()
+            && open_time == time
+        {
+            for (price, qty) in contribution {
+                if let Some(volume) = self.volume_at_price.get_mut(&price) {
+                    *volume -= qty;
+                    if *volume <= f64::EPSILON {
+                        self.volume_at_price.remove(&price);
+                    }
+                }
+            }
+        }
+
+        let session = time.floor_to(Self::SESSION);
+        if self.session != Some(session) {
+            self.session = Some(session);
+            self.volume_at_price.clear();
+        }
+
+        let contribution: Vec<(Price, f64)> = dp
+            .grouped_trades
+            .iter()
+            .map(|trade| (trade.price, trade.qty.to_f64()))
+            .collect();
+        for &(price, qty) in &contribution {
+            *self.volume_at_price.entry(price).or_default() += qty;
+        }
+        self.open = Some((time, contribution));
+
+        match value_area(&self.volume_at_price, self.value_area_pct) {
+            Some(va) => {
+                self.levels.insert(time, va);
+            }
+            None => {
+                self.levels.remove(&time);
+            }
+        }
+    }
+}
+
+/// Grows the range around the point of control one level at a time, towards whichever
+/// neighbour traded more, until it holds `pct` percent of the volume
+pub(crate) fn value_area(volume_at_price: &BTreeMap<Price, f64>, pct: usize) -> Option<ValueArea> {
+    let levels: Vec<(Price, f64)> = volume_at_price
+        .iter()
+        .map(|(price, volume)| (*price, *volume))
+        .collect();
+
+    let (poc_idx, _) = levels
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.1.total_cmp(&b.1))?;
+
+    let total: f64 = levels.iter().map(|(_, volume)| volume).sum();
+    let target = total * (pct as f64 / 100.0);
+
+    let (mut lo, mut hi) = (poc_idx, poc_idx);
+    let mut acc = levels[poc_idx].1;
+
+    while acc < target {
+        let below = lo.checked_sub(1).map(|i| levels[i].1);
+        let above = levels.get(hi + 1).map(|(_, volume)| *volume);
+
+        match (below, above) {
+            (Some(b), Some(a)) if a >= b => {
+                hi += 1;
+                acc += a;
+            }
+            (Some(b), _) => {
+                lo -= 1;
+                acc += b;
+            }
+            (None, Some(a)) => {
+                hi += 1;
+                acc += a;
+            }
+            (None, None) => break,
+        }
+    }
+
+    Some(ValueArea {
+        poc: levels[poc_idx].0,
+        vah: levels[hi].0,
+        val: levels[lo].0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::Timeframe;
+
+    #[test]
+    fn memory_limit_returns_with_a_level_resting_all_session() {
+        let mut history = HistoricalDepth::new(
+            MinQtySize::from(0.001_f32),
+            PriceStep::from(MinTicksize::from(1.0_f32)),
+            Basis::Time(Timeframe::MS100),
+        );
+
+        for i in 0..2_000_u64 {
+            let mut depth = Depth::default();
+            for level in 0..100_u64 {
+                let qty = Qty::from_f64(((i + level) % 7 + 1) as f64);
+                depth.bids.insert(Price::from_f32(level as f32), qty);
+            }
+            // A wall that never changes, its run spans the whole history
+            depth
+                .asks
+                .insert(Price::from_f32(1_000.0), Qty::from_f64(5.0));
+
+            history.insert_latest_depth(&depth, UnixMs::new(i * 100));
+        }
+
+        let cap = 1024 * 1024;
+        assert!(history.usage().bytes > cap);
+
+        assert!(history.enforce_limit(HistoryLimit::Megabytes(1)).is_some());
+        assert!(history.usage().bytes <= cap);
+        assert!(
+            history
+                .latest_order_runs(
+                    Price::from_f32(1_000.0),
+                    Price::from_f32(1_000.0),
+                    UnixMs::new(199_900)
+                )
+                .next()
+                .is_some()
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt::{self, Debug, Display};
 
+use super::oscillator::{self, OscillatorConfig};
+
 use enum_map::Enum;
+use exchange::Kline;
 use exchange::adapter::MarketKind;
 use serde::{Deserialize, Serialize};
 
@@ -8,13 +11,25 @@ pub trait Indicator: PartialEq + Display + 'static {
     fn for_market(market: MarketKind) -> &'static [Self]
     where
         Self: Sized;
+
+    /// Whether a chart can show it more than once
+    fn allows_multiple(&self) -> bool {
+        false
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Eq, Enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Eq, Hash, Enum)]
 pub enum KlineIndicator {
     Volume,
     CumulativeDelta,
     OpenInterest,
+    FundingRate,
+    OpenInterestDelta,
+    FundingWeightedOi,
+    Rsi,
+    Macd,
+    Stochastic,
+    Cci,
 }
 
 impl Indicator for KlineIndicator {
@@ -24,19 +39,199 @@ impl Indicator for KlineIndicator {
             MarketKind::LinearPerps | MarketKind::InversePerps => &Self::FOR_PERPS,
         }
     }
+
+    /// Oscillators can be stacked with different periods
+    fn allows_multiple(&self) -> bool {
+        self.is_oscillator()
+    }
 }
 
 impl KlineIndicator {
     // Indicator togglers on UI menus depend on these arrays.
     // Every variant needs to be in either SPOT, PERPS or both.
     /// Indicators that can be used with spot market tickers
-    const FOR_SPOT: [KlineIndicator; 2] = [KlineIndicator::Volume, KlineIndicator::CumulativeDelta];
+    const FOR_SPOT: [KlineIndicator; 6] = [
+        KlineIndicator::Volume,
+        KlineIndicator::CumulativeDelta,
+        KlineIndicator::Rsi,
+        KlineIndicator::Macd,
+        KlineIndicator::Stochastic,
+        KlineIndicator::Cci,
+    ];
     /// Indicators that can be used with perpetual swap market tickers
-    const FOR_PERPS: [KlineIndicator; 3] = [
+    const FOR_PERPS: [KlineIndicator; 10] = [
         KlineIndicator::Volume,
         KlineIndicator::CumulativeDelta,
         KlineIndicator::OpenInterest,
+        KlineIndicator::FundingRate,
+        KlineIndicator::OpenInterestDelta,
+        KlineIndicator::FundingWeightedOi,
+        KlineIndicator::Rsi,
+        KlineIndicator::Macd,
+        KlineIndicator::Stochastic,
+        KlineIndicator::Cci,
+    ];
+
+    /// Oscillators are computed from the candles with periods set in the chart's config
+    pub fn is_oscillator(&self) -> bool {
+        matches!(
+            self,
+            KlineIndicator::Rsi
+                | KlineIndicator::Macd
+                | KlineIndicator::Stochastic
+                | KlineIndicator::Cci
+        )
+    }
+
+    /// Computed from a single price of each candle, which [`IndicatorSettings::source`] picks
+    pub fn takes_source(&self) -> bool {
+        matches!(self, KlineIndicator::Rsi | KlineIndicator::Macd)
+    }
+
+    /// Drawn as a line, whose color [`IndicatorSettings::color`] overrides
+    pub fn has_line(&self) -> bool {
+        !matches!(
+            self,
+            KlineIndicator::Volume | KlineIndicator::OpenInterestDelta
+        )
+    }
+}
+
+/// Price of each candle an indicator is computed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PriceSource {
+    #[default]
+    Close,
+    Open,
+    High,
+    Low,
+    /// Middle of the high and low
+    Hl2,
+    /// Typical price, the average of the high, low and close
+    Hlc3,
+    Ohlc4,
+}
+
+impl PriceSource {
+    pub const ALL: [PriceSource; 7] = [
+        PriceSource::Close,
+        PriceSource::Open,
+        PriceSource::High,
+        PriceSource::Low,
+        PriceSource::Hl2,
+        PriceSource::Hlc3,
+        PriceSource::Ohlc4,
     ];
+
+    pub fn of(self, kline: &Kline) -> f64 {
+        let (open, high, low, close) = (
+            kline.open.to_f64(),
+            kline.high.to_f64(),
+            kline.low.to_f64(),
+            kline.close.to_f64(),
+        );
+
+        match self {
+            PriceSource::Close => close,
+            PriceSource::Open => open,
+            PriceSource::High => high,
+            PriceSource::Low => low,
+            PriceSource::Hl2 => (high + low) / 2.0,
+            PriceSource::Hlc3 => (high + low + close) / 3.0,
+            PriceSource::Ohlc4 => (open + high + low + close) / 4.0,
+        }
+    }
+}
+
+impl Display for PriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriceSource::Close => write!(f, "Close"),
+            PriceSource::Open => write!(f, "Open"),
+            PriceSource::High => write!(f, "High"),
+            PriceSource::Low => write!(f, "Low"),
+            PriceSource::Hl2 => write!(f, "HL/2"),
+            PriceSource::Hlc3 => write!(f, "HLC/3"),
+            PriceSource::Ohlc4 => write!(f, "OHLC/4"),
+        }
+    }
+}
+
+/// Settings of an indicator panel on a kline chart
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IndicatorSettings {
+    /// Color of the main line, the theme's when `None`
+    pub color: Option<iced_core::Color>,
+    /// Only used by indicators that [take one](KlineIndicator::takes_source)
+    pub source: PriceSource,
+    /// Only the periods of the indicator's own kind are used
+    pub periods: OscillatorConfig,
+}
+
+/// A panel of a kline chart, told apart from others of the same kind by its id
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "PersistedInstance")]
+pub struct KlineIndicatorInstance {
+    pub id: uuid::Uuid,
+    pub kind: KlineIndicator,
+    pub settings: IndicatorSettings,
+}
+
+impl KlineIndicatorInstance {
+    pub fn new(kind: KlineIndicator) -> Self {
+        Self::with_settings(kind, IndicatorSettings::default())
+    }
+
+    pub fn with_settings(kind: KlineIndicator, settings: IndicatorSettings) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            kind,
+            settings,
+        }
+    }
+}
+
+/// Periods follow the name so panels of the same kind can be told apart
+impl Display for KlineIndicatorInstance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let p = self.settings.periods;
+        match self.kind {
+            KlineIndicator::Rsi => write!(f, "RSI {}", p.rsi_period),
+            KlineIndicator::Macd => {
+                write!(f, "MACD {} {} {}", p.macd_fast, p.macd_slow, p.macd_signal)
+            }
+            KlineIndicator::Stochastic => {
+                write!(f, "Stoch {} {} {}", p.stoch_k, p.stoch_smoothing, p.stoch_d)
+            }
+            KlineIndicator::Cci => write!(f, "CCI {}", p.cci_period),
+            kind => write!(f, "{kind}"),
+        }
+    }
+}
+
+/// Layouts saved before panels had ids list only their kinds
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PersistedInstance {
+    Kind(KlineIndicator),
+    Instance {
+        id: uuid::Uuid,
+        kind: KlineIndicator,
+        #[serde(default)]
+        settings: IndicatorSettings,
+    },
+}
+
+impl From<PersistedInstance> for KlineIndicatorInstance {
+    fn from(persisted: PersistedInstance) -> Self {
+        match persisted {
+            PersistedInstance::Kind(kind) => KlineIndicatorInstance::new(kind),
+            PersistedInstance::Instance { id, kind, settings } => {
+                KlineIndicatorInstance { id, kind, settings }
+            }
+        }
+    }
 }
 
 impl Display for KlineIndicator {
@@ -45,10 +240,148 @@ impl Display for KlineIndicator {
             KlineIndicator::Volume => write!(f, "Volume"),
             KlineIndicator::CumulativeDelta => write!(f, "CVD"),
             KlineIndicator::OpenInterest => write!(f, "Open Interest"),
+            KlineIndicator::FundingRate => write!(f, "Funding Rate"),
+            KlineIndicator::OpenInterestDelta => write!(f, "OI Delta"),
+            KlineIndicator::FundingWeightedOi => write!(f, "OI × Funding"),
+            KlineIndicator::Rsi => write!(f, "RSI"),
+            KlineIndicator::Macd => write!(f, "MACD"),
+            KlineIndicator::Stochastic => write!(f, "Stochastic"),
+            KlineIndicator::Cci => write!(f, "CCI"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MovingAverageKind {
+    Ema,
+    Sma,
+    Wma,
+}
+
+impl MovingAverageKind {
+    pub const ALL: [MovingAverageKind; 3] = [
+        MovingAverageKind::Ema,
+        MovingAverageKind::Sma,
+        MovingAverageKind::Wma,
+    ];
+}
+
+impl Display for MovingAverageKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MovingAverageKind::Ema => write!(f, "EMA"),
+            MovingAverageKind::Sma => write!(f, "SMA"),
+            MovingAverageKind::Wma => write!(f, "WMA"),
+        }
+    }
+}
+
+/// A moving average of closes, drawn over the candles rather than in its own panel
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct MovingAverage {
+    pub kind: MovingAverageKind,
+    pub period: u16,
+    pub color: iced_core::Color,
+}
+
+impl MovingAverage {
+    /// One value per close, `None` until `period` closes have been seen
+    pub fn compute(&self, closes: &[f64]) -> Vec<Option<f64>> {
+        let closes = closes.iter().copied().map(Some).collect::<Vec<_>>();
+
+        match self.kind {
+            MovingAverageKind::Ema => oscillator::ema(&closes, self.period),
+            MovingAverageKind::Sma => oscillator::sma(&closes, self.period),
+            MovingAverageKind::Wma => oscillator::wma(&closes, self.period),
+        }
+    }
+}
+
+impl Display for MovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.period)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum BandKind {
+    /// Simple average of closes, widened by their standard deviation
+    Bollinger,
+    /// Exponential average of closes, widened by the average true range
+    Keltner,
+}
+
+impl BandKind {
+    pub const ALL: [BandKind; 2] = [BandKind::Bollinger, BandKind::Keltner];
+
+    pub fn default_band(self) -> VolatilityBand {
+        VolatilityBand {
+            kind: self,
+            period: 20,
+            multiplier: 2.0,
+            fill: true,
+        }
+    }
+}
+
+impl Display for BandKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BandKind::Bollinger => write!(f, "Bollinger Bands"),
+            BandKind::Keltner => write!(f, "Keltner Channel"),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct VolatilityBand {
+    pub kind: BandKind,
+    pub period: u16,
+    /// Width of each band from the middle line, in deviations or average true ranges
+    pub multiplier: f32,
+    /// Shade the area between the upper and lower band
+    pub fill: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandPoint {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+impl VolatilityBand {
+    /// One point per kline, `None` until `period` klines have been seen
+    pub fn compute(&self, highs: &[f64], lows: &[f64], closes: &[f64]) -> Vec<Option<BandPoint>> {
+        let multiplier = f64::from(self.multiplier);
+        let closes_opt = closes.iter().copied().map(Some).collect::<Vec<_>>();
+
+        let (middle, width) = match self.kind {
+            BandKind::Bollinger => (
+                oscillator::sma(&closes_opt, self.period),
+                oscillator::std_dev(closes, self.period),
+            ),
+            BandKind::Keltner => (
+                oscillator::ema(&closes_opt, self.period),
+                oscillator::atr(highs, lows, closes, self.period),
+            ),
+        };
+
+        middle
+            .into_iter()
+            .zip(width)
+            .map(|(middle, width)| {
+                let (middle, width) = (middle?, width? * multiplier);
+                Some(BandPoint {
+                    upper: middle + width,
+                    middle,
+                    lower: middle - width,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Eq, Enum)]
 pub enum HeatmapIndicator {
     Volume,
@@ -1,12 +1,15 @@
+use super::heatmap::{ValueArea, value_area};
 use crate::aggr::time::DataPoint;
 use exchange::{
     Kline, Trade, UnixMs,
+    unit::MinTicksize,
     unit::price::{Price, PriceStep},
     unit::qty::Qty,
 };
 
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Clone)]
 pub struct KlineDataPoint {
@@ -61,6 +64,19 @@ impl KlineDataPoint {
         }
     }
 
+    /// Traded between `low` and `high`, the whole candle's volume when its trades aren't kept
+    pub fn volume_within(&self, low: Price, high: Price) -> Qty {
+        if self.footprint.trades.is_empty() {
+            return self.kline.volume.total();
+        }
+
+        self.footprint
+            .trades
+            .iter()
+            .filter(|(price, _)| (low..=high).contains(*price))
+            .fold(Qty::ZERO, |acc, (_, group)| acc + group.total_qty())
+    }
+
     /// Whether this datapoint has directional (buy vs sell) data.
     pub fn is_directional(&self) -> bool {
         !self.footprint.trades.is_empty() || self.kline.volume.is_directional()
@@ -163,6 +179,7 @@ impl GroupedTrades {
 pub struct KlineTrades {
     pub trades: FxHashMap<Price, GroupedTrades>,
     pub poc: Option<PointOfControl>,
+    pub unfinished_auctions: Vec<UnfinishedAuction>,
 }
 
 impl KlineTrades {
@@ -170,6 +187,7 @@ impl KlineTrades {
         Self {
             trades: FxHashMap::default(),
             poc: None,
+            unfinished_auctions: vec![],
         }
     }
 
@@ -243,6 +261,30 @@ impl KlineTrades {
             volume: max_volume,
             status: NPoc::default(),
         });
+        self.unfinished_auctions = self.find_unfinished_auctions();
+    }
+
+    /// The highest and lowest traded levels where both buyers and sellers traded
+    pub fn find_unfinished_auctions(&self) -> Vec<UnfinishedAuction> {
+        let two_sided = |price: &Price| {
+            self.trades
+                .get(price)
+                .is_some_and(|group| group.buy_qty > Qty::ZERO && group.sell_qty > Qty::ZERO)
+        };
+
+        let high = self.trades.keys().max().filter(|price| two_sided(price));
+        let low = self.trades.keys().min().filter(|price| two_sided(price));
+
+        [(true, high), (false, low)]
+            .into_iter()
+            .filter_map(|(at_high, price)| {
+                Some(UnfinishedAuction {
+                    price: *price?,
+                    at_high,
+                    status: NPoc::default(),
+                })
+            })
+            .collect()
     }
 
     pub fn set_poc_status(&mut self, status: NPoc) {
@@ -258,6 +300,7 @@ impl KlineTrades {
     pub fn clear(&mut self) {
         self.trades.clear();
         self.poc = None;
+        self.unfinished_auctions.clear();
     }
 }
 
@@ -268,6 +311,8 @@ pub struct FootprintSummary {
     pub total: Qty,
     pub delta: Qty,
     pub delta_pct: f64,
+    /// Trades behind the volume, zero where they weren't counted
+    pub trades: usize,
 }
 
 impl FootprintSummary {
@@ -287,6 +332,7 @@ impl FootprintSummary {
             total,
             delta,
             delta_pct,
+            trades: 0,
         }
     }
 
@@ -295,14 +341,21 @@ impl FootprintSummary {
             return None;
         }
 
-        let (buy, sell) = footprint
-            .trades
-            .values()
-            .fold((Qty::ZERO, Qty::ZERO), |(buy, sell), group| {
-                (buy + group.buy_qty, sell + group.sell_qty)
-            });
+        let (buy, sell, trades) = footprint.trades.values().fold(
+            (Qty::ZERO, Qty::ZERO, 0),
+            |(buy, sell, trades), group| {
+                (
+                    buy + group.buy_qty,
+                    sell + group.sell_qty,
+                    trades + group.buy_count + group.sell_count,
+                )
+            },
+        );
 
-        Some(Self::new(buy, sell))
+        Some(Self {
+            trades,
+            ..Self::new(buy, sell)
+        })
     }
 }
 
@@ -395,12 +448,307 @@ impl std::fmt::Display for ClusterKind {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize, Serialize)]
+/// Share of the most extreme highs and lows ignored by [`Config::clip_outliers`]
+pub const OUTLIER_CLIP: f32 = 0.005;
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Config {
     // Whether to show last value labels on top right/left when not hovering
     // e.g. OHLC/bar change values for the main chart, or last value of an indicator series
     pub data_labels_always_visible: bool,
+    /// Space kept above and below the fitted range, in percent of the visible span
+    pub autoscale_padding: f32,
+    /// Fit to the visible range without the top/bottom [`OUTLIER_CLIP`] of wicks
+    pub clip_outliers: bool,
+    /// Overlay the mark and index price of perpetuals, on venues that stream them
+    pub show_mark_price: bool,
+    /// Volume by price of the visible candles, along the right edge
+    pub volume_profile: Option<VolumeProfileConfig>,
+    /// How candlestick charts draw their klines
+    pub candle_style: CandleStyle,
+    /// Rows of per-candle numbers along the bottom of footprint charts
+    pub summary_footer: Option<SummaryFooter>,
+    /// Dots on the single trades of at least a given notional
+    pub big_trades: Option<BigTrades>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            data_labels_always_visible: false,
+            autoscale_padding: 5.0,
+            clip_outliers: false,
+            show_mark_price: false,
+            volume_profile: None,
+            candle_style: CandleStyle::default(),
+            summary_footer: None,
+            big_trades: None,
+        }
+    }
+}
+
+/// Which rows the footprint summary footer shows, top to bottom in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SummaryFooter {
+    pub delta: bool,
+    pub delta_pct: bool,
+    pub volume: bool,
+    pub trades: bool,
+}
+
+impl Default for SummaryFooter {
+    fn default() -> Self {
+        Self {
+            delta: true,
+            delta_pct: true,
+            volume: true,
+            trades: true,
+        }
+    }
+}
+
+impl SummaryFooter {
+    pub fn rows(&self) -> usize {
+        [self.delta, self.delta_pct, self.volume, self.trades]
+            .into_iter()
+            .filter(|shown| *shown)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BigTrades {
+    /// Smallest trade marked, in quote currency or contracts for inverse perpetuals
+    pub min_notional: f32,
+    /// Grow the dots with the trade's notional, otherwise they're all the same size
+    pub size_scaled: bool,
+    /// Write the notional next to each dot
+    pub labels: bool,
+}
+
+impl Default for BigTrades {
+    fn default() -> Self {
+        Self {
+            min_notional: 100_000.0,
+            size_scaled: true,
+            labels: false,
+        }
+    }
+}
+
+/// How the klines of a candlestick chart are drawn, the klines themselves stay as they are
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CandleStyle {
+    #[default]
+    Candles,
+    /// Candles averaged with the one before them, see [`heikin_ashi`]
+    HeikinAshi,
+    /// Closes joined by a line
+    Line,
+    /// Line of closes with the space under it filled
+    Area,
+    /// Line of closes filled up or down to the first visible close, in the colors of a rise
+    /// and a fall
+    Baseline,
+}
+
+impl CandleStyle {
+    pub const ALL: [CandleStyle; 5] = [
+        CandleStyle::Candles,
+        CandleStyle::HeikinAshi,
+        CandleStyle::Line,
+        CandleStyle::Area,
+        CandleStyle::Baseline,
+    ];
+}
+
+impl std::fmt::Display for CandleStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CandleStyle::Candles => write!(f, "Candles"),
+            CandleStyle::HeikinAshi => write!(f, "Heikin-Ashi"),
+            CandleStyle::Line => write!(f, "Line"),
+            CandleStyle::Area => write!(f, "Area"),
+            CandleStyle::Baseline => write!(f, "Baseline"),
+        }
+    }
+}
+
+/// Heikin-Ashi candles of `klines`, oldest first. Each closes at the average of its kline's
+/// prices and opens halfway through the candle before it
+pub fn heikin_ashi<'a>(klines: impl IntoIterator<Item = &'a Kline>) -> Vec<Kline> {
+    let mut prev: Option<(i64, i64)> = None;
+
+    klines
+        .into_iter()
+        .map(|kline| {
+            let close =
+                (kline.open.units + kline.high.units + kline.low.units + kline.close.units) / 4;
+            let open = match prev {
+                Some((prev_open, prev_close)) => (prev_open + prev_close) / 2,
+                None => (kline.open.units + kline.close.units) / 2,
+            };
+            prev = Some((open, close));
+
+            Kline {
+                open: Price::from_units(open),
+                high: Price::from_units(kline.high.units.max(open).max(close)),
+                low: Price::from_units(kline.low.units.min(open).min(close)),
+                close: Price::from_units(close),
+                ..*kline
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VolumeProfileConfig {
+    pub scope: ProfileScope,
+    /// Share of the profile's volume inside the value area, in percent
+    pub value_area_pct: u8,
+    /// Length of the longest bar of a visible range profile, as a share of the chart width
+    pub width: f32,
+}
+
+impl Default for VolumeProfileConfig {
+    fn default() -> Self {
+        Self {
+            scope: ProfileScope::default(),
+            value_area_pct: 70,
+            width: 0.25,
+        }
+    }
+}
+
+/// Which candles make up a profile
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum ProfileScope {
+    /// One profile of the visible candles, along the right edge
+    #[default]
+    VisibleRange,
+    /// One profile per session, drawn over its candles
+    Sessions(SessionWindow),
+    /// One profile per range drawn on the chart, see [`ProfileRange`]
+    FixedRanges,
+}
+
+impl std::fmt::Display for ProfileScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileScope::VisibleRange => write!(f, "Visible range"),
+            ProfileScope::Sessions(_) => write!(f, "Sessions"),
+            ProfileScope::FixedRanges => write!(f, "Fixed ranges"),
+        }
+    }
+}
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+const MS_PER_MINUTE: u64 = 60_000;
+const MS_PER_DAY: u64 = MINUTES_PER_DAY as u64 * MS_PER_MINUTE;
+
+/// Daily trading session in UTC minutes of the day. Equal bounds cover the whole day and a
+/// start later than the end runs past midnight
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SessionWindow {
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+impl SessionWindow {
+    pub fn is_utc_day(&self) -> bool {
+        self.start_minute == self.end_minute
+    }
+
+    fn length_ms(&self) -> u64 {
+        let minutes = (self.end_minute + MINUTES_PER_DAY - self.start_minute) % MINUTES_PER_DAY;
+        match minutes {
+            0 => MS_PER_DAY,
+            minutes => u64::from(minutes) * MS_PER_MINUTE,
+        }
+    }
+
+    /// Sessions overlapping `from..=to` as half-open `(start, end)` spans, oldest first
+    pub fn sessions_between(&self, from: UnixMs, to: UnixMs) -> Vec<(UnixMs, UnixMs)> {
+        let offset = u64::from(self.start_minute % MINUTES_PER_DAY) * MS_PER_MINUTE;
+        let length = self.length_ms();
+
+        let first_day = from.as_u64().saturating_sub(MS_PER_DAY);
+        let mut day = first_day - first_day % MS_PER_DAY;
+        let mut sessions = vec![];
+
+        while day <= to.as_u64() {
+            let (start, end) = (day + offset, day + offset + length);
+            if end > from.as_u64() && start <= to.as_u64() {
+                sessions.push((UnixMs::new(start), UnixMs::new(end)));
+            }
+            day += MS_PER_DAY;
+        }
+
+        sessions
+    }
+}
+
+/// Candles picked on the chart for a fixed range profile, by the open time of the first and
+/// last one
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProfileRange {
+    pub from: UnixMs,
+    pub to: UnixMs,
+}
+
+impl ProfileRange {
+    pub fn new(a: UnixMs, b: UnixMs) -> Self {
+        Self {
+            from: a.min(b),
+            to: a.max(b),
+        }
+    }
+}
+
+/// Buy and sell volume traded at each price over a span of candles
+#[derive(Debug, Clone, Default)]
+pub struct VolumeProfile {
+    pub levels: BTreeMap<Price, (Qty, Qty)>,
+    pub value_area: Option<ValueArea>,
+}
+
+impl VolumeProfile {
+    /// Sums the footprints, which hold the raw trades grouped by the chart's tick size
+    pub fn from_footprints<'a>(
+        footprints: impl Iterator<Item = &'a KlineTrades>,
+        value_area_pct: u8,
+    ) -> Self {
+        let mut levels = BTreeMap::<Price, (Qty, Qty)>::new();
+
+        for footprint in footprints {
+            for (price, group) in &footprint.trades {
+                let (buy, sell) = levels.entry(*price).or_default();
+                *buy += group.buy_qty;
+                *sell += group.sell_qty;
+            }
+        }
+
+        let totals = levels
+            .iter()
+            .map(|(price, (buy, sell))| (*price, (*buy + *sell).to_f64()))
+            .collect();
+
+        Self {
+            value_area: value_area(&totals, usize::from(value_area_pct)),
+            levels,
+        }
+    }
+
+    pub fn max_qty(&self) -> Qty {
+        self.levels
+            .values()
+            .fold(Qty::ZERO, |max, (buy, sell)| max.max(*buy + *sell))
+    }
 }
 
 #[derive(Default, Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
@@ -439,12 +787,16 @@ impl std::cmp::Eq for ClusterScaling {}
 pub enum FootprintStudy {
     NPoC {
         lookback: usize,
+        /// Also tracks the unfinished auctions at the candle extremes
+        #[serde(default)]
+        unfinished_auctions: bool,
     },
     Imbalance {
         threshold: usize,
         color_scale: Option<usize>,
         ignore_zeros: bool,
     },
+    FlowAlerts(FlowAlertRules),
 }
 
 impl FootprintStudy {
@@ -456,26 +808,226 @@ impl FootprintStudy {
                     FootprintStudy::Imbalance { .. },
                     FootprintStudy::Imbalance { .. }
                 )
+                | (FootprintStudy::FlowAlerts(_), FootprintStudy::FlowAlerts(_))
         )
     }
 }
 
 impl FootprintStudy {
-    pub const ALL: [FootprintStudy; 2] = [
-        FootprintStudy::NPoC { lookback: 80 },
+    pub const ALL: [FootprintStudy; 3] = [
+        FootprintStudy::NPoC {
+            lookback: 80,
+            unfinished_auctions: true,
+        },
         FootprintStudy::Imbalance {
             threshold: 200,
             color_scale: Some(400),
             ignore_zeros: true,
         },
+        FootprintStudy::FlowAlerts(FlowAlertRules::DEFAULT),
     ];
 }
 
 impl std::fmt::Display for FootprintStudy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            FootprintStudy::NPoC { .. } => write!(f, "Naked Point of Control"),
+            FootprintStudy::NPoC { .. } => write!(f, "Naked POC & Unfinished Auctions"),
             FootprintStudy::Imbalance { .. } => write!(f, "Imbalance"),
+            FootprintStudy::FlowAlerts(_) => write!(f, "Order Flow Alerts"),
+        }
+    }
+}
+
+/// Order flow patterns checked on every footprint candle once it closes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FlowAlertRules {
+    /// Consecutive imbalanced price levels that make a stack, off if `None`
+    pub stacked_levels: Option<usize>,
+    /// Diagonal Ask:Bid threshold in percent, as in [`FootprintStudy::Imbalance`].
+    /// Levels where either side has no volume never count as imbalanced
+    pub imbalance_threshold: usize,
+    /// A new high on negative delta, or a new low on positive delta
+    pub delta_divergence: bool,
+    /// Both buyers and sellers traded at the high or low of the candle
+    pub unfinished_auction: bool,
+    pub sound: bool,
+}
+
+impl FlowAlertRules {
+    pub const DEFAULT: Self = Self {
+        stacked_levels: Some(3),
+        imbalance_threshold: 300,
+        delta_divergence: true,
+        unfinished_auction: false,
+        sound: true,
+    };
+
+    /// Patterns found in the closed candle `kline`, `previous` being the one before it
+    pub fn check(
+        &self,
+        kline: &Kline,
+        footprint: &KlineTrades,
+        previous: Option<&Kline>,
+        step: PriceStep,
+    ) -> Vec<FlowSignal> {
+        let mut signals = vec![];
+        if footprint.trades.is_empty() {
+            return signals;
+        }
+
+        let mut prices = footprint.trades.keys().copied().collect::<Vec<_>>();
+        prices.sort_unstable();
+
+        if let Some(min_levels) = self.stacked_levels {
+            for is_sell in [false, true] {
+                if let Some((levels, low, high)) =
+                    self.longest_stack(footprint, &prices, step, is_sell)
+                    && levels >= min_levels
+                {
+                    signals.push(FlowSignal::StackedImbalance {
+                        is_sell,
+                        levels,
+                        low,
+                        high,
+                    });
+                }
+            }
+        }
+
+        if self.delta_divergence
+            && let Some(previous) = previous
+        {
+            let delta = footprint
+                .trades
+                .values()
+                .fold(0.0, |acc, group| acc + group.delta_qty().to_f64());
+
+            if kline.high > previous.high && delta < 0.0 {
+                signals.push(FlowSignal::DeltaDivergence { is_sell: true });
+            } else if kline.low < previous.low && delta > 0.0 {
+                signals.push(FlowSignal::DeltaDivergence { is_sell: false });
+            }
+        }
+
+        if self.unfinished_auction {
+            signals.extend(
+                footprint
+                    .find_unfinished_auctions()
+                    .into_iter()
+                    .map(|auction| FlowSignal::UnfinishedAuction {
+                        at_high: auction.at_high,
+                        price: auction.price,
+                    }),
+            );
+        }
+
+        signals
+    }
+
+    /// Longest run of adjacent levels where buyers lift the ask over the bid one level
+    /// below, or sellers hit the bid over the ask one level above. `(levels, low, high)`
+    fn longest_stack(
+        &self,
+        footprint: &KlineTrades,
+        prices: &[Price],
+        step: PriceStep,
+        is_sell: bool,
+    ) -> Option<(usize, Price, Price)> {
+        let factor = (100 + self.imbalance_threshold) as f64 / 100.0;
+
+        let is_imbalanced = |price: Price| {
+            let group = &footprint.trades[&price];
+            let (strong, diagonal) = if is_sell {
+                let diagonal = footprint.trades.get(&price.add_steps(1, step));
+                (group.sell_qty, diagonal.map(|group| group.buy_qty))
+            } else {
+                let diagonal = footprint.trades.get(&price.add_steps(-1, step));
+                (group.buy_qty, diagonal.map(|group| group.sell_qty))
+            };
+
+            let (strong, diagonal) = (strong.to_f64(), diagonal.map_or(0.0, Qty::to_f64));
+            diagonal > 0.0 && strong > diagonal * factor
+        };
+
+        let mut best: Option<(usize, Price, Price)> = None;
+        let mut run: Option<(usize, Price, Price)> = None;
+
+        for &price in prices {
+            run = if !is_imbalanced(price) {
+                None
+            } else {
+                match run {
+                    Some((levels, low, high)) if high.add_steps(1, step) == price => {
+                        Some((levels + 1, low, price))
+                    }
+                    _ => Some((1, price, price)),
+                }
+            };
+
+            if let Some(current) = run
+                && best.is_none_or(|(levels, ..)| current.0 > levels)
+            {
+                best = Some(current);
+            }
+        }
+
+        best
+    }
+}
+
+/// A pattern found by [`FlowAlertRules::check`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowSignal {
+    StackedImbalance {
+        is_sell: bool,
+        levels: usize,
+        low: Price,
+        high: Price,
+    },
+    /// `is_sell` for a new high on negative delta
+    DeltaDivergence {
+        is_sell: bool,
+    },
+    UnfinishedAuction {
+        at_high: bool,
+        price: Price,
+    },
+}
+
+impl FlowSignal {
+    /// Whether the pattern leans bearish, picks the sound played for it
+    pub fn is_sell(&self) -> bool {
+        match *self {
+            FlowSignal::StackedImbalance { is_sell, .. }
+            | FlowSignal::DeltaDivergence { is_sell } => is_sell,
+            FlowSignal::UnfinishedAuction { at_high, .. } => !at_high,
+        }
+    }
+
+    pub fn describe(&self, precision: MinTicksize) -> String {
+        match *self {
+            FlowSignal::StackedImbalance {
+                is_sell,
+                levels,
+                low,
+                high,
+            } => format!(
+                "{levels} stacked {} imbalances, {} - {}",
+                if is_sell { "sell" } else { "buy" },
+                low.to_string(precision),
+                high.to_string(precision),
+            ),
+            FlowSignal::DeltaDivergence { is_sell: true } => {
+                "Delta divergence: new high on negative delta".to_string()
+            }
+            FlowSignal::DeltaDivergence { is_sell: false } => {
+                "Delta divergence: new low on positive delta".to_string()
+            }
+            FlowSignal::UnfinishedAuction { at_high, price } => format!(
+                "Unfinished auction at the {} {}",
+                if at_high { "high" } else { "low" },
+                price.to_string(precision),
+            ),
         }
     }
 }
@@ -497,6 +1049,15 @@ impl Default for PointOfControl {
     }
 }
 
+/// An extreme of the candle where both sides still traded, price tends to come back to
+/// finish the auction there
+#[derive(Debug, Clone, Copy)]
+pub struct UnfinishedAuction {
+    pub price: Price,
+    pub at_high: bool,
+    pub status: NPoc,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum NPoc {
     #[default]
@@ -0,0 +1,191 @@
+//! Estimated liquidation levels of the positions opened while a heatmap was recording.
+//!
+//! Liquidations aren't streamed, so they're inferred: every rise in open interest is taken
+//! as new positions opened at the volume weighted price of the trades in between, split
+//! into longs and shorts by the aggressor volume and spread evenly across a few leverage
+//! tiers. A drop closes the same share of every estimated position, and a level goes away
+//! once price trades through it.
+
+use super::heatmap::{HeatmapDataPoint, HeatmapStudy};
+use exchange::unit::price::{Price, PriceStep};
+use exchange::{OpenInterest, UnixMs};
+
+use std::collections::BTreeMap;
+
+/// Leverage positions are assumed to be opened with, up to the study's maximum
+pub const LEVERAGE_TIERS: [u16; 5] = [5, 10, 25, 50, 100];
+/// Share of the notional a position has to keep as margin before it's liquidated
+const MAINTENANCE_MARGIN: f64 = 0.005;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiquidationLevel {
+    /// When the first of the positions behind it were opened
+    pub since: UnixMs,
+    /// Open interest estimated to be liquidated at the level
+    pub size: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LiquidationMap {
+    max_leverage: u16,
+    /// Last open interest reading applied
+    last_oi: Option<OpenInterest>,
+    /// Below the price, where longs get liquidated
+    pub longs: BTreeMap<Price, LiquidationLevel>,
+    /// Above the price, where shorts get liquidated
+    pub shorts: BTreeMap<Price, LiquidationLevel>,
+}
+
+impl LiquidationMap {
+    pub fn new(max_leverage: u16) -> Self {
+        Self {
+            max_leverage,
+            last_oi: None,
+            longs: BTreeMap::new(),
+            shorts: BTreeMap::new(),
+        }
+    }
+
+    /// Creates, resets or drops `current` so it matches the enabled studies, returns
+    /// whether it was started over
+    pub fn sync(current: &mut Option<Self>, studies: &[HeatmapStudy]) -> bool {
+        match studies.iter().find_map(HeatmapStudy::max_leverage) {
+            Some(max) if current.as_ref().is_some_and(|map| map.max_leverage == max) => false,
+            Some(max) => {
+                *current = Some(Self::new(max));
+                true
+            }
+            None => {
+                *current = None;
+                false
+            }
+        }
+    }
+
+    /// Time of the last open interest reading applied
+    pub fn last_oi_time(&self) -> Option<UnixMs> {
+        self.last_oi.map(|oi| oi.time)
+    }
+
+    pub fn max_size(&self) -> f64 {
+        self.longs
+            .values()
+            .chain(self.shorts.values())
+            .map(|level| level.size)
+            .fold(0.0, f64::max)
+    }
+
+    /// Applies the readings newer than the last one, pricing each change by the trades
+    /// recorded since the reading before it
+    pub fn insert_open_interest(
+        &mut self,
+        readings: &[OpenInterest],
+        datapoints: &BTreeMap<UnixMs, HeatmapDataPoint>,
+        step: PriceStep,
+    ) {
+        let mut readings = readings
+            .iter()
+            .filter(|oi| self.last_oi.is_none_or(|last| oi.time > last.time))
+            .copied()
+            .collect::<Vec<_>>();
+        readings.sort_by_key(|oi| oi.time);
+
+        for oi in readings {
+            if let Some(prev) = self.last_oi {
+                self.apply_change(prev, oi, datapoints, step);
+            }
+            self.last_oi = Some(oi);
+        }
+    }
+
+    fn apply_change(
+        &mut self,
+        prev: OpenInterest,
+        oi: OpenInterest,
+        datapoints: &BTreeMap<UnixMs, HeatmapDataPoint>,
+        step: PriceStep,
+    ) {
+        let change = oi.value - prev.value;
+
+        if change < 0.0 {
+            let kept = if prev.value > 0.0 {
+                (oi.value / prev.value).max(0.0)
+            } else {
+                0.0
+            };
+            for level in self.longs.values_mut().chain(self.shorts.values_mut()) {
+                level.size *= kept;
+            }
+            return;
+        }
+
+        let (mut notional, mut volume, mut buys) = (0.0, 0.0, 0.0);
+        for (_, dp) in datapoints.range(prev.time..oi.time) {
+            for trade in &dp.grouped_trades {
+                let qty = trade.qty.to_f64();
+                notional += trade.price.to_f64() * qty;
+                volume += qty;
+                if !trade.is_sell {
+                    buys += qty;
+                }
+            }
+        }
+        if change == 0.0 || volume <= 0.0 {
+            return;
+        }
+
+        let vwap = notional / volume;
+        let buy_share = buys / volume;
+
+        let tiers = LEVERAGE_TIERS
+            .iter()
+            .filter(|leverage| **leverage <= self.max_leverage.max(LEVERAGE_TIERS[0]))
+            .collect::<Vec<_>>();
+        let per_tier = change / tiers.len() as f64;
+
+        for &&leverage in &tiers {
+            let distance = 1.0 / f64::from(leverage) - MAINTENANCE_MARGIN;
+
+            let long = Price::from_f64(vwap * (1.0 - distance)).round_to_step(step);
+            let short = Price::from_f64(vwap * (1.0 + distance)).round_to_step(step);
+
+            add_level(&mut self.longs, long, prev.time, per_tier * buy_share);
+            add_level(
+                &mut self.shorts,
+                short,
+                prev.time,
+                per_tier * (1.0 - buy_share),
+            );
+        }
+
+        // The trades since may have run through the new levels already
+        let traded = datapoints
+            .range(oi.time..)
+            .flat_map(|(_, dp)| dp.grouped_trades.iter().map(|trade| trade.price));
+        if let (Some(low), Some(high)) = (traded.clone().min(), traded.max()) {
+            self.sweep(low, high);
+        }
+    }
+
+    /// Drops the levels price traded through, between `low` and `high`
+    pub fn sweep(&mut self, low: Price, high: Price) {
+        self.longs.retain(|price, _| *price < low);
+        self.shorts.retain(|price, _| *price > high);
+    }
+}
+
+fn add_level(
+    levels: &mut BTreeMap<Price, LiquidationLevel>,
+    price: Price,
+    since: UnixMs,
+    size: f64,
+) {
+    if size <= 0.0 {
+        return;
+    }
+
+    levels
+        .entry(price)
+        .and_modify(|level| level.size += size)
+        .or_insert(LiquidationLevel { since, size });
+}
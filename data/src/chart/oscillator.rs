@@ -0,0 +1,373 @@
+//! Classic momentum oscillators drawn under kline charts, and the averages and volatility
+//! measures that indicators build on.
+//!
+//! Every function takes its inputs oldest first and returns one value per input, `None`
+//! until enough history has been seen to compute it.
+
+use serde::{Deserialize, Serialize};
+
+/// Periods of the oscillators, in bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OscillatorConfig {
+    pub rsi_period: u16,
+    pub macd_fast: u16,
+    pub macd_slow: u16,
+    pub macd_signal: u16,
+    pub stoch_k: u16,
+    /// Moving average applied to the raw %K, 1 for the fast stochastic
+    pub stoch_smoothing: u16,
+    pub stoch_d: u16,
+    pub cci_period: u16,
+}
+
+impl Default for OscillatorConfig {
+    fn default() -> Self {
+        Self {
+            rsi_period: 14,
+            macd_fast: 12,
+            macd_slow: 26,
+            macd_signal: 9,
+            stoch_k: 14,
+            stoch_smoothing: 3,
+            stoch_d: 3,
+            cci_period: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Macd {
+    pub macd: f64,
+    pub signal: Option<f64>,
+}
+
+impl Macd {
+    pub fn histogram(&self) -> Option<f64> {
+        self.signal.map(|signal| self.macd - signal)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stochastic {
+    pub k: f64,
+    pub d: Option<f64>,
+}
+
+/// Relative strength index with Wilder's smoothing
+pub fn rsi(closes: &[f64], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+    let mut out = vec![None; closes.len()];
+
+    let (mut avg_gain, mut avg_loss) = (0.0, 0.0);
+
+    for i in 1..closes.len() {
+        let change = closes[i] - closes[i - 1];
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+
+        if i <= period {
+            avg_gain += gain / period as f64;
+            avg_loss += loss / period as f64;
+            if i < period {
+                continue;
+            }
+        } else {
+            avg_gain = (avg_gain * (period - 1) as f64 + gain) / period as f64;
+            avg_loss = (avg_loss * (period - 1) as f64 + loss) / period as f64;
+        }
+
+        out[i] = Some(if avg_loss == 0.0 {
+            if avg_gain == 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+        });
+    }
+
+    out
+}
+
+pub fn macd(closes: &[f64], fast: u16, slow: u16, signal: u16) -> Vec<Option<Macd>> {
+    let closes = closes.iter().copied().map(Some).collect::<Vec<_>>();
+    let fast = ema(&closes, fast);
+    let slow = ema(&closes, slow);
+
+    let line = fast
+        .iter()
+        .zip(&slow)
+        .map(|(fast, slow)| Some((*fast)? - (*slow)?))
+        .collect::<Vec<_>>();
+    let signal = ema(&line, signal);
+
+    line.iter()
+        .zip(signal)
+        .map(|(macd, signal)| macd.map(|macd| Macd { macd, signal }))
+        .collect()
+}
+
+pub fn stochastic(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    k_period: u16,
+    smoothing: u16,
+    d_period: u16,
+) -> Vec<Option<Stochastic>> {
+    let k_period = usize::from(k_period.max(1));
+
+    let raw_k = (0..closes.len())
+        .map(|i| {
+            let start = (i + 1).checked_sub(k_period)?;
+            let highest = highs[start..=i].iter().copied().fold(f64::MIN, f64::max);
+            let lowest = lows[start..=i].iter().copied().fold(f64::MAX, f64::min);
+
+            Some(if highest > lowest {
+                100.0 * (closes[i] - lowest) / (highest - lowest)
+            } else {
+                50.0
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let k = sma(&raw_k, smoothing);
+    let d = sma(&k, d_period);
+
+    k.into_iter()
+        .zip(d)
+        .map(|(k, d)| k.map(|k| Stochastic { k, d }))
+        .collect()
+}
+
+/// Commodity channel index over the typical price `(high + low + close) / 3`
+pub fn cci(highs: &[f64], lows: &[f64], closes: &[f64], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+
+    let typical = (0..closes.len())
+        .map(|i| (highs[i] + lows[i] + closes[i]) / 3.0)
+        .collect::<Vec<_>>();
+
+    (0..typical.len())
+        .map(|i| {
+            let window = &typical[(i + 1).checked_sub(period)?..=i];
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let deviation = window.iter().map(|tp| (tp - mean).abs()).sum::<f64>() / period as f64;
+
+            Some(if deviation > 0.0 {
+                (typical[i] - mean) / (0.015 * deviation)
+            } else {
+                0.0
+            })
+        })
+        .collect()
+}
+
+/// Exponential moving average seeded with the simple average of the first `period` values.
+/// Leading `None`s are skipped, a gap after the seed restarts it
+pub(crate) fn ema(values: &[Option<f64>], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+    let alpha = 2.0 / (period as f64 + 1.0);
+
+    let mut out = vec![None; values.len()];
+    let mut seed = Vec::with_capacity(period);
+    let mut current: Option<f64> = None;
+
+    for (i, value) in values.iter().enumerate() {
+        let Some(value) = *value else {
+            seed.clear();
+            current = None;
+            continue;
+        };
+
+        current = match current {
+            Some(prev) => Some(prev + alpha * (value - prev)),
+            None => {
+                seed.push(value);
+                (seed.len() == period).then(|| seed.iter().sum::<f64>() / period as f64)
+            }
+        };
+        out[i] = current;
+    }
+
+    out
+}
+
+/// Simple moving average, `None` wherever the window isn't complete
+pub(crate) fn sma(values: &[Option<f64>], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+
+    (0..values.len())
+        .map(|i| {
+            let window = &values[(i + 1).checked_sub(period)?..=i];
+            let sum = window
+                .iter()
+                .try_fold(0.0, |sum, value| Some(sum + (*value)?))?;
+            Some(sum / period as f64)
+        })
+        .collect()
+}
+
+/// Linearly weighted moving average, the latest value weighing `period` times the oldest
+pub(crate) fn wma(values: &[Option<f64>], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+    let total_weight = (period * (period + 1) / 2) as f64;
+
+    (0..values.len())
+        .map(|i| {
+            let window = &values[(i + 1).checked_sub(period)?..=i];
+            let sum = window
+                .iter()
+                .zip(1..)
+                .try_fold(0.0, |sum, (value, weight)| {
+                    Some(sum + (*value)? * f64::from(weight))
+                })?;
+            Some(sum / total_weight)
+        })
+        .collect()
+}
+
+/// Population standard deviation of the last `period` values
+pub(crate) fn std_dev(values: &[f64], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+
+    (0..values.len())
+        .map(|i| {
+            let window = &values[(i + 1).checked_sub(period)?..=i];
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / period as f64;
+            Some(variance.sqrt())
+        })
+        .collect()
+}
+
+/// Average true range with Wilder's smoothing
+pub(crate) fn atr(highs: &[f64], lows: &[f64], closes: &[f64], period: u16) -> Vec<Option<f64>> {
+    let period = usize::from(period.max(1));
+    let mut out = vec![None; closes.len()];
+    let mut current = 0.0;
+
+    for i in 0..closes.len() {
+        let range = highs[i] - lows[i];
+        let true_range = match i.checked_sub(1).map(|prev| closes[prev]) {
+            Some(prev_close) => range
+                .max((highs[i] - prev_close).abs())
+                .max((lows[i] - prev_close).abs()),
+            None => range,
+        };
+
+        if i < period {
+            current += true_range / period as f64;
+            if i + 1 < period {
+                continue;
+            }
+        } else {
+            current = (current * (period - 1) as f64 + true_range) / period as f64;
+        }
+        out[i] = Some(current);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[Option<f64>], expected: &[Option<f64>]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            match (a, e) {
+                (Some(a), Some(e)) => assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}"),
+                _ => assert_eq!(a, e, "{actual:?} != {expected:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn rsi_uses_wilder_smoothing() {
+        assert_close(
+            &rsi(&[1.0, 2.0, 1.0, 2.0], 2),
+            &[None, None, Some(50.0), Some(75.0)],
+        );
+    }
+
+    #[test]
+    fn rsi_saturates_without_losses() {
+        assert_close(
+            &rsi(&[1.0, 2.0, 3.0, 3.0], 2),
+            &[None, None, Some(100.0), Some(100.0)],
+        );
+        assert_close(&rsi(&[5.0; 3], 2), &[None, None, Some(50.0)]);
+    }
+
+    #[test]
+    fn ema_is_seeded_with_the_simple_average() {
+        let values = [1.0, 2.0, 3.0, 4.0].map(Some);
+        assert_close(&ema(&values, 2), &[None, Some(1.5), Some(2.5), Some(3.5)]);
+    }
+
+    #[test]
+    fn averages_restart_after_a_gap() {
+        let values = [Some(1.0), Some(3.0), None, Some(5.0), Some(7.0)];
+
+        assert_close(&ema(&values, 2), &[None, Some(2.0), None, None, Some(6.0)]);
+        assert_close(&sma(&values, 2), &[None, Some(2.0), None, None, Some(6.0)]);
+    }
+
+    #[test]
+    fn macd_of_a_flat_series_is_zero() {
+        let out = macd(&[10.0; 6], 2, 3, 2);
+
+        assert_eq!(out[..2], [None, None]);
+        assert_eq!(
+            out[2],
+            Some(Macd {
+                macd: 0.0,
+                signal: None
+            })
+        );
+        for point in &out[3..] {
+            assert_eq!(point.and_then(|point| point.histogram()), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn stochastic_places_the_close_within_the_range() {
+        let highs = [3.0, 4.0, 5.0, 5.0];
+        let lows = [1.0, 2.0, 3.0, 3.0];
+        let closes = [2.0, 3.0, 5.0, 3.0];
+
+        let k = stochastic(&highs, &lows, &closes, 3, 1, 2)
+            .iter()
+            .map(|point| point.map(|point| point.k))
+            .collect::<Vec<_>>();
+        assert_close(&k, &[None, None, Some(100.0), Some(1.0 / 3.0 * 100.0)]);
+
+        let d = stochastic(&highs, &lows, &closes, 3, 1, 2)[3].and_then(|point| point.d);
+        assert_close(&[d], &[Some((100.0 + 100.0 / 3.0) / 2.0)]);
+    }
+
+    #[test]
+    fn cci_scales_by_the_mean_deviation() {
+        let closes = [1.0, 2.0, 3.0];
+        assert_close(
+            &cci(&closes, &closes, &closes, 3),
+            &[None, None, Some(100.0)],
+        );
+        assert_close(
+            &cci(&[2.0; 3], &[2.0; 3], &[2.0; 3], 3),
+            &[None, None, Some(0.0)],
+        );
+    }
+
+    #[test]
+    fn atr_includes_gaps_from_the_previous_close() {
+        let highs = [2.0, 3.0, 6.0];
+        let lows = [1.0, 2.0, 5.0];
+        let closes = [2.0, 3.0, 5.0];
+
+        // True ranges are 1, 1 and 3, the last one measured from the previous close
+        assert_close(
+            &atr(&highs, &lows, &closes, 2),
+            &[None, Some(1.0), Some(2.0)],
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! User written indicators, Rhai scripts kept in the `indicators` folder of the data
+//! directory and run over the chart's candles whenever they change.
+//!
+//! A script sees the candles as arrays, oldest first: `time` (open time in ms), `open`,
+//! `high`, `low`, `close`, `volume`, and `buy_volume` / `sell_volume` from the candles'
+//! trades, `()` where the side isn't known. `bars` holds their length.
+//!
+//! Helpers return one value per input, `()` until enough bars have been seen:
+//! `sma`, `ema`, `wma`, `stdev` and `rsi` take `(values, period)`, `atr` takes
+//! `(high, low, close, period)`.
+//!
+//! What the script draws over the candles, in price units:
+//! - `plot(name, values)` / `plot(name, values, "#rrggbb")`, a line with a gap at each `()`
+//! - `hline(price)` / `hline(price, "#rrggbb")`, a level across the chart
+//! - `marker(bar, price, label)` / `marker(bar, price, label, "#rrggbb")`, a labeled dot on a
+//!   bar, counted from the oldest
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use exchange::Kline;
+use iced_core::Color;
+use rhai::{AST, Array, Dynamic, Engine, Scope};
+
+use super::kline::{FootprintSummary, KlineTrades};
+use super::oscillator;
+use crate::config::theme::hex_to_color;
+
+const SCRIPTS_DIR: &str = "indicators";
+const SCRIPT_EXTENSION: &str = "rhai";
+/// Keeps a runaway loop from freezing the chart
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum ScriptError {
+    #[error("Couldn't read the script: {0}")]
+    Io(String),
+    #[error("Compile error: {0}")]
+    Compile(String),
+    #[error("Runtime error: {0}")]
+    Runtime(String),
+}
+
+pub fn scripts_dir() -> PathBuf {
+    crate::data_path(Some(SCRIPTS_DIR))
+}
+
+/// Names of the scripts in the scripts folder, sorted
+pub fn available() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(scripts_dir()) else {
+        return vec![];
+    };
+
+    let mut names = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect::<Vec<_>>();
+
+    names.sort();
+    names
+}
+
+/// Opens the scripts folder in the file manager, creating it first if needed
+pub fn open_scripts_dir() -> Result<(), crate::InternalError> {
+    let dir = scripts_dir();
+    std::fs::create_dir_all(&dir).map_err(|err| crate::InternalError::Layout(err.to_string()))?;
+
+    crate::open_url(&dir.to_string_lossy())
+}
+
+/// Candle columns handed to a script, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct Bars {
+    time: Vec<u64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    buy_volume: Vec<Option<f64>>,
+    sell_volume: Vec<Option<f64>>,
+}
+
+impl Bars {
+    pub fn push(&mut self, kline: &Kline, footprint: &KlineTrades) {
+        let (buy, sell) = match (kline.volume.buy_qty(), kline.volume.sell_qty()) {
+            (Some(buy), Some(sell)) => (Some(buy.to_f64()), Some(sell.to_f64())),
+            _ => FootprintSummary::from_trades(footprint)
+                .map(|summary| (summary.buy.to_f64(), summary.sell.to_f64()))
+                .unzip(),
+        };
+
+        self.time.push(kline.time.as_u64());
+        self.open.push(kline.open.to_f64());
+        self.high.push(kline.high.to_f64());
+        self.low.push(kline.low.to_f64());
+        self.close.push(kline.close.to_f64());
+        self.volume.push(kline.volume.total().to_f64());
+        self.buy_volume.push(buy);
+        self.sell_volume.push(sell);
+    }
+
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    fn into_scope(self) -> Scope<'static> {
+        let floats = |values: Vec<f64>| to_array(values.into_iter().map(Some));
+
+        let mut scope = Scope::new();
+        scope.push_constant("bars", self.len() as i64);
+        scope.push_constant(
+            "time",
+            self.time
+                .into_iter()
+                .map(|time| Dynamic::from(time as i64))
+                .collect::<Array>(),
+        );
+        scope.push_constant("open", floats(self.open));
+        scope.push_constant("high", floats(self.high));
+        scope.push_constant("low", floats(self.low));
+        scope.push_constant("close", floats(self.close));
+        scope.push_constant("volume", floats(self.volume));
+        scope.push_constant("buy_volume", to_array(self.buy_volume));
+        scope.push_constant("sell_volume", to_array(self.sell_volume));
+        scope
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptLine {
+    pub name: String,
+    pub color: Option<Color>,
+    /// One value per bar, oldest first
+    pub values: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptLevel {
+    pub price: f64,
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptMarker {
+    /// Index of the bar, counted from the oldest
+    pub bar: usize,
+    pub price: f64,
+    pub label: String,
+    pub color: Option<Color>,
+}
+
+/// Everything a script run asked to draw
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOutput {
+    pub lines: Vec<ScriptLine>,
+    pub levels: Vec<ScriptLevel>,
+    pub markers: Vec<ScriptMarker>,
+}
+
+pub struct Script {
+    name: String,
+    ast: AST,
+}
+
+impl Script {
+    /// Reads and compiles the script saved under `name`
+    pub fn load(name: &str) -> Result<Self, ScriptError> {
+        let path = scripts_dir().join(format!("{name}.{SCRIPT_EXTENSION}"));
+        let source =
+            std::fs::read_to_string(&path).map_err(|err| ScriptError::Io(err.to_string()))?;
+
+        let ast = Engine::new()
+            .compile(&source)
+            .map_err(|err| ScriptError::Compile(err.to_string()))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            ast,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn run(&self, bars: Bars) -> Result<ScriptOutput, ScriptError> {
+        let output = Rc::new(RefCell::new(ScriptOutput::default()));
+        let engine = engine(&output, &self.name);
+
+        engine
+            .run_ast_with_scope(&mut bars.into_scope(), &self.ast)
+            .map_err(|err| ScriptError::Runtime(err.to_string()))?;
+        drop(engine);
+
+        Ok(output.take())
+    }
+}
+
+/// Engine with the helpers and drawing calls registered, the latter writing to `output`
+fn engine(output: &Rc<RefCell<ScriptOutput>>, name: &str) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    let script = name.to_string();
+    engine.on_print(move |message| log::info!("[{script}] {message}"));
+
+    engine
+        .register_fn("sma", |values: Array, period: i64| {
+            to_array(oscillator::sma(&from_array(&values), to_period(period)))
+        })
+        .register_fn("ema", |values: Array, period: i64| {
+            to_array(oscillator::ema(&from_array(&values), to_period(period)))
+        })
+        .register_fn("wma", |values: Array, period: i64| {
+            to_array(oscillator::wma(&from_array(&values), to_period(period)))
+        })
+        .register_fn("stdev", |values: Array, period: i64| {
+            to_array(oscillator::std_dev(&dense(&values), to_period(period)))
+        })
+        .register_fn("rsi", |values: Array, period: i64| {
+            to_array(oscillator::rsi(&dense(&values), to_period(period)))
+        })
+        .register_fn(
+            "atr",
+            |high: Array, low: Array, close: Array, period: i64| {
+                to_array(oscillator::atr(
+                    &dense(&high),
+                    &dense(&low),
+                    &dense(&close),
+                    to_period(period),
+                ))
+            },
+        );
+
+    let out = Rc::clone(output);
+    engine.register_fn("plot", move |name: &str, values: Array| {
+        push_line(&out, name, &values, None);
+    });
+    let out = Rc::clone(output);
+    engine.register_fn("plot", move |name: &str, values: Array, color: &str| {
+        push_line(&out, name, &values, hex_to_color(color));
+    });
+
+    let out = Rc::clone(output);
+    engine.register_fn("hline", move |price: f64| {
+        out.borrow_mut()
+            .levels
+            .push(ScriptLevel { price, color: None });
+    });
+    let out = Rc::clone(output);
+    engine.register_fn("hline", move |price: f64, color: &str| {
+        out.borrow_mut().levels.push(ScriptLevel {
+            price,
+            color: hex_to_color(color),
+        });
+    });
+
+    let out = Rc::clone(output);
+    engine.register_fn("marker", move |bar: i64, price: f64, label: &str| {
+        push_marker(&out, bar, price, label, None);
+    });
+    let out = Rc::clone(output);
+    engine.register_fn(
+        "marker",
+        move |bar: i64, price: f64, label: &str, color: &str| {
+            push_marker(&out, bar, price, label, hex_to_color(color));
+        },
+    );
+
+    engine
+}
+
+fn push_line(output: &RefCell<ScriptOutput>, name: &str, values: &Array, color: Option<Color>) {
+    output.borrow_mut().lines.push(ScriptLine {
+        name: name.to_string(),
+        color,
+        values: from_array(values),
+    });
+}
+
+/// Markers on a negative bar index are dropped
+fn push_marker(
+    output: &RefCell<ScriptOutput>,
+    bar: i64,
+    price: f64,
+    label: &str,
+    color: Option<Color>,
+) {
+    if let Ok(bar) = usize::try_from(bar) {
+        output.borrow_mut().markers.push(ScriptMarker {
+            bar,
+            price,
+            label: label.to_string(),
+            color,
+        });
+    }
+}
+
+fn to_period(period: i64) -> u16 {
+    period.clamp(1, i64::from(u16::MAX)) as u16
+}
+
+/// Numbers as floats, anything else as a gap
+fn from_array(values: &Array) -> Vec<Option<f64>> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .as_float()
+                .ok()
+                .or_else(|| value.as_int().ok().map(|int| int as f64))
+        })
+        .collect()
+}
+
+/// For helpers that can't skip gaps, they're carried over from the previous value
+fn dense(values: &Array) -> Vec<f64> {
+    let mut last = 0.0;
+    from_array(values)
+        .into_iter()
+        .map(|value| {
+            last = value.unwrap_or(last);
+            last
+        })
+        .collect()
+}
+
+fn to_array(values: impl IntoIterator<Item = Option<f64>>) -> Array {
+    values
+        .into_iter()
+        .map(|value| value.map_or(Dynamic::UNIT, Dynamic::from_float))
+        .collect()
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Config {
+    #[serde(default)]
+    pub mode: SpreadMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SpreadMode {
+    /// Leg A minus leg B, e.g. perp–spot basis
+    #[default]
+    Difference,
+    /// Leg A divided by leg B, e.g. BTC/ETH
+    Ratio,
+}
+
+impl SpreadMode {
+    pub const ALL: [SpreadMode; 2] = [SpreadMode::Difference, SpreadMode::Ratio];
+
+    pub fn apply(self, a: f32, b: f32) -> Option<f32> {
+        match self {
+            SpreadMode::Difference => Some(a - b),
+            SpreadMode::Ratio => (b != 0.0).then(|| a / b),
+        }
+    }
+
+    pub fn operator(self) -> &'static str {
+        match self {
+            SpreadMode::Difference => "-",
+            SpreadMode::Ratio => "/",
+        }
+    }
+}
+
+impl std::fmt::Display for SpreadMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpreadMode::Difference => write!(f, "Difference"),
+            SpreadMode::Ratio => write!(f, "Ratio"),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Volume weighted average price of the trade stream, restarted at the start of every
+//! session.
+
+use std::fmt::{self, Display};
+
+use exchange::{Trade, UnixMs};
+use serde::{Deserialize, Serialize};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const WEEK_MS: u64 = 7 * DAY_MS;
+/// The epoch fell on a Thursday, weeks start on Monday
+const WEEK_OFFSET_MS: u64 = 4 * DAY_MS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum VwapAnchor {
+    /// Restarts at 00:00 UTC
+    Daily,
+    /// Restarts on Monday 00:00 UTC
+    Weekly,
+    /// A single session from the given time on
+    Anchored(UnixMs),
+}
+
+impl VwapAnchor {
+    /// Start of the session `time` falls in, `None` before an anchor
+    pub fn session_start(self, time: UnixMs) -> Option<UnixMs> {
+        let ms = time.as_u64();
+
+        match self {
+            VwapAnchor::Daily => Some(UnixMs::new(ms - ms % DAY_MS)),
+            VwapAnchor::Weekly => {
+                let since_monday = (ms + WEEK_MS - WEEK_OFFSET_MS) % WEEK_MS;
+                Some(UnixMs::new(ms - since_monday))
+            }
+            VwapAnchor::Anchored(anchor) => (time >= anchor).then_some(anchor),
+        }
+    }
+
+    pub fn is_anchored(self) -> bool {
+        matches!(self, VwapAnchor::Anchored(_))
+    }
+}
+
+impl Display for VwapAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VwapAnchor::Daily => write!(f, "Daily"),
+            VwapAnchor::Weekly => write!(f, "Weekly"),
+            VwapAnchor::Anchored(_) => write!(f, "Anchored"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VwapConfig {
+    pub anchor: VwapAnchor,
+    /// Draw the ±1σ and ±2σ bands around the average
+    pub bands: bool,
+}
+
+impl Default for VwapConfig {
+    fn default() -> Self {
+        Self {
+            anchor: VwapAnchor::Daily,
+            bands: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VwapPoint {
+    pub session: UnixMs,
+    pub vwap: f64,
+    /// Volume weighted standard deviation of the session's prices around `vwap`
+    pub std_dev: f64,
+}
+
+impl VwapPoint {
+    /// Upper and lower band `multiple` deviations away from the average
+    pub fn band(&self, multiple: f64) -> (f64, f64) {
+        let offset = self.std_dev * multiple;
+        (self.vwap + offset, self.vwap - offset)
+    }
+}
+
+/// Running sums of the current session, fed trades oldest first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VwapAccumulator {
+    session: Option<UnixMs>,
+    volume: f64,
+    price_volume: f64,
+    price_sq_volume: f64,
+}
+
+impl VwapAccumulator {
+    /// Adds the trade and returns the session's average so far. Trades before an anchor,
+    /// or from a session that already ended, are skipped
+    pub fn add(&mut self, trade: &Trade, anchor: VwapAnchor) -> Option<VwapPoint> {
+        let session = anchor.session_start(trade.time)?;
+
+        match self.session {
+            Some(current) if session < current => return None,
+            Some(current) if session == current => {}
+            _ => {
+                *self = Self {
+                    session: Some(session),
+                    ..Self::default()
+                };
+            }
+        }
+
+        let (price, qty) = (trade.price.to_f64(), trade.qty.to_f64());
+        self.volume += qty;
+        self.price_volume += price * qty;
+        self.price_sq_volume += price * price * qty;
+
+        if self.volume <= 0.0 {
+            return None;
+        }
+
+        let vwap = self.price_volume / self.volume;
+        let variance = (self.price_sq_volume / self.volume - vwap * vwap).max(0.0);
+
+        Some(VwapPoint {
+            session,
+            vwap,
+            std_dev: variance.sqrt(),
+        })
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+pub mod accent;
+pub mod backup;
 pub mod proxy;
+pub mod session;
 pub mod sidebar;
 pub mod state;
 pub mod theme;
@@ -0,0 +1,56 @@
+use exchange::{Ticker, adapter::Exchange};
+
+use iced_core::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What an accent color is assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccentTarget {
+    Exchange(Exchange),
+    Ticker(Ticker),
+}
+
+/// User picked colors tinting the pane chrome, so panes of the same symbol on
+/// different venues can be told apart at a glance
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Accents {
+    pub exchanges: HashMap<Exchange, Color>,
+    pub tickers: HashMap<Ticker, Color>,
+}
+
+impl Accents {
+    /// The ticker's own accent, falling back to its exchange's
+    pub fn resolve(&self, ticker: &Ticker) -> Option<Color> {
+        self.tickers
+            .get(ticker)
+            .or_else(|| self.exchanges.get(&ticker.exchange))
+            .copied()
+    }
+
+    pub fn get(&self, target: AccentTarget) -> Option<Color> {
+        match target {
+            AccentTarget::Exchange(exchange) => self.exchanges.get(&exchange).copied(),
+            AccentTarget::Ticker(ticker) => self.tickers.get(&ticker).copied(),
+        }
+    }
+
+    /// Assigns or, with `None`, clears the accent of `target`
+    pub fn set(&mut self, target: AccentTarget, color: Option<Color>) {
+        match (target, color) {
+            (AccentTarget::Exchange(exchange), Some(color)) => {
+                self.exchanges.insert(exchange, color);
+            }
+            (AccentTarget::Exchange(exchange), None) => {
+                self.exchanges.remove(&exchange);
+            }
+            (AccentTarget::Ticker(ticker), Some(color)) => {
+                self.tickers.insert(ticker, color);
+            }
+            (AccentTarget::Ticker(ticker), None) => {
+                self.tickers.remove(&ticker);
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::SAVED_STATE_PATH;
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const BACKUP_PREFIX: &str = "saved-state-";
+const BACKUP_EXT: &str = ".json";
+
+/// Written next to the saved state before a backup replaces it
+const PRE_RESTORE_PATH: &str = "saved-state_before-restore.json";
+
+/// Daily copies of the saved state to a second location, e.g. a synced folder.
+///
+/// Separate from the `_old` copy made when the saved state fails to parse, these are
+/// taken on schedule and rotated, keeping one per day.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub enabled: bool,
+    pub dir: Option<PathBuf>,
+    /// Daily backups kept, older ones are deleted
+    pub keep: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            keep: 7,
+        }
+    }
+}
+
+/// A backup found in the backup directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// `YYYY-MM-DD`, local time
+    pub date: String,
+}
+
+impl std::fmt::Display for Backup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.date)
+    }
+}
+
+impl BackupConfig {
+    fn active_dir(&self) -> Option<&Path> {
+        self.dir
+            .as_deref()
+            .filter(|dir| self.enabled && !dir.as_os_str().is_empty())
+    }
+
+    fn todays_path(dir: &Path) -> PathBuf {
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        dir.join(format!("{BACKUP_PREFIX}{date}{BACKUP_EXT}"))
+    }
+
+    /// Whether backups are on and today's hasn't been taken yet
+    pub fn is_due(&self) -> bool {
+        self.active_dir()
+            .is_some_and(|dir| !Self::todays_path(dir).exists())
+    }
+
+    /// Copies the saved state to today's backup if it's missing, then drops the oldest
+    /// ones beyond [`BackupConfig::keep`]. Returns the new backup, if one was taken
+    pub fn run_if_due(&self) -> io::Result<Option<PathBuf>> {
+        let Some(dir) = self.active_dir() else {
+            return Ok(None);
+        };
+
+        let target = Self::todays_path(dir);
+        if target.exists() {
+            return Ok(None);
+        }
+
+        let source = crate::data_path(Some(SAVED_STATE_PATH));
+        if !source.exists() {
+            return Ok(None);
+        }
+
+        std::fs::create_dir_all(dir)?;
+        std::fs::copy(&source, &target)?;
+
+        for stale in self.list().into_iter().skip(self.keep.max(1)) {
+            if let Err(err) = std::fs::remove_file(&stale.path) {
+                log::warn!(
+                    "Failed to remove old backup {}: {err}",
+                    stale.path.display()
+                );
+            }
+        }
+
+        Ok(Some(target))
+    }
+
+    /// Backups in the configured directory, newest first
+    pub fn list(&self) -> Vec<Backup> {
+        let Some(dir) = self.dir.as_deref() else {
+            return vec![];
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return vec![];
+        };
+
+        let mut backups = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                let date = path
+                    .file_name()?
+                    .to_str()?
+                    .strip_prefix(BACKUP_PREFIX)?
+                    .strip_suffix(BACKUP_EXT)?
+                    .to_string();
+
+                chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()?;
+                Some(Backup { path, date })
+            })
+            .collect::<Vec<_>>();
+
+        backups.sort_by(|a, b| b.date.cmp(&a.date));
+        backups
+    }
+}
+
+/// Replaces the saved state with `backup`, keeping the replaced one next to it.
+/// Takes effect on the next launch
+pub fn restore(backup: &Backup) -> io::Result<()> {
+    let current = crate::data_path(Some(SAVED_STATE_PATH));
+
+    if current.exists() {
+        std::fs::copy(&current, crate::data_path(Some(PRE_RESTORE_PATH)))?;
+    }
+    std::fs::copy(&backup.path, &current)?;
+
+    log::info!("Restored saved state from {}", backup.path.display());
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::layout::pane::ContentKind;
+use exchange::adapter::StreamKind;
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How much of the market data firehose the app is allowed to pull.
+///
+/// `Remote` is meant for tethered or metered links: only kline streams are
+/// subscribed, REST polling slows down, and panes that live on depth or trades
+/// (heatmaps, footprint, ladder, time&sales) are paused until switched back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SessionProfile {
+    #[default]
+    Full,
+    Remote,
+}
+
+impl SessionProfile {
+    pub const ALL: [SessionProfile; 2] = [SessionProfile::Full, SessionProfile::Remote];
+
+    /// Poll intervals are stretched by this factor in a remote session
+    const REMOTE_POLL_FACTOR: u32 = 4;
+
+    pub fn is_remote(self) -> bool {
+        matches!(self, SessionProfile::Remote)
+    }
+
+    pub fn allows_stream(self, stream: &StreamKind) -> bool {
+        match self {
+            SessionProfile::Full => true,
+            SessionProfile::Remote => matches!(stream, StreamKind::Kline { .. }),
+        }
+    }
+
+    pub fn allows_content(self, kind: ContentKind) -> bool {
+        match self {
+            SessionProfile::Full => true,
+            SessionProfile::Remote => matches!(
+                kind,
+                ContentKind::Starter
+                    | ContentKind::CandlestickChart
+                    | ContentKind::ComparisonChart
+                    | ContentKind::SpreadChart
+            ),
+        }
+    }
+
+    pub fn poll_interval(self, base: Duration) -> Duration {
+        match self {
+            SessionProfile::Full => base,
+            SessionProfile::Remote => base * Self::REMOTE_POLL_FACTOR,
+        }
+    }
+}
+
+impl std::fmt::Display for SessionProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionProfile::Full => write!(f, "Full"),
+            SessionProfile::Remote => write!(f, "Remote (klines only)"),
+        }
+    }
+}
@@ -66,8 +66,14 @@ impl std::fmt::Display for Position {
 #[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub enum Menu {
     Layout,
+    LayoutTemplates,
     Settings,
     Audio,
     ThemeEditor,
     Network,
+    Connections,
+    Alerts,
+    Trading,
+    Account,
+    Journal,
 }
@@ -1,7 +1,19 @@
 use super::ScaleFactor;
+use super::accent::Accents;
+use super::backup::BackupConfig;
+use super::session::SessionProfile;
 use super::sidebar::Sidebar;
 use super::timezone::UserTimezone;
+use crate::alerts::Alerts;
+use crate::broadcast::BroadcastConfig;
+use crate::external::ListenerConfig;
+use crate::history_cache::CacheConfig;
 use crate::layout::WindowSpec;
+use crate::maintenance::MaintenanceConfig;
+use crate::snapshot::SnapshotConfig;
+use crate::tickstore::RetentionPolicy;
+use crate::trading::TradingConfig;
+use crate::trading::journal::JournalConfig;
 use crate::{AudioStream, Layout, Theme};
 
 use serde::{Deserialize, Serialize};
@@ -26,6 +38,18 @@ pub struct State {
     pub trade_fetch_enabled: bool,
     pub size_in_quote_ccy: exchange::SizeUnit,
     pub proxy_cfg: Option<exchange::proxy::Proxy>,
+    pub session_profile: SessionProfile,
+    pub external_events: ListenerConfig,
+    pub market_broadcast: BroadcastConfig,
+    pub accents: Accents,
+    pub state_backup: BackupConfig,
+    pub trade_retention: RetentionPolicy,
+    pub maintenance: MaintenanceConfig,
+    pub history_cache: CacheConfig,
+    pub snapshot: SnapshotConfig,
+    pub alerts: Alerts,
+    pub trading: TradingConfig,
+    pub journal: JournalConfig,
 }
 
 impl State {
@@ -54,6 +78,7 @@ impl State {
             trade_fetch_enabled,
             size_in_quote_ccy: volume_size_unit,
             proxy_cfg,
+            ..State::default()
         }
     }
 }
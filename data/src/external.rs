@@ -0,0 +1,131 @@
+//! Events pushed in by external trading systems, one JSON object per line after a first
+//! line holding the listener's token, e.g.
+//!
+//! ```json
+//! 6f1c2e0a9b5d4c7e8f3a2b1c0d9e8f7a
+//! {"kind":"order_filled","ticker":"BTCUSDT","exchange":"BinanceLinear","side":"buy","price":64210.5,"qty":0.25}
+//! ```
+
+use exchange::adapter::Exchange;
+use exchange::{TickerInfo, UnixMs};
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PORT: u16 = 7431;
+
+/// Annotations kept per chart, oldest are dropped first
+pub const MAX_ANNOTATIONS: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ListenerConfig {
+    pub enabled: bool,
+    /// Bound on loopback only
+    pub port: u16,
+    /// Shared secret a connection has to send first, so that not every local process,
+    /// or web page, can mark the charts
+    pub token: String,
+}
+
+impl Default for ListenerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: uuid::Uuid::new_v4().simple().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    OrderFilled,
+    SignalFired,
+    PositionOpened,
+    PositionClosed,
+    Note,
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventKind::OrderFilled => write!(f, "Order filled"),
+            EventKind::SignalFired => write!(f, "Signal"),
+            EventKind::PositionOpened => write!(f, "Position opened"),
+            EventKind::PositionClosed => write!(f, "Position closed"),
+            EventKind::Note => write!(f, "Note"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalEvent {
+    pub kind: EventKind,
+    /// Exchange symbol, e.g. `BTCUSDT`, matched case-insensitively
+    pub ticker: String,
+    /// Narrows the match down to one exchange, any exchange listing the symbol otherwise
+    #[serde(default)]
+    pub exchange: Option<Exchange>,
+    #[serde(default)]
+    pub side: Option<Side>,
+    #[serde(default)]
+    pub price: Option<f64>,
+    #[serde(default)]
+    pub qty: Option<f64>,
+    /// Unix milliseconds, defaults to the time the event was received
+    #[serde(default)]
+    pub time: Option<u64>,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+impl ExternalEvent {
+    pub fn parse(line: &str, received: UnixMs) -> Result<Self, serde_json::Error> {
+        let mut event: ExternalEvent = serde_json::from_str(line)?;
+        event.time.get_or_insert(received.as_u64());
+        Ok(event)
+    }
+
+    pub fn matches(&self, ticker_info: &TickerInfo) -> bool {
+        self.exchange.is_none_or(|ex| ex == ticker_info.exchange())
+            && ticker_info
+                .ticker
+                .to_string()
+                .eq_ignore_ascii_case(&self.ticker)
+    }
+
+    pub fn time(&self) -> UnixMs {
+        UnixMs::new(self.time.unwrap_or_default())
+    }
+
+    /// Short text for chart markers and toasts, e.g. `Order filled BUY 0.25 @ 64210.5`
+    pub fn summary(&self) -> String {
+        let mut out = self.kind.to_string();
+
+        if let Some(side) = self.side {
+            out.push_str(match side {
+                Side::Buy => " BUY",
+                Side::Sell => " SELL",
+            });
+        }
+        if let Some(qty) = self.qty {
+            out.push_str(&format!(" {qty}"));
+        }
+        if let Some(price) = self.price {
+            out.push_str(&format!(" @ {price}"));
+        }
+        if let Some(text) = &self.text {
+            out.push_str(&format!(": {text}"));
+        }
+
+        out
+    }
+}
@@ -0,0 +1,224 @@
+//! Disk cache for fetched open interest and funding rate history.
+//!
+//! Each series is one JSON file under `market_data/history`, keyed by ticker and, for
+//! open interest, timeframe. Besides the points it keeps the time spans that were
+//! fetched, so a later request only has to fetch what falls outside them, usually the
+//! newest tail.
+
+use exchange::{TickerInfo, Timeframe, UnixMs};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+pub const HISTORY_DIR: &str = "market_data/history";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    OpenInterest(Timeframe),
+    FundingRate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeriesKey {
+    pub ticker_info: TickerInfo,
+    pub kind: SeriesKind,
+}
+
+impl SeriesKey {
+    fn path(&self) -> PathBuf {
+        let file_name = match self.kind {
+            SeriesKind::OpenInterest(timeframe) => format!("oi-{timeframe}.json"),
+            SeriesKind::FundingRate => "funding.json".to_string(),
+        };
+
+        crate::data_path(Some(HISTORY_DIR))
+            .join(format!("{:?}", self.ticker_info.exchange()))
+            .join(self.ticker_info.ticker.to_string())
+            .join(file_name)
+    }
+}
+
+/// A cached series, with the spans its points are complete for
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CachedSeries {
+    /// Disjoint, ascending `[start, end]` spans that were fetched
+    covered: Vec<(u64, u64)>,
+    points: BTreeMap<u64, f64>,
+}
+
+impl CachedSeries {
+    /// Reads the cached series for `key`, empty if there's none or it's unreadable
+    pub fn load(key: &SeriesKey) -> Self {
+        let path = key.path();
+
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|err| {
+                log::warn!("Discarding unreadable cache {}: {err}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, key: &SeriesKey) -> io::Result<()> {
+        let path = key.path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(tmp_path, path)
+    }
+
+    /// Parts of `[from, to]` not covered by the cache, oldest first
+    pub fn missing(&self, from: UnixMs, to: UnixMs) -> Vec<(UnixMs, UnixMs)> {
+        let (mut start, to) = (from.as_u64(), to.as_u64());
+        let mut gaps = vec![];
+
+        for &(covered_start, covered_end) in &self.covered {
+            if start > to {
+                break;
+            }
+            if covered_end < start {
+                continue;
+            }
+            if covered_start > start {
+                gaps.push((start, (covered_start - 1).min(to)));
+            }
+            start = start.max(covered_end.saturating_add(1));
+        }
+
+        if start <= to {
+            gaps.push((start, to));
+        }
+
+        gaps.into_iter()
+            .map(|(start, end)| (UnixMs::new(start), UnixMs::new(end)))
+            .collect()
+    }
+
+    /// Cached points within `[from, to]`, oldest first
+    pub fn points(&self, from: UnixMs, to: UnixMs) -> Vec<(UnixMs, f64)> {
+        self.points
+            .range(from.as_u64()..=to.as_u64())
+            .map(|(time, value)| (UnixMs::new(*time), *value))
+            .collect()
+    }
+
+    /// Adds fetched points, marking the span between the first and last of them as
+    /// covered. Exchanges cap how many points a request returns, so the requested range
+    /// isn't assumed to be complete
+    pub fn insert(&mut self, points: impl IntoIterator<Item = (UnixMs, f64)>) {
+        let mut span: Option<(u64, u64)> = None;
+
+        for (time, value) in points {
+            let time = time.as_u64();
+            self.points.insert(time, value);
+
+            span = Some(span.map_or((time, time), |(start, end)| {
+                (start.min(time), end.max(time))
+            }));
+        }
+
+        if let Some(span) = span {
+            self.add_span(span);
+        }
+    }
+
+    fn add_span(&mut self, span: (u64, u64)) {
+        self.covered.push(span);
+        self.covered.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.covered.len());
+        for (start, end) in self.covered.drain(..) {
+            match merged.last_mut() {
+                Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                    *last_end = (*last_end).max(end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        self.covered = merged;
+    }
+}
+
+/// Total size of the cache on disk, in bytes
+pub fn size_on_disk() -> u64 {
+    let mut size = 0;
+    let mut pending = vec![crate::data_path(Some(HISTORY_DIR))];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            match entry.metadata() {
+                Ok(meta) if meta.is_dir() => pending.push(entry.path()),
+                Ok(meta) => size += meta.len(),
+                Err(_) => {}
+            }
+        }
+    }
+
+    size
+}
+
+pub fn clear() -> io::Result<()> {
+    let dir = crate::data_path(Some(HISTORY_DIR));
+
+    match std::fs::remove_dir_all(&dir) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Removes series that weren't written for `max_age`, i.e. tickers no pane fetched
+/// history for since. Returns the number of removed series
+pub fn evict_stale(max_age: std::time::Duration) -> usize {
+    let Some(cutoff) = std::time::SystemTime::now().checked_sub(max_age) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    let mut pending = vec![crate::data_path(Some(HISTORY_DIR))];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else if meta.modified().is_ok_and(|modified| modified < cutoff) {
+                match std::fs::remove_file(entry.path()) {
+                    Ok(()) => removed += 1,
+                    Err(err) => {
+                        log::warn!("Failed to evict {}: {err}", entry.path().display());
+                    }
+                }
+            }
+        }
+    }
+
+    removed
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 pub mod dashboard;
 pub mod pane;
+pub mod template;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Layout {
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use super::{WindowSpec, pane::Pane};
+use super::{
+    WindowSpec,
+    pane::{LinkGroup, LinkOptions, Pane},
+};
 use crate::util::ok_or_default;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -9,4 +12,7 @@ pub struct Dashboard {
     pub pane: Pane,
     #[serde(deserialize_with = "ok_or_default", default)]
     pub popout: Vec<(Pane, WindowSpec)>,
+    /// Only the groups set to something other than the default
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub link_options: Vec<(LinkGroup, LinkOptions)>,
 }
@@ -1,16 +1,20 @@
 use exchange::{TickMultiplier, TickerInfo, Timeframe};
 use serde::{Deserialize, Serialize};
 
-use crate::chart::{comparison, heatmap, kline};
+use crate::chart::{comparison, heatmap, kline, spread};
 use crate::panel::{ladder, timeandsales};
 use crate::stream::PersistStreamKind;
 use crate::util::ok_or_default;
 
 use crate::chart::{
     Basis, ViewConfig,
+    drawing::{Drawings, Notes, Snap},
     heatmap::HeatmapStudy,
-    indicator::{HeatmapIndicator, KlineIndicator},
-    kline::KlineChartKind,
+    indicator::{
+        HeatmapIndicator, KlineIndicator, KlineIndicatorInstance, MovingAverage, VolatilityBand,
+    },
+    kline::{KlineChartKind, ProfileRange},
+    vwap::VwapConfig,
 };
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
@@ -64,7 +68,7 @@ pub enum Pane {
         #[serde(deserialize_with = "ok_or_default")]
         settings: Settings,
         #[serde(deserialize_with = "ok_or_default", default)]
-        indicators: Vec<KlineIndicator>,
+        indicators: Vec<KlineIndicatorInstance>,
         #[serde(deserialize_with = "ok_or_default", default)]
         link_group: Option<LinkGroup>,
     },
@@ -75,6 +79,13 @@ pub enum Pane {
         #[serde(deserialize_with = "ok_or_default", default)]
         link_group: Option<LinkGroup>,
     },
+    SpreadChart {
+        stream_type: Vec<PersistStreamKind>,
+        #[serde(deserialize_with = "ok_or_default")]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
     TimeAndSales {
         stream_type: Vec<PersistStreamKind>,
         settings: Settings,
@@ -101,6 +112,32 @@ pub struct Settings {
     pub tick_multiply: Option<exchange::TickMultiplier>,
     pub visual_config: Option<VisualConfig>,
     pub selected_basis: Option<Basis>,
+    pub appearance: Appearance,
+    /// Drawn over the candles of kline charts
+    pub moving_averages: Vec<MovingAverage>,
+    /// Drawn over the candles of kline charts, at most one of each kind
+    pub bands: Vec<VolatilityBand>,
+    /// Session VWAP of the trade stream on kline charts, `None` when hidden
+    pub vwap: Option<VwapConfig>,
+    /// Candle ranges of the fixed range volume profiles
+    pub profile_ranges: Vec<ProfileRange>,
+    /// File names of the indicator scripts drawn on kline charts, without extension
+    pub scripts: Vec<String>,
+    /// Drawn over kline and heatmap charts of the ticker they were placed on
+    pub drawings: Drawings,
+    /// Notes and markers, kept per ticker like `drawings`
+    pub notes: Notes,
+    pub drawing_snap: Snap,
+}
+
+/// Cosmetic overrides that help telling similar panes apart
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Appearance {
+    /// Drawn at a low opacity behind the pane content
+    pub tint: Option<iced_core::Color>,
+    /// Shows the ticker symbol in large faded text behind the pane content
+    pub watermark: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -147,6 +184,18 @@ impl std::fmt::Display for LinkGroup {
     }
 }
 
+/// What panes of a link group share besides the ticker. The crosshair's time always is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LinkOptions {
+    /// Crosshair price along with its time
+    pub crosshair_price: bool,
+    /// Basis picked on any of the panes, applied to those it suits
+    pub basis: bool,
+    /// Time span the charts show, as they're zoomed or scrolled horizontally
+    pub zoom: bool,
+}
+
 /// Defines the specific configuration for different types of pane settings.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum VisualConfig {
@@ -155,6 +204,7 @@ pub enum VisualConfig {
     Kline(kline::Config),
     Ladder(ladder::Config),
     Comparison(comparison::Config),
+    Spread(spread::Config),
 }
 
 impl VisualConfig {
@@ -192,6 +242,13 @@ impl VisualConfig {
             _ => None,
         }
     }
+
+    pub fn spread(&self) -> Option<spread::Config> {
+        match self {
+            Self::Spread(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -202,18 +259,20 @@ pub enum ContentKind {
     FootprintChart,
     CandlestickChart,
     ComparisonChart,
+    SpreadChart,
     TimeAndSales,
     Ladder,
 }
 
 impl ContentKind {
-    pub const ALL: [ContentKind; 8] = [
+    pub const ALL: [ContentKind; 9] = [
         ContentKind::Starter,
         ContentKind::HeatmapChart,
         ContentKind::ShaderHeatmap,
         ContentKind::FootprintChart,
         ContentKind::CandlestickChart,
         ContentKind::ComparisonChart,
+        ContentKind::SpreadChart,
         ContentKind::TimeAndSales,
         ContentKind::Ladder,
     ];
@@ -228,6 +287,7 @@ impl std::fmt::Display for ContentKind {
             ContentKind::FootprintChart => "Footprint Chart",
             ContentKind::CandlestickChart => "Candlestick Chart",
             ContentKind::ComparisonChart => "Comparison Chart",
+            ContentKind::SpreadChart => "Spread Chart",
             ContentKind::TimeAndSales => "Time&Sales",
             ContentKind::Ladder => "DOM/Ladder",
         };
@@ -235,6 +295,54 @@ impl std::fmt::Display for ContentKind {
     }
 }
 
+/// Ready-made chart arrangements for a single ticker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanePreset {
+    /// Candles with open interest and funding rate panels stacked below,
+    /// all on the same time axis
+    PriceOiFunding,
+}
+
+impl PanePreset {
+    pub const ALL: [PanePreset; 1] = [PanePreset::PriceOiFunding];
+
+    pub fn is_available(self, market: exchange::adapter::MarketKind) -> bool {
+        match self {
+            PanePreset::PriceOiFunding => !matches!(market, exchange::adapter::MarketKind::Spot),
+        }
+    }
+
+    pub fn content_kind(self) -> ContentKind {
+        match self {
+            PanePreset::PriceOiFunding => ContentKind::CandlestickChart,
+        }
+    }
+
+    /// Indicator panels, top to bottom
+    pub fn kline_indicators(self) -> &'static [KlineIndicator] {
+        match self {
+            PanePreset::PriceOiFunding => {
+                &[KlineIndicator::OpenInterest, KlineIndicator::FundingRate]
+            }
+        }
+    }
+
+    /// Height share of the main chart, the rest is split evenly between indicators
+    pub fn main_split(self) -> f32 {
+        match self {
+            PanePreset::PriceOiFunding => 0.5,
+        }
+    }
+}
+
+impl std::fmt::Display for PanePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PanePreset::PriceOiFunding => write!(f, "Price / OI / Funding"),
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct PaneSetup {
     pub ticker_info: exchange::TickerInfo,
@@ -275,8 +383,10 @@ impl PaneSetup {
                 ),
                 ContentKind::FootprintChart => {
                     let current = current_basis.and_then(|b| match b {
-                        Basis::Time(tf) if exchange.supports_kline_timeframe(tf) => Some(b),
-                        Basis::Tick(_) => Some(b),
+                        Basis::Time(tf) if exchange.kline_fetch_timeframe(tf).is_some() => Some(b),
+                        // Built from trades on any venue
+                        Basis::Time(tf) if Timeframe::SECONDS.contains(&tf) => Some(b),
+                        Basis::Tick(_) | Basis::Bars(_) => Some(b),
                         _ => None,
                     });
 
@@ -284,9 +394,18 @@ impl PaneSetup {
                         Basis::default_kline_time(Some(base_ticker), Timeframe::M5)
                     }))
                 }
-                ContentKind::CandlestickChart | ContentKind::ComparisonChart => {
+                ContentKind::CandlestickChart
+                | ContentKind::ComparisonChart
+                | ContentKind::SpreadChart => {
                     let current = current_basis.and_then(|b| match b {
                         Basis::Time(tf) if exchange.supports_kline_timeframe(tf) => Some(b),
+                        Basis::Time(tf)
+                            if content_kind == ContentKind::CandlestickChart
+                                && (Timeframe::SECONDS.contains(&tf)
+                                    || exchange.kline_fetch_timeframe(tf).is_some()) =>
+                        {
+                            Some(b)
+                        }
                         _ => None,
                     });
 
@@ -315,6 +434,7 @@ impl PaneSetup {
             }
             ContentKind::CandlestickChart
             | ContentKind::ComparisonChart
+            | ContentKind::SpreadChart
             | ContentKind::TimeAndSales
             | ContentKind::Starter => current_tick_multiplier,
         };
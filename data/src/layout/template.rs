@@ -0,0 +1,228 @@
+//! Named sets of kline chart indicators, and whole dashboards, saved apart from the layouts so
+//! any pane, or layout, can apply them.
+
+use serde::{Deserialize, Serialize};
+
+use super::pane::{Axis, LinkGroup, Settings};
+use super::{Dashboard, Pane};
+use crate::chart::indicator::{
+    KlineIndicator, KlineIndicatorInstance, MovingAverage, VolatilityBand,
+};
+use crate::chart::kline::{ClusterKind, ClusterScaling, KlineChartKind};
+use crate::chart::vwap::VwapConfig;
+use crate::chart::{Autoscale, ViewConfig};
+
+const TEMPLATES_FILE: &str = "indicator-templates.json";
+const LAYOUT_TEMPLATES_FILE: &str = "layout-templates.json";
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IndicatorTemplate {
+    pub name: String,
+    /// Panels in the order they're stacked under the chart, with their settings
+    pub indicators: Vec<KlineIndicatorInstance>,
+    #[serde(default)]
+    pub moving_averages: Vec<MovingAverage>,
+    #[serde(default)]
+    pub bands: Vec<VolatilityBand>,
+    #[serde(default)]
+    pub vwap: Option<VwapConfig>,
+    #[serde(default)]
+    pub scripts: Vec<String>,
+}
+
+impl IndicatorTemplate {
+    /// Captures the indicators of a kline pane
+    pub fn from_pane(
+        name: String,
+        indicators: &[KlineIndicatorInstance],
+        settings: &Settings,
+    ) -> Self {
+        Self {
+            name,
+            indicators: indicators.to_vec(),
+            moving_averages: settings.moving_averages.clone(),
+            bands: settings.bands.clone(),
+            vwap: settings.vwap,
+            scripts: settings.scripts.clone(),
+        }
+    }
+
+    /// Overwrites the pane settings the template covers
+    pub fn apply_to(&self, settings: &mut Settings) {
+        settings.moving_averages = self.moving_averages.clone();
+        settings.bands = self.bands.clone();
+        settings.vwap = self.vwap;
+        settings.scripts = self.scripts.clone();
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct IndicatorTemplates {
+    templates: Vec<IndicatorTemplate>,
+}
+
+impl IndicatorTemplates {
+    pub fn load() -> Self {
+        let path = crate::data_path(Some(TEMPLATES_FILE));
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("Ignoring unreadable {TEMPLATES_FILE}: {err}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        crate::write_json_to_file(&json, TEMPLATES_FILE)
+    }
+
+    pub fn templates(&self) -> &[IndicatorTemplate] {
+        &self.templates
+    }
+
+    pub fn get(&self, name: &str) -> Option<&IndicatorTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    /// Adds the template, replacing one saved under the same name
+    pub fn insert(&mut self, template: IndicatorTemplate) {
+        match self.templates.iter_mut().find(|t| t.name == template.name) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.templates.retain(|template| template.name != name);
+    }
+}
+
+/// A dashboard to start the active layout over from, panes, popouts and link options
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LayoutTemplate {
+    pub name: String,
+    pub dashboard: Dashboard,
+}
+
+impl LayoutTemplate {
+    /// Shipped with the app. Their panes have no ticker yet, those sharing a link group all
+    /// take the first one picked
+    pub fn builtin() -> Vec<LayoutTemplate> {
+        let scalping = Pane::Split {
+            axis: Axis::Vertical,
+            ratio: 0.25,
+            a: Box::new(Pane::Ladder {
+                stream_type: vec![],
+                settings: Settings::default(),
+                link_group: Some(LinkGroup::A),
+            }),
+            b: Box::new(Pane::Split {
+                axis: Axis::Vertical,
+                ratio: 0.7,
+                a: Box::new(kline_pane(
+                    KlineChartKind::Footprint {
+                        clusters: ClusterKind::default(),
+                        scaling: ClusterScaling::default(),
+                        studies: vec![],
+                    },
+                    Some(LinkGroup::A),
+                )),
+                b: Box::new(Pane::TimeAndSales {
+                    stream_type: vec![],
+                    settings: Settings::default(),
+                    link_group: Some(LinkGroup::A),
+                }),
+            }),
+        };
+
+        let candles_row = || Pane::Split {
+            axis: Axis::Vertical,
+            ratio: 0.5,
+            a: Box::new(kline_pane(KlineChartKind::Candles, None)),
+            b: Box::new(kline_pane(KlineChartKind::Candles, None)),
+        };
+        let overview = Pane::Split {
+            axis: Axis::Horizontal,
+            ratio: 0.5,
+            a: Box::new(candles_row()),
+            b: Box::new(candles_row()),
+        };
+
+        [
+            ("Scalping: ladder + tape + footprint", scalping),
+            ("Overview: 4 candlesticks", overview),
+        ]
+        .into_iter()
+        .map(|(name, pane)| LayoutTemplate {
+            name: name.to_string(),
+            dashboard: Dashboard {
+                pane,
+                ..Dashboard::default()
+            },
+        })
+        .collect()
+    }
+}
+
+/// A kline chart waiting for a ticker, with the volume panel new ones start with
+fn kline_pane(kind: KlineChartKind, link_group: Option<LinkGroup>) -> Pane {
+    Pane::KlineChart {
+        layout: ViewConfig {
+            splits: vec![],
+            autoscale: Some(Autoscale::FitToVisible),
+        },
+        kind,
+        stream_type: vec![],
+        settings: Settings::default(),
+        indicators: vec![KlineIndicatorInstance::new(KlineIndicator::Volume)],
+        link_group,
+    }
+}
+
+/// Dashboards the user saved as templates, the built-in ones aren't stored
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LayoutTemplates {
+    templates: Vec<LayoutTemplate>,
+}
+
+impl LayoutTemplates {
+    pub fn load() -> Self {
+        let path = crate::data_path(Some(LAYOUT_TEMPLATES_FILE));
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("Ignoring unreadable {LAYOUT_TEMPLATES_FILE}: {err}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        crate::write_json_to_file(&json, LAYOUT_TEMPLATES_FILE)
+    }
+
+    pub fn templates(&self) -> &[LayoutTemplate] {
+        &self.templates
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LayoutTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    /// Adds the template, replacing one saved under the same name
+    pub fn insert(&mut self, template: LayoutTemplate) {
+        match self.templates.iter_mut().find(|t| t.name == template.name) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.templates.retain(|template| template.name != name);
+    }
+}
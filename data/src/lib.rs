@@ -1,12 +1,23 @@
 pub mod aggr;
+pub mod alerts;
 pub mod audio;
+pub mod broadcast;
 pub mod chart;
 pub mod config;
+pub mod external;
+pub mod history_cache;
 pub mod layout;
 pub mod log;
+pub mod maintenance;
 pub mod panel;
+pub mod quality;
+pub mod rates;
+pub mod replay;
+pub mod snapshot;
 pub mod stream;
 pub mod tickers_table;
+pub mod tickstore;
+pub mod trading;
 pub mod util;
 
 use std::fs::File;
@@ -181,7 +192,7 @@ fn cleanup_directory(data_path: &PathBuf) -> usize {
                 && let Ok(file_date) = chrono::NaiveDate::parse_from_str(&cap[1], "%Y-%m-%d")
             {
                 let days_old = today.signed_duration_since(file_date).num_days();
-                if days_old > 4 {
+                if days_old > 1 {
                     if let Err(e) = std::fs::remove_file(&path) {
                         error!("Failed to remove old file {}: {}", filename, e);
                     } else {
@@ -196,7 +207,12 @@ fn cleanup_directory(data_path: &PathBuf) -> usize {
     deleted_files.len()
 }
 
-pub fn cleanup_old_market_data() -> usize {
+/// Applies the trade store retention, then drops downloaded aggTrades archives.
+/// Their trades are kept in the store once fetched, so the archives are only a
+/// short-lived download cache
+pub fn cleanup_old_market_data(retention: tickstore::RetentionPolicy) -> usize {
+    let removed_partitions = tickstore::TickStore::default().compact(retention);
+
     let paths = ["um", "cm"].map(|market_type| {
         data_path(Some(&format!(
             "market_data/binance/data/futures/{}/daily/aggTrades",
@@ -206,6 +222,9 @@ pub fn cleanup_old_market_data() -> usize {
 
     let total_deleted: usize = paths.iter().map(cleanup_directory).sum();
 
-    info!("File cleanup completed. Deleted {} files", total_deleted);
-    total_deleted
+    info!(
+        "File cleanup completed. Deleted {} archives and {} trade store partitions",
+        total_deleted, removed_partitions
+    );
+    total_deleted + removed_partitions
 }
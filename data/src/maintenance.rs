@@ -0,0 +1,166 @@
+//! Storage upkeep run while the app sits idle.
+//!
+//! Besides the cleanup at launch, the trade store, downloaded archives, history cache and
+//! exports folder are tidied up once the user hasn't touched the app for a while, so a
+//! session left running for days doesn't keep growing them.
+
+use crate::tickstore::RetentionPolicy;
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Cached history series not written for this long belong to tickers no longer charted
+const HISTORY_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// Least time between two idle runs, however often the app goes idle
+const MIN_RUN_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    pub enabled: bool,
+    /// Minutes without input before the app counts as idle
+    pub idle_mins: u16,
+    /// Pane snapshots and time-lapses older than this are deleted, kept if `None`
+    pub snapshot_max_age_days: Option<u16>,
+}
+
+impl MaintenanceConfig {
+    pub const IDLE_OPTIONS: [u16; 5] = [5, 10, 15, 30, 60];
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_mins: 10,
+            snapshot_max_age_days: None,
+        }
+    }
+}
+
+/// How long pane snapshots are kept, as offered in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotAge(pub Option<u16>);
+
+impl SnapshotAge {
+    pub const OPTIONS: [SnapshotAge; 5] = [
+        SnapshotAge(None),
+        SnapshotAge(Some(7)),
+        SnapshotAge(Some(30)),
+        SnapshotAge(Some(90)),
+        SnapshotAge(Some(365)),
+    ];
+}
+
+impl std::fmt::Display for SnapshotAge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "Forever"),
+            Some(1) => write!(f, "1 day"),
+            Some(days) => write!(f, "{days} days"),
+        }
+    }
+}
+
+/// What an idle run removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Trade store partitions and downloaded archives
+    pub market_data: usize,
+    pub history_series: usize,
+    /// Old snapshots and leftovers of interrupted time-lapses
+    pub exports: usize,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.market_data == 0 && self.history_series == 0 && self.exports == 0
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "Nothing to clean up");
+        }
+
+        let parts = [
+            (self.market_data, "market data files"),
+            (self.history_series, "stale history series"),
+            (self.exports, "old exports"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{count} {what}"))
+        .collect::<Vec<_>>();
+
+        write!(f, "Removed {}", parts.join(", "))
+    }
+}
+
+/// Runs every cleanup step, blocking on disk I/O
+pub fn run(config: MaintenanceConfig, retention: RetentionPolicy) -> Report {
+    let report = Report {
+        market_data: crate::cleanup_old_market_data(retention),
+        history_series: crate::history_cache::evict_stale(HISTORY_MAX_AGE),
+        exports: crate::snapshot::prune(
+            config
+                .snapshot_max_age_days
+                .map(|days| Duration::from_secs(u64::from(days) * 24 * 60 * 60)),
+        ),
+    };
+
+    log::info!("Idle maintenance finished: {report}");
+    report
+}
+
+/// Decides when the app went idle long enough for a run.
+///
+/// Runs at most once per idle stretch, the next one waits for new input first.
+#[derive(Debug, Clone, Copy)]
+pub struct IdleScheduler {
+    last_input: Instant,
+    last_run: Option<Instant>,
+    ran_since_input: bool,
+    running: bool,
+}
+
+impl IdleScheduler {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_input: now,
+            last_run: None,
+            ran_since_input: false,
+            running: false,
+        }
+    }
+
+    pub fn on_input(&mut self, now: Instant) {
+        self.last_input = now;
+        self.ran_since_input = false;
+    }
+
+    /// Whether a run is due at `now`, marking it as started if so
+    pub fn start_if_due(&mut self, now: Instant, config: &MaintenanceConfig) -> bool {
+        let idle_for = Duration::from_secs(u64::from(config.idle_mins) * 60);
+
+        let is_due = config.enabled
+            && !self.running
+            && !self.ran_since_input
+            && now.duration_since(self.last_input) >= idle_for
+            && self
+                .last_run
+                .is_none_or(|last_run| now.duration_since(last_run) >= MIN_RUN_INTERVAL);
+
+        if is_due {
+            self.running = true;
+            self.ran_since_input = true;
+            self.last_run = Some(now);
+        }
+        is_due
+    }
+
+    pub fn finish(&mut self) {
+        self.running = false;
+    }
+}
@@ -1,7 +1,7 @@
 use crate::chart::kline::KlineTrades;
 use crate::util::ok_or_default;
 use exchange::{
-    Trade, UnixMs,
+    Timeframe, Trade, UnixMs,
     unit::price::{Price, PriceStep},
     unit::qty::Qty,
 };
@@ -14,6 +14,10 @@ use std::{
 
 const TRADE_RETENTION_MS: u64 = 8 * 60_000;
 const CHASE_MIN_VISIBLE_OPACITY: f32 = 0.15;
+/// Session volume starts over at the start of every UTC day
+const SESSION: Timeframe = Timeframe::D1;
+/// Span of the rolling spread average in the header
+const SPREAD_AVG_WINDOW_MS: u64 = 60_000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub struct Config {
@@ -21,6 +25,30 @@ pub struct Config {
     #[serde(deserialize_with = "ok_or_default", default)]
     pub show_chase_tracker: bool,
     pub trade_retention: Duration,
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub size_display: SizeDisplay,
+    /// Clicks on the order columns place and cancel limit orders, see [`crate::trading`]
+    #[serde(default)]
+    pub order_entry: bool,
+    /// Adds a column with the size resting between the touch and each level
+    #[serde(default)]
+    pub show_cumulative: bool,
+    /// Shades each row by its cumulative size, across the whole ladder
+    #[serde(default)]
+    pub show_depth_bars: bool,
+    /// Adds a column with the volume traded at each level since the session started
+    #[serde(default)]
+    pub show_session_volume: bool,
+    /// Flags levels that keep refilling once traded volume reaches this many times the
+    /// most they ever showed, `None` when off
+    #[serde(default)]
+    pub refill_ratio: Option<f32>,
+    #[serde(default)]
+    pub recenter: RecenterMode,
+    /// Shows a header with the bid/ask imbalance over this many levels on each side and
+    /// the spread stats, `None` when off
+    #[serde(default)]
+    pub header_levels: Option<usize>,
 }
 
 impl Default for Config {
@@ -29,8 +57,134 @@ impl Default for Config {
             show_spread: false,
             show_chase_tracker: true,
             trade_retention: Duration::from_millis(TRADE_RETENTION_MS),
+            size_display: SizeDisplay::default(),
+            order_entry: false,
+            show_cumulative: false,
+            show_depth_bars: false,
+            show_session_volume: false,
+            refill_ratio: None,
+            recenter: RecenterMode::default(),
+            header_levels: None,
+        }
+    }
+}
+
+impl Config {
+    pub const DEFAULT_HEADER_LEVELS: usize = 10;
+}
+
+/// How resting order sizes are labelled on the ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SizeDisplay {
+    #[default]
+    Absolute,
+    /// Share of all resting size within the visible rows
+    PercentOfVisible,
+    /// Share of the N levels closest to the touch, on both sides
+    PercentOfTop(usize),
+}
+
+impl SizeDisplay {
+    pub const DEFAULT_TOP_LEVELS: usize = 20;
+
+    /// Options for a picker, keeping the currently configured level count
+    pub fn options(current: SizeDisplay) -> [SizeDisplay; 3] {
+        let top_levels = match current {
+            SizeDisplay::PercentOfTop(n) => n,
+            _ => Self::DEFAULT_TOP_LEVELS,
+        };
+
+        [
+            SizeDisplay::Absolute,
+            SizeDisplay::PercentOfVisible,
+            SizeDisplay::PercentOfTop(top_levels),
+        ]
+    }
+}
+
+impl std::fmt::Display for SizeDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizeDisplay::Absolute => write!(f, "Absolute"),
+            SizeDisplay::PercentOfVisible => write!(f, "% of visible book"),
+            SizeDisplay::PercentOfTop(_) => write!(f, "% of top levels"),
+        }
+    }
+}
+
+/// How the ladder's rows follow the market once scrolled away from the spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum RecenterMode {
+    /// Scrolling only shifts the view around the spread, the rows move with it
+    #[default]
+    FollowSpread,
+    /// Stays on the scrolled to prices, back on the spread after a while without scrolling
+    Delayed { secs: u8 },
+    /// Stays on the scrolled to prices until recentered
+    Locked,
+}
+
+impl RecenterMode {
+    pub const DEFAULT_DELAY_SECS: u8 = 5;
+
+    /// Options for a picker, keeping the currently configured delay
+    pub fn options(current: RecenterMode) -> [RecenterMode; 3] {
+        let delayed = match current {
+            RecenterMode::Delayed { .. } => current,
+            _ => RecenterMode::Delayed {
+                secs: Self::DEFAULT_DELAY_SECS,
+            },
+        };
+
+        [RecenterMode::FollowSpread, delayed, RecenterMode::Locked]
+    }
+
+    /// Whether rows stay on their prices while scrolled
+    pub fn pins_prices(self) -> bool {
+        !matches!(self, RecenterMode::FollowSpread)
+    }
+}
+
+impl std::fmt::Display for RecenterMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecenterMode::FollowSpread => write!(f, "Follow spread"),
+            RecenterMode::Delayed { .. } => write!(f, "Recenter after delay"),
+            RecenterMode::Locked => write!(f, "Locked"),
+        }
+    }
+}
+
+/// Spread readings over the last minute, in ticks, for the header's rolling average
+#[derive(Debug, Default)]
+pub struct SpreadStats {
+    samples: VecDeque<(UnixMs, f64)>,
+    sum: f64,
+}
+
+impl SpreadStats {
+    pub fn push(&mut self, time: UnixMs, ticks: f64) {
+        self.samples.push_back((time, ticks));
+        self.sum += ticks;
+
+        let keep_from = time.saturating_sub(SPREAD_AVG_WINDOW_MS);
+        while let Some((oldest, ticks)) = self.samples.front().copied() {
+            if oldest >= keep_from {
+                break;
+            }
+            self.samples.pop_front();
+            self.sum -= ticks;
         }
     }
+
+    pub fn average(&self) -> Option<f64> {
+        (!self.samples.is_empty()).then(|| self.sum / self.samples.len() as f64)
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.sum = 0.0;
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -184,6 +338,146 @@ impl TradeStore {
     }
 }
 
+/// Buy and sell volume traded at each price since the session started, kept at the traded
+/// prices as well so it can be grouped again at any tick size
+#[derive(Debug, Default)]
+pub struct SessionVolume {
+    session: Option<UnixMs>,
+    raw: BTreeMap<Price, (Qty, Qty)>,
+    grouped: BTreeMap<Price, (Qty, Qty)>,
+}
+
+impl SessionVolume {
+    pub fn insert_trades(&mut self, buffer: &[Trade], step: PriceStep) {
+        for trade in buffer {
+            let session = trade.time.floor_to(SESSION);
+            match self.session {
+                Some(current) if session < current => continue,
+                Some(current) if session == current => {}
+                _ => {
+                    self.session = Some(session);
+                    self.raw.clear();
+                    self.grouped.clear();
+                }
+            }
+
+            Self::add(&mut self.raw, trade.price, trade);
+            Self::add(
+                &mut self.grouped,
+                trade.price.round_to_side_step(trade.is_sell, step),
+                trade,
+            );
+        }
+    }
+
+    pub fn regroup(&mut self, step: PriceStep) {
+        self.grouped.clear();
+        for (price, (buy, sell)) in &self.raw {
+            // Grouped the way the trade columns are, sells down and buys up
+            for (qty, is_sell) in [(*buy, false), (*sell, true)] {
+                if qty.is_zero() {
+                    continue;
+                }
+                let entry = self
+                    .grouped
+                    .entry(price.round_to_side_step(is_sell, step))
+                    .or_default();
+                if is_sell {
+                    entry.1 += qty;
+                } else {
+                    entry.0 += qty;
+                }
+            }
+        }
+    }
+
+    /// Buy and sell volume of the row at `price`
+    pub fn qty_at(&self, price: Price) -> (Qty, Qty) {
+        self.grouped.get(&price).copied().unwrap_or_default()
+    }
+
+    fn add(levels: &mut BTreeMap<Price, (Qty, Qty)>, price: Price, trade: &Trade) {
+        let entry = levels.entry(price).or_default();
+        if trade.is_sell {
+            entry.1 += trade.qty;
+        } else {
+            entry.0 += trade.qty;
+        }
+    }
+}
+
+/// Traded and displayed size of a level since it was first hit
+#[derive(Debug, Default, Clone, Copy)]
+struct Refill {
+    traded: Qty,
+    max_shown: Qty,
+    last_shown: Qty,
+    traded_since_update: Qty,
+    refills: u32,
+}
+
+/// Levels that keep showing size after being traded into, like an iceberg order topping
+/// itself back up would
+#[derive(Debug, Default)]
+pub struct RefillTracker {
+    /// Keyed by the grouped price and whether it's on the bid side
+    levels: BTreeMap<(Price, bool), Refill>,
+}
+
+impl RefillTracker {
+    pub const DEFAULT_RATIO: f32 = 3.0;
+    /// Times a level has to refill before it's flagged
+    const MIN_REFILLS: u32 = 2;
+
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+
+    /// Sells hit the bids and buys lift the asks
+    pub fn insert_trades(&mut self, buffer: &[Trade], step: PriceStep) {
+        for trade in buffer {
+            let is_bid = trade.is_sell;
+            let price = trade.price.round_to_side_step(is_bid, step);
+
+            let refill = self.levels.entry((price, is_bid)).or_default();
+            refill.traded += trade.qty;
+            refill.traded_since_update += trade.qty;
+        }
+    }
+
+    /// Compares the levels that were traded into with the book after. Levels gone from
+    /// the book are forgotten
+    pub fn update(&mut self, bids: &BTreeMap<Price, Qty>, asks: &BTreeMap<Price, Qty>) {
+        self.levels.retain(|(price, is_bid), refill| {
+            let book = if *is_bid { bids } else { asks };
+            let shown = book.get(price).copied().unwrap_or_default();
+            if shown.is_zero() {
+                return false;
+            }
+
+            let traded = std::mem::take(&mut refill.traded_since_update);
+            // Half of what traded showing back up on top of what should be left
+            if !refill.last_shown.is_zero()
+                && !traded.is_zero()
+                && shown.to_f64() >= refill.last_shown.to_f64() - traded.to_f64() * 0.5
+            {
+                refill.refills += 1;
+            }
+
+            refill.last_shown = shown;
+            refill.max_shown = refill.max_shown.max(shown);
+            true
+        });
+    }
+
+    pub fn is_refilling(&self, price: Price, is_bid: bool, ratio: f32) -> bool {
+        self.levels.get(&(price, is_bid)).is_some_and(|refill| {
+            refill.refills >= Self::MIN_REFILLS
+                && refill.traded.to_f64() >= f64::from(ratio) * refill.max_shown.to_f64()
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 enum ChaseProgress {
     #[default]
@@ -1,9 +1,13 @@
+use std::io::Write;
+use std::path::PathBuf;
 use std::time::Duration;
 
-use exchange::UnixMs;
 use exchange::unit::{Price, Qty};
+use exchange::{TickerInfo, UnixMs};
 use serde::{Deserialize, Serialize};
 
+use crate::audio::TradeSound;
+use crate::chart::heatmap::EXPORTS_DIR;
 use crate::util::ok_or_default;
 
 const TRADE_RETENTION_MS: u64 = 120_000;
@@ -15,22 +19,159 @@ pub struct Config {
     pub trade_retention: Duration,
     #[serde(deserialize_with = "ok_or_default", default)]
     pub stacked_bar: Option<StackedBar>,
+    #[serde(default)]
+    pub sound: TradeSound,
+    /// Trades of at least this size are highlighted, brighter again at each of
+    /// [`SIZE_TIER_MULTIPLES`], `None` when off
+    #[serde(default)]
+    pub size_tiers: Option<f32>,
+    /// Trades at the same price and side as the row before, within this many milliseconds
+    /// of its first trade, are added to it instead of getting their own, `None` when off
+    #[serde(default)]
+    pub aggregation_ms: Option<u16>,
+    /// Holds new trades back while the cursor is over the tape
+    #[serde(default)]
+    pub pause_on_hover: bool,
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub columns: TapeColumns,
+    /// Shows the buy and sell volume of this many last seconds under the tape, `None`
+    /// when off
+    #[serde(default)]
+    pub footer_secs: Option<u16>,
+    /// Marks the trades passing the size filter on the charts of the pane's link group
+    #[serde(default)]
+    pub mark_on_chart: bool,
 }
 
+/// Multiples of the smallest highlighted size each tier starts at
+pub const SIZE_TIER_MULTIPLES: [f32; 3] = [1.0, 10.0, 100.0];
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             trade_size_filter: 0.0,
             trade_retention: Duration::from_millis(TRADE_RETENTION_MS),
             stacked_bar: StackedBar::Compact(StackedBarRatio::default()).into(),
+            sound: TradeSound::default(),
+            size_tiers: None,
+            aggregation_ms: None,
+            pause_on_hover: false,
+            columns: TapeColumns::default(),
+            footer_secs: None,
+            mark_on_chart: false,
         }
     }
 }
 
+impl Config {
+    pub const DEFAULT_SIZE_TIER: f32 = 10_000.0;
+    pub const DEFAULT_AGGREGATION_MS: u16 = 100;
+    pub const DEFAULT_FOOTER_SECS: u16 = 30;
+
+    /// Index of the highest tier a trade of `size` reaches
+    pub fn size_tier(&self, size: f64) -> Option<usize> {
+        let smallest = f64::from(self.size_tiers?);
+        SIZE_TIER_MULTIPLES
+            .iter()
+            .rposition(|multiple| size >= smallest * f64::from(*multiple))
+    }
+}
+
 fn default_buffer_filter() -> Duration {
     Duration::from_millis(TRADE_RETENTION_MS)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapeColumn {
+    Exchange,
+    Time,
+    Price,
+    Size,
+    Notional,
+    Aggressor,
+}
+
+impl TapeColumn {
+    /// In the order they're laid out, left to right
+    pub const ALL: [TapeColumn; 6] = [
+        TapeColumn::Exchange,
+        TapeColumn::Time,
+        TapeColumn::Price,
+        TapeColumn::Size,
+        TapeColumn::Notional,
+        TapeColumn::Aggressor,
+    ];
+}
+
+impl std::fmt::Display for TapeColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TapeColumn::Exchange => write!(f, "Exchange"),
+            TapeColumn::Time => write!(f, "Time"),
+            TapeColumn::Price => write!(f, "Price"),
+            TapeColumn::Size => write!(f, "Size"),
+            TapeColumn::Notional => write!(f, "Notional"),
+            TapeColumn::Aggressor => write!(f, "Aggressor"),
+        }
+    }
+}
+
+/// Columns shown on each row of the tape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TapeColumns {
+    pub exchange: bool,
+    pub time: bool,
+    pub price: bool,
+    pub size: bool,
+    pub notional: bool,
+    pub aggressor: bool,
+}
+
+impl Default for TapeColumns {
+    fn default() -> Self {
+        Self {
+            exchange: true,
+            time: true,
+            price: true,
+            size: true,
+            notional: false,
+            aggressor: false,
+        }
+    }
+}
+
+impl TapeColumns {
+    pub fn is_shown(&self, column: TapeColumn) -> bool {
+        match column {
+            TapeColumn::Exchange => self.exchange,
+            TapeColumn::Time => self.time,
+            TapeColumn::Price => self.price,
+            TapeColumn::Size => self.size,
+            TapeColumn::Notional => self.notional,
+            TapeColumn::Aggressor => self.aggressor,
+        }
+    }
+
+    pub fn with(mut self, column: TapeColumn, shown: bool) -> Self {
+        let field = match column {
+            TapeColumn::Exchange => &mut self.exchange,
+            TapeColumn::Time => &mut self.time,
+            TapeColumn::Price => &mut self.price,
+            TapeColumn::Size => &mut self.size,
+            TapeColumn::Notional => &mut self.notional,
+            TapeColumn::Aggressor => &mut self.aggressor,
+        };
+        *field = shown;
+        self
+    }
+
+    pub fn shown(self) -> impl Iterator<Item = TapeColumn> {
+        TapeColumn::ALL
+            .into_iter()
+            .filter(move |column| self.is_shown(*column))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TradeDisplay {
     pub time_str: String,
@@ -43,6 +184,48 @@ pub struct TradeDisplay {
 pub struct TradeEntry {
     pub ts_ms: UnixMs,
     pub display: TradeDisplay,
+    /// Originating ticker, set only when the tape merges several sources
+    pub source: Option<TickerInfo>,
+}
+
+/// Writes the trades to a CSV in the exports folder, one row per tape row. Trades without
+/// a source of their own are from `ticker_info`
+pub fn write_csv<'a>(
+    trades: impl Iterator<Item = &'a TradeEntry>,
+    ticker_info: &TickerInfo,
+) -> std::io::Result<PathBuf> {
+    let dir = crate::data_path(Some(EXPORTS_DIR));
+    std::fs::create_dir_all(&dir)?;
+
+    let now = UnixMs::now();
+    let file_name = format!(
+        "tape_{}_{}_{}.csv",
+        ticker_info.exchange().to_string().replace(' ', "-"),
+        ticker_info.ticker,
+        now.as_datetime_utc()
+            .map(|dt| dt.format("%Y%m%d-%H%M%S").to_string())
+            .unwrap_or_else(|| now.as_u64().to_string()),
+    );
+    let path = dir.join(file_name);
+
+    let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+    writeln!(out, "time,exchange,side,price,qty")?;
+
+    for entry in trades {
+        let source = entry.source.unwrap_or(*ticker_info);
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            entry.ts_ms.as_u64(),
+            source.exchange(),
+            if entry.display.is_sell { "sell" } else { "buy" },
+            entry.display.price.to_string(source.min_ticksize),
+            entry.display.qty.to_f64(),
+        )?;
+    }
+    out.flush()?;
+
+    Ok(path)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Copy)]
@@ -0,0 +1,207 @@
+//! Daily feed quality summaries, aggregated from live market events per ticker.
+//!
+//! Every live stream is covered, not only the recorded ones: recording is a replay capture
+//! started per pane, there's no app-wide setting the reports could follow.
+
+use exchange::adapter::{Event, StreamKind};
+use exchange::{TickerInfo, UnixMs};
+
+use chrono::NaiveDate;
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Sub-directory of the data path holding one `YYYY-MM-DD.json` per day
+pub const REPORTS_DIR: &str = "quality-reports";
+
+/// Silence between two messages of a ticker longer than this counts as a gap
+const GAP_THRESHOLD_MS: u64 = 30_000;
+
+const DAY_MS: u64 = 86_400_000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TickerQuality {
+    pub messages: u64,
+    pub gaps: u32,
+    pub longest_gap_ms: u64,
+    pub reconnects: u32,
+    pub max_latency_ms: u64,
+    #[serde(skip)]
+    last_message: Option<UnixMs>,
+}
+
+impl TickerQuality {
+    fn record_message(&mut self, received: UnixMs, event_time: Option<UnixMs>) {
+        self.messages += 1;
+
+        if let Some(prev) = self.last_message {
+            let silence = received.as_u64().saturating_sub(prev.as_u64());
+            if silence > GAP_THRESHOLD_MS {
+                self.gaps += 1;
+                self.longest_gap_ms = self.longest_gap_ms.max(silence);
+            }
+        }
+        self.last_message = Some(received);
+
+        if let Some(event_time) = event_time {
+            let latency = received.as_u64().saturating_sub(event_time.as_u64());
+            self.max_latency_ms = self.max_latency_ms.max(latency);
+        }
+    }
+}
+
+/// One UTC day worth of [`TickerQuality`], keyed by `EXCHANGE:SYMBOL`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub tickers: BTreeMap<String, TickerQuality>,
+}
+
+impl DailyReport {
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            tickers: BTreeMap::new(),
+        }
+    }
+
+    /// The report saved earlier that day, e.g. before a restart, for the new counts to add to
+    fn resume(date: NaiveDate) -> Self {
+        match read_report(date) {
+            Ok(report) => report,
+            Err(err) => {
+                if err
+                    .downcast_ref::<std::io::Error>()
+                    .is_none_or(|err| err.kind() != std::io::ErrorKind::NotFound)
+                {
+                    log::warn!("Failed to read data quality report for {date}: {err}");
+                }
+                Self::new(date)
+            }
+        }
+    }
+
+    fn file_name(date: NaiveDate) -> String {
+        format!("{REPORTS_DIR}/{}.json", date.format("%Y-%m-%d"))
+    }
+
+    fn entry(&mut self, ticker_info: &TickerInfo) -> &mut TickerQuality {
+        self.tickers
+            .entry(ticker_info.ticker.symbol_and_exchange_string())
+            .or_default()
+    }
+}
+
+pub struct QualityTracker {
+    report: DailyReport,
+    day_end: UnixMs,
+    /// Streams that connected at least once, so the next connect is a reconnect
+    seen_streams: FxHashSet<StreamKind>,
+}
+
+impl QualityTracker {
+    pub fn new(now: UnixMs) -> Self {
+        let (date, day_end) = utc_day(now);
+
+        Self {
+            report: DailyReport::resume(date),
+            day_end,
+            seen_streams: FxHashSet::default(),
+        }
+    }
+
+    /// Returns the finished report of the previous day once the UTC date rolls over
+    pub fn record(&mut self, event: &Event, received: UnixMs) -> Option<DailyReport> {
+        let finished = self.roll_over(received);
+
+        match event {
+            Event::Connected(streams) => {
+                for stream in streams.iter() {
+                    if !self.seen_streams.insert(*stream) {
+                        self.report.entry(&stream.ticker_info()).reconnects += 1;
+                    }
+                }
+            }
+            Event::Disconnected(..)
+            | Event::Resumed(..)
+            | Event::Reconnecting(..)
+            | Event::TickerStatsReceived(..) => {}
+            Event::DepthReceived(stream, event_time, _)
+            | Event::TradesReceived(stream, event_time, _) => {
+                self.report
+                    .entry(&stream.ticker_info())
+                    .record_message(received, Some(*event_time));
+            }
+            Event::KlineReceived(stream, _) => {
+                self.report
+                    .entry(&stream.ticker_info())
+                    .record_message(received, None);
+            }
+            Event::MarkPriceReceived(stream, mark_price) => {
+                self.report
+                    .entry(&stream.ticker_info())
+                    .record_message(received, Some(mark_price.time));
+            }
+        }
+
+        finished
+    }
+
+    pub fn current(&self) -> &DailyReport {
+        &self.report
+    }
+
+    fn roll_over(&mut self, now: UnixMs) -> Option<DailyReport> {
+        if now < self.day_end {
+            return None;
+        }
+
+        let (date, day_end) = utc_day(now);
+        self.day_end = day_end;
+
+        Some(std::mem::replace(
+            &mut self.report,
+            DailyReport::resume(date),
+        ))
+    }
+}
+
+fn utc_day(now: UnixMs) -> (NaiveDate, UnixMs) {
+    let day_start = now.as_u64() - now.as_u64() % DAY_MS;
+    let date = now
+        .as_datetime_utc()
+        .map(|dt| dt.date_naive())
+        .unwrap_or_default();
+
+    (date, UnixMs::from_millis(day_start + DAY_MS))
+}
+
+pub fn write_report(report: &DailyReport) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(report).map_err(std::io::Error::other)?;
+    crate::write_json_to_file(&json, &DailyReport::file_name(report.date))
+}
+
+pub fn read_report(date: NaiveDate) -> Result<DailyReport, Box<dyn std::error::Error>> {
+    let path = crate::data_path(Some(&DailyReport::file_name(date)));
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Dates of the reports saved on disk, newest first
+pub fn saved_report_dates() -> Vec<NaiveDate> {
+    let Ok(entries) = std::fs::read_dir(crate::data_path(Some(REPORTS_DIR))) else {
+        return vec![];
+    };
+
+    let mut dates = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let stem = name.to_str()?.strip_suffix(".json")?;
+            NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok()
+        })
+        .collect::<Vec<_>>();
+
+    dates.sort_unstable_by(|a, b| b.cmp(a));
+    dates
+}
@@ -0,0 +1,138 @@
+//! Typical message rates per exchange and stream kind, measured from the live feed
+//! and kept across sessions so costs can be estimated before subscribing.
+
+use exchange::adapter::{Event, Exchange, StreamKind};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const RATES_FILE: &str = "stream-rates.json";
+
+/// Rates are averaged over windows of this length before being folded in
+const WINDOW: Duration = Duration::from_secs(15);
+/// Weight of the newest window in the typical rate
+const EMA_ALPHA: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum RateKind {
+    /// Orderbook updates
+    Depth,
+    /// Individual trades, not websocket batches
+    Trades,
+    /// Kline updates
+    Kline,
+    /// Mark and index price updates
+    MarkPrice,
+}
+
+impl RateKind {
+    fn of(stream: &StreamKind) -> Self {
+        match stream {
+            StreamKind::Depth { .. } | StreamKind::CompositeDepth { .. } => RateKind::Depth,
+            StreamKind::Trades { .. } => RateKind::Trades,
+            StreamKind::Kline { .. } => RateKind::Kline,
+            StreamKind::MarkPrice { .. } => RateKind::MarkPrice,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+struct SavedRate {
+    exchange: Exchange,
+    kind: RateKind,
+    per_sec: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct StreamRates {
+    /// Smoothed per-stream rate, averaged over all tickers seen on the exchange
+    typical: FxHashMap<(Exchange, RateKind), f32>,
+    window: FxHashMap<StreamKind, u32>,
+    window_start: Option<Instant>,
+}
+
+impl StreamRates {
+    pub fn load() -> Self {
+        let path = crate::data_path(Some(RATES_FILE));
+
+        let saved: Vec<SavedRate> = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("Ignoring unreadable {RATES_FILE}: {err}");
+                vec![]
+            }),
+            Err(_) => vec![],
+        };
+
+        Self {
+            typical: saved
+                .into_iter()
+                .map(|rate| ((rate.exchange, rate.kind), rate.per_sec))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let saved = self
+            .typical
+            .iter()
+            .map(|(&(exchange, kind), &per_sec)| SavedRate {
+                exchange,
+                kind,
+                per_sec,
+            })
+            .collect::<Vec<_>>();
+
+        let json = serde_json::to_string(&saved).map_err(std::io::Error::other)?;
+        crate::write_json_to_file(&json, RATES_FILE)
+    }
+
+    pub fn record(&mut self, event: &Event, now: Instant) {
+        let (stream, units) = match event {
+            Event::DepthReceived(stream, ..)
+            | Event::KlineReceived(stream, _)
+            | Event::MarkPriceReceived(stream, _) => (stream, 1),
+            Event::TradesReceived(stream, _, trades) => (stream, trades.len() as u32),
+            Event::Connected(_)
+            | Event::Disconnected(..)
+            | Event::Resumed(..)
+            | Event::Reconnecting(..)
+            | Event::TickerStatsReceived(..) => return,
+        };
+
+        *self.window.entry(*stream).or_default() += units;
+
+        let started = *self.window_start.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(started);
+
+        if elapsed >= WINDOW {
+            self.close_window(elapsed.as_secs_f32());
+            self.window_start = Some(now);
+        }
+    }
+
+    /// Typical units per second of one stream of `kind` on `exchange`, if ever measured
+    pub fn typical(&self, exchange: Exchange, kind: RateKind) -> Option<f32> {
+        self.typical.get(&(exchange, kind)).copied()
+    }
+
+    fn close_window(&mut self, secs: f32) {
+        let mut sums: FxHashMap<(Exchange, RateKind), (f32, u32)> = FxHashMap::default();
+
+        for (stream, count) in self.window.drain() {
+            let key = (stream.ticker_info().exchange(), RateKind::of(&stream));
+            let (sum, streams) = sums.entry(key).or_default();
+            *sum += count as f32 / secs;
+            *streams += 1;
+        }
+
+        for (key, (sum, streams)) in sums {
+            let rate = sum / streams as f32;
+            self.typical
+                .entry(key)
+                .and_modify(|typical| *typical += EMA_ALPHA * (rate - *typical))
+                .or_insert(rate);
+        }
+    }
+}
@@ -0,0 +1,519 @@
+//! Recording of live depth and trade events for one ticker, and their playback into panes.
+//!
+//! A recording is a JSON-lines file under the `replays` data folder. The first line holds the
+//! ticker, every following line one event, in the order they arrived:
+//!
+//! ```json
+//! {"ticker_info":{...},"started":1718000000000}
+//! {"type":"trades","time":1718000000000,"trades":[[1718000000000,64210.5,0.25,true]]}
+//! {"type":"depth","time":1718000000100,"bids":[[64210.4,1.2]],"asks":[[64210.5,0.0]]}
+//! ```
+//!
+//! Depth lines only carry the levels that changed since the previous one, a zero quantity
+//! removing the level.
+
+use exchange::{
+    TickerInfo, Trade, UnixMs,
+    depth::Depth,
+    unit::{Price, Qty},
+};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Sub-directory of the data path holding recordings
+pub const REPLAYS_DIR: &str = "replays";
+
+/// Levels recorded per side of the book, nearest to the spread first
+pub const MAX_DEPTH_LEVELS: usize = 200;
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    ticker_info: TickerInfo,
+    started: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Line {
+    Trades {
+        time: u64,
+        /// `(time, price, qty, is_sell)`
+        trades: Vec<(u64, f64, f64, bool)>,
+    },
+    Depth {
+        time: u64,
+        bids: Vec<(f64, f64)>,
+        asks: Vec<(f64, f64)>,
+    },
+}
+
+fn write_line(out: &mut impl Write, line: &impl Serialize) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, line).map_err(io::Error::other)?;
+    writeln!(out)
+}
+
+fn file_prefix(ticker_info: &TickerInfo) -> String {
+    format!(
+        "{}_{}_",
+        ticker_info.exchange().to_string().replace(' ', "-"),
+        ticker_info.ticker,
+    )
+}
+
+/// The most recent recording of the ticker, if any
+pub fn latest_recording(ticker_info: &TickerInfo) -> Option<PathBuf> {
+    let prefix = file_prefix(ticker_info);
+
+    // Names end with the start time, so they sort chronologically
+    fs::read_dir(crate::data_path(Some(REPLAYS_DIR)))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".jsonl"))
+        })
+        .max()
+}
+
+/// Appends the live events of one ticker to a new recording
+pub struct Recorder {
+    ticker_info: TickerInfo,
+    path: PathBuf,
+    out: BufWriter<File>,
+    /// Book as of the last depth line written
+    book: Depth,
+}
+
+impl Recorder {
+    pub fn start(ticker_info: TickerInfo) -> io::Result<Self> {
+        let dir = crate::data_path(Some(REPLAYS_DIR));
+        fs::create_dir_all(&dir)?;
+
+        let started = UnixMs::now();
+        let file_name = format!(
+            "{}{}.jsonl",
+            file_prefix(&ticker_info),
+            started
+                .format_utc("%Y%m%d-%H%M%S")
+                .unwrap_or_else(|| started.as_u64().to_string()),
+        );
+        let path = dir.join(file_name);
+
+        let mut out = BufWriter::new(File::create(&path)?);
+        write_line(
+            &mut out,
+            &Header {
+                ticker_info,
+                started: started.as_u64(),
+            },
+        )?;
+
+        Ok(Self {
+            ticker_info,
+            path,
+            out,
+            book: Depth::default(),
+        })
+    }
+
+    pub fn ticker_info(&self) -> TickerInfo {
+        self.ticker_info
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record_trades(&mut self, update_t: UnixMs, trades: &[Trade]) -> io::Result<()> {
+        if trades.is_empty() {
+            return Ok(());
+        }
+
+        let trades = trades
+            .iter()
+            .map(|trade| {
+                (
+                    trade.time.as_u64(),
+                    trade.price.to_f64(),
+                    trade.qty.to_f64(),
+                    trade.is_sell,
+                )
+            })
+            .collect();
+
+        write_line(
+            &mut self.out,
+            &Line::Trades {
+                time: update_t.as_u64(),
+                trades,
+            },
+        )
+    }
+
+    pub fn record_depth(&mut self, update_t: UnixMs, depth: &Depth) -> io::Result<()> {
+        let bids: BTreeMap<Price, Qty> = depth
+            .bids
+            .iter()
+            .rev()
+            .take(MAX_DEPTH_LEVELS)
+            .map(|(price, qty)| (*price, *qty))
+            .collect();
+        let asks: BTreeMap<Price, Qty> = depth
+            .asks
+            .iter()
+            .take(MAX_DEPTH_LEVELS)
+            .map(|(price, qty)| (*price, *qty))
+            .collect();
+
+        let bid_changes = changed_levels(&self.book.bids, &bids);
+        let ask_changes = changed_levels(&self.book.asks, &asks);
+
+        if bid_changes.is_empty() && ask_changes.is_empty() {
+            return Ok(());
+        }
+
+        write_line(
+            &mut self.out,
+            &Line::Depth {
+                time: update_t.as_u64(),
+                bids: bid_changes,
+                asks: ask_changes,
+            },
+        )?;
+
+        self.book = Depth { bids, asks };
+        Ok(())
+    }
+
+    /// Flushes the remaining events, returning where the recording was saved
+    pub fn finish(mut self) -> io::Result<PathBuf> {
+        self.out.flush()?;
+        Ok(self.path)
+    }
+}
+
+fn changed_levels(prev: &BTreeMap<Price, Qty>, next: &BTreeMap<Price, Qty>) -> Vec<(f64, f64)> {
+    let removed = prev
+        .keys()
+        .filter(|price| !next.contains_key(price))
+        .map(|price| (price.to_f64(), 0.0));
+    let changed = next
+        .iter()
+        .filter(|(price, qty)| prev.get(price) != Some(qty))
+        .map(|(price, qty)| (price.to_f64(), qty.to_f64()));
+
+    removed.chain(changed).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Speed {
+    #[default]
+    X1,
+    X2,
+    X10,
+}
+
+impl Speed {
+    pub const ALL: [Speed; 3] = [Speed::X1, Speed::X2, Speed::X10];
+
+    fn factor(self) -> u64 {
+        match self {
+            Speed::X1 => 1,
+            Speed::X2 => 2,
+            Speed::X10 => 10,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Speed::X1 => Speed::X2,
+            Speed::X2 => Speed::X10,
+            Speed::X10 => Speed::X1,
+        }
+    }
+}
+
+impl std::fmt::Display for Speed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x", self.factor())
+    }
+}
+
+enum Frame {
+    Trades(Vec<Trade>),
+    Depth {
+        bids: Vec<(Price, Qty)>,
+        asks: Vec<(Price, Qty)>,
+    },
+}
+
+/// Events handed to panes, timestamped as they were recorded
+pub enum Output {
+    Trades(UnixMs, Vec<Trade>),
+    Depth(UnixMs, Arc<Depth>),
+}
+
+/// A loaded recording and the playback position within it
+pub struct Replay {
+    ticker_info: TickerInfo,
+    path: PathBuf,
+    /// Sorted by time, never empty
+    frames: Vec<(UnixMs, Frame)>,
+    /// Index of the first frame not yet played
+    next: usize,
+    book: Depth,
+    position: UnixMs,
+    speed: Speed,
+    /// Wall clock and recording time playback is measured from, `None` while paused
+    anchor: Option<(Instant, UnixMs)>,
+    /// Seek bar position while it's being dragged
+    scrub: Option<f32>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let invalid = |err: serde_json::Error| io::Error::new(io::ErrorKind::InvalidData, err);
+
+        let mut lines = BufReader::new(File::open(path)?).lines();
+
+        let header: Header = match lines.next() {
+            Some(line) => serde_json::from_str(&line?).map_err(invalid)?,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "empty recording",
+                ));
+            }
+        };
+
+        let mut frames = vec![];
+
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let frame = match serde_json::from_str::<Line>(&line) {
+                Ok(Line::Trades { time, trades }) => {
+                    let trades = trades
+                        .into_iter()
+                        .map(|(time, price, qty, is_sell)| Trade {
+                            time: UnixMs::new(time),
+                            is_sell,
+                            price: Price::from_f64(price),
+                            qty: Qty::from_f64(qty),
+                        })
+                        .collect();
+                    (UnixMs::new(time), Frame::Trades(trades))
+                }
+                Ok(Line::Depth { time, bids, asks }) => {
+                    let levels = |side: Vec<(f64, f64)>| {
+                        side.into_iter()
+                            .map(|(price, qty)| (Price::from_f64(price), Qty::from_f64(qty)))
+                            .collect()
+                    };
+                    (
+                        UnixMs::new(time),
+                        Frame::Depth {
+                            bids: levels(bids),
+                            asks: levels(asks),
+                        },
+                    )
+                }
+                // Recordings cut short by a crash end with a partial line
+                Err(err) => {
+                    log::warn!("Replay {} ends early: {err}", path.display());
+                    break;
+                }
+            };
+            frames.push(frame);
+        }
+
+        if frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "recording has no events",
+            ));
+        }
+
+        // Stable, so depth diffs from the same stream keep their order
+        frames.sort_by_key(|(time, _)| *time);
+        let position = frames[0].0;
+
+        Ok(Self {
+            ticker_info: header.ticker_info,
+            path: path.to_path_buf(),
+            frames,
+            next: 0,
+            book: Depth::default(),
+            position,
+            speed: Speed::default(),
+            anchor: None,
+            scrub: None,
+        })
+    }
+
+    pub fn ticker_info(&self) -> TickerInfo {
+        self.ticker_info
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    fn start_time(&self) -> UnixMs {
+        self.frames[0].0
+    }
+
+    fn end_time(&self) -> UnixMs {
+        self.frames[self.frames.len() - 1].0
+    }
+
+    /// Milliseconds played and total length of the recording
+    pub fn elapsed(&self) -> (u64, u64) {
+        let start = self.start_time().as_u64();
+        (
+            self.position.as_u64().saturating_sub(start),
+            self.end_time().as_u64().saturating_sub(start),
+        )
+    }
+
+    /// Fraction of the recording played, or where the seek bar is being dragged to
+    pub fn progress(&self) -> f32 {
+        if let Some(fraction) = self.scrub {
+            return fraction;
+        }
+
+        match self.elapsed() {
+            (_, 0) => 1.0,
+            (played, total) => (played as f64 / total as f64) as f32,
+        }
+    }
+
+    pub fn play(&mut self, now: Instant) {
+        if self.next >= self.frames.len() {
+            self.seek(0.0, now);
+        }
+        self.anchor = Some((now, self.position));
+    }
+
+    pub fn pause(&mut self) {
+        self.anchor = None;
+    }
+
+    pub fn toggle(&mut self, now: Instant) {
+        if self.is_playing() {
+            self.pause();
+        } else {
+            self.play(now);
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: Speed, now: Instant) {
+        self.speed = speed;
+        if self.is_playing() {
+            self.anchor = Some((now, self.position));
+        }
+    }
+
+    pub fn scrub(&mut self, fraction: f32) {
+        self.scrub = Some(fraction.clamp(0.0, 1.0));
+    }
+
+    /// Moves to the dragged seek bar position, see [`Replay::seek`]
+    pub fn finish_scrub(&mut self, now: Instant) {
+        if let Some(fraction) = self.scrub.take() {
+            self.seek(fraction, now);
+        }
+    }
+
+    /// Jumps to `fraction` of the recording, rebuilding the book up to there without
+    /// emitting anything, so panes should be cleared beforehand
+    pub fn seek(&mut self, fraction: f32, now: Instant) {
+        let (_, total) = self.elapsed();
+        let offset = (total as f64 * f64::from(fraction.clamp(0.0, 1.0))) as u64;
+        let target = self.start_time().saturating_add(offset);
+
+        self.book = Depth::default();
+        self.next = 0;
+
+        while let Some((time, frame)) = self.frames.get(self.next) {
+            if *time > target {
+                break;
+            }
+            if let Frame::Depth { bids, asks } = frame {
+                apply_levels(&mut self.book.bids, bids);
+                apply_levels(&mut self.book.asks, asks);
+            }
+            self.next += 1;
+        }
+
+        self.position = target;
+        if self.is_playing() {
+            self.anchor = Some((now, target));
+        }
+    }
+
+    /// Events due by `now` at the current speed, pausing at the end of the recording
+    pub fn advance(&mut self, now: Instant) -> Vec<Output> {
+        let Some((anchor_at, anchor_pos)) = self.anchor else {
+            return vec![];
+        };
+
+        let elapsed = now.saturating_duration_since(anchor_at).as_millis() as u64;
+        let target = anchor_pos.saturating_add(elapsed.saturating_mul(self.speed.factor()));
+
+        let mut output = vec![];
+
+        while let Some((time, frame)) = self.frames.get(self.next) {
+            if *time > target {
+                break;
+            }
+            match frame {
+                Frame::Trades(trades) => output.push(Output::Trades(*time, trades.clone())),
+                Frame::Depth { bids, asks } => {
+                    apply_levels(&mut self.book.bids, bids);
+                    apply_levels(&mut self.book.asks, asks);
+                    output.push(Output::Depth(*time, Arc::new(self.book.clone())));
+                }
+            }
+            self.next += 1;
+        }
+
+        if self.next >= self.frames.len() {
+            self.position = self.end_time();
+            self.anchor = None;
+        } else {
+            self.position = target;
+        }
+
+        output
+    }
+}
+
+fn apply_levels(side: &mut BTreeMap<Price, Qty>, levels: &[(Price, Qty)]) {
+    for (price, qty) in levels {
+        if *qty == Qty::ZERO {
+            side.remove(price);
+        } else {
+            side.insert(*price, *qty);
+        }
+    }
+}
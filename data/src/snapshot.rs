@@ -0,0 +1,444 @@
+//! PNG export of pane captures, and animated PNG time-lapses of them.
+
+use crate::chart::heatmap::EXPORTS_DIR;
+
+use flate2::{Compression, write::ZlibEncoder};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Integer upscale applied to the captured pixels
+    pub scale: u8,
+}
+
+impl SnapshotConfig {
+    pub const SCALES: [u8; 3] = [1, 2, 3];
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self { scale: 1 }
+    }
+}
+
+impl std::fmt::Display for SnapshotConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x", self.scale)
+    }
+}
+
+/// Settings for recording a pane as a time-lapse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TimelapseConfig {
+    /// Seconds between captured frames
+    pub interval_secs: u16,
+    /// Playback rate of the encoded animation
+    pub fps: u8,
+}
+
+impl Default for TimelapseConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 5,
+            fps: 10,
+        }
+    }
+}
+
+/// An RGBA8 image
+pub struct Capture {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Capture {
+    /// Repeats every pixel `factor` times along both axes
+    pub fn upscale(self, factor: u8) -> Self {
+        let factor = u32::from(factor.max(1));
+        if factor == 1 {
+            return self;
+        }
+
+        let (width, height) = (self.width * factor, self.height * factor);
+        let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+
+        for row in self.rgba.chunks_exact(self.width as usize * 4) {
+            let start = rgba.len();
+            for pixel in row.chunks_exact(4) {
+                for _ in 0..factor {
+                    rgba.extend_from_slice(pixel);
+                }
+            }
+            let scaled_row = rgba[start..].to_vec();
+            for _ in 1..factor {
+                rgba.extend_from_slice(&scaled_row);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            rgba,
+        }
+    }
+
+    /// Compressed image data, as carried by `IDAT` and `fdAT` chunks
+    fn compressed(&self) -> io::Result<Vec<u8>> {
+        let row_len = self.width as usize * 4;
+
+        // Chart captures are mostly flat color, the fastest level already does well on them
+        let mut encoder = ZlibEncoder::new(vec![], Compression::fast());
+        // Each scanline is prefixed with filter type 0 (none)
+        for row in self.rgba.chunks_exact(row_len) {
+            encoder.write_all(&[0])?;
+            encoder.write_all(row)?;
+        }
+
+        encoder.finish()
+    }
+
+    pub fn encode_png(&self) -> io::Result<Vec<u8>> {
+        let mut png = PNG_SIGNATURE.to_vec();
+        write_chunk(&mut png, b"IHDR", &ihdr(self.width, self.height))?;
+        write_chunk(&mut png, b"IDAT", &self.compressed()?)?;
+        write_chunk(&mut png, b"IEND", &[])?;
+        Ok(png)
+    }
+
+    /// Writes the capture to the exports folder, named after `label` and the current time
+    pub fn save_png(&self, label: &str) -> io::Result<PathBuf> {
+        let path = export_path("pane", label, "png")?;
+        fs::write(&path, self.encode_png()?)?;
+
+        Ok(path)
+    }
+}
+
+/// A time-lapse saved by [`Timelapse::record`]
+#[derive(Debug)]
+pub struct Recorded {
+    pub path: PathBuf,
+    pub frames: u32,
+    /// Frames dropped because the pane was resized while recording
+    pub skipped: u32,
+    /// Error that ended the recording early, the frames before it are kept
+    pub stopped_by: Option<io::Error>,
+}
+
+/// Records captures as the frames of an animated PNG.
+///
+/// Frames are compressed and appended to a `.part` file as they arrive, so a long session
+/// doesn't pile up in memory. The header has to state the frame count up front, which is
+/// why the playable file is only assembled by [`Timelapse::finish`].
+pub struct Timelapse {
+    path: PathBuf,
+    part_path: PathBuf,
+    body: BufWriter<File>,
+    /// Set by the first frame, the animation canvas can't change size afterwards
+    size: Option<(u32, u32)>,
+    fps: u8,
+    frames: u32,
+    skipped: u32,
+    /// Shared by `fcTL` and `fdAT` chunks
+    sequence: u32,
+}
+
+impl Timelapse {
+    pub fn start(label: &str, config: TimelapseConfig) -> io::Result<Self> {
+        let path = export_path("timelapse", label, "png")?;
+        let part_path = path.with_extension("png.part");
+        let body = BufWriter::new(File::create(&part_path)?);
+
+        Ok(Self {
+            path,
+            part_path,
+            body,
+            size: None,
+            fps: config.fps.max(1),
+            frames: 0,
+            skipped: 0,
+            sequence: 0,
+        })
+    }
+
+    /// Records the captures sent over `frames` until the sender hangs up, then assembles
+    /// the animation. Blocks throughout, meant to run on a thread of its own
+    pub fn record(
+        label: &str,
+        config: TimelapseConfig,
+        frames: mpsc::Receiver<Capture>,
+    ) -> io::Result<Recorded> {
+        let mut timelapse = Self::start(label, config)?;
+
+        let stopped_by = frames
+            .iter()
+            .find_map(|capture| timelapse.push(&capture).err());
+        let (frame_count, skipped) = (timelapse.frames(), timelapse.skipped());
+
+        match timelapse.finish() {
+            Ok(path) => Ok(Recorded {
+                path,
+                frames: frame_count,
+                skipped,
+                stopped_by,
+            }),
+            Err(err) => Err(stopped_by.unwrap_or(err)),
+        }
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Frames dropped because the pane was resized while recording
+    pub fn skipped(&self) -> u32 {
+        self.skipped
+    }
+
+    pub fn push(&mut self, capture: &Capture) -> io::Result<()> {
+        let size = (capture.width, capture.height);
+        if capture.width == 0 || capture.height == 0 || *self.size.get_or_insert(size) != size {
+            self.skipped += 1;
+            return Ok(());
+        }
+
+        let mut frame_control = Vec::with_capacity(26);
+        frame_control.extend_from_slice(&self.sequence.to_be_bytes());
+        frame_control.extend_from_slice(&capture.width.to_be_bytes());
+        frame_control.extend_from_slice(&capture.height.to_be_bytes());
+        // Offsets, then the delay as a fraction of a second
+        frame_control.extend_from_slice(&[0; 8]);
+        frame_control.extend_from_slice(&1u16.to_be_bytes());
+        frame_control.extend_from_slice(&u16::from(self.fps).to_be_bytes());
+        // Keep the previous frame, replace its pixels
+        frame_control.extend_from_slice(&[0, 0]);
+
+        let mut chunks = vec![];
+        write_chunk(&mut chunks, b"fcTL", &frame_control)?;
+        self.sequence += 1;
+
+        // The first frame doubles as the default image shown by non-animating viewers
+        if self.frames == 0 {
+            write_chunk(&mut chunks, b"IDAT", &capture.compressed()?)?;
+        } else {
+            let mut frame_data = self.sequence.to_be_bytes().to_vec();
+            frame_data.extend_from_slice(&capture.compressed()?);
+            write_chunk(&mut chunks, b"fdAT", &frame_data)?;
+            self.sequence += 1;
+        }
+
+        self.body.write_all(&chunks)?;
+        self.frames += 1;
+
+        Ok(())
+    }
+
+    /// Assembles the animation into the exports folder and removes the partial file
+    pub fn finish(mut self) -> io::Result<PathBuf> {
+        self.body.flush()?;
+        drop(self.body);
+
+        let Some((width, height)) = self.size.filter(|_| self.frames > 0) else {
+            let _ = fs::remove_file(&self.part_path);
+            return Err(io::Error::other("no frames were captured"));
+        };
+
+        let mut animation_control = Vec::with_capacity(8);
+        animation_control.extend_from_slice(&self.frames.to_be_bytes());
+        // Loop forever
+        animation_control.extend_from_slice(&0u32.to_be_bytes());
+
+        let mut header = PNG_SIGNATURE.to_vec();
+        write_chunk(&mut header, b"IHDR", &ihdr(width, height))?;
+        write_chunk(&mut header, b"acTL", &animation_control)?;
+
+        let mut trailer = vec![];
+        write_chunk(&mut trailer, b"IEND", &[])?;
+
+        let mut out = BufWriter::new(File::create(&self.path)?);
+        out.write_all(&header)?;
+        io::copy(&mut File::open(&self.part_path)?, &mut out)?;
+        out.write_all(&trailer)?;
+        out.flush()?;
+
+        fs::remove_file(&self.part_path)?;
+
+        Ok(self.path)
+    }
+}
+
+/// Partial time-lapses not written to for this long were left by a crash or a forced quit
+const STALE_PART_AGE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Removes time-lapse leftovers from the exports folder and, with `max_age`, the pane
+/// snapshots and time-lapses older than that. Other exports, like depth CSVs, are kept.
+/// Returns the number of removed files
+pub fn prune(max_age: Option<std::time::Duration>) -> usize {
+    let Ok(entries) = fs::read_dir(crate::data_path(Some(EXPORTS_DIR))) else {
+        return 0;
+    };
+    let now = std::time::SystemTime::now();
+
+    let mut removed = 0;
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(age) = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+        else {
+            continue;
+        };
+
+        let is_stale = if name.ends_with(".png.part") {
+            age > STALE_PART_AGE
+        } else if (name.starts_with("pane_") || name.starts_with("timelapse_"))
+            && name.ends_with(".png")
+        {
+            max_age.is_some_and(|max_age| age > max_age)
+        } else {
+            false
+        };
+
+        if is_stale {
+            match fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(err) => log::warn!("Failed to remove {}: {err}", path.display()),
+            }
+        }
+    }
+
+    removed
+}
+
+/// A fresh path in the exports folder, named after `label` and the current time
+fn export_path(prefix: &str, label: &str, ext: &str) -> io::Result<PathBuf> {
+    let dir = crate::data_path(Some(EXPORTS_DIR));
+    fs::create_dir_all(&dir)?;
+
+    let label = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    let file_name = format!(
+        "{prefix}_{label}_{}.{ext}",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+
+    Ok(dir.join(file_name))
+}
+
+fn ihdr(width: u32, height: u32) -> Vec<u8> {
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 8-bit depth, truecolor with alpha, deflate, adaptive filtering, no interlace
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+    ihdr
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "PNG chunk exceeds u32"))?;
+    out.extend_from_slice(&len.to_be_bytes());
+
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+
+    let crc = crc32fast::hash(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    /// Chunks of an encoded PNG, as (kind, data), checking each one's CRC on the way
+    fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+        assert_eq!(png[..8], PNG_SIGNATURE);
+
+        let mut chunks = vec![];
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (body, crc) = rest[4..8 + len + 4].split_at(4 + len);
+            assert_eq!(crc32fast::hash(body).to_be_bytes(), crc);
+
+            chunks.push((body[..4].try_into().unwrap(), body[4..].to_vec()));
+            rest = &rest[12 + len..];
+        }
+        chunks
+    }
+
+    #[test]
+    fn empty_chunk_has_the_reference_crc() {
+        let mut out = vec![];
+        write_chunk(&mut out, b"IEND", &[]).unwrap();
+
+        assert_eq!(
+            out,
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn png_round_trips_through_zlib() {
+        let capture = Capture {
+            width: 3,
+            height: 2,
+            rgba: (0..24).collect(),
+        };
+        let chunks = chunks(&capture.encode_png().unwrap());
+
+        let kinds = chunks.iter().map(|(kind, _)| kind).collect::<Vec<_>>();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, ihdr(3, 2));
+
+        let mut raw = vec![];
+        ZlibDecoder::new(&chunks[1].1[..])
+            .read_to_end(&mut raw)
+            .unwrap();
+        assert_eq!(
+            raw,
+            [
+                &[0][..],
+                &(0..12).collect::<Vec<u8>>(),
+                &[0],
+                &(12..24).collect::<Vec<u8>>(),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn upscale_repeats_pixels_along_both_axes() {
+        let capture = Capture {
+            width: 2,
+            height: 1,
+            rgba: vec![1, 1, 1, 1, 2, 2, 2, 2],
+        }
+        .upscale(2);
+
+        assert_eq!((capture.width, capture.height), (4, 2));
+        let row = [[1; 4], [1; 4], [2; 4], [2; 4]].concat();
+        assert_eq!(capture.rgba, [row.clone(), row].concat());
+    }
+}
@@ -1,4 +1,7 @@
-use exchange::{PushFrequency, Ticker, TickerInfo, Timeframe, adapter::StreamKind};
+use exchange::{
+    PushFrequency, Ticker, TickerInfo, Timeframe,
+    adapter::{CompositeSources, StreamKind},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -14,6 +17,16 @@ pub enum PersistStreamKind {
     /// Deprecated combined stream, kept for backward compatibility.
     /// Will be converted to separate Depth and Trades on load.
     DepthAndTrades(PersistDepth),
+    CompositeDepth {
+        tickers: Vec<Ticker>,
+        #[serde(default = "default_depth_aggr")]
+        depth_aggr: exchange::adapter::StreamTicksize,
+        #[serde(default = "default_push_freq")]
+        push_freq: PushFrequency,
+    },
+    MarkPrice {
+        ticker: Ticker,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -47,6 +60,18 @@ impl From<StreamKind> for PersistStreamKind {
             StreamKind::Trades { ticker_info } => PersistStreamKind::Trades {
                 ticker: ticker_info.ticker,
             },
+            StreamKind::CompositeDepth {
+                sources,
+                depth_aggr,
+                push_freq,
+            } => PersistStreamKind::CompositeDepth {
+                tickers: sources.iter().map(|ti| ti.ticker).collect(),
+                depth_aggr,
+                push_freq,
+            },
+            StreamKind::MarkPrice { ticker_info } => PersistStreamKind::MarkPrice {
+                ticker: ticker_info.ticker,
+            },
         }
     }
 }
@@ -79,6 +104,9 @@ impl PersistStreamKind {
             PersistStreamKind::Trades { ticker } => resolver(&ticker)
                 .map(|ti| vec![StreamKind::Trades { ticker_info: ti }])
                 .ok_or_else(|| format!("TickerInfo not found for {}", ticker)),
+            PersistStreamKind::MarkPrice { ticker } => resolver(&ticker)
+                .map(|ti| vec![StreamKind::MarkPrice { ticker_info: ti }])
+                .ok_or_else(|| format!("TickerInfo not found for {}", ticker)),
             PersistStreamKind::DepthAndTrades(d) => resolver(&d.ticker)
                 .map(|ti| {
                     vec![
@@ -91,6 +119,29 @@ impl PersistStreamKind {
                     ]
                 })
                 .ok_or_else(|| format!("TickerInfo not found for {}", d.ticker)),
+            PersistStreamKind::CompositeDepth {
+                tickers,
+                depth_aggr,
+                push_freq,
+            } => {
+                let infos = tickers
+                    .iter()
+                    .map(|ticker| {
+                        resolver(ticker)
+                            .ok_or_else(|| format!("TickerInfo not found for {}", ticker))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                CompositeSources::from_slice(&infos)
+                    .map(|sources| {
+                        vec![StreamKind::CompositeDepth {
+                            sources,
+                            depth_aggr,
+                            push_freq,
+                        }]
+                    })
+                    .ok_or_else(|| format!("Invalid composite depth sources: {:?}", tickers))
+            }
         }
     }
 }
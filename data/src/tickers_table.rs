@@ -1,10 +1,21 @@
 use exchange::{
-    Ticker, TickerStats,
+    Kline, Ticker, TickerStats, Timeframe, UnixMs,
     adapter::{Exchange, MarketKind, Venue},
     unit::{MinTicksize, price::Price},
 };
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+/// Candles a ticker card's sparkline is drawn from, the last [`SPARKLINE_WINDOW_MS`] of them
+pub const SPARKLINE_TIMEFRAME: Timeframe = Timeframe::H1;
+pub const SPARKLINE_WINDOW_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Interval the movers' price change is measured over
+pub const MOVERS_CHANGE_WINDOW_MS: u64 = 5 * 60 * 1000;
+/// Days of daily candles a ticker's usual volume is averaged from
+pub const VOLUME_AVERAGE_DAYS: u64 = 30;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Settings {
@@ -13,6 +24,11 @@ pub struct Settings {
     pub selected_sort_option: SortOptions,
     pub selected_exchanges: Vec<Venue>,
     pub selected_markets: Vec<MarketKind>,
+    #[serde(default)]
+    pub screener: ScreenerFilters,
+    /// Ranks the tickers by this instead of the sort option while set
+    #[serde(default)]
+    pub movers: Option<MoverMetric>,
 }
 
 impl Default for Settings {
@@ -23,8 +39,194 @@ impl Default for Settings {
             selected_sort_option: SortOptions::VolumeDesc,
             selected_exchanges: Venue::ALL.to_vec(),
             selected_markets: MarketKind::ALL.into_iter().collect(),
+            screener: ScreenerFilters::default(),
+            movers: None,
+        }
+    }
+}
+
+/// Short interval metrics the movers view ranks by, the largest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MoverMetric {
+    /// Price change over [`MOVERS_CHANGE_WINDOW_MS`], either way
+    Change,
+    /// 24h volume against the daily average of the last [`VOLUME_AVERAGE_DAYS`]
+    VolumeRatio,
+}
+
+impl MoverMetric {
+    pub const ALL: [MoverMetric; 2] = [MoverMetric::Change, MoverMetric::VolumeRatio];
+}
+
+impl std::fmt::Display for MoverMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoverMetric::Change => write!(f, "5m change"),
+            MoverMetric::VolumeRatio => write!(f, "Volume / 30d"),
+        }
+    }
+}
+
+/// Prices of every ticker from the periodic stats snapshots and their usual daily volume,
+/// what the movers are worked out from
+#[derive(Debug, Default)]
+pub struct MoverTracker {
+    prices: FxHashMap<Ticker, VecDeque<(UnixMs, f64)>>,
+    /// Mean daily volume in USD, `None` if the venue had too little history
+    daily_averages: FxHashMap<Ticker, Option<f64>>,
+}
+
+impl MoverTracker {
+    pub fn record(&mut self, ticker: Ticker, time: UnixMs, price: f64) {
+        let prices = self.prices.entry(ticker).or_default();
+        prices.push_back((time, price));
+
+        // Keeps the newest reading from before the window, it's what the change is against
+        let cutoff = time.saturating_sub(MOVERS_CHANGE_WINDOW_MS);
+        while prices.get(1).is_some_and(|(time, _)| *time <= cutoff) {
+            prices.pop_front();
+        }
+    }
+
+    /// Change in percent since [`MOVERS_CHANGE_WINDOW_MS`] ago, `None` until the
+    /// snapshots go back that far
+    pub fn change(&self, ticker: &Ticker) -> Option<f32> {
+        let prices = self.prices.get(ticker)?;
+        let (latest_time, latest) = *prices.back()?;
+        let (base_time, base) = *prices.front()?;
+
+        if base_time > latest_time.saturating_sub(MOVERS_CHANGE_WINDOW_MS) || base <= 0.0 {
+            return None;
+        }
+        Some(((latest - base) / base * 100.0) as f32)
+    }
+
+    pub fn has_daily_average(&self, ticker: &Ticker) -> bool {
+        self.daily_averages.contains_key(ticker)
+    }
+
+    pub fn set_daily_average(&mut self, ticker: Ticker, average: Option<f64>) {
+        self.daily_averages.insert(ticker, average);
+    }
+
+    /// 24h volume as a multiple of the usual daily volume
+    pub fn volume_ratio(&self, ticker: &Ticker, daily_volume: f64) -> Option<f32> {
+        let average = (*self.daily_averages.get(ticker)?)?;
+        (average > 0.0).then(|| (daily_volume / average) as f32)
+    }
+}
+
+/// Mean daily volume in USD of the days that have closed, `None` without any
+pub fn average_daily_volume(
+    klines: &[Kline],
+    market: MarketKind,
+    size_in_quote: bool,
+) -> Option<f64> {
+    let mut klines = klines.iter().collect::<Vec<_>>();
+    klines.sort_by_key(|kline| kline.time);
+    // The last one is today, still forming
+    let (_, closed) = klines.split_last()?;
+    if closed.is_empty() {
+        return None;
+    }
+
+    let total = closed
+        .iter()
+        .map(|kline| market.qty_in_quote_value(kline.volume.total(), kline.close, size_in_quote))
+        .sum::<f64>();
+    Some(total / closed.len() as f64)
+}
+
+/// Thresholds a ticker's stats have to meet to be listed, on top of the exchange and
+/// market filters. Each is off when `None`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenerFilters {
+    /// 24h volume in USD
+    pub min_volume: Option<f64>,
+    /// 24h change in percent, either way
+    pub min_abs_change: Option<f64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+}
+
+impl ScreenerFilters {
+    pub fn is_active(&self) -> bool {
+        ScreenerField::ALL
+            .iter()
+            .any(|field| self.get(*field).is_some())
+    }
+
+    pub fn get(&self, field: ScreenerField) -> Option<f64> {
+        match field {
+            ScreenerField::MinVolume => self.min_volume,
+            ScreenerField::MinAbsChange => self.min_abs_change,
+            ScreenerField::MinPrice => self.min_price,
+            ScreenerField::MaxPrice => self.max_price,
+        }
+    }
+
+    pub fn set(&mut self, field: ScreenerField, value: Option<f64>) {
+        match field {
+            ScreenerField::MinVolume => self.min_volume = value,
+            ScreenerField::MinAbsChange => self.min_abs_change = value,
+            ScreenerField::MinPrice => self.min_price = value,
+            ScreenerField::MaxPrice => self.max_price = value,
+        }
+    }
+
+    pub fn matches(&self, stats: &TickerStats) -> bool {
+        let price = stats.mark_price.to_f64();
+
+        self.min_volume
+            .is_none_or(|min| stats.daily_volume.to_f64() >= min)
+            && self
+                .min_abs_change
+                .is_none_or(|min| f64::from(stats.daily_price_chg.abs()) >= min)
+            && self.min_price.is_none_or(|min| price >= min)
+            && self.max_price.is_none_or(|max| price <= max)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenerField {
+    MinVolume,
+    MinAbsChange,
+    MinPrice,
+    MaxPrice,
+}
+
+impl ScreenerField {
+    pub const ALL: [ScreenerField; 4] = [
+        ScreenerField::MinVolume,
+        ScreenerField::MinAbsChange,
+        ScreenerField::MinPrice,
+        ScreenerField::MaxPrice,
+    ];
+
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            ScreenerField::MinVolume => "Volume >",
+            ScreenerField::MinAbsChange => "|Change| % >",
+            ScreenerField::MinPrice => "Price >",
+            ScreenerField::MaxPrice => "Price <",
         }
     }
+
+    /// Reads a threshold as typed, `K`, `M` and `B` suffixes included, `None` unless it's a
+    /// non-negative number
+    pub fn parse(input: &str) -> Option<f64> {
+        let input = input.trim();
+
+        let (number, multiplier) = match input.chars().last().map(|c| c.to_ascii_uppercase()) {
+            Some('K') => (&input[..input.len() - 1], 1e3),
+            Some('M') => (&input[..input.len() - 1], 1e6),
+            Some('B') => (&input[..input.len() - 1], 1e9),
+            _ => (input, 1.0),
+        };
+
+        let value = number.trim().parse::<f64>().ok()?;
+        (value.is_finite() && value >= 0.0).then_some(value * multiplier)
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
@@ -34,6 +236,45 @@ pub enum SortOptions {
     VolumeDesc,
     ChangeAsc,
     ChangeDesc,
+    OpenInterestAsc,
+    OpenInterestDesc,
+    FundingAsc,
+    FundingDesc,
+    SpreadAsc,
+    SpreadDesc,
+}
+
+impl SortOptions {
+    /// Ascending and descending variants of the same column
+    pub fn pair(self) -> (SortOptions, SortOptions) {
+        match self {
+            SortOptions::VolumeAsc | SortOptions::VolumeDesc => {
+                (SortOptions::VolumeAsc, SortOptions::VolumeDesc)
+            }
+            SortOptions::ChangeAsc | SortOptions::ChangeDesc => {
+                (SortOptions::ChangeAsc, SortOptions::ChangeDesc)
+            }
+            SortOptions::OpenInterestAsc | SortOptions::OpenInterestDesc => {
+                (SortOptions::OpenInterestAsc, SortOptions::OpenInterestDesc)
+            }
+            SortOptions::FundingAsc | SortOptions::FundingDesc => {
+                (SortOptions::FundingAsc, SortOptions::FundingDesc)
+            }
+            SortOptions::SpreadAsc | SortOptions::SpreadDesc => {
+                (SortOptions::SpreadAsc, SortOptions::SpreadDesc)
+            }
+        }
+    }
+
+    pub fn reversed(self) -> SortOptions {
+        let (asc, desc) = self.pair();
+        if self == asc { desc } else { asc }
+    }
+
+    /// Whether both sort by the same column
+    pub fn same_column(self, other: SortOptions) -> bool {
+        self.pair() == other.pair()
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -42,6 +283,113 @@ pub enum PriceChange {
     Decreased,
 }
 
+impl PriceChange {
+    /// Whether a 24h change in percent is in this direction
+    pub fn matches(self, daily_price_chg: f32) -> bool {
+        match self {
+            PriceChange::Increased => daily_price_chg > 0.0,
+            PriceChange::Decreased => daily_price_chg < 0.0,
+        }
+    }
+}
+
+/// Market breadth over a set of tickers, e.g. the favorited ones
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Breadth {
+    pub advancing: usize,
+    pub declining: usize,
+    pub unchanged: usize,
+    /// Mean 24h change in percent
+    pub avg_change: f32,
+    /// Summed 24h volume in USD
+    pub total_volume: f64,
+}
+
+impl Breadth {
+    /// `None` if there are no rows to summarize
+    pub fn from_rows<'a>(rows: impl IntoIterator<Item = &'a TickerRowData>) -> Option<Self> {
+        let mut breadth = Breadth::default();
+        let mut change_sum = 0.0;
+
+        for row in rows {
+            let change = row.stats.daily_price_chg;
+
+            if PriceChange::Increased.matches(change) {
+                breadth.advancing += 1;
+            } else if PriceChange::Decreased.matches(change) {
+                breadth.declining += 1;
+            } else {
+                breadth.unchanged += 1;
+            }
+
+            change_sum += f64::from(change);
+            breadth.total_volume += row.stats.daily_volume.to_f64();
+        }
+
+        let count = breadth.count();
+        if count == 0 {
+            return None;
+        }
+
+        breadth.avg_change = (change_sum / count as f64) as f32;
+        Some(breadth)
+    }
+
+    pub fn count(&self) -> usize {
+        self.advancing + self.declining + self.unchanged
+    }
+}
+
+/// A ticker's closes over the last day, scaled for a card's sparkline
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sparkline {
+    /// From the oldest, `0.0` at the lowest close and `1.0` at the highest
+    pub points: Vec<f32>,
+    /// Last close against the first, `None` if flat
+    pub direction: Option<PriceChange>,
+}
+
+impl Sparkline {
+    /// `None` with fewer than two candles to draw a line between
+    pub fn from_klines(klines: &[Kline]) -> Option<Self> {
+        let mut klines = klines.iter().collect::<Vec<_>>();
+        klines.sort_by_key(|kline| kline.time);
+
+        let closes = klines
+            .iter()
+            .map(|kline| kline.close.to_f64())
+            .collect::<Vec<_>>();
+        let (first, last) = (*closes.first()?, *closes.last()?);
+        if closes.len() < 2 {
+            return None;
+        }
+
+        let low = closes.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = closes.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = high - low;
+
+        Some(Self {
+            points: closes
+                .iter()
+                .map(|close| {
+                    if range > 0.0 {
+                        ((close - low) / range) as f32
+                    } else {
+                        0.5
+                    }
+                })
+                .collect(),
+            direction: if last > first {
+                Some(PriceChange::Increased)
+            } else if last < first {
+                Some(PriceChange::Decreased)
+            } else {
+                None
+            },
+        })
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct TickerRowData {
     pub exchange: Exchange,
@@ -49,6 +397,24 @@ pub struct TickerRowData {
     pub stats: TickerStats,
     pub previous_stats: Option<TickerStats>,
     pub is_favorited: bool,
+    /// See [`MoverTracker::change`]
+    pub mover_change: Option<f32>,
+    /// See [`MoverTracker::volume_ratio`]
+    pub volume_ratio: Option<f32>,
+}
+
+impl TickerRowData {
+    /// The row's value of `metric` as shown on its card
+    pub fn mover_display(&self, metric: MoverMetric) -> String {
+        match metric {
+            MoverMetric::Change => self
+                .mover_change
+                .map_or_else(|| "-".to_string(), super::util::pct_change),
+            MoverMetric::VolumeRatio => self
+                .volume_ratio
+                .map_or_else(|| "-".to_string(), |ratio| format!("{ratio:.1}x")),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -56,11 +422,35 @@ pub struct TickerDisplayData {
     pub display_ticker: String,
     pub daily_change_pct: String,
     pub volume_display: String,
+    pub open_interest_display: Option<String>,
+    pub funding_display: Option<String>,
+    pub spread_display: Option<String>,
     pub mark_price_display: Option<String>,
     pub price_unchanged_part: Option<String>,
     pub price_changed_part: Option<String>,
     pub price_change: Option<PriceChange>,
     pub card_color_alpha: f32,
+    /// Fetched apart from the stats, for the cards in view
+    pub sparkline: Option<Sparkline>,
+}
+
+impl TickerDisplayData {
+    /// Value shown on the card's second line, the one of the column sorted by when it
+    /// isn't price change
+    pub fn sorted_value(&self, sort: SortOptions) -> &str {
+        let value = match sort {
+            SortOptions::VolumeAsc
+            | SortOptions::VolumeDesc
+            | SortOptions::ChangeAsc
+            | SortOptions::ChangeDesc => return &self.volume_display,
+            SortOptions::OpenInterestAsc | SortOptions::OpenInterestDesc => {
+                &self.open_interest_display
+            }
+            SortOptions::FundingAsc | SortOptions::FundingDesc => &self.funding_display,
+            SortOptions::SpreadAsc | SortOptions::SpreadDesc => &self.spread_display,
+        };
+        value.as_deref().unwrap_or("-")
+    }
 }
 
 pub fn compare_ticker_rows_by_sort(
@@ -73,6 +463,49 @@ pub fn compare_ticker_rows_by_sort(
         SortOptions::VolumeAsc => a.stats.daily_volume.cmp(&b.stats.daily_volume),
         SortOptions::ChangeDesc => b.stats.daily_price_chg.total_cmp(&a.stats.daily_price_chg),
         SortOptions::ChangeAsc => a.stats.daily_price_chg.total_cmp(&b.stats.daily_price_chg),
+        SortOptions::OpenInterestDesc => {
+            cmp_listed(a.stats.open_interest, b.stats.open_interest, true)
+        }
+        SortOptions::OpenInterestAsc => {
+            cmp_listed(a.stats.open_interest, b.stats.open_interest, false)
+        }
+        SortOptions::FundingDesc => cmp_listed(a.stats.funding_rate, b.stats.funding_rate, true),
+        SortOptions::FundingAsc => cmp_listed(a.stats.funding_rate, b.stats.funding_rate, false),
+        SortOptions::SpreadDesc => cmp_listed(a.stats.spread_bps, b.stats.spread_bps, true),
+        SortOptions::SpreadAsc => cmp_listed(a.stats.spread_bps, b.stats.spread_bps, false),
+    }
+}
+
+/// Largest first, by size for the price change so moves both ways rank together
+pub fn compare_ticker_rows_by_mover(
+    a: &TickerRowData,
+    b: &TickerRowData,
+    metric: MoverMetric,
+) -> Ordering {
+    match metric {
+        MoverMetric::Change => cmp_listed(
+            a.mover_change.map(f32::abs),
+            b.mover_change.map(f32::abs),
+            true,
+        ),
+        MoverMetric::VolumeRatio => cmp_listed(a.volume_ratio, b.volume_ratio, true),
+    }
+}
+
+/// Orders the listed values, the tickers without one go last either way
+fn cmp_listed<T: PartialOrd>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
     }
 }
 
@@ -197,11 +630,19 @@ pub fn compute_display_data(
         display_ticker,
         daily_change_pct: super::util::pct_change(stats.daily_price_chg),
         volume_display: super::util::currency_abbr(stats.daily_volume.to_f32_lossy()),
+        open_interest_display: stats
+            .open_interest
+            .map(|oi| super::util::currency_abbr(oi as f32)),
+        funding_display: stats
+            .funding_rate
+            .map(|rate| format!("{:+.4}%", rate * 100.0)),
+        spread_display: stats.spread_bps.map(|bps| format!("{bps:.1}bp")),
         mark_price_display: current_price_display,
         price_unchanged_part,
         price_changed_part,
         price_change,
         card_color_alpha: { (stats.daily_price_chg / 8.0).clamp(-1.0, 1.0) },
+        sparkline: None,
     }
 }
 
@@ -0,0 +1,391 @@
+//! On-disk store of historical trades, used to backfill footprint charts without
+//! downloading the same history again.
+//!
+//! Trades are partitioned per ticker, size unit and UTC day under `market_data/ticks`. Each
+//! partition is a `.bin` file of fixed-size records, appended as batches are fetched, with a
+//! `.json` sidecar tracking which time spans of the day are known to be complete:
+//!
+//! ```text
+//! market_data/ticks/BinanceLinear/BTCUSDT/quote/2024-06-10.bin
+//! market_data/ticks/BinanceLinear/BTCUSDT/quote/2024-06-10.json
+//! ```
+//!
+//! Batches that arrive out of order leave a partition unsorted, reads sort it in memory
+//! and [`TickStore::compact`] rewrites it in place.
+
+use exchange::{
+    TickerInfo, Trade, UnixMs,
+    unit::{Price, Qty, qty::volume_size_unit},
+};
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub const TICKS_DIR: &str = "market_data/ticks";
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// `time: u64`, `price units: i64`, `qty units: i64`, `is_sell: u8`, little-endian
+const RECORD_LEN: usize = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Partitions for days older than this are deleted
+    pub days: u16,
+}
+
+impl RetentionPolicy {
+    pub const DAY_OPTIONS: [u16; 5] = [1, 4, 7, 14, 30];
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self { days: 7 }
+    }
+}
+
+impl std::fmt::Display for RetentionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.days {
+            1 => write!(f, "1 day"),
+            days => write!(f, "{days} days"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct Sidecar {
+    /// Disjoint, ascending `[start, end]` spans whose trades are all stored
+    covered: Vec<(u64, u64)>,
+    /// Time of the last appended record
+    last_time: u64,
+    /// Whether records are in time order, cleared by out of order appends
+    sorted: bool,
+}
+
+impl Sidecar {
+    fn covers(&self, time: u64) -> Option<u64> {
+        self.covered
+            .iter()
+            .find(|(start, end)| (*start..=*end).contains(&time))
+            .map(|(_, end)| *end)
+    }
+
+    fn is_covered(&self, time: u64) -> bool {
+        self.covers(time).is_some()
+    }
+
+    fn add_span(&mut self, start: u64, end: u64) {
+        self.covered.push((start, end));
+        self.covered.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.covered.len());
+        for (start, end) in self.covered.drain(..) {
+            match merged.last_mut() {
+                Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                    *last_end = (*last_end).max(end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        self.covered = merged;
+    }
+}
+
+pub struct TickStore {
+    root: PathBuf,
+}
+
+impl Default for TickStore {
+    fn default() -> Self {
+        Self::new(crate::data_path(Some(TICKS_DIR)))
+    }
+}
+
+impl TickStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn ticker_dir(&self, ticker_info: &TickerInfo) -> PathBuf {
+        let unit = match volume_size_unit() {
+            exchange::SizeUnit::Base => "base",
+            exchange::SizeUnit::Quote => "quote",
+        };
+
+        self.root
+            .join(format!("{:?}", ticker_info.exchange()))
+            .join(ticker_info.ticker.to_string())
+            .join(unit)
+    }
+
+    fn partition(dir: &Path, day_start: u64) -> (PathBuf, PathBuf) {
+        let name = UnixMs::new(day_start)
+            .format_utc("%Y-%m-%d")
+            .unwrap_or_else(|| day_start.to_string());
+
+        (
+            dir.join(format!("{name}.bin")),
+            dir.join(format!("{name}.json")),
+        )
+    }
+
+    fn read_sidecar(path: &Path) -> Sidecar {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or(Sidecar {
+                sorted: true,
+                ..Sidecar::default()
+            })
+    }
+
+    fn write_sidecar(path: &Path, sidecar: &Sidecar) -> io::Result<()> {
+        let json = serde_json::to_string(sidecar).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// End of the stored span that starts at or before `from`, walking across days.
+    /// `None` if `from` itself hasn't been stored
+    pub fn covered_until(&self, ticker_info: &TickerInfo, from: UnixMs) -> Option<UnixMs> {
+        let dir = self.ticker_dir(ticker_info);
+
+        let mut time = from.as_u64();
+        let mut covered_end = None;
+
+        loop {
+            let day_start = time - time % DAY_MS;
+            let (_, sidecar_path) = Self::partition(&dir, day_start);
+
+            let Some(end) = Self::read_sidecar(&sidecar_path).covers(time) else {
+                break;
+            };
+            covered_end = Some(end);
+
+            // Spans are clipped to their day, one reaching midnight may continue the next day
+            if end != day_start + DAY_MS - 1 {
+                break;
+            }
+            time = end + 1;
+        }
+
+        covered_end.map(UnixMs::new)
+    }
+
+    /// Stored trades within `[from, to]`, oldest first
+    pub fn read(
+        &self,
+        ticker_info: &TickerInfo,
+        from: UnixMs,
+        to: UnixMs,
+    ) -> io::Result<Vec<Trade>> {
+        let dir = self.ticker_dir(ticker_info);
+        let (from, to) = (from.as_u64(), to.as_u64());
+
+        let mut trades = vec![];
+        let mut day_start = from - from % DAY_MS;
+
+        while day_start <= to {
+            let (data_path, sidecar_path) = Self::partition(&dir, day_start);
+
+            if data_path.exists() {
+                let mut records = read_records(&data_path)?;
+                if !Self::read_sidecar(&sidecar_path).sorted {
+                    sort_dedup(&mut records);
+                }
+                trades.extend(
+                    records
+                        .into_iter()
+                        .filter(|trade| (from..=to).contains(&trade.time.as_u64())),
+                );
+            }
+
+            day_start += DAY_MS;
+        }
+
+        Ok(trades)
+    }
+
+    /// Stores the trades of a fetched batch within `[from, to]`, marking that span as
+    /// complete. Trades in spans that were already stored are skipped, so overlapping
+    /// batches don't duplicate records
+    pub fn insert(
+        &self,
+        ticker_info: &TickerInfo,
+        from: UnixMs,
+        to: UnixMs,
+        trades: &[Trade],
+    ) -> io::Result<()> {
+        let (from, to) = (from.as_u64(), to.as_u64());
+        if to < from {
+            return Ok(());
+        }
+
+        let dir = self.ticker_dir(ticker_info);
+        fs::create_dir_all(&dir)?;
+
+        let mut day_start = from - from % DAY_MS;
+
+        while day_start <= to {
+            let day_end = day_start + DAY_MS - 1;
+            let (data_path, sidecar_path) = Self::partition(&dir, day_start);
+            let mut sidecar = Self::read_sidecar(&sidecar_path);
+
+            let fresh = trades
+                .iter()
+                .filter(|trade| {
+                    let time = trade.time.as_u64();
+                    (from.max(day_start)..=to.min(day_end)).contains(&time)
+                        && !sidecar.is_covered(time)
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(first) = fresh.first() {
+                if first.time.as_u64() < sidecar.last_time {
+                    sidecar.sorted = false;
+                }
+
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&data_path)?;
+                let mut out = BufWriter::new(file);
+                for trade in &fresh {
+                    out.write_all(&encode(trade))?;
+                    sidecar.last_time = sidecar.last_time.max(trade.time.as_u64());
+                }
+                out.flush()?;
+            }
+
+            sidecar.add_span(from.max(day_start), to.min(day_end));
+            Self::write_sidecar(&sidecar_path, &sidecar)?;
+
+            day_start += DAY_MS;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes partitions older than the retention and rewrites unsorted ones in time
+    /// order. Returns the number of partitions removed
+    pub fn compact(&self, retention: RetentionPolicy) -> usize {
+        let cutoff =
+            chrono::Utc::now().date_naive() - chrono::Duration::days(i64::from(retention.days));
+
+        let mut removed = 0;
+
+        for data_path in partition_files(&self.root) {
+            let Some(date) = data_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| chrono::NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok())
+            else {
+                continue;
+            };
+            let sidecar_path = data_path.with_extension("json");
+
+            if date < cutoff {
+                let _ = fs::remove_file(&sidecar_path);
+                match fs::remove_file(&data_path) {
+                    Ok(()) => removed += 1,
+                    Err(err) => log::error!("Failed to remove {}: {err}", data_path.display()),
+                }
+                continue;
+            }
+
+            let mut sidecar = Self::read_sidecar(&sidecar_path);
+            if sidecar.sorted {
+                continue;
+            }
+
+            let result = read_records(&data_path).and_then(|mut records| {
+                sort_dedup(&mut records);
+                write_records(&data_path, &records)?;
+
+                sidecar.sorted = true;
+                Self::write_sidecar(&sidecar_path, &sidecar)
+            });
+            if let Err(err) = result {
+                log::error!("Failed to compact {}: {err}", data_path.display());
+            }
+        }
+
+        removed
+    }
+}
+
+fn partition_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "bin") {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+}
+
+fn encode(trade: &Trade) -> [u8; RECORD_LEN] {
+    let mut record = [0u8; RECORD_LEN];
+    record[0..8].copy_from_slice(&trade.time.as_u64().to_le_bytes());
+    record[8..16].copy_from_slice(&trade.price.units.to_le_bytes());
+    record[16..24].copy_from_slice(&trade.qty.units.to_le_bytes());
+    record[24] = u8::from(trade.is_sell);
+    record
+}
+
+fn decode(record: &[u8]) -> Trade {
+    let field = |range: std::ops::Range<usize>| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&record[range]);
+        bytes
+    };
+
+    Trade {
+        time: UnixMs::new(u64::from_le_bytes(field(0..8))),
+        price: Price::from_units(i64::from_le_bytes(field(8..16))),
+        qty: Qty::from_units(i64::from_le_bytes(field(16..24))),
+        is_sell: record[24] != 0,
+    }
+}
+
+fn read_records(path: &Path) -> io::Result<Vec<Trade>> {
+    let mut bytes = vec![];
+    BufReader::new(File::open(path)?).read_to_end(&mut bytes)?;
+
+    // A trailing partial record is left by an interrupted append
+    Ok(bytes.chunks_exact(RECORD_LEN).map(decode).collect())
+}
+
+fn write_records(path: &Path, trades: &[Trade]) -> io::Result<()> {
+    let tmp_path = path.with_extension("bin.tmp");
+
+    let mut out = BufWriter::new(File::create(&tmp_path)?);
+    for trade in trades {
+        out.write_all(&encode(trade))?;
+    }
+    out.flush()?;
+    drop(out);
+
+    fs::rename(tmp_path, path)
+}
+
+fn sort_dedup(trades: &mut Vec<Trade>) {
+    trades.sort_by_key(|trade| trade.time);
+    trades.dedup_by(|a, b| {
+        a.time == b.time && a.price == b.price && a.qty == b.qty && a.is_sell == b.is_sell
+    });
+}
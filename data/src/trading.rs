@@ -0,0 +1,164 @@
+//! Order entry settings and the API keys it signs requests with.
+//!
+//! Keys are kept in the OS keychain, one entry per venue, never in the saved state.
+
+pub mod journal;
+pub mod paper;
+
+use exchange::adapter::Venue;
+use exchange::adapter::trading::{Credentials, OrderSide, Position, WorkingOrder};
+use exchange::unit::{Price, Qty};
+use exchange::{Ticker, UnixMs};
+
+use serde::{Deserialize, Serialize};
+
+const KEYCHAIN_SERVICE: &str = "flowsurface.trading";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TradingConfig {
+    /// Off by default, ladders only place orders once this is turned on
+    pub enabled: bool,
+    /// Ladder orders are filled by [`paper::PaperAccount`] instead of being sent to the venue
+    pub paper: bool,
+    /// Quantity a ladder click orders, in the venue's order unit
+    order_sizes: Vec<(Ticker, f64)>,
+}
+
+impl TradingConfig {
+    pub fn order_size(&self, ticker: Ticker) -> Option<f64> {
+        self.order_sizes
+            .iter()
+            .find_map(|(t, qty)| (*t == ticker).then_some(*qty))
+    }
+
+    pub fn set_order_size(&mut self, ticker: Ticker, qty: f64) {
+        match self.order_sizes.iter_mut().find(|(t, _)| *t == ticker) {
+            Some((_, size)) => *size = qty,
+            None => self.order_sizes.push((ticker, qty)),
+        }
+    }
+
+    pub fn remove_order_size(&mut self, ticker: Ticker) {
+        self.order_sizes.retain(|(t, _)| *t != ticker);
+    }
+
+    pub fn order_sizes(&self) -> impl Iterator<Item = (Ticker, f64)> + '_ {
+        self.order_sizes.iter().copied()
+    }
+}
+
+/// Working orders and positions of one ticker, as last fetched
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Account {
+    pub orders: Vec<WorkingOrder>,
+    pub positions: Vec<Position>,
+    /// Executions charts mark, only tracked for paper trading
+    pub fills: Vec<Fill>,
+}
+
+/// An execution of one of the paper account's orders
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    pub time: UnixMs,
+    pub side: OrderSide,
+    pub price: Price,
+    pub qty: Qty,
+}
+
+impl Account {
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty() && self.positions.is_empty() && self.fills.is_empty()
+    }
+
+    /// Horizontal levels charts draw for the ticker
+    pub fn chart_levels(&self) -> impl Iterator<Item = ChartLevel> + Clone + '_ {
+        let positions = self.positions.iter().flat_map(|position| {
+            let is_long = position.side == OrderSide::Buy;
+
+            std::iter::once(ChartLevel::Entry {
+                price: position.entry_price,
+                is_long,
+                qty: position.qty,
+            })
+            .chain(position.liquidation_price.map(ChartLevel::Liquidation))
+        });
+
+        let orders = self.orders.iter().map(|order| ChartLevel::Order {
+            price: order.price,
+            is_buy: order.side == OrderSide::Buy,
+            qty: order.remaining(),
+        });
+
+        positions.chain(orders)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartLevel {
+    Entry {
+        price: Price,
+        is_long: bool,
+        qty: Qty,
+    },
+    Liquidation(Price),
+    Order {
+        price: Price,
+        is_buy: bool,
+        qty: Qty,
+    },
+}
+
+impl ChartLevel {
+    pub fn price(&self) -> Price {
+        match self {
+            ChartLevel::Entry { price, .. }
+            | ChartLevel::Liquidation(price)
+            | ChartLevel::Order { price, .. } => *price,
+        }
+    }
+}
+
+fn entry(venue: Venue) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &venue.to_string())
+        .map_err(|err| format!("Keychain unavailable: {err}"))
+}
+
+pub fn load_credentials(venue: Venue) -> Credentials {
+    let entry = match entry(venue) {
+        Ok(entry) => entry,
+        Err(err) => {
+            log::warn!("{err} (service={KEYCHAIN_SERVICE}, venue={venue})");
+            return Credentials::default();
+        }
+    };
+
+    match entry.get_password() {
+        Ok(secret) => serde_json::from_str(&secret).unwrap_or_else(|err| {
+            log::warn!("{venue} API key in keychain is invalid JSON: {err}");
+            Credentials::default()
+        }),
+        Err(keyring::Error::NoEntry) => Credentials::default(),
+        Err(err) => {
+            log::warn!("Failed to read {venue} API key from keychain: {err}");
+            Credentials::default()
+        }
+    }
+}
+
+/// Stores the key for `venue`, clearing its entry when both fields are empty
+pub fn save_credentials(venue: Venue, credentials: &Credentials) -> Result<(), String> {
+    let entry = entry(venue)?;
+
+    if *credentials == Credentials::default() {
+        return match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(format!("Failed to clear keychain entry: {err}")),
+        };
+    }
+
+    let secret = serde_json::to_string(credentials).map_err(|err| err.to_string())?;
+    entry
+        .set_password(&secret)
+        .map_err(|err| format!("Failed to store in keychain: {err}"))
+}
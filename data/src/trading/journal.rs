@@ -0,0 +1,210 @@
+//! Executed and manually logged trades, kept on disk for review.
+//!
+//! Live executions are imported from the venue, paper fills as they happen. Entries are
+//! replayed per ticker to tell entries from exits and what each exit realized.
+
+use super::paper::Pnl;
+
+use exchange::adapter::trading::OrderSide;
+use exchange::unit::Price;
+use exchange::{Ticker, UnixMs};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+const JOURNAL_FILE: &str = "journal.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct JournalConfig {
+    /// Entries are marked on kline charts of their ticker
+    pub plot_on_charts: bool,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            plot_on_charts: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Source {
+    Live,
+    Paper,
+    Manual,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Live => write!(f, "Live"),
+            Source::Paper => write!(f, "Paper"),
+            Source::Manual => write!(f, "Manual"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Entry {
+    /// Assigned by the journal once recorded
+    pub id: u64,
+    pub ticker: Ticker,
+    pub time: UnixMs,
+    pub side: OrderSide,
+    pub price: Price,
+    pub qty: f64,
+    pub source: Source,
+    /// The venue's trade id of live executions, so refetched ones aren't logged twice
+    #[serde(default)]
+    pub trade_id: Option<String>,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Entry,
+    Exit,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Entry => write!(f, "Entry"),
+            Role::Exit => write!(f, "Exit"),
+        }
+    }
+}
+
+/// What an entry did to the position of its ticker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Review {
+    pub role: Role,
+    /// Profit realized by an exit, in the quote currency
+    pub realized: Option<f64>,
+}
+
+/// An entry as kline charts mark it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker {
+    pub time: UnixMs,
+    pub price: Price,
+    pub is_buy: bool,
+    pub qty: f64,
+    pub review: Review,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Journal {
+    next_id: u64,
+    /// Sorted by time
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    pub fn load() -> Self {
+        let path = crate::data_path(Some(JOURNAL_FILE));
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                log::warn!("Ignoring unreadable {JOURNAL_FILE}: {err}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        crate::write_json_to_file(&json, JOURNAL_FILE)
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Logs `entry` unless it's a live execution already logged, returns whether it was new
+    pub fn record(&mut self, mut entry: Entry) -> bool {
+        if let Some(trade_id) = &entry.trade_id
+            && self.entries.iter().any(|logged| {
+                logged.ticker == entry.ticker && logged.trade_id.as_ref() == Some(trade_id)
+            })
+        {
+            return false;
+        }
+
+        self.next_id += 1;
+        entry.id = self.next_id;
+
+        let idx = self
+            .entries
+            .partition_point(|logged| logged.time <= entry.time);
+        self.entries.insert(idx, entry);
+        true
+    }
+
+    pub fn remove(&mut self, id: u64) -> bool {
+        let count = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.len() != count
+    }
+
+    /// Latest logged live execution of `ticker`, where importing picks up from
+    pub fn last_execution(&self, ticker: Ticker) -> Option<UnixMs> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.ticker == ticker && entry.source == Source::Live)
+            .map(|entry| entry.time)
+    }
+
+    /// Entries replayed in time order, paper and real positions kept apart
+    pub fn reviews(&self) -> FxHashMap<u64, Review> {
+        let mut positions = FxHashMap::<(Ticker, bool), Pnl>::default();
+
+        self.entries
+            .iter()
+            .map(|entry| {
+                let position = positions
+                    .entry((entry.ticker, entry.source == Source::Paper))
+                    .or_default();
+
+                let review = if position.is_opening(entry.side) {
+                    position.apply(entry.side, entry.price.to_f64(), entry.qty);
+                    Review {
+                        role: Role::Entry,
+                        realized: None,
+                    }
+                } else {
+                    Review {
+                        role: Role::Exit,
+                        realized: Some(position.apply(entry.side, entry.price.to_f64(), entry.qty)),
+                    }
+                };
+
+                (entry.id, review)
+            })
+            .collect()
+    }
+
+    pub fn markers(&self) -> FxHashMap<Ticker, Vec<Marker>> {
+        let reviews = self.reviews();
+        let mut markers = FxHashMap::<Ticker, Vec<Marker>>::default();
+
+        for entry in &self.entries {
+            let Some(review) = reviews.get(&entry.id) else {
+                continue;
+            };
+            markers.entry(entry.ticker).or_default().push(Marker {
+                time: entry.time,
+                price: entry.price,
+                is_buy: entry.side == OrderSide::Buy,
+                qty: entry.qty,
+                review: *review,
+            });
+        }
+
+        markers
+    }
+}
@@ -0,0 +1,333 @@
+//! Simulated execution against the live market streams.
+//!
+//! Orders rest on the real book: a resting order only fills once the resting size that was
+//! ahead of it at its price has traded, or the market trades through its price. Orders that
+//! cross the spread fill right away at the opposite best price.
+
+use super::{Account, Fill};
+
+use exchange::adapter::trading::{OrderSide, Position, WorkingOrder};
+use exchange::depth::Depth;
+use exchange::unit::{Price, Qty};
+use exchange::{Ticker, Trade, UnixMs};
+use rustc_hash::FxHashMap;
+
+/// Fills kept per ticker for charts to draw
+const MAX_FILLS: usize = 200;
+
+#[derive(Default)]
+pub struct PaperAccount {
+    next_id: u64,
+    books: FxHashMap<Ticker, Book>,
+}
+
+#[derive(Default)]
+struct Book {
+    best_bid: Option<(Price, Qty)>,
+    best_ask: Option<(Price, Qty)>,
+    /// In placement order, which breaks ties between orders at the same price
+    orders: Vec<Resting>,
+    position: Pnl,
+    last_price: Option<Price>,
+    fills: Vec<Fill>,
+}
+
+struct Resting {
+    order: WorkingOrder,
+    /// Size that was resting at the price before this order, filled first
+    queue_ahead: f64,
+}
+
+/// Position and profit of one ticker, in the quote currency
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pnl {
+    /// Positive for longs
+    pub qty: f64,
+    pub avg_price: f64,
+    pub realized: f64,
+    pub unrealized: f64,
+}
+
+impl Pnl {
+    /// Adds a fill to the position, returns the profit it realized by reducing it
+    pub fn apply(&mut self, side: OrderSide, price: f64, qty: f64) -> f64 {
+        let signed = match side {
+            OrderSide::Buy => qty,
+            OrderSide::Sell => -qty,
+        };
+        let mut realized = 0.0;
+
+        if self.qty == 0.0 || self.qty.signum() == signed.signum() {
+            let total = self.qty + signed;
+            self.avg_price = (self.avg_price * self.qty.abs() + price * qty) / total.abs();
+            self.qty = total;
+        } else {
+            let closed = qty.min(self.qty.abs());
+            realized = closed * (price - self.avg_price) * self.qty.signum();
+            self.realized += realized;
+
+            let total = self.qty + signed;
+            if total != 0.0 && total.signum() != self.qty.signum() {
+                // Flipped, the rest opens a position the other way at the fill price
+                self.avg_price = price;
+            }
+            self.qty = total;
+        }
+        if self.qty.abs() < 1e-12 {
+            self.qty = 0.0;
+            self.avg_price = 0.0;
+        }
+
+        realized
+    }
+
+    /// Whether a fill on `side` adds to the position rather than reducing it
+    pub fn is_opening(&self, side: OrderSide) -> bool {
+        match side {
+            OrderSide::Buy => self.qty >= 0.0,
+            OrderSide::Sell => self.qty <= 0.0,
+        }
+    }
+}
+
+impl PaperAccount {
+    pub fn clear(&mut self) {
+        self.books.clear();
+    }
+
+    pub fn tickers(&self) -> impl Iterator<Item = Ticker> + '_ {
+        self.books.keys().copied()
+    }
+
+    pub fn pnl(&self, ticker: Ticker) -> Option<Pnl> {
+        self.books.get(&ticker).map(|book| book.position)
+    }
+
+    /// Orders, position and fills of `ticker` in the shape live accounts are drawn from
+    pub fn account(&self, ticker: Ticker) -> Account {
+        let Some(book) = self.books.get(&ticker) else {
+            return Account::default();
+        };
+
+        let position = &book.position;
+        let positions = (position.qty != 0.0)
+            .then(|| Position {
+                ticker,
+                side: if position.qty > 0.0 {
+                    OrderSide::Buy
+                } else {
+                    OrderSide::Sell
+                },
+                qty: Qty::from_f64(position.qty.abs()),
+                entry_price: Price::from_f64(position.avg_price),
+                liquidation_price: None,
+            })
+            .into_iter()
+            .collect();
+
+        Account {
+            orders: book.orders.iter().map(|r| r.order.clone()).collect(),
+            positions,
+            fills: book.fills.clone(),
+        }
+    }
+
+    /// Rests the order on the book, or fills it right away if it crosses the spread
+    pub fn place(
+        &mut self,
+        ticker: Ticker,
+        side: OrderSide,
+        price: Price,
+        qty: f64,
+    ) -> Result<Vec<Fill>, String> {
+        let book = self.books.entry(ticker).or_default();
+        let (Some((bid, bid_qty)), Some((ask, ask_qty))) = (book.best_bid, book.best_ask) else {
+            return Err(format!(
+                "No live order book for {}, open a ladder or heatmap of it first",
+                ticker.symbol_and_exchange_string()
+            ));
+        };
+
+        self.next_id += 1;
+        let order = WorkingOrder {
+            id: format!("paper-{}", self.next_id),
+            ticker,
+            side,
+            price,
+            qty: Qty::from_f64(qty),
+            filled: Qty::ZERO,
+        };
+
+        let crosses = match side {
+            OrderSide::Buy => price >= ask,
+            OrderSide::Sell => price <= bid,
+        };
+        if crosses {
+            let fill_price = if side == OrderSide::Buy { ask } else { bid };
+            return Ok(vec![book.fill(side, fill_price, qty, UnixMs::now())]);
+        }
+
+        let queue_ahead = match side {
+            OrderSide::Buy if price == bid => bid_qty.to_f64(),
+            OrderSide::Sell if price == ask => ask_qty.to_f64(),
+            // Behind the touch the size ahead isn't known, trades through the price fill it
+            _ => f64::INFINITY,
+        };
+        book.orders.push(Resting { order, queue_ahead });
+
+        Ok(vec![])
+    }
+
+    pub fn cancel(&mut self, ticker: Ticker, id: &str) -> bool {
+        let Some(book) = self.books.get_mut(&ticker) else {
+            return false;
+        };
+        let count = book.orders.len();
+        book.orders.retain(|resting| resting.order.id != id);
+        book.orders.len() != count
+    }
+
+    /// Keeps the touch up to date, filling resting orders the book moved through
+    pub fn on_depth(&mut self, ticker: Ticker, depth: &Depth) -> Vec<Fill> {
+        let book = self.books.entry(ticker).or_default();
+
+        book.best_bid = depth.bids.last_key_value().map(|(p, q)| (*p, *q));
+        book.best_ask = depth.asks.first_key_value().map(|(p, q)| (*p, *q));
+
+        let mut fills = vec![];
+        let now = UnixMs::now();
+
+        for idx in (0..book.orders.len()).rev() {
+            let resting = &mut book.orders[idx];
+            let (side, price) = (resting.order.side, resting.order.price);
+
+            let level_qty = match side {
+                OrderSide::Buy => depth.bids.get(&price),
+                OrderSide::Sell => depth.asks.get(&price),
+            };
+            // Cancellations ahead of the order move it up the queue
+            if let Some(level_qty) = level_qty {
+                resting.queue_ahead = resting.queue_ahead.min(level_qty.to_f64());
+            }
+
+            let crossed = match side {
+                OrderSide::Buy => book.best_ask.is_some_and(|(ask, _)| ask <= price),
+                OrderSide::Sell => book.best_bid.is_some_and(|(bid, _)| bid >= price),
+            };
+            if crossed {
+                let resting = book.orders.remove(idx);
+                let qty = resting.order.remaining().to_f64();
+                fills.push(book.fill(side, price, qty, now));
+            }
+        }
+
+        fills
+    }
+
+    /// Matches the trades against resting orders, best price first and then placement order
+    pub fn on_trades(&mut self, ticker: Ticker, trades: &[Trade]) -> Vec<Fill> {
+        let Some(book) = self.books.get_mut(&ticker) else {
+            return vec![];
+        };
+
+        let mut fills = vec![];
+
+        for trade in trades {
+            book.last_price = Some(trade.price);
+
+            // Sells hit the bids, so they can fill our buys, and the other way around
+            let side = if trade.is_sell {
+                OrderSide::Buy
+            } else {
+                OrderSide::Sell
+            };
+
+            let mut candidates = book
+                .orders
+                .iter()
+                .enumerate()
+                .filter(|(_, resting)| resting.order.side == side)
+                .filter(|(_, resting)| match side {
+                    OrderSide::Buy => trade.price <= resting.order.price,
+                    OrderSide::Sell => trade.price >= resting.order.price,
+                })
+                .map(|(idx, resting)| (idx, resting.order.price))
+                .collect::<Vec<_>>();
+            match side {
+                OrderSide::Buy => candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0))),
+                OrderSide::Sell => candidates.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0))),
+            }
+
+            let mut traded = trade.qty.to_f64();
+            let mut done = vec![];
+
+            for (idx, price) in candidates {
+                let resting = &mut book.orders[idx];
+                let remaining = resting.order.remaining().to_f64();
+
+                // Traded through the price, the whole level is gone
+                let fill_qty = if price != trade.price {
+                    remaining
+                } else {
+                    let past_queue = (traded - resting.queue_ahead).max(0.0);
+                    resting.queue_ahead = (resting.queue_ahead - traded).max(0.0);
+                    let qty = past_queue.min(remaining);
+                    traded -= qty;
+                    qty
+                };
+
+                if fill_qty <= 0.0 {
+                    continue;
+                }
+
+                resting.order.filled += Qty::from_f64(fill_qty);
+                if resting.order.remaining().to_f64() <= 0.0 {
+                    done.push(idx);
+                }
+                fills.push((side, price, fill_qty, trade.time));
+            }
+
+            done.sort_unstable();
+            for idx in done.into_iter().rev() {
+                book.orders.remove(idx);
+            }
+        }
+
+        let fills = fills
+            .into_iter()
+            .map(|(side, price, qty, time)| book.fill(side, price, qty, time))
+            .collect();
+
+        book.mark_to_market();
+        fills
+    }
+}
+
+impl Book {
+    fn fill(&mut self, side: OrderSide, price: Price, qty: f64, time: UnixMs) -> Fill {
+        self.position.apply(side, price.to_f64(), qty);
+
+        let fill = Fill {
+            time,
+            side,
+            price,
+            qty: Qty::from_f64(qty),
+        };
+
+        self.fills.push(fill);
+        if self.fills.len() > MAX_FILLS {
+            self.fills.remove(0);
+        }
+        self.last_price = Some(price);
+        self.mark_to_market();
+
+        fill
+    }
+
+    fn mark_to_market(&mut self) {
+        let position = &mut self.position;
+        position.unrealized = self.last_price.map_or(0.0, |last| {
+            position.qty * (last.to_f64() - position.avg_price)
+        });
+    }
+}
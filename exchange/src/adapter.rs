@@ -1,22 +1,29 @@
 mod client;
+pub mod connect;
+pub mod health;
 mod http;
 mod hub;
 mod limiter;
 pub mod proxy;
+pub mod source;
+pub mod trading;
 mod ws;
 
 use super::Timeframe;
 pub use super::error::AdapterError;
 use crate::{
-    Kline, Price, PushFrequency, TickMultiplier, TickerInfo, Trade, UnixMs, depth::Depth, unit::Qty,
+    Kline, MarkPrice, Price, PushFrequency, TickMultiplier, Ticker, TickerInfo, TickerStats, Trade,
+    UnixMs, depth::Depth, unit::Qty,
 };
 
 use enum_map::{Enum, EnumMap};
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
-use std::{str::FromStr, sync::Arc};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 pub use client::{AdapterHandles, MAX_KLINE_STREAMS_PER_STREAM, MAX_TRADE_TICKERS_PER_STREAM};
+pub use connect::ReconnectStatus;
+pub use http::post_json;
 pub use proxy::Proxy;
 
 pub fn allowed_multipliers_for_min_tick(min_ticksize: crate::unit::MinTicksize) -> &'static [u16] {
@@ -98,6 +105,18 @@ pub enum StreamKind {
     Trades {
         ticker_info: TickerInfo,
     },
+    /// Orderbooks of the same underlying on several venues, merged client-side.
+    /// `depth_aggr` and `push_freq` apply to the base ticker's own depth stream
+    CompositeDepth {
+        sources: CompositeSources,
+        #[serde(default = "default_depth_aggr")]
+        depth_aggr: StreamTicksize,
+        push_freq: PushFrequency,
+    },
+    /// Mark and index price of a perpetual, see [`Exchange::supports_mark_price`]
+    MarkPrice {
+        ticker_info: TickerInfo,
+    },
 }
 
 impl StreamKind {
@@ -105,10 +124,53 @@ impl StreamKind {
         match self {
             StreamKind::Kline { ticker_info, .. }
             | StreamKind::Depth { ticker_info, .. }
-            | StreamKind::Trades { ticker_info, .. } => *ticker_info,
+            | StreamKind::Trades { ticker_info, .. }
+            | StreamKind::MarkPrice { ticker_info } => *ticker_info,
+            StreamKind::CompositeDepth { sources, .. } => sources.base(),
         }
     }
 
+    /// Per-venue depth streams that have to be subscribed to feed a composite book
+    pub fn composite_members(&self) -> Option<impl Iterator<Item = StreamKind> + '_> {
+        let StreamKind::CompositeDepth {
+            sources,
+            depth_aggr,
+            push_freq,
+        } = self
+        else {
+            return None;
+        };
+
+        let base = sources.base();
+
+        Some(sources.iter().map(move |ticker_info| {
+            let exchange = ticker_info.exchange();
+
+            if ticker_info == base {
+                StreamKind::Depth {
+                    ticker_info,
+                    depth_aggr: *depth_aggr,
+                    push_freq: *push_freq,
+                }
+            } else {
+                StreamKind::Depth {
+                    ticker_info,
+                    depth_aggr: exchange.stream_ticksize(None, TickMultiplier(1)),
+                    push_freq: if exchange.is_custom_push_freq() {
+                        *push_freq
+                    } else {
+                        PushFrequency::ServerDefault
+                    },
+                }
+            }
+        }))
+    }
+
+    pub fn is_composite_member(&self, stream: &StreamKind) -> bool {
+        self.composite_members()
+            .is_some_and(|mut members| members.any(|member| member == *stream))
+    }
+
     pub fn as_depth_stream(&self) -> Option<(TickerInfo, StreamTicksize, PushFrequency)> {
         match self {
             StreamKind::Depth {
@@ -136,6 +198,13 @@ impl StreamKind {
             _ => None,
         }
     }
+
+    pub fn as_mark_price_stream(&self) -> Option<TickerInfo> {
+        match self {
+            StreamKind::MarkPrice { ticker_info } => Some(*ticker_info),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -154,11 +223,13 @@ impl UniqueStreams {
     }
 
     pub fn add(&mut self, stream: StreamKind) {
-        let (exchange, ticker_info) = match stream {
-            StreamKind::Kline { ticker_info, .. }
-            | StreamKind::Depth { ticker_info, .. }
-            | StreamKind::Trades { ticker_info, .. } => (ticker_info.exchange(), ticker_info),
-        };
+        if let Some(members) = stream.composite_members() {
+            members.for_each(|member| self.add(member));
+            return;
+        }
+
+        let ticker_info = stream.ticker_info();
+        let exchange = ticker_info.exchange();
 
         self.streams[exchange]
             .get_or_insert_with(FxHashMap::default)
@@ -179,11 +250,13 @@ impl UniqueStreams {
         let depth_streams = self.depth_streams(Some(exchange));
         let trade_streams = self.trade_streams(Some(exchange));
         let kline_streams = self.kline_streams(Some(exchange));
+        let mark_price_streams = self.mark_price_streams(Some(exchange));
 
         self.specs[exchange] = Some(StreamSpecs {
             depth: depth_streams,
             trade: trade_streams,
             kline: kline_streams,
+            mark_price: mark_price_streams,
         });
     }
 
@@ -222,6 +295,10 @@ impl UniqueStreams {
         self.streams(exchange_filter, |_, stream| stream.as_trade_stream())
     }
 
+    pub fn mark_price_streams(&self, exchange_filter: Option<Exchange>) -> Vec<TickerInfo> {
+        self.streams(exchange_filter, |_, stream| stream.as_mark_price_stream())
+    }
+
     pub fn combined_used(&self) -> impl Iterator<Item = (Exchange, &StreamSpecs)> {
         self.specs
             .iter()
@@ -231,6 +308,13 @@ impl UniqueStreams {
     pub fn combined(&self) -> &EnumMap<Exchange, Option<StreamSpecs>> {
         &self.specs
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StreamKind> {
+        self.streams
+            .values()
+            .flatten()
+            .flat_map(|ticker_map| ticker_map.values().flatten())
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -244,11 +328,88 @@ fn default_depth_aggr() -> StreamTicksize {
     StreamTicksize::Client
 }
 
+pub const MAX_COMPOSITE_SOURCES: usize = 4;
+
+/// Most lower timeframe klines a custom timeframe candle is resampled from
+const MAX_RESAMPLED_KLINES: u16 = 60;
+
+/// Fixed-capacity ticker set backing a [`StreamKind::CompositeDepth`].
+///
+/// Kept `Copy` so it can live inside `StreamKind`; the first entry is the base ticker
+/// and unused slots are padded with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct CompositeSources {
+    tickers: [TickerInfo; MAX_COMPOSITE_SOURCES],
+    len: u8,
+}
+
+impl CompositeSources {
+    pub fn new(base: TickerInfo) -> Self {
+        Self {
+            tickers: [base; MAX_COMPOSITE_SOURCES],
+            len: 1,
+        }
+    }
+
+    /// Returns `None` if the slice is empty or exceeds [`MAX_COMPOSITE_SOURCES`]
+    pub fn from_slice(tickers: &[TickerInfo]) -> Option<Self> {
+        let (base, rest) = tickers.split_first()?;
+        rest.iter()
+            .try_fold(Self::new(*base), |sources, ti| sources.with(*ti))
+    }
+
+    pub fn base(&self) -> TickerInfo {
+        self.tickers[0]
+    }
+
+    pub fn as_slice(&self) -> &[TickerInfo] {
+        &self.tickers[..self.len as usize]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = TickerInfo> + '_ {
+        self.as_slice().iter().copied()
+    }
+
+    pub fn count(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn contains(&self, ticker_info: &TickerInfo) -> bool {
+        self.as_slice().contains(ticker_info)
+    }
+
+    /// Returns `None` when there is no room left for a new source
+    pub fn with(mut self, ticker_info: TickerInfo) -> Option<Self> {
+        if self.contains(&ticker_info) {
+            return Some(self);
+        }
+        if self.count() >= MAX_COMPOSITE_SOURCES {
+            return None;
+        }
+
+        self.tickers[self.count()] = ticker_info;
+        self.len += 1;
+        Some(self)
+    }
+
+    /// The base ticker can't be removed
+    pub fn without(self, ticker_info: TickerInfo) -> Self {
+        let base = self.base();
+        self.iter()
+            .skip(1)
+            .filter(|ti| *ti != ticker_info)
+            .fold(Self::new(base), |sources, ti| {
+                sources.with(ti).unwrap_or(sources)
+            })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct StreamSpecs {
     pub depth: Vec<(TickerInfo, StreamTicksize, PushFrequency)>,
     pub trade: Vec<TickerInfo>,
     pub kline: Vec<(TickerInfo, Timeframe)>,
+    pub mark_price: Vec<TickerInfo>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -258,15 +419,18 @@ pub enum Venue {
     Hyperliquid,
     Okex,
     Mexc,
+    /// Feeds added through [`source::register`]
+    Plugin,
 }
 
 impl Venue {
-    pub const ALL: [Venue; 5] = [
+    pub const ALL: [Venue; 6] = [
         Venue::Bybit,
         Venue::Binance,
         Venue::Hyperliquid,
         Venue::Okex,
         Venue::Mexc,
+        Venue::Plugin,
     ];
 }
 
@@ -281,6 +445,7 @@ impl std::fmt::Display for Venue {
                 Venue::Hyperliquid => "Hyperliquid",
                 Venue::Okex => "OKX",
                 Venue::Mexc => "MEXC",
+                Venue::Plugin => "Plugin",
             }
         )
     }
@@ -300,6 +465,8 @@ impl FromStr for Venue {
             Ok(Self::Okex)
         } else if s.eq_ignore_ascii_case("mexc") {
             Ok(Self::Mexc)
+        } else if s.eq_ignore_ascii_case("plugin") {
+            Ok(Self::Plugin)
         } else {
             Err(format!("Invalid venue: {}", s))
         }
@@ -322,6 +489,8 @@ pub enum Exchange {
     MexcLinear,
     MexcInverse,
     MexcSpot,
+    PluginLinear,
+    PluginSpot,
 }
 
 impl std::fmt::Display for Exchange {
@@ -355,7 +524,7 @@ impl FromStr for Exchange {
 }
 
 impl Exchange {
-    pub const ALL: [Exchange; 14] = [
+    pub const ALL: [Exchange; 16] = [
         Exchange::BinanceLinear,
         Exchange::BinanceInverse,
         Exchange::BinanceSpot,
@@ -370,6 +539,8 @@ impl Exchange {
         Exchange::MexcLinear,
         Exchange::MexcInverse,
         Exchange::MexcSpot,
+        Exchange::PluginLinear,
+        Exchange::PluginSpot,
     ];
 
     pub fn from_venue_and_market(venue: Venue, market: MarketKind) -> Option<Self> {
@@ -384,7 +555,8 @@ impl Exchange {
             | Exchange::BybitLinear
             | Exchange::HyperliquidLinear
             | Exchange::OkexLinear
-            | Exchange::MexcLinear => MarketKind::LinearPerps,
+            | Exchange::MexcLinear
+            | Exchange::PluginLinear => MarketKind::LinearPerps,
             Exchange::BinanceInverse
             | Exchange::BybitInverse
             | Exchange::OkexInverse
@@ -393,7 +565,8 @@ impl Exchange {
             | Exchange::BybitSpot
             | Exchange::HyperliquidSpot
             | Exchange::OkexSpot
-            | Exchange::MexcSpot => MarketKind::Spot,
+            | Exchange::MexcSpot
+            | Exchange::PluginSpot => MarketKind::Spot,
         }
     }
 
@@ -406,6 +579,7 @@ impl Exchange {
             Exchange::HyperliquidLinear | Exchange::HyperliquidSpot => Venue::Hyperliquid,
             Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => Venue::Okex,
             Exchange::MexcLinear | Exchange::MexcInverse | Exchange::MexcSpot => Venue::Mexc,
+            Exchange::PluginLinear | Exchange::PluginSpot => Venue::Plugin,
         }
     }
 
@@ -439,9 +613,26 @@ impl Exchange {
         }
     }
 
+    /// Whether [`AdapterHandles::fetch_open_interest`] serves the exchange
+    pub fn has_open_interest(&self) -> bool {
+        matches!(
+            self,
+            Exchange::BinanceLinear
+                | Exchange::BinanceInverse
+                | Exchange::BybitLinear
+                | Exchange::BybitInverse
+                | Exchange::OkexLinear
+                | Exchange::OkexInverse
+        )
+    }
+
     pub fn supports_kline_timeframe(&self, tf: Timeframe) -> bool {
+        if tf == Timeframe::MS1000 {
+            return *self == Exchange::BinanceSpot;
+        }
+
         match self.venue() {
-            Venue::Binance | Venue::Bybit | Venue::Hyperliquid | Venue::Okex => {
+            Venue::Binance | Venue::Bybit | Venue::Hyperliquid | Venue::Okex | Venue::Plugin => {
                 Timeframe::KLINE.contains(&tf)
             }
             Venue::Mexc => {
@@ -451,6 +642,48 @@ impl Exchange {
         }
     }
 
+    /// Timeframe to fetch and stream klines in for `tf`, a custom one is resampled from the
+    /// longest supported timeframe that divides it, as long as it takes few enough of them
+    pub fn kline_fetch_timeframe(&self, tf: Timeframe) -> Option<Timeframe> {
+        if self.supports_kline_timeframe(tf) {
+            return Some(tf);
+        }
+        let Timeframe::Custom(minutes) = tf else {
+            return None;
+        };
+
+        Timeframe::KLINE.into_iter().rev().find(|base| {
+            let base_minutes = base.to_minutes();
+            minutes % base_minutes == 0
+                && minutes / base_minutes <= MAX_RESAMPLED_KLINES
+                && self.supports_kline_timeframe(*base)
+        })
+    }
+
+    /// Venues with a websocket feed of mark and index prices
+    pub fn supports_mark_price(&self) -> bool {
+        matches!(
+            self,
+            Exchange::BinanceLinear
+                | Exchange::BinanceInverse
+                | Exchange::BybitLinear
+                | Exchange::BybitInverse
+        )
+    }
+
+    /// Venues that push the 24h stats of their tickers over websocket, the rest are polled
+    pub fn supports_ticker_stats_stream(&self) -> bool {
+        matches!(
+            self,
+            Exchange::BinanceLinear
+                | Exchange::BinanceInverse
+                | Exchange::BinanceSpot
+                | Exchange::BybitLinear
+                | Exchange::BybitInverse
+                | Exchange::BybitSpot
+        )
+    }
+
     pub fn is_perps(&self) -> bool {
         matches!(
             self,
@@ -463,6 +696,7 @@ impl Exchange {
                 | Exchange::OkexInverse
                 | Exchange::MexcLinear
                 | Exchange::MexcInverse
+                | Exchange::PluginLinear
         )
     }
 
@@ -520,9 +754,18 @@ impl Exchange {
 pub enum Event {
     Connected(Arc<[StreamKind]>),
     Disconnected(Arc<[StreamKind]>, String),
+    /// Sent right after a reconnect, carrying the time of the last data received
+    /// before the drop so consumers can backfill the gap over REST
+    Resumed(Arc<[StreamKind]>, UnixMs),
+    /// Connection attempt failed, next one is scheduled after [`ReconnectStatus::delay`]
+    Reconnecting(Arc<[StreamKind]>, ReconnectStatus),
     DepthReceived(StreamKind, UnixMs, Arc<Depth>),
     TradesReceived(StreamKind, UnixMs, Box<[Trade]>),
     KlineReceived(StreamKind, Kline),
+    MarkPriceReceived(StreamKind, MarkPrice),
+    /// 24h stats of the tickers that changed since the last push, from a venue wide
+    /// tickers feed. Fields the feed doesn't carry are left `None`
+    TickerStatsReceived(Exchange, HashMap<Ticker, TickerStats>),
 }
 
 #[derive(Debug, Clone, Hash)]
@@ -1,9 +1,11 @@
 use super::{
     AdapterError, Event, Exchange, MarketKind, StreamConfig, StreamKind, StreamTicksize, Venue,
     hub::{binance, bybit, hyperliquid, mexc, okex},
+    source,
 };
 use crate::{
-    Kline, OpenInterest, TickMultiplier, Ticker, TickerInfo, TickerStats, Timeframe, Trade, UnixMs,
+    FundingRate, Kline, OpenInterest, TickMultiplier, Ticker, TickerInfo, TickerStats, Timeframe,
+    Trade, UnixMs,
 };
 
 use futures::{StreamExt, stream, stream::BoxStream};
@@ -24,6 +26,8 @@ pub struct AdapterHandles {
 }
 
 impl AdapterHandles {
+    /// (Re)starts the adapter of `venue`, replacing any previous handle.
+    /// Must be called from within a tokio runtime.
     pub fn spawn_venue(
         &mut self,
         venue: Venue,
@@ -45,11 +49,14 @@ impl AdapterHandles {
             Venue::Mexc => {
                 self.mexc = Some(mexc::MexcHandle::new(proxy)?);
             }
+            // Custom sources are registered globally through `source::register`
+            Venue::Plugin => {}
         }
 
         Ok(())
     }
 
+    /// Spawns an adapter for each venue, logging the ones that fail to start
     pub fn spawn_venues(
         venues: impl IntoIterator<Item = Venue>,
         proxy: Option<&super::proxy::Proxy>,
@@ -129,6 +136,20 @@ impl AdapterHandles {
         )
     }
 
+    fn mark_price_scope(config: &StreamConfig<Vec<TickerInfo>>) -> Arc<[StreamKind]> {
+        Arc::from(
+            config
+                .id
+                .iter()
+                .map(|ticker_info| StreamKind::MarkPrice {
+                    ticker_info: *ticker_info,
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        )
+    }
+
+    /// Kline updates for every `(ticker, timeframe)` pair, split across connections as needed
     pub fn kline_stream(
         &self,
         config: &StreamConfig<Vec<(TickerInfo, Timeframe)>>,
@@ -171,9 +192,19 @@ impl AdapterHandles {
                 .map_or_else(missing_venue_stream, |handle| {
                     handle.connect_kline_stream(streams, market_kind).boxed()
                 }),
+            Venue::Plugin => source::grouped_stream(
+                streams,
+                |(ticker_info, _)| *ticker_info,
+                |source, group| source.kline_stream(group),
+                |(ticker_info, timeframe)| StreamKind::Kline {
+                    ticker_info: *ticker_info,
+                    timeframe: *timeframe,
+                },
+            ),
         }
     }
 
+    /// Trades of every ticker in the config, all from the same exchange
     pub fn trade_stream(
         &self,
         config: &StreamConfig<Vec<TickerInfo>>,
@@ -216,9 +247,94 @@ impl AdapterHandles {
                 .map_or_else(missing_venue_stream, |handle| {
                     handle.connect_trade_stream(streams, market_kind).boxed()
                 }),
+            Venue::Plugin => source::grouped_stream(
+                streams,
+                |ticker_info| *ticker_info,
+                |source, group| source.trade_stream(group),
+                |ticker_info| StreamKind::Trades {
+                    ticker_info: *ticker_info,
+                },
+            ),
+        }
+    }
+
+    /// Mark and index prices of perpetuals, only Binance and Bybit push them
+    pub fn mark_price_stream(
+        &self,
+        config: &StreamConfig<Vec<TickerInfo>>,
+    ) -> BoxStream<'static, Event> {
+        let stream_scope = Self::mark_price_scope(config);
+        let streams = config.id.clone();
+        let market_kind = config.exchange.market_type();
+
+        let missing_venue_stream =
+            || Self::missing_venue_stream(config.exchange, stream_scope.clone());
+
+        match config.exchange.venue() {
+            Venue::Binance => self
+                .binance
+                .clone()
+                .map_or_else(missing_venue_stream, |handle| {
+                    handle
+                        .connect_mark_price_stream(streams, market_kind)
+                        .boxed()
+                }),
+            Venue::Bybit => self
+                .bybit
+                .clone()
+                .map_or_else(missing_venue_stream, |handle| {
+                    handle
+                        .connect_mark_price_stream(streams, market_kind)
+                        .boxed()
+                }),
+            venue => {
+                let err = format!("Mark price stream isn't supported on {venue}");
+                stream::once(async move { Event::Disconnected(stream_scope, err) }).boxed()
+            }
+        }
+    }
+
+    /// 24h stats of the listed tickers pushed as they change, see
+    /// [`Exchange::supports_ticker_stats_stream`]. Binance has one feed for the whole
+    /// market, Bybit's topics are split across connections like trades
+    pub fn ticker_stats_stream(
+        &self,
+        config: &StreamConfig<Vec<TickerInfo>>,
+    ) -> BoxStream<'static, Event> {
+        let tickers = config.id.clone();
+        let market_kind = config.exchange.market_type();
+
+        let missing_venue_stream = || Self::missing_venue_stream(config.exchange, Arc::default());
+
+        match config.exchange.venue() {
+            Venue::Binance => self
+                .binance
+                .clone()
+                .map_or_else(missing_venue_stream, |handle| {
+                    handle
+                        .connect_ticker_stats_stream(tickers, market_kind)
+                        .boxed()
+                }),
+            Venue::Bybit => self
+                .bybit
+                .clone()
+                .map_or_else(missing_venue_stream, |handle| {
+                    stream::select_all(tickers.chunks(MAX_TRADE_TICKERS_PER_STREAM).map(|chunk| {
+                        handle
+                            .clone()
+                            .connect_ticker_stats_stream(chunk.to_vec(), market_kind)
+                            .boxed()
+                    }))
+                    .boxed()
+                }),
+            venue => {
+                let err = format!("Ticker stats stream isn't supported on {venue}");
+                stream::once(async move { Event::Disconnected(Arc::default(), err) }).boxed()
+            }
         }
     }
 
+    /// L2 orderbook of a single ticker, kept in sync locally and yielded after every update
     pub fn depth_stream(&self, config: &StreamConfig<TickerInfo>) -> BoxStream<'static, Event> {
         let stream_scope = Self::depth_scope(config);
         let ticker_info = config.id;
@@ -274,6 +390,10 @@ impl AdapterHandles {
                         .connect_depth_stream(ticker_info, depth_aggr, push_freq)
                         .boxed()
                 }),
+            Venue::Plugin => source::owner(&ticker_info.ticker).map_or_else(
+                |_| missing_venue_stream(),
+                |source| source.depth_stream(ticker_info, push_freq),
+            ),
         }
     }
 
@@ -339,6 +459,7 @@ impl AdapterHandles {
                     .fetch_ticker_metadata(mexc::MexcMarketScope::metadata(markets))
                     .await
             }
+            Venue::Plugin => source::fetch_tickers(markets).await,
         }
     }
 
@@ -407,6 +528,7 @@ impl AdapterHandles {
                     .fetch_ticker_stats(mexc::MexcMarketScope::stats(markets, contract_sizes))
                     .await
             }
+            Venue::Plugin => source::fetch_ticker_stats().await,
         }
     }
 
@@ -449,6 +571,11 @@ impl AdapterHandles {
                 };
                 handle.fetch_klines(ticker_info, timeframe, range).await
             }
+            Venue::Plugin => {
+                source::owner(&ticker_info.ticker)?
+                    .fetch_klines(ticker_info, timeframe, range)
+                    .await
+            }
         }
     }
 
@@ -491,6 +618,32 @@ impl AdapterHandles {
         }
     }
 
+    pub async fn fetch_funding_rate(
+        &self,
+        ticker_info: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> Result<Vec<FundingRate>, AdapterError> {
+        let exchange = ticker_info.ticker.exchange;
+
+        match exchange {
+            Exchange::BinanceLinear | Exchange::BinanceInverse => {
+                let Some(handle) = self.binance.as_ref() else {
+                    return Err(Self::missing_venue_error(exchange.venue()));
+                };
+                handle.fetch_funding_rate(ticker_info, range).await
+            }
+            Exchange::BybitLinear | Exchange::BybitInverse => {
+                let Some(handle) = self.bybit.as_ref() else {
+                    return Err(Self::missing_venue_error(exchange.venue()));
+                };
+                handle.fetch_funding_rate(ticker_info, range).await
+            }
+            _ => Err(AdapterError::InvalidRequest(format!(
+                "Funding rate data not available for {exchange}"
+            ))),
+        }
+    }
+
     pub async fn fetch_trades(
         &self,
         ticker_info: TickerInfo,
@@ -506,6 +659,11 @@ impl AdapterHandles {
                 };
                 handle.fetch_trades(ticker_info, from_time, data_path).await
             }
+            Venue::Plugin => {
+                source::owner(&ticker_info.ticker)?
+                    .fetch_trades(ticker_info, from_time)
+                    .await
+            }
             _ => Err(AdapterError::InvalidRequest(format!(
                 "Trade fetch not available for {exchange}"
             ))),
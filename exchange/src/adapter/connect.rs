@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+/// Where a stream stands in its reconnect cycle, reported before each wait
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectStatus {
+    /// Consecutive failed attempts so far, starting at 1
+    pub attempt: u32,
+    pub delay: Duration,
+    /// Fast retries are used up; attempts continue at [`ReconnectPolicy::EXHAUSTED_DELAY`]
+    pub exhausted: bool,
+}
+
+/// Reconnect pacing shared by every WebSocket adapter.
+///
+/// Delay doubles on each failure, resets to the initial 500ms on success.
+/// Capped at 30s with ±25% multiplicative jitter to spread reconnections
+/// across streams when multiple disconnect at once. After [`Self::MAX_RETRIES`]
+/// consecutive failures the stream is reported as exhausted and falls back
+/// to a slow retry cadence instead of hammering an unreachable venue.
+pub struct ReconnectPolicy {
+    current: Duration,
+    attempts: u32,
+}
+
+impl ReconnectPolicy {
+    const INITIAL: Duration = Duration::from_millis(500);
+    const MAX: Duration = Duration::from_secs(30);
+    const JITTER: f32 = 0.25;
+
+    pub const MAX_RETRIES: u32 = 12;
+    pub const EXHAUSTED_DELAY: Duration = Duration::from_secs(300);
+
+    pub fn new() -> Self {
+        Self {
+            current: Self::INITIAL,
+            attempts: 0,
+        }
+    }
+
+    /// Registers a failed attempt and returns how long to wait before the next one.
+    pub fn next_attempt(&mut self) -> ReconnectStatus {
+        self.attempts = self.attempts.saturating_add(1);
+        let exhausted = self.attempts > Self::MAX_RETRIES;
+
+        let base = if exhausted {
+            Self::EXHAUSTED_DELAY
+        } else {
+            self.current
+        };
+        let factor = 1.0 + (rand::random::<f32>() * 2.0 - 1.0) * Self::JITTER;
+        let delay = Duration::from_secs_f32((base.as_secs_f32() * factor).max(0.0));
+
+        self.current = self.current.mul_f32(2.0).min(Self::MAX);
+
+        ReconnectStatus {
+            attempt: self.attempts,
+            delay: if exhausted {
+                delay
+            } else {
+                delay.min(Self::MAX)
+            },
+            exhausted,
+        }
+    }
+
+    /// Resets the delay to the initial value after genuine success
+    /// (real market-data events were produced by the connection).
+    pub fn record_success(&mut self) {
+        self.current = Self::INITIAL;
+        self.attempts = 0;
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bounds of `base` once jittered, give or take float rounding
+    fn jittered(base: Duration) -> (Duration, Duration) {
+        let slack = Duration::from_millis(1);
+        (
+            base.mul_f32(1.0 - ReconnectPolicy::JITTER) - slack,
+            base.mul_f32(1.0 + ReconnectPolicy::JITTER) + slack,
+        )
+    }
+
+    #[test]
+    fn delay_doubles_up_to_the_cap() {
+        let mut policy = ReconnectPolicy::new();
+        let mut base = ReconnectPolicy::INITIAL;
+
+        for attempt in 1..=ReconnectPolicy::MAX_RETRIES {
+            let status = policy.next_attempt();
+            let (min, max) = jittered(base);
+
+            assert_eq!(status.attempt, attempt);
+            assert!(!status.exhausted);
+            assert!(status.delay >= min && status.delay <= max);
+            assert!(status.delay <= ReconnectPolicy::MAX);
+
+            base = base.mul_f32(2.0).min(ReconnectPolicy::MAX);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_slow_cadence_once_exhausted() {
+        let mut policy = ReconnectPolicy::new();
+        for _ in 0..ReconnectPolicy::MAX_RETRIES {
+            policy.next_attempt();
+        }
+
+        let status = policy.next_attempt();
+        let (min, max) = jittered(ReconnectPolicy::EXHAUSTED_DELAY);
+
+        assert!(status.exhausted);
+        assert_eq!(status.attempt, ReconnectPolicy::MAX_RETRIES + 1);
+        assert!(status.delay >= min && status.delay <= max);
+    }
+
+    #[test]
+    fn success_starts_over() {
+        let mut policy = ReconnectPolicy::new();
+        for _ in 0..=ReconnectPolicy::MAX_RETRIES {
+            policy.next_attempt();
+        }
+        policy.record_success();
+
+        let status = policy.next_attempt();
+        let (min, max) = jittered(ReconnectPolicy::INITIAL);
+
+        assert_eq!(status.attempt, 1);
+        assert!(!status.exhausted);
+        assert!(status.delay >= min && status.delay <= max);
+    }
+}
@@ -0,0 +1,150 @@
+use super::{Event, StreamKind};
+use crate::UnixMs;
+
+use rustc_hash::FxHashMap;
+use std::time::{Duration, Instant};
+
+/// Weight of the newest sample in the smoothed latency
+const LATENCY_EMA_ALPHA: f64 = 0.2;
+
+/// Depth is pushed continuously, so a short silence already means trouble
+const DEPTH_STALE_AFTER: Duration = Duration::from_secs(10);
+/// Trades and klines can legitimately go quiet on illiquid tickers
+const ACTIVITY_STALE_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default)]
+pub struct StreamHealth {
+    pub connected: bool,
+    pub last_message: Option<Instant>,
+    /// Connects after the first one, per stream
+    pub reconnects: u32,
+    /// Smoothed gap between the exchange event time and local receive time
+    pub latency_ms: Option<f64>,
+    pub last_error: Option<String>,
+    connected_at: Option<Instant>,
+}
+
+impl StreamHealth {
+    pub fn since_last_message(&self, now: Instant) -> Option<Duration> {
+        self.last_message.map(|t| now.saturating_duration_since(t))
+    }
+
+    fn record_latency(&mut self, event_time: UnixMs, received_at: UnixMs) {
+        let sample = received_at.as_u64() as f64 - event_time.as_u64() as f64;
+
+        self.latency_ms = Some(match self.latency_ms {
+            Some(prev) => prev + LATENCY_EMA_ALPHA * (sample - prev),
+            None => sample,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+    /// Subscribed but no connection event seen yet
+    Pending,
+    Live,
+    /// Connected, but nothing arrived within the expected window
+    Stale,
+    Disconnected,
+}
+
+impl std::fmt::Display for HealthState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthState::Pending => write!(f, "Pending"),
+            HealthState::Live => write!(f, "Live"),
+            HealthState::Stale => write!(f, "Stale"),
+            HealthState::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+/// Per-stream connection diagnostics, fed from adapter [`Event`]s as they arrive
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    streams: FxHashMap<StreamKind, StreamHealth>,
+}
+
+impl ConnectionHealth {
+    pub fn record(&mut self, event: &Event, now: Instant) {
+        match event {
+            Event::Connected(streams) => {
+                for stream in streams.iter() {
+                    let health = self.streams.entry(*stream).or_default();
+                    if health.connected_at.is_some() {
+                        health.reconnects += 1;
+                    }
+                    health.connected_at = Some(now);
+                    health.connected = true;
+                }
+            }
+            Event::Disconnected(streams, reason) => {
+                for stream in streams.iter() {
+                    let health = self.streams.entry(*stream).or_default();
+                    health.connected = false;
+                    health.last_error = Some(reason.clone());
+                }
+            }
+            Event::Resumed(..) | Event::Reconnecting(..) => {}
+            // Venue wide, not tied to any stream
+            Event::TickerStatsReceived(..) => {}
+            Event::DepthReceived(stream, event_time, _) => {
+                self.record_message(stream, now, Some(*event_time));
+            }
+            Event::TradesReceived(stream, event_time, _) => {
+                self.record_message(stream, now, Some(*event_time));
+            }
+            // Kline payloads only carry the candle open time
+            Event::KlineReceived(stream, _) => {
+                self.record_message(stream, now, None);
+            }
+            Event::MarkPriceReceived(stream, mark_price) => {
+                self.record_message(stream, now, Some(mark_price.time));
+            }
+        }
+    }
+
+    fn record_message(&mut self, stream: &StreamKind, now: Instant, event_time: Option<UnixMs>) {
+        let health = self.streams.entry(*stream).or_default();
+        health.last_message = Some(now);
+        health.connected = true;
+
+        if let Some(event_time) = event_time {
+            health.record_latency(event_time, UnixMs::now());
+        }
+    }
+
+    pub fn get(&self, stream: &StreamKind) -> Option<&StreamHealth> {
+        self.streams.get(stream)
+    }
+
+    pub fn state(&self, stream: &StreamKind, now: Instant) -> HealthState {
+        let Some(health) = self.streams.get(stream) else {
+            return HealthState::Pending;
+        };
+
+        if !health.connected {
+            return if health.connected_at.is_some() || health.last_error.is_some() {
+                HealthState::Disconnected
+            } else {
+                HealthState::Pending
+            };
+        }
+
+        let stale_after = match stream {
+            StreamKind::Depth { .. } | StreamKind::CompositeDepth { .. } => DEPTH_STALE_AFTER,
+            StreamKind::Trades { .. } | StreamKind::Kline { .. } | StreamKind::MarkPrice { .. } => {
+                ACTIVITY_STALE_AFTER
+            }
+        };
+
+        let last_seen = health.last_message.max(health.connected_at);
+
+        match last_seen.map(|t| now.saturating_duration_since(t)) {
+            Some(elapsed) if elapsed > stale_after => HealthState::Stale,
+            Some(_) => HealthState::Live,
+            None => HealthState::Pending,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::adapter::AdapterError;
 use crate::adapter::limiter::RateLimiter;
 use crate::depth::DepthPayload;
-use crate::{Kline, OpenInterest, Ticker, TickerInfo, TickerStats, Timeframe, Trade, UnixMs};
+use crate::{
+    FundingRate, Kline, OpenInterest, Ticker, TickerInfo, TickerStats, Timeframe, Trade, UnixMs,
+};
 
 use futures::future::BoxFuture;
 use reqwest::{Client, Method, Response, header};
@@ -41,6 +43,11 @@ pub(super) enum FetchCommand<M> {
         range: Option<(UnixMs, UnixMs)>,
         reply: ResponseTx<Vec<OpenInterest>>,
     },
+    FundingRate {
+        ticker: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+        reply: ResponseTx<Vec<FundingRate>>,
+    },
     DepthSnapshot {
         ticker: Ticker,
         reply: ResponseTx<DepthPayload>,
@@ -274,6 +281,45 @@ impl<L: RateLimiter> HttpHub<L> {
     }
 }
 
+/// Posts `body` as JSON to a third-party endpoint, e.g. to deliver an alert.
+///
+/// `url` may embed credentials, so it's kept out of errors and logs, `label` names the
+/// endpoint instead
+pub async fn post_json(
+    url: &str,
+    label: &str,
+    body: &serde_json::Value,
+    proxy_cfg: Option<&crate::adapter::Proxy>,
+) -> Result<(), AdapterError> {
+    let builder = Client::builder()
+        .connect_timeout(HTTP_CONNECT_TIMEOUT)
+        .timeout(HTTP_REQUEST_TIMEOUT);
+
+    let client = crate::adapter::proxy::try_apply_proxy(builder, proxy_cfg)
+        .build()
+        .map_err(|error| {
+            AdapterError::InvalidRequest(format!("Failed to build HTTP client: {error}"))
+        })?;
+
+    let response =
+        client.post(url).json(body).send().await.map_err(|error| {
+            AdapterError::request_failed(&Method::POST, label, error.without_url())
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let preview = body.trim().chars().take(200).collect::<String>();
+
+        return Err(AdapterError::http_status_failed(
+            status,
+            format!("POST {label}: HTTP {status} | preview={preview:?}"),
+        ));
+    }
+
+    Ok(())
+}
+
 pub(super) trait FetchCommandHandler<M> {
     fn fetch_ticker_metadata(
         &mut self,
@@ -305,6 +351,15 @@ pub(super) trait FetchCommandHandler<M> {
         Box::pin(async { Err(unsupported_fetch("Open interest fetch")) })
     }
 
+    fn fetch_funding_rate(
+        &mut self,
+        ticker_info: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> BoxFuture<'_, Result<Vec<FundingRate>, AdapterError>> {
+        let _ = (ticker_info, range);
+        Box::pin(async { Err(unsupported_fetch("Funding rate fetch")) })
+    }
+
     fn fetch_depth_snapshot(
         &mut self,
         ticker: Ticker,
@@ -380,6 +435,14 @@ where
             let result = handler.fetch_open_interest(ticker, timeframe, range).await;
             let _ = reply.send(result);
         }
+        FetchCommand::FundingRate {
+            ticker,
+            range,
+            reply,
+        } => {
+            let result = handler.fetch_funding_rate(ticker, range).await;
+            let _ = reply.send(result);
+        }
         FetchCommand::DepthSnapshot { ticker, reply } => {
             let result = handler.fetch_depth_snapshot(ticker).await;
             let _ = reply.send(result);
@@ -1,5 +1,6 @@
 use crate::{
-    Event, Kline, OpenInterest, PushFrequency, Ticker, TickerInfo, Timeframe, Trade, UnixMs,
+    Event, FundingRate, Kline, OpenInterest, PushFrequency, Ticker, TickerInfo, Timeframe, Trade,
+    UnixMs,
     adapter::{Exchange, MarketKind, StreamTicksize, limiter::DynamicRateLimiterConfig},
     depth::DepthPayload,
     unit::{ContractSize, qty::RawQtyUnit},
@@ -10,6 +11,7 @@ use std::{collections::HashMap, path::PathBuf, time::Duration};
 
 pub mod fetch;
 pub mod stream;
+pub(crate) mod trade;
 
 const SPOT_DOMAIN: &str = "https://api.binance.com";
 const LINEAR_PERP_DOMAIN: &str = "https://fapi.binance.com";
@@ -176,6 +178,20 @@ impl BinanceHandle {
             .await
     }
 
+    pub async fn fetch_funding_rate(
+        &self,
+        ticker: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> Result<Vec<FundingRate>, AdapterError> {
+        self.request_port
+            .request(move |reply| BinanceCommand::FundingRate {
+                ticker,
+                range,
+                reply,
+            })
+            .await
+    }
+
     pub async fn fetch_trades(
         &self,
         ticker: TickerInfo,
@@ -223,6 +239,22 @@ impl BinanceHandle {
     ) -> impl futures::Stream<Item = Event> {
         stream::connect_kline_stream(streams, market_type, self.proxy_cfg)
     }
+
+    pub fn connect_mark_price_stream(
+        self,
+        tickers: Vec<TickerInfo>,
+        market_type: MarketKind,
+    ) -> impl futures::Stream<Item = Event> {
+        stream::connect_mark_price_stream(tickers, market_type, self.proxy_cfg)
+    }
+
+    pub fn connect_ticker_stats_stream(
+        self,
+        tickers: Vec<TickerInfo>,
+        market_type: MarketKind,
+    ) -> impl futures::Stream<Item = Event> {
+        stream::connect_ticker_stats_stream(tickers, market_type, self.proxy_cfg)
+    }
 }
 
 struct Worker {
@@ -315,6 +347,17 @@ impl super::FetchCommandHandler<BinanceMarketScope> for Worker {
         })
     }
 
+    fn fetch_funding_rate(
+        &mut self,
+        ticker_info: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> futures::future::BoxFuture<'_, Result<Vec<FundingRate>, AdapterError>> {
+        let market = ticker_info.market_type();
+        Box::pin(async move {
+            fetch::fetch_funding_rate(self.hub_for_market(market), ticker_info, range).await
+        })
+    }
+
     fn fetch_depth_snapshot(
         &mut self,
         ticker: Ticker,
@@ -1,6 +1,6 @@
 use crate::{
-    Kline, OpenInterest, Price, Qty, Ticker, TickerInfo, TickerStats, Timeframe, Trade, UnixMs,
-    Volume,
+    FundingRate, Kline, OpenInterest, Price, Qty, Ticker, TickerInfo, TickerStats, Timeframe,
+    Trade, UnixMs, Volume,
     depth::{DeOrder, DepthPayload},
     serde_util,
     serde_util::de_string_to_number,
@@ -42,6 +42,14 @@ struct DeOpenInterest {
     pub sum: f64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeFundingRate {
+    pub funding_time: u64,
+    #[serde(deserialize_with = "de_string_to_number")]
+    pub funding_rate: f64,
+}
+
 #[derive(Deserialize, Debug)]
 struct DeTrade {
     #[serde(rename = "T")]
@@ -284,6 +292,14 @@ pub(super) async fn fetch_ticker_stats(
     let exchange = exchange_from_market_type(market);
     let mut ticker_price_map = HashMap::new();
 
+    // Futures 24h tickers leave out the book and funding, they're listed separately
+    let (funding_rates, spreads) = match market {
+        MarketKind::Spot => (HashMap::new(), HashMap::new()),
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            fetch_perp_funding_and_spreads(hub, market).await
+        }
+    };
+
     for item in parsed_response {
         let symbol = item["symbol"]
             .as_str()
@@ -330,10 +346,21 @@ pub(super) async fn fetch_ticker_stats(
             }
         };
 
+        let spread_bps = match market {
+            MarketKind::Spot => TickerStats::spread_in_bps(
+                serde_util::value_as_f64(&item["bidPrice"]),
+                serde_util::value_as_f64(&item["askPrice"]),
+            ),
+            MarketKind::LinearPerps | MarketKind::InversePerps => spreads.get(symbol).copied(),
+        };
+
         let ticker_stats = TickerStats {
             mark_price: Price::from_f64(last_price),
             daily_price_chg: price_change_pt,
             daily_volume,
+            open_interest: None,
+            funding_rate: funding_rates.get(symbol).copied(),
+            spread_bps,
         };
 
         ticker_price_map.insert(ticker, ticker_stats);
@@ -342,6 +369,58 @@ pub(super) async fn fetch_ticker_stats(
     Ok(ticker_price_map)
 }
 
+/// Last funding rate and top of book spread of every perpetual, keyed by symbol. Either
+/// is left empty if its request fails, the rest of the stats don't depend on them
+async fn fetch_perp_funding_and_spreads(
+    hub: &mut HttpHub<BinanceLimiter>,
+    market: MarketKind,
+) -> (HashMap<String, f32>, HashMap<String, f32>) {
+    let domain = match market {
+        MarketKind::LinearPerps => format!("{LINEAR_PERP_DOMAIN}/fapi/v1"),
+        MarketKind::InversePerps => format!("{INVERSE_PERP_DOMAIN}/dapi/v1"),
+        MarketKind::Spot => return (HashMap::new(), HashMap::new()),
+    };
+
+    let mut funding_rates = HashMap::new();
+    match hub
+        .http_json_with_limiter::<Vec<Value>>(&format!("{domain}/premiumIndex"), 10, None, None)
+        .await
+    {
+        Ok(items) => {
+            for item in items {
+                if let (Some(symbol), Some(rate)) = (
+                    item["symbol"].as_str(),
+                    serde_util::value_as_f32(&item["lastFundingRate"]),
+                ) {
+                    funding_rates.insert(symbol.to_string(), rate);
+                }
+            }
+        }
+        Err(e) => log::warn!("Failed to fetch funding rates for {market:?}: {e}"),
+    }
+
+    let mut spreads = HashMap::new();
+    match hub
+        .http_json_with_limiter::<Vec<Value>>(&format!("{domain}/ticker/bookTicker"), 5, None, None)
+        .await
+    {
+        Ok(items) => {
+            for item in items {
+                let spread = TickerStats::spread_in_bps(
+                    serde_util::value_as_f64(&item["bidPrice"]),
+                    serde_util::value_as_f64(&item["askPrice"]),
+                );
+                if let (Some(symbol), Some(spread)) = (item["symbol"].as_str(), spread) {
+                    spreads.insert(symbol.to_string(), spread);
+                }
+            }
+        }
+        Err(e) => log::warn!("Failed to fetch book tickers for {market:?}: {e}"),
+    }
+
+    (funding_rates, spreads)
+}
+
 pub(super) async fn fetch_klines(
     hub: &mut HttpHub<BinanceLimiter>,
     ticker_info: TickerInfo,
@@ -546,6 +625,45 @@ pub(super) async fn fetch_historical_oi(
     Ok(open_interest)
 }
 
+pub(super) async fn fetch_funding_rate(
+    hub: &mut HttpHub<BinanceLimiter>,
+    ticker_info: TickerInfo,
+    range: Option<(UnixMs, UnixMs)>,
+) -> Result<Vec<FundingRate>, AdapterError> {
+    let (ticker_str, market) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let domain = match market {
+        MarketKind::LinearPerps => format!("{LINEAR_PERP_DOMAIN}/fapi/v1/fundingRate"),
+        MarketKind::InversePerps => format!("{INVERSE_PERP_DOMAIN}/dapi/v1/fundingRate"),
+        MarketKind::Spot => {
+            let err_msg = format!("Unsupported market type for funding rate: {market:?}");
+            log::error!("{}", err_msg);
+            return Err(AdapterError::InvalidRequest(err_msg));
+        }
+    };
+
+    let mut url = format!("{domain}?symbol={ticker_str}&limit=1000");
+
+    if let Some((start, end)) = range {
+        url.push_str(&format!(
+            "&startTime={}&endTime={}",
+            start.as_u64(),
+            end.as_u64()
+        ));
+    }
+
+    let binance_funding: Vec<DeFundingRate> =
+        hub.http_json_with_limiter(&url, 1, None, None).await?;
+
+    Ok(binance_funding
+        .iter()
+        .map(|x| FundingRate {
+            time: x.funding_time.into(),
+            rate: x.funding_rate,
+        })
+        .collect())
+}
+
 async fn fetch_intraday_trades(
     hub: &mut HttpHub<BinanceLimiter>,
     ticker_info: TickerInfo,
@@ -1,12 +1,15 @@
 use crate::{
-    Event, Kline, Price, PushFrequency, Ticker, TickerInfo, Trade, Volume,
+    Event, Kline, MarkPrice, Price, PushFrequency, Ticker, TickerInfo, TickerStats, Trade, Volume,
     adapter::{
         MarketKind, StreamKind, StreamTicksize,
         hub::{TradeBuffer, WsAdapter, WsSession, WsTransport},
     },
     depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
     serde_util::de_string_to_number,
-    unit::qty::{QtyNormalization, SizeUnit, volume_size_unit},
+    unit::{
+        Qty,
+        qty::{QtyNormalization, SizeUnit, volume_size_unit},
+    },
 };
 
 use super::{BinanceHandle, exchange_from_market_type, raw_qty_unit_from_market_type};
@@ -102,6 +105,44 @@ struct SonicTrade {
     is_sell: bool,
 }
 
+#[derive(Deserialize, Debug)]
+struct SonicMarkPrice {
+    #[serde(rename = "E")]
+    time: u64,
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "p", deserialize_with = "de_string_to_number")]
+    mark: f64,
+    #[serde(rename = "i", default)]
+    index: Option<String>,
+}
+
+/// `!ticker@arr` push, the tickers whose 24h stats changed in the last second
+#[derive(Deserialize, Debug)]
+struct SonicTickerArr {
+    data: Vec<SonicTicker>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SonicTicker {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "c", deserialize_with = "de_string_to_number")]
+    last_price: f64,
+    #[serde(rename = "P", deserialize_with = "de_string_to_number")]
+    price_change_pt: f32,
+    /// Base asset volume on spot and linear, contracts on inverse
+    #[serde(rename = "v", deserialize_with = "de_string_to_number")]
+    volume: f64,
+    #[serde(rename = "q", deserialize_with = "de_string_to_number")]
+    quote_volume: f64,
+    /// Best bid and ask, only spot tickers carry them
+    #[serde(rename = "b", default)]
+    bid: Option<String>,
+    #[serde(rename = "a", default)]
+    ask: Option<String>,
+}
+
 enum SonicDepth {
     Spot(SpotDepth),
     Perp(PerpDepth),
@@ -245,12 +286,14 @@ enum StreamData {
     Trade(Ticker, SonicTrade),
     Depth(SonicDepth),
     Kline(Ticker, SonicKline),
+    MarkPrice(Ticker, SonicMarkPrice),
 }
 
 enum StreamWrapper {
     Trade,
     Depth,
     Kline,
+    MarkPrice,
 }
 
 impl StreamWrapper {
@@ -262,6 +305,7 @@ impl StreamWrapper {
                 s if s.starts_with("de") => Some(StreamWrapper::Depth),
                 s if s.starts_with("ag") => Some(StreamWrapper::Trade),
                 s if s.starts_with("kl") => Some(StreamWrapper::Kline),
+                s if s.starts_with("ma") => Some(StreamWrapper::MarkPrice),
                 _ => None,
             })
     }
@@ -326,6 +370,15 @@ fn feed_de(slice: &[u8], market: MarketKind) -> Result<StreamData, AdapterError>
                         kline_wrap.kline,
                     ));
                 }
+                Some(StreamWrapper::MarkPrice) => {
+                    let mark_price: SonicMarkPrice = sonic_rs::from_str(&v.as_raw_faststr())
+                        .map_err(|e| AdapterError::ParseError(e.to_string()))?;
+
+                    return Ok(StreamData::MarkPrice(
+                        Ticker::new(&mark_price.symbol, exchange),
+                        mark_price,
+                    ));
+                }
                 _ => {
                     log::error!("Unknown stream type");
                 }
@@ -843,3 +896,197 @@ pub fn connect_kline_stream(
 
     WsSession::with_opcode_ping(BINANCE_OPCODE_PING_PAYLOAD, stream_scope).run(adapter)
 }
+
+struct MarkPriceAdapter {
+    market: MarketKind,
+    ticker_info_map: HashMap<Ticker, TickerInfo>,
+    stream_str: String,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+}
+
+impl WsAdapter for MarkPriceAdapter {
+    async fn connect(&mut self) -> Result<WsTransport, String> {
+        connect_stream_socket(
+            self.market,
+            WsTrafficKind::Market,
+            &self.stream_str,
+            self.proxy_cfg.as_ref(),
+        )
+        .await
+    }
+
+    async fn on_connected(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
+
+    async fn on_text(&mut self, payload: &[u8]) -> Result<Vec<Event>, String> {
+        if let Ok(StreamData::MarkPrice(ticker, de_mark)) = feed_de(payload, self.market) {
+            let Some(ticker_info) = self.ticker_info_map.get(&ticker).copied() else {
+                log::error!("Ticker info not found for ticker: {ticker}");
+                return Err("Received mark price for unknown ticker".to_string());
+            };
+
+            let to_price =
+                |value: f64| Price::from_f64(value).round_to_min_tick(ticker_info.min_ticksize);
+
+            let mark_price = MarkPrice {
+                time: de_mark.time.into(),
+                mark: to_price(de_mark.mark),
+                index: de_mark
+                    .index
+                    .and_then(|index| index.parse::<f64>().ok())
+                    .filter(|index| *index > 0.0)
+                    .map(to_price),
+            };
+
+            return Ok(vec![Event::MarkPriceReceived(
+                StreamKind::MarkPrice { ticker_info },
+                mark_price,
+            )]);
+        }
+
+        Ok(Vec::new())
+    }
+
+    async fn on_disconnected(&mut self, _reason: &str) -> Vec<Event> {
+        Vec::new()
+    }
+}
+
+/// Mark and index price of perpetuals, pushed once a second
+pub fn connect_mark_price_stream(
+    tickers: Vec<TickerInfo>,
+    market: MarketKind,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+) -> impl Stream<Item = Event> {
+    let stream_scope: Arc<[StreamKind]> = Arc::from(
+        tickers
+            .iter()
+            .map(|ticker_info| StreamKind::MarkPrice {
+                ticker_info: *ticker_info,
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice(),
+    );
+
+    let stream_str = tickers
+        .iter()
+        .map(|ticker_info| {
+            format!(
+                "{}@markPrice@1s",
+                ticker_info
+                    .ticker
+                    .to_full_symbol_and_type()
+                    .0
+                    .to_lowercase()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let ticker_info_map = tickers
+        .iter()
+        .map(|ticker_info| (ticker_info.ticker, *ticker_info))
+        .collect();
+
+    let adapter = MarkPriceAdapter {
+        market,
+        ticker_info_map,
+        stream_str,
+        proxy_cfg,
+    };
+
+    WsSession::with_opcode_ping(BINANCE_OPCODE_PING_PAYLOAD, stream_scope).run(adapter)
+}
+
+struct TickerStatsAdapter {
+    market: MarketKind,
+    ticker_info_map: HashMap<Ticker, TickerInfo>,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+}
+
+impl WsAdapter for TickerStatsAdapter {
+    async fn connect(&mut self) -> Result<WsTransport, String> {
+        connect_stream_socket(
+            self.market,
+            WsTrafficKind::Market,
+            "!ticker@arr",
+            self.proxy_cfg.as_ref(),
+        )
+        .await
+    }
+
+    async fn on_connected(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
+
+    async fn on_text(&mut self, payload: &[u8]) -> Result<Vec<Event>, String> {
+        let Ok(arr) = sonic_rs::from_slice::<SonicTickerArr>(payload) else {
+            return Ok(Vec::new());
+        };
+        let exchange = exchange_from_market_type(self.market);
+
+        let stats = arr
+            .data
+            .into_iter()
+            .filter_map(|de_ticker| {
+                let ticker = Ticker::new(&de_ticker.symbol, exchange);
+                let ticker_info = self.ticker_info_map.get(&ticker)?;
+
+                // Same as the REST stats, inverse volume is contracts times their USD size
+                let daily_volume = match self.market {
+                    MarketKind::Spot | MarketKind::LinearPerps => de_ticker.quote_volume,
+                    MarketKind::InversePerps => {
+                        de_ticker.volume * ticker_info.contract_size?.as_f64()
+                    }
+                };
+                let parse = |value: Option<String>| value?.parse::<f64>().ok();
+
+                let ticker_stats = TickerStats {
+                    mark_price: Price::from_f64(de_ticker.last_price),
+                    daily_price_chg: de_ticker.price_change_pt,
+                    daily_volume: Qty::from_f64(daily_volume),
+                    open_interest: None,
+                    funding_rate: None,
+                    spread_bps: TickerStats::spread_in_bps(
+                        parse(de_ticker.bid),
+                        parse(de_ticker.ask),
+                    ),
+                };
+
+                Some((ticker, ticker_stats))
+            })
+            .collect::<HashMap<_, _>>();
+
+        if stats.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(vec![Event::TickerStatsReceived(exchange, stats)])
+    }
+
+    async fn on_disconnected(&mut self, _reason: &str) -> Vec<Event> {
+        Vec::new()
+    }
+}
+
+/// 24h stats of the listed tickers, pushed once a second for those that changed
+pub fn connect_ticker_stats_stream(
+    tickers: Vec<TickerInfo>,
+    market: MarketKind,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+) -> impl Stream<Item = Event> {
+    let ticker_info_map = tickers
+        .iter()
+        .map(|ticker_info| (ticker_info.ticker, *ticker_info))
+        .collect();
+
+    let adapter = TickerStatsAdapter {
+        market,
+        ticker_info_map,
+        proxy_cfg,
+    };
+
+    WsSession::with_opcode_ping(BINANCE_OPCODE_PING_PAYLOAD, Arc::default())
+        .unscoped()
+        .run(adapter)
+}
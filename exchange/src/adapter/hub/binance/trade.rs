@@ -0,0 +1,419 @@
+//! Signed order endpoints, see [`crate::adapter::trading`]
+
+use super::{INVERSE_PERP_DOMAIN, LINEAR_PERP_DOMAIN, SPOT_DOMAIN};
+use crate::adapter::trading::{
+    self, Balance, Credentials, LimitOrder, OrderSide, Position, RECV_WINDOW_MS, UserTrade,
+    WorkingOrder,
+};
+use crate::adapter::{AdapterError, MarketKind};
+use crate::unit::{Price, Qty};
+use crate::{TickerInfo, UnixMs};
+
+use reqwest::{Client, Method};
+
+const API_KEY_HEADER: &str = "X-MBX-APIKEY";
+
+fn endpoint(market: MarketKind, path: &str) -> String {
+    match market {
+        MarketKind::Spot => format!("{SPOT_DOMAIN}/api/v3/{path}"),
+        MarketKind::LinearPerps => format!("{LINEAR_PERP_DOMAIN}/fapi/v1/{path}"),
+        MarketKind::InversePerps => format!("{INVERSE_PERP_DOMAIN}/dapi/v1/{path}"),
+    }
+}
+
+/// Sends `params` as a signed query string, errors carry the `msg` of rejections
+async fn signed(
+    client: &Client,
+    credentials: &Credentials,
+    method: Method,
+    url: &str,
+    params: &[(&str, String)],
+) -> Result<serde_json::Value, AdapterError> {
+    let mut query = params
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>();
+    query.push(format!("recvWindow={RECV_WINDOW_MS}"));
+    query.push(format!("timestamp={}", trading::timestamp_ms()));
+
+    let query = query.join("&");
+    let signature = trading::sign(&credentials.secret, &query);
+
+    let response = client
+        .request(
+            method.clone(),
+            format!("{url}?{query}&signature={signature}"),
+        )
+        .header(API_KEY_HEADER, credentials.api_key.trim())
+        .send()
+        .await
+        .map_err(|error| AdapterError::request_failed(&method, url, error.without_url()))?;
+
+    trading::read_response(response, url, |value| {
+        let code = value.get("code")?.as_i64()?;
+        (code < 0).then(|| {
+            let msg = value.get("msg").and_then(|msg| msg.as_str()).unwrap_or("");
+            format!("Binance rejected the request ({code}): {msg}")
+        })
+    })
+    .await
+}
+
+pub(crate) async fn place_limit(
+    client: &Client,
+    credentials: &Credentials,
+    order: LimitOrder,
+) -> Result<WorkingOrder, AdapterError> {
+    trading::validate(&order)?;
+
+    let ticker_info = order.ticker_info;
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let side = match order.side {
+        OrderSide::Buy => "BUY",
+        OrderSide::Sell => "SELL",
+    };
+
+    let response = signed(
+        client,
+        credentials,
+        Method::POST,
+        &endpoint(market, "order"),
+        &[
+            ("symbol", symbol.to_uppercase()),
+            ("side", side.to_string()),
+            ("type", "LIMIT".to_string()),
+            ("timeInForce", "GTC".to_string()),
+            (
+                "quantity",
+                trading::format_qty(order.qty, ticker_info.min_qty),
+            ),
+            ("price", order.price.to_string(ticker_info.min_ticksize)),
+        ],
+    )
+    .await?;
+
+    parse_order(&response, ticker_info).ok_or_else(|| {
+        AdapterError::ParseError("Binance order response is missing fields".to_string())
+    })
+}
+
+pub(crate) async fn cancel(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    order_id: &str,
+) -> Result<(), AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+
+    signed(
+        client,
+        credentials,
+        Method::DELETE,
+        &endpoint(market, "order"),
+        &[
+            ("symbol", symbol.to_uppercase()),
+            ("orderId", order_id.to_string()),
+        ],
+    )
+    .await
+    .map(|_| ())
+}
+
+pub(crate) async fn open_orders(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+) -> Result<Vec<WorkingOrder>, AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let response = signed(
+        client,
+        credentials,
+        Method::GET,
+        &endpoint(market, "openOrders"),
+        &[("symbol", symbol.to_uppercase())],
+    )
+    .await?;
+
+    let orders = response
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Binance open orders: not a list".to_string()))?;
+
+    Ok(orders
+        .iter()
+        .filter(|order| order.get("type").and_then(|kind| kind.as_str()) == Some("LIMIT"))
+        .filter_map(|order| parse_order(order, ticker_info))
+        .collect())
+}
+
+pub(crate) async fn positions(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+) -> Result<Vec<Position>, AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let symbol = symbol.to_uppercase();
+
+    let (url, params) = match market {
+        MarketKind::LinearPerps => (
+            format!("{LINEAR_PERP_DOMAIN}/fapi/v2/positionRisk"),
+            vec![("symbol", symbol.clone())],
+        ),
+        // Filtered by pair, e.g. BTCUSD for BTCUSD_PERP and its quarterlies
+        MarketKind::InversePerps => (
+            endpoint(market, "positionRisk"),
+            vec![(
+                "pair",
+                symbol.split('_').next().unwrap_or(&symbol).to_string(),
+            )],
+        ),
+        MarketKind::Spot => return Ok(vec![]),
+    };
+
+    let response = signed(client, credentials, Method::GET, &url, &params).await?;
+
+    let positions = response
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Binance positions: not a list".to_string()))?;
+
+    Ok(positions
+        .iter()
+        .filter(|position| position.get("symbol").and_then(|s| s.as_str()) == Some(symbol.as_str()))
+        .filter_map(|position| parse_position(position, ticker_info))
+        .collect())
+}
+
+pub(crate) async fn user_trades(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    since: UnixMs,
+) -> Result<Vec<UserTrade>, AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let path = match market {
+        MarketKind::Spot => "myTrades",
+        MarketKind::LinearPerps | MarketKind::InversePerps => "userTrades",
+    };
+
+    let response = signed(
+        client,
+        credentials,
+        Method::GET,
+        &endpoint(market, path),
+        &[
+            ("symbol", symbol.to_uppercase()),
+            ("startTime", since.as_u64().to_string()),
+            ("limit", "1000".to_string()),
+        ],
+    )
+    .await?;
+
+    let trades = response
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Binance user trades: not a list".to_string()))?;
+
+    Ok(trades
+        .iter()
+        .filter_map(|trade| {
+            // Spot trades only carry `isBuyer`, futures ones `buyer` and `side`
+            let is_buy = trade
+                .get("isBuyer")
+                .or_else(|| trade.get("buyer"))?
+                .as_bool()?;
+
+            Some(UserTrade {
+                id: trade.get("id")?.as_u64()?.to_string(),
+                ticker: ticker_info.ticker,
+                side: if is_buy {
+                    OrderSide::Buy
+                } else {
+                    OrderSide::Sell
+                },
+                price: Price::from_f64(trading::decimal(trade.get("price")?)?)
+                    .round_to_min_tick(ticker_info.min_ticksize),
+                qty: Qty::from_f64(trading::decimal(trade.get("qty")?)?),
+                time: UnixMs::new(trade.get("time")?.as_u64()?),
+            })
+        })
+        .collect())
+}
+
+/// Spot, USDⓈ-M and COIN-M wallets, fetched at once. Keys usually aren't enabled for all of
+/// them, so only fails if none could be read
+pub(crate) async fn balances(
+    client: &Client,
+    credentials: &Credentials,
+) -> Result<Vec<Balance>, AdapterError> {
+    let (spot, linear, inverse) = tokio::join!(
+        spot_balances(client, credentials),
+        futures_balances(client, credentials, MarketKind::LinearPerps),
+        futures_balances(client, credentials, MarketKind::InversePerps),
+    );
+
+    let mut balances = vec![];
+    let mut error = None;
+
+    for (market, result) in [
+        (MarketKind::Spot, spot),
+        (MarketKind::LinearPerps, linear),
+        (MarketKind::InversePerps, inverse),
+    ] {
+        match result {
+            Ok(mut fetched) => balances.append(&mut fetched),
+            Err(err) => {
+                log::debug!("Binance {market} balances unavailable: {err}");
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    match error {
+        Some(err) if balances.is_empty() => Err(err),
+        _ => Ok(balances),
+    }
+}
+
+async fn spot_balances(
+    client: &Client,
+    credentials: &Credentials,
+) -> Result<Vec<Balance>, AdapterError> {
+    let response = signed(
+        client,
+        credentials,
+        Method::GET,
+        &endpoint(MarketKind::Spot, "account"),
+        &[("omitZeroBalances", "true".to_string())],
+    )
+    .await?;
+
+    let balances = response
+        .get("balances")
+        .and_then(|balances| balances.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Binance spot account: no balances".to_string()))?;
+
+    Ok(balances
+        .iter()
+        .filter_map(|balance| {
+            let free = trading::decimal(balance.get("free")?)?;
+            let locked = trading::decimal(balance.get("locked")?)?;
+            if free + locked <= 0.0 {
+                return None;
+            }
+
+            Some(Balance {
+                account: "Spot",
+                asset: balance.get("asset")?.as_str()?.to_string(),
+                wallet: free + locked,
+                available: free,
+                unrealized_pnl: 0.0,
+                realized_pnl: None,
+                margin_used: 0.0,
+            })
+        })
+        .collect())
+}
+
+async fn futures_balances(
+    client: &Client,
+    credentials: &Credentials,
+    market: MarketKind,
+) -> Result<Vec<Balance>, AdapterError> {
+    let (account_url, account) = match market {
+        MarketKind::LinearPerps => (format!("{LINEAR_PERP_DOMAIN}/fapi/v2/account"), "Linear"),
+        MarketKind::InversePerps => (endpoint(market, "account"), "Inverse"),
+        MarketKind::Spot => return Ok(vec![]),
+    };
+
+    let response = signed(client, credentials, Method::GET, &account_url, &[]).await?;
+    let income = signed(
+        client,
+        credentials,
+        Method::GET,
+        &endpoint(market, "income"),
+        &[
+            ("incomeType", "REALIZED_PNL".to_string()),
+            ("startTime", trading::utc_day_start_ms().to_string()),
+            ("limit", "1000".to_string()),
+        ],
+    )
+    .await?;
+
+    let assets = response
+        .get("assets")
+        .and_then(|assets| assets.as_array())
+        .ok_or_else(|| {
+            AdapterError::ParseError("Binance futures account: no assets".to_string())
+        })?;
+    let income = income.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    Ok(assets
+        .iter()
+        .filter_map(|asset| {
+            let name = asset.get("asset")?.as_str()?;
+            let wallet = trading::decimal(asset.get("walletBalance")?)?;
+            let unrealized_pnl = trading::decimal(asset.get("unrealizedProfit")?)?;
+            if wallet == 0.0 && unrealized_pnl == 0.0 {
+                return None;
+            }
+
+            let realized_pnl = income
+                .iter()
+                .filter(|entry| entry.get("asset").and_then(|a| a.as_str()) == Some(name))
+                .filter_map(|entry| entry.get("income").and_then(trading::decimal))
+                .sum();
+
+            Some(Balance {
+                account,
+                asset: name.to_string(),
+                wallet,
+                available: trading::decimal(asset.get("availableBalance")?)?,
+                unrealized_pnl,
+                realized_pnl: Some(realized_pnl),
+                margin_used: trading::decimal(asset.get("initialMargin")?)?,
+            })
+        })
+        .collect())
+}
+
+/// `positionAmt` is signed, zero for symbols without a position
+fn parse_position(position: &serde_json::Value, ticker_info: TickerInfo) -> Option<Position> {
+    let amount = trading::decimal(position.get("positionAmt")?)?;
+    if amount == 0.0 {
+        return None;
+    }
+
+    let min_tick = ticker_info.min_ticksize;
+    let liquidation = trading::decimal(position.get("liquidationPrice")?)?;
+
+    Some(Position {
+        ticker: ticker_info.ticker,
+        side: if amount > 0.0 {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        },
+        qty: Qty::from_f64(amount.abs()),
+        entry_price: Price::from_f64(trading::decimal(position.get("entryPrice")?)?)
+            .round_to_min_tick(min_tick),
+        liquidation_price: (liquidation > 0.0)
+            .then(|| Price::from_f64(liquidation).round_to_min_tick(min_tick)),
+    })
+}
+
+fn parse_order(order: &serde_json::Value, ticker_info: TickerInfo) -> Option<WorkingOrder> {
+    let side = match order.get("side")?.as_str()? {
+        "BUY" => OrderSide::Buy,
+        "SELL" => OrderSide::Sell,
+        _ => return None,
+    };
+
+    Some(WorkingOrder {
+        id: order.get("orderId")?.as_u64()?.to_string(),
+        ticker: ticker_info.ticker,
+        side,
+        price: Price::from_f64(trading::decimal(order.get("price")?)?)
+            .round_to_min_tick(ticker_info.min_ticksize),
+        qty: Qty::from_f64(trading::decimal(order.get("origQty")?)?),
+        filled: Qty::from_f64(trading::decimal(order.get("executedQty")?)?),
+    })
+}
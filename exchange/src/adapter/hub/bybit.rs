@@ -1,5 +1,5 @@
 use crate::{
-    Event, Kline, OpenInterest, PushFrequency, TickerInfo, Timeframe, UnixMs,
+    Event, FundingRate, Kline, OpenInterest, PushFrequency, TickerInfo, Timeframe, UnixMs,
     adapter::limiter::FixedWindowRateLimiterConfig,
     adapter::{Exchange, MarketKind, StreamTicksize},
     unit::qty::RawQtyUnit,
@@ -10,6 +10,7 @@ use std::time::Duration;
 
 pub mod fetch;
 pub mod stream;
+pub(crate) mod trade;
 
 const WS_DOMAIN: &str = "stream.bybit.com";
 const FETCH_DOMAIN: &str = "https://api.bybit.com";
@@ -137,6 +138,20 @@ impl BybitHandle {
             .await
     }
 
+    pub async fn fetch_funding_rate(
+        &self,
+        ticker: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> Result<Vec<FundingRate>, AdapterError> {
+        self.request_port
+            .request(move |reply| BybitCommand::FundingRate {
+                ticker,
+                range,
+                reply,
+            })
+            .await
+    }
+
     pub fn connect_depth_stream(
         self,
         ticker_info: TickerInfo,
@@ -161,6 +176,22 @@ impl BybitHandle {
     ) -> impl futures::Stream<Item = Event> {
         stream::connect_kline_stream(streams, market_type, self.proxy_cfg)
     }
+
+    pub fn connect_mark_price_stream(
+        self,
+        tickers: Vec<TickerInfo>,
+        market_type: MarketKind,
+    ) -> impl futures::Stream<Item = Event> {
+        stream::connect_mark_price_stream(tickers, market_type, self.proxy_cfg)
+    }
+
+    pub fn connect_ticker_stats_stream(
+        self,
+        tickers: Vec<TickerInfo>,
+        market_type: MarketKind,
+    ) -> impl futures::Stream<Item = Event> {
+        stream::connect_ticker_stats_stream(tickers, market_type, self.proxy_cfg)
+    }
 }
 
 struct Worker {
@@ -214,4 +245,12 @@ impl super::FetchCommandHandler<MarketKind> for Worker {
             fetch::fetch_historical_oi(&mut self.hub, ticker_info, range, timeframe).await
         })
     }
+
+    fn fetch_funding_rate(
+        &mut self,
+        ticker_info: TickerInfo,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> futures::future::BoxFuture<'_, Result<Vec<FundingRate>, AdapterError>> {
+        Box::pin(async move { fetch::fetch_funding_rate(&mut self.hub, ticker_info, range).await })
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    Kline, OpenInterest, Price, Qty, Ticker, TickerInfo, TickerStats, Timeframe, UnixMs,
+    FundingRate, Kline, OpenInterest, Price, Qty, Ticker, TickerInfo, TickerStats, Timeframe,
+    UnixMs,
     adapter::hub::TickerMetadataMap,
     serde_util,
     unit::qty::{QtyNormalization, SizeUnit, volume_size_unit},
@@ -26,6 +27,15 @@ struct DeOpenInterest {
     pub timestamp: u64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeFundingRate {
+    #[serde(deserialize_with = "serde_util::de_string_to_number")]
+    pub funding_rate: f64,
+    #[serde(deserialize_with = "serde_util::de_string_to_number")]
+    pub funding_rate_timestamp: u64,
+}
+
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct ApiResponse {
@@ -173,6 +183,13 @@ pub(super) async fn fetch_ticker_stats(
             mark_price: Price::from_f64(mark_price),
             daily_price_chg: daily_price_chg * 100.0,
             daily_volume: Qty::from_f64(volume_in_usd),
+            // Both left empty for spot
+            open_interest: serde_util::value_as_f64(&item["openInterestValue"]),
+            funding_rate: serde_util::value_as_f32(&item["fundingRate"]),
+            spread_bps: TickerStats::spread_in_bps(
+                serde_util::value_as_f64(&item["bid1Price"]),
+                serde_util::value_as_f64(&item["ask1Price"]),
+            ),
         };
 
         ticker_prices_map.insert(Ticker::new(symbol, exchange), ticker_stats);
@@ -348,3 +365,72 @@ pub(super) async fn fetch_historical_oi(
 
     Ok(open_interest)
 }
+
+pub(super) async fn fetch_funding_rate(
+    hub: &mut HttpHub<BybitLimiter>,
+    ticker_info: TickerInfo,
+    range: Option<(UnixMs, UnixMs)>,
+) -> Result<Vec<FundingRate>, AdapterError> {
+    let (ticker_str, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let ticker_str = ticker_str.to_uppercase();
+
+    let category = match market {
+        MarketKind::LinearPerps => "linear",
+        MarketKind::InversePerps => "inverse",
+        MarketKind::Spot => {
+            return Err(AdapterError::InvalidRequest(format!(
+                "Unsupported market type for funding rate: {market:?}"
+            )));
+        }
+    };
+
+    let mut url = format!(
+        "{FETCH_DOMAIN}/v5/market/funding/history?category={category}&symbol={ticker_str}&limit=200",
+    );
+
+    // Bybit rejects a start time without an end time
+    if let Some((start, end)) = range {
+        url.push_str(&format!(
+            "&startTime={}&endTime={}",
+            start.as_u64(),
+            end.as_u64()
+        ));
+    }
+
+    let response_text = hub.http_text_with_limiter(&url, 1, None, None).await?;
+
+    let content: Value = sonic_rs::from_str(&response_text).map_err(|e| {
+        log::error!(
+            "Failed to parse JSON from {}: {}\nResponse: {}",
+            url,
+            e,
+            response_text
+        );
+        AdapterError::ParseError(e.to_string())
+    })?;
+
+    let result_list = content["result"]["list"].as_array().ok_or_else(|| {
+        log::error!("Result list is not an array in response: {}", response_text);
+        AdapterError::ParseError("Result list is not an array".to_string())
+    })?;
+
+    let bybit_funding: Vec<DeFundingRate> =
+        serde_json::from_value(json!(result_list)).map_err(|e| {
+            log::error!(
+                "Failed to parse funding rate array: {}\nResponse: {}",
+                e,
+                response_text
+            );
+            AdapterError::ParseError(format!("Failed to parse funding rate: {e}"))
+        })?;
+
+    // Newest first in the response
+    Ok(bybit_funding
+        .into_iter()
+        .rev()
+        .map(|x| FundingRate {
+            time: x.funding_rate_timestamp.into(),
+            rate: x.funding_rate,
+        })
+        .collect())
+}
@@ -1,23 +1,28 @@
 use crate::{
-    Event, Kline, Price, PushFrequency, Ticker, TickerInfo, Timeframe, Trade, Volume,
+    Event, Kline, MarkPrice, Price, PushFrequency, Ticker, TickerInfo, TickerStats, Timeframe,
+    Trade, Volume,
     adapter::{
         MarketKind, StreamKind, StreamTicksize,
         hub::{TradeBuffer, WsAdapter, WsSession, WsTransport},
     },
     depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
     serde_util::de_string_to_number,
-    unit::qty::{QtyNormalization, SizeUnit, volume_size_unit},
+    unit::{
+        Qty,
+        qty::{QtyNormalization, SizeUnit, volume_size_unit},
+    },
 };
 
 use super::{WS_DOMAIN, exchange_from_market_type, raw_qty_unit_from_market_type};
 use crate::adapter::hub::AdapterError;
 use fastwebsockets::Frame;
 use futures::Stream;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde_json::Value;
 use sonic_rs::{Deserialize, JsonValueTrait, to_object_iter_unchecked};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 const BYBIT_PING_PAYLOAD: &[u8] = br#"{"op":"ping"}"#;
 
@@ -61,10 +66,84 @@ pub struct SonicKline {
     pub interval: String,
 }
 
+/// `tickers` topic payload, deltas only carry the fields that changed
+#[derive(Deserialize, Debug, Default)]
+struct SonicTickerUpdate {
+    #[serde(rename = "markPrice", default)]
+    pub mark_price: Option<String>,
+    #[serde(rename = "indexPrice", default)]
+    pub index_price: Option<String>,
+    #[serde(rename = "lastPrice", default)]
+    pub last_price: Option<String>,
+    #[serde(rename = "price24hPcnt", default)]
+    pub price_24h_pcnt: Option<String>,
+    #[serde(rename = "volume24h", default)]
+    pub volume_24h: Option<String>,
+    #[serde(rename = "openInterestValue", default)]
+    pub open_interest_value: Option<String>,
+    #[serde(rename = "fundingRate", default)]
+    pub funding_rate: Option<String>,
+    #[serde(rename = "bid1Price", default)]
+    pub bid1_price: Option<String>,
+    #[serde(rename = "ask1Price", default)]
+    pub ask1_price: Option<String>,
+}
+
+impl SonicTickerUpdate {
+    /// Takes the fields a delta carries, keeping the rest
+    fn merge(&mut self, update: SonicTickerUpdate) {
+        let fields = [
+            (&mut self.mark_price, update.mark_price),
+            (&mut self.index_price, update.index_price),
+            (&mut self.last_price, update.last_price),
+            (&mut self.price_24h_pcnt, update.price_24h_pcnt),
+            (&mut self.volume_24h, update.volume_24h),
+            (&mut self.open_interest_value, update.open_interest_value),
+            (&mut self.funding_rate, update.funding_rate),
+            (&mut self.bid1_price, update.bid1_price),
+            (&mut self.ask1_price, update.ask1_price),
+        ];
+
+        for (field, value) in fields {
+            if value.is_some() {
+                *field = value;
+            }
+        }
+    }
+
+    /// Same as the REST stats, `None` until the last price, change and volume are known
+    fn to_stats(&self, market_type: MarketKind) -> Option<TickerStats> {
+        let parse = |value: &Option<String>| value.as_deref()?.parse::<f64>().ok();
+
+        let last_price = parse(&self.last_price)?;
+        let daily_price_chg = parse(&self.price_24h_pcnt)? as f32;
+        let daily_volume = parse(&self.volume_24h)?;
+
+        let volume_in_usd = if market_type == MarketKind::InversePerps {
+            daily_volume
+        } else {
+            daily_volume * last_price
+        };
+
+        Some(TickerStats {
+            mark_price: Price::from_f64(last_price),
+            daily_price_chg: daily_price_chg * 100.0,
+            daily_volume: Qty::from_f64(volume_in_usd),
+            open_interest: parse(&self.open_interest_value),
+            funding_rate: parse(&self.funding_rate).map(|rate| rate as f32),
+            spread_bps: TickerStats::spread_in_bps(
+                parse(&self.bid1_price),
+                parse(&self.ask1_price),
+            ),
+        })
+    }
+}
+
 enum StreamData {
     Trade(Ticker, Vec<SonicTrade>),
     Depth(SonicDepth, String, u64),
     Kline(Ticker, Vec<SonicKline>),
+    Tickers(Ticker, SonicTickerUpdate, u64),
 }
 
 #[derive(Debug)]
@@ -72,6 +151,7 @@ enum StreamName {
     Depth(Ticker),
     Trade(Ticker),
     Kline(Ticker),
+    Tickers(Ticker),
     Unknown,
 }
 
@@ -87,6 +167,7 @@ impl StreamName {
                 Some(&"publicTrade") => StreamName::Trade(ticker),
                 Some(&"orderbook") => StreamName::Depth(ticker),
                 Some(&"kline") => StreamName::Kline(ticker),
+                Some(&"tickers") => StreamName::Tickers(ticker),
                 _ => StreamName::Unknown,
             }
         } else {
@@ -100,6 +181,7 @@ enum StreamWrapper {
     Trade,
     Depth,
     Kline,
+    Tickers,
 }
 
 async fn connect_and_subscribe(
@@ -139,6 +221,7 @@ fn feed_de(
 ) -> Result<StreamData, AdapterError> {
     let mut stream_type: Option<StreamWrapper> = None;
     let mut depth_wrap: Option<SonicDepth> = None;
+    let mut ticker_wrap: Option<SonicTickerUpdate> = None;
 
     let mut data_type = String::new();
     let mut topic_ticker: Option<Ticker> = ticker;
@@ -169,6 +252,10 @@ fn feed_de(
                         stream_type = Some(StreamWrapper::Kline);
                         topic_ticker = Some(t);
                     }
+                    StreamName::Tickers(t) => {
+                        stream_type = Some(StreamWrapper::Tickers);
+                        topic_ticker = Some(t);
+                    }
                     _ => {
                         log::error!("Unknown stream name");
                     }
@@ -221,6 +308,12 @@ fn feed_de(
                         ));
                     }
                 }
+                Some(StreamWrapper::Tickers) => {
+                    ticker_wrap = Some(
+                        sonic_rs::from_str(&v.as_raw_faststr())
+                            .map_err(|e| AdapterError::ParseError(e.to_string()))?,
+                    );
+                }
                 _ => {
                     log::error!("Unknown stream type");
                 }
@@ -233,6 +326,19 @@ fn feed_de(
                 .ok_or_else(|| AdapterError::ParseError("Failed to parse u64".to_string()))?;
 
             return Ok(StreamData::Depth(dw, data_type.to_string(), time));
+        } else if k == "ts"
+            && let Some(tw) = ticker_wrap.take()
+        {
+            let time: u64 = v
+                .as_u64()
+                .ok_or_else(|| AdapterError::ParseError("Failed to parse u64".to_string()))?;
+
+            return match topic_ticker {
+                Some(t) => Ok(StreamData::Tickers(t, tw, time)),
+                None => Err(AdapterError::ParseError(
+                    "Missing ticker for tickers data".to_string(),
+                )),
+            };
         }
     }
 
@@ -643,3 +749,222 @@ pub fn connect_kline_stream(
 
     WsSession::with_text_ping(BYBIT_PING_PAYLOAD, stream_scope).run(adapter)
 }
+
+struct MarkPriceAdapter {
+    market_type: MarketKind,
+    ticker_info_map: HashMap<Ticker, TickerInfo>,
+    /// Last known `(mark, index)` per ticker, deltas omit unchanged fields
+    last: HashMap<Ticker, (Option<Price>, Option<Price>)>,
+    subscribe_message: serde_json::Value,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+}
+
+impl WsAdapter for MarkPriceAdapter {
+    async fn connect(&mut self) -> Result<WsTransport, String> {
+        let market_type = self.market_type;
+        connect_and_subscribe(
+            &self.subscribe_message,
+            market_type,
+            self.proxy_cfg.as_ref(),
+        )
+        .await
+    }
+
+    async fn on_connected(&mut self) -> Vec<Event> {
+        // Bybit sends a fresh snapshot after subscribing
+        self.last.clear();
+        Vec::new()
+    }
+
+    async fn on_text(&mut self, payload: &[u8]) -> Result<Vec<Event>, String> {
+        if let Ok(StreamData::Tickers(ticker, update, time)) =
+            feed_de(payload, None, self.market_type)
+        {
+            let Some(ticker_info) = self.ticker_info_map.get(&ticker).copied() else {
+                log::error!("Ticker info not found for ticker: {}", ticker);
+                return Ok(Vec::new());
+            };
+
+            let to_price = |value: Option<String>| {
+                value
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .filter(|value| *value > 0.0)
+                    .map(|value| Price::from_f64(value).round_to_min_tick(ticker_info.min_ticksize))
+            };
+
+            let last = self.last.entry(ticker).or_default();
+            if let Some(mark) = to_price(update.mark_price) {
+                last.0 = Some(mark);
+            }
+            if let Some(index) = to_price(update.index_price) {
+                last.1 = Some(index);
+            }
+
+            if let (Some(mark), index) = *last {
+                return Ok(vec![Event::MarkPriceReceived(
+                    StreamKind::MarkPrice { ticker_info },
+                    MarkPrice {
+                        time: time.into(),
+                        mark,
+                        index,
+                    },
+                )]);
+            }
+        }
+
+        Ok(Vec::new())
+    }
+
+    async fn on_disconnected(&mut self, _reason: &str) -> Vec<Event> {
+        Vec::new()
+    }
+}
+
+/// Mark and index price of perpetuals, taken from the `tickers` topic
+pub fn connect_mark_price_stream(
+    tickers: Vec<TickerInfo>,
+    market_type: MarketKind,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+) -> impl Stream<Item = Event> {
+    let stream_scope: Arc<[StreamKind]> = Arc::from(
+        tickers
+            .iter()
+            .map(|ticker_info| StreamKind::MarkPrice {
+                ticker_info: *ticker_info,
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice(),
+    );
+
+    let stream_str = tickers
+        .iter()
+        .map(|ticker_info| format!("tickers.{}", ticker_info.ticker.to_full_symbol_and_type().0))
+        .collect::<Vec<String>>();
+    let subscribe_message = serde_json::json!({
+        "op": "subscribe",
+        "args": stream_str
+    });
+
+    let ticker_info_map = tickers
+        .iter()
+        .map(|ticker_info| (ticker_info.ticker, *ticker_info))
+        .collect();
+
+    let adapter = MarkPriceAdapter {
+        market_type,
+        ticker_info_map,
+        last: HashMap::new(),
+        subscribe_message,
+        proxy_cfg,
+    };
+
+    WsSession::with_text_ping(BYBIT_PING_PAYLOAD, stream_scope).run(adapter)
+}
+
+/// How often the ticker stats that changed are pushed, the topic itself updates every 100ms
+const TICKER_STATS_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+struct TickerStatsAdapter {
+    market_type: MarketKind,
+    ticker_info_map: HashMap<Ticker, TickerInfo>,
+    /// Latest snapshot of each ticker with the deltas since applied
+    last: HashMap<Ticker, SonicTickerUpdate>,
+    /// Tickers updated since the last flush
+    changed: FxHashSet<Ticker>,
+    subscribe_message: serde_json::Value,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+}
+
+impl WsAdapter for TickerStatsAdapter {
+    async fn connect(&mut self) -> Result<WsTransport, String> {
+        let market_type = self.market_type;
+        connect_and_subscribe(
+            &self.subscribe_message,
+            market_type,
+            self.proxy_cfg.as_ref(),
+        )
+        .await
+    }
+
+    fn tick_interval(&self) -> Duration {
+        TICKER_STATS_FLUSH_INTERVAL
+    }
+
+    async fn on_connected(&mut self) -> Vec<Event> {
+        // Bybit sends a fresh snapshot after subscribing
+        self.last.clear();
+        self.changed.clear();
+        Vec::new()
+    }
+
+    async fn on_tick(&mut self) -> Vec<Event> {
+        if self.changed.is_empty() {
+            return Vec::new();
+        }
+
+        let stats = self
+            .changed
+            .drain()
+            .filter_map(|ticker| {
+                let stats = self.last.get(&ticker)?.to_stats(self.market_type)?;
+                Some((ticker, stats))
+            })
+            .collect::<HashMap<_, _>>();
+
+        if stats.is_empty() {
+            return Vec::new();
+        }
+        let exchange = exchange_from_market_type(self.market_type);
+        vec![Event::TickerStatsReceived(exchange, stats)]
+    }
+
+    async fn on_text(&mut self, payload: &[u8]) -> Result<Vec<Event>, String> {
+        if let Ok(StreamData::Tickers(ticker, update, _)) = feed_de(payload, None, self.market_type)
+            && self.ticker_info_map.contains_key(&ticker)
+        {
+            self.last.entry(ticker).or_default().merge(update);
+            self.changed.insert(ticker);
+        }
+
+        Ok(Vec::new())
+    }
+
+    async fn on_disconnected(&mut self, _reason: &str) -> Vec<Event> {
+        Vec::new()
+    }
+}
+
+/// 24h stats of the listed tickers from the `tickers` topic, pushed once a second for
+/// those that changed
+pub fn connect_ticker_stats_stream(
+    tickers: Vec<TickerInfo>,
+    market_type: MarketKind,
+    proxy_cfg: Option<crate::proxy::Proxy>,
+) -> impl Stream<Item = Event> {
+    let stream_str = tickers
+        .iter()
+        .map(|ticker_info| format!("tickers.{}", ticker_info.ticker.to_full_symbol_and_type().0))
+        .collect::<Vec<String>>();
+    let subscribe_message = serde_json::json!({
+        "op": "subscribe",
+        "args": stream_str
+    });
+
+    let ticker_info_map = tickers
+        .iter()
+        .map(|ticker_info| (ticker_info.ticker, *ticker_info))
+        .collect();
+
+    let adapter = TickerStatsAdapter {
+        market_type,
+        ticker_info_map,
+        last: HashMap::new(),
+        changed: FxHashSet::default(),
+        subscribe_message,
+        proxy_cfg,
+    };
+
+    WsSession::with_text_ping(BYBIT_PING_PAYLOAD, Arc::default())
+        .unscoped()
+        .run(adapter)
+}
@@ -0,0 +1,422 @@
+//! Signed v5 order endpoints, see [`crate::adapter::trading`]
+
+use super::FETCH_DOMAIN;
+use crate::adapter::trading::{
+    self, Balance, Credentials, LimitOrder, OrderSide, Position, RECV_WINDOW_MS, UserTrade,
+    WorkingOrder,
+};
+use crate::adapter::{AdapterError, MarketKind};
+use crate::unit::{Price, Qty};
+use crate::{TickerInfo, UnixMs};
+
+use reqwest::{Client, Method};
+
+fn category(market: MarketKind) -> &'static str {
+    match market {
+        MarketKind::Spot => "spot",
+        MarketKind::LinearPerps => "linear",
+        MarketKind::InversePerps => "inverse",
+    }
+}
+
+/// Signs `payload`, the JSON body of a POST or the query string of a GET, and returns the
+/// `result` object. Errors carry the `retMsg` of rejections
+async fn signed(
+    client: &Client,
+    credentials: &Credentials,
+    method: Method,
+    path: &str,
+    payload: String,
+) -> Result<serde_json::Value, AdapterError> {
+    let timestamp = trading::timestamp_ms();
+    let api_key = credentials.api_key.trim();
+    let signature = trading::sign(
+        &credentials.secret,
+        &format!("{timestamp}{api_key}{RECV_WINDOW_MS}{payload}"),
+    );
+
+    let url = format!("{FETCH_DOMAIN}{path}");
+    let request = if method == Method::GET {
+        client.get(format!("{url}?{payload}"))
+    } else {
+        client
+            .request(method.clone(), &url)
+            .header("Content-Type", "application/json")
+            .body(payload)
+    };
+
+    let response = request
+        .header("X-BAPI-API-KEY", api_key)
+        .header("X-BAPI-TIMESTAMP", timestamp.to_string())
+        .header("X-BAPI-RECV-WINDOW", RECV_WINDOW_MS.to_string())
+        .header("X-BAPI-SIGN", signature)
+        .send()
+        .await
+        .map_err(|error| AdapterError::request_failed(&method, &url, error.without_url()))?;
+
+    let mut value = trading::read_response(response, &url, |value| {
+        let code = value.get("retCode")?.as_i64()?;
+        (code != 0).then(|| {
+            let msg = value
+                .get("retMsg")
+                .and_then(|msg| msg.as_str())
+                .unwrap_or("");
+            format!("Bybit rejected the request ({code}): {msg}")
+        })
+    })
+    .await?;
+
+    Ok(value
+        .get_mut("result")
+        .map(serde_json::Value::take)
+        .unwrap_or_default())
+}
+
+pub(crate) async fn place_limit(
+    client: &Client,
+    credentials: &Credentials,
+    order: LimitOrder,
+) -> Result<WorkingOrder, AdapterError> {
+    trading::validate(&order)?;
+
+    let ticker_info = order.ticker_info;
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let price = order.price.to_string(ticker_info.min_ticksize);
+    let qty = trading::format_qty(order.qty, ticker_info.min_qty);
+
+    let body = serde_json::json!({
+        "category": category(market),
+        "symbol": symbol.to_uppercase(),
+        "side": order.side.to_string(),
+        "orderType": "Limit",
+        "qty": qty,
+        "price": price,
+        "timeInForce": "GTC",
+    });
+
+    let result = signed(
+        client,
+        credentials,
+        Method::POST,
+        "/v5/order/create",
+        body.to_string(),
+    )
+    .await?;
+
+    let id = result
+        .get("orderId")
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| AdapterError::ParseError("Bybit order response has no id".to_string()))?;
+
+    // Creation only returns the id, the order is as requested until the next refresh
+    Ok(WorkingOrder {
+        id: id.to_string(),
+        ticker: ticker_info.ticker,
+        side: order.side,
+        price: order.price,
+        qty: Qty::from_f64(qty.parse().unwrap_or(order.qty)),
+        filled: Qty::ZERO,
+    })
+}
+
+pub(crate) async fn cancel(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    order_id: &str,
+) -> Result<(), AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let body = serde_json::json!({
+        "category": category(market),
+        "symbol": symbol.to_uppercase(),
+        "orderId": order_id,
+    });
+
+    signed(
+        client,
+        credentials,
+        Method::POST,
+        "/v5/order/cancel",
+        body.to_string(),
+    )
+    .await
+    .map(|_| ())
+}
+
+pub(crate) async fn open_orders(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+) -> Result<Vec<WorkingOrder>, AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let query = format!(
+        "category={}&symbol={}&openOnly=0&limit=50",
+        category(market),
+        symbol.to_uppercase()
+    );
+
+    let result = signed(
+        client,
+        credentials,
+        Method::GET,
+        "/v5/order/realtime",
+        query,
+    )
+    .await?;
+
+    let orders = result
+        .get("list")
+        .and_then(|list| list.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Bybit open orders: no list".to_string()))?;
+
+    Ok(orders
+        .iter()
+        .filter(|order| order.get("orderType").and_then(|kind| kind.as_str()) == Some("Limit"))
+        .filter_map(|order| parse_order(order, ticker_info))
+        .collect())
+}
+
+pub(crate) async fn positions(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+) -> Result<Vec<Position>, AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+    if market == MarketKind::Spot {
+        return Ok(vec![]);
+    }
+
+    let query = format!(
+        "category={}&symbol={}",
+        category(market),
+        symbol.to_uppercase()
+    );
+
+    let result = signed(client, credentials, Method::GET, "/v5/position/list", query).await?;
+
+    let positions = result
+        .get("list")
+        .and_then(|list| list.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Bybit positions: no list".to_string()))?;
+
+    Ok(positions
+        .iter()
+        .filter_map(|position| parse_position(position, ticker_info))
+        .collect())
+}
+
+/// Only the latest 100 executions since `since` are returned
+pub(crate) async fn user_trades(
+    client: &Client,
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    since: UnixMs,
+) -> Result<Vec<UserTrade>, AdapterError> {
+    let (symbol, market) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let query = format!(
+        "category={}&symbol={}&startTime={}&limit=100",
+        category(market),
+        symbol.to_uppercase(),
+        since.as_u64()
+    );
+
+    let result = signed(
+        client,
+        credentials,
+        Method::GET,
+        "/v5/execution/list",
+        query,
+    )
+    .await?;
+
+    let executions = result
+        .get("list")
+        .and_then(|list| list.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Bybit executions: no list".to_string()))?;
+
+    Ok(executions
+        .iter()
+        // Funding settlements and liquidations are listed too
+        .filter(|execution| execution.get("execType").and_then(|t| t.as_str()) == Some("Trade"))
+        .filter_map(|execution| {
+            let side = match execution.get("side")?.as_str()? {
+                "Buy" => OrderSide::Buy,
+                "Sell" => OrderSide::Sell,
+                _ => return None,
+            };
+
+            Some(UserTrade {
+                id: execution.get("execId")?.as_str()?.to_string(),
+                ticker: ticker_info.ticker,
+                side,
+                price: Price::from_f64(trading::decimal(execution.get("execPrice")?)?)
+                    .round_to_min_tick(ticker_info.min_ticksize),
+                qty: Qty::from_f64(trading::decimal(execution.get("execQty")?)?),
+                time: UnixMs::new(execution.get("execTime")?.as_str()?.parse().ok()?),
+            })
+        })
+        .collect())
+}
+
+/// Coins of the unified trading account, the only one v5 keys trade from
+pub(crate) async fn balances(
+    client: &Client,
+    credentials: &Credentials,
+) -> Result<Vec<Balance>, AdapterError> {
+    let result = signed(
+        client,
+        credentials,
+        Method::GET,
+        "/v5/account/wallet-balance",
+        "accountType=UNIFIED".to_string(),
+    )
+    .await?;
+
+    let coins = result
+        .get("list")
+        .and_then(|list| list.as_array())
+        .and_then(|list| list.first())
+        .and_then(|account| account.get("coin"))
+        .and_then(|coins| coins.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Bybit wallet balance: no coins".to_string()))?;
+
+    let (linear, inverse) = tokio::join!(
+        closed_pnl(client, credentials, MarketKind::LinearPerps),
+        closed_pnl(client, credentials, MarketKind::InversePerps),
+    );
+    let closed = [linear, inverse]
+        .into_iter()
+        .filter_map(|result| {
+            result
+                .inspect_err(|err| log::debug!("Bybit closed PnL unavailable: {err}"))
+                .ok()
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    Ok(coins
+        .iter()
+        .filter_map(|coin| {
+            let field = |key: &str| coin.get(key).and_then(trading::decimal).unwrap_or(0.0);
+
+            let asset = coin.get("coin")?.as_str()?;
+            let wallet = field("walletBalance");
+            let unrealized_pnl = field("unrealisedPnl");
+            if wallet == 0.0 && unrealized_pnl == 0.0 {
+                return None;
+            }
+            let margin_used = field("totalPositionIM") + field("totalOrderIM");
+
+            Some(Balance {
+                account: "Unified",
+                asset: asset.to_string(),
+                wallet,
+                available: (wallet - margin_used - field("locked")).max(0.0),
+                unrealized_pnl,
+                realized_pnl: Some(
+                    closed
+                        .iter()
+                        .filter(|(settle_coin, _)| settle_coin == asset)
+                        .map(|(_, pnl)| pnl)
+                        .sum(),
+                ),
+                margin_used,
+            })
+        })
+        .collect())
+}
+
+/// PnL of the positions closed since the start of the UTC day, with the coin each settled in.
+/// Only the latest 100 closes are summed
+async fn closed_pnl(
+    client: &Client,
+    credentials: &Credentials,
+    market: MarketKind,
+) -> Result<Vec<(String, f64)>, AdapterError> {
+    let query = format!(
+        "category={}&startTime={}&limit=100",
+        category(market),
+        trading::utc_day_start_ms()
+    );
+
+    let result = signed(
+        client,
+        credentials,
+        Method::GET,
+        "/v5/position/closed-pnl",
+        query,
+    )
+    .await?;
+
+    let closes = result
+        .get("list")
+        .and_then(|list| list.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Bybit closed PnL: no list".to_string()))?;
+
+    Ok(closes
+        .iter()
+        .filter_map(|close| {
+            let symbol = close.get("symbol")?.as_str()?;
+            let settle_coin = match market {
+                MarketKind::LinearPerps if symbol.ends_with("USDT") => "USDT",
+                MarketKind::LinearPerps => "USDC",
+                // Inverse contracts settle in their base coin, e.g. BTC for BTCUSD
+                _ => symbol.split("USD").next()?,
+            };
+
+            Some((
+                settle_coin.to_string(),
+                trading::decimal(close.get("closedPnl")?)?,
+            ))
+        })
+        .collect())
+}
+
+/// Flat positions are listed with an empty `side` and zero size
+fn parse_position(position: &serde_json::Value, ticker_info: TickerInfo) -> Option<Position> {
+    let side = match position.get("side")?.as_str()? {
+        "Buy" => OrderSide::Buy,
+        "Sell" => OrderSide::Sell,
+        _ => return None,
+    };
+    let qty = trading::decimal(position.get("size")?)?;
+    if qty <= 0.0 {
+        return None;
+    }
+
+    let min_tick = ticker_info.min_ticksize;
+    let liquidation = position
+        .get("liqPrice")
+        .and_then(trading::decimal)
+        .filter(|price| *price > 0.0);
+
+    Some(Position {
+        ticker: ticker_info.ticker,
+        side,
+        qty: Qty::from_f64(qty),
+        entry_price: Price::from_f64(trading::decimal(position.get("avgPrice")?)?)
+            .round_to_min_tick(min_tick),
+        liquidation_price: liquidation
+            .map(|price| Price::from_f64(price).round_to_min_tick(min_tick)),
+    })
+}
+
+fn parse_order(order: &serde_json::Value, ticker_info: TickerInfo) -> Option<WorkingOrder> {
+    let side = match order.get("side")?.as_str()? {
+        "Buy" => OrderSide::Buy,
+        "Sell" => OrderSide::Sell,
+        _ => return None,
+    };
+
+    Some(WorkingOrder {
+        id: order.get("orderId")?.as_str()?.to_string(),
+        ticker: ticker_info.ticker,
+        side,
+        price: Price::from_f64(trading::decimal(order.get("price")?)?)
+            .round_to_min_tick(ticker_info.min_ticksize),
+        qty: Qty::from_f64(trading::decimal(order.get("qty")?)?),
+        filled: Qty::from_f64(trading::decimal(order.get("cumExecQty")?)?),
+    })
+}
@@ -48,6 +48,15 @@ struct HyperliquidAssetContext {
     mid_price: f64,
     #[serde(rename = "prevDayPx", deserialize_with = "de_string_to_number")]
     prev_day_price: f64,
+    /// Hourly rate, perps only
+    #[serde(default)]
+    funding: Option<String>,
+    /// In the base asset, perps only
+    #[serde(rename = "openInterest", default)]
+    open_interest: Option<String>,
+    /// Prices a fixed notional would fill at on either side, null on thin books
+    #[serde(rename = "impactPxs", default)]
+    impact_prices: Option<[String; 2]>,
 }
 
 impl HyperliquidAssetContext {
@@ -266,6 +275,15 @@ fn insert_ticker_from_ctx(
             mark_price: Price::from_f64(ctx.mark_price),
             daily_price_chg: daily_price_chg_pct(price, ctx.prev_day_price),
             daily_volume: Qty::from_f64(ctx.day_notional_volume),
+            open_interest: ctx
+                .open_interest
+                .as_deref()
+                .and_then(|oi| oi.parse::<f64>().ok())
+                .map(|oi| oi * ctx.mark_price),
+            funding_rate: ctx.funding.as_deref().and_then(|rate| rate.parse().ok()),
+            spread_bps: ctx.impact_prices.as_ref().and_then(|[bid, ask]| {
+                TickerStats::spread_in_bps(bid.parse().ok(), ask.parse().ok())
+            }),
         },
     );
 }
@@ -290,6 +290,12 @@ pub(super) async fn fetch_ticker_stats(
                 mark_price: Price::from_f64(last_price),
                 daily_price_chg,
                 daily_volume: Qty::from_f64(volume_in_usd),
+                open_interest: None,
+                funding_rate: None,
+                spread_bps: TickerStats::spread_in_bps(
+                    serde_util::value_as_f64(&item["bidPrice"]),
+                    serde_util::value_as_f64(&item["askPrice"]),
+                ),
             };
 
             ticker_prices_map.insert(Ticker::new(symbol, exchange), ticker_stats);
@@ -350,10 +356,25 @@ pub(super) async fn fetch_ticker_stats(
 
             let daily_price_chg = rise_fall_rate * 100.0;
 
+            // Held in contracts, like the volume
+            let open_interest = serde_util::value_as_f64(&item["holdVol"]).map(|hold_vol| {
+                if perps_market == MarketKind::InversePerps {
+                    hold_vol * cs.as_f64()
+                } else {
+                    hold_vol * cs.as_f64() * last_price
+                }
+            });
+
             let ticker_stats = TickerStats {
                 mark_price: Price::from_f64(last_price),
                 daily_price_chg,
                 daily_volume: Qty::from_f64(volume_in_usd),
+                open_interest,
+                funding_rate: serde_util::value_as_f32(&item["fundingRate"]),
+                spread_bps: TickerStats::spread_in_bps(
+                    serde_util::value_as_f64(&item["bid1"]),
+                    serde_util::value_as_f64(&item["ask1"]),
+                ),
             };
 
             ticker_prices_map.insert(ticker, ticker_stats);
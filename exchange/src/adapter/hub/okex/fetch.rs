@@ -182,6 +182,12 @@ pub(super) async fn fetch_ticker_stats(
                     mark_price: Price::from_f64(last_price),
                     daily_price_chg,
                     daily_volume: Qty::from_f64(vol24h),
+                    open_interest: None,
+                    funding_rate: None,
+                    spread_bps: TickerStats::spread_in_bps(
+                        serde_util::value_as_f64(&item["bidPx"]),
+                        serde_util::value_as_f64(&item["askPx"]),
+                    ),
                 },
             );
         }
@@ -196,6 +202,8 @@ pub(super) async fn fetch_ticker_stats(
             .as_array()
             .ok_or_else(|| AdapterError::ParseError("Result list is not an array".to_string()))?;
 
+        let open_interest = fetch_swap_open_interest(hub).await;
+
         for item in list {
             let symbol = match item["instId"].as_str() {
                 Some(s) => s,
@@ -249,6 +257,13 @@ pub(super) async fn fetch_ticker_stats(
                     mark_price: Price::from_f64(last_price),
                     daily_price_chg,
                     daily_volume: Qty::from_f64(vol24h * last_price),
+                    open_interest: open_interest.get(symbol).copied(),
+                    // Only listed one instrument at a time
+                    funding_rate: None,
+                    spread_bps: TickerStats::spread_in_bps(
+                        serde_util::value_as_f64(&item["bidPx"]),
+                        serde_util::value_as_f64(&item["askPx"]),
+                    ),
                 },
             );
         }
@@ -257,6 +272,31 @@ pub(super) async fn fetch_ticker_stats(
     Ok(map)
 }
 
+/// Open interest in USD of every swap, keyed by instrument id. Left empty if the request
+/// fails, the rest of the stats don't depend on it
+async fn fetch_swap_open_interest(hub: &mut HttpHub<OkexLimiter>) -> HashMap<String, f64> {
+    let url = format!("{REST_API_BASE}/public/open-interest?instType=SWAP");
+
+    let parsed_response: Value = match hub.http_json_with_limiter(&url, 1, None, None).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("Failed to fetch swap open interest: {e}");
+            return HashMap::new();
+        }
+    };
+
+    parsed_response["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let symbol = item["instId"].as_str()?;
+            let oi_usd = serde_util::value_as_f64(&item["oiUsd"])?;
+            Some((symbol.to_string(), oi_usd))
+        })
+        .collect()
+}
+
 pub(super) async fn fetch_klines(
     hub: &mut HttpHub<OkexLimiter>,
     ticker_info: TickerInfo,
@@ -0,0 +1,266 @@
+//! Custom data sources, for feeds that aren't one of the built-in venues.
+//!
+//! A source is registered once at startup with [`register`] and its tickers are listed
+//! under [`Venue::Plugin`], using [`Exchange::PluginSpot`] or [`Exchange::PluginLinear`]
+//! as their exchange. From there on they are fetched and streamed through
+//! [`AdapterHandles`](super::AdapterHandles) like any other ticker.
+//!
+//! ```ignore
+//! struct Equities;
+//!
+//! impl DataSource for Equities {
+//!     fn name(&self) -> &str {
+//!         "equities"
+//!     }
+//!
+//!     fn fetch_tickers(&self) -> BoxFuture<'static, Result<Vec<TickerInfo>, AdapterError>> {
+//!         async {
+//!             let ticker = Ticker::new("AAPL", Exchange::PluginSpot);
+//!             Ok(vec![TickerInfo::new(ticker, 0.01, 1.0, None)])
+//!         }
+//!         .boxed()
+//!     }
+//!
+//!     // fetch_klines, trade_stream...
+//! }
+//!
+//! exchange::adapter::source::register(Arc::new(Equities));
+//! ```
+
+pub mod local;
+
+use super::{AdapterError, Event, MarketKind, StreamKind, StreamTicksize, Venue};
+use crate::{Kline, PushFrequency, Ticker, TickerInfo, TickerStats, Timeframe, Trade, UnixMs};
+
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+pub trait DataSource: Send + Sync + 'static {
+    /// Unique name, used in logs and error messages
+    fn name(&self) -> &str;
+
+    /// Every ticker the source can serve. Their exchange must be one of the plugin
+    /// exchanges and symbols must not collide with other registered sources
+    fn fetch_tickers(&self) -> BoxFuture<'static, Result<Vec<TickerInfo>, AdapterError>>;
+
+    /// 24h stats shown in the tickers table, tickers without stats are listed without them
+    fn fetch_ticker_stats(
+        &self,
+    ) -> BoxFuture<'static, Result<HashMap<Ticker, TickerStats>, AdapterError>> {
+        async { Ok(HashMap::new()) }.boxed()
+    }
+
+    /// Historical klines, the latest ones when `range` is `None`
+    fn fetch_klines(
+        &self,
+        ticker_info: TickerInfo,
+        timeframe: Timeframe,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> BoxFuture<'static, Result<Vec<Kline>, AdapterError>>;
+
+    /// Historical trades after `from_time`, oldest first. Used to backfill footprint
+    /// charts, so batches should end on a millisecond boundary
+    fn fetch_trades(
+        &self,
+        ticker_info: TickerInfo,
+        _from_time: UnixMs,
+    ) -> BoxFuture<'static, Result<Vec<Trade>, AdapterError>> {
+        let err = AdapterError::InvalidRequest(format!(
+            "{} doesn't provide historical trades for {}",
+            self.name(),
+            ticker_info.ticker
+        ));
+        async move { Err(err) }.boxed()
+    }
+
+    /// Yields [`Event::TradesReceived`] for the given tickers, plus connection events
+    fn trade_stream(&self, tickers: Vec<TickerInfo>) -> BoxStream<'static, Event>;
+
+    /// Yields [`Event::KlineReceived`] for every `(ticker, timeframe)` pair
+    fn kline_stream(&self, streams: Vec<(TickerInfo, Timeframe)>) -> BoxStream<'static, Event> {
+        let scope = streams
+            .into_iter()
+            .map(|(ticker_info, timeframe)| StreamKind::Kline {
+                ticker_info,
+                timeframe,
+            })
+            .collect();
+        unsupported(self.name(), "kline streams", scope)
+    }
+
+    /// Yields [`Event::DepthReceived`] with the full book after each update.
+    /// Books are aggregated client-side, so levels should be at the ticker's min tick
+    fn depth_stream(
+        &self,
+        ticker_info: TickerInfo,
+        push_freq: PushFrequency,
+    ) -> BoxStream<'static, Event> {
+        let scope = vec![StreamKind::Depth {
+            ticker_info,
+            depth_aggr: StreamTicksize::Client,
+            push_freq,
+        }];
+        unsupported(self.name(), "depth streams", scope)
+    }
+}
+
+/// A stream that disconnects right away, for sources that don't provide a kind of data
+pub fn unsupported(source: &str, what: &str, scope: Vec<StreamKind>) -> BoxStream<'static, Event> {
+    disconnected(scope, format!("{source} doesn't provide {what}"))
+}
+
+fn disconnected(scope: Vec<StreamKind>, reason: String) -> BoxStream<'static, Event> {
+    stream::once(async move { Event::Disconnected(Arc::from(scope), reason) }).boxed()
+}
+
+#[derive(Default)]
+struct Registry {
+    sources: Vec<Arc<dyn DataSource>>,
+    /// Filled on ticker discovery, so streams and fetches can be routed by ticker
+    owners: HashMap<Ticker, Arc<dyn DataSource>>,
+}
+
+static REGISTRY: LazyLock<RwLock<Registry>> = LazyLock::new(RwLock::default);
+
+/// Adds a source to the plugin venue, replacing any previous one with the same name
+pub fn register(source: Arc<dyn DataSource>) {
+    let Ok(mut registry) = REGISTRY.write() else {
+        return;
+    };
+
+    let name = source.name().to_owned();
+    registry.sources.retain(|existing| existing.name() != name);
+    registry.owners.retain(|_, owner| owner.name() != name);
+    registry.sources.push(source);
+
+    log::info!("Registered data source: {name}");
+}
+
+/// Names of the registered sources, in registration order
+pub fn registered() -> Vec<String> {
+    REGISTRY
+        .read()
+        .map(|registry| {
+            registry
+                .sources
+                .iter()
+                .map(|source| source.name().to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn sources() -> Vec<Arc<dyn DataSource>> {
+    REGISTRY
+        .read()
+        .map(|registry| registry.sources.clone())
+        .unwrap_or_default()
+}
+
+pub(super) fn owner(ticker: &Ticker) -> Result<Arc<dyn DataSource>, AdapterError> {
+    REGISTRY
+        .read()
+        .ok()
+        .and_then(|registry| registry.owners.get(ticker).cloned())
+        .ok_or_else(|| {
+            AdapterError::unavailable(Venue::Plugin, format!("No data source serves {ticker}"))
+        })
+}
+
+/// Tickers of every registered source, remembering which source serves each one
+pub(super) async fn fetch_tickers(
+    markets: &[MarketKind],
+) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
+    let mut out = HashMap::new();
+
+    for source in sources() {
+        let tickers = match source.fetch_tickers().await {
+            Ok(tickers) => tickers,
+            Err(err) => {
+                log::error!(
+                    "Data source {} failed to list tickers: {err}",
+                    source.name()
+                );
+                continue;
+            }
+        };
+
+        let Ok(mut registry) = REGISTRY.write() else {
+            break;
+        };
+
+        for ticker_info in tickers {
+            let ticker = ticker_info.ticker;
+
+            if ticker.exchange.venue() != Venue::Plugin {
+                log::warn!(
+                    "Data source {} listed {ticker} outside of the plugin venue, skipping",
+                    source.name()
+                );
+                continue;
+            }
+            if !markets.contains(&ticker.exchange.market_type()) {
+                continue;
+            }
+
+            registry.owners.insert(ticker, Arc::clone(&source));
+            out.insert(ticker, Some(ticker_info));
+        }
+    }
+
+    Ok(out)
+}
+
+pub(super) async fn fetch_ticker_stats() -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
+    let mut out = HashMap::new();
+
+    for source in sources() {
+        match source.fetch_ticker_stats().await {
+            Ok(stats) => out.extend(stats),
+            Err(err) => log::warn!("Data source {} stats: {err}", source.name()),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Splits the requested streams by owning source and merges the results back
+pub(super) fn grouped_stream<T>(
+    items: Vec<T>,
+    ticker_of: impl Fn(&T) -> TickerInfo,
+    connect: impl Fn(&dyn DataSource, Vec<T>) -> BoxStream<'static, Event>,
+    scope_of: impl Fn(&T) -> StreamKind,
+) -> BoxStream<'static, Event> {
+    let mut groups: Vec<(Arc<dyn DataSource>, Vec<T>)> = vec![];
+    let mut orphaned = vec![];
+
+    for item in items {
+        match owner(&ticker_of(&item).ticker) {
+            Ok(source) => match groups
+                .iter_mut()
+                .find(|(existing, _)| Arc::ptr_eq(existing, &source))
+            {
+                Some((_, group)) => group.push(item),
+                None => groups.push((source, vec![item])),
+            },
+            Err(_) => orphaned.push(scope_of(&item)),
+        }
+    }
+
+    let mut streams = groups
+        .into_iter()
+        .map(|(source, group)| connect(source.as_ref(), group))
+        .collect::<Vec<_>>();
+
+    if !orphaned.is_empty() {
+        streams.push(disconnected(
+            orphaned,
+            "No registered data source serves this ticker".to_string(),
+        ));
+    }
+
+    stream::select_all(streams).boxed()
+}
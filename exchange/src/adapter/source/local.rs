@@ -0,0 +1,563 @@
+//! Klines or trades loaded from CSV/JSON/Parquet files in a local directory, one ticker per file.
+//!
+//! The file name (without extension) becomes the symbol. Columns, or object fields for
+//! JSON arrays, are matched by name:
+//!
+//! - klines: `time`, `open`, `high`, `low`, `close`, `volume`, plus optional
+//!   `buy_volume` and `sell_volume`
+//! - trades: `time`, `price`, `qty`, plus an optional `side` (`buy`/`sell`)
+//!
+//! Times are unix milliseconds, or seconds which are detected and converted. Parquet
+//! timestamp columns are taken in whatever unit they declare.
+//! Coarser timeframes are aggregated from the file on request, trade files can be
+//! charted at any timeframe and feed footprint charts through trade fetches.
+
+use super::DataSource;
+use crate::adapter::{AdapterError, Event, Exchange, StreamKind};
+use crate::{Kline, Ticker, TickerInfo, Timeframe, Trade, UnixMs, Volume, unit::Price, unit::Qty};
+
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+pub const NAME: &str = "Local files";
+
+/// Klines returned when no range is requested, like exchange REST defaults
+const LATEST_KLINES: usize = 1000;
+/// Trades returned per fetch, extended to the end of the last millisecond
+const TRADES_PER_BATCH: usize = 50_000;
+/// Rows looked at to guess the tick and lot size
+const PRECISION_SAMPLE: usize = 1000;
+/// Times below this are taken as seconds
+const SECONDS_CUTOFF: f64 = 100_000_000_000.0;
+
+#[derive(Debug, Deserialize)]
+struct Row {
+    #[serde(alias = "timestamp", alias = "ts")]
+    time: f64,
+    #[serde(default)]
+    open: Option<f64>,
+    #[serde(default)]
+    high: Option<f64>,
+    #[serde(default)]
+    low: Option<f64>,
+    #[serde(default)]
+    close: Option<f64>,
+    #[serde(default)]
+    volume: Option<f64>,
+    #[serde(default)]
+    buy_volume: Option<f64>,
+    #[serde(default)]
+    sell_volume: Option<f64>,
+    #[serde(default)]
+    price: Option<f64>,
+    #[serde(default, alias = "size", alias = "amount", alias = "quantity")]
+    qty: Option<f64>,
+    #[serde(default)]
+    side: Option<String>,
+}
+
+impl Row {
+    fn time(&self) -> UnixMs {
+        let ms = if self.time < SECONDS_CUTOFF {
+            self.time * 1000.0
+        } else {
+            self.time
+        };
+        UnixMs::new(ms.max(0.0) as u64)
+    }
+
+    fn is_kline(&self) -> bool {
+        self.open.is_some() && self.high.is_some() && self.low.is_some() && self.close.is_some()
+    }
+
+    fn is_sell(&self) -> bool {
+        self.side
+            .as_deref()
+            .is_some_and(|side| side.trim().to_ascii_lowercase().starts_with('s'))
+    }
+}
+
+enum Series {
+    Klines(Vec<Kline>),
+    Trades(Vec<Trade>),
+}
+
+struct LoadedFile {
+    ticker_info: TickerInfo,
+    series: Series,
+}
+
+/// Serves every supported file found in `dir`, files are (re)read on each ticker listing
+pub struct LocalFiles {
+    dir: PathBuf,
+    loaded: Arc<Mutex<HashMap<Ticker, Arc<LoadedFile>>>>,
+}
+
+impl LocalFiles {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            loaded: Arc::default(),
+        }
+    }
+
+    fn get(&self, ticker: &Ticker) -> Result<Arc<LoadedFile>, AdapterError> {
+        self.loaded
+            .lock()
+            .ok()
+            .and_then(|loaded| loaded.get(ticker).cloned())
+            .ok_or_else(|| AdapterError::InvalidRequest(format!("{ticker} is not loaded")))
+    }
+}
+
+impl DataSource for LocalFiles {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn fetch_tickers(&self) -> BoxFuture<'static, Result<Vec<TickerInfo>, AdapterError>> {
+        let dir = self.dir.clone();
+        let loaded = Arc::clone(&self.loaded);
+
+        async move {
+            let files = tokio::task::spawn_blocking(move || load_dir(&dir))
+                .await
+                .map_err(|err| AdapterError::ParseError(err.to_string()))??;
+
+            let tickers = files.iter().map(|file| file.ticker_info).collect();
+
+            if let Ok(mut loaded) = loaded.lock() {
+                *loaded = files
+                    .into_iter()
+                    .map(|file| (file.ticker_info.ticker, Arc::new(file)))
+                    .collect();
+            }
+
+            Ok(tickers)
+        }
+        .boxed()
+    }
+
+    fn fetch_klines(
+        &self,
+        ticker_info: TickerInfo,
+        timeframe: Timeframe,
+        range: Option<(UnixMs, UnixMs)>,
+    ) -> BoxFuture<'static, Result<Vec<Kline>, AdapterError>> {
+        let file = self.get(&ticker_info.ticker);
+
+        async move {
+            let file = file?;
+
+            let klines = match &file.series {
+                Series::Klines(klines) => resample(klines.iter().copied(), timeframe),
+                Series::Trades(trades) => resample(trades.iter().map(trade_kline), timeframe),
+            };
+
+            Ok(match range {
+                Some((start, end)) => klines
+                    .into_iter()
+                    .filter(|kline| kline.time >= start && kline.time <= end)
+                    .collect(),
+                None => {
+                    let skip = klines.len().saturating_sub(LATEST_KLINES);
+                    klines.into_iter().skip(skip).collect()
+                }
+            })
+        }
+        .boxed()
+    }
+
+    fn fetch_trades(
+        &self,
+        ticker_info: TickerInfo,
+        from_time: UnixMs,
+    ) -> BoxFuture<'static, Result<Vec<Trade>, AdapterError>> {
+        let file = self.get(&ticker_info.ticker);
+
+        async move {
+            let file = file?;
+            let Series::Trades(trades) = &file.series else {
+                return Err(AdapterError::InvalidRequest(format!(
+                    "{} holds klines, not trades",
+                    ticker_info.ticker
+                )));
+            };
+
+            let start = trades.partition_point(|trade| trade.time <= from_time);
+            let mut end = (start + TRADES_PER_BATCH).min(trades.len());
+
+            // Batches end on a millisecond boundary, the next one starts after it
+            if let Some(last) = end.checked_sub(1).and_then(|idx| trades.get(idx)) {
+                end += trades[end..]
+                    .iter()
+                    .take_while(|trade| trade.time == last.time)
+                    .count();
+            }
+
+            Ok(trades[start..end].to_vec())
+        }
+        .boxed()
+    }
+
+    fn trade_stream(&self, tickers: Vec<TickerInfo>) -> BoxStream<'static, Event> {
+        idle(
+            tickers
+                .into_iter()
+                .map(|ticker_info| StreamKind::Trades { ticker_info })
+                .collect(),
+        )
+    }
+
+    fn kline_stream(&self, streams: Vec<(TickerInfo, Timeframe)>) -> BoxStream<'static, Event> {
+        idle(
+            streams
+                .into_iter()
+                .map(|(ticker_info, timeframe)| StreamKind::Kline {
+                    ticker_info,
+                    timeframe,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Files don't update, so their streams connect and then stay quiet
+fn idle(scope: Vec<StreamKind>) -> BoxStream<'static, Event> {
+    stream::once(async move { Event::Connected(Arc::from(scope)) })
+        .chain(stream::pending())
+        .boxed()
+}
+
+fn load_dir(dir: &Path) -> Result<Vec<LoadedFile>, AdapterError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => {
+            return Err(AdapterError::ParseError(format!(
+                "Failed to read {}: {err}",
+                dir.display()
+            )));
+        }
+    };
+
+    let mut files = vec![];
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+            continue;
+        };
+
+        let rows = match extension.to_ascii_lowercase().as_str() {
+            "csv" => read_csv(&path),
+            "json" => read_json(&path),
+            "parquet" => read_parquet(&path),
+            _ => continue,
+        };
+
+        let Some(ticker) = ticker_for(&path) else {
+            log::warn!("No usable symbol in file name: {}", path.display());
+            continue;
+        };
+
+        match rows.and_then(|rows| build(ticker, rows)) {
+            Ok(file) => files.push(file),
+            Err(err) => log::warn!("Skipping {}: {err}", path.display()),
+        }
+    }
+
+    Ok(files)
+}
+
+fn read_csv(path: &Path) -> Result<Vec<Row>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|err| err.to_string())?;
+
+    reader
+        .deserialize()
+        .collect::<Result<Vec<Row>, _>>()
+        .map_err(|err| err.to_string())
+}
+
+fn read_json(path: &Path) -> Result<Vec<Row>, String> {
+    let file = std::fs::File::open(path).map_err(|err| err.to_string())?;
+    serde_json::from_reader(std::io::BufReader::new(file)).map_err(|err| err.to_string())
+}
+
+fn read_parquet(path: &Path) -> Result<Vec<Row>, String> {
+    let file = std::fs::File::open(path).map_err(|err| err.to_string())?;
+    let reader = SerializedFileReader::new(file).map_err(|err| err.to_string())?;
+
+    reader
+        .get_row_iter(None)
+        .map_err(|err| err.to_string())?
+        .map(|row| {
+            let row = row.map_err(|err| err.to_string())?;
+            // Goes through JSON so columns match by the same names and aliases
+            let fields = row
+                .get_column_iter()
+                .filter_map(|(name, field)| Some((name.clone(), parquet_value(field)?)))
+                .collect::<serde_json::Map<_, _>>();
+
+            serde_json::from_value(serde_json::Value::Object(fields)).map_err(|err| err.to_string())
+        })
+        .collect()
+}
+
+/// Numbers and strings of a Parquet cell, timestamps in milliseconds. Other types are
+/// left out and read as missing
+fn parquet_value(field: &Field) -> Option<serde_json::Value> {
+    let number = match *field {
+        Field::Byte(n) => f64::from(n),
+        Field::Short(n) => f64::from(n),
+        Field::Int(n) => f64::from(n),
+        Field::Long(n) | Field::TimestampMillis(n) => n as f64,
+        Field::UByte(n) => f64::from(n),
+        Field::UShort(n) => f64::from(n),
+        Field::UInt(n) => f64::from(n),
+        Field::ULong(n) => n as f64,
+        Field::TimestampMicros(n) => n as f64 / 1000.0,
+        Field::Float16(n) => f64::from(n),
+        Field::Float(n) => f64::from(n),
+        Field::Double(n) => n,
+        Field::Decimal(ref decimal) => {
+            let bytes = decimal.data();
+            if bytes.is_empty() || bytes.len() > 16 {
+                return None;
+            }
+            // Big-endian two's complement, sign extended to 128 bits
+            let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0 };
+            let mut wide = [fill; 16];
+            wide[16 - bytes.len()..].copy_from_slice(bytes);
+
+            i128::from_be_bytes(wide) as f64 / 10f64.powi(decimal.scale())
+        }
+        Field::Str(ref s) => return Some(serde_json::Value::String(s.clone())),
+        _ => return None,
+    };
+
+    serde_json::Number::from_f64(number).map(serde_json::Value::Number)
+}
+
+fn ticker_for(path: &Path) -> Option<Ticker> {
+    let symbol = path
+        .file_stem()?
+        .to_str()?
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        .take(28)
+        .collect::<String>()
+        .to_ascii_uppercase();
+
+    (!symbol.is_empty()).then(|| Ticker::new(&symbol, Exchange::PluginSpot))
+}
+
+fn build(ticker: Ticker, mut rows: Vec<Row>) -> Result<LoadedFile, String> {
+    let Some(first) = rows.first() else {
+        return Err("no rows".to_string());
+    };
+    let is_kline = first.is_kline();
+
+    rows.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+    let sample = &rows[..rows.len().min(PRECISION_SAMPLE)];
+    let (prices, sizes): (Vec<f64>, Vec<f64>) = if is_kline {
+        (
+            sample
+                .iter()
+                .flat_map(|row| [row.open, row.high, row.low, row.close])
+                .flatten()
+                .collect(),
+            sample.iter().filter_map(|row| row.volume).collect(),
+        )
+    } else {
+        sample
+            .iter()
+            .filter_map(|row| Some((row.price?, row.qty?)))
+            .unzip()
+    };
+
+    let ticker_info = TickerInfo::new(
+        ticker,
+        10f32.powi(-max_decimals(&prices)),
+        10f32.powi(-max_decimals(&sizes)),
+        None,
+    );
+    let min_tick = ticker_info.min_ticksize;
+
+    let series = if is_kline {
+        Series::Klines(
+            rows.iter()
+                .filter_map(|row| {
+                    let volume = match (row.buy_volume, row.sell_volume) {
+                        (Some(buy), Some(sell)) => {
+                            Volume::BuySell(Qty::from_f64(buy), Qty::from_f64(sell))
+                        }
+                        _ => Volume::TotalOnly(Qty::from_f64(row.volume.unwrap_or_default())),
+                    };
+
+                    Some(Kline::new(
+                        row.time(),
+                        row.open?,
+                        row.high?,
+                        row.low?,
+                        row.close?,
+                        volume,
+                        min_tick,
+                    ))
+                })
+                .collect(),
+        )
+    } else {
+        Series::Trades(
+            rows.iter()
+                .filter_map(|row| {
+                    Some(Trade {
+                        time: row.time(),
+                        is_sell: row.is_sell(),
+                        price: Price::from_f64(row.price?).round_to_min_tick(min_tick),
+                        qty: Qty::from_f64(row.qty?),
+                    })
+                })
+                .collect(),
+        )
+    };
+
+    Ok(LoadedFile {
+        ticker_info,
+        series,
+    })
+}
+
+/// Decimal places needed to write every value exactly, capped at 8
+fn max_decimals(values: &[f64]) -> i32 {
+    values
+        .iter()
+        .map(|value| {
+            (0..8)
+                .find(|&places| {
+                    let scaled = value * 10f64.powi(places);
+                    (scaled - scaled.round()).abs() < 1e-6 * scaled.abs().max(1.0)
+                })
+                .unwrap_or(8)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn trade_kline(trade: &Trade) -> Kline {
+    let volume = if trade.is_sell {
+        Volume::BuySell(Qty::ZERO, trade.qty)
+    } else {
+        Volume::BuySell(trade.qty, Qty::ZERO)
+    };
+
+    Kline {
+        time: trade.time,
+        open: trade.price,
+        high: trade.price,
+        low: trade.price,
+        close: trade.price,
+        volume,
+    }
+}
+
+/// Merges time-sorted klines into `timeframe` buckets
+fn resample(klines: impl Iterator<Item = Kline>, timeframe: Timeframe) -> Vec<Kline> {
+    let mut out: Vec<Kline> = vec![];
+
+    for kline in klines {
+        let time = kline.time.floor_to(timeframe);
+
+        match out.last_mut() {
+            Some(last) if last.time == time => {
+                last.high = last.high.max(kline.high);
+                last.low = last.low.min(kline.low);
+                last.close = kline.close;
+                last.volume = match (last.volume, kline.volume) {
+                    (Volume::BuySell(buy, sell), Volume::BuySell(more_buy, more_sell)) => {
+                        Volume::BuySell(buy + more_buy, sell + more_sell)
+                    }
+                    (total, more) => Volume::TotalOnly(total.total() + more.total()),
+                };
+            }
+            _ => out.push(Kline { time, ..kline }),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    #[test]
+    fn parquet_trades_are_read_by_column_name() {
+        let path = std::env::temp_dir().join(format!("flowsurface-{}.parquet", std::process::id()));
+
+        let schema = parse_message_type(
+            "message trades {
+                required int64 ts (TIMESTAMP(MICROS, true));
+                required double price;
+                required double size;
+                required binary side (UTF8);
+            }",
+        )
+        .unwrap();
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer = SerializedFileWriter::new(file, schema.into(), Arc::default()).unwrap();
+        let mut group = writer.next_row_group().unwrap();
+
+        let mut column = group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[1_700_000_000_000_000, 1_700_000_000_250_000], None, None)
+            .unwrap();
+        column.close().unwrap();
+        for values in [[100.5, 101.0], [0.25, 2.0]] {
+            let mut column = group.next_column().unwrap().unwrap();
+            column
+                .typed::<DoubleType>()
+                .write_batch(&values, None, None)
+                .unwrap();
+            column.close().unwrap();
+        }
+        let mut column = group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(
+                &[ByteArray::from("buy"), ByteArray::from("sell")],
+                None,
+                None,
+            )
+            .unwrap();
+        column.close().unwrap();
+
+        group.close().unwrap();
+        writer.close().unwrap();
+
+        let rows = read_parquet(&path);
+        std::fs::remove_file(&path).unwrap();
+        let rows = rows.unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].time(), UnixMs::new(1_700_000_000_000));
+        assert_eq!(rows[1].time(), UnixMs::new(1_700_000_000_250));
+        assert_eq!((rows[0].price, rows[0].qty), (Some(100.5), Some(0.25)));
+        assert!(!rows[0].is_sell() && rows[1].is_sell());
+        assert!(!rows[0].is_kline());
+    }
+}
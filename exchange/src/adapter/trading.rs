@@ -0,0 +1,357 @@
+//! Limit order entry through the REST APIs of the venues that support it.
+//!
+//! Requests are signed with the user's API key, which never leaves this module other than
+//! as the venue's auth header. Nothing here is rate limited by the fetch workers, order
+//! entry is user driven and far below the venue limits.
+
+use super::{AdapterError, Exchange, Venue};
+use crate::unit::{MinQtySize, Price, Qty};
+use crate::{Ticker, TickerInfo, UnixMs};
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a signed request stays valid after its timestamp
+pub(crate) const RECV_WINDOW_MS: u64 = 5_000;
+
+/// Venues with order entry
+pub const SUPPORTED_VENUES: [Venue; 2] = [Venue::Binance, Venue::Bybit];
+
+pub fn is_supported(exchange: Exchange) -> bool {
+    SUPPORTED_VENUES.contains(&exchange.venue())
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Credentials {
+    pub api_key: String,
+    pub secret: String,
+}
+
+impl Credentials {
+    pub fn is_complete(&self) -> bool {
+        !self.api_key.trim().is_empty() && !self.secret.trim().is_empty()
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &"<redacted>")
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl std::fmt::Display for OrderSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderSide::Buy => write!(f, "Buy"),
+            OrderSide::Sell => write!(f, "Sell"),
+        }
+    }
+}
+
+/// A good-till-cancelled limit order to place.
+///
+/// `qty` is in the venue's order unit: base asset for spot and linear perpetuals,
+/// contracts for inverse ones
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitOrder {
+    pub ticker_info: TickerInfo,
+    pub side: OrderSide,
+    pub price: Price,
+    pub qty: f64,
+}
+
+/// An open order resting on the book
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkingOrder {
+    pub id: String,
+    pub ticker: Ticker,
+    pub side: OrderSide,
+    pub price: Price,
+    pub qty: Qty,
+    pub filled: Qty,
+}
+
+impl WorkingOrder {
+    pub fn remaining(&self) -> Qty {
+        self.qty - self.filled
+    }
+}
+
+/// An open derivatives position, spot balances aren't positions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub ticker: Ticker,
+    /// `Buy` for longs, `Sell` for shorts
+    pub side: OrderSide,
+    pub qty: Qty,
+    pub entry_price: Price,
+    /// `None` when the venue reports no liquidation price, e.g. for fully collateralized ones
+    pub liquidation_price: Option<Price>,
+}
+
+/// One of the user's own executions
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserTrade {
+    /// The venue's trade id, unique per ticker
+    pub id: String,
+    pub ticker: Ticker,
+    pub side: OrderSide,
+    pub price: Price,
+    pub qty: Qty,
+    pub time: UnixMs,
+}
+
+/// One asset of one of the venue's accounts, amounts in that asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct Balance {
+    /// Which of the venue's accounts holds it, e.g. `Spot` or `Unified`
+    pub account: &'static str,
+    pub asset: String,
+    pub wallet: f64,
+    pub available: f64,
+    pub unrealized_pnl: f64,
+    /// Closed since the start of the UTC day, `None` for accounts without positions
+    pub realized_pnl: Option<f64>,
+    /// Initial margin held by positions and orders
+    pub margin_used: f64,
+}
+
+impl Balance {
+    pub fn equity(&self) -> f64 {
+        self.wallet + self.unrealized_pnl
+    }
+
+    /// Share of the equity held as margin, in percent
+    pub fn margin_usage(&self) -> Option<f64> {
+        let equity = self.equity();
+        (self.margin_used > 0.0 && equity > 0.0).then(|| self.margin_used / equity * 100.0)
+    }
+}
+
+pub async fn place_limit(
+    credentials: &Credentials,
+    order: LimitOrder,
+    proxy_cfg: Option<&super::Proxy>,
+) -> Result<WorkingOrder, AdapterError> {
+    let client = client(proxy_cfg)?;
+
+    match order.ticker_info.exchange().venue() {
+        Venue::Binance => {
+            super::hub::binance::trade::place_limit(&client, credentials, order).await
+        }
+        Venue::Bybit => super::hub::bybit::trade::place_limit(&client, credentials, order).await,
+        venue => Err(unsupported(venue)),
+    }
+}
+
+pub async fn cancel(
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    order_id: &str,
+    proxy_cfg: Option<&super::Proxy>,
+) -> Result<(), AdapterError> {
+    let client = client(proxy_cfg)?;
+
+    match ticker_info.exchange().venue() {
+        Venue::Binance => {
+            super::hub::binance::trade::cancel(&client, credentials, ticker_info, order_id).await
+        }
+        Venue::Bybit => {
+            super::hub::bybit::trade::cancel(&client, credentials, ticker_info, order_id).await
+        }
+        venue => Err(unsupported(venue)),
+    }
+}
+
+pub async fn open_orders(
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    proxy_cfg: Option<&super::Proxy>,
+) -> Result<Vec<WorkingOrder>, AdapterError> {
+    let client = client(proxy_cfg)?;
+
+    match ticker_info.exchange().venue() {
+        Venue::Binance => {
+            super::hub::binance::trade::open_orders(&client, credentials, ticker_info).await
+        }
+        Venue::Bybit => {
+            super::hub::bybit::trade::open_orders(&client, credentials, ticker_info).await
+        }
+        venue => Err(unsupported(venue)),
+    }
+}
+
+/// Open positions on `ticker_info`, always empty for spot markets
+pub async fn positions(
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    proxy_cfg: Option<&super::Proxy>,
+) -> Result<Vec<Position>, AdapterError> {
+    if !ticker_info.is_perps() {
+        return Ok(vec![]);
+    }
+    let client = client(proxy_cfg)?;
+
+    match ticker_info.exchange().venue() {
+        Venue::Binance => {
+            super::hub::binance::trade::positions(&client, credentials, ticker_info).await
+        }
+        Venue::Bybit => {
+            super::hub::bybit::trade::positions(&client, credentials, ticker_info).await
+        }
+        venue => Err(unsupported(venue)),
+    }
+}
+
+/// The user's executions on `ticker_info` since `since`, oldest first
+pub async fn user_trades(
+    credentials: &Credentials,
+    ticker_info: TickerInfo,
+    since: UnixMs,
+    proxy_cfg: Option<&super::Proxy>,
+) -> Result<Vec<UserTrade>, AdapterError> {
+    let client = client(proxy_cfg)?;
+
+    let mut trades = match ticker_info.exchange().venue() {
+        Venue::Binance => {
+            super::hub::binance::trade::user_trades(&client, credentials, ticker_info, since)
+                .await?
+        }
+        Venue::Bybit => {
+            super::hub::bybit::trade::user_trades(&client, credentials, ticker_info, since).await?
+        }
+        venue => return Err(unsupported(venue)),
+    };
+
+    trades.sort_by_key(|trade| trade.time);
+    Ok(trades)
+}
+
+/// Non-empty balances across the accounts the key can read, accounts it has no permission
+/// for are skipped
+pub async fn balances(
+    credentials: &Credentials,
+    venue: Venue,
+    proxy_cfg: Option<&super::Proxy>,
+) -> Result<Vec<Balance>, AdapterError> {
+    let client = client(proxy_cfg)?;
+
+    match venue {
+        Venue::Binance => super::hub::binance::trade::balances(&client, credentials).await,
+        Venue::Bybit => super::hub::bybit::trade::balances(&client, credentials).await,
+        venue => Err(unsupported(venue)),
+    }
+}
+
+fn unsupported(venue: Venue) -> AdapterError {
+    AdapterError::unavailable(venue, format!("Order entry isn't supported on {venue}"))
+}
+
+fn client(proxy_cfg: Option<&super::Proxy>) -> Result<Client, AdapterError> {
+    let builder = Client::builder()
+        .connect_timeout(HTTP_TIMEOUT)
+        .timeout(HTTP_TIMEOUT);
+
+    super::proxy::try_apply_proxy(builder, proxy_cfg)
+        .build()
+        .map_err(|error| {
+            AdapterError::InvalidRequest(format!("Failed to build HTTP client: {error}"))
+        })
+}
+
+/// Hex encoded HMAC-SHA256 of `payload`, what both venues expect as the signature
+pub(crate) fn sign(secret: &str, payload: &str) -> String {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.trim().as_bytes());
+    let tag = ring::hmac::sign(&key, payload.as_bytes());
+
+    tag.as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+pub(crate) fn timestamp_ms() -> u64 {
+    crate::UnixMs::now().as_u64()
+}
+
+/// Start of the current UTC day, where daily realized PnL is summed from
+pub(crate) fn utc_day_start_ms() -> u64 {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    timestamp_ms() / DAY_MS * DAY_MS
+}
+
+/// `qty` floored to the ticker's quantity step, formatted with its decimals
+pub(crate) fn format_qty(qty: f64, min_qty: MinQtySize) -> String {
+    let step = min_qty.as_f64();
+    let decimals = usize::try_from(-min_qty.power).unwrap_or(0);
+
+    format!("{:.decimals$}", (qty / step + 1e-9).floor() * step)
+}
+
+/// Rejects orders that round down to nothing before they reach the venue
+pub(crate) fn validate(order: &LimitOrder) -> Result<(), AdapterError> {
+    let step = order.ticker_info.min_qty.as_f64();
+
+    if !order.qty.is_finite() || order.qty < step {
+        return Err(AdapterError::InvalidRequest(format!(
+            "Order size must be at least {}",
+            format_qty(step, order.ticker_info.min_qty)
+        )));
+    }
+    if order.price.to_f64() <= 0.0 {
+        return Err(AdapterError::InvalidRequest(
+            "Order price must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reads a response, turning rejections into errors carrying the venue's reason
+pub(crate) async fn read_response(
+    response: reqwest::Response,
+    label: &str,
+    reason: impl Fn(&serde_json::Value) -> Option<String>,
+) -> Result<serde_json::Value, AdapterError> {
+    let status = response.status();
+    let body = response.text().await.map_err(|error| {
+        AdapterError::InvalidRequest(format!("{label}: failed reading response: {error}"))
+    })?;
+
+    let value = serde_json::from_str::<serde_json::Value>(&body).map_err(|error| {
+        let preview = body.trim().chars().take(200).collect::<String>();
+        log::warn!("{label}: HTTP {status} | preview={preview:?}");
+        AdapterError::ParseError(format!("{label}: {error}"))
+    })?;
+
+    if let Some(reason) = reason(&value) {
+        log::warn!("{label} rejected: {reason}");
+        return Err(AdapterError::InvalidRequest(reason));
+    }
+    if !status.is_success() {
+        return Err(AdapterError::http_status_failed(
+            status,
+            format!("{label}: HTTP {status}"),
+        ));
+    }
+
+    Ok(value)
+}
+
+/// Decimal strings or numbers, as venues use either for prices and sizes
+pub(crate) fn decimal(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::String(text) => text.parse().ok(),
+        value => value.as_f64(),
+    }
+}
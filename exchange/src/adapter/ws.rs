@@ -1,3 +1,4 @@
+use crate::adapter::connect::ReconnectPolicy;
 use crate::adapter::{AdapterError, Event, StreamKind};
 use crate::proxy::{Proxy, ProxyStream};
 use crate::unit::qty::QtyNormalization;
@@ -135,6 +136,8 @@ impl<T> Drop for ChannelStream<T> {
 pub(super) struct WsSession {
     ping_payload: PingPayload,
     streams: Arc<[StreamKind]>,
+    /// Runs without any streams in scope, for venue wide feeds that aren't tied to a ticker
+    unscoped: bool,
 }
 
 /// How often [`on_tick`](WsAdapter::on_tick) fires. Also serves as the time-bucket
@@ -144,8 +147,7 @@ const ADAPTER_TICK_INTERVAL: Duration = Duration::from_micros(33_333);
 
 pub(super) trait WsAdapter {
     /// Connects to the WebSocket and returns a transport for it.
-    /// This will be retried indefinitely until it succeeds, with an exponential backoff
-    /// between attempts (base ~500ms, doubling, capped at 30s, with jitter).
+    /// This will be retried indefinitely until it succeeds, paced by a [`ReconnectPolicy`].
     fn connect(&mut self) -> impl std::future::Future<Output = Result<WsTransport, String>> + Send;
 
     /// Tick interval controlling how often [`on_tick`](WsAdapter::on_tick) is called
@@ -195,6 +197,7 @@ impl WsSession {
         Self {
             ping_payload: PingPayload::Text(ping_payload),
             streams,
+            unscoped: false,
         }
     }
 
@@ -205,17 +208,25 @@ impl WsSession {
         Self {
             ping_payload: PingPayload::OpCode(ping_payload),
             streams,
+            unscoped: false,
         }
     }
 
+    /// Lets the session run with an empty stream scope, its connection events carry none
+    pub(super) fn unscoped(mut self) -> Self {
+        self.unscoped = true;
+        self
+    }
+
     pub(super) fn run<A: WsAdapter + Send + 'static>(self, mut adapter: A) -> ChannelStream<Event> {
         let (mut event_tx, event_rx) = channel(512);
 
         let ping_payload = self.ping_payload;
         let streams = Arc::clone(&self.streams);
+        let unscoped = self.unscoped;
 
         let task = tokio::spawn(async move {
-            if streams.is_empty() {
+            if streams.is_empty() && !unscoped {
                 let _ = event_tx.send(Event::Disconnected(
                     streams,
                     "Empty stream payload".to_string(),
@@ -223,15 +234,18 @@ impl WsSession {
                 return;
             }
 
-            let mut backoff = ReconnectBackoff::new();
+            let mut backoff = ReconnectPolicy::new();
+            let mut last_data_at: Option<UnixMs> = None;
 
             loop {
                 let transport = match adapter.connect().await {
                     Ok(t) => t,
                     Err(reason) => {
                         let _ = event_tx.send(Event::Disconnected(Arc::clone(&streams), reason));
-                        tokio::time::sleep(backoff.delay()).await;
-                        backoff.record_failure();
+
+                        let status = backoff.next_attempt();
+                        let _ = event_tx.send(Event::Reconnecting(Arc::clone(&streams), status));
+                        tokio::time::sleep(status.delay).await;
                         continue;
                     }
                 };
@@ -243,6 +257,9 @@ impl WsSession {
                     let _ = event_tx.send(event);
                 }
                 let _ = event_tx.send(Event::Connected(Arc::clone(&streams)));
+                if let Some(gap_start) = last_data_at {
+                    let _ = event_tx.send(Event::Resumed(Arc::clone(&streams), gap_start));
+                }
 
                 let tick_interval = adapter.tick_interval();
                 let tick_sleep = tokio::time::sleep(tick_interval);
@@ -262,6 +279,7 @@ impl WsSession {
                                             }
                                             if had_events {
                                                 backoff.record_success();
+                                                last_data_at = Some(UnixMs::now());
                                             }
                                         }
                                         Err(reason) => break Some(reason),
@@ -303,8 +321,9 @@ impl WsSession {
                     let _ = event_tx.send(Event::Disconnected(Arc::clone(&streams), reason));
                 }
 
-                tokio::time::sleep(backoff.delay()).await;
-                backoff.record_failure();
+                let status = backoff.next_attempt();
+                let _ = event_tx.send(Event::Reconnecting(Arc::clone(&streams), status));
+                tokio::time::sleep(status.delay).await;
             }
         });
 
@@ -597,45 +616,6 @@ impl Default for WsHeartbeat {
     }
 }
 
-/// Exponential backoff for WebSocket reconnection attempts.
-///
-/// Delay doubles on each failure, resets to the initial 500ms on success.
-/// Capped at 30s with ±25% multiplicative jitter to spread reconnections
-/// across streams when multiple disconnect at once.
-struct ReconnectBackoff {
-    current: Duration,
-}
-
-impl ReconnectBackoff {
-    const INITIAL: Duration = Duration::from_millis(500);
-    const MAX: Duration = Duration::from_secs(30);
-    const JITTER: f32 = 0.25;
-
-    fn new() -> Self {
-        Self {
-            current: Self::INITIAL,
-        }
-    }
-
-    /// Returns the delay before the next reconnect attempt, with ±jitter applied.
-    fn delay(&self) -> Duration {
-        let factor = 1.0 + (rand::random::<f32>() * 2.0 - 1.0) * Self::JITTER;
-        let secs = self.current.as_secs_f32() * factor;
-        Duration::from_secs_f32(secs.max(0.0)).min(Self::MAX)
-    }
-
-    /// Doubles the delay (capped) after a failed attempt.
-    fn record_failure(&mut self) {
-        self.current = (self.current.mul_f32(2.0)).min(Self::MAX);
-    }
-
-    /// Resets the delay to the initial value after genuine success
-    /// (real market-data events were produced by the connection).
-    fn record_success(&mut self) {
-        self.current = Self::INITIAL;
-    }
-}
-
 pub(super) struct TradeBuffer {
     buffer_map: FxHashMap<Ticker, Vec<Trade>>,
     ticker_info_map: FxHashMap<Ticker, (TickerInfo, QtyNormalization)>,
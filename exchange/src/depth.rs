@@ -1,13 +1,21 @@
 use crate::{
-    MinTicksize, Price, UnixMs, serde_util,
-    unit::qty::{Qty, QtyNormalization},
+    MinTicksize, Price, TickerInfo, UnixMs,
+    adapter::CompositeSources,
+    serde_util,
+    unit::{
+        PriceStep,
+        qty::{Qty, QtyNormalization},
+    },
 };
 
 use serde::Deserializer;
 use serde::de::Error as SerdeError;
 use serde_json::Value;
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    sync::Arc,
+};
 
 #[derive(Clone, Copy)]
 pub struct DeOrder {
@@ -181,3 +189,155 @@ impl LocalDepthCache {
         }
     }
 }
+
+/// Latest orderbooks of every [`CompositeSources`] venue, merged into a single book.
+///
+/// Venues list the same underlying with different tick sizes, so each level is
+/// re-bucketed onto the coarsest tick among the sources before quantities are summed,
+/// flooring bids and ceiling asks the same way the charts group their levels.
+///
+/// An update only swaps the contribution of its own venue in the merged book.
+pub struct CompositeDepth {
+    sources: CompositeSources,
+    step: PriceStep,
+    /// Latest book of each source, already re-bucketed onto `step`
+    books: Vec<Depth>,
+    merged: Arc<Depth>,
+}
+
+impl CompositeDepth {
+    pub fn new(sources: CompositeSources) -> Self {
+        let step = sources
+            .iter()
+            .map(|ti| PriceStep::from(ti.min_ticksize))
+            .max_by_key(|step| step.units)
+            .unwrap_or_else(|| PriceStep::from(sources.base().min_ticksize));
+
+        Self {
+            sources,
+            step,
+            books: vec![Depth::default(); sources.count()],
+            merged: Arc::default(),
+        }
+    }
+
+    pub fn sources(&self) -> &CompositeSources {
+        &self.sources
+    }
+
+    /// Replaces the book of `ticker_info` and returns the updated composite.
+    ///
+    /// The merged book is edited in place, so it's only copied while a previously
+    /// returned one is still held on to.
+    pub fn insert(&mut self, ticker_info: TickerInfo, depth: &Depth) -> Arc<Depth> {
+        if let Some(idx) = self.sources.iter().position(|ti| ti == ticker_info) {
+            let book = Depth {
+                bids: bucketed(&depth.bids, true, self.step),
+                asks: bucketed(&depth.asks, false, self.step),
+            };
+            let prev = std::mem::replace(&mut self.books[idx], book);
+            let merged = Arc::make_mut(&mut self.merged);
+
+            swap_levels(&mut merged.bids, &prev.bids, &self.books[idx].bids);
+            swap_levels(&mut merged.asks, &prev.asks, &self.books[idx].asks);
+        }
+
+        Arc::clone(&self.merged)
+    }
+}
+
+fn bucketed(side: &BTreeMap<Price, Qty>, is_bid: bool, step: PriceStep) -> BTreeMap<Price, Qty> {
+    let mut levels = BTreeMap::new();
+    for (price, qty) in side {
+        *levels
+            .entry(price.round_to_side_step(is_bid, step))
+            .or_default() += *qty;
+    }
+    levels
+}
+
+/// Takes a source's previous levels out of one side of the merged book and adds its new ones
+fn swap_levels(
+    merged: &mut BTreeMap<Price, Qty>,
+    prev: &BTreeMap<Price, Qty>,
+    next: &BTreeMap<Price, Qty>,
+) {
+    for (price, qty) in prev {
+        if let Entry::Occupied(mut level) = merged.entry(*price) {
+            *level.get_mut() -= *qty;
+            if *level.get() <= Qty::ZERO {
+                level.remove();
+            }
+        }
+    }
+    for (price, qty) in next {
+        *merged.entry(*price).or_default() += *qty;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Exchange, Ticker};
+
+    fn ticker(exchange: Exchange, min_ticksize: f32) -> TickerInfo {
+        TickerInfo::new(Ticker::new("BTCUSDT", exchange), min_ticksize, 0.001, None)
+    }
+
+    fn book(bids: &[(f32, f64)], asks: &[(f32, f64)]) -> Depth {
+        let side = |levels: &[(f32, f64)]| {
+            levels
+                .iter()
+                .map(|&(price, qty)| (Price::from_f32(price), Qty::from_f64(qty)))
+                .collect()
+        };
+        Depth {
+            bids: side(bids),
+            asks: side(asks),
+        }
+    }
+
+    fn composite() -> (CompositeDepth, TickerInfo, TickerInfo) {
+        let fine = ticker(Exchange::BinanceLinear, 0.1);
+        let coarse = ticker(Exchange::BybitLinear, 0.5);
+        let sources = CompositeSources::from_slice(&[fine, coarse]).unwrap();
+
+        (CompositeDepth::new(sources), fine, coarse)
+    }
+
+    #[test]
+    fn levels_are_bucketed_onto_the_coarsest_tick() {
+        let (mut depth, fine, coarse) = composite();
+
+        depth.insert(fine, &book(&[(100.3, 1.0), (100.1, 2.0)], &[(100.6, 1.0)]));
+        let merged = depth.insert(coarse, &book(&[(100.0, 4.0)], &[(101.0, 3.0)]));
+
+        let expected = book(&[(100.0, 7.0)], &[(101.0, 4.0)]);
+        assert_eq!(merged.bids, expected.bids);
+        assert_eq!(merged.asks, expected.asks);
+    }
+
+    #[test]
+    fn an_update_replaces_only_its_venue() {
+        let (mut depth, fine, coarse) = composite();
+
+        depth.insert(fine, &book(&[(100.0, 1.0)], &[(100.5, 1.0)]));
+        depth.insert(coarse, &book(&[(100.0, 2.0)], &[(101.0, 2.0)]));
+        let merged = depth.insert(fine, &book(&[(99.0, 5.0)], &[]));
+
+        let expected = book(&[(100.0, 2.0), (99.0, 5.0)], &[(101.0, 2.0)]);
+        assert_eq!(merged.bids, expected.bids);
+        assert_eq!(merged.asks, expected.asks);
+    }
+
+    #[test]
+    fn unknown_tickers_leave_the_book_alone() {
+        let (mut depth, fine, _) = composite();
+
+        depth.insert(fine, &book(&[(100.0, 1.0)], &[]));
+        let stranger = ticker(Exchange::OkexLinear, 0.1);
+        let merged = depth.insert(stranger, &book(&[(100.0, 9.0)], &[]));
+
+        assert_eq!(merged.bids, book(&[(100.0, 1.0)], &[]).bids);
+    }
+}
@@ -1,11 +1,16 @@
+#![doc = include_str!("../README.md")]
+
 pub mod adapter;
 pub mod depth;
 mod error;
 mod serde_util;
 pub mod unit;
 
-pub use adapter::{Event, proxy};
-use adapter::{Exchange, MarketKind};
+pub use adapter::{
+    AdapterError, AdapterHandles, Event, Exchange, MarketKind, StreamConfig, StreamKind, Venue,
+    connect, proxy,
+    source::{self, DataSource},
+};
 
 use unit::price::de_price_from_number;
 use unit::price::{Price, PriceStep};
@@ -40,6 +45,14 @@ impl std::fmt::Display for PushFrequency {
 
 impl std::fmt::Display for Timeframe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Timeframe::Custom(minutes) = *self {
+            return match minutes {
+                m if m % 1440 == 0 => write!(f, "{}d", m / 1440),
+                m if m % 60 == 0 => write!(f, "{}h", m / 60),
+                m => write!(f, "{m}m"),
+            };
+        }
+
         write!(
             f,
             "{}",
@@ -49,6 +62,8 @@ impl std::fmt::Display for Timeframe {
                 Timeframe::MS300 => "300ms",
                 Timeframe::MS500 => "500ms",
                 Timeframe::MS1000 => "1s",
+                Timeframe::S5 => "5s",
+                Timeframe::S15 => "15s",
                 Timeframe::M1 => "1m",
                 Timeframe::M3 => "3m",
                 Timeframe::M5 => "5m",
@@ -59,6 +74,7 @@ impl std::fmt::Display for Timeframe {
                 Timeframe::H4 => "4h",
                 Timeframe::H12 => "12h",
                 Timeframe::D1 => "1d",
+                Timeframe::Custom(_) => unreachable!(),
             }
         )
     }
@@ -71,6 +87,8 @@ pub enum Timeframe {
     MS300,
     MS500,
     MS1000,
+    S5,
+    S15,
     M1,
     M3,
     M5,
@@ -81,6 +99,8 @@ pub enum Timeframe {
     H4,
     H12,
     D1,
+    /// Any other whole number of minutes, resampled from a lower timeframe the venue serves
+    Custom(u16),
 }
 
 impl Timeframe {
@@ -97,14 +117,34 @@ impl Timeframe {
         Timeframe::D1,
     ];
 
-    pub const HEATMAP: [Timeframe; 5] = [
+    pub const HEATMAP: [Timeframe; 7] = [
         Timeframe::MS100,
         Timeframe::MS200,
         Timeframe::MS300,
         Timeframe::MS500,
         Timeframe::MS1000,
+        Timeframe::S5,
+        Timeframe::S15,
     ];
 
+    /// Sub-minute kline timeframes, built from trades where the venue has no klines for them
+    pub const SECONDS: [Timeframe; 3] = [Timeframe::MS1000, Timeframe::S5, Timeframe::S15];
+
+    pub const CUSTOM_MIN_MINUTES: u16 = 2;
+    pub const CUSTOM_MAX_MINUTES: u16 = 10_080;
+
+    /// The standard timeframe of that length if there's one, a custom one otherwise
+    pub fn from_minutes(minutes: u16) -> Self {
+        Timeframe::KLINE
+            .into_iter()
+            .find(|tf| tf.to_minutes() == minutes)
+            .unwrap_or(Timeframe::Custom(minutes))
+    }
+
+    pub fn is_custom(self) -> bool {
+        matches!(self, Timeframe::Custom(_))
+    }
+
     /// # Panics
     ///
     /// Will panic if the `Timeframe` is not one of the defined variants
@@ -120,6 +160,7 @@ impl Timeframe {
             Timeframe::H4 => 240,
             Timeframe::H12 => 720,
             Timeframe::D1 => 1440,
+            Timeframe::Custom(minutes) => minutes,
             _ => panic!("Invalid timeframe: {:?}", self),
         }
     }
@@ -131,6 +172,8 @@ impl Timeframe {
             Timeframe::MS300 => 300,
             Timeframe::MS500 => 500,
             Timeframe::MS1000 => 1_000,
+            Timeframe::S5 => 5_000,
+            Timeframe::S15 => 15_000,
             _ => {
                 let minutes = self.to_minutes();
                 u64::from(minutes) * 60_000
@@ -161,6 +204,8 @@ impl TryFrom<u64> for Timeframe {
             300 => Ok(Timeframe::MS300),
             500 => Ok(Timeframe::MS500),
             1_000 => Ok(Timeframe::MS1000),
+            5_000 => Ok(Timeframe::S5),
+            15_000 => Ok(Timeframe::S15),
             60_000 => Ok(Timeframe::M1),
             180_000 => Ok(Timeframe::M3),
             300_000 => Ok(Timeframe::M5),
@@ -676,6 +721,28 @@ pub struct TickerStats {
     /// 24h volume in USD
     #[serde(deserialize_with = "de_qty_from_number")]
     pub daily_volume: Qty,
+    /// Open interest in USD, for the perpetuals of venues that list it with their tickers
+    #[serde(default)]
+    pub open_interest: Option<f64>,
+    /// Current funding rate, e.g. `0.0001` for 0.01% per the venue's funding interval
+    #[serde(default)]
+    pub funding_rate: Option<f32>,
+    /// Top of book spread in basis points of the mid price
+    #[serde(default)]
+    pub spread_bps: Option<f32>,
+}
+
+impl TickerStats {
+    /// Spread between the best bid and ask in basis points of their mid, `None` if either
+    /// side is missing or the book is crossed
+    pub fn spread_in_bps(bid: Option<f64>, ask: Option<f64>) -> Option<f32> {
+        let (bid, ask) = (bid?, ask?);
+        let mid = (bid + ask) / 2.0;
+        if bid <= 0.0 || ask < bid {
+            return None;
+        }
+        Some(((ask - bid) / mid * 10_000.0) as f32)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -684,6 +751,24 @@ pub struct OpenInterest {
     pub value: f64,
 }
 
+/// Reference prices of a perpetual, pushed by the venue about once a second
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkPrice {
+    pub time: UnixMs,
+    /// Price used for margining and liquidations
+    pub mark: Price,
+    /// Spot index the contract tracks, not every venue sends it
+    pub index: Option<Price>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FundingRate {
+    /// Settlement time of the funding interval
+    pub time: UnixMs,
+    /// Fraction paid by longs to shorts, e.g. `0.0001` for 0.01%
+    pub rate: f64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
 pub struct TickMultiplier(pub u16);
 
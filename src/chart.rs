@@ -3,14 +3,19 @@ pub mod heatmap;
 pub mod indicator;
 pub mod kline;
 mod scale;
+pub mod spread;
 
 use crate::connector::fetcher::{FetchRange, FetchSpec, RequestHandler};
 use crate::style;
 use crate::widget::multi_split::{DRAG_SIZE, MultiSplit};
 use crate::widget::tooltip;
-use data::chart::{Autoscale, Basis, PlotData, ViewConfig, indicator::Indicator};
-use exchange::TickerInfo;
-use exchange::unit::{Price, PriceStep};
+use data::alerts::LevelEdit;
+use data::chart::drawing::{Anchor, Drawing, Note, Snap, Tool};
+use data::chart::{Autoscale, Basis, PlotData, ViewConfig};
+use data::trading::{Account, ChartLevel, Fill};
+use exchange::adapter::trading::OrderSide;
+use exchange::unit::{Price, PriceStep, Qty};
+use exchange::{TickerInfo, UnixMs};
 use scale::linear::PriceInfoLabel;
 use scale::{AxisLabelsX, AxisLabelsY};
 
@@ -23,6 +28,10 @@ use iced::{
 
 const ZOOM_SENSITIVITY: f32 = 30.0;
 const TEXT_SIZE: f32 = crate::style::text_size::BODY;
+/// How far from a drawing a click still picks it, in pixels
+const DRAWING_HIT_PX: f32 = 5.0;
+/// Size of a note's marker, its text is set beside it
+const NOTE_MARKER_PX: f32 = 14.0;
 
 #[derive(Default, Debug, Clone, Copy)]
 pub enum Interaction {
@@ -38,6 +47,16 @@ pub enum Interaction {
     Ruler {
         start: Option<Point>,
     },
+    /// Picking the first and last candle of a range, see [`Chart::selects_ranges`]
+    RangeSelect {
+        start: Option<u64>,
+    },
+    /// First point of a drawing placed, the next click places the other one
+    Drawing {
+        from: Anchor,
+        /// Under the cursor, snapped like the next click would be
+        to: Option<Anchor>,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -46,21 +65,50 @@ pub enum AxisScaleClicked {
     Y,
 }
 
+/// The candle hovered on another pane of the same link group, see [`sync_linked_crosshair`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkedCrosshair {
+    /// Open time of the hovered candle
+    pub time: UnixMs,
+    pub span_ms: u64,
+    /// Left out unless the group shares prices as well
+    pub price: Option<Price>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Message {
     Translated(Vector),
     Scaled(f32, Vector),
     AutoscaleToggled,
-    CrosshairMoved,
+    /// Where the cursor is on a time based chart, shared with the pane's link group
+    CrosshairMoved(Option<LinkedCrosshair>),
     YScaling(f32, f32, bool),
     XScaling(f32, f32, bool),
     BoundsChanged(Rectangle),
     SplitDragged(usize, f32),
     DoubleClick(AxisScaleClicked),
+    /// Handled by the pane, alerts live outside the chart
+    AlertLevel(LevelEdit),
+    /// Intervals of the first and last candle picked, handled by the pane
+    RangeSelected(u64, u64),
+    /// Interval of the column clicked without dragging, see [`Chart::picks_columns`]
+    ColumnClicked(u64),
+    /// Tool the next clicks place a drawing with, `None` to go back to panning
+    DrawingToolChanged(Option<Tool>),
+    /// Kept in the pane settings, which mirror it back onto the chart
+    DrawingPlaced(Drawing),
+    DrawingSelected(Option<usize>),
+    /// Kept in the pane settings, which mirror it back onto the chart
+    DrawingRemoved(usize),
+    /// A note placed with [`Tool::Note`], the pane opens it for editing
+    NotePlaced(Anchor),
+    /// Handled by the pane, which opens the note for editing
+    NoteClicked(usize),
 }
 
 pub trait Chart: PlotConstants + canvas::Program<Message> {
-    type IndicatorKind: Indicator;
+    /// What the pane lists as enabled indicators, in the order they are stacked
+    type IndicatorKind;
 
     fn state(&self) -> &ViewState;
 
@@ -81,6 +129,21 @@ pub trait Chart: PlotConstants + canvas::Program<Message> {
     fn supports_fit_autoscaling(&self) -> bool;
 
     fn is_empty(&self) -> bool;
+
+    /// Whether pressing Alt starts picking a range of candles
+    fn selects_ranges(&self) -> bool {
+        false
+    }
+
+    /// Whether a click that doesn't pan picks the column under the cursor
+    fn picks_columns(&self) -> bool {
+        false
+    }
+
+    /// Open, high, low and close of the candle at `interval`, see [`Snap::Ohlc`]
+    fn ohlc_at(&self, _interval: u64) -> Option<[Price; 4]> {
+        None
+    }
 }
 
 fn canvas_interaction<T: Chart>(
@@ -99,7 +162,18 @@ fn canvas_interaction<T: Chart>(
 
     if let Event::Mouse(mouse::Event::ButtonReleased(_)) = event {
         match interaction {
-            Interaction::Panning { .. } | Interaction::Zoomin { .. } => {
+            Interaction::Panning { start, .. } => {
+                // Let go where it was pressed, a click rather than a drag
+                let clicked = chart.picks_columns()
+                    && cursor_position.is_some_and(|position| position.distance(*start) < 3.0);
+                *interaction = Interaction::None;
+
+                if clicked && let Some(position) = cursor.position_in(bounds) {
+                    let interval = chart.state().interval_at(position.x);
+                    return Some(canvas::Action::publish(Message::ColumnClicked(interval)));
+                }
+            }
+            Interaction::Zoomin { .. } => {
                 *interaction = Interaction::None;
             }
             _ => {}
@@ -121,6 +195,12 @@ fn canvas_interaction<T: Chart>(
                     let cursor_in_bounds = cursor_position?;
 
                     if let mouse::Button::Left = button {
+                        if let Some(action) =
+                            drawing_click(chart, interaction, cursor.position_in(bounds)?)
+                        {
+                            return Some(action);
+                        }
+
                         match interaction {
                             Interaction::None
                             | Interaction::Panning { .. }
@@ -138,11 +218,35 @@ fn canvas_interaction<T: Chart>(
                             Interaction::Ruler { .. } => {
                                 *interaction = Interaction::None;
                             }
+                            Interaction::RangeSelect { start: None } => {
+                                let interval = state.interval_at(cursor.position_in(bounds)?.x);
+                                *interaction = Interaction::RangeSelect {
+                                    start: Some(interval),
+                                };
+                            }
+                            Interaction::RangeSelect { start: Some(start) } => {
+                                let interval = state.interval_at(cursor.position_in(bounds)?.x);
+                                let msg = Message::RangeSelected(*start, interval);
+                                *interaction = Interaction::None;
+                                return Some(canvas::Action::publish(msg).and_capture());
+                            }
+                            // The tool was dropped halfway through, from the palette
+                            Interaction::Drawing { .. } => {
+                                *interaction = Interaction::None;
+                            }
                         }
                     }
                     Some(canvas::Action::request_redraw().and_capture())
                 }
                 mouse::Event::CursorMoved { .. } => match *interaction {
+                    Interaction::Drawing { from, .. } => {
+                        *interaction = Interaction::Drawing {
+                            from,
+                            to: snapped_anchor(chart, cursor.position_in(bounds)?),
+                        };
+                        let hovered = state.linked_crosshair_at(cursor.position_in(bounds));
+                        Some(canvas::Action::publish(Message::CrosshairMoved(hovered)))
+                    }
                     Interaction::Panning { translation, start } => {
                         let cursor_in_bounds = cursor_position?;
                         let msg = Message::Translated(
@@ -150,8 +254,11 @@ fn canvas_interaction<T: Chart>(
                         );
                         Some(canvas::Action::publish(msg).and_capture())
                     }
-                    Interaction::None | Interaction::Ruler { .. } => {
-                        Some(canvas::Action::publish(Message::CrosshairMoved))
+                    Interaction::None
+                    | Interaction::Ruler { .. }
+                    | Interaction::RangeSelect { .. } => {
+                        let hovered = state.linked_crosshair_at(cursor.position_in(bounds));
+                        Some(canvas::Action::publish(Message::CrosshairMoved(hovered)))
                     }
                     _ => None,
                 },
@@ -260,10 +367,33 @@ fn canvas_interaction<T: Chart>(
                         *interaction = Interaction::Ruler { start: None };
                         Some(canvas::Action::request_redraw().and_capture())
                     }
+                    keyboard::Key::Named(keyboard::key::Named::Alt) if chart.selects_ranges() => {
+                        *interaction = Interaction::RangeSelect { start: None };
+                        Some(canvas::Action::request_redraw().and_capture())
+                    }
                     keyboard::Key::Named(keyboard::key::Named::Escape) => {
                         *interaction = Interaction::None;
-                        Some(canvas::Action::request_redraw().and_capture())
+
+                        let state = chart.state();
+                        if state.drawing_tool.is_some() {
+                            Some(
+                                canvas::Action::publish(Message::DrawingToolChanged(None))
+                                    .and_capture(),
+                            )
+                        } else if state.selected_drawing.is_some() {
+                            Some(
+                                canvas::Action::publish(Message::DrawingSelected(None))
+                                    .and_capture(),
+                            )
+                        } else {
+                            Some(canvas::Action::request_redraw().and_capture())
+                        }
                     }
+                    keyboard::Key::Named(
+                        keyboard::key::Named::Delete | keyboard::key::Named::Backspace,
+                    ) => chart.state().selected_drawing.map(|index| {
+                        canvas::Action::publish(Message::DrawingRemoved(index)).and_capture()
+                    }),
                     _ => None,
                 },
                 _ => None,
@@ -273,6 +403,85 @@ fn canvas_interaction<T: Chart>(
     }
 }
 
+/// Places a drawing with the picked tool, or selects the one under the cursor.
+/// `None` leaves the click to the other interactions
+fn drawing_click<T: Chart>(
+    chart: &T,
+    interaction: &mut Interaction,
+    position: Point,
+) -> Option<canvas::Action<Message>> {
+    let state = chart.state();
+
+    if let Some(tool) = state.drawing_tool {
+        let anchor = snapped_anchor(chart, position)?;
+
+        if tool == Tool::Note {
+            *interaction = Interaction::None;
+            return Some(canvas::Action::publish(Message::NotePlaced(anchor)).and_capture());
+        }
+
+        let from = match *interaction {
+            Interaction::Drawing { from, .. } => from,
+            _ if tool.anchors() == 1 => anchor,
+            _ => {
+                *interaction = Interaction::Drawing {
+                    from: anchor,
+                    to: None,
+                };
+                return Some(canvas::Action::request_redraw().and_capture());
+            }
+        };
+
+        *interaction = Interaction::None;
+        let drawing = Drawing {
+            tool,
+            from,
+            to: anchor,
+        };
+        return Some(canvas::Action::publish(Message::DrawingPlaced(drawing)).and_capture());
+    }
+
+    if !matches!(interaction, Interaction::None | Interaction::Panning { .. }) {
+        return None;
+    }
+
+    if let Some(index) = state.note_at(position) {
+        return Some(canvas::Action::publish(Message::NoteClicked(index)).and_capture());
+    }
+
+    match state.drawing_at(position) {
+        Some(index) if state.selected_drawing != Some(index) => {
+            Some(canvas::Action::publish(Message::DrawingSelected(Some(index))).and_capture())
+        }
+        Some(_) => Some(canvas::Action::capture()),
+        None if state.selected_drawing.is_some() => {
+            Some(canvas::Action::publish(Message::DrawingSelected(None)).and_capture())
+        }
+        None => None,
+    }
+}
+
+/// The candle and price under `position`, pulled onto what the chart snaps drawings to
+fn snapped_anchor<T: Chart>(chart: &T, position: Point) -> Option<Anchor> {
+    let state = chart.state();
+    let mut anchor = state.anchor_at(position)?;
+
+    let nearest_ohlc = match state.drawing_snap {
+        Snap::Off => return Some(anchor),
+        Snap::Ohlc => chart.ohlc_at(anchor.time.as_u64()),
+        Snap::Tick => None,
+    };
+
+    anchor.price = match nearest_ohlc {
+        Some(ohlc) => ohlc
+            .into_iter()
+            .min_by_key(|price| (price.units - anchor.price.units).unsigned_abs())
+            .unwrap_or(anchor.price),
+        None => anchor.price.round_to_step(state.price_step()),
+    };
+    Some(anchor)
+}
+
 pub enum Action {
     ErrorOccurred(data::InternalError),
     RequestFetch(Vec<FetchSpec>),
@@ -409,7 +618,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
 
                         state.interval_to_x(cursor_time)
                     }
-                    Basis::Tick(_) => {
+                    Basis::Tick(_) | Basis::Bars(_) => {
                         let tick_index = cursor_chart_x / state.cell_width;
                         state.cell_width = new_width;
 
@@ -486,11 +695,101 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                 *split = (size * 100.0).round() / 100.0;
             }
         }
-        Message::CrosshairMoved => return chart.invalidate_crosshair(),
+        Message::DrawingToolChanged(tool) => {
+            let state = chart.mut_state();
+            state.drawing_tool = tool.filter(|_| state.basis.is_time());
+            state.selected_drawing = None;
+        }
+        Message::DrawingPlaced(_) | Message::NotePlaced(_) => {
+            chart.mut_state().drawing_tool = None;
+        }
+        Message::DrawingSelected(index) => {
+            chart.mut_state().selected_drawing = *index;
+        }
+        Message::DrawingRemoved(_) => {
+            chart.mut_state().selected_drawing = None;
+        }
+        Message::CrosshairMoved(_) => return chart.invalidate_crosshair(),
+        Message::AlertLevel(_)
+        | Message::RangeSelected(..)
+        | Message::ColumnClicked(_)
+        | Message::NoteClicked(_) => return,
     }
     chart.invalidate_all();
 }
 
+pub fn sync_scale_factor<T: Chart>(chart: &mut T, scale_factor: f32) {
+    if chart.mut_state().set_scale_factor(scale_factor) {
+        chart.invalidate_all();
+    }
+}
+
+pub fn sync_alert_levels<T: Chart>(chart: &mut T, levels: &[(u32, f64)]) {
+    if chart.mut_state().set_alert_levels(levels) {
+        chart.invalidate_all();
+    }
+}
+
+pub fn sync_chart_levels<T: Chart>(chart: &mut T, account: Option<&Account>) {
+    if chart.mut_state().set_chart_levels(account) {
+        chart.invalidate_all();
+    }
+}
+
+/// Mirrors the drawings and notes the pane keeps for the chart's ticker, and how their
+/// anchors snap
+pub fn sync_drawings<T: Chart>(chart: &mut T, drawings: &[Drawing], notes: &[Note], snap: Snap) {
+    chart.mut_state().drawing_snap = snap;
+
+    if chart.mut_state().set_drawings(drawings, notes) {
+        chart.invalidate_all();
+    }
+}
+
+/// Shows where the cursor is on another pane of the link group, `None` once it left
+pub fn sync_linked_crosshair<T: Chart>(chart: &mut T, crosshair: Option<LinkedCrosshair>) {
+    let state = chart.mut_state();
+    if state.linked_crosshair != crosshair {
+        state.linked_crosshair = crosshair;
+        chart.invalidate_crosshair();
+    }
+}
+
+/// Earliest and latest time the chart shows, on time based charts only
+pub fn visible_timespan<T: Chart>(chart: &T) -> Option<(UnixMs, UnixMs)> {
+    chart.state().visible_timespan()
+}
+
+/// Zooms and scrolls horizontally to show the time span another pane of the link group does
+pub fn sync_linked_timespan<T: Chart>(chart: &mut T, (earliest, latest): (UnixMs, UnixMs)) {
+    let min_cell_width = T::min_cell_width(chart);
+    let max_cell_width = T::max_cell_width(chart);
+
+    if chart
+        .mut_state()
+        .fit_timespan(earliest, latest, min_cell_width, max_cell_width)
+    {
+        chart.invalidate_all();
+    }
+}
+
+/// The tool the next clicks on the chart place a drawing with, if any
+pub fn drawing_tool<T: Chart>(chart: &T) -> Option<Tool> {
+    chart.state().drawing_tool
+}
+
+/// Drawings are anchored in time, so they're only placed and shown on time basis
+pub fn supports_drawings<T: Chart>(chart: &T) -> bool {
+    chart.state().basis.is_time()
+}
+
+/// Pans to `time` and `price`, on time based charts only
+pub fn focus_on<T: Chart>(chart: &mut T, time: UnixMs, price: Price) {
+    if chart.mut_state().focus_on(time, price) {
+        chart.invalidate_all();
+    }
+}
+
 pub fn view<'a, T: Chart>(
     chart: &'a T,
     indicators: &'a [T::IndicatorKind],
@@ -513,6 +812,7 @@ pub fn view<'a, T: Chart>(
         chart_bounds: state.bounds,
         interval_keys: chart.interval_keys(),
         autoscaling: state.layout.autoscale,
+        scale_factor: state.scale_factor,
     })
     .width(Length::Fill)
     .height(Length::Fill);
@@ -560,6 +860,8 @@ pub fn view<'a, T: Chart>(
             cell_height: state.cell_height,
             basis: state.basis,
             chart_bounds: state.bounds,
+            scale_factor: state.scale_factor,
+            alert_levels: &state.alert_levels,
         })
         .width(Length::Fill)
         .height(Length::Fill);
@@ -660,6 +962,20 @@ pub struct ViewState {
     decimals: usize,
     ticker_info: TickerInfo,
     layout: ViewConfig,
+    /// Effective window scale factor (OS DPI x UI scale), used to snap text and strokes
+    scale_factor: f32,
+    /// Price cross alerts of the ticker, drawn as lines and draggable on the price axis
+    alert_levels: Vec<(u32, Price)>,
+    /// Own position entry, liquidation and resting order prices of the ticker
+    chart_levels: Vec<ChartLevel>,
+    fills: Vec<Fill>,
+    /// Kept in the pane settings for the ticker, see [`sync_drawings`]
+    drawings: Vec<Drawing>,
+    notes: Vec<Note>,
+    drawing_tool: Option<Tool>,
+    drawing_snap: Snap,
+    selected_drawing: Option<usize>,
+    linked_crosshair: Option<LinkedCrosshair>,
 }
 
 impl ViewState {
@@ -687,7 +1003,126 @@ impl ViewState {
             decimals,
             ticker_info,
             layout,
+            scale_factor: 1.0,
+            alert_levels: vec![],
+            chart_levels: vec![],
+            fills: vec![],
+            drawings: vec![],
+            notes: vec![],
+            drawing_tool: None,
+            drawing_snap: Snap::Off,
+            selected_drawing: None,
+            linked_crosshair: None,
+        }
+    }
+
+    /// Returns `true` if the factor changed and the caches need to be redrawn.
+    fn set_scale_factor(&mut self, scale_factor: f32) -> bool {
+        if (self.scale_factor - scale_factor).abs() < f32::EPSILON {
+            return false;
+        }
+        self.scale_factor = scale_factor;
+        true
+    }
+
+    /// Returns `true` if the levels changed and the caches need to be redrawn.
+    fn set_alert_levels(&mut self, levels: &[(u32, f64)]) -> bool {
+        let levels = levels
+            .iter()
+            .map(|&(id, price)| (id, Price::from_f64(price)));
+
+        // Synced every frame, so only allocate once something changed
+        if levels.clone().eq(self.alert_levels.iter().copied()) {
+            return false;
+        }
+        self.alert_levels = levels.collect();
+        true
+    }
+
+    /// Returns `true` if the levels changed and the caches need to be redrawn.
+    fn set_chart_levels(&mut self, account: Option<&Account>) -> bool {
+        let Some(account) = account else {
+            if self.chart_levels.is_empty() && self.fills.is_empty() {
+                return false;
+            }
+            self.chart_levels.clear();
+            self.fills.clear();
+            return true;
+        };
+
+        if account.chart_levels().eq(self.chart_levels.iter().copied())
+            && account.fills == self.fills
+        {
+            return false;
+        }
+        self.chart_levels = account.chart_levels().collect();
+        self.fills.clone_from(&account.fills);
+        true
+    }
+
+    /// Returns `true` if the drawings or notes changed and the caches need to be redrawn.
+    fn set_drawings(&mut self, drawings: &[Drawing], notes: &[Note]) -> bool {
+        if self.drawings == drawings && self.notes == notes {
+            return false;
+        }
+        if self.drawings != drawings {
+            self.drawings = drawings.to_vec();
+            self.selected_drawing = None;
+        }
+        self.notes = notes.to_vec();
+        true
+    }
+
+    fn focus_on(&mut self, time: UnixMs, price: Price) -> bool {
+        let Basis::Time(timeframe) = self.basis else {
+            return false;
+        };
+        let interval = time.floor_to(timeframe).as_u64();
+
+        self.layout.autoscale = None;
+        self.translation = Vector::new(-self.interval_to_x(interval), -self.price_to_y(price));
+        true
+    }
+
+    fn visible_timespan(&self) -> Option<(UnixMs, UnixMs)> {
+        if !self.basis.is_time() || self.bounds.width <= f32::EPSILON {
+            return None;
+        }
+        let region = self.visible_region(self.bounds.size());
+
+        Some((
+            UnixMs::new(self.x_to_interval(region.x)),
+            UnixMs::new(self.x_to_interval(region.x + region.width)),
+        ))
+    }
+
+    /// Returns `true` if the view changed. The cell width is clamped, so a span too long or
+    /// too short for the chart only gets centered
+    fn fit_timespan(
+        &mut self,
+        earliest: UnixMs,
+        latest: UnixMs,
+        min_cell_width: f32,
+        max_cell_width: f32,
+    ) -> bool {
+        let Basis::Time(timeframe) = self.basis else {
+            return false;
+        };
+        let span = latest.as_u64().saturating_sub(earliest.as_u64());
+        if span == 0 || self.bounds.width <= f32::EPSILON {
+            return false;
+        }
+
+        let width = self.bounds.width / self.scaling;
+        let interval = timeframe.to_milliseconds() as f32;
+        self.cell_width = (width * interval / span as f32).clamp(min_cell_width, max_cell_width);
+
+        // Centering on the latest candle would pull it right back
+        if self.layout.autoscale == Some(Autoscale::CenterLatest) {
+            self.layout.autoscale = None;
         }
+        self.translation.x = -self.interval_to_x(earliest.as_u64() + span / 2);
+        true
     }
 
     fn effective_tick_units(&self) -> i64 {
@@ -719,7 +1154,7 @@ impl ViewState {
 
     fn interval_range(&self, region: &Rectangle) -> (u64, u64) {
         match self.basis {
-            Basis::Tick(_) => (
+            Basis::Tick(_) | Basis::Bars(_) => (
                 self.x_to_interval(region.x + region.width),
                 self.x_to_interval(region.x),
             ),
@@ -750,7 +1185,21 @@ impl ViewState {
                 let diff = value as f64 - self.latest_x as f64;
                 (diff / interval * cell_width) as f32
             }
-            Basis::Tick(_) => -((value as f32) * self.cell_width),
+            Basis::Tick(_) | Basis::Bars(_) => -((value as f32) * self.cell_width),
+        }
+    }
+
+    /// Interval of the candle under `x`, in canvas coordinates
+    fn interval_at(&self, x: f32) -> u64 {
+        let region = self.visible_region(self.bounds.size());
+        let chart_x = region.x + (x / self.bounds.width) * region.width;
+
+        match self.basis {
+            Basis::Time(timeframe) => {
+                let interval = timeframe.to_milliseconds().max(1);
+                (self.x_to_interval(chart_x) + interval / 2) / interval * interval
+            }
+            Basis::Tick(_) | Basis::Bars(_) => self.x_to_interval(chart_x),
         }
     }
 
@@ -767,7 +1216,7 @@ impl ViewState {
                     self.latest_x.saturating_add(diff)
                 }
             }
-            Basis::Tick(_) => {
+            Basis::Tick(_) | Basis::Bars(_) => {
                 let tick = -(x / self.cell_width);
                 tick.round() as u64
             }
@@ -802,11 +1251,49 @@ impl ViewState {
         let highest: f32 = highest_p.to_f32_lossy();
         let lowest: f32 = lowest_p.to_f32_lossy();
 
-        let effective_step = if self.tick_size.units > 0 {
-            self.tick_size
-        } else {
-            self.ticker_info.min_ticksize.into()
-        };
+        let effective_step = self.price_step();
+
+        if let Interaction::RangeSelect { start: Some(start) } = interaction {
+            let start_x = (self.interval_to_x(*start) - region.x) / region.width * bounds.width;
+            let (left, right) = (
+                start_x.min(cursor_position.x),
+                start_x.max(cursor_position.x),
+            );
+
+            frame.fill_rectangle(
+                Point::new(left, 0.0),
+                Size::new(right - left, bounds.height),
+                theme
+                    .extended_palette()
+                    .primary
+                    .base
+                    .color
+                    .scale_alpha(0.08),
+            );
+        }
+
+        if let Interaction::Drawing { from, to: Some(to) } = interaction {
+            let (from, to) = (
+                self.to_canvas(self.anchor_point(from), region),
+                self.to_canvas(self.anchor_point(to), region),
+            );
+
+            let stroke = Stroke::with_color(
+                Stroke {
+                    width: style::dpi::stroke_width(1.0, self.scale_factor),
+                    ..Default::default()
+                },
+                theme.extended_palette().primary.base.color,
+            );
+
+            if self.drawing_tool.is_some_and(Tool::is_boxed) {
+                let top_left = Point::new(from.x.min(to.x), from.y.min(to.y));
+                let size = Size::new((to.x - from.x).abs(), (to.y - from.y).abs());
+                frame.stroke(&Path::rectangle(top_left, size), stroke);
+            } else {
+                frame.stroke(&Path::line(from, to), stroke);
+            }
+        }
 
         if let Interaction::Ruler { start: Some(start) } = interaction {
             let p1 = *start;
@@ -860,6 +1347,8 @@ impl ViewState {
                     let tick_diff = tick1.abs_diff(tick2);
                     format!("{} ticks", tick_diff)
                 }
+                // Bars closed by price don't span a set number of trades
+                Basis::Bars(_) => String::new(),
             };
 
             let rect_x = snapped_p1_x.min(snapped_p2_x);
@@ -911,17 +1400,21 @@ impl ViewState {
                     let datapoints = (diff_ms / interval_ms).max(1);
                     format!("{} bars", datapoints)
                 }
-                Basis::Tick(aggregation) => {
+                Basis::Tick(_) | Basis::Bars(_) => {
                     let (tick1, _) = self.snap_x_to_index(p1.x, bounds, region);
                     let (tick2, _) = self.snap_x_to_index(p2.x, bounds, region);
 
                     let tick_diff = tick1.abs_diff(tick2);
-                    let datapoints = (tick_diff / u64::from(aggregation.0)).max(1);
+                    let datapoints = (tick_diff / self.basis.index_step()).max(1);
                     format!("{} bars", datapoints)
                 }
             };
 
-            let label_text = format!("{}, {} | {}", datapoints_text, interval_diff, pct_text);
+            let label_text = if interval_diff.is_empty() {
+                format!("{} | {}", datapoints_text, pct_text)
+            } else {
+                format!("{}, {} | {}", datapoints_text, interval_diff, pct_text)
+            };
 
             let text_width = (label_text.len() as f32) * TEXT_SIZE * 0.6;
             let text_height = TEXT_SIZE * 1.2;
@@ -960,7 +1453,10 @@ impl ViewState {
                 content: label_text,
                 position: text_pos,
                 color: palette.background.base.text,
-                size: iced::Pixels(crate::style::text_size::SMALL),
+                size: iced::Pixels(style::dpi::text_size(
+                    style::text_size::SMALL,
+                    self.scale_factor,
+                )),
                 align_x: match idx {
                     0 | 2 => Alignment::Start.into(),
                     1 | 3 => Alignment::End.into(),
@@ -1008,7 +1504,7 @@ impl ViewState {
                 );
                 (rounded_price, rounded_timestamp)
             }
-            Basis::Tick(aggregation) => {
+            Basis::Tick(_) | Basis::Bars(_) => {
                 let (chart_x_min, chart_x_max) = (region.x, region.x + region.width);
                 let crosshair_pos = chart_x_min + (cursor_position.x / bounds.width) * region.width;
 
@@ -1017,7 +1513,7 @@ impl ViewState {
                 let snapped_crosshair = cell_index * self.cell_width;
                 let snap_ratio = (snapped_crosshair - chart_x_min) / (chart_x_max - chart_x_min);
 
-                let rounded_tick = (-cell_index as u64) * (u64::from(aggregation.0));
+                let rounded_tick = (-cell_index as u64) * self.basis.index_step();
 
                 frame.stroke(
                     &Path::line(
@@ -1043,7 +1539,7 @@ impl ViewState {
 
             let marker_line = Stroke::with_color(
                 Stroke {
-                    width: 1.0,
+                    width: style::dpi::stroke_width(1.0, self.scale_factor),
                     line_dash: LineDash {
                         segments: &[2.0, 2.0],
                         offset: 4,
@@ -1063,6 +1559,506 @@ impl ViewState {
         }
     }
 
+    fn draw_alert_levels(&self, frame: &mut canvas::Frame, palette: &Extended, region: Rectangle) {
+        let line = Stroke::with_color(
+            Stroke {
+                width: style::dpi::stroke_width(1.0, self.scale_factor),
+                line_dash: LineDash {
+                    segments: &[6.0, 3.0],
+                    offset: 0,
+                },
+                ..Default::default()
+            },
+            palette.warning.base.color.scale_alpha(0.8),
+        );
+
+        for &(_, price) in &self.alert_levels {
+            let y_pos = self.price_to_y(price);
+
+            frame.stroke(
+                &Path::line(
+                    Point::new(region.x, y_pos),
+                    Point::new(region.x + region.width, y_pos),
+                ),
+                line,
+            );
+        }
+    }
+
+    /// Entries as solid lines, liquidations dashed and resting orders dotted.
+    /// Fills are marked where they happened, on time based charts only
+    fn draw_chart_levels(&self, frame: &mut canvas::Frame, palette: &Extended, region: Rectangle) {
+        let width = style::dpi::stroke_width(1.0, self.scale_factor);
+        let side_color = |is_buy: bool| {
+            if is_buy {
+                palette.success.base.color
+            } else {
+                palette.danger.base.color
+            }
+        };
+
+        for level in &self.chart_levels {
+            let (color, segments): (_, &[f32]) = match *level {
+                ChartLevel::Entry { is_long, .. } => (side_color(is_long), &[]),
+                ChartLevel::Liquidation(_) => (palette.warning.strong.color, &[8.0, 4.0]),
+                ChartLevel::Order { is_buy, .. } => {
+                    (side_color(is_buy).scale_alpha(0.8), &[2.0, 3.0])
+                }
+            };
+
+            let line = Stroke::with_color(
+                Stroke {
+                    width,
+                    line_dash: LineDash {
+                        segments,
+                        offset: 0,
+                    },
+                    ..Default::default()
+                },
+                color,
+            );
+
+            let y_pos = self.price_to_y(level.price());
+            frame.stroke(
+                &Path::line(
+                    Point::new(region.x, y_pos),
+                    Point::new(region.x + region.width, y_pos),
+                ),
+                line,
+            );
+        }
+
+        if !matches!(self.basis, Basis::Time(_)) {
+            return;
+        }
+        let radius = 4.0 / self.scaling;
+
+        for fill in &self.fills {
+            let x_pos = self.interval_to_x(fill.time.as_u64());
+            if x_pos < region.x - radius || x_pos > region.x + region.width + radius {
+                continue;
+            }
+
+            let center = Point::new(x_pos, self.price_to_y(fill.price));
+            let color = side_color(fill.side == OrderSide::Buy);
+
+            frame.fill(&Path::circle(center, radius), color.scale_alpha(0.6));
+            frame.stroke(
+                &Path::circle(center, radius),
+                Stroke::default().with_color(color).with_width(width),
+            );
+        }
+    }
+
+    /// Trend lines and rays as drawn, horizontal and vertical lines across the whole region,
+    /// the boxed tools as the area their anchors span. The selected one is drawn thicker with
+    /// its anchors marked. `volume_of` sums what was traded within a measured area
+    fn draw_drawings(
+        &self,
+        frame: &mut canvas::Frame,
+        palette: &Extended,
+        region: Rectangle,
+        volume_of: impl Fn(&Drawing) -> Qty,
+    ) {
+        if !self.basis.is_time() {
+            return;
+        }
+        let width = style::dpi::stroke_width(1.0, self.scale_factor);
+
+        for (index, drawing) in self.drawings.iter().enumerate() {
+            let is_selected = self.selected_drawing == Some(index);
+            let color = match drawing.tool {
+                Tool::Measure if drawing.to.price >= drawing.from.price => {
+                    palette.success.base.color
+                }
+                Tool::Measure => palette.danger.base.color,
+                _ if is_selected => palette.primary.strong.color,
+                _ => palette.primary.base.color,
+            };
+            let stroke = Stroke::with_color(
+                Stroke {
+                    width: if is_selected { width * 2.0 } else { width },
+                    ..Default::default()
+                },
+                color,
+            );
+
+            match drawing.tool {
+                Tool::Rectangle | Tool::Measure => {
+                    let (from, to) = (
+                        self.anchor_point(&drawing.from),
+                        self.anchor_point(&drawing.to),
+                    );
+                    let top_left = Point::new(from.x.min(to.x), from.y.min(to.y));
+                    let size = Size::new((to.x - from.x).abs(), (to.y - from.y).abs());
+
+                    frame.fill_rectangle(top_left, size, color.scale_alpha(0.1));
+                    if drawing.tool == Tool::Measure {
+                        frame.stroke(&Path::line(from, to), stroke);
+                        self.draw_measure_label(frame, drawing, volume_of(drawing), color, palette);
+                    } else {
+                        frame.stroke(&Path::rectangle(top_left, size), stroke);
+                    }
+                }
+                Tool::FibRetracement | Tool::FibExtension => {
+                    let precision = self.ticker_info.min_ticksize;
+                    let text_size = TEXT_SIZE / self.scaling;
+
+                    frame.stroke(
+                        &Path::line(
+                            self.anchor_point(&drawing.from),
+                            self.anchor_point(&drawing.to),
+                        ),
+                        Stroke {
+                            line_dash: LineDash {
+                                segments: &[4.0, 4.0],
+                                offset: 0,
+                            },
+                            ..stroke
+                        },
+                    );
+
+                    for ((from, to), level) in self
+                        .drawing_segments(drawing, region)
+                        .into_iter()
+                        .zip(drawing.tool.fib_levels())
+                    {
+                        frame.stroke(&Path::line(from, to), stroke);
+
+                        let price = drawing
+                            .fib_price(*level)
+                            .round_to_min_tick(precision)
+                            .to_string(precision);
+                        frame.fill_text(canvas::Text {
+                            content: format!("{level} ({price})"),
+                            position: Point::new(from.x, from.y - 2.0 / self.scaling),
+                            size: iced::Pixels(text_size),
+                            color: color.scale_alpha(0.9),
+                            font: style::AZERET_MONO,
+                            align_y: Alignment::End.into(),
+                            ..canvas::Text::default()
+                        });
+                    }
+                }
+                Tool::TrendLine | Tool::Ray | Tool::HorizontalLine | Tool::VerticalLine => {
+                    for (from, to) in self.drawing_segments(drawing, region) {
+                        frame.stroke(&Path::line(from, to), stroke);
+                    }
+                }
+                // Placed as a note, never kept as a drawing
+                Tool::Note => {}
+            }
+
+            if is_selected {
+                let radius = 3.0 / self.scaling;
+                let anchors = [drawing.from, drawing.to];
+
+                for anchor in &anchors[..drawing.tool.anchors()] {
+                    let center = self.anchor_point(anchor);
+                    frame.fill(&Path::circle(center, radius), palette.background.base.color);
+                    frame.stroke(
+                        &Path::circle(center, radius),
+                        Stroke::default().with_color(color).with_width(width),
+                    );
+                }
+            }
+        }
+
+        self.draw_notes(frame, palette, region);
+    }
+
+    /// Each note's marker sitting on its anchor, with its text beside it
+    fn draw_notes(&self, frame: &mut canvas::Frame, palette: &Extended, region: Rectangle) {
+        let size = NOTE_MARKER_PX / self.scaling;
+        let text_size = TEXT_SIZE / self.scaling;
+
+        for note in &self.notes {
+            let at = self.anchor_point(&note.at);
+            if at.x < region.x - size || at.x > region.x + region.width + size {
+                continue;
+            }
+            let center = Point::new(at.x, at.y - size / 2.0);
+
+            match note.marker.emoji() {
+                Some(emoji) => frame.fill_text(canvas::Text {
+                    content: emoji.to_string(),
+                    position: center,
+                    size: iced::Pixels(size),
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..canvas::Text::default()
+                }),
+                None => {
+                    let head = Point::new(at.x, at.y - size * 0.7);
+                    frame.stroke(
+                        &Path::line(at, head),
+                        Stroke::with_color(
+                            Stroke {
+                                width: style::dpi::stroke_width(1.5, self.scale_factor),
+                                ..Default::default()
+                            },
+                            palette.background.base.text,
+                        ),
+                    );
+                    frame.fill(&Path::circle(head, size * 0.3), palette.danger.base.color);
+                }
+            }
+
+            if note.text.is_empty() {
+                continue;
+            }
+            let position = Point::new(at.x + size * 0.6, center.y);
+            let padding = 2.0 / self.scaling;
+
+            frame.fill_rectangle(
+                Point::new(position.x - padding, position.y - text_size * 0.6 - padding),
+                Size::new(
+                    note.text.chars().count() as f32 * text_size * 0.6 + padding * 2.0,
+                    text_size * 1.2 + padding * 2.0,
+                ),
+                palette.background.weakest.color.scale_alpha(0.85),
+            );
+            frame.fill_text(canvas::Text {
+                content: note.text.clone(),
+                position,
+                size: iced::Pixels(text_size),
+                color: palette.background.base.text,
+                font: style::AZERET_MONO,
+                align_y: Alignment::Center.into(),
+                ..canvas::Text::default()
+            });
+        }
+    }
+
+    /// Topmost note whose marker is under `position`, in canvas coordinates
+    fn note_at(&self, position: Point) -> Option<usize> {
+        if !self.basis.is_time() {
+            return None;
+        }
+        let region = self.visible_region(self.bounds.size());
+
+        self.notes.iter().rposition(|note| {
+            let at = self.to_canvas(self.anchor_point(&note.at), region);
+            let center = Point::new(at.x, at.y - NOTE_MARKER_PX / 2.0);
+            center.distance(position) <= NOTE_MARKER_PX / 2.0 + DRAWING_HIT_PX / 2.0
+        })
+    }
+
+    /// Price, percent and time change of a measure with the volume traded within it, under
+    /// the measured area
+    fn draw_measure_label(
+        &self,
+        frame: &mut canvas::Frame,
+        drawing: &Drawing,
+        volume: Qty,
+        color: iced::Color,
+        palette: &Extended,
+    ) {
+        let Basis::Time(timeframe) = self.basis else {
+            return;
+        };
+        let (earliest, latest, low, _) = drawing.bounds();
+        let precision = self.ticker_info.min_ticksize;
+
+        let change = drawing.to.price - drawing.from.price;
+        let sign = if change.units > 0 { "+" } else { "" };
+        let from_price = drawing.from.price.to_f64();
+        let pct = if from_price == 0.0 {
+            0.0
+        } else {
+            (drawing.to.price.to_f64() / from_price - 1.0) * 100.0
+        };
+
+        let diff_ms = latest.as_u64() - earliest.as_u64();
+        let lines = [
+            format!("{sign}{} ({pct:+.2}%)", change.to_string(precision)),
+            format!(
+                "{} bars, {}",
+                diff_ms / timeframe.to_milliseconds(),
+                data::util::format_duration_ms(diff_ms)
+            ),
+            format!("Vol {}", data::util::abbr_large_numbers(volume.to_f64())),
+        ];
+
+        let text_size = TEXT_SIZE / self.scaling;
+        let padding = 4.0 / self.scaling;
+        let line_height = text_size * 1.2;
+        let text_width = lines.iter().map(String::len).max().unwrap_or(0) as f32 * text_size * 0.6;
+
+        let center_x =
+            (self.interval_to_x(earliest.as_u64()) + self.interval_to_x(latest.as_u64())) / 2.0;
+        let top = self.price_to_y(low) + padding * 2.0;
+
+        frame.fill_rectangle(
+            Point::new(center_x - text_width / 2.0 - padding, top),
+            Size::new(
+                text_width + padding * 2.0,
+                line_height * lines.len() as f32 + padding * 2.0,
+            ),
+            palette.background.weakest.color.scale_alpha(0.9),
+        );
+
+        for (i, content) in lines.into_iter().enumerate() {
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(center_x, top + padding + line_height * i as f32),
+                size: iced::Pixels(text_size),
+                color,
+                font: style::AZERET_MONO,
+                align_x: Alignment::Center.into(),
+                ..canvas::Text::default()
+            });
+        }
+    }
+
+    /// The candle and price under `position`, in canvas coordinates, as shared with the link
+    /// group. Time basis only
+    fn linked_crosshair_at(&self, position: Option<Point>) -> Option<LinkedCrosshair> {
+        let Basis::Time(timeframe) = self.basis else {
+            return None;
+        };
+        let anchor = self.anchor_at(position?)?;
+
+        Some(LinkedCrosshair {
+            time: anchor.time,
+            span_ms: timeframe.to_milliseconds(),
+            price: Some(anchor.price),
+        })
+    }
+
+    /// A dashed line at the candle hovered on another pane, across the price too when shared
+    fn draw_linked_crosshair(&self, frame: &mut Frame, theme: &Theme, bounds: Size) {
+        let (Basis::Time(timeframe), Some(crosshair)) = (self.basis, self.linked_crosshair) else {
+            return;
+        };
+        let region = self.visible_region(bounds);
+        let dashed_line = style::dashed_line(theme);
+
+        let interval = crosshair.time.floor_to(timeframe).as_u64();
+        let x = self
+            .to_canvas(Point::new(self.interval_to_x(interval), 0.0), region)
+            .x;
+        if (0.0..=bounds.width).contains(&x) {
+            frame.stroke(
+                &Path::line(Point::new(x, 0.0), Point::new(x, bounds.height)),
+                dashed_line,
+            );
+        }
+
+        if let Some(price) = crosshair.price {
+            let y = self
+                .to_canvas(Point::new(0.0, self.price_to_y(price)), region)
+                .y;
+            if (0.0..=bounds.height).contains(&y) {
+                frame.stroke(
+                    &Path::line(Point::new(0.0, y), Point::new(bounds.width, y)),
+                    dashed_line,
+                );
+            }
+        }
+    }
+
+    /// Candle and price under `position`, in canvas coordinates. Time basis only
+    fn anchor_at(&self, position: Point) -> Option<Anchor> {
+        if !self.basis.is_time() {
+            return None;
+        }
+        let region = self.visible_region(self.bounds.size());
+
+        Some(Anchor {
+            time: UnixMs::new(self.interval_at(position.x)),
+            price: self.y_to_price(region.y + position.y / self.scaling),
+        })
+    }
+
+    /// The chart's tick size, or the ticker's while it has none
+    fn price_step(&self) -> PriceStep {
+        if self.tick_size.units > 0 {
+            self.tick_size
+        } else {
+            self.ticker_info.min_ticksize.into()
+        }
+    }
+
+    fn anchor_point(&self, anchor: &Anchor) -> Point {
+        Point::new(
+            self.interval_to_x(anchor.time.as_u64()),
+            self.price_to_y(anchor.price),
+        )
+    }
+
+    /// A point in chart coordinates, in canvas coordinates
+    fn to_canvas(&self, point: Point, region: Rectangle) -> Point {
+        Point::new(
+            (point.x - region.x) * self.scaling,
+            (point.y - region.y) * self.scaling,
+        )
+    }
+
+    /// The drawing as segments in chart coordinates, reaching across `region` for the tools
+    /// that aren't bound by their anchors. The edges of a box, a line per level of a fib
+    fn drawing_segments(&self, drawing: &Drawing, region: Rectangle) -> Vec<(Point, Point)> {
+        let from = self.anchor_point(&drawing.from);
+        let to = self.anchor_point(&drawing.to);
+
+        match drawing.tool {
+            Tool::TrendLine => vec![(from, to)],
+            Tool::Ray => {
+                let direction = to - from;
+                let length = direction.x.hypot(direction.y);
+                if length <= f32::EPSILON {
+                    return vec![(from, to)];
+                }
+                // Far enough to leave the region from anywhere in or around it
+                let reach = from.distance(region.center()) + region.width + region.height;
+                vec![(from, from + direction * (reach / length))]
+            }
+            Tool::HorizontalLine => vec![(
+                Point::new(region.x, from.y),
+                Point::new(region.x + region.width, from.y),
+            )],
+            Tool::VerticalLine => vec![(
+                Point::new(from.x, region.y),
+                Point::new(from.x, region.y + region.height),
+            )],
+            Tool::Rectangle | Tool::Measure => {
+                let corners = [from, Point::new(to.x, from.y), to, Point::new(from.x, to.y)];
+                (0..4).map(|i| (corners[i], corners[(i + 1) % 4])).collect()
+            }
+            Tool::FibRetracement | Tool::FibExtension => {
+                let (left, right) = (from.x.min(to.x), from.x.max(to.x));
+
+                drawing
+                    .tool
+                    .fib_levels()
+                    .iter()
+                    .map(|level| {
+                        let y = self.price_to_y(drawing.fib_price(*level));
+                        (Point::new(left, y), Point::new(right, y))
+                    })
+                    .collect()
+            }
+            Tool::Note => vec![],
+        }
+    }
+
+    /// Topmost drawing passing within a few pixels of `position`, in canvas coordinates
+    fn drawing_at(&self, position: Point) -> Option<usize> {
+        if !self.basis.is_time() {
+            return None;
+        }
+        let region = self.visible_region(self.bounds.size());
+
+        self.drawings.iter().rposition(|drawing| {
+            self.drawing_segments(drawing, region)
+                .into_iter()
+                .any(|(from, to)| {
+                    let (from, to) = (self.to_canvas(from, region), self.to_canvas(to, region));
+                    distance_to_segment(position, from, to) <= DRAWING_HIT_PX
+                })
+        })
+    }
+
     fn layout(&self) -> ViewConfig {
         let layout = &self.layout;
         ViewConfig {
@@ -1101,7 +2097,7 @@ impl ViewState {
 
                 (rounded_timestamp, snap_ratio)
             }
-            Basis::Tick(aggregation) => {
+            Basis::Tick(_) | Basis::Bars(_) => {
                 let (chart_x_min, chart_x_max) = (region.x, region.x + region.width);
                 let chart_x = chart_x_min + x_ratio * (chart_x_max - chart_x_min);
 
@@ -1114,7 +2110,7 @@ impl ViewState {
                     0.5
                 };
 
-                let rounded_tick = (-cell_index as u64) * u64::from(aggregation.0);
+                let rounded_tick = (-cell_index as u64) * self.basis.index_step();
 
                 (rounded_tick, snap_ratio)
             }
@@ -1122,6 +2118,18 @@ impl ViewState {
     }
 }
 
+fn distance_to_segment(point: Point, from: Point, to: Point) -> f32 {
+    let segment = to - from;
+    let length_sq = segment.x * segment.x + segment.y * segment.y;
+    if length_sq <= f32::EPSILON {
+        return point.distance(from);
+    }
+
+    let offset = point - from;
+    let t = ((offset.x * segment.x + offset.y * segment.y) / length_sq).clamp(0.0, 1.0);
+    point.distance(from + segment * t)
+}
+
 fn request_fetch(handler: &mut RequestHandler, range: FetchRange) -> Option<Action> {
     match handler.add_request(range) {
         Ok(Some(req_id)) => {
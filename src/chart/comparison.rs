@@ -1,23 +1,34 @@
+use super::spread::SpreadSeries;
 use crate::connector::fetcher::{FetchRange, FetchSpec, RequestHandler};
 use crate::widget::chart::comparison::{DEFAULT_ZOOM_POINTS, LineComparison, LineComparisonEvent};
-use crate::widget::chart::{Series, Zoom, domain};
+use crate::widget::chart::{Series, SeriesLike, ValueScale, Zoom, domain};
 
 use data::chart::Basis;
-use data::chart::comparison::Config;
+use data::chart::comparison::{ComparisonMode, Config, DerivedSeries, Normalization, correlation};
+use data::chart::spread::SpreadMode;
 use exchange::adapter::StreamKind;
-use exchange::{Kline, SerTicker, TickerInfo, Timeframe, UnixMs};
+use exchange::{Kline, SerTicker, TickerInfo, Trade, UnixMs};
 
 use rustc_hash::FxHashMap;
+use std::borrow::Cow;
 use std::time::Instant;
 
-const SERIES_MAX_POINTS: usize = 5000;
+pub(super) const SERIES_MAX_POINTS: usize = 5000;
 const DEFAULT_PAN_POINTS: f32 = 8.0;
+/// Recent tick bars the typical bar length is estimated from
+const TICK_STEP_SAMPLE: usize = 100;
 
 pub enum Action {
     SeriesColorChanged(TickerInfo, iced::Color),
     SeriesNameChanged(TickerInfo, String),
     RemoveSeries(TickerInfo),
     OpenSeriesEditor,
+    PriceAxisChanged(Option<TickerInfo>),
+    AddDerived([TickerInfo; 2], SpreadMode),
+    DerivedColorChanged(usize, iced::Color),
+    RemoveDerived(usize),
+    CsvExported(std::io::Result<std::path::PathBuf>),
+    CopyLegendValues(String),
 }
 
 pub struct ComparisonChart {
@@ -25,13 +36,22 @@ pub struct ComparisonChart {
     pan: f32,
     last_tick: Instant,
     pub series: Vec<Series>,
+    /// Lines of `config.derived`, in the same order
+    derived: Vec<SpreadSeries>,
     series_index: FxHashMap<TickerInfo, usize>,
-    pub timeframe: Timeframe,
+    /// Klines of a timeframe, or bars of every `n` trades of each ticker keyed by the time of
+    /// their last trade
+    basis: Basis,
+    /// Trades in the latest tick bar of each ticker
+    tick_counts: FxHashMap<TickerInfo, u16>,
     request_handler: FxHashMap<TickerInfo, RequestHandler>,
     selected_tickers: Vec<TickerInfo>,
     pub config: data::chart::comparison::Config,
     pub series_editor: series_editor::TickerSeriesEditor,
     cache_rev: u64,
+    /// Rebase point picked on the chart, the left edge of the window when `None`
+    anchor: Option<u64>,
+    scale_factor: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -39,15 +59,11 @@ pub enum Message {
     Chart(LineComparisonEvent),
     Editor(series_editor::Message),
     OpenEditorFor(TickerInfo),
+    ExportCsv,
 }
 
 impl ComparisonChart {
     pub fn new(basis: Basis, tickers: &[TickerInfo], config: Option<Config>) -> Self {
-        let timeframe = match basis {
-            Basis::Time(tf) => tf,
-            Basis::Tick(_) => todo!("WIP: ComparisonChart does not support tick basis"),
-        };
-
         let cfg = config.unwrap_or_default();
 
         let color_map: FxHashMap<SerTicker, iced::Color> = cfg.colors.iter().cloned().collect();
@@ -69,12 +85,14 @@ impl ComparisonChart {
             series_index.insert(*t, i);
         }
 
-        Self {
+        let mut chart = Self {
             last_tick: Instant::now(),
             zoom: Zoom::points(DEFAULT_ZOOM_POINTS),
             series,
+            derived: vec![],
             series_index,
-            timeframe,
+            basis,
+            tick_counts: FxHashMap::default(),
             request_handler: tickers
                 .iter()
                 .map(|t| (*t, RequestHandler::default()))
@@ -84,7 +102,11 @@ impl ComparisonChart {
             config: cfg,
             series_editor: series_editor::TickerSeriesEditor::default(),
             cache_rev: 0,
-        }
+            anchor: None,
+            scale_factor: 1.0,
+        };
+        chart.rebuild_derived();
+        chart
     }
 
     pub fn update(&mut self, message: Message) -> Option<Action> {
@@ -104,14 +126,25 @@ impl ComparisonChart {
                 LineComparisonEvent::SeriesRemove(ticker_info) => {
                     Some(Action::RemoveSeries(ticker_info))
                 }
+                LineComparisonEvent::DerivedCog(index) => self.open_editor_for_derived(index),
+                LineComparisonEvent::DerivedRemove(index) => Some(Action::RemoveDerived(index)),
                 LineComparisonEvent::XAxisDoubleClick => {
                     self.zoom = Zoom::points(DEFAULT_ZOOM_POINTS);
                     self.pan = DEFAULT_PAN_POINTS;
+                    self.anchor = None;
+                    None
+                }
+                LineComparisonEvent::AnchorChanged(anchor) => {
+                    // Picking the same point again goes back to the left edge
+                    self.anchor = (self.anchor != Some(anchor)).then_some(anchor);
+                    self.cache_rev = self.cache_rev.wrapping_add(1);
                     None
                 }
+                LineComparisonEvent::LegendValues(values) => Some(Action::CopyLegendValues(values)),
             },
             Message::Editor(msg) => self.series_editor.update(msg),
             Message::OpenEditorFor(ticker_info) => self.open_editor_for_ticker(ticker_info),
+            Message::ExportCsv => Some(Action::CsvExported(self.export_csv())),
         }
     }
 
@@ -123,10 +156,23 @@ impl ComparisonChart {
             .into();
         }
 
-        let chart: iced::Element<_> = LineComparison::<Series>::new(&self.series, self.timeframe)
+        if self.config.mode == ComparisonMode::Correlation {
+            return self.correlation_view();
+        }
+
+        let chart: iced::Element<_> = LineComparison::<Series>::new(&self.series, self.step_ms())
+            .with_derived(&self.derived)
             .with_timezone(timezone)
             .with_zoom(self.zoom)
             .with_pan(self.pan)
+            .with_value_scale(match self.config.normalization {
+                Normalization::Percent => ValueScale::PercentChange,
+                Normalization::Log => ValueScale::LogChange,
+                Normalization::ZScore => ValueScale::ZScore,
+            })
+            .with_anchor(self.anchor)
+            .with_price_axis(self.price_axis())
+            .with_scale_factor(self.scale_factor)
             .version(self.cache_rev)
             .into();
 
@@ -135,13 +181,93 @@ impl ComparisonChart {
             .into()
     }
 
+    /// Correlation of the returns of every pair of series over the configured window,
+    /// as a grid colored from red (-1) to green (+1)
+    fn correlation_view(&self) -> iced::Element<'_, Message> {
+        use iced::widget::{column, container, row, scrollable, text};
+
+        const CELL_WIDTH: f32 = 76.0;
+        const CELL_HEIGHT: f32 = 28.0;
+
+        if self.series.len() < 2 {
+            return iced::widget::center(
+                text("Add a second ticker to correlate").size(crate::style::text_size::TITLE),
+            )
+            .into();
+        }
+
+        let window = usize::from(self.config.correlation_window);
+        let aligned = self
+            .series
+            .iter()
+            .map(|s| self.aligned_points(&s.points))
+            .collect::<Vec<_>>();
+
+        let cell = |content: iced::widget::Text<'static>| {
+            container(content.size(crate::style::text_size::BODY))
+                .center_x(CELL_WIDTH)
+                .center_y(CELL_HEIGHT)
+        };
+        let label = |series: &Series| {
+            let color = series.color;
+            cell(
+                text(series.name())
+                    .style(move |_| iced::widget::text::Style { color: Some(color) }),
+            )
+        };
+
+        let header = self.series.iter().fold(
+            row![cell(text(format!("{window} bars")))].spacing(2),
+            |header, series| header.push(label(series)),
+        );
+
+        let grid =
+            self.series
+                .iter()
+                .enumerate()
+                .fold(column![header].spacing(2), |grid, (i, a)| {
+                    let cells = self.series.iter().enumerate().fold(
+                        row![label(a)].spacing(2),
+                        |cells, (j, _)| {
+                            let value = if i == j {
+                                Some(1.0)
+                            } else {
+                                correlation(&aligned[i], &aligned[j], window)
+                            };
+                            let content =
+                                value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+
+                            cells.push(
+                                cell(text(content).font(crate::style::AZERET_MONO)).style(
+                                    move |theme| crate::style::correlation_cell(theme, value),
+                                ),
+                            )
+                        },
+                    );
+                    grid.push(cells)
+                });
+
+        iced::widget::container(
+            scrollable(grid)
+                .direction(scrollable::Direction::Both {
+                    vertical: scrollable::Scrollbar::default(),
+                    horizontal: scrollable::Scrollbar::default(),
+                })
+                .style(crate::style::scroll_bar),
+        )
+        .padding(8)
+        .into()
+    }
+
     pub fn insert_history(
         &mut self,
         req_id: uuid::Uuid,
         ticker_info: TickerInfo,
         klines: &[Kline],
     ) {
-        let timeframe = self.timeframe;
+        let Basis::Time(timeframe) = self.basis else {
+            return;
+        };
         let mut incoming: Vec<(u64, f32)> = klines
             .iter()
             .map(|k| (k.time.floor_to(timeframe).as_u64(), k.close.to_f32_lossy()))
@@ -203,10 +329,15 @@ impl ComparisonChart {
         if let Some(handler) = self.request_handler.get_mut(&ticker_info) {
             handler.mark_completed(req_id);
         }
+
+        self.rebuild_derived();
     }
 
     pub fn update_latest_kline(&mut self, ticker_info: &TickerInfo, kline: &Kline) {
-        let t = kline.time.floor_to(self.timeframe).as_u64();
+        let Basis::Time(timeframe) = self.basis else {
+            return;
+        };
+        let t = kline.time.floor_to(timeframe).as_u64();
 
         let idx = self.get_or_create_series_idx(ticker_info);
         let series = &mut self.series[idx];
@@ -229,6 +360,61 @@ impl ComparisonChart {
             let drop = series.points.len() - SERIES_MAX_POINTS;
             series.points.drain(0..drop);
         }
+
+        self.rebuild_derived();
+    }
+
+    /// Folds live trades into tick bars, there's no history to backfill them from
+    pub fn insert_trades(&mut self, ticker_info: &TickerInfo, buffer: &[Trade]) {
+        let Basis::Tick(tick_count) = self.basis else {
+            return;
+        };
+
+        let idx = self.get_or_create_series_idx(ticker_info);
+        let points = &mut self.series[idx].points;
+        let count = self.tick_counts.entry(*ticker_info).or_default();
+
+        for trade in buffer {
+            let (time, close) = (trade.time.as_u64(), trade.price.to_f32_lossy());
+
+            match points.last_mut() {
+                // Trades on the same millisecond as the last bar stay in it, keeping
+                // timestamps unique
+                Some(last) if *count < tick_count.0 || time <= last.0 => {
+                    *last = (time.max(last.0), close);
+                    *count = count.saturating_add(1);
+                }
+                _ => {
+                    points.push((time, close));
+                    *count = 1;
+                }
+            }
+        }
+
+        if points.len() > SERIES_MAX_POINTS {
+            let drop = points.len() - SERIES_MAX_POINTS;
+            points.drain(0..drop);
+        }
+
+        self.rebuild_derived();
+    }
+
+    /// Writes the closes of every series and the derived lines to a CSV, on the shared
+    /// grid the derived lines are built on
+    pub fn export_csv(&self) -> std::io::Result<std::path::PathBuf> {
+        let aligned = self
+            .series
+            .iter()
+            .map(|s| (s.name(), self.aligned_points(&s.points)))
+            .collect::<Vec<_>>();
+
+        let columns = aligned
+            .iter()
+            .map(|(name, points)| (name.clone(), points.as_ref()))
+            .chain(self.derived.iter().map(|d| (d.name(), d.points())))
+            .collect::<Vec<_>>();
+
+        data::chart::comparison::write_csv(&columns)
     }
 
     fn get_or_create_series_idx(&mut self, ticker_info: &TickerInfo) -> usize {
@@ -258,6 +444,7 @@ impl ComparisonChart {
 
         let _ = self.get_or_create_series_idx(ticker_info);
         self.rebuild_handlers();
+        self.rebuild_derived();
         self.streams_for_all()
     }
 
@@ -279,7 +466,10 @@ impl ComparisonChart {
             self.series_editor.show_config_for = None;
         }
 
+        // Lines built on the removed ticker go with it
+        self.series_editor.show_derived = None;
         self.rebuild_handlers();
+        self.rebuild_derived();
         self.streams_for_all()
     }
 
@@ -289,6 +479,9 @@ impl ComparisonChart {
         range: FetchRange,
         out: &mut Vec<(uuid::Uuid, FetchRange, Option<StreamKind>)>,
     ) {
+        let Basis::Time(timeframe) = self.basis else {
+            return;
+        };
         let handler = self.request_handler.entry(ticker).or_default();
         if let Ok(Some(req_id)) = handler.add_request(range) {
             out.push((
@@ -296,7 +489,7 @@ impl ComparisonChart {
                 range,
                 Some(StreamKind::Kline {
                     ticker_info: ticker,
-                    timeframe: self.timeframe,
+                    timeframe,
                 }),
             ));
         }
@@ -346,8 +539,10 @@ impl ComparisonChart {
 
     pub fn set_basis(&mut self, basis: data::chart::Basis) -> Option<super::Action> {
         match basis {
-            Basis::Time(tf) => {
-                self.timeframe = tf;
+            Basis::Time(_) | Basis::Tick(_) => {
+                self.basis = basis;
+                self.anchor = None;
+                self.tick_counts.clear();
 
                 let prev_colors: FxHashMap<TickerInfo, iced::Color> = self
                     .series
@@ -375,16 +570,21 @@ impl ComparisonChart {
                 }
 
                 self.rebuild_handlers();
+                self.rebuild_derived();
 
                 let reqs = self.collect_fetch_reqs(self.desired_fetch_batches(self.pan));
                 self.fetch_action(reqs)
             }
-            Basis::Tick(_) => unimplemented!(),
+            Basis::Bars(_) => {
+                log::warn!("Comparison charts can't be built from range bars, keeping the basis");
+                None
+            }
         }
     }
 
     fn open_editor_for_ticker(&mut self, ticker_info: TickerInfo) -> Option<Action> {
         self.series_editor.show_config_for = Some(ticker_info);
+        self.series_editor.show_derived = None;
 
         if let Some(idx) = self.series_index.get(&ticker_info) {
             self.series_editor.editing_color =
@@ -398,6 +598,86 @@ impl ComparisonChart {
         Some(Action::OpenSeriesEditor)
     }
 
+    fn open_editor_for_derived(&mut self, index: usize) -> Option<Action> {
+        let derived = self.derived.get(index)?;
+
+        self.series_editor.show_config_for = None;
+        self.series_editor.show_derived = Some(index);
+        self.series_editor.editing_color = Some(data::config::theme::to_hsva(derived.color()));
+        self.series_editor.editing_name = None;
+
+        Some(Action::OpenSeriesEditor)
+    }
+
+    /// Plots `a op b` alongside the series, unless the same line is already there
+    pub fn add_derived(&mut self, legs: [TickerInfo; 2], op: SpreadMode) {
+        let [a, b] = legs.map(|ti| SerTicker::from_parts(ti.ticker));
+        if a == b
+            || self
+                .config
+                .derived
+                .iter()
+                .any(|d| d.a == a && d.b == b && d.op == op)
+        {
+            return;
+        }
+
+        self.config.derived.push(DerivedSeries {
+            a,
+            b,
+            op,
+            color: derived_color_for(&legs),
+        });
+        self.rebuild_derived();
+        self.cache_rev = self.cache_rev.wrapping_add(1);
+    }
+
+    pub fn set_derived_color(&mut self, index: usize, color: iced::Color) {
+        if let Some(derived) = self.config.derived.get_mut(index) {
+            derived.color = color;
+            self.rebuild_derived();
+            self.cache_rev = self.cache_rev.wrapping_add(1);
+        }
+    }
+
+    pub fn remove_derived(&mut self, index: usize) {
+        if index < self.config.derived.len() {
+            self.config.derived.remove(index);
+            self.series_editor.show_derived = None;
+            self.rebuild_derived();
+            self.cache_rev = self.cache_rev.wrapping_add(1);
+        }
+    }
+
+    /// Recomputes the derived lines, dropping the ones a leg of which is no longer compared
+    fn rebuild_derived(&mut self) {
+        let leg = |ser: &SerTicker| {
+            self.series
+                .iter()
+                .find(|s| SerTicker::from_parts(s.ticker_info.ticker) == *ser)
+        };
+
+        self.config
+            .derived
+            .retain(|d| leg(&d.a).is_some() && leg(&d.b).is_some());
+
+        self.derived = self
+            .config
+            .derived
+            .iter()
+            .filter_map(|d| {
+                let (a, b) = (leg(&d.a)?, leg(&d.b)?);
+                Some(SpreadSeries::build(
+                    [a.ticker_info, b.ticker_info],
+                    d.op,
+                    &self.aligned_points(&a.points),
+                    &self.aligned_points(&b.points),
+                    d.color,
+                ))
+            })
+            .collect();
+    }
+
     fn clamp_label(name: &str) -> String {
         name.chars().take(24).collect()
     }
@@ -428,6 +708,12 @@ impl ComparisonChart {
         }
     }
 
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+        self.rebuild_derived();
+        self.cache_rev = self.cache_rev.wrapping_add(1);
+    }
+
     pub fn serializable_config(&self) -> data::chart::comparison::Config {
         let mut colors = vec![];
         let mut names = vec![];
@@ -440,7 +726,15 @@ impl ComparisonChart {
                 names.push((ser_ticker, name.clone()));
             }
         }
-        data::chart::comparison::Config { colors, names }
+        data::chart::comparison::Config {
+            colors,
+            names,
+            mode: self.config.mode,
+            normalization: self.config.normalization,
+            correlation_window: self.config.correlation_window,
+            derived: self.config.derived.clone(),
+            price_axis: self.config.price_axis.clone(),
+        }
     }
 
     fn color_for_or_default(&self, ticker_info: &TickerInfo) -> iced::Color {
@@ -452,10 +746,59 @@ impl ComparisonChart {
         }
     }
 
+    pub fn basis(&self) -> Basis {
+        self.basis
+    }
+
+    /// The series pinned to the price axis, if it's still compared
+    pub fn price_axis(&self) -> Option<TickerInfo> {
+        let pinned = self.config.price_axis.as_ref()?;
+        self.selected_tickers
+            .iter()
+            .find(|ti| SerTicker::from_parts(ti.ticker) == *pinned)
+            .copied()
+    }
+
+    pub fn set_price_axis(&mut self, ticker_info: Option<TickerInfo>) {
+        self.config.price_axis = ticker_info.map(|ti| SerTicker::from_parts(ti.ticker));
+        self.cache_rev = self.cache_rev.wrapping_add(1);
+    }
+
+    pub fn derived(&self) -> &[SpreadSeries] {
+        &self.derived
+    }
+
     pub fn selected_tickers(&self) -> &[TickerInfo] {
         &self.selected_tickers
     }
 
+    pub fn zoom(&self) -> Zoom {
+        self.zoom
+    }
+
+    pub fn pan(&self) -> f32 {
+        self.pan
+    }
+
+    pub fn cache_rev(&self) -> u64 {
+        self.cache_rev
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if (self.scale_factor - scale_factor).abs() >= f32::EPSILON {
+            self.scale_factor = scale_factor;
+            self.cache_rev = self.cache_rev.wrapping_add(1);
+        }
+    }
+
+    pub fn reset_request_handlers(&mut self) {
+        self.rebuild_handlers();
+    }
+
     fn rebuild_handlers(&mut self) {
         self.request_handler.clear();
 
@@ -464,12 +807,15 @@ impl ComparisonChart {
         }
     }
 
-    fn streams_for_all(&self) -> Vec<StreamKind> {
+    pub fn streams_for_all(&self) -> Vec<StreamKind> {
         let mut streams = Vec::with_capacity(self.selected_tickers.len());
         for &t in &self.selected_tickers {
-            streams.push(StreamKind::Kline {
-                ticker_info: t,
-                timeframe: self.timeframe,
+            streams.push(match self.basis {
+                Basis::Time(timeframe) => StreamKind::Kline {
+                    ticker_info: t,
+                    timeframe,
+                },
+                Basis::Tick(_) | Basis::Bars(_) => StreamKind::Trades { ticker_info: t },
             });
         }
         streams
@@ -484,16 +830,60 @@ impl ComparisonChart {
         }
     }
 
-    fn dt_ms_est(&self) -> u64 {
-        self.timeframe.to_milliseconds().max(1)
+    /// Spacing of the points, the timeframe or on tick basis the median length of the
+    /// recent bars of the slowest ticker, so every series has about a point per step
+    pub(super) fn step_ms(&self) -> u64 {
+        if let Basis::Time(timeframe) = self.basis {
+            return timeframe.to_milliseconds().max(1);
+        }
+
+        self.series
+            .iter()
+            .filter_map(|s| {
+                let recent = &s.points[s.points.len().saturating_sub(TICK_STEP_SAMPLE + 1)..];
+                let mut gaps = recent
+                    .windows(2)
+                    .map(|pair| pair[1].0.saturating_sub(pair[0].0))
+                    .collect::<Vec<_>>();
+                if gaps.is_empty() {
+                    return None;
+                }
+                let mid = gaps.len() / 2;
+                Some(*gaps.select_nth_unstable(mid).1)
+            })
+            .max()
+            .unwrap_or(1000)
+            .max(1)
+    }
+
+    /// Points on a grid shared by every series. Tick bars of different tickers close at
+    /// different times, so they're bucketed into steps for correlations and spreads to pair up
+    pub(super) fn aligned_points<'a>(&self, points: &'a [(u64, f32)]) -> Cow<'a, [(u64, f32)]> {
+        if self.basis.is_time() {
+            return Cow::Borrowed(points);
+        }
+
+        let step = self.step_ms();
+        let mut aligned: Vec<(u64, f32)> = Vec::with_capacity(points.len());
+        for &(x, y) in points {
+            let slot = domain::align_floor(x, step);
+            match aligned.last_mut() {
+                Some(last) if last.0 == slot => last.1 = y,
+                _ => aligned.push((slot, y)),
+            }
+        }
+        Cow::Owned(aligned)
     }
 
     fn align_floor(&self, ts: UnixMs) -> UnixMs {
-        ts.floor_to(self.timeframe)
+        match self.basis {
+            Basis::Time(timeframe) => ts.floor_to(timeframe),
+            Basis::Tick(_) | Basis::Bars(_) => ts,
+        }
     }
 
     fn compute_visible_window(&self, pan_points: f32) -> Option<(UnixMs, UnixMs)> {
-        let dt = self.dt_ms_est();
+        let dt = self.step_ms();
         let points: Vec<&[(u64, f32)]> = self.series.iter().map(|s| s.points.as_slice()).collect();
 
         domain::window(&points, self.zoom, pan_points, dt)
@@ -501,7 +891,12 @@ impl ComparisonChart {
     }
 
     fn desired_fetch_batches(&self, pan_points: f32) -> Vec<(FetchRange, Vec<TickerInfo>)> {
-        let dt = self.dt_ms_est();
+        // Tick bars are only ever built from live trades
+        if !self.basis.is_time() {
+            return vec![];
+        }
+
+        let dt = self.step_ms();
         let span = 500u64.saturating_mul(dt);
         let last_closed = self.align_floor(UnixMs::now());
 
@@ -543,11 +938,20 @@ impl ComparisonChart {
     }
 }
 
-fn default_color_for(ticker: &TickerInfo) -> iced::Color {
-    use std::hash::{DefaultHasher, Hash, Hasher};
+pub(super) fn default_color_for(ticker: &TickerInfo) -> iced::Color {
+    color_from_hash(ticker)
+}
+
+/// Hashed on both legs, so the line doesn't take the color of its first one
+fn derived_color_for(legs: &[TickerInfo; 2]) -> iced::Color {
+    color_from_hash(legs)
+}
+
+fn color_from_hash(value: &impl std::hash::Hash) -> iced::Color {
+    use std::hash::{DefaultHasher, Hasher};
 
     let mut hasher = DefaultHasher::new();
-    ticker.hash(&mut hasher);
+    value.hash(&mut hasher);
     let seed = hasher.finish();
 
     // Golden-angle distribution for hue (in degrees)
@@ -563,16 +967,28 @@ fn default_color_for(ticker: &TickerInfo) -> iced::Color {
 }
 
 pub mod series_editor {
-    use crate::style;
-    use crate::widget::chart::Series;
+    use crate::chart::spread::SpreadSeries;
+    use crate::style::{self, Icon, icon_text};
+    use crate::widget::chart::{Series, SeriesLike};
     use crate::widget::color_picker::color_picker;
+    use data::chart::spread::SpreadMode;
     use exchange::TickerInfo;
-    use iced::widget::{button, column, container, row, text};
+    use iced::widget::{button, checkbox, column, container, pick_list, row, text};
     use iced::{Element, Length};
     use palette::Hsva;
 
     const MAX_LABEL_CHARS: usize = 24;
 
+    /// A compared ticker as a pick list option
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Leg(TickerInfo);
+
+    impl std::fmt::Display for Leg {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0.ticker.display_symbol_and_type().0)
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub enum Message {
         ToggleEditFor {
@@ -580,15 +996,29 @@ pub mod series_editor {
             applied_color: iced::Color,
             applied_name: Option<String>,
         },
+        ToggleEditDerived {
+            index: usize,
+            applied_color: iced::Color,
+        },
         ColorChangedHsva(Hsva),
         NameChanged(String),
+        PriceAxisToggled(TickerInfo, bool),
+        /// Picks the first (`0`) or second (`1`) leg of the next derived series
+        DraftLegChanged(usize, TickerInfo),
+        DraftOpChanged(SpreadMode),
+        AddDerived,
+        RemoveDerived(usize),
     }
 
     #[derive(Default)]
     pub struct TickerSeriesEditor {
         pub show_config_for: Option<TickerInfo>,
+        /// Derived series the color picker is open for
+        pub show_derived: Option<usize>,
         pub editing_color: Option<Hsva>,
         pub editing_name: Option<String>,
+        draft_legs: [Option<TickerInfo>; 2],
+        draft_op: SpreadMode,
     }
 
     impl TickerSeriesEditor {
@@ -608,12 +1038,34 @@ pub mod series_editor {
                         return None;
                     }
                     self.show_config_for = Some(ticker);
+                    self.show_derived = None;
                     self.editing_color = Some(data::config::theme::to_hsva(applied_color));
                     self.editing_name = applied_name;
                     None
                 }
+                Message::ToggleEditDerived {
+                    index,
+                    applied_color,
+                } => {
+                    if self.show_derived == Some(index) {
+                        self.show_derived = None;
+                        self.editing_color = None;
+                        return None;
+                    }
+                    self.show_config_for = None;
+                    self.show_derived = Some(index);
+                    self.editing_color = Some(data::config::theme::to_hsva(applied_color));
+                    self.editing_name = None;
+                    None
+                }
                 Message::ColorChangedHsva(hsva) => {
                     self.editing_color = Some(hsva);
+                    if let Some(index) = self.show_derived {
+                        return Some(super::Action::DerivedColorChanged(
+                            index,
+                            data::config::theme::from_hsva(hsva),
+                        ));
+                    }
                     if let Some(t) = self.show_config_for {
                         return Some(super::Action::SeriesColorChanged(
                             t,
@@ -631,10 +1083,36 @@ pub mod series_editor {
                     }
                     None
                 }
+                Message::PriceAxisToggled(ticker_info, is_pinned) => Some(
+                    super::Action::PriceAxisChanged(is_pinned.then_some(ticker_info)),
+                ),
+                Message::DraftLegChanged(slot, ticker_info) => {
+                    if let Some(leg) = self.draft_legs.get_mut(slot) {
+                        *leg = Some(ticker_info);
+                    }
+                    None
+                }
+                Message::DraftOpChanged(op) => {
+                    self.draft_op = op;
+                    None
+                }
+                Message::AddDerived => {
+                    let [Some(a), Some(b)] = self.draft_legs else {
+                        return None;
+                    };
+                    self.draft_legs = [None, None];
+                    Some(super::Action::AddDerived([a, b], self.draft_op))
+                }
+                Message::RemoveDerived(index) => Some(super::Action::RemoveDerived(index)),
             }
         }
 
-        pub fn view<'a>(&'a self, series: &'a Vec<Series>) -> Element<'a, Message> {
+        pub fn view<'a>(
+            &'a self,
+            series: &'a Vec<Series>,
+            derived: &'a [SpreadSeries],
+            price_axis: Option<TickerInfo>,
+        ) -> Element<'a, Message> {
             let mut content = column![].spacing(6);
 
             for s in series {
@@ -682,12 +1160,114 @@ pub mod series_editor {
                             .width(Length::Fill),
                     );
 
+                    let ticker = s.ticker_info;
+                    inner_col = inner_col.push(
+                        checkbox(price_axis == Some(ticker))
+                            .label("Show its price on the axis")
+                            .on_toggle(move |is_pinned| {
+                                Message::PriceAxisToggled(ticker, is_pinned)
+                            }),
+                    );
+
                     col = col.push(inner_col.spacing(12).padding(4)).spacing(4);
                 }
 
                 content = content.push(container(col).style(style::modal_container));
             }
 
+            if series.len() >= 2 {
+                content = content.push(self.derived_view(series, derived));
+            }
+
+            content.into()
+        }
+
+        /// The derived lines with their colors, and a row to put a new one together
+        fn derived_view<'a>(
+            &'a self,
+            series: &'a [Series],
+            derived: &'a [SpreadSeries],
+        ) -> Element<'a, Message> {
+            let mut content = column![
+                text("Derived").size(crate::style::text_size::SECTION),
+                text("Differences can cross zero, the Z-score scale reads them best")
+                    .size(crate::style::text_size::SMALL),
+            ]
+            .spacing(6)
+            .padding([8, 0]);
+
+            for (index, d) in derived.iter().enumerate() {
+                let applied = d.color();
+                let is_open = self.show_derived == Some(index);
+
+                let header = row![
+                    button(
+                        row![
+                            container("").width(14).height(14).style(move |theme| {
+                                style::colored_circle_container(theme, applied)
+                            }),
+                            text(d.legend_label()).size(crate::style::text_size::EMPHASIS),
+                        ]
+                        .spacing(8)
+                        .align_y(iced::Alignment::Center),
+                    )
+                    .on_press(Message::ToggleEditDerived {
+                        index,
+                        applied_color: applied,
+                    })
+                    .style(move |theme, status| style::button::transparent(theme, status, is_open))
+                    .width(Length::Fill),
+                    button(icon_text(Icon::TrashBin, 12))
+                        .style(|theme, status| style::button::transparent(theme, status, false))
+                        .on_press(Message::RemoveDerived(index)),
+                ]
+                .align_y(iced::Alignment::Center);
+
+                let mut col = column![header].padding(4);
+
+                if is_open {
+                    let hsva_in = self
+                        .editing_color
+                        .unwrap_or_else(|| data::config::theme::to_hsva(applied));
+                    col = col
+                        .push(column![color_picker(hsva_in, Message::ColorChangedHsva)].padding(4))
+                        .spacing(4);
+                }
+
+                content = content.push(container(col).style(style::modal_container));
+            }
+
+            let legs = series
+                .iter()
+                .map(|s| Leg(s.ticker_info))
+                .collect::<Vec<_>>();
+            let leg_picker = |slot: usize| {
+                pick_list(
+                    legs.clone(),
+                    self.draft_legs[slot].map(Leg),
+                    move |leg: Leg| Message::DraftLegChanged(slot, leg.0),
+                )
+                .placeholder("Ticker")
+                .width(Length::Fill)
+            };
+
+            let can_add = matches!(self.draft_legs, [Some(a), Some(b)] if a != b);
+
+            content = content.push(
+                row![
+                    leg_picker(0),
+                    pick_list(
+                        SpreadMode::ALL,
+                        Some(self.draft_op),
+                        Message::DraftOpChanged
+                    ),
+                    leg_picker(1),
+                    button(text("Add")).on_press_maybe(can_add.then_some(Message::AddDerived)),
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center),
+            );
+
             content.into()
         }
 
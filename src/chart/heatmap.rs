@@ -1,15 +1,17 @@
 use super::{Chart, Interaction, Message, PlotConstants, ViewState, scale::linear::PriceInfoLabel};
 use crate::{
+    connector::fetcher::{FetchRange, FetchSpec},
     modal::pane::settings::study::{self, Study},
     style,
 };
 use data::chart::{
     Basis, ViewConfig,
     heatmap::{
-        CLEANUP_THRESHOLD, Config, HeatmapDataPoint, HeatmapStudy, HistoricalDepth, ProfileKind,
-        QtyScale,
+        CLEANUP_THRESHOLD, Config, DevelopingValueArea, HeatmapDataPoint, HeatmapStudy,
+        HistoricalDepth, HistoryLimit, HistoryUsage, ProfileKind, QtyScale, ValueArea,
     },
     indicator::HeatmapIndicator,
+    liquidation::LiquidationMap,
 };
 use data::util::abbr_large_numbers;
 use data::{
@@ -17,13 +19,14 @@ use data::{
     chart::Autoscale,
 };
 use exchange::{
-    SizeUnit, TickerInfo, Trade, UnixMs,
+    OpenInterest, SizeUnit, TickerInfo, Timeframe, Trade, UnixMs,
+    adapter::StreamKind,
     depth::Depth,
     unit::qty::volume_size_unit,
-    unit::{Price, PriceStep},
+    unit::{Price, PriceStep, Qty},
 };
 
-use iced::widget::canvas::{self, Event, Geometry, Path};
+use iced::widget::canvas::{self, Event, Geometry, Path, Stroke};
 use iced::{
     Alignment, Color, Element, Point, Rectangle, Renderer, Size, Theme, Vector, mouse,
     theme::palette::Extended,
@@ -31,7 +34,7 @@ use iced::{
 
 use enum_map::EnumMap;
 use rustc_hash::FxHashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 const MIN_SCALING: f32 = 0.6;
 const MAX_SCALING: f32 = 1.2;
@@ -53,6 +56,22 @@ const MAX_CIRCLE_RADIUS: f32 = 16.0;
 const CURRENT_DEPTH_AREA_WIDTH_PX: f32 = 160.0;
 const CURRENT_DEPTH_AREA_RIGHT_PAD_PX: f32 = 8.0;
 const CURRENT_DEPTH_LABEL_TOP_PAD_PX: f32 = 6.0;
+const TRADED_PROFILE_GAP_PX: f32 = 4.0;
+/// Depth rows thinner than this get merged with their neighbours
+const MIN_DEPTH_ROW_PX: f32 = 1.5;
+const MAX_ROWS_PER_LEVEL: i64 = 64;
+
+const LEGEND_PAD_PX: f32 = 6.0;
+const LEGEND_GAP_PX: f32 = 8.0;
+const LEGEND_SHADE_BAR_WIDTH_PX: f32 = 120.0;
+const LEGEND_SHADE_BAR_HEIGHT_PX: f32 = 6.0;
+const LEGEND_SHADE_STEPS: usize = 12;
+/// Trade circles shown at these fractions of the largest visible trade
+const LEGEND_TRADE_FRACTIONS: [f64; 3] = [0.25, 0.5, 1.0];
+
+/// Open interest readings the liquidation levels are estimated from
+const LIQUIDATION_OI_TIMEFRAME: Timeframe = Timeframe::M5;
+const LIQUIDATION_OI_POLL: Duration = Duration::from_secs(300);
 
 impl Chart for HeatmapChart {
     type IndicatorKind = HeatmapIndicator;
@@ -110,6 +129,10 @@ impl Chart for HeatmapChart {
     fn is_empty(&self) -> bool {
         self.heatmap.is_empty()
     }
+
+    fn picks_columns(&self) -> bool {
+        true
+    }
 }
 
 impl PlotConstants for HeatmapChart {
@@ -158,6 +181,14 @@ pub struct HeatmapChart {
     study_configurator: study::Configurator<HeatmapStudy>,
     last_tick: Instant,
     pub studies: Vec<HeatmapStudy>,
+    value_area: Option<DevelopingValueArea>,
+    liquidations: Option<LiquidationMap>,
+    /// When open interest was last requested for the liquidation levels
+    oi_requested: Option<Instant>,
+    /// Column whose book is shown in the side profile instead of the latest one
+    snapshot_at: Option<UnixMs>,
+    /// Trades of at least this size get ringed, for a tape in the same link group
+    tape_marks: Option<f32>,
 }
 
 impl HeatmapChart {
@@ -200,6 +231,17 @@ impl HeatmapChart {
             trades: TimeSeries::<HeatmapDataPoint>::new(basis, step),
             visual_config: config.unwrap_or_default(),
             study_configurator: study::Configurator::new(),
+            value_area: studies
+                .iter()
+                .find_map(HeatmapStudy::value_area_pct)
+                .map(DevelopingValueArea::new),
+            liquidations: studies
+                .iter()
+                .find_map(HeatmapStudy::max_leverage)
+                .map(LiquidationMap::new),
+            oi_requested: None,
+            snapshot_at: None,
+            tape_marks: None,
             studies,
             last_tick: Instant::now(),
         }
@@ -214,6 +256,73 @@ impl HeatmapChart {
         for trade in buffer {
             entry.add_trade(trade, tick_size);
         }
+
+        DevelopingValueArea::sync(&mut self.value_area, &self.studies, &self.trades.datapoints);
+        if let Some(value_area) = &mut self.value_area {
+            value_area.update(&self.trades.datapoints);
+        }
+
+        if let Some(liquidations) = &mut self.liquidations
+            && let Some(low) = buffer.iter().map(|trade| trade.price).min()
+            && let Some(high) = buffer.iter().map(|trade| trade.price).max()
+        {
+            liquidations.sweep(low, high);
+        }
+    }
+
+    pub fn insert_open_interest(&mut self, oi: &[OpenInterest]) {
+        if let Some(liquidations) = &mut self.liquidations {
+            liquidations.insert_open_interest(oi, &self.trades.datapoints, self.chart.tick_size);
+        }
+        self.invalidate(None);
+    }
+
+    /// Open interest still to be fetched for the liquidation levels, polled while the study
+    /// is on since the readings only come over REST
+    fn missing_open_interest(&mut self, now: Instant) -> Option<super::Action> {
+        let liquidations = self.liquidations.as_ref()?;
+        let ticker_info = self.chart.ticker_info;
+
+        if !ticker_info.exchange().has_open_interest()
+            || self
+                .oi_requested
+                .is_some_and(|at| now.duration_since(at) < LIQUIDATION_OI_POLL)
+        {
+            return None;
+        }
+
+        // One reading before the first trades, the change after it is priced by them
+        let from = match liquidations.last_oi_time() {
+            Some(time) => time,
+            None => {
+                let first = *self.trades.datapoints.keys().next()?;
+                UnixMs::new(
+                    first
+                        .as_u64()
+                        .saturating_sub(LIQUIDATION_OI_TIMEFRAME.to_milliseconds()),
+                )
+            }
+        };
+        self.oi_requested = Some(now);
+
+        Some(super::Action::RequestFetch(vec![FetchSpec {
+            req_id: uuid::Uuid::new_v4(),
+            fetch: FetchRange::OpenInterest(from, UnixMs::now()),
+            stream: Some(StreamKind::Kline {
+                ticker_info,
+                timeframe: LIQUIDATION_OI_TIMEFRAME,
+            }),
+        }]))
+    }
+
+    /// Starts the liquidation levels over, to be estimated again from fresh readings
+    fn reset_liquidations(&mut self) {
+        self.liquidations = self
+            .studies
+            .iter()
+            .find_map(HeatmapStudy::max_leverage)
+            .map(LiquidationMap::new);
+        self.oi_requested = None;
     }
 
     pub fn insert_depth(&mut self, depth: &Depth, update_t: UnixMs) {
@@ -259,7 +368,18 @@ impl HeatmapChart {
     }
 
     fn cleanup_old_data(&mut self) {
-        if self.trades.datapoints.len() > CLEANUP_THRESHOLD {
+        if let Some(cutoff) = self.heatmap.enforce_limit(self.visual_config.history) {
+            self.trades.datapoints = self.trades.datapoints.split_off(&cutoff);
+
+            if let Some(value_area) = &mut self.value_area {
+                value_area.prune_before(cutoff);
+            }
+            return;
+        }
+
+        if self.visual_config.history == HistoryLimit::Auto
+            && self.trades.datapoints.len() > CLEANUP_THRESHOLD
+        {
             let keys_to_remove = self
                 .trades
                 .datapoints
@@ -274,6 +394,10 @@ impl HeatmapChart {
 
             if let Some(oldest_time) = self.trades.datapoints.keys().next().copied() {
                 self.heatmap.cleanup_old_price_levels(oldest_time);
+
+                if let Some(value_area) = &mut self.value_area {
+                    value_area.prune_before(oldest_time);
+                }
             }
         }
     }
@@ -281,7 +405,7 @@ impl HeatmapChart {
     fn round_to_basis_time(&self, update_t: UnixMs) -> UnixMs {
         match self.chart.basis {
             Basis::Time(interval) => update_t.floor_to(interval),
-            Basis::Tick(_) => update_t,
+            Basis::Tick(_) | Basis::Bars(_) => update_t,
         }
     }
 
@@ -289,17 +413,37 @@ impl HeatmapChart {
         self.visual_config
     }
 
+    pub fn history_usage(&self) -> HistoryUsage {
+        self.heatmap.usage()
+    }
+
+    pub fn export_depth(&self) -> std::io::Result<std::path::PathBuf> {
+        self.visual_config
+            .export
+            .write_csv(&self.heatmap, &self.chart.ticker_info)
+    }
+
     pub fn set_visual_config(&mut self, visual_config: Config) {
         self.visual_config = visual_config;
         self.invalidate(Some(Instant::now()));
     }
 
+    pub fn set_tape_marks(&mut self, min_size: Option<f32>) {
+        if self.tape_marks != min_size {
+            self.tape_marks = min_size;
+            self.invalidate(None);
+        }
+    }
+
     pub fn set_basis(&mut self, basis: Basis) {
         self.chart.basis = basis;
 
         self.trades.datapoints.clear();
         self.heatmap =
             HistoricalDepth::new(self.chart.ticker_info.min_qty, self.chart.tick_size, basis);
+        self.value_area = None;
+        self.reset_liquidations();
+        self.snapshot_at = None;
 
         let chart = &mut self.chart;
         chart.translation = Vector::new(
@@ -336,13 +480,17 @@ impl HeatmapChart {
             None => {}
         }
 
+        DevelopingValueArea::sync(&mut self.value_area, &self.studies, &self.trades.datapoints);
+        if LiquidationMap::sync(&mut self.liquidations, &self.studies) {
+            self.oi_requested = None;
+        }
         self.invalidate(None);
     }
 
     pub fn basis_interval(&self) -> Option<u64> {
         match self.chart.basis {
             Basis::Time(interval) => Some(interval.to_milliseconds()),
-            Basis::Tick(_) => None,
+            Basis::Tick(_) | Basis::Bars(_) => None,
         }
     }
 
@@ -361,6 +509,26 @@ impl HeatmapChart {
 
         self.trades.datapoints.clear();
         self.heatmap = HistoricalDepth::new(self.chart.ticker_info.min_qty, step, basis);
+        self.value_area = None;
+        self.reset_liquidations();
+        self.snapshot_at = None;
+    }
+
+    /// Freezes the side profile on the book at the clicked column, clicking it again or
+    /// past the latest data goes back to the live book
+    pub fn toggle_snapshot(&mut self, interval: u64) {
+        let time = self.round_to_basis_time(UnixMs::new(interval));
+        let is_live = self
+            .trades
+            .latest_timestamp()
+            .is_none_or(|latest| time >= latest);
+
+        self.snapshot_at = if is_live || self.snapshot_at == Some(time) {
+            None
+        } else {
+            Some(time)
+        };
+        self.invalidate(None);
     }
 
     pub fn tick_size(&self) -> PriceStep {
@@ -392,6 +560,7 @@ impl HeatmapChart {
 
         if let Some(t) = now {
             self.last_tick = t;
+            return self.missing_open_interest(t);
         }
 
         None
@@ -490,7 +659,47 @@ impl canvas::Program<Message> for HeatmapChart {
 
             let volume_indicator = self.indicators[HeatmapIndicator::Volume].is_some();
 
-            if let Some(merge_strat) = self.visual_config().coalescing {
+            let rows_per_level = rows_per_level(chart);
+
+            if rows_per_level > 1 {
+                let merged_runs = self.heatmap.rebucketed_runs(
+                    UnixMs::new(earliest),
+                    UnixMs::new(latest),
+                    highest,
+                    lowest,
+                    rows_per_level,
+                    market_type,
+                    self.visual_config.order_size_filter,
+                );
+                let max_merged_qty = merged_runs
+                    .iter()
+                    .map(|(_, run)| run.qty.to_f64())
+                    .fold(0.0, f64::max);
+                let level_height = cell_height * rows_per_level as f32;
+
+                for (price, run) in merged_runs {
+                    let top = price.add_steps(rows_per_level - 1, chart.tick_size);
+                    let y_top = chart.price_to_y(top) - (cell_height / 2.0);
+
+                    let start_x =
+                        chart.interval_to_x(run.start_time.max(UnixMs::new(earliest)).as_u64());
+                    let end_x = chart
+                        .interval_to_x(run.until_time.min(UnixMs::new(latest)).as_u64())
+                        .min(0.0);
+
+                    let width = end_x - start_x;
+
+                    if width > 0.001 {
+                        let color_alpha = (run.qty.to_f64() / max_merged_qty).min(1.0) as f32;
+
+                        frame.fill_rectangle(
+                            Point::new(start_x, y_top),
+                            Size::new(width, level_height),
+                            depth_color(palette, run.is_bid, color_alpha),
+                        );
+                    }
+                }
+            } else if let Some(merge_strat) = self.visual_config().coalescing {
                 let coalesced_visual_runs = self.heatmap.coalesced_runs(
                     UnixMs::new(earliest),
                     UnixMs::new(latest),
@@ -576,29 +785,65 @@ impl canvas::Program<Message> for HeatmapChart {
                         desired_depth_area_width
                     };
 
-                let max_qty = self
-                    .heatmap
-                    .latest_order_runs(highest, lowest, latest_timestamp)
+                // Traded volume takes the outer half of the area when it's shown
+                let (current_depth_area_width, traded_area_width) =
+                    if self.visual_config.traded_profile {
+                        let gap = TRADED_PROFILE_GAP_PX / chart.scaling;
+                        let half = ((current_depth_area_width - gap) / 2.0).max(0.0);
+                        (half, half)
+                    } else {
+                        (current_depth_area_width, 0.0)
+                    };
+
+                if traded_area_width > 0.0 {
+                    draw_traded_profile(
+                        frame,
+                        chart,
+                        &self.trades,
+                        (earliest, latest),
+                        (highest, lowest),
+                        current_depth_area_width + TRADED_PROFILE_GAP_PX / chart.scaling,
+                        traded_area_width,
+                        region.y,
+                        palette,
+                    );
+                }
+
+                let runs = match self.snapshot_at {
+                    Some(time) => self
+                        .heatmap
+                        .order_runs_at(highest, lowest, time)
+                        .collect::<Vec<_>>(),
+                    None => self
+                        .heatmap
+                        .latest_order_runs(highest, lowest, latest_timestamp)
+                        .collect::<Vec<_>>(),
+                };
+
+                let max_qty = runs
+                    .iter()
                     .map(|(_, run)| run.qty.to_f32_lossy())
                     .fold(f32::MIN, f32::max)
                     .ceil()
                     * 5.0
                     / 5.0;
 
+                if let Some(time) = self.snapshot_at {
+                    draw_snapshot_marker(frame, chart, &region, time, palette);
+                }
+
                 if max_qty.is_finite() && max_qty > 0.0 && current_depth_area_width > 0.0 {
-                    self.heatmap
-                        .latest_order_runs(highest, lowest, latest_timestamp)
-                        .for_each(|(price, run)| {
-                            let y_position = chart.price_to_y(*price);
-                            let bar_width =
-                                (run.qty.to_f32_lossy() / max_qty) * current_depth_area_width;
-
-                            frame.fill_rectangle(
-                                Point::new(0.0, y_position - (cell_height / 2.0)),
-                                Size::new(bar_width, cell_height),
-                                depth_color(palette, run.is_bid, 0.5),
-                            );
-                        });
+                    runs.iter().for_each(|(price, run)| {
+                        let y_position = chart.price_to_y(**price);
+                        let bar_width =
+                            (run.qty.to_f32_lossy() / max_qty) * current_depth_area_width;
+
+                        frame.fill_rectangle(
+                            Point::new(0.0, y_position - (cell_height / 2.0)),
+                            Size::new(bar_width, cell_height),
+                            depth_color(palette, run.is_bid, 0.5),
+                        );
+                    });
 
                     // max bid/ask quantity text
                     let text_size = crate::style::text_size::TINY / chart.scaling;
@@ -638,6 +883,19 @@ impl canvas::Program<Message> for HeatmapChart {
                             size_in_quote_ccy,
                         );
 
+                        let radius = {
+                            if let Some(trade_size_scale) = self.visual_config.trade_size_scale {
+                                let scale_factor = (trade_size_scale as f64) / 100.0;
+                                (1.0_f64
+                                    + (trade_qty / max_trade_qty)
+                                        * f64::from(MAX_CIRCLE_RADIUS - 1.0)
+                                        * scale_factor) as f32
+                            } else {
+                                cell_height / 2.0
+                            }
+                        };
+                        let center = Point::new(x_position, y_position);
+
                         if trade_size > f64::from(self.visual_config.trade_size_filter) {
                             let color = if trade.is_sell {
                                 palette.danger.base.color
@@ -645,23 +903,22 @@ impl canvas::Program<Message> for HeatmapChart {
                                 palette.success.base.color
                             };
 
-                            let radius = {
-                                if let Some(trade_size_scale) = self.visual_config.trade_size_scale
-                                {
-                                    let scale_factor = (trade_size_scale as f64) / 100.0;
-                                    (1.0_f64
-                                        + (trade_qty / max_trade_qty)
-                                            * f64::from(MAX_CIRCLE_RADIUS - 1.0)
-                                            * scale_factor)
-                                        as f32
-                                } else {
-                                    cell_height / 2.0
-                                }
-                            };
+                            frame.fill(&Path::circle(center, radius), color);
+                        }
 
-                            frame.fill(
-                                &Path::circle(Point::new(x_position, y_position), radius),
-                                color,
+                        if self
+                            .tape_marks
+                            .is_some_and(|min_size| trade_size >= f64::from(min_size))
+                        {
+                            frame.stroke(
+                                &Path::circle(center, radius + 2.0 / chart.scaling),
+                                Stroke::with_color(
+                                    Stroke {
+                                        width: 1.5 / chart.scaling,
+                                        ..Stroke::default()
+                                    },
+                                    palette.warning.base.color,
+                                ),
                             );
                         }
                     });
@@ -709,13 +966,27 @@ impl canvas::Program<Message> for HeatmapChart {
                 });
             }
 
-            let volume_profile: Option<&ProfileKind> = self
-                .studies
-                .iter()
-                .map(|study| match study {
-                    HeatmapStudy::VolumeProfile(profile) => profile,
-                })
-                .next();
+            if let Some(value_area) = &self.value_area {
+                draw_developing_value_area(frame, chart, value_area, earliest, latest, palette);
+            }
+
+            if let Some(liquidations) = &self.liquidations {
+                draw_liquidation_levels(frame, chart, liquidations, &region, palette);
+            }
+
+            chart.draw_alert_levels(frame, palette, region);
+            chart.draw_chart_levels(frame, palette, region);
+            chart.draw_drawings(frame, palette, region, |drawing| {
+                let (earliest, latest, low, high) = drawing.bounds();
+
+                self.trades
+                    .datapoints
+                    .range(earliest..=latest)
+                    .fold(Qty::ZERO, |acc, (_, dp)| acc + dp.volume_within(low, high))
+            });
+
+            let volume_profile: Option<&ProfileKind> =
+                self.studies.iter().find_map(HeatmapStudy::volume_profile);
 
             if let Some(profile_kind) = volume_profile {
                 let area_width = (bounds.width / chart.scaling) * 0.1;
@@ -747,6 +1018,25 @@ impl canvas::Program<Message> for HeatmapChart {
                 );
             }
 
+            if self.visual_config.show_legend {
+                let left_offset = if volume_profile.is_some() {
+                    (bounds.width / chart.scaling) * 0.1
+                } else {
+                    0.0
+                };
+
+                draw_legend(
+                    frame,
+                    chart,
+                    &region,
+                    left_offset,
+                    &self.visual_config,
+                    max_trade_qty,
+                    max_depth_qty,
+                    palette,
+                );
+            }
+
             let is_paused = chart.translation.x * chart.scaling > chart.bounds.width / 2.0;
             if is_paused {
                 let bar_width = 8.0 / chart.scaling;
@@ -778,6 +1068,10 @@ impl canvas::Program<Message> for HeatmapChart {
 
         if !self.is_empty() {
             let crosshair = chart.cache.crosshair.draw(renderer, bounds_size, |frame| {
+                if cursor.position_in(bounds).is_none() {
+                    chart.draw_linked_crosshair(frame, theme, bounds_size);
+                }
+
                 if let Some(cursor_position) = cursor.position_in(bounds) {
                     let (cursor_at_price, cursor_at_time) = chart.draw_crosshair(
                         frame,
@@ -795,7 +1089,7 @@ impl canvas::Program<Message> for HeatmapChart {
 
                     let interval = match chart.basis {
                         Basis::Time(interval) => interval,
-                        Basis::Tick(_) => return,
+                        Basis::Tick(_) | Basis::Bars(_) => return,
                     };
                     let step = chart.tick_size;
 
@@ -915,7 +1209,10 @@ impl canvas::Program<Message> for HeatmapChart {
         match interaction {
             Interaction::Panning { .. } => mouse::Interaction::Grabbing,
             Interaction::Zoomin { .. } => mouse::Interaction::ZoomIn,
-            Interaction::None | Interaction::Ruler { .. } => {
+            Interaction::None
+            | Interaction::Ruler { .. }
+            | Interaction::RangeSelect { .. }
+            | Interaction::Drawing { .. } => {
                 if cursor.is_over(bounds) {
                     return mouse::Interaction::Crosshair;
                 }
@@ -933,6 +1230,300 @@ fn depth_color(palette: &Extended, is_bid: bool, alpha: f32) -> Color {
     }
 }
 
+/// Legend of the current normalization, drawn in the top-left corner of the visible region.
+///
+/// Trade circles are drawn in chart units so they match the plotted ones at any zoom, the
+/// rest is sized in screen pixels.
+/// How many price steps each drawn depth level spans, doubling while the rows would be
+/// thinner than a few pixels on screen
+fn rows_per_level(chart: &ViewState) -> i64 {
+    let row_px = chart.cell_height * chart.scaling;
+
+    let mut rows = 1;
+    while row_px * (rows as f32) < MIN_DEPTH_ROW_PX && rows < MAX_ROWS_PER_LEVEL {
+        rows *= 2;
+    }
+    rows
+}
+
+fn draw_legend(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    region: &Rectangle,
+    left_offset: f32,
+    config: &Config,
+    max_trade_qty: f64,
+    max_depth_qty: f64,
+    palette: &Extended,
+) {
+    let px = |value: f32| value / chart.scaling;
+    let text_size = crate::style::text_size::TINY;
+    let text_color = palette.background.base.text.scale_alpha(0.85);
+
+    let label = |frame: &mut canvas::Frame,
+                 content: String,
+                 position: Point,
+                 align_x: iced::widget::text::Alignment| {
+        frame.fill_text(canvas::Text {
+            content,
+            position,
+            size: iced::Pixels(px(text_size)),
+            color: text_color,
+            font: style::AZERET_MONO,
+            align_x,
+            align_y: Alignment::Start.into(),
+            ..canvas::Text::default()
+        });
+    };
+
+    let trade_radii: Option<Vec<(f64, f32)>> = config
+        .trade_size_scale
+        .filter(|_| max_trade_qty > 0.0)
+        .map(|trade_size_scale| {
+            let scale_factor = f64::from(trade_size_scale) / 100.0;
+            LEGEND_TRADE_FRACTIONS
+                .iter()
+                .map(|fraction| {
+                    let radius =
+                        (1.0 + fraction * f64::from(MAX_CIRCLE_RADIUS - 1.0) * scale_factor) as f32;
+                    (max_trade_qty * fraction, radius)
+                })
+                .collect()
+        });
+    let has_depth = max_depth_qty > 0.0;
+
+    if trade_radii.is_none() && !has_depth {
+        return;
+    }
+
+    // Circle radii are in chart units, everything else is laid out in screen pixels
+    let max_radius_px = trade_radii
+        .as_ref()
+        .and_then(|radii| radii.last())
+        .map_or(0.0, |(_, radius)| radius * chart.scaling);
+    let label_width_px = text_size * 5.0;
+
+    let trades_width_px = trade_radii.as_ref().map_or(0.0, |radii| {
+        radii
+            .iter()
+            .map(|(_, radius)| (radius * chart.scaling * 2.0).max(label_width_px))
+            .sum::<f32>()
+            + LEGEND_GAP_PX * (radii.len() - 1) as f32
+    });
+    let trades_height_px = if trade_radii.is_some() {
+        max_radius_px * 2.0 + text_size + 4.0
+    } else {
+        0.0
+    };
+    let depth_height_px = if has_depth {
+        LEGEND_SHADE_BAR_HEIGHT_PX * 2.0 + text_size + 4.0
+    } else {
+        0.0
+    };
+
+    let width_px = trades_width_px.max(if has_depth {
+        LEGEND_SHADE_BAR_WIDTH_PX
+    } else {
+        0.0
+    }) + LEGEND_PAD_PX * 2.0;
+    let height_px = trades_height_px
+        + depth_height_px
+        + if trade_radii.is_some() && has_depth {
+            LEGEND_GAP_PX
+        } else {
+            0.0
+        }
+        + LEGEND_PAD_PX * 2.0;
+
+    let origin = Point::new(
+        region.x + left_offset + px(LEGEND_PAD_PX),
+        region.y + px(LEGEND_PAD_PX),
+    );
+
+    frame.fill_rectangle(
+        origin,
+        Size::new(px(width_px), px(height_px)),
+        palette.background.weakest.color.scale_alpha(0.8),
+    );
+
+    let mut y_px = LEGEND_PAD_PX;
+
+    if let Some(radii) = &trade_radii {
+        let mut x_px = LEGEND_PAD_PX;
+
+        for (qty, radius) in radii {
+            let column_width_px = (radius * chart.scaling * 2.0).max(label_width_px);
+            let center_x = origin.x + px(x_px + column_width_px / 2.0);
+
+            frame.fill(
+                &Path::circle(
+                    Point::new(center_x, origin.y + px(y_px + max_radius_px)),
+                    *radius,
+                ),
+                palette.background.base.text.scale_alpha(0.5),
+            );
+            label(
+                frame,
+                abbr_large_numbers(*qty),
+                Point::new(center_x, origin.y + px(y_px + max_radius_px * 2.0 + 2.0)),
+                Alignment::Center.into(),
+            );
+
+            x_px += column_width_px + LEGEND_GAP_PX;
+        }
+
+        y_px += trades_height_px + LEGEND_GAP_PX;
+    }
+
+    if has_depth {
+        let step_width_px = LEGEND_SHADE_BAR_WIDTH_PX / LEGEND_SHADE_STEPS as f32;
+
+        for step in 0..LEGEND_SHADE_STEPS {
+            let alpha = (step + 1) as f32 / LEGEND_SHADE_STEPS as f32;
+            let x = origin.x + px(LEGEND_PAD_PX + step as f32 * step_width_px);
+
+            for (row, is_bid) in [(0.0, true), (1.0, false)] {
+                frame.fill_rectangle(
+                    Point::new(x, origin.y + px(y_px + row * LEGEND_SHADE_BAR_HEIGHT_PX)),
+                    Size::new(px(step_width_px), px(LEGEND_SHADE_BAR_HEIGHT_PX)),
+                    depth_color(palette, is_bid, alpha),
+                );
+            }
+        }
+
+        let labels_y = origin.y + px(y_px + LEGEND_SHADE_BAR_HEIGHT_PX * 2.0 + 2.0);
+        let min_label = if config.order_size_filter > 0.0 {
+            format!(
+                ">${}",
+                abbr_large_numbers(f64::from(config.order_size_filter))
+            )
+        } else {
+            "0".to_string()
+        };
+
+        label(
+            frame,
+            min_label,
+            Point::new(origin.x + px(LEGEND_PAD_PX), labels_y),
+            Alignment::Start.into(),
+        );
+        label(
+            frame,
+            abbr_large_numbers(max_depth_qty),
+            Point::new(
+                origin.x + px(LEGEND_PAD_PX + LEGEND_SHADE_BAR_WIDTH_PX),
+                labels_y,
+            ),
+            Alignment::End.into(),
+        );
+    }
+}
+
+fn draw_developing_value_area(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    value_area: &DevelopingValueArea,
+    earliest: u64,
+    latest: u64,
+    palette: &Extended,
+) {
+    let half_cell = chart.cell_width / 2.0;
+    let stroke_width = style::dpi::stroke_width(1.0, chart.scale_factor) / chart.scaling;
+
+    type LevelOf = fn(&ValueArea) -> Price;
+
+    let lines: [(LevelOf, Color); 3] = [
+        (|va| va.vah, palette.secondary.strong.color),
+        (|va| va.val, palette.secondary.strong.color),
+        (|va| va.poc, palette.primary.strong.color),
+    ];
+
+    for (level_of, color) in lines {
+        let path = Path::new(|builder| {
+            let mut prev: Option<(UnixMs, f32)> = None;
+
+            for (time, va) in value_area
+                .levels
+                .range(UnixMs::new(earliest)..=UnixMs::new(latest))
+            {
+                let x = chart.interval_to_x(time.as_u64());
+                let y = chart.price_to_y(level_of(va));
+
+                match prev {
+                    Some((prev_time, prev_y))
+                        if DevelopingValueArea::is_same_session(prev_time, *time) =>
+                    {
+                        builder.line_to(Point::new(x - half_cell, prev_y));
+                        builder.line_to(Point::new(x - half_cell, y));
+                    }
+                    _ => builder.move_to(Point::new(x - half_cell, y)),
+                }
+                builder.line_to(Point::new(x + half_cell, y));
+
+                prev = Some((*time, y));
+            }
+        });
+
+        frame.stroke(
+            &path,
+            Stroke::with_color(
+                Stroke {
+                    width: stroke_width,
+                    ..Default::default()
+                },
+                color.scale_alpha(0.8),
+            ),
+        );
+    }
+}
+
+/// Each level glows from when its positions were opened up to the latest datapoint,
+/// brighter the more open interest it would liquidate
+fn draw_liquidation_levels(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    liquidations: &LiquidationMap,
+    region: &Rectangle,
+    palette: &Extended,
+) {
+    let max_size = liquidations.max_size();
+    if max_size <= 0.0 {
+        return;
+    }
+
+    let (highest, lowest) = chart.price_range(region);
+    let height = chart.cell_height.max(1.0 / chart.scaling);
+
+    let sides = [
+        (&liquidations.longs, palette.danger.base.color),
+        (&liquidations.shorts, palette.success.base.color),
+    ];
+
+    for (levels, color) in sides {
+        for (price, level) in levels.range(lowest..=highest) {
+            let start_x = chart.interval_to_x(level.since.as_u64()).max(region.x);
+            let end_x = 0.0_f32.min(region.x + region.width);
+            if end_x <= start_x {
+                continue;
+            }
+
+            let intensity = (level.size / max_size).clamp(0.05, 1.0) as f32;
+            let y = chart.price_to_y(*price);
+            let width = end_x - start_x;
+
+            // A wider, fainter band under the line for the glow
+            for (spread, alpha) in [(4.0, 0.12), (2.0, 0.25), (1.0, 0.9)] {
+                let band = height * spread;
+                frame.fill_rectangle(
+                    Point::new(start_x, y - band / 2.0),
+                    Size::new(width, band),
+                    color.scale_alpha(alpha * intensity),
+                );
+            }
+        }
+    }
+}
+
 fn draw_volume_profile(
     frame: &mut canvas::Frame,
     region: &Rectangle,
@@ -953,7 +1544,7 @@ fn draw_volume_profile(
         ProfileKind::FixedWindow(datapoints) => {
             let basis_interval = match chart.basis {
                 Basis::Time(interval) => interval.to_milliseconds(),
-                Basis::Tick(_) => return,
+                Basis::Tick(_) | Basis::Bars(_) => return,
             };
 
             let latest = chart
@@ -965,52 +1556,17 @@ fn draw_volume_profile(
         }
     };
 
-    let step = chart.tick_size;
-
-    let first_tick = lowest.round_to_side_step(false, step);
-    let last_tick = highest.round_to_side_step(true, step);
-
-    let num_ticks = match Price::steps_between_inclusive(first_tick, last_tick, step) {
-        Some(n) => n,
-        None => return,
-    };
-
-    if num_ticks > 4096 {
+    let Some((first_tick, profile, max_aggr_volume)) = traded_profile(
+        timeseries,
+        time_start,
+        time_end,
+        highest,
+        lowest,
+        chart.tick_size,
+    ) else {
         return;
-    }
-
-    let mut profile = vec![(0.0f64, 0.0f64); num_ticks];
-    let mut max_aggr_volume = 0.0f64;
-
-    timeseries
-        .datapoints
-        .range(UnixMs::new(time_start)..=UnixMs::new(time_end))
-        .for_each(|(_, dp)| {
-            dp.grouped_trades
-                .iter()
-                .filter(|trade| trade.price >= lowest && trade.price <= highest)
-                .for_each(|trade| {
-                    let grouped_price = trade.price.round_to_side_step(trade.is_sell, step);
-
-                    if grouped_price.units < first_tick.units
-                        || grouped_price.units > last_tick.units
-                    {
-                        return;
-                    }
-
-                    let index = ((grouped_price.units - first_tick.units) / step.units) as usize;
-
-                    if let Some(entry) = profile.get_mut(index) {
-                        let trade_qty = trade.qty.to_f64();
-                        if trade.is_sell {
-                            entry.1 += trade_qty;
-                        } else {
-                            entry.0 += trade_qty;
-                        }
-                        max_aggr_volume = max_aggr_volume.max(entry.0 + entry.1);
-                    }
-                });
-        });
+    };
+    let step = chart.tick_size;
 
     profile
         .iter()
@@ -1057,3 +1613,171 @@ fn draw_volume_profile(
         });
     }
 }
+
+/// Outlines the column the side profile was frozen at, with its time above it
+fn draw_snapshot_marker(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    region: &Rectangle,
+    time: UnixMs,
+    palette: &Extended,
+) {
+    let x = chart.interval_to_x(time.as_u64());
+    let width = match chart.basis {
+        Basis::Time(interval) => {
+            chart.interval_to_x(time.as_u64() + interval.to_milliseconds()) - x
+        }
+        Basis::Tick(_) | Basis::Bars(_) => chart.cell_width,
+    };
+    let color = palette.primary.strong.color;
+
+    frame.fill_rectangle(
+        Point::new(x, region.y),
+        Size::new(width.max(1.0 / chart.scaling), region.height),
+        color.scale_alpha(0.15),
+    );
+    frame.stroke(
+        &Path::line(
+            Point::new(x, region.y),
+            Point::new(x, region.y + region.height),
+        ),
+        Stroke::with_color(
+            Stroke {
+                width: 1.0 / chart.scaling,
+                ..Stroke::default()
+            },
+            color,
+        ),
+    );
+
+    frame.fill_text(canvas::Text {
+        content: time.format_utc("Book at %H:%M:%S%.3f").unwrap_or_default(),
+        position: Point::new(
+            x + 4.0 / chart.scaling,
+            region.y + (CURRENT_DEPTH_LABEL_TOP_PAD_PX / chart.scaling),
+        ),
+        size: iced::Pixels(crate::style::text_size::TINY / chart.scaling),
+        color,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
+
+/// Volume traded at each price over the visible window, right of the resting depth so
+/// the two can be told apart
+fn draw_traded_profile(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    timeseries: &TimeSeries<HeatmapDataPoint>,
+    (earliest, latest): (u64, u64),
+    (highest, lowest): (Price, Price),
+    x: f32,
+    area_width: f32,
+    top: f32,
+    palette: &Extended,
+) {
+    let step = chart.tick_size;
+    let Some((first_tick, profile, max_aggr_volume)) =
+        traded_profile(timeseries, earliest, latest, highest, lowest, step)
+    else {
+        return;
+    };
+    if max_aggr_volume <= 0.0 {
+        return;
+    }
+
+    for (index, (buy_v, sell_v)) in profile.iter().enumerate() {
+        if *buy_v <= 0.0 && *sell_v <= 0.0 {
+            continue;
+        }
+        let price = first_tick.add_steps(index as i64, step);
+        let y_position = chart.price_to_y(price);
+        let bar_height = (chart.price_to_y(price.add_steps(1, step)) - y_position).abs();
+
+        super::draw_volume_bar(
+            frame,
+            x,
+            y_position,
+            *buy_v,
+            *sell_v,
+            max_aggr_volume,
+            area_width,
+            bar_height,
+            palette.success.weak.color,
+            palette.danger.weak.color,
+            1.0,
+            true,
+        );
+    }
+
+    frame.fill_text(canvas::Text {
+        content: abbr_large_numbers(max_aggr_volume),
+        position: Point::new(
+            x + area_width,
+            top + (CURRENT_DEPTH_LABEL_TOP_PAD_PX / chart.scaling),
+        ),
+        size: iced::Pixels(crate::style::text_size::TINY / chart.scaling),
+        color: palette.background.base.text,
+        font: style::AZERET_MONO,
+        align_x: Alignment::End.into(),
+        align_y: Alignment::Start.into(),
+        ..canvas::Text::default()
+    });
+}
+
+/// Lowest tick, the buy and sell volume traded at each tick up from it and the largest total
+type TradedProfile = (Price, Vec<(f64, f64)>, f64);
+
+/// Buy and sell volume traded at each tick between `lowest` and `highest` over the time range,
+/// from the lowest tick up, with the largest total at a tick
+fn traded_profile(
+    timeseries: &TimeSeries<HeatmapDataPoint>,
+    time_start: u64,
+    time_end: u64,
+    highest: Price,
+    lowest: Price,
+    step: PriceStep,
+) -> Option<TradedProfile> {
+    let first_tick = lowest.round_to_side_step(false, step);
+    let last_tick = highest.round_to_side_step(true, step);
+
+    let num_ticks = Price::steps_between_inclusive(first_tick, last_tick, step)?;
+    if num_ticks > 4096 {
+        return None;
+    }
+
+    let mut profile = vec![(0.0f64, 0.0f64); num_ticks];
+    let mut max_aggr_volume = 0.0f64;
+
+    timeseries
+        .datapoints
+        .range(UnixMs::new(time_start)..=UnixMs::new(time_end))
+        .for_each(|(_, dp)| {
+            dp.grouped_trades
+                .iter()
+                .filter(|trade| trade.price >= lowest && trade.price <= highest)
+                .for_each(|trade| {
+                    let grouped_price = trade.price.round_to_side_step(trade.is_sell, step);
+
+                    if grouped_price.units < first_tick.units
+                        || grouped_price.units > last_tick.units
+                    {
+                        return;
+                    }
+
+                    let index = ((grouped_price.units - first_tick.units) / step.units) as usize;
+
+                    if let Some(entry) = profile.get_mut(index) {
+                        let trade_qty = trade.qty.to_f64();
+                        if trade.is_sell {
+                            entry.1 += trade_qty;
+                        } else {
+                            entry.0 += trade_qty;
+                        }
+                        max_aggr_volume = max_aggr_volume.max(entry.0 + entry.1);
+                    }
+                });
+        });
+
+    Some((first_tick, profile, max_aggr_volume))
+}
@@ -48,7 +48,7 @@ where
             }
             e
         }
-        Basis::Tick(_) => {
+        Basis::Tick(_) | Basis::Bars(_) => {
             if x_shift > 0 {
                 visible_earliest.saturating_sub(x_shift as u64)
             } else {
@@ -65,7 +65,7 @@ where
             }
             l
         }
-        Basis::Tick(_) => {
+        Basis::Tick(_) | Basis::Bars(_) => {
             let mut l = visible_latest.saturating_add(1);
             if x_shift < 0 {
                 l = l.saturating_add((-x_shift) as u64);
@@ -98,6 +98,7 @@ where
         max,
         min,
         chart_bounds: main_chart.bounds,
+        scale_factor: main_chart.scale_factor,
     })
     .height(Length::Fill)
     .width(main_chart.y_labels_width());
@@ -115,6 +116,7 @@ pub struct IndicatorLabel<'a> {
     pub max: f32,
     pub min: f32,
     pub chart_bounds: Rectangle,
+    pub scale_factor: f32,
 }
 
 impl canvas::Program<Message> for IndicatorLabel<'_> {
@@ -184,7 +186,7 @@ impl canvas::Program<Message> for IndicatorLabel<'_> {
                 });
             }
 
-            AxisLabel::filter_and_draw(&all_labels, frame);
+            AxisLabel::filter_and_draw(&all_labels, frame, self.scale_factor);
         });
 
         vec![labels]
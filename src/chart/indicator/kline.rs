@@ -1,7 +1,7 @@
 use crate::chart::{Basis, Message, ViewState};
 use crate::connector::fetcher::FetchRange;
 
-use data::chart::indicator::KlineIndicator;
+use data::chart::indicator::{IndicatorSettings, KlineIndicator, PriceSource};
 use data::chart::kline::KlineDataPoint;
 use data::chart::{BasisSeries, PlotData};
 use exchange::adapter::Exchange;
@@ -9,9 +9,22 @@ use exchange::{Kline, Timeframe, Trade, UnixMs};
 
 use super::plot::AnySeries;
 
+pub mod band;
+pub mod big_trades;
+pub mod cci;
+pub mod compare;
 pub mod cumulative_delta;
+pub mod funding_rate;
+pub mod funding_weighted_oi;
+pub mod macd;
+pub mod moving_average;
 pub mod open_interest;
+pub mod open_interest_delta;
+pub mod rsi;
+pub mod script;
+pub mod stochastic;
 pub mod volume;
+pub mod vwap;
 
 /// UI adapter methods for converting domain `BasisSeries` into plot-ready series.
 trait BasisSeriesExt<T> {
@@ -27,6 +40,59 @@ impl<T> BasisSeriesExt<T> for BasisSeries<T> {
     }
 }
 
+/// Runs `calc` over the source's klines oldest first and keys its values like the source,
+/// leaving out the ones it couldn't compute yet
+fn derive_from_klines<T>(
+    source: &PlotData<KlineDataPoint>,
+    calc: impl FnOnce(&[Kline]) -> Vec<Option<T>>,
+) -> BasisSeries<T> {
+    match source {
+        PlotData::TimeBased(timeseries) => {
+            let (times, klines): (Vec<UnixMs>, Vec<Kline>) = timeseries
+                .datapoints
+                .iter()
+                .map(|(time, dp)| (*time, dp.kline))
+                .unzip();
+
+            BasisSeries::time(
+                times
+                    .into_iter()
+                    .zip(calc(&klines))
+                    .filter_map(|(time, value)| Some((time, value?)))
+                    .collect(),
+            )
+        }
+        PlotData::TickBased(tick_aggr) => {
+            let klines = tick_aggr
+                .datapoints
+                .iter()
+                .map(|dp| dp.kline)
+                .collect::<Vec<_>>();
+
+            BasisSeries::tick(
+                calc(&klines)
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(idx, value)| Some((idx as u64, value?)))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Close, high and low prices of the klines
+fn price_columns(klines: &[Kline]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let closes = klines.iter().map(|k| k.close.to_f64()).collect();
+    let highs = klines.iter().map(|k| k.high.to_f64()).collect();
+    let lows = klines.iter().map(|k| k.low.to_f64()).collect();
+    (closes, highs, lows)
+}
+
+/// The `source` price of each kline
+fn source_column(klines: &[Kline], source: PriceSource) -> Vec<f64> {
+    klines.iter().map(|k| source.of(k)).collect()
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, Default, PartialEq)]
 pub enum IndicatorAvailability {
@@ -121,6 +187,8 @@ pub trait KlineIndicatorImpl {
     fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
 
     fn on_open_interest(&mut self, _pairs: &[exchange::OpenInterest]) {}
+
+    fn on_funding_rate(&mut self, _rates: &[exchange::FundingRate]) {}
 }
 
 pub struct FetchCtx<'a> {
@@ -131,14 +199,34 @@ pub struct FetchCtx<'a> {
     pub prefetch_earliest: UnixMs,
 }
 
-pub fn make_empty(which: KlineIndicator) -> Box<dyn KlineIndicatorImpl> {
+pub fn make_empty(
+    which: KlineIndicator,
+    settings: IndicatorSettings,
+) -> Box<dyn KlineIndicatorImpl> {
+    let color = settings.color;
+
     match which {
         KlineIndicator::Volume => Box::new(super::kline::volume::VolumeIndicator::new()),
         KlineIndicator::CumulativeDelta => {
-            Box::new(super::kline::cumulative_delta::CumulativeDeltaIndicator::new())
+            Box::new(super::kline::cumulative_delta::CumulativeDeltaIndicator::new(color))
+        }
+        KlineIndicator::OpenInterest => Box::new(
+            super::kline::open_interest::OpenInterestIndicator::new(color),
+        ),
+        KlineIndicator::FundingRate => {
+            Box::new(super::kline::funding_rate::FundingRateIndicator::new(color))
+        }
+        KlineIndicator::OpenInterestDelta => {
+            Box::new(super::kline::open_interest_delta::OpenInterestDeltaIndicator::new())
+        }
+        KlineIndicator::FundingWeightedOi => {
+            Box::new(super::kline::funding_weighted_oi::FundingWeightedOiIndicator::new(color))
         }
-        KlineIndicator::OpenInterest => {
-            Box::new(super::kline::open_interest::OpenInterestIndicator::new())
+        KlineIndicator::Rsi => Box::new(super::kline::rsi::RsiIndicator::new(settings)),
+        KlineIndicator::Macd => Box::new(super::kline::macd::MacdIndicator::new(settings)),
+        KlineIndicator::Stochastic => {
+            Box::new(super::kline::stochastic::StochasticIndicator::new(settings))
         }
+        KlineIndicator::Cci => Box::new(super::kline::cci::CciIndicator::new(settings)),
     }
 }
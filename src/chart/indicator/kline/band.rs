@@ -0,0 +1,138 @@
+use crate::chart::{
+    ViewState,
+    indicator::kline::{BasisSeries, BasisSeriesExt, derive_from_klines, price_columns},
+    indicator::plot::Series,
+};
+
+use data::chart::indicator::{BandKind, BandPoint, VolatilityBand};
+use data::chart::{Basis, PlotData, kline::KlineDataPoint};
+use exchange::unit::Price;
+
+use iced::Point;
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Path, Stroke, path};
+
+/// Volatility bands drawn over the candles, kept in step with the chart's data source
+#[derive(Default)]
+pub struct BandOverlay {
+    bands: Vec<(VolatilityBand, BasisSeries<BandPoint>)>,
+}
+
+impl BandOverlay {
+    pub fn bands(&self) -> Vec<VolatilityBand> {
+        self.bands.iter().map(|(band, _)| *band).collect()
+    }
+
+    /// Replaces the drawn bands, returns whether anything changed
+    pub fn set_bands(
+        &mut self,
+        bands: &[VolatilityBand],
+        source: &PlotData<KlineDataPoint>,
+    ) -> bool {
+        if self.bands.len() == bands.len() && self.bands.iter().zip(bands).all(|((a, _), b)| a == b)
+        {
+            return false;
+        }
+
+        self.bands = bands
+            .iter()
+            .map(|band| (*band, BasisSeries::default()))
+            .collect();
+        self.rebuild_from_source(source);
+        true
+    }
+
+    pub fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        for (band, series) in &mut self.bands {
+            *series = derive_from_klines(source, |klines| {
+                let (closes, highs, lows) = price_columns(klines);
+                band.compute(&highs, &lows, &closes)
+            });
+        }
+    }
+
+    pub fn on_insert_trades(&mut self, source: &PlotData<KlineDataPoint>) {
+        if let PlotData::TickBased(_) = source {
+            self.rebuild_from_source(source);
+        }
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        chart: &ViewState,
+        earliest: u64,
+        latest: u64,
+        palette: &Extended,
+    ) {
+        if latest < earliest {
+            return;
+        }
+
+        // One bar past each edge so the bands reach out of view
+        let padded = match chart.basis {
+            Basis::Time(tf) => {
+                earliest.saturating_sub(tf.to_milliseconds())
+                    ..=latest.saturating_add(tf.to_milliseconds())
+            }
+            Basis::Tick(_) | Basis::Bars(_) => {
+                earliest.saturating_sub(1)..=latest.saturating_add(1)
+            }
+        };
+
+        for (band, series) in &self.bands {
+            let color = match band.kind {
+                BandKind::Bollinger => palette.primary.base.color,
+                BandKind::Keltner => palette.secondary.strong.color,
+            };
+
+            let mut upper = vec![];
+            let mut middle = vec![];
+            let mut lower = vec![];
+
+            series
+                .as_plot_series()
+                .for_each_in(padded.clone(), |x, point| {
+                    let x = chart.interval_to_x(x);
+                    let y = |price: f64| chart.price_to_y(Price::from_f64(price));
+
+                    upper.push(Point::new(x, y(point.upper)));
+                    middle.push(Point::new(x, y(point.middle)));
+                    lower.push(Point::new(x, y(point.lower)));
+                });
+
+            if upper.len() < 2 {
+                continue;
+            }
+
+            if band.fill {
+                let area = Path::new(|builder| {
+                    builder.move_to(upper[0]);
+                    upper[1..]
+                        .iter()
+                        .chain(lower.iter().rev())
+                        .for_each(|point| builder.line_to(*point));
+                    builder.close();
+                });
+                frame.fill(&area, color.scale_alpha(0.08));
+            }
+
+            let stroke = Stroke::with_color(Stroke::default(), color);
+            frame.stroke(&polyline(&upper), stroke);
+            frame.stroke(&polyline(&lower), stroke);
+            frame.stroke(
+                &polyline(&middle),
+                Stroke::with_color(Stroke::default(), color.scale_alpha(0.5)),
+            );
+        }
+    }
+}
+
+fn polyline(points: &[Point]) -> Path {
+    let mut builder = path::Builder::new();
+    if let Some((first, rest)) = points.split_first() {
+        builder.move_to(*first);
+        rest.iter().for_each(|point| builder.line_to(*point));
+    }
+    builder.build()
+}
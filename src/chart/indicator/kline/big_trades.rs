@@ -0,0 +1,243 @@
+use crate::chart::{TEXT_SIZE, ViewState, kline::marker_interval};
+use crate::style;
+
+use data::chart::PlotData;
+use data::chart::kline::{BigTrades, KlineDataPoint};
+use data::util::{abbr_large_numbers, format_with_commas};
+use exchange::adapter::MarketKind;
+use exchange::unit::qty::{SizeUnit, volume_size_unit};
+use exchange::{TickerInfo, Trade};
+
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Path, Stroke};
+use iced::{Alignment, Point, Rectangle, Size};
+
+/// Radius of the smallest dot, in pixels
+const MIN_RADIUS: f32 = 3.0;
+const MAX_RADIUS: f32 = 14.0;
+
+/// Single trades of at least the configured notional, dotted over the candles
+pub struct BigTradeOverlay {
+    config: Option<BigTrades>,
+    market: MarketKind,
+    /// The chart's raw trades that made the cut, with their notional
+    trades: Vec<(Trade, f64)>,
+}
+
+impl BigTradeOverlay {
+    pub fn new(config: Option<BigTrades>, market: MarketKind, trades: &[Trade]) -> Self {
+        let mut overlay = Self {
+            config,
+            market,
+            trades: vec![],
+        };
+        overlay.rebuild(trades);
+        overlay
+    }
+
+    /// Returns whether anything changed
+    pub fn set_config(&mut self, config: Option<BigTrades>, trades: &[Trade]) -> bool {
+        if self.config == config {
+            return false;
+        }
+
+        let threshold_changed =
+            self.config.map(|c| c.min_notional) != config.map(|c| c.min_notional);
+        self.config = config;
+
+        if threshold_changed {
+            self.rebuild(trades);
+        }
+        true
+    }
+
+    pub fn rebuild(&mut self, trades: &[Trade]) {
+        self.trades.clear();
+        self.on_insert_trades(trades);
+    }
+
+    pub fn on_insert_trades(&mut self, buffer: &[Trade]) {
+        let Some(config) = self.config else {
+            return;
+        };
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let min_notional = f64::from(config.min_notional);
+
+        self.trades.extend(buffer.iter().filter_map(|trade| {
+            let notional =
+                self.market
+                    .qty_in_quote_value(trade.qty, trade.price, size_in_quote_ccy);
+            (notional >= min_notional).then_some((*trade, notional))
+        }));
+    }
+
+    /// Center and radius of every dot inside `region`, in chart coordinates
+    fn markers<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_source: &'a PlotData<KlineDataPoint>,
+        region: Rectangle,
+    ) -> impl Iterator<Item = (Point, f32, &'a (Trade, f64))> + 'a {
+        let config = self.config;
+
+        self.trades.iter().filter_map(move |entry| {
+            let config = config?;
+            let (trade, notional) = entry;
+
+            let radius = if config.size_scaled {
+                let ratio = (notional / f64::from(config.min_notional).max(1.0)).sqrt() as f32;
+                (MIN_RADIUS * ratio).clamp(MIN_RADIUS, MAX_RADIUS)
+            } else {
+                MIN_RADIUS
+            } / chart.scaling;
+
+            let interval = marker_interval(trade.time, data_source, chart.basis)?;
+            let center = Point::new(chart.interval_to_x(interval), chart.price_to_y(trade.price));
+
+            let inside = center.x >= region.x - radius
+                && center.x <= region.x + region.width + radius
+                && center.y >= region.y - radius
+                && center.y <= region.y + region.height + radius;
+
+            inside.then_some((center, radius, entry))
+        })
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        chart: &ViewState,
+        data_source: &PlotData<KlineDataPoint>,
+        region: Rectangle,
+        palette: &Extended,
+    ) {
+        let Some(config) = self.config else {
+            return;
+        };
+        let text_size = TEXT_SIZE / chart.scaling;
+
+        for (center, radius, (trade, notional)) in self.markers(chart, data_source, region) {
+            let color = if trade.is_sell {
+                palette.danger.base.color
+            } else {
+                palette.success.base.color
+            };
+
+            let circle = Path::circle(center, radius);
+            frame.fill(&circle, color.scale_alpha(0.5));
+            frame.stroke(
+                &circle,
+                Stroke::with_color(
+                    Stroke {
+                        width: 1.0 / chart.scaling,
+                        ..Stroke::default()
+                    },
+                    color,
+                ),
+            );
+
+            if config.labels {
+                frame.fill_text(canvas::Text {
+                    content: abbr_large_numbers(*notional),
+                    position: Point::new(center.x + radius + 2.0 / chart.scaling, center.y),
+                    size: iced::Pixels(text_size),
+                    color,
+                    align_y: Alignment::Center.into(),
+                    font: style::AZERET_MONO,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+    }
+
+    /// Size, price and time of the largest trade under the cursor, next to it
+    pub fn draw_tooltip(
+        &self,
+        frame: &mut canvas::Frame,
+        chart: &ViewState,
+        data_source: &PlotData<KlineDataPoint>,
+        ticker_info: &TickerInfo,
+        cursor_position: Point,
+        palette: &Extended,
+    ) {
+        if self.config.is_none() {
+            return;
+        }
+
+        let bounds = frame.size();
+        let region = chart.visible_region(bounds);
+        let cursor = Point::new(
+            region.x + cursor_position.x / chart.scaling,
+            region.y + cursor_position.y / chart.scaling,
+        );
+
+        let Some((_, _, (trade, notional))) = self
+            .markers(chart, data_source, region)
+            .filter(|(center, radius, _)| center.distance(cursor) <= *radius)
+            .max_by(|(_, _, a), (_, _, b)| a.1.total_cmp(&b.1))
+        else {
+            return;
+        };
+
+        let lines = [
+            format!(
+                "{} {} @ {}",
+                if trade.is_sell { "Sell" } else { "Buy" },
+                abbr_large_numbers(trade.qty.to_f64()),
+                trade.price.to_string(ticker_info.min_ticksize),
+            ),
+            format!("Notional {}", format_with_commas(*notional)),
+            trade
+                .time
+                .format_utc("%Y-%m-%d %H:%M:%S%.3f UTC")
+                .unwrap_or_default(),
+        ];
+
+        let line_height = TEXT_SIZE + 4.0;
+        let width = lines
+            .iter()
+            .map(|line| line.len() as f32 * (TEXT_SIZE * 0.65))
+            .fold(0.0, f32::max)
+            + 12.0;
+        let height = line_height * lines.len() as f32 + 8.0;
+
+        // Flipped to the other side of the cursor where it would run off the chart
+        let mut position = Point::new(cursor_position.x + 14.0, cursor_position.y + 14.0);
+        if position.x + width > bounds.width {
+            position.x = cursor_position.x - 14.0 - width;
+        }
+        if position.y + height > bounds.height {
+            position.y = cursor_position.y - 14.0 - height;
+        }
+
+        frame.fill_rectangle(
+            position,
+            Size::new(width, height),
+            palette.background.weakest.color.scale_alpha(0.9),
+        );
+
+        let color = if trade.is_sell {
+            palette.danger.base.color
+        } else {
+            palette.success.base.color
+        };
+
+        for (index, line) in lines.into_iter().enumerate() {
+            frame.fill_text(canvas::Text {
+                content: line,
+                position: Point::new(
+                    position.x + 6.0,
+                    position.y + 4.0 + line_height * index as f32,
+                ),
+                size: iced::Pixels(TEXT_SIZE),
+                color: if index == 0 {
+                    color
+                } else {
+                    palette.background.base.text
+                },
+                font: style::AZERET_MONO,
+                ..canvas::Text::default()
+            });
+        }
+    }
+}
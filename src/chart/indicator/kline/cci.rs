@@ -0,0 +1,117 @@
+use crate::chart::{
+    Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{
+            BasisSeries, BasisSeriesExt, KlineIndicatorImpl, derive_from_klines, price_columns,
+        },
+        plot::{PlotTooltip, oscillator::OscillatorPlot},
+    },
+};
+
+use data::chart::indicator::IndicatorSettings;
+use data::chart::oscillator;
+use data::chart::{PlotData, kline::KlineDataPoint};
+use exchange::{Kline, Trade};
+
+use iced::Color;
+use std::ops::RangeInclusive;
+
+/// Beyond these the typical price is unusually far from its average
+const BAND: f32 = 100.0;
+
+pub struct CciIndicator {
+    cache: Caches,
+    period: u16,
+    color: Option<Color>,
+    data: BasisSeries<f64>,
+}
+
+impl CciIndicator {
+    pub fn new(settings: IndicatorSettings) -> Self {
+        Self {
+            cache: Caches::default(),
+            period: settings.periods.cci_period,
+            color: settings.color,
+            data: BasisSeries::default(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        let period = self.period;
+        let tooltip = move |value: &f64, _next: Option<&f64>| {
+            PlotTooltip::new(format!("CCI({period}): {value:.2}"))
+        };
+
+        let plot = OscillatorPlot::new()
+            .line(|value: &f64| Some(*value as f32))
+            .levels(&[-BAND, 0.0, BAND])
+            .color(self.color)
+            .with_tooltip(tooltip);
+
+        indicator_row(
+            main_chart,
+            &self.cache,
+            data_labels_always_visible,
+            plot,
+            self.data.as_plot_series(),
+            visible_range,
+        )
+    }
+}
+
+impl KlineIndicatorImpl for CciIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, data_labels_always_visible, visible_range)
+    }
+
+    fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        let period = self.period;
+        self.data = derive_from_klines(source, |klines| {
+            let (closes, highs, lows) = price_columns(klines);
+            oscillator::cci(&highs, &lows, &closes, period)
+        });
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline], source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        source: &PlotData<KlineDataPoint>,
+    ) {
+        if let PlotData::TickBased(_) = source {
+            self.rebuild_from_source(source);
+        }
+    }
+
+    fn on_ticksize_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+}
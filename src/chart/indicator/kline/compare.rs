@@ -0,0 +1,248 @@
+use crate::chart::{TEXT_SIZE, ViewState};
+use crate::connector::fetcher::{FetchRange, FetchSpec, RequestHandler};
+use crate::style;
+
+use data::chart::{Basis, PlotData, kline::KlineDataPoint};
+use exchange::adapter::StreamKind;
+use exchange::unit::Price;
+use exchange::{Kline, TickerInfo, Timeframe, UnixMs};
+
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Path, Stroke};
+use iced::{Point, Size};
+
+use std::collections::BTreeMap;
+
+/// A second ticker drawn as a line over the candles, only on time basis.
+///
+/// Its closes are rebased to the main ticker's close at the first visible candle they
+/// share, so both start from the same point and the gap between them is the
+/// difference in percent change.
+#[derive(Default)]
+pub struct CompareOverlay {
+    ticker_info: Option<TickerInfo>,
+    /// Last close of each kline interval of the chart
+    closes: BTreeMap<u64, Price>,
+    request_handler: RequestHandler,
+}
+
+impl CompareOverlay {
+    pub fn ticker_info(&self) -> Option<TickerInfo> {
+        self.ticker_info
+    }
+
+    /// The overlaid ticker as a selection for the mini tickers list
+    pub fn tickers(&self) -> &[TickerInfo] {
+        self.ticker_info.as_slice()
+    }
+
+    pub fn set_ticker(&mut self, ticker_info: Option<TickerInfo>) {
+        self.ticker_info = ticker_info;
+        self.reset();
+    }
+
+    /// Drops the loaded closes so they are fetched again in the new timeframe
+    pub fn reset(&mut self) {
+        self.closes.clear();
+        self.request_handler = RequestHandler::default();
+    }
+
+    /// The kline stream feeding the overlay, in the timeframe the venue can serve for `basis`
+    pub fn stream(&self, basis: Basis) -> Option<StreamKind> {
+        let ticker_info = self.ticker_info?;
+        let Basis::Time(tf) = basis else {
+            return None;
+        };
+
+        ticker_info
+            .exchange()
+            .kline_fetch_timeframe(tf)
+            .map(|timeframe| StreamKind::Kline {
+                ticker_info,
+                timeframe,
+            })
+    }
+
+    /// Requests the closes missing between the chart's earliest and latest candle
+    pub fn fetch_missing(
+        &mut self,
+        basis: Basis,
+        kline_earliest: UnixMs,
+        kline_latest: UnixMs,
+    ) -> Option<FetchSpec> {
+        let stream = self.stream(basis)?;
+
+        let range = match self.closes.first_key_value() {
+            None => FetchRange::Kline(kline_earliest, kline_latest),
+            Some((first, _)) if UnixMs::new(*first) > kline_earliest => {
+                FetchRange::Kline(kline_earliest, UnixMs::new(*first))
+            }
+            Some(_) => return None,
+        };
+
+        match self.request_handler.add_request(range) {
+            Ok(Some(req_id)) => Some(FetchSpec {
+                req_id,
+                fetch: range,
+                stream: Some(stream),
+            }),
+            Ok(None) | Err(_) => None,
+        }
+    }
+
+    pub fn insert_hist_klines(
+        &mut self,
+        req_id: uuid::Uuid,
+        timeframe: Timeframe,
+        klines: &[Kline],
+    ) {
+        if klines.is_empty() {
+            self.request_handler
+                .mark_failed(req_id, "No data received".to_string());
+            return;
+        }
+        self.request_handler.mark_completed(req_id);
+
+        let mut fetched = BTreeMap::new();
+        for kline in klines {
+            fetched.insert(kline.time.floor_to(timeframe).as_u64(), kline.close);
+        }
+
+        // Intervals the stream already reached hold a newer close
+        for (interval, close) in fetched {
+            self.closes.entry(interval).or_insert(close);
+        }
+    }
+
+    pub fn update_latest_kline(&mut self, timeframe: Timeframe, kline: &Kline) {
+        self.closes
+            .insert(kline.time.floor_to(timeframe).as_u64(), kline.close);
+    }
+
+    /// Close of the overlay and of the main ticker at the first visible candle both have
+    fn anchor(
+        &self,
+        source: &PlotData<KlineDataPoint>,
+        earliest: u64,
+        latest: u64,
+    ) -> Option<(Price, Price)> {
+        let PlotData::TimeBased(timeseries) = source else {
+            return None;
+        };
+
+        self.closes
+            .range(earliest..=latest)
+            .find_map(|(interval, close)| {
+                timeseries
+                    .datapoints
+                    .get(&UnixMs::new(*interval))
+                    .map(|dp| (*close, dp.kline.close))
+            })
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        chart: &ViewState,
+        source: &PlotData<KlineDataPoint>,
+        earliest: u64,
+        latest: u64,
+        palette: &Extended,
+    ) {
+        if earliest > latest {
+            return;
+        }
+        let Some((anchor, main_anchor)) = self.anchor(source, earliest, latest) else {
+            return;
+        };
+        if anchor.to_f64() <= 0.0 {
+            return;
+        }
+        let scale = main_anchor.to_f64() / anchor.to_f64();
+
+        // One point past each edge so the line reaches across the visible region
+        let points = self
+            .closes
+            .range(..earliest)
+            .next_back()
+            .into_iter()
+            .chain(self.closes.range(earliest..=latest))
+            .chain(self.closes.range(latest.saturating_add(1)..).next())
+            .map(|(interval, close)| {
+                Point::new(
+                    chart.interval_to_x(*interval),
+                    chart.price_to_y(Price::from_f64(close.to_f64() * scale)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if points.len() < 2 {
+            return;
+        }
+
+        let line = Path::new(|p| {
+            p.move_to(points[0]);
+            for point in &points[1..] {
+                p.line_to(*point);
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::with_color(
+                Stroke {
+                    width: 1.5,
+                    ..Stroke::default()
+                },
+                palette.primary.strong.color,
+            ),
+        );
+    }
+
+    /// Close and change since the anchor candle, at the hovered interval or the latest one
+    pub fn draw_legend(
+        &self,
+        frame: &mut canvas::Frame,
+        source: &PlotData<KlineDataPoint>,
+        visible_range: (u64, u64),
+        at_interval: Option<u64>,
+        position: Point,
+        palette: &Extended,
+    ) {
+        let Some(ticker_info) = self.ticker_info else {
+            return;
+        };
+
+        let entry = match at_interval {
+            Some(interval) => self.closes.range(..=interval).next_back(),
+            None => self.closes.last_key_value(),
+        };
+        let Some((_, close)) = entry else {
+            return;
+        };
+
+        let symbol = ticker_info.ticker.display_symbol_and_type().0;
+        let mut content = format!("{symbol} {}", close.to_string(ticker_info.min_ticksize));
+
+        let (earliest, latest) = visible_range;
+        if let Some((anchor, _)) = self.anchor(source, earliest, latest)
+            && anchor.to_f64() > 0.0
+        {
+            let change = (close.to_f64() / anchor.to_f64() - 1.0) * 100.0;
+            content.push_str(&format!(" ({change:+.2}%)"));
+        }
+
+        frame.fill_rectangle(
+            position,
+            Size::new(content.len() as f32 * (TEXT_SIZE * 0.8), 16.0),
+            palette.background.weakest.color.scale_alpha(0.9),
+        );
+        frame.fill_text(canvas::Text {
+            content,
+            position,
+            size: iced::Pixels(style::text_size::BODY),
+            color: palette.primary.strong.color,
+            font: style::AZERET_MONO,
+            ..canvas::Text::default()
+        });
+    }
+}
@@ -14,6 +14,7 @@ use data::chart::{PlotData, kline::KlineDataPoint};
 use data::util::format_with_commas;
 use exchange::{Kline, Trade, unit::Qty};
 
+use iced::Color;
 use iced::widget::{center, text};
 
 use std::collections::{BTreeMap, BTreeSet};
@@ -40,6 +41,7 @@ struct CumulativeDeltaPoint {
 
 pub struct CumulativeDeltaIndicator {
     cache: Caches,
+    color: Option<Color>,
     /// Per-bucket delta. Stored separately so inserting/replacing older klines can
     /// rebuild the cumulative line without needing the full chart source.
     delta: BasisSeries<Qty>,
@@ -48,9 +50,10 @@ pub struct CumulativeDeltaIndicator {
 }
 
 impl CumulativeDeltaIndicator {
-    pub fn new() -> Self {
+    pub fn new(color: Option<Color>) -> Self {
         Self {
             cache: Caches::default(),
+            color,
             delta: BasisSeries::default(),
             data: BasisSeries::default(),
             availability: IndicatorAvailability::Unknown,
@@ -77,6 +80,7 @@ impl CumulativeDeltaIndicator {
         let value_fn = |point: &CumulativeDeltaPoint| point.cumulative.to_f64() as f32;
 
         let plot = LinePlot::new(value_fn)
+            .color(self.color)
             .stroke_width(1.0)
             .show_points(true)
             .point_radius_factor(0.2)
@@ -0,0 +1,191 @@
+use crate::chart::{
+    Basis, Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{AvailabilityCause, FetchCtx, IndicatorAvailability, KlineIndicatorImpl},
+        plot::{AnySeries, PlotTooltip, line::LinePlot},
+    },
+};
+use crate::connector::fetcher::FetchRange;
+
+use data::chart::{PlotData, kline::KlineDataPoint};
+use exchange::adapter::Exchange;
+use exchange::{FundingRate, Kline, Trade, UnixMs};
+
+use iced::Color;
+use iced::widget::{center, row, text};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// Shortest funding interval among supported exchanges
+const MIN_INTERVAL_MS: u64 = 60 * 60 * 1000;
+
+pub struct FundingRateIndicator {
+    cache: Caches,
+    color: Option<Color>,
+    /// Rate in percent, keyed by settlement time
+    pub data: BTreeMap<UnixMs, f64>,
+}
+
+impl FundingRateIndicator {
+    pub fn new(color: Option<Color>) -> Self {
+        Self {
+            cache: Caches::default(),
+            color,
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        if let Some(message) = self.unavailable_message(main_chart, "Funding Rate") {
+            return center(text(message)).into();
+        }
+
+        let (earliest, latest) = visible_range.clone().into_inner();
+        if latest < earliest {
+            return row![].into();
+        }
+
+        let tooltip = |value: &f64, _next: Option<&f64>| {
+            PlotTooltip::new(format!("Funding Rate: {value:+.4}%"))
+        };
+
+        let value_fn = |v: &f64| *v as f32;
+
+        let plot = LinePlot::new(value_fn)
+            .color(self.color)
+            .stroke_width(1.0)
+            .show_points(true)
+            .point_radius_factor(0.2)
+            .padding(0.08)
+            .with_tooltip(tooltip);
+
+        indicator_row(
+            main_chart,
+            &self.cache,
+            data_labels_always_visible,
+            plot,
+            AnySeries::forward_unix_ms(&self.data),
+            visible_range,
+        )
+    }
+
+    pub(super) fn is_supported_exchange(exchange: Exchange) -> bool {
+        matches!(
+            exchange,
+            Exchange::BinanceLinear
+                | Exchange::BinanceInverse
+                | Exchange::BybitLinear
+                | Exchange::BybitInverse
+        )
+    }
+
+    fn availability_for(basis: Basis, exchange: Exchange) -> IndicatorAvailability {
+        match basis {
+            Basis::Tick(_) | Basis::Bars(_) => {
+                IndicatorAvailability::Unavailable(AvailabilityCause::Basis(basis))
+            }
+            Basis::Time(_) if !Self::is_supported_exchange(exchange) => {
+                IndicatorAvailability::Unavailable(AvailabilityCause::Exchange(exchange))
+            }
+            Basis::Time(_) => IndicatorAvailability::Available,
+        }
+    }
+}
+
+impl KlineIndicatorImpl for FundingRateIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, data_labels_always_visible, visible_range)
+    }
+
+    fn availability(&self, chart: &ViewState) -> IndicatorAvailability {
+        Self::availability_for(chart.basis, chart.ticker_info.exchange())
+    }
+
+    fn fetch_range(&mut self, ctx: &FetchCtx) -> Option<FetchRange> {
+        let availability = Self::availability_for(
+            Basis::Time(ctx.timeframe),
+            ctx.main_chart.ticker_info.exchange(),
+        );
+        if !matches!(availability, IndicatorAvailability::Available) {
+            return None;
+        }
+
+        missing_range(&self.data, ctx)
+    }
+
+    fn rebuild_from_source(&mut self, _source: &PlotData<KlineDataPoint>) {
+        // funding is fetched separately, like open interest
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline], _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        _source: &PlotData<KlineDataPoint>,
+    ) {
+    }
+
+    fn on_ticksize_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_funding_rate(&mut self, data: &[FundingRate]) {
+        self.data.extend(
+            data.iter()
+                .map(|funding| (funding.time, funding.rate * 100.0)),
+        );
+        self.clear_all_caches();
+    }
+}
+
+/// Range still to be fetched for the visible candles, given the settlements loaded so far
+pub(super) fn missing_range(data: &BTreeMap<UnixMs, f64>, ctx: &FetchCtx) -> Option<FetchRange> {
+    let (Some(earliest), Some(latest)) = (
+        data.keys().next().copied(),
+        data.keys().next_back().copied(),
+    ) else {
+        return Some(FetchRange::FundingRate(
+            ctx.prefetch_earliest,
+            ctx.kline_latest,
+        ));
+    };
+
+    // Settlements are spaced by the funding interval, so gaps shorter than that are expected
+    let interval = data.keys().nth_back(1).map_or(MIN_INTERVAL_MS, |prev| {
+        latest.saturating_diff(*prev).max(MIN_INTERVAL_MS)
+    });
+
+    if earliest.saturating_diff(ctx.visible_earliest) > interval {
+        return Some(FetchRange::FundingRate(ctx.prefetch_earliest, earliest));
+    }
+
+    if ctx.kline_latest.saturating_diff(latest) > interval {
+        return Some(FetchRange::FundingRate(
+            latest.saturating_add(1),
+            ctx.kline_latest,
+        ));
+    }
+
+    None
+}
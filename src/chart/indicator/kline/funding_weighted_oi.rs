@@ -0,0 +1,175 @@
+use crate::chart::{
+    Basis, Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{
+            AvailabilityCause, FetchCtx, IndicatorAvailability, KlineIndicatorImpl, funding_rate,
+            open_interest,
+        },
+        plot::{AnySeries, PlotTooltip, line::LinePlot},
+    },
+};
+use crate::connector::fetcher::FetchRange;
+
+use data::chart::{PlotData, kline::KlineDataPoint};
+use data::util::format_with_commas;
+use exchange::{FundingRate, Kline, Trade, UnixMs};
+
+use iced::Color;
+use iced::widget::{center, row, text};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// Open interest scaled by the last settled funding rate, roughly what longs pay shorts per
+/// settlement. Large values of either sign point at crowded, aggressively built positions
+pub struct FundingWeightedOiIndicator {
+    cache: Caches,
+    color: Option<Color>,
+    open_interest: BTreeMap<UnixMs, f64>,
+    /// Rate as a fraction, keyed by settlement time
+    funding: BTreeMap<UnixMs, f64>,
+    data: BTreeMap<UnixMs, f64>,
+}
+
+impl FundingWeightedOiIndicator {
+    pub fn new(color: Option<Color>) -> Self {
+        Self {
+            cache: Caches::default(),
+            color,
+            open_interest: BTreeMap::new(),
+            funding: BTreeMap::new(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        if let Some(message) = self.unavailable_message(main_chart, "Funding-weighted OI") {
+            return center(text(message)).into();
+        }
+
+        let (earliest, latest) = visible_range.clone().into_inner();
+        if latest < earliest {
+            return row![].into();
+        }
+
+        let tooltip = |value: &f64, _next: Option<&f64>| {
+            PlotTooltip::new(format!("OI × Funding: {}", format_with_commas(*value)))
+        };
+
+        let plot = LinePlot::new(|v: &f64| *v as f32)
+            .color(self.color)
+            .stroke_width(1.0)
+            .show_points(true)
+            .point_radius_factor(0.2)
+            // Same alignment as the open interest it's derived from
+            .shift(-1)
+            .padding(0.08)
+            .with_tooltip(tooltip);
+
+        indicator_row(
+            main_chart,
+            &self.cache,
+            data_labels_always_visible,
+            plot,
+            AnySeries::forward_unix_ms(&self.data),
+            visible_range,
+        )
+    }
+
+    fn availability_for(
+        basis: Basis,
+        exchange: exchange::adapter::Exchange,
+    ) -> IndicatorAvailability {
+        match open_interest::OpenInterestIndicator::availability_for(basis, exchange) {
+            IndicatorAvailability::Available
+                if !funding_rate::FundingRateIndicator::is_supported_exchange(exchange) =>
+            {
+                IndicatorAvailability::Unavailable(AvailabilityCause::Exchange(exchange))
+            }
+            availability => availability,
+        }
+    }
+
+    /// Each open interest snapshot against the latest settlement before it
+    fn recompute(&mut self) {
+        self.data = self
+            .open_interest
+            .iter()
+            .filter_map(|(time, oi)| {
+                let (_, rate) = self.funding.range(..=*time).next_back()?;
+                Some((*time, oi * rate))
+            })
+            .collect();
+        self.clear_all_caches();
+    }
+}
+
+impl KlineIndicatorImpl for FundingWeightedOiIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, data_labels_always_visible, visible_range)
+    }
+
+    fn availability(&self, chart: &ViewState) -> IndicatorAvailability {
+        Self::availability_for(chart.basis, chart.ticker_info.exchange())
+    }
+
+    fn fetch_range(&mut self, ctx: &FetchCtx) -> Option<FetchRange> {
+        let availability = Self::availability_for(
+            Basis::Time(ctx.timeframe),
+            ctx.main_chart.ticker_info.exchange(),
+        );
+        if !matches!(availability, IndicatorAvailability::Available) {
+            return None;
+        }
+
+        open_interest::missing_range(&self.open_interest, ctx)
+            .or_else(|| funding_rate::missing_range(&self.funding, ctx))
+    }
+
+    fn rebuild_from_source(&mut self, _source: &PlotData<KlineDataPoint>) {
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline], _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        _source: &PlotData<KlineDataPoint>,
+    ) {
+    }
+
+    fn on_ticksize_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_open_interest(&mut self, data: &[exchange::OpenInterest]) {
+        self.open_interest
+            .extend(data.iter().map(|oi| (oi.time, oi.value)));
+        self.recompute();
+    }
+
+    fn on_funding_rate(&mut self, data: &[FundingRate]) {
+        self.funding
+            .extend(data.iter().map(|funding| (funding.time, funding.rate)));
+        self.recompute();
+    }
+}
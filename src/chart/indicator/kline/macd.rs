@@ -0,0 +1,131 @@
+use crate::chart::{
+    Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{
+            BasisSeries, BasisSeriesExt, KlineIndicatorImpl, derive_from_klines, source_column,
+        },
+        plot::{PlotTooltip, oscillator::OscillatorPlot},
+    },
+};
+
+use data::chart::indicator::{IndicatorSettings, PriceSource};
+use data::chart::oscillator::{self, Macd};
+use data::chart::{PlotData, kline::KlineDataPoint};
+use exchange::{Kline, Trade};
+
+use iced::Color;
+use std::ops::RangeInclusive;
+
+pub struct MacdIndicator {
+    cache: Caches,
+    fast: u16,
+    slow: u16,
+    signal: u16,
+    source: PriceSource,
+    color: Option<Color>,
+    data: BasisSeries<Macd>,
+}
+
+impl MacdIndicator {
+    pub fn new(settings: IndicatorSettings) -> Self {
+        let periods = settings.periods;
+        Self {
+            cache: Caches::default(),
+            fast: periods.macd_fast,
+            slow: periods.macd_slow,
+            signal: periods.macd_signal,
+            source: settings.source,
+            color: settings.color,
+            data: BasisSeries::default(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        // The line is a difference of prices, finer than the tick size is still telling
+        let decimals = main_chart.decimals + 1;
+        let label = format!("MACD({}, {}, {})", self.fast, self.slow, self.signal);
+
+        let tooltip = move |point: &Macd, _next: Option<&Macd>| {
+            let mut text = format!("{label}: {:.*}", decimals, point.macd);
+            if let (Some(signal), Some(histogram)) = (point.signal, point.histogram()) {
+                text.push_str(&format!(
+                    "\nSignal: {signal:.decimals$}\nHistogram: {histogram:.decimals$}"
+                ));
+            }
+            PlotTooltip::new(text)
+        };
+
+        let plot = OscillatorPlot::new()
+            .histogram(|point: &Macd| point.histogram().map(|v| v as f32))
+            .line(|point: &Macd| Some(point.macd as f32))
+            .line(|point: &Macd| point.signal.map(|v| v as f32))
+            .levels(&[0.0])
+            .color(self.color)
+            .with_tooltip(tooltip);
+
+        indicator_row(
+            main_chart,
+            &self.cache,
+            data_labels_always_visible,
+            plot,
+            self.data.as_plot_series(),
+            visible_range,
+        )
+    }
+}
+
+impl KlineIndicatorImpl for MacdIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, data_labels_always_visible, visible_range)
+    }
+
+    fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        let (fast, slow, signal, price) = (self.fast, self.slow, self.signal, self.source);
+        self.data = derive_from_klines(source, |klines| {
+            oscillator::macd(&source_column(klines, price), fast, slow, signal)
+        });
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline], source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        source: &PlotData<KlineDataPoint>,
+    ) {
+        if let PlotData::TickBased(_) = source {
+            self.rebuild_from_source(source);
+        }
+    }
+
+    fn on_ticksize_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::chart::{
+    ViewState,
+    indicator::kline::{BasisSeries, BasisSeriesExt, derive_from_klines, price_columns},
+    indicator::plot::Series,
+};
+
+use data::chart::indicator::MovingAverage;
+use data::chart::{PlotData, kline::KlineDataPoint};
+use exchange::unit::Price;
+
+use iced::Point;
+use iced::widget::canvas::{self, Path, Stroke};
+
+/// Moving averages drawn over the candles, kept in step with the chart's data source
+#[derive(Default)]
+pub struct MovingAverageOverlay {
+    lines: Vec<(MovingAverage, BasisSeries<f64>)>,
+}
+
+impl MovingAverageOverlay {
+    pub fn lines(&self) -> Vec<MovingAverage> {
+        self.lines.iter().map(|(line, _)| *line).collect()
+    }
+
+    /// Replaces the drawn lines, returns whether anything changed
+    pub fn set_lines(
+        &mut self,
+        lines: &[MovingAverage],
+        source: &PlotData<KlineDataPoint>,
+    ) -> bool {
+        if self.lines.len() == lines.len() && self.lines.iter().zip(lines).all(|((a, _), b)| a == b)
+        {
+            return false;
+        }
+
+        self.lines = lines
+            .iter()
+            .map(|line| (*line, BasisSeries::default()))
+            .collect();
+        self.rebuild_from_source(source);
+        true
+    }
+
+    pub fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        for (line, series) in &mut self.lines {
+            *series = derive_from_klines(source, |klines| {
+                let (closes, _, _) = price_columns(klines);
+                line.compute(&closes)
+            });
+        }
+    }
+
+    /// Time based candles only change with the kline stream, tick based ones with trades
+    pub fn on_insert_trades(&mut self, source: &PlotData<KlineDataPoint>) {
+        if let PlotData::TickBased(_) = source {
+            self.rebuild_from_source(source);
+        }
+    }
+
+    pub fn draw(&self, frame: &mut canvas::Frame, chart: &ViewState, earliest: u64, latest: u64) {
+        if latest < earliest {
+            return;
+        }
+
+        for (line, series) in &self.lines {
+            let stroke = Stroke::with_color(Stroke::default(), line.color);
+            let series = series.as_plot_series();
+            let mut prev: Option<Point> = None;
+
+            // One bar past each edge so the line reaches out of view
+            let padded = match chart.basis {
+                data::chart::Basis::Time(tf) => {
+                    earliest.saturating_sub(tf.to_milliseconds())
+                        ..=latest.saturating_add(tf.to_milliseconds())
+                }
+                data::chart::Basis::Tick(_) | data::chart::Basis::Bars(_) => {
+                    earliest.saturating_sub(1)..=latest.saturating_add(1)
+                }
+            };
+
+            series.for_each_in(padded, |x, value| {
+                let point = Point::new(
+                    chart.interval_to_x(x),
+                    chart.price_to_y(Price::from_f64(*value)),
+                );
+                if let Some(prev) = prev {
+                    frame.stroke(&Path::line(prev, point), stroke);
+                }
+                prev = Some(point);
+            });
+        }
+    }
+}
@@ -13,18 +13,21 @@ use data::util::format_with_commas;
 use exchange::adapter::Exchange;
 use exchange::{Kline, Timeframe, Trade, UnixMs};
 
+use iced::Color;
 use iced::widget::{center, row, text};
 use std::{collections::BTreeMap, ops::RangeInclusive};
 
 pub struct OpenInterestIndicator {
     cache: Caches,
+    color: Option<Color>,
     pub data: BTreeMap<UnixMs, f64>,
 }
 
 impl OpenInterestIndicator {
-    pub fn new() -> Self {
+    pub fn new(color: Option<Color>) -> Self {
         Self {
             cache: Caches::default(),
+            color,
             data: BTreeMap::new(),
         }
     }
@@ -59,6 +62,7 @@ impl OpenInterestIndicator {
         let value_fn = |v: &f64| *v as f32;
 
         let plot = LinePlot::new(value_fn)
+            .color(self.color)
             .stroke_width(1.0)
             .show_points(true)
             .point_radius_factor(0.2)
@@ -78,32 +82,23 @@ impl OpenInterestIndicator {
         )
     }
 
-    // helper to compute (earliest, latest) present OI keys
-    fn oi_timerange(&self, latest_kline: UnixMs) -> (UnixMs, UnixMs) {
-        let mut from_time = latest_kline;
-        let mut to_time = UnixMs::ZERO;
-
-        self.data.iter().for_each(|(time, _)| {
-            from_time = from_time.min(*time);
-            to_time = to_time.max(*time);
-        });
-        (from_time, to_time)
-    }
-
-    fn is_supported_exchange(exchange: Exchange) -> bool {
+    pub(super) fn is_supported_exchange(exchange: Exchange) -> bool {
         exchange.is_perps()
             && exchange != Exchange::HyperliquidLinear
             && exchange != Exchange::MexcLinear
             && exchange != Exchange::MexcInverse
+            && exchange != Exchange::PluginLinear
     }
 
     fn is_supported_timeframe(timeframe: Timeframe) -> bool {
         timeframe >= Timeframe::M5 && timeframe <= Timeframe::H4 && timeframe != Timeframe::H2
     }
 
-    fn availability_for(basis: Basis, exchange: Exchange) -> IndicatorAvailability {
+    pub(super) fn availability_for(basis: Basis, exchange: Exchange) -> IndicatorAvailability {
         match basis {
-            Basis::Tick(_) => IndicatorAvailability::Unavailable(AvailabilityCause::Basis(basis)),
+            Basis::Tick(_) | Basis::Bars(_) => {
+                IndicatorAvailability::Unavailable(AvailabilityCause::Basis(basis))
+            }
             Basis::Time(timeframe) => {
                 if !Self::is_supported_exchange(exchange) {
                     IndicatorAvailability::Unavailable(AvailabilityCause::Exchange(exchange))
@@ -148,20 +143,7 @@ impl KlineIndicatorImpl for OpenInterestIndicator {
             return None;
         }
 
-        let (oi_earliest, oi_latest) = self.oi_timerange(ctx.kline_latest);
-
-        if ctx.visible_earliest < oi_earliest {
-            return Some(FetchRange::OpenInterest(ctx.prefetch_earliest, oi_earliest));
-        }
-
-        if oi_latest < ctx.kline_latest {
-            return Some(FetchRange::OpenInterest(
-                oi_latest.max(ctx.prefetch_earliest),
-                ctx.kline_latest,
-            ));
-        }
-
-        None
+        missing_range(&self.data, ctx)
     }
 
     fn rebuild_from_source(&mut self, _source: &PlotData<KlineDataPoint>) {
@@ -188,3 +170,25 @@ impl KlineIndicatorImpl for OpenInterestIndicator {
         self.clear_all_caches();
     }
 }
+
+/// Range still to be fetched for the visible candles, given the open interest loaded so far
+pub(super) fn missing_range(data: &BTreeMap<UnixMs, f64>, ctx: &FetchCtx) -> Option<FetchRange> {
+    let oi_earliest = data
+        .keys()
+        .next()
+        .map_or(ctx.kline_latest, |time| (*time).min(ctx.kline_latest));
+    let oi_latest = data.keys().next_back().copied().unwrap_or(UnixMs::ZERO);
+
+    if ctx.visible_earliest < oi_earliest {
+        return Some(FetchRange::OpenInterest(ctx.prefetch_earliest, oi_earliest));
+    }
+
+    if oi_latest < ctx.kline_latest {
+        return Some(FetchRange::OpenInterest(
+            oi_latest.max(ctx.prefetch_earliest),
+            ctx.kline_latest,
+        ));
+    }
+
+    None
+}
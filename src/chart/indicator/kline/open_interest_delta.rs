@@ -0,0 +1,139 @@
+use crate::chart::{
+    Basis, Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{FetchCtx, IndicatorAvailability, KlineIndicatorImpl, open_interest},
+        plot::{AnySeries, PlotTooltip, oscillator::OscillatorPlot},
+    },
+};
+use crate::connector::fetcher::FetchRange;
+
+use data::chart::{PlotData, kline::KlineDataPoint};
+use data::util::format_with_commas;
+use exchange::{Kline, Trade, UnixMs};
+
+use iced::widget::{center, row, text};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// Change of open interest over each candle, positions opened above zero and closed below
+pub struct OpenInterestDeltaIndicator {
+    cache: Caches,
+    open_interest: BTreeMap<UnixMs, f64>,
+    /// Keyed by the candle the change happened in
+    data: BTreeMap<UnixMs, f64>,
+}
+
+impl OpenInterestDeltaIndicator {
+    pub fn new() -> Self {
+        Self {
+            cache: Caches::default(),
+            open_interest: BTreeMap::new(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        if let Some(message) = self.unavailable_message(main_chart, "OI Delta") {
+            return center(text(message)).into();
+        }
+
+        let (earliest, latest) = visible_range.clone().into_inner();
+        if latest < earliest {
+            return row![].into();
+        }
+
+        let tooltip = |delta: &f64, _next: Option<&f64>| {
+            let sign = if *delta >= 0.0 { "+" } else { "" };
+            PlotTooltip::new(format!("OI Change: {sign}{}", format_with_commas(*delta)))
+        };
+
+        let plot = OscillatorPlot::new()
+            .histogram(|delta: &f64| Some(*delta as f32))
+            .levels(&[0.0])
+            .with_tooltip(tooltip);
+
+        indicator_row(
+            main_chart,
+            &self.cache,
+            data_labels_always_visible,
+            plot,
+            AnySeries::forward_unix_ms(&self.data),
+            visible_range,
+        )
+    }
+}
+
+impl KlineIndicatorImpl for OpenInterestDeltaIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, data_labels_always_visible, visible_range)
+    }
+
+    fn availability(&self, chart: &ViewState) -> IndicatorAvailability {
+        open_interest::OpenInterestIndicator::availability_for(
+            chart.basis,
+            chart.ticker_info.exchange(),
+        )
+    }
+
+    fn fetch_range(&mut self, ctx: &FetchCtx) -> Option<FetchRange> {
+        let availability = open_interest::OpenInterestIndicator::availability_for(
+            Basis::Time(ctx.timeframe),
+            ctx.main_chart.ticker_info.exchange(),
+        );
+        if !matches!(availability, IndicatorAvailability::Available) {
+            return None;
+        }
+
+        open_interest::missing_range(&self.open_interest, ctx)
+    }
+
+    fn rebuild_from_source(&mut self, _source: &PlotData<KlineDataPoint>) {
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline], _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        _source: &PlotData<KlineDataPoint>,
+    ) {
+    }
+
+    fn on_ticksize_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_open_interest(&mut self, data: &[exchange::OpenInterest]) {
+        self.open_interest
+            .extend(data.iter().map(|oi| (oi.time, oi.value)));
+
+        // Snapshots are taken at candle open, the next one is where the candle left it
+        self.data = self
+            .open_interest
+            .iter()
+            .zip(self.open_interest.values().skip(1))
+            .map(|((time, open), close)| (*time, close - open))
+            .collect();
+        self.clear_all_caches();
+    }
+}
@@ -0,0 +1,316 @@
+use crate::chart::{
+    ViewState,
+    indicator::kline::{BasisSeries, BasisSeriesExt},
+    indicator::plot::Series,
+};
+use crate::style;
+
+use data::chart::script::{self, Bars, Script, ScriptError, ScriptLevel, ScriptOutput};
+use data::chart::{Basis, PlotData, kline::KlineDataPoint};
+use exchange::UnixMs;
+use exchange::unit::Price;
+
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Path, Stroke};
+use iced::{Alignment, Color, Point, Rectangle};
+
+/// User scripts drawn over the candles. Live data only reruns them once a bar closes,
+/// a script walks the whole history on the UI thread
+#[derive(Default)]
+pub struct ScriptOverlay {
+    /// Scripts found in the scripts folder when last looked
+    available: Vec<String>,
+    scripts: Vec<LoadedScript>,
+    /// Bars in the source when the scripts last ran
+    bars: usize,
+}
+
+struct LoadedScript {
+    name: String,
+    compiled: Result<Script, ScriptError>,
+    drawn: Result<Drawn, ScriptError>,
+}
+
+/// A run's output keyed like the chart's candles
+struct Drawn {
+    lines: Vec<(Option<Color>, BasisSeries<f64>)>,
+    levels: Vec<ScriptLevel>,
+    /// Interval of the bar, price, label and color
+    markers: Vec<(u64, f64, String, Option<Color>)>,
+}
+
+impl ScriptOverlay {
+    pub fn new() -> Self {
+        Self {
+            available: script::available(),
+            scripts: vec![],
+            bars: 0,
+        }
+    }
+
+    pub fn available(&self) -> &[String] {
+        &self.available
+    }
+
+    pub fn enabled(&self) -> Vec<String> {
+        self.scripts.iter().map(|s| s.name.clone()).collect()
+    }
+
+    /// Names of the enabled scripts that failed, with what went wrong
+    pub fn errors(&self) -> Vec<(String, String)> {
+        self.scripts
+            .iter()
+            .filter_map(|s| {
+                let err = s.compiled.as_ref().err().or(s.drawn.as_ref().err())?;
+                Some((s.name.clone(), err.to_string()))
+            })
+            .collect()
+    }
+
+    /// Replaces the enabled scripts, returns whether anything changed. Scripts kept
+    /// enabled aren't read again
+    pub fn set_scripts(&mut self, names: &[String], source: &PlotData<KlineDataPoint>) -> bool {
+        if self.scripts.len() == names.len()
+            && self
+                .scripts
+                .iter()
+                .zip(names)
+                .all(|(s, name)| &s.name == name)
+        {
+            return false;
+        }
+
+        let mut previous = std::mem::take(&mut self.scripts);
+        self.scripts = names
+            .iter()
+            .map(|name| match previous.iter().position(|s| &s.name == name) {
+                Some(index) => previous.swap_remove(index),
+                None => {
+                    let compiled = Script::load(name);
+                    let drawn = run(&compiled, source);
+                    LoadedScript {
+                        name: name.clone(),
+                        compiled,
+                        drawn,
+                    }
+                }
+            })
+            .collect();
+        true
+    }
+
+    /// Looks through the scripts folder again and recompiles the enabled scripts from disk
+    pub fn reload(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.available = script::available();
+
+        for loaded in &mut self.scripts {
+            loaded.compiled = Script::load(&loaded.name);
+        }
+        self.rebuild_from_source(source);
+    }
+
+    pub fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.bars = bar_count(source);
+        for loaded in &mut self.scripts {
+            loaded.drawn = run(&loaded.compiled, source);
+        }
+    }
+
+    pub fn on_insert_trades(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rerun_on_new_bar(source);
+    }
+
+    pub fn on_insert_klines(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rerun_on_new_bar(source);
+    }
+
+    /// Updates to the forming bar are left out until the next one opens
+    fn rerun_on_new_bar(&mut self, source: &PlotData<KlineDataPoint>) {
+        if bar_count(source) != self.bars {
+            self.rebuild_from_source(source);
+        }
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        chart: &ViewState,
+        earliest: u64,
+        latest: u64,
+        region: &Rectangle,
+        palette: &Extended,
+    ) {
+        if latest < earliest {
+            return;
+        }
+
+        let padded = match chart.basis {
+            Basis::Time(tf) => {
+                earliest.saturating_sub(tf.to_milliseconds())
+                    ..=latest.saturating_add(tf.to_milliseconds())
+            }
+            Basis::Tick(_) | Basis::Bars(_) => {
+                earliest.saturating_sub(1)..=latest.saturating_add(1)
+            }
+        };
+        let default_color = |index: usize| style::PANE_TINTS[index % style::PANE_TINTS.len()];
+
+        let drawn = self.scripts.iter().filter_map(|s| s.drawn.as_ref().ok());
+        for (index, drawn) in drawn.enumerate() {
+            for (color, series) in &drawn.lines {
+                let stroke =
+                    Stroke::with_color(Stroke::default(), color.unwrap_or(default_color(index)));
+                let mut prev: Option<(u64, Point)> = None;
+
+                series
+                    .as_plot_series()
+                    .for_each_in(padded.clone(), |x, value| {
+                        let point = Point::new(
+                            chart.interval_to_x(x),
+                            chart.price_to_y(Price::from_f64(*value)),
+                        );
+                        // A gap in the values leaves one between the bars around it
+                        if let Some((prev_x, prev_point)) = prev
+                            && is_next_bar(chart.basis, prev_x, x)
+                        {
+                            frame.stroke(&Path::line(prev_point, point), stroke);
+                        }
+                        prev = Some((x, point));
+                    });
+            }
+
+            for level in &drawn.levels {
+                let y = chart.price_to_y(Price::from_f64(level.price));
+                frame.stroke(
+                    &Path::line(
+                        Point::new(region.x, y),
+                        Point::new(region.x + region.width, y),
+                    ),
+                    Stroke::with_color(
+                        style::dashed_line_from_palette(palette),
+                        level.color.unwrap_or(default_color(index)),
+                    ),
+                );
+            }
+
+            let size = 3.0 / chart.scaling;
+            for (interval, price, label, color) in &drawn.markers {
+                if !padded.contains(interval) {
+                    continue;
+                }
+                let color = color.unwrap_or(palette.primary.strong.color);
+                let point = Point::new(
+                    chart.interval_to_x(*interval),
+                    chart.price_to_y(Price::from_f64(*price)),
+                );
+
+                frame.fill(&Path::circle(point, size), color);
+                if !label.is_empty() {
+                    frame.fill_text(canvas::Text {
+                        content: label.clone(),
+                        position: Point::new(point.x + 2.0 * size, point.y),
+                        size: iced::Pixels(style::text_size::SMALL / chart.scaling),
+                        color,
+                        align_y: Alignment::Center.into(),
+                        font: style::AZERET_MONO,
+                        ..canvas::Text::default()
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn bar_count(source: &PlotData<KlineDataPoint>) -> usize {
+    match source {
+        PlotData::TimeBased(timeseries) => timeseries.datapoints.len(),
+        PlotData::TickBased(tick_aggr) => tick_aggr.datapoints.len(),
+    }
+}
+
+/// Whether `to` is the bar right after `from`, in the order the series is walked
+fn is_next_bar(basis: Basis, from: u64, to: u64) -> bool {
+    match basis {
+        Basis::Time(tf) => to.saturating_sub(from) <= tf.to_milliseconds(),
+        Basis::Tick(_) | Basis::Bars(_) => from.abs_diff(to) <= 1,
+    }
+}
+
+fn run(
+    compiled: &Result<Script, ScriptError>,
+    source: &PlotData<KlineDataPoint>,
+) -> Result<Drawn, ScriptError> {
+    let compiled = compiled.as_ref().map_err(Clone::clone)?;
+
+    let mut bars = Bars::default();
+    let times = match source {
+        PlotData::TimeBased(timeseries) => {
+            timeseries
+                .datapoints
+                .values()
+                .for_each(|dp| bars.push(&dp.kline, &dp.footprint));
+            Some(
+                timeseries
+                    .datapoints
+                    .keys()
+                    .copied()
+                    .collect::<Vec<UnixMs>>(),
+            )
+        }
+        PlotData::TickBased(tick_aggr) => {
+            tick_aggr
+                .datapoints
+                .iter()
+                .for_each(|dp| bars.push(&dp.kline, &dp.footprint));
+            None
+        }
+    };
+    let bar_count = bars.len();
+
+    let ScriptOutput {
+        lines,
+        levels,
+        markers,
+    } = compiled.run(bars)?;
+
+    // Bars are counted from the oldest, tick intervals from the latest
+    let interval_of = |bar: usize| match &times {
+        Some(times) => times.get(bar).map(|time| time.as_u64()),
+        None => bar_count.checked_sub(bar + 1).map(|index| index as u64),
+    };
+
+    Ok(Drawn {
+        lines: lines
+            .into_iter()
+            .map(|line| {
+                let values = line.values.into_iter().enumerate();
+                let series = match &times {
+                    Some(times) => BasisSeries::time(
+                        values
+                            .filter_map(|(bar, value)| Some((*times.get(bar)?, value?)))
+                            .collect(),
+                    ),
+                    None => BasisSeries::tick(
+                        values
+                            .take(bar_count)
+                            .filter_map(|(bar, value)| Some((bar as u64, value?)))
+                            .collect(),
+                    ),
+                };
+                (line.color, series)
+            })
+            .collect(),
+        levels,
+        markers: markers
+            .into_iter()
+            .filter_map(|marker| {
+                Some((
+                    interval_of(marker.bar)?,
+                    marker.price,
+                    marker.label,
+                    marker.color,
+                ))
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,132 @@
+use crate::chart::{
+    Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{
+            BasisSeries, BasisSeriesExt, KlineIndicatorImpl, derive_from_klines, price_columns,
+        },
+        plot::{PlotTooltip, oscillator::OscillatorPlot},
+    },
+};
+
+use data::chart::indicator::IndicatorSettings;
+use data::chart::oscillator::{self, Stochastic};
+use data::chart::{PlotData, kline::KlineDataPoint};
+use exchange::{Kline, Trade};
+
+use iced::Color;
+use std::ops::RangeInclusive;
+
+const OVERBOUGHT: f32 = 80.0;
+const OVERSOLD: f32 = 20.0;
+
+pub struct StochasticIndicator {
+    cache: Caches,
+    k_period: u16,
+    smoothing: u16,
+    d_period: u16,
+    color: Option<Color>,
+    data: BasisSeries<Stochastic>,
+}
+
+impl StochasticIndicator {
+    pub fn new(settings: IndicatorSettings) -> Self {
+        let periods = settings.periods;
+        Self {
+            cache: Caches::default(),
+            k_period: periods.stoch_k,
+            smoothing: periods.stoch_smoothing,
+            d_period: periods.stoch_d,
+            color: settings.color,
+            data: BasisSeries::default(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        let label = format!(
+            "Stoch({}, {}, {})",
+            self.k_period, self.smoothing, self.d_period
+        );
+
+        let tooltip = move |point: &Stochastic, _next: Option<&Stochastic>| {
+            let mut text = format!("{label} %K: {:.2}", point.k);
+            if let Some(d) = point.d {
+                text.push_str(&format!("\n%D: {d:.2}"));
+            }
+            PlotTooltip::new(text)
+        };
+
+        let plot = OscillatorPlot::new()
+            .line(|point: &Stochastic| Some(point.k as f32))
+            .line(|point: &Stochastic| point.d.map(|v| v as f32))
+            .bounds(0.0, 100.0)
+            .color(self.color)
+            .levels(&[OVERSOLD, OVERBOUGHT])
+            .with_tooltip(tooltip);
+
+        indicator_row(
+            main_chart,
+            &self.cache,
+            data_labels_always_visible,
+            plot,
+            self.data.as_plot_series(),
+            visible_range,
+        )
+    }
+}
+
+impl KlineIndicatorImpl for StochasticIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        data_labels_always_visible: bool,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, data_labels_always_visible, visible_range)
+    }
+
+    fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        let (k_period, smoothing, d_period) = (self.k_period, self.smoothing, self.d_period);
+        self.data = derive_from_klines(source, |klines| {
+            let (closes, highs, lows) = price_columns(klines);
+            oscillator::stochastic(&highs, &lows, &closes, k_period, smoothing, d_period)
+        });
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline], source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        source: &PlotData<KlineDataPoint>,
+    ) {
+        if let PlotData::TickBased(_) = source {
+            self.rebuild_from_source(source);
+        }
+    }
+
+    fn on_ticksize_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::chart::{
+    ViewState,
+    indicator::kline::{BasisSeries, BasisSeriesExt},
+    indicator::plot::Series,
+};
+
+use data::chart::Basis;
+use data::chart::vwap::{VwapAccumulator, VwapConfig, VwapPoint};
+use exchange::Trade;
+use exchange::unit::Price;
+
+use iced::Point;
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Path, Stroke};
+
+/// Session VWAP drawn over the candles, fed by the chart's raw trades rather than its klines
+#[derive(Default)]
+pub struct VwapOverlay {
+    config: Option<VwapConfig>,
+    accumulator: VwapAccumulator,
+    /// Trades taken in so far, tick based bars are consecutive runs of them
+    trade_count: usize,
+    series: BasisSeries<VwapPoint>,
+}
+
+impl VwapOverlay {
+    pub fn config(&self) -> Option<VwapConfig> {
+        self.config
+    }
+
+    /// Returns whether anything changed
+    pub fn set_config(
+        &mut self,
+        config: Option<VwapConfig>,
+        basis: Basis,
+        trades: &[Trade],
+    ) -> bool {
+        if self.config == config {
+            return false;
+        }
+
+        self.config = config;
+        self.rebuild(basis, trades);
+        true
+    }
+
+    pub fn rebuild(&mut self, basis: Basis, trades: &[Trade]) {
+        self.accumulator = VwapAccumulator::default();
+        self.trade_count = 0;
+        self.series = match basis {
+            Basis::Time(_) => BasisSeries::time(Default::default()),
+            Basis::Tick(_) | Basis::Bars(_) => BasisSeries::tick(Default::default()),
+        };
+
+        if self.config.is_none() {
+            return;
+        }
+
+        match basis {
+            // Fetched history is appended after the live trades
+            Basis::Time(_) => {
+                let mut sorted = trades.iter().collect::<Vec<_>>();
+                sorted.sort_by_key(|trade| trade.time);
+                sorted
+                    .into_iter()
+                    .for_each(|trade| self.add_trade(basis, trade));
+            }
+            // Tick bars are built in arrival order, so the average has to follow it
+            Basis::Tick(_) => trades.iter().for_each(|trade| self.add_trade(basis, trade)),
+            // Trades don't map to bricks or columns by their count
+            Basis::Bars(_) => {}
+        }
+    }
+
+    pub fn on_insert_trades(&mut self, basis: Basis, buffer: &[Trade]) {
+        if self.config.is_some() {
+            buffer.iter().for_each(|trade| self.add_trade(basis, trade));
+        }
+    }
+
+    fn add_trade(&mut self, basis: Basis, trade: &Trade) {
+        let Some(config) = self.config else {
+            return;
+        };
+
+        let index = self.trade_count;
+        self.trade_count += 1;
+
+        let Some(point) = self.accumulator.add(trade, config.anchor) else {
+            return;
+        };
+
+        match basis {
+            Basis::Time(timeframe) => {
+                if let Some(series) = self.series.time_mut() {
+                    series.insert(trade.time.floor_to(timeframe), point);
+                }
+            }
+            Basis::Tick(count) => {
+                if let Some(series) = self.series.tick_mut() {
+                    series.insert((index / usize::from(count.0.max(1))) as u64, point);
+                }
+            }
+            Basis::Bars(_) => {}
+        }
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        chart: &ViewState,
+        earliest: u64,
+        latest: u64,
+        palette: &Extended,
+    ) {
+        let Some(config) = self.config else {
+            return;
+        };
+        if latest < earliest {
+            return;
+        }
+
+        let padded = match chart.basis {
+            Basis::Time(tf) => {
+                earliest.saturating_sub(tf.to_milliseconds())
+                    ..=latest.saturating_add(tf.to_milliseconds())
+            }
+            Basis::Tick(_) | Basis::Bars(_) => {
+                earliest.saturating_sub(1)..=latest.saturating_add(1)
+            }
+        };
+
+        let color = palette.warning.base.color;
+        let line = Stroke::with_color(
+            Stroke {
+                width: 1.5,
+                ..Stroke::default()
+            },
+            color,
+        );
+        let band = Stroke::with_color(Stroke::default(), color.scale_alpha(0.4));
+
+        let mut prev: Option<VwapPoint> = None;
+        let mut prev_x = 0.0;
+
+        self.series
+            .as_plot_series()
+            .for_each_in(padded, |x, point| {
+                let x = chart.interval_to_x(x);
+
+                // A new session starts from scratch, it isn't joined to the last one
+                if let Some(prev) = prev.filter(|prev| prev.session == point.session) {
+                    let mut segment = |from: f64, to: f64, stroke: Stroke| {
+                        frame.stroke(
+                            &Path::line(
+                                Point::new(prev_x, chart.price_to_y(Price::from_f64(from))),
+                                Point::new(x, chart.price_to_y(Price::from_f64(to))),
+                            ),
+                            stroke,
+                        );
+                    };
+
+                    segment(prev.vwap, point.vwap, line);
+
+                    if config.bands {
+                        for multiple in [1.0, 2.0] {
+                            let (prev_upper, prev_lower) = prev.band(multiple);
+                            let (upper, lower) = point.band(multiple);
+                            segment(prev_upper, upper, band);
+                            segment(prev_lower, lower, band);
+                        }
+                    }
+                }
+
+                prev = Some(*point);
+                prev_x = x;
+            });
+    }
+}
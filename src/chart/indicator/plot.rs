@@ -10,6 +10,7 @@ use std::ops::RangeInclusive;
 
 pub mod bar;
 pub mod line;
+pub mod oscillator;
 
 pub trait Series {
     type Y;
@@ -359,7 +360,7 @@ where
             canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
                 let msg = matches!(*interaction, Interaction::None)
                     .then(|| cursor.is_over(bounds))
-                    .and_then(|over| over.then_some(Message::CrosshairMoved));
+                    .and_then(|over| over.then_some(Message::CrosshairMoved(None)));
                 let action = msg.map_or(canvas::Action::request_redraw(), canvas::Action::publish);
                 Some(match interaction {
                     Interaction::None => action,
@@ -446,7 +447,7 @@ where
                         };
                         (rx, sr)
                     }
-                    Basis::Tick(_) => {
+                    Basis::Tick(_) | Basis::Bars(_) => {
                         let world_x = region.x + (cursor_position.x / bounds.width) * region.width;
                         let snapped_world_x = (world_x / ctx.cell_width).round() * ctx.cell_width;
 
@@ -475,20 +476,22 @@ where
                                 Basis::Time(_) if rounded_x >= earliest => {
                                     self.series.last_in(earliest..=rounded_x)
                                 }
-                                Basis::Time(_) | Basis::Tick(_) => None,
+                                Basis::Time(_) | Basis::Tick(_) | Basis::Bars(_) => None,
                             })
                     })
                     .flatten()
                     .or_else(|| {
                         let right_of_latest = match ctx.basis {
                             Basis::Time(_) => rounded_x > latest,
-                            Basis::Tick(_) => rounded_x < earliest,
+                            Basis::Tick(_) | Basis::Bars(_) => rounded_x < earliest,
                         };
 
                         right_of_latest
                             .then(|| match ctx.basis {
                                 Basis::Time(_) => self.series.last_in(earliest..=latest),
-                                Basis::Tick(_) => self.series.first_in(earliest..=latest),
+                                Basis::Tick(_) | Basis::Bars(_) => {
+                                    self.series.first_in(earliest..=latest)
+                                }
                             })
                             .flatten()
                     });
@@ -529,7 +532,7 @@ where
             } else if self.data_labels_always_visible
                 && let Some((x, y)) = match ctx.basis {
                     Basis::Time(_) => self.series.last_in(earliest..=latest),
-                    Basis::Tick(_) => self.series.first_in(earliest..=latest),
+                    Basis::Tick(_) | Basis::Bars(_) => self.series.first_in(earliest..=latest),
                 }
             {
                 if self.plot.is_point_valid(y) {
@@ -1,7 +1,7 @@
 use std::ops::RangeInclusive;
 
 use iced::{
-    Theme,
+    Color, Theme,
     widget::canvas::{self, Path, Stroke},
 };
 
@@ -35,6 +35,8 @@ pub struct LinePlot<V, T> {
     /// always-visible label lands on) an invalid point.  When `None` (the
     /// default) the tooltip is suppressed entirely.
     pub invalid_point_message: Option<String>,
+    /// Overrides the theme's line color
+    pub color: Option<Color>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -51,6 +53,7 @@ impl<V, T> LinePlot<V, T> {
             x_shift_buckets: 0,
             is_valid: None,
             invalid_point_message: None,
+            color: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -108,6 +111,11 @@ impl<V, T> LinePlot<V, T> {
         self
     }
 
+    pub fn color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
     pub fn with_tooltip<F>(mut self, tooltip: F) -> Self
     where
         F: Fn(&T, Option<&T>) -> PlotTooltip + 'static,
@@ -165,7 +173,7 @@ where
         scale: &YScale,
     ) {
         let palette = theme.extended_palette();
-        let color = palette.secondary.strong.color;
+        let color = self.color.unwrap_or(palette.secondary.strong.color);
 
         let stroke = Stroke::with_color(
             Stroke {
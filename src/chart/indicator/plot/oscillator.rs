@@ -0,0 +1,212 @@
+use std::ops::RangeInclusive;
+
+use iced::{
+    Color, Point, Size, Theme,
+    widget::canvas::{self, Path, Stroke},
+};
+
+use crate::chart::{
+    ViewState,
+    indicator::plot::{Plot, PlotTooltip, Series, TooltipFn, YScale},
+};
+use crate::style::dashed_line;
+
+const DEFAULT_BAR_WIDTH_FACTOR: f32 = 0.9;
+
+/// Maps a datapoint to one of the plotted values, `None` while it's still warming up.
+type ValueFn<T> = Box<dyn Fn(&T) -> Option<f32>>;
+
+/// Up to a few lines over an optional zero-based histogram, e.g. MACD or Stochastic
+pub struct OscillatorPlot<T> {
+    /// Drawn in order, the first one in the color single line plots use
+    pub lines: Vec<ValueFn<T>>,
+    pub histogram: Option<ValueFn<T>>,
+    /// Fixed extents for bounded oscillators, otherwise fit to the visible values
+    pub bounds: Option<(f32, f32)>,
+    /// Dashed reference levels, e.g. overbought and oversold
+    pub levels: Vec<f32>,
+    pub padding: f32,
+    /// Overrides the theme's color of the first line
+    pub color: Option<Color>,
+    pub tooltip: Option<TooltipFn<T>>,
+}
+
+impl<T> OscillatorPlot<T> {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![],
+            histogram: None,
+            bounds: None,
+            levels: vec![],
+            padding: 0.08,
+            color: None,
+            tooltip: None,
+        }
+    }
+
+    pub fn line<F>(mut self, value: F) -> Self
+    where
+        F: Fn(&T) -> Option<f32> + 'static,
+    {
+        self.lines.push(Box::new(value));
+        self
+    }
+
+    pub fn histogram<F>(mut self, value: F) -> Self
+    where
+        F: Fn(&T) -> Option<f32> + 'static,
+    {
+        self.histogram = Some(Box::new(value));
+        self
+    }
+
+    pub fn bounds(mut self, min: f32, max: f32) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    pub fn levels(mut self, levels: &[f32]) -> Self {
+        self.levels = levels.to_vec();
+        self
+    }
+
+    pub fn color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_tooltip<F>(mut self, tooltip: F) -> Self
+    where
+        F: Fn(&T, Option<&T>) -> PlotTooltip + 'static,
+    {
+        self.tooltip = Some(Box::new(tooltip));
+        self
+    }
+}
+
+impl<S> Plot<S> for OscillatorPlot<S::Y>
+where
+    S: Series,
+{
+    fn y_extents(&self, datapoints: &S, range: RangeInclusive<u64>) -> Option<(f32, f32)> {
+        if let Some(bounds) = self.bounds {
+            return Some(bounds);
+        }
+
+        let mut min_v = f32::MAX;
+        let mut max_v = f32::MIN;
+
+        datapoints.for_each_in(range, |_, y| {
+            let values = self
+                .lines
+                .iter()
+                .chain(self.histogram.as_ref())
+                .filter_map(|value| value(y));
+
+            for v in values {
+                min_v = min_v.min(v);
+                max_v = max_v.max(v);
+            }
+        });
+
+        if min_v == f32::MAX {
+            None
+        } else if self.histogram.is_some() {
+            // Bars grow out of zero, keep it in view
+            Some((min_v.min(0.0), max_v.max(0.0)))
+        } else {
+            Some((min_v, max_v))
+        }
+    }
+
+    fn adjust_extents(&self, min: f32, max: f32) -> (f32, f32) {
+        if self.bounds.is_none() && self.padding > 0.0 && max > min {
+            let pad = (max - min) * self.padding;
+            (min - pad, max + pad)
+        } else {
+            (min, max)
+        }
+    }
+
+    fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        ctx: &ViewState,
+        theme: &Theme,
+        datapoints: &S,
+        range: RangeInclusive<u64>,
+        scale: &YScale,
+    ) {
+        let palette = theme.extended_palette();
+
+        if !self.levels.is_empty() {
+            let width = frame.width() / ctx.scaling;
+            let left = -ctx.translation.x - width / 2.0;
+            let dashed = dashed_line(theme);
+
+            for level in &self.levels {
+                let y = scale.to_y(*level);
+                frame.stroke(
+                    &Path::line(Point::new(left, y), Point::new(left + width, y)),
+                    dashed,
+                );
+            }
+        }
+
+        if let Some(histogram) = &self.histogram {
+            let bar_width = ctx.cell_width * DEFAULT_BAR_WIDTH_FACTOR;
+            let y_zero = scale.to_y(0.0);
+
+            datapoints.for_each_in(range.clone(), |x, y| {
+                let Some(value) = histogram(y) else {
+                    return;
+                };
+                let y_value = scale.to_y(value);
+                let color = if value >= 0.0 {
+                    palette.success.base.color
+                } else {
+                    palette.danger.base.color
+                };
+
+                frame.fill_rectangle(
+                    Point::new(ctx.interval_to_x(x) - bar_width / 2.0, y_value.min(y_zero)),
+                    Size::new(bar_width, (y_zero - y_value).abs()),
+                    color.scale_alpha(0.5),
+                );
+            });
+        }
+
+        let half_bar_width = (ctx.cell_width * DEFAULT_BAR_WIDTH_FACTOR) / 2.0;
+        let colors = [
+            self.color.unwrap_or(palette.secondary.strong.color),
+            palette.primary.base.color,
+        ];
+
+        for (idx, line) in self.lines.iter().enumerate() {
+            let stroke = Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    ..Stroke::default()
+                },
+                colors[idx % colors.len()],
+            );
+
+            let mut prev: Option<Point> = None;
+            datapoints.for_each_in(range.clone(), |x, y| {
+                let Some(value) = line(y) else {
+                    prev = None;
+                    return;
+                };
+                let point = Point::new(ctx.interval_to_x(x) + half_bar_width, scale.to_y(value));
+                if let Some(prev) = prev {
+                    frame.stroke(&Path::line(prev, point), stroke);
+                }
+                prev = Some(point);
+            });
+        }
+    }
+
+    fn tooltip_fn(&self) -> Option<&TooltipFn<S::Y>> {
+        self.tooltip.as_ref()
+    }
+}
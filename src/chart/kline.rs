@@ -3,31 +3,56 @@ use super::{
     indicator, request_fetch, scale::linear::PriceInfoLabel,
 };
 use crate::chart::indicator::kline::KlineIndicatorImpl;
+use crate::chart::indicator::kline::band::BandOverlay;
+use crate::chart::indicator::kline::big_trades::BigTradeOverlay;
+use crate::chart::indicator::kline::compare::CompareOverlay;
+use crate::chart::indicator::kline::moving_average::MovingAverageOverlay;
+use crate::chart::indicator::kline::script::ScriptOverlay;
+use crate::chart::indicator::kline::vwap::VwapOverlay;
 use crate::connector::fetcher::{FetchRange, RequestHandler, is_trade_fetch_enabled};
 use crate::{modal::pane::settings::study, style};
+use data::aggr::BarKind;
 use data::aggr::ticks::TickAggr;
 use data::aggr::time::TimeSeries;
-use data::chart::indicator::{Indicator, KlineIndicator};
+use data::chart::indicator::{
+    Indicator, IndicatorSettings, KlineIndicator, KlineIndicatorInstance, MovingAverage,
+    VolatilityBand,
+};
 use data::chart::kline::{
-    ClusterKind, ClusterScaling, Config, FootprintStudy, FootprintSummary, KlineDataPoint,
-    KlineTrades, NPoc, PointOfControl,
+    BigTrades, CandleStyle, ClusterKind, ClusterScaling, Config, FlowAlertRules, FlowSignal,
+    FootprintStudy, FootprintSummary, KlineDataPoint, KlineTrades, NPoc, ProfileRange,
+    ProfileScope, SummaryFooter, VolumeProfile, VolumeProfileConfig,
 };
+use data::chart::vwap::VwapConfig;
 use data::chart::{Autoscale, KlineChartKind, ViewConfig};
+use data::external::{self, ExternalEvent, MAX_ANNOTATIONS};
+use data::trading::journal::{Marker as JournalMarker, Role as JournalRole};
 
 use data::util::abbr_large_numbers;
+use exchange::adapter::StreamKind;
 use exchange::unit::{Price, PriceStep, Qty};
-use exchange::{Kline, OpenInterest as OIData, TickerInfo, Trade, UnixMs};
+use exchange::{
+    FundingRate, Kline, MarkPrice, OpenInterest as OIData, TickerInfo, Timeframe, Trade, UnixMs,
+};
 
 use iced::task::Handle;
 use iced::theme::palette::Extended;
 use iced::widget::canvas::{self, Event, Geometry, Path, Stroke};
-use iced::{Alignment, Element, Point, Rectangle, Renderer, Size, Theme, Vector, mouse};
+use iced::{Alignment, Color, Element, Point, Rectangle, Renderer, Size, Theme, Vector, mouse};
 
-use enum_map::EnumMap;
+use std::collections::BTreeMap;
 use std::time::Instant;
 
+/// Mark price points kept per chart, one per kline interval
+const MAX_MARK_PRICE_POINTS: usize = 5_000;
+
+/// Height of a footprint summary footer row, in pixels
+const FOOTER_ROW_HEIGHT: f32 = 14.0;
+/// Below this candle width in pixels the footer only shows the delta shading
+const FOOTER_MIN_CELL_WIDTH: f32 = 36.0;
+
 impl Chart for KlineChart {
-    type IndicatorKind = KlineIndicator;
+    type IndicatorKind = KlineIndicatorInstance;
 
     fn state(&self) -> &ViewState {
         &self.chart
@@ -40,9 +65,8 @@ impl Chart for KlineChart {
     fn invalidate_crosshair(&mut self) {
         self.chart.cache.clear_crosshair();
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
-            .for_each(|indi| indi.clear_crosshair_caches());
+            .iter_mut()
+            .for_each(|(_, indi)| indi.clear_crosshair_caches());
     }
 
     fn invalidate_all(&mut self) {
@@ -62,11 +86,11 @@ impl Chart for KlineChart {
         let market = chart_state.ticker_info.market_type();
         let mut elements = vec![];
 
-        for selected_indicator in enabled {
-            if !KlineIndicator::for_market(market).contains(selected_indicator) {
+        for selected in enabled {
+            if !KlineIndicator::for_market(market).contains(&selected.kind) {
                 continue;
             }
-            if let Some(indi) = self.indicators[*selected_indicator].as_ref() {
+            if let Some((_, indi)) = self.indicators.iter().find(|(i, _)| i.id == selected.id) {
                 elements.push(indi.element(
                     chart_state,
                     data_labels_always_visible,
@@ -125,6 +149,21 @@ impl Chart for KlineChart {
             PlotData::TickBased(tick_aggr) => tick_aggr.datapoints.is_empty(),
         }
     }
+
+    fn selects_ranges(&self) -> bool {
+        self.visual_config
+            .volume_profile
+            .is_some_and(|profile| profile.scope == ProfileScope::FixedRanges)
+    }
+
+    fn ohlc_at(&self, interval: u64) -> Option<[Price; 4]> {
+        let PlotData::TimeBased(timeseries) = &self.data_source else {
+            return None;
+        };
+        let kline = &timeseries.datapoints.get(&UnixMs::new(interval))?.kline;
+
+        Some([kline.open, kline.high, kline.low, kline.close])
+    }
 }
 
 impl PlotConstants for KlineChart {
@@ -161,11 +200,35 @@ pub struct KlineChart {
     chart: ViewState,
     data_source: PlotData<KlineDataPoint>,
     raw_trades: Vec<Trade>,
-    indicators: EnumMap<KlineIndicator, Option<Box<dyn KlineIndicatorImpl>>>,
+    /// Enabled panels, stacked in the order the pane lists them
+    indicators: Vec<(KlineIndicatorInstance, Box<dyn KlineIndicatorImpl>)>,
     fetching_trades: (bool, Option<Handle>),
+    /// Gaps left by a websocket reconnect, waiting to be refetched over REST
+    kline_gap: Option<UnixMs>,
+    trade_gap: Option<(UnixMs, UnixMs)>,
+    /// Markers pushed in by external systems, e.g. fills or signals
+    annotations: Vec<ExternalEvent>,
+    /// Logged trades of the ticker, see [`data::trading::journal`]
+    journal: Vec<JournalMarker>,
+    moving_averages: MovingAverageOverlay,
+    bands: BandOverlay,
+    vwap: VwapOverlay,
+    scripts: ScriptOverlay,
+    big_trades: BigTradeOverlay,
+    /// Trades passing the filter of a tape in the same link group
+    tape_marks: BigTradeOverlay,
+    profile_ranges: Vec<ProfileRange>,
+    /// Last `(mark, index)` price of each kline interval, only kept on time basis
+    mark_prices: BTreeMap<u64, (Price, Option<Price>)>,
+    /// Second ticker drawn over the candles, picked from the mini tickers list
+    overlay: CompareOverlay,
     pub(crate) kind: KlineChartKind,
     request_handler: RequestHandler,
     study_configurator: study::Configurator<FootprintStudy>,
+    /// Order flow patterns found in closed candles, waiting to be shown
+    flow_signals: Vec<FlowSignal>,
+    /// Key of the last closed candle checked against the flow alert rules
+    flow_checked: Option<u64>,
     last_tick: Instant,
     visual_config: Config,
 }
@@ -177,7 +240,7 @@ impl KlineChart {
         step: PriceStep,
         klines_raw: &[Kline],
         raw_trades: Vec<Trade>,
-        enabled_indicators: &[KlineIndicator],
+        enabled_indicators: &[KlineIndicatorInstance],
         ticker_info: TickerInfo,
         kind: &KlineChartKind,
         visual_config: Option<Config>,
@@ -186,8 +249,14 @@ impl KlineChart {
 
         match basis {
             Basis::Time(interval) => {
-                let timeseries = TimeSeries::<KlineDataPoint>::new(interval, step, klines_raw)
-                    .with_trades(&raw_trades);
+                let timeseries = if klines_from_trades(basis, ticker_info) {
+                    let mut timeseries = TimeSeries::<KlineDataPoint>::new(interval, step, &[]);
+                    timeseries.insert_trades_as_klines(&raw_trades);
+                    timeseries
+                } else {
+                    TimeSeries::<KlineDataPoint>::new(interval, step, klines_raw)
+                        .with_trades(&raw_trades)
+                };
 
                 let base_price_y = timeseries.base_price();
                 let latest_x = timeseries
@@ -246,12 +315,15 @@ impl KlineChart {
 
                 let data_source = PlotData::TimeBased(timeseries);
 
-                let mut indicators = EnumMap::default();
-                for &i in enabled_indicators {
-                    let mut indi = indicator::kline::make_empty(i);
-                    indi.rebuild_from_source(&data_source);
-                    indicators[i] = Some(indi);
-                }
+                let indicators = enabled_indicators
+                    .iter()
+                    .map(|&i| (i, make_indicator(i, &data_source)))
+                    .collect();
+                let big_trades = BigTradeOverlay::new(
+                    visual_config.big_trades,
+                    ticker_info.market_type(),
+                    &raw_trades,
+                );
 
                 KlineChart {
                     chart,
@@ -260,13 +332,28 @@ impl KlineChart {
                     raw_trades,
                     indicators,
                     fetching_trades: (false, None),
+                    kline_gap: None,
+                    trade_gap: None,
+                    annotations: Vec::new(),
+                    journal: Vec::new(),
+                    moving_averages: MovingAverageOverlay::default(),
+                    bands: BandOverlay::default(),
+                    vwap: VwapOverlay::default(),
+                    scripts: ScriptOverlay::new(),
+                    big_trades,
+                    tape_marks: BigTradeOverlay::new(None, ticker_info.market_type(), &[]),
+                    profile_ranges: Vec::new(),
+                    mark_prices: BTreeMap::new(),
+                    overlay: CompareOverlay::default(),
                     request_handler: RequestHandler::default(),
                     kind: kind.clone(),
                     study_configurator: study::Configurator::new(),
+                    flow_signals: vec![],
+                    flow_checked: None,
                     last_tick: Instant::now(),
                 }
             }
-            Basis::Tick(interval) => {
+            Basis::Tick(_) | Basis::Bars(_) => {
                 let cell_width = match kind {
                     KlineChartKind::Footprint { .. } => 80.0,
                     KlineChartKind::Candles => 4.0,
@@ -301,14 +388,24 @@ impl KlineChart {
                 };
                 chart.translation.x = x_translation;
 
-                let data_source = PlotData::TickBased(TickAggr::new(interval, step, &raw_trades));
+                let tick_aggr = match basis {
+                    Basis::Tick(interval) => TickAggr::new(interval, step, &raw_trades),
+                    Basis::Bars(bars) => {
+                        TickAggr::bars(bars, ticker_info.market_type(), step, &raw_trades)
+                    }
+                    Basis::Time(_) => unreachable!(),
+                };
+                let data_source = PlotData::TickBased(tick_aggr);
 
-                let mut indicators = EnumMap::default();
-                for &i in enabled_indicators {
-                    let mut indi = indicator::kline::make_empty(i);
-                    indi.rebuild_from_source(&data_source);
-                    indicators[i] = Some(indi);
-                }
+                let indicators = enabled_indicators
+                    .iter()
+                    .map(|&i| (i, make_indicator(i, &data_source)))
+                    .collect();
+                let big_trades = BigTradeOverlay::new(
+                    visual_config.big_trades,
+                    ticker_info.market_type(),
+                    &raw_trades,
+                );
 
                 KlineChart {
                     chart,
@@ -317,9 +414,24 @@ impl KlineChart {
                     raw_trades,
                     indicators,
                     fetching_trades: (false, None),
+                    kline_gap: None,
+                    trade_gap: None,
+                    annotations: Vec::new(),
+                    journal: Vec::new(),
+                    moving_averages: MovingAverageOverlay::default(),
+                    bands: BandOverlay::default(),
+                    vwap: VwapOverlay::default(),
+                    scripts: ScriptOverlay::new(),
+                    big_trades,
+                    tape_marks: BigTradeOverlay::new(None, ticker_info.market_type(), &[]),
+                    profile_ranges: Vec::new(),
+                    mark_prices: BTreeMap::new(),
+                    overlay: CompareOverlay::default(),
                     request_handler: RequestHandler::default(),
                     kind: kind.clone(),
                     study_configurator: study::Configurator::new(),
+                    flow_signals: vec![],
+                    flow_checked: None,
                     last_tick: Instant::now(),
                 }
             }
@@ -330,11 +442,16 @@ impl KlineChart {
         match self.data_source {
             PlotData::TimeBased(ref mut timeseries) => {
                 timeseries.insert_klines(&[*kline]);
+                let Some(kline) = timeseries.resampled(&[*kline]).pop() else {
+                    return;
+                };
 
                 self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
-                    .for_each(|indi| indi.on_insert_klines(&[*kline], &self.data_source));
+                    .iter_mut()
+                    .for_each(|(_, indi)| indi.on_insert_klines(&[kline], &self.data_source));
+                self.moving_averages.rebuild_from_source(&self.data_source);
+                self.bands.rebuild_from_source(&self.data_source);
+                self.scripts.on_insert_klines(&self.data_source);
 
                 let chart = self.mut_state();
 
@@ -353,6 +470,11 @@ impl KlineChart {
     }
 
     fn fetch_missing_data(&mut self) -> Option<Action> {
+        // Nothing to fetch, the candles only go back as far as the live trades
+        if klines_from_trades(self.chart.basis, self.chart.ticker_info) {
+            return None;
+        }
+
         match &self.data_source {
             PlotData::TimeBased(timeseries) => {
                 let timeframe_ms = timeseries.interval.to_milliseconds();
@@ -383,7 +505,27 @@ impl KlineChart {
                     }
                 }
 
-                // priority 2, trades
+                // priority 2, gap left by a websocket reconnect
+                // (starts one candle early so the candle cut off by the drop gets its final close)
+                if let Some(gap_start) = self.kline_gap.take() {
+                    let range =
+                        FetchRange::Kline(gap_start.saturating_sub(timeframe_ms), UnixMs::now());
+                    if let Some(action) = request_fetch(&mut self.request_handler, range) {
+                        return Some(action);
+                    }
+                }
+
+                if !self.fetching_trades.0
+                    && let Some((gap_start, resumed_at)) = self.trade_gap.take()
+                {
+                    let range = FetchRange::Trades(gap_start, resumed_at);
+                    if let Some(action) = request_fetch(&mut self.request_handler, range) {
+                        self.fetching_trades = (true, None);
+                        return Some(action);
+                    }
+                }
+
+                // priority 3, trades
                 if let KlineChartKind::Footprint { .. } = self.kind
                     && !self.fetching_trades.0
                     && is_trade_fetch_enabled()
@@ -397,7 +539,7 @@ impl KlineChart {
                     }
                 }
 
-                // priority 3, indicators
+                // priority 4, indicators
                 // (e.g. open interest needs external fetch as it's not derived from klines)
                 let ctx = indicator::kline::FetchCtx {
                     main_chart: &self.chart,
@@ -406,7 +548,7 @@ impl KlineChart {
                     kline_latest,
                     prefetch_earliest: UnixMs::new(prefetch_earliest),
                 };
-                for indi in self.indicators.values_mut().filter_map(Option::as_mut) {
+                for (_, indi) in self.indicators.iter_mut() {
                     if let Some(range) = indi.fetch_range(&ctx)
                         && let Some(action) = request_fetch(&mut self.request_handler, range)
                     {
@@ -414,7 +556,15 @@ impl KlineChart {
                     }
                 }
 
-                // priority 4, missing klines & integrity check
+                // priority 5, the overlaid ticker over the span of the candles
+                if let Some(fetch_spec) =
+                    self.overlay
+                        .fetch_missing(self.chart.basis, kline_earliest, kline_latest)
+                {
+                    return Some(Action::RequestFetch(vec![fetch_spec]));
+                }
+
+                // priority 6, missing klines & integrity check
                 let check_earliest = UnixMs::new(prefetch_earliest).max(kline_earliest);
                 let check_latest = visible_latest_ms.saturating_add(timeframe_ms);
 
@@ -446,6 +596,106 @@ impl KlineChart {
         None
     }
 
+    /// Queues a REST backfill for data missed between `gap_start` and the reconnect
+    pub fn backfill_gap(&mut self, gap_start: UnixMs) {
+        let PlotData::TimeBased(_) = self.data_source else {
+            return;
+        };
+
+        self.kline_gap = Some(self.kline_gap.map_or(gap_start, |prev| prev.min(gap_start)));
+
+        if let KlineChartKind::Footprint { .. } = self.kind
+            && is_trade_fetch_enabled()
+        {
+            let resumed_at = UnixMs::now();
+            self.trade_gap = Some(match self.trade_gap {
+                Some((prev_start, _)) => (prev_start.min(gap_start), resumed_at),
+                None => (gap_start, resumed_at),
+            });
+        }
+    }
+
+    pub fn add_annotation(&mut self, event: ExternalEvent) {
+        if self.annotations.len() >= MAX_ANNOTATIONS {
+            self.annotations.remove(0);
+        }
+        self.annotations.push(event);
+
+        self.chart.cache.clear_all();
+    }
+
+    /// Marks the trades of at least `min_size`, in the size unit of the tape's filter
+    pub fn set_tape_marks(&mut self, min_size: Option<f32>) {
+        let config = min_size.map(|min_notional| BigTrades {
+            min_notional,
+            size_scaled: false,
+            labels: false,
+        });
+
+        if self.tape_marks.set_config(config, &self.raw_trades) {
+            self.chart.cache.clear_all();
+        }
+    }
+
+    pub fn set_journal_markers(&mut self, markers: &[JournalMarker]) {
+        if self.journal != markers {
+            self.journal = markers.to_vec();
+            self.chart.cache.clear_all();
+        }
+    }
+
+    pub fn insert_mark_price(&mut self, mark_price: &MarkPrice) {
+        let Basis::Time(timeframe) = self.chart.basis else {
+            return;
+        };
+
+        let interval = mark_price.time.floor_to(timeframe).as_u64();
+        self.mark_prices
+            .insert(interval, (mark_price.mark, mark_price.index));
+
+        while self.mark_prices.len() > MAX_MARK_PRICE_POINTS {
+            self.mark_prices.pop_first();
+        }
+    }
+
+    pub fn overlay_ticker(&self) -> Option<TickerInfo> {
+        self.overlay.ticker_info()
+    }
+
+    pub fn overlay_tickers(&self) -> &[TickerInfo] {
+        self.overlay.tickers()
+    }
+
+    /// The kline stream of the overlaid ticker, if any and on time basis
+    pub fn overlay_stream(&self) -> Option<StreamKind> {
+        self.overlay.stream(self.chart.basis)
+    }
+
+    /// Overlays `ticker_info` on the candles, replacing the previous one, its klines are
+    /// fetched on the next tick. Ignored off time basis or for the chart's own ticker
+    pub fn set_overlay(&mut self, ticker_info: Option<TickerInfo>) {
+        let ticker_info = ticker_info
+            .filter(|ti| self.chart.basis.is_time() && ti.ticker != self.chart.ticker_info.ticker);
+
+        if self.overlay.ticker_info() != ticker_info {
+            self.overlay.set_ticker(ticker_info);
+            self.chart.cache.clear_all();
+        }
+    }
+
+    pub fn insert_overlay_klines(&mut self, req_id: uuid::Uuid, klines: &[Kline]) {
+        if let Basis::Time(timeframe) = self.chart.basis {
+            self.overlay.insert_hist_klines(req_id, timeframe, klines);
+            self.invalidate(None);
+        }
+    }
+
+    pub fn update_overlay_kline(&mut self, kline: &Kline) {
+        if let Basis::Time(timeframe) = self.chart.basis {
+            self.overlay.update_latest_kline(timeframe, kline);
+        }
+    }
+
     pub fn reset_request_handler(&mut self) {
         self.request_handler = RequestHandler::default();
         self.fetching_trades = (false, None);
@@ -455,6 +705,42 @@ impl KlineChart {
         self.raw_trades.clone()
     }
 
+    /// A fresh chart with the same settings and the klines and trades loaded so far, so a
+    /// duplicated pane doesn't have to fetch them again
+    pub fn duplicate(&self) -> Self {
+        let klines = match &self.data_source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .values()
+                .map(|dp| dp.kline)
+                .collect::<Vec<_>>(),
+            PlotData::TickBased(_) => vec![],
+        };
+        let indicators = self.indicators.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
+        let mut copy = KlineChart::new(
+            self.chart_layout(),
+            self.basis(),
+            self.tick_size(),
+            &klines,
+            self.raw_trades(),
+            &indicators,
+            self.chart.ticker_info,
+            &self.kind,
+            Some(self.visual_config),
+        );
+        copy.annotations = self.annotations.clone();
+        copy.journal = self.journal.clone();
+        copy.set_moving_averages(&self.moving_averages.lines());
+        copy.set_bands(&self.bands.bands());
+        copy.set_vwap(self.vwap.config());
+        copy.set_scripts(&self.scripts.enabled());
+        copy.profile_ranges = self.profile_ranges.clone();
+        copy.mark_prices = self.mark_prices.clone();
+        copy.overlay.set_ticker(self.overlay.ticker_info());
+        copy
+    }
+
     pub fn set_handle(&mut self, handle: Handle) {
         self.fetching_trades.1 = Some(handle);
     }
@@ -505,13 +791,109 @@ impl KlineChart {
         self.visual_config
     }
 
+    pub fn moving_averages(&self) -> Vec<MovingAverage> {
+        self.moving_averages.lines()
+    }
+
+    pub fn set_moving_averages(&mut self, lines: &[MovingAverage]) {
+        if self.moving_averages.set_lines(lines, &self.data_source) {
+            self.chart.cache.clear_all();
+        }
+    }
+
+    pub fn bands(&self) -> Vec<VolatilityBand> {
+        self.bands.bands()
+    }
+
+    pub fn set_bands(&mut self, bands: &[VolatilityBand]) {
+        if self.bands.set_bands(bands, &self.data_source) {
+            self.chart.cache.clear_all();
+        }
+    }
+
+    pub fn vwap(&self) -> Option<VwapConfig> {
+        self.vwap.config()
+    }
+
+    pub fn set_vwap(&mut self, config: Option<VwapConfig>) {
+        if self
+            .vwap
+            .set_config(config, self.chart.basis, &self.raw_trades)
+        {
+            self.chart.cache.clear_all();
+        }
+    }
+
+    pub fn scripts(&self) -> &ScriptOverlay {
+        &self.scripts
+    }
+
+    pub fn set_scripts(&mut self, names: &[String]) {
+        if self.scripts.set_scripts(names, &self.data_source) {
+            self.chart.cache.clear_all();
+        }
+    }
+
+    /// Picks up edits to the scripts and new files in their folder
+    pub fn reload_scripts(&mut self) {
+        self.scripts.reload(&self.data_source);
+        self.chart.cache.clear_all();
+    }
+
+    pub fn profile_ranges(&self) -> &[ProfileRange] {
+        &self.profile_ranges
+    }
+
+    pub fn set_profile_ranges(&mut self, ranges: Vec<ProfileRange>) {
+        self.profile_ranges = ranges;
+        self.chart.cache.clear_all();
+    }
+
+    /// Open time of the candle at `interval`, the interval itself on time basis
+    pub fn interval_time(&self, interval: u64) -> Option<UnixMs> {
+        match &self.data_source {
+            PlotData::TimeBased(_) => Some(UnixMs::new(interval)),
+            PlotData::TickBased(tick_aggr) => {
+                let index = tick_aggr
+                    .datapoints
+                    .len()
+                    .checked_sub(1)?
+                    .checked_sub(interval as usize)?;
+                tick_aggr.datapoints.get(index).map(|dp| dp.kline.time)
+            }
+        }
+    }
+
+    /// Open time of the leftmost visible bar, where an anchored VWAP starts by default
+    pub fn visible_start_time(&self) -> Option<UnixMs> {
+        let (earliest, latest) = self.visible_timerange()?;
+
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .range(UnixMs::new(earliest)..)
+                .next()
+                .map(|(time, _)| *time),
+            // Tick intervals count back from the latest bar
+            PlotData::TickBased(tick_aggr) => {
+                let last = tick_aggr.datapoints.len().checked_sub(1)?;
+                let oldest_visible = last.saturating_sub(latest as usize);
+                tick_aggr
+                    .datapoints
+                    .get(oldest_visible)
+                    .map(|dp| dp.kline.time)
+            }
+        }
+    }
+
     pub fn set_visual_config(&mut self, visual_config: Config) {
+        self.big_trades
+            .set_config(visual_config.big_trades, &self.raw_trades);
         self.visual_config = visual_config;
         self.chart.cache.clear_all();
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
-            .for_each(|indi| indi.clear_all_caches());
+            .iter_mut()
+            .for_each(|(_, indi)| indi.clear_all_caches());
     }
 
     pub fn set_cluster_kind(&mut self, new_kind: ClusterKind) {
@@ -556,21 +938,37 @@ impl KlineChart {
         }
 
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
-            .for_each(|indi| indi.on_ticksize_change(&self.data_source));
+            .iter_mut()
+            .for_each(|(_, indi)| indi.on_ticksize_change(&self.data_source));
+        self.moving_averages.rebuild_from_source(&self.data_source);
+        self.bands.rebuild_from_source(&self.data_source);
+        self.scripts.rebuild_from_source(&self.data_source);
 
+        self.flow_checked = None;
         self.invalidate(None);
     }
 
     pub fn set_basis(&mut self, new_basis: Basis) -> Option<Action> {
         self.chart.last_price = None;
         self.chart.basis = new_basis;
+        self.mark_prices.clear();
+        if new_basis.is_time() {
+            self.overlay.reset();
+        } else {
+            self.overlay.set_ticker(None);
+        }
 
         match new_basis {
             Basis::Time(interval) => {
                 let step = self.chart.tick_size;
-                let timeseries = TimeSeries::<KlineDataPoint>::new(interval, step, &[]);
+                let mut timeseries = TimeSeries::<KlineDataPoint>::new(interval, step, &[]);
+
+                if klines_from_trades(new_basis, self.chart.ticker_info) {
+                    timeseries.insert_trades_as_klines(&self.raw_trades);
+                    self.chart.latest_x = timeseries
+                        .latest_timestamp()
+                        .map_or(0, |timestamp| timestamp.as_u64());
+                }
                 self.data_source = PlotData::TimeBased(timeseries);
             }
             Basis::Tick(tick_count) => {
@@ -578,13 +976,23 @@ impl KlineChart {
                 let tick_aggr = TickAggr::new(tick_count, step, &self.raw_trades);
                 self.data_source = PlotData::TickBased(tick_aggr);
             }
+            Basis::Bars(kind) => {
+                let step = self.chart.tick_size;
+                let market = self.chart.ticker_info.market_type();
+                let tick_aggr = TickAggr::bars(kind, market, step, &self.raw_trades);
+                self.data_source = PlotData::TickBased(tick_aggr);
+            }
         }
 
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
-            .for_each(|indi| indi.on_basis_change(&self.data_source));
-
+            .iter_mut()
+            .for_each(|(_, indi)| indi.on_basis_change(&self.data_source));
+        self.moving_averages.rebuild_from_source(&self.data_source);
+        self.bands.rebuild_from_source(&self.data_source);
+        self.scripts.rebuild_from_source(&self.data_source);
+        self.vwap.rebuild(new_basis, &self.raw_trades);
+
+        self.flow_checked = None;
         self.reset_request_handler();
         self.invalidate(Some(Instant::now()))
     }
@@ -609,6 +1017,9 @@ impl KlineChart {
 
     pub fn insert_trades(&mut self, buffer: &[Trade]) {
         self.raw_trades.extend_from_slice(buffer);
+        self.vwap.on_insert_trades(self.chart.basis, buffer);
+        self.big_trades.on_insert_trades(buffer);
+        self.tape_marks.on_insert_trades(buffer);
 
         match self.data_source {
             PlotData::TickBased(ref mut tick_aggr) => {
@@ -622,23 +1033,122 @@ impl KlineChart {
                     self.chart.last_price = None;
                 }
 
-                self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
-                    .for_each(|indi| indi.on_insert_trades(buffer, old_dp_len, &self.data_source));
+                self.indicators.iter_mut().for_each(|(_, indi)| {
+                    indi.on_insert_trades(buffer, old_dp_len, &self.data_source)
+                });
+                self.moving_averages.on_insert_trades(&self.data_source);
+                self.bands.on_insert_trades(&self.data_source);
+                self.scripts.on_insert_trades(&self.data_source);
+            }
+            PlotData::TimeBased(ref mut timeseries)
+                if klines_from_trades(self.chart.basis, self.chart.ticker_info) =>
+            {
+                let Some(first) = buffer.iter().map(|trade| trade.time).min() else {
+                    return;
+                };
+                timeseries.insert_trades_as_klines(buffer);
 
-                self.invalidate(None);
+                let klines = timeseries
+                    .datapoints
+                    .range(first.floor_to(timeseries.interval)..)
+                    .map(|(_, dp)| dp.kline)
+                    .collect::<Vec<_>>();
+
+                self.indicators.iter_mut().for_each(|(_, indi)| {
+                    indi.on_insert_klines(&klines, &self.data_source);
+                    indi.on_insert_trades(buffer, 0, &self.data_source);
+                });
+                self.moving_averages.rebuild_from_source(&self.data_source);
+                self.bands.rebuild_from_source(&self.data_source);
+                self.scripts.on_insert_trades(&self.data_source);
+
+                if let Some(kline) = klines.last() {
+                    if kline.time.as_u64() > self.chart.latest_x {
+                        self.chart.latest_x = kline.time.as_u64();
+                    }
+                    self.chart.last_price = Some(PriceInfoLabel::new(kline.close, kline.open));
+                }
             }
             PlotData::TimeBased(ref mut timeseries) => {
                 timeseries.insert_trades_existing_buckets(buffer);
 
                 self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
-                    .for_each(|indi| indi.on_insert_trades(buffer, 0, &self.data_source));
+                    .iter_mut()
+                    .for_each(|(_, indi)| indi.on_insert_trades(buffer, 0, &self.data_source));
+            }
+        }
 
-                self.invalidate(None);
+        self.check_flow_alerts();
+        self.invalidate(None);
+    }
+
+    pub fn flow_alert_rules(&self) -> Option<FlowAlertRules> {
+        let KlineChartKind::Footprint { studies, .. } = &self.kind else {
+            return None;
+        };
+
+        studies.iter().find_map(|study| match study {
+            FootprintStudy::FlowAlerts(rules) => Some(*rules),
+            _ => None,
+        })
+    }
+
+    /// Patterns found since the last call, in the order their candles closed
+    pub fn take_flow_signals(&mut self) -> Vec<FlowSignal> {
+        std::mem::take(&mut self.flow_signals)
+    }
+
+    /// Checks the most recently closed candle once, candles that were already closed
+    /// when the chart loaded are skipped
+    fn check_flow_alerts(&mut self) {
+        let Some(rules) = self.flow_alert_rules() else {
+            return;
+        };
+        let step = self.chart.tick_size;
+
+        let (key, signals) = match &self.data_source {
+            PlotData::TimeBased(timeseries) => {
+                let mut closed = timeseries.datapoints.iter().rev().skip(1);
+                let Some((time, dp)) = closed.next() else {
+                    return;
+                };
+                if self
+                    .flow_checked
+                    .is_some_and(|checked| checked >= time.as_u64())
+                {
+                    return;
+                }
+                let previous = closed.next().map(|(_, dp)| &dp.kline);
+
+                (
+                    time.as_u64(),
+                    rules.check(&dp.kline, &dp.footprint, previous, step),
+                )
+            }
+            PlotData::TickBased(tick_aggr) => {
+                let Some(index) = tick_aggr.datapoints.len().checked_sub(2) else {
+                    return;
+                };
+                if self
+                    .flow_checked
+                    .is_some_and(|checked| checked >= index as u64)
+                {
+                    return;
+                }
+                let dp = &tick_aggr.datapoints[index];
+                let previous = index
+                    .checked_sub(1)
+                    .map(|previous| &tick_aggr.datapoints[previous].kline);
+
+                (
+                    index as u64,
+                    rules.check(&dp.kline, &dp.footprint, previous, step),
+                )
             }
+        };
+
+        if self.flow_checked.replace(key).is_some() {
+            self.flow_signals.extend(signals);
         }
     }
 
@@ -652,12 +1162,17 @@ impl KlineChart {
             }
         }
 
-        self.raw_trades.extend_from_slice(&raw_trades);
+        self.merge_raw_trades(&raw_trades);
+        self.vwap.rebuild(self.chart.basis, &self.raw_trades);
+        self.big_trades.on_insert_trades(&raw_trades);
+        self.tape_marks.on_insert_trades(&raw_trades);
 
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
-            .for_each(|indi| indi.on_insert_trades(&raw_trades, 0, &self.data_source));
+            .iter_mut()
+            .for_each(|(_, indi)| indi.on_insert_trades(&raw_trades, 0, &self.data_source));
+        self.moving_averages.on_insert_trades(&self.data_source);
+        self.bands.on_insert_trades(&self.data_source);
+        self.scripts.on_insert_trades(&self.data_source);
 
         if is_batches_done {
             self.fetching_trades = (false, None);
@@ -666,16 +1181,40 @@ impl KlineChart {
         self.invalidate(None);
     }
 
+    /// Keeps the trades in time order when a fetched batch lands behind live ones,
+    /// e.g. the backfill of a reconnect gap
+    fn merge_raw_trades(&mut self, batch: &[Trade]) {
+        let Some(first) = batch.first() else {
+            return;
+        };
+
+        let idx = self
+            .raw_trades
+            .partition_point(|trade| trade.time <= first.time);
+        let mut later = self.raw_trades.split_off(idx).into_iter().peekable();
+
+        for trade in batch {
+            while let Some(live) = later.next_if(|live| live.time <= trade.time) {
+                self.raw_trades.push(live);
+            }
+            self.raw_trades.push(*trade);
+        }
+        self.raw_trades.extend(later);
+    }
+
     pub fn insert_hist_klines(&mut self, req_id: uuid::Uuid, klines_raw: &[Kline]) {
         match self.data_source {
             PlotData::TimeBased(ref mut timeseries) => {
                 timeseries.insert_klines(klines_raw);
                 timeseries.insert_trades_existing_buckets(&self.raw_trades);
+                let klines = timeseries.resampled(klines_raw);
 
                 self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
-                    .for_each(|indi| indi.on_insert_klines(klines_raw, &self.data_source));
+                    .iter_mut()
+                    .for_each(|(_, indi)| indi.on_insert_klines(&klines, &self.data_source));
+                self.moving_averages.rebuild_from_source(&self.data_source);
+                self.bands.rebuild_from_source(&self.data_source);
+                self.scripts.rebuild_from_source(&self.data_source);
 
                 if klines_raw.is_empty() {
                     self.request_handler
@@ -699,9 +1238,24 @@ impl KlineChart {
             }
         }
 
-        if let Some(indi) = self.indicators[KlineIndicator::OpenInterest].as_mut() {
-            indi.on_open_interest(oi_data);
+        self.indicators
+            .iter_mut()
+            .for_each(|(_, indi)| indi.on_open_interest(oi_data));
+    }
+
+    pub fn insert_funding_rate(&mut self, req_id: Option<uuid::Uuid>, funding: &[FundingRate]) {
+        if let Some(req_id) = req_id {
+            if funding.is_empty() {
+                self.request_handler
+                    .mark_failed(req_id, "No data received".to_string());
+            } else {
+                self.request_handler.mark_completed(req_id);
+            }
         }
+
+        self.indicators
+            .iter_mut()
+            .for_each(|(_, indi)| indi.on_funding_rate(funding));
     }
 
     fn calc_qty_scales(
@@ -797,16 +1351,28 @@ impl KlineChart {
                     let visible_region = chart.visible_region(chart.bounds.size());
                     let (start_interval, end_interval) = chart.interval_range(&visible_region);
 
-                    if let Some((lowest, highest)) = self
-                        .data_source
-                        .visible_price_range(start_interval, end_interval)
-                    {
+                    let price_range = if self.visual_config.clip_outliers {
+                        self.data_source.visible_price_range_clipped(
+                            start_interval,
+                            end_interval,
+                            data::chart::kline::OUTLIER_CLIP,
+                        )
+                    } else {
+                        self.data_source
+                            .visible_price_range(start_interval, end_interval)
+                    };
+
+                    if let Some((lowest, highest)) = price_range {
                         let chart_height = chart.bounds.height;
                         let tick_size = chart.tick_size.to_f32_lossy();
 
                         if chart_height > f32::EPSILON && tick_size > 0.0 {
+                            // The footprint range covers every traded level, so clipping
+                            // sticks to the candle range
                             let (fit_lowest, fit_highest) =
-                                if let KlineChartKind::Footprint { .. } = self.kind {
+                                if let KlineChartKind::Footprint { .. } = self.kind
+                                    && !self.visual_config.clip_outliers
+                                {
                                     if let Some((footprint_low, footprint_high)) = self
                                         .data_source
                                         .visible_footprint_price_range(start_interval, end_interval)
@@ -824,7 +1390,8 @@ impl KlineChart {
                                 };
 
                             let visible_span = (fit_highest - fit_lowest).max(tick_size);
-                            let base_padding = visible_span * 0.05; // 5% padding on top and bottom
+                            let base_padding =
+                                visible_span * (self.visual_config.autoscale_padding / 100.0);
 
                             let mut top_padding = base_padding;
                             let mut bottom_padding = base_padding;
@@ -843,13 +1410,21 @@ impl KlineChart {
                                     top_padding += outer_padding;
                                     bottom_padding += outer_padding;
 
-                                    bottom_padding = bottom_padding.max(footprint_summary_padding(
-                                        provisional_cell_height,
-                                        chart.scaling,
-                                        chart.cell_width,
-                                        tick_size,
-                                        clusters,
-                                    ));
+                                    bottom_padding = match self.visual_config.summary_footer {
+                                        Some(footer) => {
+                                            let footer_px =
+                                                FOOTER_ROW_HEIGHT * footer.rows() as f32;
+                                            bottom_padding
+                                                + (footer_px / provisional_cell_height) * tick_size
+                                        }
+                                        None => bottom_padding.max(footprint_summary_padding(
+                                            provisional_cell_height,
+                                            chart.scaling,
+                                            chart.cell_width,
+                                            tick_size,
+                                            clusters,
+                                        )),
+                                    };
                                 }
                             }
 
@@ -866,7 +1441,7 @@ impl KlineChart {
         }
 
         chart.cache.clear_all();
-        for indi in self.indicators.values_mut().filter_map(Option::as_mut) {
+        for (_, indi) in self.indicators.iter_mut() {
             indi.clear_all_caches();
         }
 
@@ -878,26 +1453,79 @@ impl KlineChart {
         }
     }
 
-    pub fn toggle_indicator(&mut self, indicator: KlineIndicator) {
-        let prev_indi_count = self.indicators.values().filter(|v| v.is_some()).count();
-
-        if self.indicators[indicator].is_some() {
-            self.indicators[indicator] = None;
-        } else {
-            let mut box_indi = indicator::kline::make_empty(indicator);
-            box_indi.rebuild_from_source(&self.data_source);
-            self.indicators[indicator] = Some(box_indi);
+    /// Recreates the panel with its new settings
+    pub fn set_indicator_settings(&mut self, id: uuid::Uuid, settings: IndicatorSettings) {
+        if let Some((instance, imp)) = self.indicators.iter_mut().find(|(i, _)| i.id == id)
+            && instance.settings != settings
+        {
+            instance.settings = settings;
+            *imp = make_indicator(*instance, &self.data_source);
         }
+    }
 
+    pub fn add_indicator(&mut self, instance: KlineIndicatorInstance) {
+        let prev_indi_count = self.indicators.len();
+
+        self.indicators
+            .push((instance, make_indicator(instance, &self.data_source)));
+        self.resplit_panels(prev_indi_count);
+    }
+
+    pub fn remove_indicator(&mut self, id: uuid::Uuid) {
+        let prev_indi_count = self.indicators.len();
+
+        self.indicators.retain(|(i, _)| i.id != id);
+        self.resplit_panels(prev_indi_count);
+    }
+
+    fn resplit_panels(&mut self, prev_indi_count: usize) {
         if let Some(main_split) = self.chart.layout.splits.first() {
-            let current_indi_count = self.indicators.values().filter(|v| v.is_some()).count();
             self.chart.layout.splits = data::util::calc_panel_splits(
                 *main_split,
-                current_indi_count,
+                self.indicators.len(),
                 Some(prev_indi_count),
             );
         }
     }
+
+    /// Keeps only the `wanted` indicators that suit the ticker's market and gives the main
+    /// chart `main_split` of the height. Returns the indicators kept, in `wanted` order
+    pub fn set_indicators(
+        &mut self,
+        wanted: &[KlineIndicatorInstance],
+        main_split: f32,
+    ) -> Vec<KlineIndicatorInstance> {
+        let available = KlineIndicator::for_market(self.chart.ticker_info.market_type());
+        let wanted = wanted
+            .iter()
+            .copied()
+            .filter(|indi| available.contains(&indi.kind))
+            .collect::<Vec<_>>();
+
+        let mut previous = std::mem::take(&mut self.indicators);
+        self.indicators = wanted
+            .iter()
+            .map(
+                |&instance| match previous.iter().position(|(i, _)| *i == instance) {
+                    Some(index) => previous.swap_remove(index),
+                    None => (instance, make_indicator(instance, &self.data_source)),
+                },
+            )
+            .collect();
+
+        self.chart.layout.splits = data::util::calc_panel_splits(main_split, wanted.len(), None);
+
+        wanted
+    }
+}
+
+fn make_indicator(
+    instance: KlineIndicatorInstance,
+    source: &PlotData<KlineDataPoint>,
+) -> Box<dyn KlineIndicatorImpl> {
+    let mut indi = indicator::kline::make_empty(instance.kind, instance.settings);
+    indi.rebuild_from_source(source);
+    indi
 }
 
 impl canvas::Program<Message> for KlineChart {
@@ -943,6 +1571,24 @@ impl canvas::Program<Message> for KlineChart {
             let price_to_y = |price| chart.price_to_y(price);
             let interval_to_x = |interval| chart.interval_to_x(interval);
 
+            if let Some(profile) = self.visual_config.volume_profile {
+                draw_volume_profiles(
+                    frame,
+                    chart,
+                    &self.data_source,
+                    &region,
+                    earliest,
+                    latest,
+                    profile,
+                    &self.profile_ranges,
+                    theme,
+                );
+            }
+
+            // Filled in by footprints, the footer goes over everything else
+            let footer = self.visual_config.summary_footer;
+            let mut summaries = vec![];
+
             match &self.kind {
                 KlineChartKind::Footprint {
                     clusters,
@@ -1014,6 +1660,7 @@ impl canvas::Program<Message> for KlineChart {
                         |frame, x_position, kline, trades| {
                             let cluster_scaling =
                                 effective_cluster_qty(*scaling, max_cluster_qty, trades, *clusters);
+                            let summary = FootprintSummary::from_trades(trades);
 
                             draw_clusters(
                                 frame,
@@ -1033,42 +1680,201 @@ impl canvas::Program<Message> for KlineChart {
                                 *clusters,
                                 content_spacing,
                             );
+
+                            match (footer, summary) {
+                                (Some(_), Some(summary)) => {
+                                    summaries.push((x_position, summary));
+                                }
+                                (None, Some(summary)) if show_text => draw_candle_summary(
+                                    frame,
+                                    price_to_y,
+                                    x_position,
+                                    chart.cell_height,
+                                    text_size,
+                                    palette,
+                                    kline,
+                                    &summary,
+                                ),
+                                _ => {}
+                            }
                         },
                     );
                 }
                 KlineChartKind::Candles => {
                     let candle_width = chart.cell_width * 0.8;
+                    let box_units = match chart.basis {
+                        Basis::Bars(BarKind::PointFigure { box_ticks, .. }) => {
+                            Some(chart.tick_size.units * i64::from(box_ticks.max(1)))
+                        }
+                        _ => None,
+                    };
+                    // Point & figure columns are only ever drawn as such
+                    let style = match box_units {
+                        Some(_) => CandleStyle::Candles,
+                        None => self.visual_config.candle_style,
+                    };
 
-                    render_data_source(
-                        &self.data_source,
-                        frame,
-                        earliest,
-                        latest,
-                        interval_to_x,
-                        |frame, x_position, kline, _| {
-                            draw_candle_dp(
+                    match style {
+                        CandleStyle::Candles => render_data_source(
+                            &self.data_source,
+                            frame,
+                            earliest,
+                            latest,
+                            interval_to_x,
+                            |frame, x_position, kline, _| match box_units {
+                                Some(box_units) => draw_pnf_column(
+                                    frame,
+                                    price_to_y,
+                                    candle_width,
+                                    palette,
+                                    x_position,
+                                    kline,
+                                    box_units,
+                                ),
+                                None => draw_candle_dp(
+                                    frame,
+                                    price_to_y,
+                                    candle_width,
+                                    palette,
+                                    x_position,
+                                    kline,
+                                ),
+                            },
+                        ),
+                        CandleStyle::HeikinAshi => {
+                            for (interval, kline) in
+                                heikin_ashi_in_range(&self.data_source, earliest, latest)
+                            {
+                                draw_candle_dp(
+                                    frame,
+                                    price_to_y,
+                                    candle_width,
+                                    palette,
+                                    interval_to_x(interval),
+                                    &kline,
+                                );
+                            }
+                        }
+                        CandleStyle::Line | CandleStyle::Area | CandleStyle::Baseline => {
+                            // One bar past each edge so the line reaches out of view
+                            let (from, to) = match chart.basis {
+                                Basis::Time(tf) => (
+                                    earliest.saturating_sub(tf.to_milliseconds()),
+                                    latest.saturating_add(tf.to_milliseconds()),
+                                ),
+                                Basis::Tick(_) | Basis::Bars(_) => {
+                                    (earliest.saturating_sub(1), latest.saturating_add(1))
+                                }
+                            };
+
+                            let mut closes = vec![];
+                            render_data_source(
+                                &self.data_source,
                                 frame,
-                                price_to_y,
-                                candle_width,
-                                palette,
-                                x_position,
-                                kline,
+                                from,
+                                to,
+                                interval_to_x,
+                                |_, x_position, kline, _| {
+                                    closes.push(Point::new(x_position, price_to_y(kline.close)));
+                                },
                             );
-                        },
-                    );
+                            closes.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+                            draw_close_line(frame, style, &closes, &region, palette);
+                        }
+                    }
                 }
             }
 
+            self.bands.draw(frame, chart, earliest, latest, palette);
+            self.vwap.draw(frame, chart, earliest, latest, palette);
+            self.moving_averages.draw(frame, chart, earliest, latest);
+            self.scripts
+                .draw(frame, chart, earliest, latest, &region, palette);
+            self.big_trades
+                .draw(frame, chart, &self.data_source, region, palette);
+            self.tape_marks
+                .draw(frame, chart, &self.data_source, region, palette);
+            self.overlay
+                .draw(frame, chart, &self.data_source, earliest, latest, palette);
+
             chart.draw_last_price_line(frame, palette, region);
+            chart.draw_alert_levels(frame, palette, region);
+            chart.draw_chart_levels(frame, palette, region);
+            chart.draw_drawings(frame, palette, region, |drawing| {
+                let PlotData::TimeBased(timeseries) = &self.data_source else {
+                    return Qty::ZERO;
+                };
+                let (earliest, latest, low, high) = drawing.bounds();
+
+                timeseries
+                    .datapoints
+                    .range(earliest..=latest)
+                    .fold(Qty::ZERO, |acc, (_, dp)| acc + dp.volume_within(low, high))
+            });
+
+            if self.visual_config.show_mark_price {
+                draw_mark_prices(
+                    frame,
+                    &self.mark_prices,
+                    price_to_y,
+                    interval_to_x,
+                    earliest,
+                    latest,
+                    palette,
+                );
+            }
+
+            draw_annotations(
+                frame,
+                &self.annotations,
+                &self.data_source,
+                price_to_y,
+                interval_to_x,
+                chart.basis,
+                chart.scaling,
+                region,
+                palette,
+            );
+
+            draw_journal_markers(
+                frame,
+                &self.journal,
+                &self.data_source,
+                price_to_y,
+                interval_to_x,
+                chart.basis,
+                chart.scaling,
+                region,
+                palette,
+            );
+
+            if let (Some(footer), KlineChartKind::Footprint { .. }) = (footer, &self.kind) {
+                draw_summary_footer(
+                    frame,
+                    footer,
+                    &summaries,
+                    &region,
+                    chart.cell_width,
+                    chart.scaling,
+                    palette,
+                );
+            }
         });
 
         let crosshair = chart.cache.crosshair.draw(renderer, bounds_size, |frame| {
             let visible_region = chart.visible_region(bounds_size);
             let visible_range = chart.interval_range(&visible_region);
+            let mut hovered_interval = None;
+
+            if cursor.position_in(bounds).is_none() {
+                chart.draw_linked_crosshair(frame, theme, bounds_size);
+            }
 
             if let Some(cursor_position) = cursor.position_in(bounds) {
                 let (_, rounded_aggregation) =
                     chart.draw_crosshair(frame, theme, bounds_size, cursor_position, interaction);
+                hovered_interval = Some(rounded_aggregation);
 
                 draw_crosshair_tooltip(
                     &self.data_source,
@@ -1079,6 +1885,15 @@ impl canvas::Program<Message> for KlineChart {
                     Some(rounded_aggregation),
                     visible_range,
                 );
+
+                self.big_trades.draw_tooltip(
+                    frame,
+                    chart,
+                    &self.data_source,
+                    &chart.ticker_info,
+                    cursor_position,
+                    palette,
+                );
             } else if self.visual_config.data_labels_always_visible {
                 draw_crosshair_tooltip(
                     &self.data_source,
@@ -1090,6 +1905,31 @@ impl canvas::Program<Message> for KlineChart {
                     visible_range,
                 );
             }
+
+            if self.visual_config.show_mark_price {
+                draw_mark_price_legend(
+                    frame,
+                    &self.mark_prices,
+                    hovered_interval,
+                    &chart.ticker_info,
+                    palette,
+                );
+            }
+
+            // Stacked under the mark price legend when that one is shown
+            let legend_y = if self.visual_config.show_mark_price {
+                46.0
+            } else {
+                28.0
+            };
+            self.overlay.draw_legend(
+                frame,
+                &self.data_source,
+                visible_range,
+                hovered_interval,
+                Point::new(8.0, legend_y),
+                palette,
+            );
         });
 
         vec![klines, crosshair]
@@ -1104,7 +1944,10 @@ impl canvas::Program<Message> for KlineChart {
         match interaction {
             Interaction::Panning { .. } => mouse::Interaction::Grabbing,
             Interaction::Zoomin { .. } => mouse::Interaction::ZoomIn,
-            Interaction::None | Interaction::Ruler { .. } => {
+            Interaction::None
+            | Interaction::Ruler { .. }
+            | Interaction::RangeSelect { .. }
+            | Interaction::Drawing { .. } => {
                 if cursor.is_over(bounds) {
                     mouse::Interaction::Crosshair
                 } else {
@@ -1115,6 +1958,16 @@ impl canvas::Program<Message> for KlineChart {
     }
 }
 
+/// Sub-minute candles the venue has no klines for are built from the live trades
+fn klines_from_trades(basis: Basis, ticker_info: TickerInfo) -> bool {
+    match basis {
+        Basis::Time(tf) => {
+            Timeframe::SECONDS.contains(&tf) && !ticker_info.exchange().supports_kline_timeframe(tf)
+        }
+        Basis::Tick(_) | Basis::Bars(_) => false,
+    }
+}
+
 fn draw_footprint_kline(
     frame: &mut canvas::Frame,
     price_to_y: impl Fn(Price) -> f32,
@@ -1196,15 +2049,193 @@ fn draw_candle_dp(
     );
 }
 
+/// An X for each box of a rising column, an O for each of a falling one
+fn draw_pnf_column(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    column_width: f32,
+    palette: &Extended,
+    x_position: f32,
+    kline: &Kline,
+    box_units: i64,
+) {
+    let rising = kline.close >= kline.open;
+    let color = if rising {
+        palette.success.base.color
+    } else {
+        palette.danger.base.color
+    };
+    let stroke = Stroke::with_color(
+        Stroke {
+            width: 1.5,
+            ..Stroke::default()
+        },
+        color,
+    );
+
+    let box_height =
+        (price_to_y(kline.low) - price_to_y(Price::from_units(kline.low.units + box_units))).abs();
+    let half = 0.4 * column_width.min(box_height);
+
+    let mut level = kline.low.units;
+    while level <= kline.high.units {
+        let center = Point::new(x_position, price_to_y(Price::from_units(level)));
+
+        if rising {
+            frame.stroke(
+                &Path::line(
+                    center + Vector::new(-half, -half),
+                    center + Vector::new(half, half),
+                ),
+                stroke,
+            );
+            frame.stroke(
+                &Path::line(
+                    center + Vector::new(-half, half),
+                    center + Vector::new(half, -half),
+                ),
+                stroke,
+            );
+        } else {
+            frame.stroke(&Path::circle(center, half), stroke);
+        }
+
+        level += box_units;
+    }
+}
+
+/// Heikin-Ashi candles in the range, worked out from the oldest kline so they stay the same
+/// wherever the chart is scrolled
+fn heikin_ashi_in_range(
+    data_source: &PlotData<KlineDataPoint>,
+    earliest: u64,
+    latest: u64,
+) -> Vec<(u64, Kline)> {
+    let (intervals, klines): (Vec<u64>, Vec<&Kline>) = match data_source {
+        PlotData::TimeBased(timeseries) => timeseries
+            .datapoints
+            .range(..=UnixMs::new(latest))
+            .map(|(time, dp)| (time.as_u64(), &dp.kline))
+            .unzip(),
+        PlotData::TickBased(tick_aggr) => {
+            let last = tick_aggr.datapoints.len().saturating_sub(1);
+            tick_aggr
+                .datapoints
+                .iter()
+                .enumerate()
+                .map(|(index, dp)| ((last - index) as u64, &dp.kline))
+                .unzip()
+        }
+    };
+
+    intervals
+        .into_iter()
+        .zip(data::chart::kline::heikin_ashi(klines))
+        .filter(|(interval, _)| (earliest..=latest).contains(interval))
+        .collect()
+}
+
+/// Closes joined left to right, filled under the line for [`CandleStyle::Area`] and towards
+/// the first visible close for [`CandleStyle::Baseline`]
+fn draw_close_line(
+    frame: &mut canvas::Frame,
+    style: CandleStyle,
+    closes: &[Point],
+    region: &Rectangle,
+    palette: &Extended,
+) {
+    if closes.len() < 2 {
+        return;
+    }
+    let stroke = |color| {
+        Stroke::with_color(
+            Stroke {
+                width: 1.5,
+                ..Stroke::default()
+            },
+            color,
+        )
+    };
+
+    if style == CandleStyle::Baseline {
+        let base = closes
+            .iter()
+            .find(|point| point.x >= region.x)
+            .unwrap_or(&closes[0])
+            .y;
+
+        // Split where the line crosses the baseline so each piece takes one color
+        let mut segment = |from: Point, to: Point| {
+            // Higher prices are further up
+            let color = if from.y + to.y <= 2.0 * base {
+                palette.success.base.color
+            } else {
+                palette.danger.base.color
+            };
+
+            let fill = Path::new(|builder| {
+                builder.move_to(from);
+                builder.line_to(to);
+                builder.line_to(Point::new(to.x, base));
+                builder.line_to(Point::new(from.x, base));
+                builder.close();
+            });
+            frame.fill(&fill, color.scale_alpha(0.2));
+            frame.stroke(&Path::line(from, to), stroke(color));
+        };
+
+        for pair in closes.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+
+            if (from.y - base) * (to.y - base) < 0.0 {
+                let t = (base - from.y) / (to.y - from.y);
+                let crossing = Point::new(from.x + t * (to.x - from.x), base);
+                segment(from, crossing);
+                segment(crossing, to);
+            } else {
+                segment(from, to);
+            }
+        }
+
+        frame.stroke(
+            &Path::line(
+                Point::new(region.x, base),
+                Point::new(region.x + region.width, base),
+            ),
+            style::dashed_line_from_palette(palette),
+        );
+        return;
+    }
+
+    let color = palette.primary.base.color;
+    let line = Path::new(|builder| {
+        builder.move_to(closes[0]);
+        closes[1..].iter().for_each(|point| builder.line_to(*point));
+    });
+
+    if style == CandleStyle::Area {
+        let bottom = region.y + region.height;
+        let area = Path::new(|builder| {
+            builder.move_to(Point::new(closes[0].x, bottom));
+            closes.iter().for_each(|point| builder.line_to(*point));
+            builder.line_to(Point::new(closes[closes.len() - 1].x, bottom));
+            builder.close();
+        });
+        frame.fill(&area, color.scale_alpha(0.2));
+    }
+
+    frame.stroke(&line, stroke(color));
+}
+
 fn render_data_source<F>(
     data_source: &PlotData<KlineDataPoint>,
     frame: &mut canvas::Frame,
     earliest: u64,
     latest: u64,
     interval_to_x: impl Fn(u64) -> f32,
-    draw_fn: F,
+    mut draw_fn: F,
 ) where
-    F: Fn(&mut canvas::Frame, f32, &Kline, &KlineTrades),
+    F: FnMut(&mut canvas::Frame, f32, &Kline, &KlineTrades),
 {
     match data_source {
         PlotData::TickBased(tick_aggr) => {
@@ -1240,6 +2271,212 @@ fn render_data_source<F>(
     }
 }
 
+/// Volume profiles of the configured scope, behind the candles
+fn draw_volume_profiles(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    data_source: &PlotData<KlineDataPoint>,
+    region: &Rectangle,
+    earliest: u64,
+    latest: u64,
+    cfg: VolumeProfileConfig,
+    fixed_ranges: &[ProfileRange],
+    theme: &Theme,
+) {
+    if latest < earliest {
+        return;
+    }
+
+    let half_cell = chart.cell_width / 2.0;
+    // Profiles over their own candles grow rightwards from the first one
+    let draw_over_candles = |frame: &mut canvas::Frame, from: UnixMs, to: UnixMs| {
+        if let Some((profile, first, last)) =
+            span_profile(data_source, from, to, cfg.value_area_pct)
+        {
+            let (first_x, last_x) = (chart.interval_to_x(first), chart.interval_to_x(last));
+            let left = first_x.min(last_x) - half_cell;
+            let width = (first_x - last_x).abs() + chart.cell_width;
+
+            draw_profile(frame, chart, &profile, left, width, false, theme);
+        }
+    };
+
+    match cfg.scope {
+        ProfileScope::VisibleRange => {
+            let profile = match data_source {
+                PlotData::TickBased(tick_aggr) => VolumeProfile::from_footprints(
+                    tick_aggr
+                        .datapoints
+                        .iter()
+                        .rev()
+                        .enumerate()
+                        .filter(|(index, _)| (earliest as usize..=latest as usize).contains(index))
+                        .map(|(_, dp)| &dp.footprint),
+                    cfg.value_area_pct,
+                ),
+                PlotData::TimeBased(timeseries) => VolumeProfile::from_footprints(
+                    timeseries
+                        .datapoints
+                        .range(UnixMs::new(earliest)..=UnixMs::new(latest))
+                        .map(|(_, dp)| &dp.footprint),
+                    cfg.value_area_pct,
+                ),
+            };
+
+            let width = region.width * cfg.width;
+            let left = region.x + region.width - width;
+            draw_profile(frame, chart, &profile, left, width, true, theme);
+        }
+        ProfileScope::Sessions(window) => {
+            let Some((from, to)) = visible_time_span(data_source, earliest, latest) else {
+                return;
+            };
+
+            for (start, end) in window.sessions_between(from, to) {
+                draw_over_candles(frame, start, end.saturating_sub(1));
+            }
+        }
+        ProfileScope::FixedRanges => {
+            for range in fixed_ranges {
+                draw_over_candles(frame, range.from, range.to);
+            }
+        }
+    }
+}
+
+/// Open times of the oldest and newest visible candles
+fn visible_time_span(
+    data_source: &PlotData<KlineDataPoint>,
+    earliest: u64,
+    latest: u64,
+) -> Option<(UnixMs, UnixMs)> {
+    match data_source {
+        PlotData::TimeBased(_) => Some((UnixMs::new(earliest), UnixMs::new(latest))),
+        // Tick intervals count back from the newest candle
+        PlotData::TickBased(tick_aggr) => {
+            let last = tick_aggr.datapoints.len().checked_sub(1)?;
+            let oldest = tick_aggr
+                .datapoints
+                .get(last.saturating_sub(latest as usize))?;
+            let newest = tick_aggr
+                .datapoints
+                .get(last.saturating_sub(earliest as usize))?;
+            Some((oldest.kline.time, newest.kline.time))
+        }
+    }
+}
+
+/// Profile of the candles opened within `from..=to`, with the intervals of the first and
+/// last of them
+fn span_profile(
+    data_source: &PlotData<KlineDataPoint>,
+    from: UnixMs,
+    to: UnixMs,
+    value_area_pct: u8,
+) -> Option<(VolumeProfile, u64, u64)> {
+    match data_source {
+        PlotData::TimeBased(timeseries) => {
+            let candles = timeseries.datapoints.range(from..=to);
+            let first = candles.clone().next()?.0.as_u64();
+            let last = candles.clone().next_back()?.0.as_u64();
+
+            let profile = VolumeProfile::from_footprints(
+                candles.map(|(_, dp)| &dp.footprint),
+                value_area_pct,
+            );
+            Some((profile, first, last))
+        }
+        PlotData::TickBased(tick_aggr) => {
+            let datapoints = &tick_aggr.datapoints;
+            let start = datapoints.partition_point(|dp| dp.kline.time < from);
+            let end = datapoints.partition_point(|dp| dp.kline.time <= to);
+            if start >= end {
+                return None;
+            }
+
+            let profile = VolumeProfile::from_footprints(
+                datapoints[start..end].iter().map(|dp| &dp.footprint),
+                value_area_pct,
+            );
+            let newest = datapoints.len() - 1;
+            Some((
+                profile,
+                (newest - start) as u64,
+                (newest - (end - 1)) as u64,
+            ))
+        }
+    }
+}
+
+/// Horizontal histogram of a profile spanning `left..left + width`, its bars anchored to
+/// the right edge when `from_right`, with its point of control and value area bounds
+fn draw_profile(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    profile: &VolumeProfile,
+    left: f32,
+    width: f32,
+    from_right: bool,
+    theme: &Theme,
+) {
+    let max_qty = profile.max_qty().to_f64();
+    if max_qty <= 0.0 {
+        return;
+    }
+
+    let palette = theme.extended_palette();
+    let right = left + width;
+    let bar_height = chart.cell_height * 0.9;
+
+    let in_value_area = |price: Price| {
+        profile
+            .value_area
+            .is_some_and(|va| price >= va.val && price <= va.vah)
+    };
+
+    for (price, (buy, sell)) in &profile.levels {
+        let y = chart.price_to_y(*price) - bar_height / 2.0;
+        let alpha = if in_value_area(*price) { 0.4 } else { 0.15 };
+
+        let sell_width = width * (sell.to_f64() / max_qty) as f32;
+        let buy_width = width * (buy.to_f64() / max_qty) as f32;
+
+        // Sells sit against the anchored edge, buys stacked after them
+        let (sell_x, buy_x) = if from_right {
+            (right - sell_width, right - sell_width - buy_width)
+        } else {
+            (left, left + sell_width)
+        };
+
+        frame.fill_rectangle(
+            Point::new(sell_x, y),
+            Size::new(sell_width, bar_height),
+            palette.danger.base.color.scale_alpha(alpha),
+        );
+        frame.fill_rectangle(
+            Point::new(buy_x, y),
+            Size::new(buy_width, bar_height),
+            palette.success.base.color.scale_alpha(alpha),
+        );
+    }
+
+    if let Some(va) = profile.value_area {
+        let across = |price: Price| {
+            let y = chart.price_to_y(price);
+            Path::line(Point::new(left, y), Point::new(right, y))
+        };
+
+        frame.stroke(
+            &across(va.poc),
+            Stroke::with_color(Stroke::default(), palette.warning.base.color),
+        );
+
+        let dashed = style::dashed_line(theme);
+        frame.stroke(&across(va.vah), dashed);
+        frame.stroke(&across(va.val), dashed);
+    }
+}
+
 fn draw_all_npocs(
     data_source: &PlotData<KlineDataPoint>,
     frame: &mut canvas::Frame,
@@ -1256,9 +2493,13 @@ fn draw_all_npocs(
     spacing: ContentGaps,
     imb_study_on: bool,
 ) {
-    let Some(lookback) = studies.iter().find_map(|study| {
-        if let FootprintStudy::NPoC { lookback } = study {
-            Some(*lookback)
+    let Some((lookback, unfinished_auctions)) = studies.iter().find_map(|study| {
+        if let FootprintStudy::NPoC {
+            lookback,
+            unfinished_auctions,
+        } = study
+        {
+            Some((*lookback, *unfinished_auctions))
         } else {
             None
         }
@@ -1266,13 +2507,18 @@ fn draw_all_npocs(
         return;
     };
 
-    let (filled_color, naked_color) = (
+    let (filled_color, naked_color, unfinished_color) = (
         palette.background.strong.color,
         if palette.is_dark {
             palette.warning.weak.color.scale_alpha(0.5)
         } else {
             palette.warning.strong.color
         },
+        if palette.is_dark {
+            palette.primary.weak.color.scale_alpha(0.5)
+        } else {
+            palette.primary.strong.color
+        },
     );
 
     let line_height = cell_height.min(1.0);
@@ -1335,10 +2581,10 @@ fn draw_all_npocs(
         }
     };
 
-    let mut draw_the_line = |interval: u64, poc: &PointOfControl| {
+    let mut draw_the_line = |interval: u64, price: Price, status: NPoc, naked_color: Color| {
         let start_x = start_x_for(interval_to_x(interval));
 
-        let (line_width, color) = match poc.status {
+        let (line_width, color) = match status {
             NPoc::Naked => {
                 let end_x = end_x_for(rightmost_cell_center_x);
                 let line_width = end_x - start_x;
@@ -1359,12 +2605,23 @@ fn draw_all_npocs(
         };
 
         frame.fill_rectangle(
-            Point::new(start_x, price_to_y(poc.price) - line_height / 2.0),
+            Point::new(start_x, price_to_y(price) - line_height / 2.0),
             Size::new(line_width, line_height),
             color,
         );
     };
 
+    let mut draw_levels = |interval: u64, footprint: &KlineTrades| {
+        if let Some(poc) = &footprint.poc {
+            draw_the_line(interval, poc.price, poc.status, naked_color);
+        }
+        if unfinished_auctions {
+            for auction in &footprint.unfinished_auctions {
+                draw_the_line(interval, auction.price, auction.status, unfinished_color);
+            }
+        }
+    };
+
     match data_source {
         PlotData::TickBased(tick_aggr) => {
             tick_aggr
@@ -1373,8 +2630,7 @@ fn draw_all_npocs(
                 .rev()
                 .enumerate()
                 .take(lookback)
-                .filter_map(|(index, dp)| dp.footprint.poc.as_ref().map(|poc| (index as u64, poc)))
-                .for_each(|(interval, poc)| draw_the_line(interval, poc));
+                .for_each(|(index, dp)| draw_levels(index as u64, &dp.footprint));
         }
         PlotData::TimeBased(timeseries) => {
             timeseries
@@ -1382,13 +2638,7 @@ fn draw_all_npocs(
                 .iter()
                 .rev()
                 .take(lookback)
-                .filter_map(|(timestamp, dp)| {
-                    dp.footprint
-                        .poc
-                        .as_ref()
-                        .map(|poc| (timestamp.as_u64(), poc))
-                })
-                .for_each(|(interval, poc)| draw_the_line(interval, poc));
+                .for_each(|(timestamp, dp)| draw_levels(timestamp.as_u64(), &dp.footprint));
         }
     }
 }
@@ -1683,40 +2933,165 @@ fn draw_clusters(
             );
         }
     }
+}
 
-    if show_text {
-        let Some(summary) = FootprintSummary::from_trades(footprint) else {
-            return;
-        };
+/// Volume and delta of the candle, written under its low
+fn draw_candle_summary(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    x_position: f32,
+    cell_height: f32,
+    text_size: f32,
+    palette: &Extended,
+    kline: &Kline,
+    summary: &FootprintSummary,
+) {
+    let summary_y = price_to_y(kline.low) + cell_height * 1.5;
+    let line_spacing = text_size * 1.2;
 
-        let summary_y = price_to_y(kline.low) + cell_height * 1.5;
-        let line_spacing = text_size * 1.2;
-
-        draw_cluster_text(
-            frame,
-            &format!("V: {}", abbr_large_numbers(summary.total.to_f64())),
-            Point::new(x_position, summary_y),
-            text_size * 0.9,
-            palette.background.weakest.text,
-            Alignment::Center,
-            Alignment::Start,
-        );
+    draw_cluster_text(
+        frame,
+        &format!("V: {}", abbr_large_numbers(summary.total.to_f64())),
+        Point::new(x_position, summary_y),
+        text_size * 0.9,
+        palette.background.weakest.text,
+        Alignment::Center,
+        Alignment::Start,
+    );
+
+    let delta_color = if summary.delta >= Qty::ZERO {
+        palette.success.base.color
+    } else {
+        palette.danger.base.color
+    };
+
+    draw_cluster_text(
+        frame,
+        &format!("Δ: {}", abbr_large_numbers(summary.delta.to_f64())),
+        Point::new(x_position, summary_y + line_spacing),
+        text_size * 0.9,
+        delta_color,
+        Alignment::Center,
+        Alignment::Start,
+    );
+}
 
-        let delta_color = if summary.delta >= Qty::ZERO {
+/// Rows of per-candle numbers pinned to the bottom of the chart. Delta cells are shaded by
+/// their size against the visible candles, the lowest and highest delta in full color
+fn draw_summary_footer(
+    frame: &mut canvas::Frame,
+    footer: SummaryFooter,
+    summaries: &[(f32, FootprintSummary)],
+    region: &Rectangle,
+    cell_width: f32,
+    scaling: f32,
+    palette: &Extended,
+) {
+    let rows = footer.rows();
+    if rows == 0 {
+        return;
+    }
+
+    let row_height = FOOTER_ROW_HEIGHT / scaling;
+    let text_size = style::text_size::SMALL / scaling;
+    let top = region.y + region.height - row_height * rows as f32;
+
+    frame.fill_rectangle(
+        Point::new(region.x, top),
+        Size::new(region.width, row_height * rows as f32),
+        palette.background.base.color.scale_alpha(0.9),
+    );
+
+    let show_values = cell_width * scaling >= FOOTER_MIN_CELL_WIDTH;
+    let max_abs_delta = summaries
+        .iter()
+        .map(|(_, summary)| summary.delta.to_f64().abs())
+        .fold(0.0, f64::max);
+    let lowest = summaries.iter().map(|(_, summary)| summary.delta).min();
+    let highest = summaries.iter().map(|(_, summary)| summary.delta).max();
+
+    let text_color = palette.background.weakest.text;
+    let delta_color = |delta: Qty| {
+        if delta >= Qty::ZERO {
             palette.success.base.color
         } else {
             palette.danger.base.color
+        }
+    };
+
+    let mut row_y = top;
+    let mut draw_row =
+        |label: &str, shaded: bool, cell: &dyn Fn(&FootprintSummary) -> (String, Color)| {
+            let center_y = row_y + row_height / 2.0;
+
+            for (x, summary) in summaries {
+                if shaded {
+                    let is_extreme =
+                        Some(summary.delta) == lowest || Some(summary.delta) == highest;
+                    let alpha = if is_extreme {
+                        0.6
+                    } else if max_abs_delta > 0.0 {
+                        0.1 + 0.35 * (summary.delta.to_f64().abs() / max_abs_delta) as f32
+                    } else {
+                        0.1
+                    };
+
+                    frame.fill_rectangle(
+                        Point::new(x - cell_width * 0.45, row_y),
+                        Size::new(cell_width * 0.9, row_height),
+                        delta_color(summary.delta).scale_alpha(alpha),
+                    );
+                }
+
+                let (content, color) = cell(summary);
+                if show_values {
+                    draw_cluster_text(
+                        frame,
+                        &content,
+                        Point::new(*x, center_y),
+                        text_size,
+                        color,
+                        Alignment::Center,
+                        Alignment::Center,
+                    );
+                }
+            }
+
+            frame.fill_text(canvas::Text {
+                content: label.to_string(),
+                position: Point::new(region.x + 4.0 / scaling, center_y),
+                size: iced::Pixels(text_size),
+                color: text_color.scale_alpha(0.6),
+                align_y: Alignment::Center.into(),
+                font: style::AZERET_MONO,
+                ..canvas::Text::default()
+            });
+
+            row_y += row_height;
         };
 
-        draw_cluster_text(
-            frame,
-            &format!("Δ: {}", abbr_large_numbers(summary.delta.to_f64())),
-            Point::new(x_position, summary_y + line_spacing),
-            text_size * 0.9,
-            delta_color,
-            Alignment::Center,
-            Alignment::Start,
-        );
+    if footer.delta {
+        draw_row("Δ", true, &|summary| {
+            (abbr_large_numbers(summary.delta.to_f64()), text_color)
+        });
+    }
+    if footer.delta_pct {
+        draw_row("Δ%", false, &|summary| {
+            (
+                format!("{:+.1}%", summary.delta_pct),
+                delta_color(summary.delta),
+            )
+        });
+    }
+    if footer.volume {
+        draw_row("Vol", false, &|summary| {
+            (abbr_large_numbers(summary.total.to_f64()), text_color)
+        });
+    }
+    if footer.trades {
+        draw_row("#", false, &|summary| {
+            (summary.trades.to_string(), text_color)
+        });
     }
 }
 
@@ -1831,6 +3206,269 @@ fn draw_cluster_text(
     });
 }
 
+/// Mark price as a solid line, index price dashed, both in the warning color
+fn draw_mark_prices(
+    frame: &mut canvas::Frame,
+    mark_prices: &BTreeMap<u64, (Price, Option<Price>)>,
+    price_to_y: impl Fn(Price) -> f32,
+    interval_to_x: impl Fn(u64) -> f32,
+    earliest: u64,
+    latest: u64,
+    palette: &Extended,
+) {
+    if earliest > latest {
+        return;
+    }
+
+    // One point past each edge so the lines reach across the visible region
+    let points = mark_prices
+        .range(..earliest)
+        .next_back()
+        .into_iter()
+        .chain(mark_prices.range(earliest..=latest))
+        .chain(mark_prices.range(latest.saturating_add(1)..).next())
+        .map(|(interval, (mark, index))| {
+            (
+                interval_to_x(*interval),
+                price_to_y(*mark),
+                index.map(&price_to_y),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if points.len() < 2 {
+        return;
+    }
+
+    let color = palette.warning.base.color;
+
+    let mark_line = Path::new(|p| {
+        p.move_to(Point::new(points[0].0, points[0].1));
+        for (x, y, _) in &points[1..] {
+            p.line_to(Point::new(*x, *y));
+        }
+    });
+    frame.stroke(&mark_line, Stroke::with_color(Stroke::default(), color));
+
+    let index_line = Path::new(|p| {
+        let mut connected = false;
+        for (x, _, index_y) in &points {
+            match index_y {
+                Some(y) if connected => p.line_to(Point::new(*x, *y)),
+                Some(y) => {
+                    p.move_to(Point::new(*x, *y));
+                    connected = true;
+                }
+                None => connected = false,
+            }
+        }
+    });
+    frame.stroke(
+        &index_line,
+        Stroke::with_color(
+            style::dashed_line_from_palette(palette),
+            color.scale_alpha(0.7),
+        ),
+    );
+}
+
+/// Mark and index price at the hovered interval, or the latest ones, under the OHLC readout
+fn draw_mark_price_legend(
+    frame: &mut canvas::Frame,
+    mark_prices: &BTreeMap<u64, (Price, Option<Price>)>,
+    at_interval: Option<u64>,
+    ticker_info: &TickerInfo,
+    palette: &Extended,
+) {
+    let entry = match at_interval {
+        Some(interval) => mark_prices.range(..=interval).next_back(),
+        None => mark_prices.last_key_value(),
+    };
+    let Some((_, (mark, index))) = entry else {
+        return;
+    };
+
+    let precision = ticker_info.min_ticksize;
+    let mut content = format!("Mark {}", mark.to_string(precision));
+    if let Some(index) = index {
+        content.push_str(&format!("  Index {}", index.to_string(precision)));
+    }
+
+    let position = Point::new(8.0, 28.0);
+
+    frame.fill_rectangle(
+        position,
+        Size::new(content.len() as f32 * (TEXT_SIZE * 0.8), 16.0),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.fill_text(canvas::Text {
+        content,
+        position,
+        size: iced::Pixels(crate::style::text_size::BODY),
+        color: palette.warning.base.color,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
+
+/// Buy/sell events are drawn as triangles pointing into their side, everything else
+/// as a dot. Events without a price sit at the top of the visible region.
+fn draw_annotations(
+    frame: &mut canvas::Frame,
+    annotations: &[ExternalEvent],
+    data_source: &PlotData<KlineDataPoint>,
+    price_to_y: impl Fn(Price) -> f32,
+    interval_to_x: impl Fn(u64) -> f32,
+    basis: Basis,
+    scaling: f32,
+    region: Rectangle,
+    palette: &Extended,
+) {
+    let size = 5.0 / scaling;
+    let text_size = TEXT_SIZE / scaling;
+
+    for annotation in annotations {
+        let Some(interval) = marker_interval(annotation.time(), data_source, basis) else {
+            continue;
+        };
+
+        let x = interval_to_x(interval);
+        if x < region.x - size || x > region.x + region.width + size {
+            continue;
+        }
+
+        let y = annotation.price.map_or(region.y + 2.0 * size, |price| {
+            price_to_y(Price::from_f64(price))
+        });
+
+        let (color, marker) = match annotation.side {
+            Some(external::Side::Buy) => (
+                palette.success.base.color,
+                Path::new(|p| {
+                    p.move_to(Point::new(x, y));
+                    p.line_to(Point::new(x - size, y + 1.6 * size));
+                    p.line_to(Point::new(x + size, y + 1.6 * size));
+                    p.close();
+                }),
+            ),
+            Some(external::Side::Sell) => (
+                palette.danger.base.color,
+                Path::new(|p| {
+                    p.move_to(Point::new(x, y));
+                    p.line_to(Point::new(x - size, y - 1.6 * size));
+                    p.line_to(Point::new(x + size, y - 1.6 * size));
+                    p.close();
+                }),
+            ),
+            None => (
+                palette.primary.base.color,
+                Path::circle(Point::new(x, y), size * 0.8),
+            ),
+        };
+
+        frame.fill(&marker, color);
+
+        frame.fill_text(canvas::Text {
+            content: annotation.summary(),
+            position: Point::new(x + 1.5 * size, y),
+            size: iced::Pixels(text_size),
+            color: color.scale_alpha(0.9),
+            align_y: Alignment::Center.into(),
+            font: style::AZERET_MONO,
+            ..canvas::Text::default()
+        });
+    }
+}
+
+/// Key of the kline `time` falls in, the index from the latest bar on tick basis
+pub(crate) fn marker_interval(
+    time: UnixMs,
+    data_source: &PlotData<KlineDataPoint>,
+    basis: Basis,
+) -> Option<u64> {
+    match (basis, data_source) {
+        (Basis::Time(timeframe), _) => Some(time.floor_to(timeframe).as_u64()),
+        // Datapoints open in time order, the last one opened by `time` holds it
+        (Basis::Tick(_) | Basis::Bars(_), PlotData::TickBased(tick_aggr)) => {
+            let opened = tick_aggr
+                .datapoints
+                .partition_point(|dp| dp.kline.time <= time);
+            (opened > 0).then(|| (tick_aggr.datapoints.len() - opened) as u64)
+        }
+        (Basis::Tick(_) | Basis::Bars(_), PlotData::TimeBased(_)) => None,
+    }
+}
+
+/// Entries as filled triangles pointing into their side, exits as outlined ones labeled
+/// with the profit they realized
+fn draw_journal_markers(
+    frame: &mut canvas::Frame,
+    markers: &[JournalMarker],
+    data_source: &PlotData<KlineDataPoint>,
+    price_to_y: impl Fn(Price) -> f32,
+    interval_to_x: impl Fn(u64) -> f32,
+    basis: Basis,
+    scaling: f32,
+    region: Rectangle,
+    palette: &Extended,
+) {
+    let size = 5.0 / scaling;
+    let text_size = TEXT_SIZE / scaling;
+
+    for marker in markers {
+        let Some(interval) = marker_interval(marker.time, data_source, basis) else {
+            continue;
+        };
+
+        let x = interval_to_x(interval);
+        if x < region.x - size || x > region.x + region.width + size {
+            continue;
+        }
+        let y = price_to_y(marker.price);
+
+        let (color, tip_offset) = if marker.is_buy {
+            (palette.success.strong.color, 1.6 * size)
+        } else {
+            (palette.danger.strong.color, -1.6 * size)
+        };
+        let triangle = Path::new(|p| {
+            p.move_to(Point::new(x, y));
+            p.line_to(Point::new(x - size, y + tip_offset));
+            p.line_to(Point::new(x + size, y + tip_offset));
+            p.close();
+        });
+
+        let label = match marker.review.role {
+            JournalRole::Entry => {
+                frame.fill(&triangle, color);
+                format!("{} {}", JournalRole::Entry, marker.qty)
+            }
+            JournalRole::Exit => {
+                frame.stroke(
+                    &triangle,
+                    Stroke::default()
+                        .with_color(color)
+                        .with_width(1.5 / scaling),
+                );
+                match marker.review.realized {
+                    Some(pnl) => format!("{} {} ({pnl:+.2})", JournalRole::Exit, marker.qty),
+                    None => format!("{} {}", JournalRole::Exit, marker.qty),
+                }
+            }
+        };
+
+        frame.fill_text(canvas::Text {
+            content: label,
+            position: Point::new(x + 1.5 * size, y + tip_offset / 2.0),
+            size: iced::Pixels(text_size),
+            color: color.scale_alpha(0.9),
+            align_y: Alignment::Center.into(),
+            font: style::AZERET_MONO,
+            ..canvas::Text::default()
+        });
+    }
+}
+
 fn draw_crosshair_tooltip(
     data: &PlotData<KlineDataPoint>,
     ticker_info: &TickerInfo,
@@ -1865,7 +3503,7 @@ fn draw_crosshair_tooltip(
                 .or_else(|| {
                     let right_of_latest = match basis {
                         Basis::Time(_) => at_interval > visible_latest,
-                        Basis::Tick(_) => at_interval < visible_earliest,
+                        Basis::Tick(_) | Basis::Bars(_) => at_interval < visible_earliest,
                     };
 
                     if right_of_latest {
@@ -1885,7 +3523,7 @@ fn draw_crosshair_tooltip(
         }
         (PlotData::TickBased(tick_aggr), Some(at_interval)) => {
             let kline_at = |interval: u64| {
-                let index = (interval / u64::from(tick_aggr.interval.0)) as usize;
+                let index = (interval / basis.index_step()) as usize;
                 (index < tick_aggr.datapoints.len())
                     .then(|| &tick_aggr.datapoints[tick_aggr.datapoints.len() - 1 - index].kline)
             };
@@ -1895,7 +3533,7 @@ fn draw_crosshair_tooltip(
             kline_at(at_interval).or_else(|| {
                 let right_of_latest = match basis {
                     Basis::Time(_) => at_interval > visible_latest,
-                    Basis::Tick(_) => at_interval < visible_earliest,
+                    Basis::Tick(_) | Basis::Bars(_) => at_interval < visible_earliest,
                 };
 
                 if in_visible || right_of_latest {
@@ -1,9 +1,13 @@
 pub mod linear;
 pub mod timeseries;
 
-use crate::{chart::TEXT_SIZE, style::AZERET_MONO};
+use crate::{
+    chart::TEXT_SIZE,
+    style::{AZERET_MONO, dpi},
+};
 
 use super::{Basis, Interaction, Message};
+use data::alerts::LevelEdit;
 use data::chart::Autoscale;
 use data::config::timezone::TimeLabelKind;
 use data::util::round_to_tick;
@@ -130,19 +134,21 @@ impl AxisLabel {
         }
     }
 
-    pub fn filter_and_draw(labels: &[AxisLabel], frame: &mut Frame) {
+    pub fn filter_and_draw(labels: &[AxisLabel], frame: &mut Frame, scale_factor: f32) {
         for i in (0..labels.len()).rev() {
             let should_draw = labels[i + 1..]
                 .iter()
                 .all(|existing| !existing.intersects(&labels[i]));
 
             if should_draw {
-                labels[i].draw(frame);
+                labels[i].draw(frame, scale_factor);
             }
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    fn draw(&self, frame: &mut Frame, scale_factor: f32) {
+        let text_size = |label: &LabelContent| dpi::text_size(label.text_size, scale_factor);
+
         match self {
             AxisLabel::X { bounds, label } => {
                 let frame_bounds = frame.size();
@@ -161,7 +167,7 @@ impl AxisLabel {
                 let label = canvas::Text {
                     content: label.content.clone(),
                     position: bounds.center(),
-                    size: label.text_size.into(),
+                    size: text_size(label).into(),
                     color: label.text_color,
                     align_y: Alignment::Center.into(),
                     align_x: Alignment::Center.into(),
@@ -189,7 +195,7 @@ impl AxisLabel {
                         content: value_label.content.clone(),
                         position: Point::new(bounds.x + 4.0, bounds.y + 2.0),
                         color: value_label.text_color,
-                        size: value_label.text_size.into(),
+                        size: text_size(value_label).into(),
                         font: AZERET_MONO,
                         ..canvas::Text::default()
                     };
@@ -200,7 +206,7 @@ impl AxisLabel {
                         content: timer_label.content.clone(),
                         position: Point::new(bounds.x + 4.0, bounds.y + 15.0),
                         color: timer_label.text_color,
-                        size: timer_label.text_size.into(),
+                        size: text_size(timer_label).into(),
                         font: AZERET_MONO,
                         ..canvas::Text::default()
                     };
@@ -211,7 +217,7 @@ impl AxisLabel {
                         content: value_label.content.clone(),
                         position: Point::new(bounds.x + 4.0, bounds.y + 4.0),
                         color: value_label.text_color,
-                        size: value_label.text_size.into(),
+                        size: text_size(value_label).into(),
                         font: AZERET_MONO,
                         ..canvas::Text::default()
                     };
@@ -235,11 +241,12 @@ pub struct AxisLabelsX<'a> {
     pub chart_bounds: Rectangle,
     pub interval_keys: Option<Vec<u64>>,
     pub autoscaling: Option<Autoscale>,
+    pub scale_factor: f32,
 }
 
 impl AxisLabelsX<'_> {
-    fn drag_bounds(bounds: Rectangle) -> Rectangle {
-        bounds.shrink(AXIS_DRAG_EDGE_GUARD)
+    fn drag_bounds(&self, bounds: Rectangle) -> Rectangle {
+        bounds.shrink(dpi::hit_target(AXIS_DRAG_EDGE_GUARD, self.scale_factor))
     }
 
     fn calc_crosshair_pos(&self, cursor_pos: Point, region: Rectangle) -> (f32, f32, i32) {
@@ -259,7 +266,7 @@ impl AxisLabelsX<'_> {
         palette: &Extended,
     ) -> Option<AxisLabel> {
         match self.basis {
-            Basis::Tick(_) => {
+            Basis::Tick(_) | Basis::Bars(_) => {
                 let Some(interval_keys) = &self.interval_keys else {
                     return None;
                 };
@@ -367,7 +374,7 @@ impl AxisLabelsX<'_> {
                     self.max.saturating_add(diff)
                 }
             }
-            Basis::Tick(_) => {
+            Basis::Tick(_) | Basis::Bars(_) => {
                 let tick = -(x / self.cell_width);
                 tick.round() as u64
             }
@@ -385,7 +392,7 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
-        let drag_bounds = Self::drag_bounds(bounds);
+        let drag_bounds = self.drag_bounds(bounds);
 
         if let Event::Mouse(mouse::Event::ButtonReleased(_)) = event {
             *interaction = Interaction::None;
@@ -474,7 +481,7 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
             let mut labels: Vec<AxisLabel> = Vec::with_capacity(label_count + 1); // +1 for crosshair
 
             match self.basis {
-                Basis::Tick(_) => {
+                Basis::Tick(_) | Basis::Bars(_) => {
                     if let Some(interval_keys) = &self.interval_keys {
                         let last_idx = interval_keys.len() - 1;
                         let mut last_x: Option<f32> = None;
@@ -534,7 +541,7 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
                 labels.push(label);
             }
 
-            AxisLabel::filter_and_draw(&labels, frame);
+            AxisLabel::filter_and_draw(&labels, frame, self.scale_factor);
         });
 
         vec![labels]
@@ -549,7 +556,7 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
         match interaction {
             Interaction::Panning { .. } => mouse::Interaction::None,
             Interaction::Zoomin { .. } => mouse::Interaction::ResizingHorizontally,
-            Interaction::None if cursor.is_over(Self::drag_bounds(bounds)) => {
+            Interaction::None if cursor.is_over(self.drag_bounds(bounds)) => {
                 mouse::Interaction::ResizingHorizontally
             }
             _ => mouse::Interaction::default(),
@@ -569,11 +576,21 @@ pub struct AxisLabelsY<'a> {
     pub cell_height: f32,
     pub basis: Basis,
     pub chart_bounds: Rectangle,
+    pub scale_factor: f32,
+    pub alert_levels: &'a [(u32, exchange::unit::Price)],
+}
+
+/// Price axis interaction, plus the alert level being dragged along it
+#[derive(Default)]
+pub struct AxisYState {
+    interaction: Interaction,
+    /// Id of the dragged alert and the cursor's y within the axis
+    dragged_alert: Option<(u32, f32)>,
 }
 
 impl AxisLabelsY<'_> {
-    fn drag_bounds(bounds: Rectangle) -> Rectangle {
-        bounds.shrink(AXIS_DRAG_EDGE_GUARD)
+    fn drag_bounds(&self, bounds: Rectangle) -> Rectangle {
+        bounds.shrink(dpi::hit_target(AXIS_DRAG_EDGE_GUARD, self.scale_factor))
     }
 
     fn visible_region(&self, size: Size) -> Rectangle {
@@ -591,19 +608,78 @@ impl AxisLabelsY<'_> {
     fn y_to_price(&self, y: f32) -> f32 {
         self.min - (y / self.cell_height) * self.tick_size
     }
+
+    /// Highest and lowest price shown by an axis of `size`
+    fn price_range(&self, size: Size) -> (f32, f32) {
+        let region = self.visible_region(size);
+
+        (
+            self.y_to_price(region.y),
+            self.y_to_price(region.y + region.height),
+        )
+    }
+
+    /// Price under `y`, in axis coordinates, rounded to the tick size
+    fn price_at(&self, bounds: Rectangle, y: f32) -> f32 {
+        let (highest, lowest) = self.price_range(bounds.size());
+        let price = lowest + (highest - lowest) * (bounds.height - y) / bounds.height;
+
+        round_to_tick(price, self.tick_size)
+    }
+
+    fn price_to_axis_y(&self, bounds: Rectangle, price: f32) -> f32 {
+        let (highest, lowest) = self.price_range(bounds.size());
+
+        bounds.height - ((price - lowest) / (highest - lowest) * bounds.height)
+    }
+
+    /// Alert level whose label is under `y`, in axis coordinates
+    fn alert_at(&self, bounds: Rectangle, y: f32) -> Option<u32> {
+        self.alert_levels.iter().find_map(|&(id, price)| {
+            let y_pos = self.price_to_axis_y(bounds, price.to_f32_lossy());
+            let label = calc_label_rect(y_pos, 1, crate::style::text_size::BODY, bounds);
+
+            (y >= label.y && y <= label.y + label.height).then_some(id)
+        })
+    }
+
+    fn alert_label(&self, bounds: Rectangle, price: f32, palette: &Extended) -> AxisLabel {
+        let y_pos = self.price_to_axis_y(bounds, price);
+
+        AxisLabel::Y {
+            bounds: calc_label_rect(y_pos, 1, crate::style::text_size::BODY, bounds),
+            value_label: LabelContent {
+                content: format!("{:.*}", self.decimals, price),
+                background_color: Some(palette.warning.base.color),
+                text_color: palette.warning.base.text,
+                text_size: crate::style::text_size::BODY,
+            },
+            timer_label: None,
+        }
+    }
 }
 
 impl canvas::Program<Message> for AxisLabelsY<'_> {
-    type State = Interaction;
+    type State = AxisYState;
 
     fn update(
         &self,
-        interaction: &mut Interaction,
+        state: &mut AxisYState,
         event: &Event,
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
-        let drag_bounds = Self::drag_bounds(bounds);
+        let drag_bounds = self.drag_bounds(bounds);
+        let interaction = &mut state.interaction;
+
+        if let Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) = event
+            && let Some((id, y)) = state.dragged_alert.take()
+        {
+            let price = f64::from(self.price_at(bounds, y));
+            let message = Message::AlertLevel(LevelEdit::Move(id, price));
+
+            return Some(canvas::Action::publish(message).and_capture());
+        }
 
         if let Event::Mouse(mouse::Event::ButtonReleased(_)) = event {
             *interaction = Interaction::None;
@@ -612,6 +688,13 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
         if let Event::Mouse(mouse_event) = event {
             match mouse_event {
                 mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                    if let Some(position) = cursor.position_in(bounds)
+                        && let Some(id) = self.alert_at(bounds, position.y)
+                    {
+                        state.dragged_alert = Some((id, position.y));
+                        return Some(canvas::Action::request_redraw().and_capture());
+                    }
+
                     if cursor.position_in(drag_bounds).is_some()
                         && let Some(cursor_position) = cursor.position()
                     {
@@ -620,7 +703,24 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                         };
                     }
                 }
+                mouse::Event::ButtonPressed(mouse::Button::Right) => {
+                    let position = cursor.position_in(bounds)?;
+
+                    let edit = match self.alert_at(bounds, position.y) {
+                        Some(id) => LevelEdit::Remove(id),
+                        None => LevelEdit::Place(f64::from(self.price_at(bounds, position.y))),
+                    };
+
+                    return Some(canvas::Action::publish(Message::AlertLevel(edit)).and_capture());
+                }
                 mouse::Event::CursorMoved { .. } => {
+                    if let Some((_, y)) = &mut state.dragged_alert {
+                        if let Some(position) = cursor.position() {
+                            *y = (position.y - bounds.y).clamp(0.0, bounds.height);
+                        }
+                        return Some(canvas::Action::request_redraw().and_capture());
+                    }
+
                     if let Interaction::Zoomin {
                         ref mut last_position,
                     } = *interaction
@@ -667,7 +767,7 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         renderer: &Renderer,
         theme: &Theme,
         bounds: Rectangle,
@@ -693,6 +793,12 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                 Some(self.decimals),
             );
 
+            all_labels.extend(
+                self.alert_levels
+                    .iter()
+                    .map(|(_, price)| self.alert_label(bounds, price.to_f32_lossy(), palette)),
+            );
+
             // Last price (priority 2)
             if let Some(label) = self.last_price {
                 let candle_close_label = match self.basis {
@@ -729,7 +835,7 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                             None
                         }
                     }
-                    Basis::Tick(_) => None,
+                    Basis::Tick(_) | Basis::Bars(_) => None,
                 };
 
                 let (price, color) = label.get_with_color(palette);
@@ -784,22 +890,40 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                 });
             }
 
-            AxisLabel::filter_and_draw(&all_labels, frame);
+            AxisLabel::filter_and_draw(&all_labels, frame, self.scale_factor);
         });
 
-        vec![labels]
+        let Some((_, y)) = state.dragged_alert else {
+            return vec![labels];
+        };
+
+        // Follows the cursor, so it's kept out of the cache
+        let mut frame = Frame::new(renderer, bounds.size());
+        self.alert_label(bounds, self.price_at(bounds, y), palette)
+            .draw(&mut frame, self.scale_factor);
+
+        vec![labels, frame.into_geometry()]
     }
 
     fn mouse_interaction(
         &self,
-        interaction: &Interaction,
+        state: &AxisYState,
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> mouse::Interaction {
-        match interaction {
+        if state.dragged_alert.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+        if let Some(position) = cursor.position_in(bounds)
+            && self.alert_at(bounds, position.y).is_some()
+        {
+            return mouse::Interaction::Grab;
+        }
+
+        match &state.interaction {
             Interaction::Zoomin { .. } => mouse::Interaction::ResizingVertically,
             Interaction::Panning { .. } => mouse::Interaction::None,
-            Interaction::None if cursor.is_over(Self::drag_bounds(bounds)) => {
+            Interaction::None if cursor.is_over(self.drag_bounds(bounds)) => {
                 mouse::Interaction::ResizingVertically
             }
             _ => mouse::Interaction::default(),
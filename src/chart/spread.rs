@@ -0,0 +1,314 @@
+use super::comparison::{self, ComparisonChart, SERIES_MAX_POINTS, default_color_for};
+use crate::widget::chart::comparison::{LineComparison, LineComparisonEvent};
+use crate::widget::chart::{SeriesLike, ValueScale};
+
+use data::chart::Basis;
+use data::chart::spread::{Config, SpreadMode};
+use exchange::adapter::StreamKind;
+use exchange::{Kline, TickerInfo, Trade};
+
+use std::cmp::Ordering;
+use std::time::Instant;
+
+/// A spread is always built from exactly two legs, `A op B`
+pub const MAX_LEGS: usize = 2;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Chart(LineComparisonEvent),
+}
+
+/// Derived series holding `A op B` for every timestamp both legs have a close on
+pub struct SpreadSeries {
+    legs: [TickerInfo; MAX_LEGS],
+    mode: SpreadMode,
+    points: Vec<(u64, f32)>,
+    color: iced::Color,
+}
+
+impl SpreadSeries {
+    /// Pairs up the closes of both legs, `pa` and `pb` have to be sorted by time
+    pub(super) fn build(
+        legs: [TickerInfo; MAX_LEGS],
+        mode: SpreadMode,
+        pa: &[(u64, f32)],
+        pb: &[(u64, f32)],
+        color: iced::Color,
+    ) -> Self {
+        let mut points = Vec::with_capacity(pa.len().min(pb.len()));
+
+        let (mut i, mut j) = (0usize, 0usize);
+        while i < pa.len() && j < pb.len() {
+            let (xa, ya) = pa[i];
+            let (xb, yb) = pb[j];
+            match xa.cmp(&xb) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    if let Some(v) = mode.apply(ya, yb) {
+                        points.push((xa, v));
+                    }
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        Self {
+            legs,
+            mode,
+            points,
+            color,
+        }
+    }
+}
+
+impl SeriesLike for SpreadSeries {
+    fn name(&self) -> String {
+        self.legend_label()
+    }
+
+    fn points(&self) -> &[(u64, f32)] {
+        &self.points
+    }
+
+    fn color(&self) -> iced::Color {
+        self.color
+    }
+
+    fn ticker_info(&self) -> &TickerInfo {
+        &self.legs[0]
+    }
+
+    fn legend_label(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.legs[0].ticker.symbol_and_exchange_string(),
+            self.mode.operator(),
+            self.legs[1].ticker.symbol_and_exchange_string(),
+        )
+    }
+}
+
+/// Plots the difference or ratio of two tickers, e.g. perp–spot basis or BTC/ETH.
+///
+/// The legs themselves are kept in a [`ComparisonChart`], which already knows how to
+/// fetch, backfill and align kline closes for several tickers on one timeframe.
+pub struct SpreadChart {
+    legs: ComparisonChart,
+    spread: Option<SpreadSeries>,
+    pub config: Config,
+    rev: u64,
+}
+
+impl SpreadChart {
+    pub fn new(basis: Basis, tickers: &[TickerInfo], config: Option<Config>) -> Self {
+        let tickers = &tickers[..tickers.len().min(MAX_LEGS)];
+
+        let mut chart = Self {
+            legs: ComparisonChart::new(basis, tickers, None),
+            spread: None,
+            config: config.unwrap_or_default(),
+            rev: 0,
+        };
+        chart.rebuild_spread();
+        chart
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Chart(event) => match event {
+                // Legend buttons act on individual legs, which aren't drawn here
+                LineComparisonEvent::SeriesCog(_)
+                | LineComparisonEvent::SeriesRemove(_)
+                | LineComparisonEvent::DerivedCog(_)
+                | LineComparisonEvent::DerivedRemove(_) => {}
+                // Spreads are drawn in absolute values, there is nothing to rebase
+                LineComparisonEvent::AnchorChanged(_) => {}
+                // Copying the legend is only wired up on the comparison chart
+                LineComparisonEvent::LegendValues(_) => {}
+                event => {
+                    let _ = self.legs.update(comparison::Message::Chart(event));
+                }
+            },
+        }
+    }
+
+    pub fn view(&self, timezone: data::UserTimezone) -> iced::Element<'_, Message> {
+        let placeholder = match &self.spread {
+            None => Some("Add a second ticker to plot the spread"),
+            Some(spread) if spread.points.is_empty() => Some("Waiting for data..."),
+            Some(_) => None,
+        };
+
+        if let Some(label) = placeholder {
+            return iced::widget::center(
+                iced::widget::text(label).size(crate::style::text_size::TITLE),
+            )
+            .into();
+        }
+
+        let chart: iced::Element<_> =
+            LineComparison::<SpreadSeries>::new(self.spread.as_slice(), self.legs.step_ms())
+                .with_timezone(timezone)
+                .with_value_scale(ValueScale::Absolute)
+                .with_zoom(self.legs.zoom())
+                .with_pan(self.legs.pan())
+                .with_scale_factor(self.legs.scale_factor())
+                .version(self.legs.cache_rev().wrapping_add(self.rev))
+                .into();
+
+        iced::widget::container(chart.map(Message::Chart))
+            .padding(1)
+            .into()
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.legs.set_scale_factor(scale_factor);
+    }
+
+    pub fn basis(&self) -> Basis {
+        self.legs.basis()
+    }
+
+    pub fn streams_for_all(&self) -> Vec<StreamKind> {
+        self.legs.streams_for_all()
+    }
+
+    pub fn selected_tickers(&self) -> &[TickerInfo] {
+        self.legs.selected_tickers()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.selected_tickers().len() >= MAX_LEGS
+    }
+
+    pub fn last_update(&self) -> Instant {
+        self.legs.last_update()
+    }
+
+    pub fn insert_history(
+        &mut self,
+        req_id: uuid::Uuid,
+        ticker_info: TickerInfo,
+        klines: &[Kline],
+    ) {
+        self.legs.insert_history(req_id, ticker_info, klines);
+        self.rebuild_spread();
+    }
+
+    pub fn update_latest_kline(&mut self, ticker_info: &TickerInfo, kline: &Kline) {
+        if !self.selected_tickers().contains(ticker_info) {
+            return;
+        }
+
+        self.legs.update_latest_kline(ticker_info, kline);
+        self.refresh_tail();
+    }
+
+    pub fn insert_trades(&mut self, ticker_info: &TickerInfo, buffer: &[Trade]) {
+        if !self.selected_tickers().contains(ticker_info) {
+            return;
+        }
+
+        self.legs.insert_trades(ticker_info, buffer);
+        // Tick bars of the legs close at different times, the pairing has to be redone
+        self.rebuild_spread();
+    }
+
+    pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        self.legs.invalidate(now)
+    }
+
+    pub fn set_basis(&mut self, basis: Basis) -> Option<super::Action> {
+        let action = self.legs.set_basis(basis);
+        self.rebuild_spread();
+        action
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        if self.config != config {
+            self.config = config;
+            self.rebuild_spread();
+        }
+    }
+
+    /// Returns `None` if both legs are already taken
+    pub fn add_ticker(&mut self, ticker_info: &TickerInfo) -> Option<Vec<StreamKind>> {
+        if self.is_full() && !self.selected_tickers().contains(ticker_info) {
+            return None;
+        }
+
+        let streams = self.legs.add_ticker(ticker_info);
+        self.rebuild_spread();
+        Some(streams)
+    }
+
+    pub fn remove_ticker(&mut self, ticker_info: &TickerInfo) -> Vec<StreamKind> {
+        let streams = self.legs.remove_ticker(ticker_info);
+        self.rebuild_spread();
+        streams
+    }
+
+    pub fn serializable_config(&self) -> Config {
+        self.config
+    }
+
+    fn leg_points(&self, ticker_info: &TickerInfo) -> &[(u64, f32)] {
+        self.legs
+            .series
+            .iter()
+            .find(|s| s.ticker_info == *ticker_info)
+            .map(|s| s.points.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn rebuild_spread(&mut self) {
+        self.rev = self.rev.wrapping_add(1);
+
+        let &[a, b, ..] = self.selected_tickers() else {
+            self.spread = None;
+            return;
+        };
+
+        self.spread = Some(SpreadSeries::build(
+            [a, b],
+            self.config.mode,
+            &self.legs.aligned_points(self.leg_points(&a)),
+            &self.legs.aligned_points(self.leg_points(&b)),
+            default_color_for(&a),
+        ));
+    }
+
+    /// Cheap path for live updates, only touches the newest point once both legs reached it
+    fn refresh_tail(&mut self) {
+        let Some([a, b]) = self.spread.as_ref().map(|s| s.legs) else {
+            return self.rebuild_spread();
+        };
+
+        let (Some(&(xa, ya)), Some(&(xb, yb))) =
+            (self.leg_points(&a).last(), self.leg_points(&b).last())
+        else {
+            return;
+        };
+        if xa != xb {
+            return;
+        }
+        let Some(value) = self.config.mode.apply(ya, yb) else {
+            return;
+        };
+
+        if let Some(spread) = &mut self.spread {
+            match spread.points.last_mut() {
+                Some((x, y)) if *x == xa => *y = value,
+                Some((x, _)) if *x > xa => {}
+                _ => spread.points.push((xa, value)),
+            }
+
+            if spread.points.len() > SERIES_MAX_POINTS {
+                let drop = spread.points.len() - SERIES_MAX_POINTS;
+                spread.points.drain(0..drop);
+            }
+        }
+    }
+}
@@ -1,4 +1,7 @@
+pub mod broadcast;
 pub mod fetcher;
+pub mod ingest;
+mod loopback;
 pub mod stream;
 
 pub use stream::ResolvedStream;
@@ -0,0 +1,267 @@
+use super::loopback::{self, POLL_INTERVAL};
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use std::io::{self, Read, Write};
+
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Messages queued for the writer thread before new ones are dropped
+const OUTBOX_CAPACITY: usize = 1024;
+/// Messages queued for one client before it's disconnected as too slow
+const CLIENT_QUEUE_CAPACITY: usize = 256;
+/// A client that can't take a message within this long is disconnected
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_HANDSHAKE_LEN: usize = 8 * 1024;
+
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Frames waiting for one client's writer thread
+type ClientQueue = SyncSender<Arc<[u8]>>;
+
+/// Websocket server on `127.0.0.1:port` pushing market events to every connected client.
+///
+/// Only server-to-client text messages are sent, anything the clients send is ignored.
+/// Sockets are served on plain threads and publishing never blocks, if the writer falls
+/// behind messages are dropped instead of stalling the UI. Every client has its own queue
+/// and thread, one that can't keep up is dropped instead of holding up the others.
+pub struct Server {
+    port: u16,
+    outbox: SyncSender<Vec<u8>>,
+    clients: Arc<AtomicUsize>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Server {
+    pub fn start(port: u16, allowed_origins: Vec<String>) -> io::Result<Self> {
+        let listener = loopback::bind(port)?;
+
+        let (outbox, inbox) = mpsc::sync_channel(OUTBOX_CAPACITY);
+        let queues = Arc::new(Mutex::new(Vec::new()));
+        let clients = Arc::new(AtomicUsize::new(0));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        {
+            let queues = Arc::clone(&queues);
+            let clients = Arc::clone(&clients);
+            let shutdown = Arc::clone(&shutdown);
+            let allowed_origins = Arc::new(allowed_origins);
+            std::thread::spawn(move || {
+                accept_loop(listener, queues, clients, shutdown, allowed_origins);
+            });
+        }
+        {
+            let clients = Arc::clone(&clients);
+            std::thread::spawn(move || write_loop(inbox, queues, clients));
+        }
+
+        log::info!("Re-broadcasting market data on ws://127.0.0.1:{port}");
+
+        Ok(Self {
+            port,
+            outbox,
+            clients,
+            shutdown,
+        })
+    }
+
+    pub fn publish(&self, event: &exchange::Event) {
+        // Skip serializing entirely while nobody is listening
+        if self.clients.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        let Some(json) = data::broadcast::encode(event) else {
+            return;
+        };
+
+        match self.outbox.try_send(text_frame(json.as_bytes())) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+            Err(TrySendError::Full(_)) => {
+                log::debug!("Market data broadcast is falling behind, dropping a message");
+            }
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        log::info!("Stopped re-broadcasting market data on port {}", self.port);
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    queues: Arc<Mutex<Vec<ClientQueue>>>,
+    clients: Arc<AtomicUsize>,
+    shutdown: Arc<AtomicBool>,
+    allowed_origins: Arc<Vec<String>>,
+) {
+    let is_stopped = || shutdown.load(Ordering::Relaxed);
+
+    loopback::accept_loop(
+        &listener,
+        "Market data broadcast",
+        is_stopped,
+        |socket, peer| {
+            let queues = Arc::clone(&queues);
+            let clients = Arc::clone(&clients);
+            let allowed_origins = Arc::clone(&allowed_origins);
+
+            std::thread::spawn(move || match handshake(&socket, &allowed_origins) {
+                Ok(()) => {
+                    log::info!("Market data client connected from {peer}");
+                    let (queue, frames) = mpsc::sync_channel(CLIENT_QUEUE_CAPACITY);
+                    if let Ok(mut queues) = queues.lock() {
+                        queues.push(queue);
+                        clients.store(queues.len(), Ordering::Relaxed);
+                    }
+                    client_loop(socket, &frames);
+                }
+                Err(err) => log::warn!("Market data client {peer} rejected: {err}"),
+            });
+        },
+    );
+}
+
+/// Hands every message to the client queues. Exits once the [`Server`] is dropped, which
+/// closes every client
+fn write_loop(
+    inbox: mpsc::Receiver<Vec<u8>>,
+    queues: Arc<Mutex<Vec<ClientQueue>>>,
+    clients: Arc<AtomicUsize>,
+) {
+    loop {
+        match inbox.recv_timeout(POLL_INTERVAL) {
+            Ok(frame) => {
+                let frame: Arc<[u8]> = frame.into();
+                let Ok(mut queues) = queues.lock() else {
+                    return;
+                };
+                queues.retain(|queue| match queue.try_send(Arc::clone(&frame)) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => {
+                        log::warn!("Market data client is too slow, disconnecting it");
+                        false
+                    }
+                    Err(TrySendError::Disconnected(_)) => false,
+                });
+                clients.store(queues.len(), Ordering::Relaxed);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    if let Ok(mut queues) = queues.lock() {
+        queues.clear();
+    }
+    clients.store(0, Ordering::Relaxed);
+}
+
+/// Writes queued messages to one client until it fails or its queue is dropped
+fn client_loop(mut socket: TcpStream, frames: &mpsc::Receiver<Arc<[u8]>>) {
+    while let Ok(frame) = frames.recv() {
+        if socket.write_all(&frame).is_err() {
+            return;
+        }
+    }
+
+    // Close frame without a status code
+    let _ = socket.write_all(&[0x88, 0x00]);
+}
+
+/// Reads the HTTP upgrade request and answers it, leaving the socket ready for frames.
+///
+/// Browsers let any page open a websocket to localhost, so requests carrying an `Origin`
+/// are turned away unless it was allowed in the config
+fn handshake(mut socket: &TcpStream, allowed_origins: &[String]) -> io::Result<()> {
+    socket.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    socket.set_write_timeout(Some(CLIENT_WRITE_TIMEOUT))?;
+
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.ends_with(b"\r\n\r\n") {
+        let read = socket.read(&mut buf)?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "closed during handshake",
+            ));
+        }
+        request.extend_from_slice(&buf[..read]);
+        if request.len() > MAX_HANDSHAKE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "handshake too large",
+            ));
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let header = |header: &str| {
+        request
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(header))
+            .map(|(_, value)| value.trim())
+    };
+
+    if let Some(origin) = header("origin").filter(|origin| !origin.is_empty())
+        && !allowed_origins
+            .iter()
+            .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    {
+        socket.write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")?;
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("origin {origin} is not allowed"),
+        ));
+    }
+
+    let key = header("sec-websocket-key").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a websocket upgrade request",
+        )
+    })?;
+
+    let accept = base64::engine::general_purpose::STANDARD
+        .encode(Sha1::digest(format!("{key}{WEBSOCKET_GUID}").as_bytes()));
+
+    socket.write_all(
+        format!(
+            "HTTP/1.1 101 Switching Protocols\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {accept}\r\n\r\n"
+        )
+        .as_bytes(),
+    )
+}
+
+/// Unmasked, unfragmented text frame, as sent from server to client
+fn text_frame(payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x81);
+
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+
+    frame.extend_from_slice(payload);
+    frame
+}
@@ -1,5 +1,7 @@
-use exchange::adapter::{AdapterError, AdapterHandles, Exchange, StreamKind};
-use exchange::{Kline, OpenInterest, TickerInfo, Trade, UnixMs};
+use data::history_cache::{CachedSeries, SeriesKey, SeriesKind};
+use data::tickstore::TickStore;
+use exchange::adapter::{AdapterError, AdapterHandles, Exchange, StreamKind, Venue};
+use exchange::{FundingRate, Kline, OpenInterest, TickerInfo, Trade, UnixMs};
 use iced::{
     Task,
     task::{Handle, Straw, sipper},
@@ -7,8 +9,16 @@ use iced::{
 use rustc_hash::FxHashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+/// Stored trades are sent in batches of this size, like the ones fetched over the network
+const STORED_TRADES_CHUNK: usize = 10_000;
+
+/// A pending request not answered within this is sent again, doubling the wait each attempt
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_REQUEST_ATTEMPTS: u32 = 3;
+
 static TRADE_FETCH_ENABLED: AtomicBool = AtomicBool::new(false);
 
 pub fn toggle_trade_fetch(value: bool) {
@@ -19,6 +29,16 @@ pub fn is_trade_fetch_enabled() -> bool {
     TRADE_FETCH_ENABLED.load(Ordering::Relaxed)
 }
 
+static HISTORY_CACHE_ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn toggle_history_cache(value: bool) {
+    HISTORY_CACHE_ENABLED.store(value, Ordering::Relaxed);
+}
+
+pub fn is_history_cache_enabled() -> bool {
+    HISTORY_CACHE_ENABLED.load(Ordering::Relaxed)
+}
+
 #[derive(Debug, Clone)]
 pub enum FetchedData {
     Trades {
@@ -33,6 +53,10 @@ pub enum FetchedData {
         data: Vec<OpenInterest>,
         req_id: Option<uuid::Uuid>,
     },
+    Funding {
+        data: Vec<FundingRate>,
+        req_id: Option<uuid::Uuid>,
+    },
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -78,7 +102,30 @@ impl RequestHandler {
                         Ok(None)
                     }
                 }
-                RequestStatus::Pending => Err(ReqError::Overlaps),
+                RequestStatus::Pending => {
+                    if !existing_req.is_overdue(Instant::now()) {
+                        return Err(ReqError::Overlaps);
+                    }
+
+                    // The task may have died without reporting back, e.g. a dropped connection
+                    let attempts = existing_req.attempts;
+                    if let Some(request) = self.requests.get_mut(&existing_id) {
+                        if attempts >= MAX_REQUEST_ATTEMPTS {
+                            let error = format!("Timed out after {attempts} attempts");
+                            request.status = RequestStatus::Failed(error.clone());
+                            return Err(ReqError::Failed(error));
+                        }
+
+                        request.attempts += 1;
+                        request.sent_at = Instant::now();
+                        log::warn!(
+                            "Retrying {:?}, attempt {}",
+                            request.fetch_type,
+                            request.attempts
+                        );
+                    }
+                    Ok(Some(existing_id))
+                }
             };
         }
 
@@ -108,6 +155,7 @@ impl RequestHandler {
 pub enum FetchRange {
     Kline(UnixMs, UnixMs),
     OpenInterest(UnixMs, UnixMs),
+    FundingRate(UnixMs, UnixMs),
     Trades(UnixMs, UnixMs),
 }
 
@@ -115,6 +163,8 @@ pub enum FetchRange {
 struct FetchRequest {
     fetch_type: FetchRange,
     status: RequestStatus,
+    sent_at: Instant,
+    attempts: u32,
 }
 
 impl FetchRequest {
@@ -122,15 +172,25 @@ impl FetchRequest {
         FetchRequest {
             fetch_type,
             status: RequestStatus::Pending,
+            sent_at: Instant::now(),
+            attempts: 1,
         }
     }
 
+    fn is_overdue(&self, now: Instant) -> bool {
+        let timeout = REQUEST_TIMEOUT * 2u32.pow(self.attempts.saturating_sub(1));
+        now.duration_since(self.sent_at) >= timeout
+    }
+
     fn same_with(&self, other: &FetchRequest) -> bool {
         match (&self.fetch_type, &other.fetch_type) {
             (FetchRange::Kline(s1, e1), FetchRange::Kline(s2, e2)) => e1 == e2 && s1 == s2,
             (FetchRange::OpenInterest(s1, e1), FetchRange::OpenInterest(s2, e2)) => {
                 e1 == e2 && s1 == s2
             }
+            (FetchRange::FundingRate(s1, e1), FetchRange::FundingRate(s2, e2)) => {
+                e1 == e2 && s1 == s2
+            }
             _ => false,
         }
     }
@@ -177,6 +237,7 @@ pub enum InfoKind {
     FetchingKlines,
     FetchingTrades(usize),
     FetchingOI,
+    FetchingFunding,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -194,7 +255,8 @@ pub enum FetchUpdate {
     Data {
         layout_id: Uuid,
         pane_id: Uuid,
-        stream: StreamKind,
+        /// Boxed, composite depth streams make it much larger than the other variants
+        stream: Box<StreamKind>,
         data: FetchedData,
     },
     Error {
@@ -262,6 +324,25 @@ pub fn request_fetch(
                 );
             }
         }
+        FetchRange::FundingRate(from, to) => {
+            let kline_stream = stream.or_else(|| {
+                ready_streams
+                    .iter()
+                    .find(|stream| matches!(stream, StreamKind::Kline { .. }))
+                    .copied()
+            });
+
+            if let Some(stream) = kline_stream {
+                return funding_fetch_task(
+                    handles.clone(),
+                    layout_id,
+                    pane_id,
+                    stream,
+                    Some(req_id),
+                    Some((from, to)),
+                );
+            }
+        }
         FetchRange::Trades(from_time, to_time) => {
             let trade_info = ready_streams.iter().find_map(|stream| {
                 if let StreamKind::Trades { ticker_info } = stream {
@@ -272,12 +353,12 @@ pub fn request_fetch(
             });
 
             if let Some((ticker_info, pane_id, stream)) = trade_info {
-                let is_binance = matches!(
+                let has_trade_history = matches!(
                     ticker_info.exchange(),
                     Exchange::BinanceSpot | Exchange::BinanceLinear | Exchange::BinanceInverse
-                );
+                ) || ticker_info.exchange().venue() == Venue::Plugin;
 
-                if is_binance {
+                if has_trade_history {
                     let data_path = data::data_path(Some("market_data/binance/"));
 
                     let (task, handle) = Task::sip(
@@ -298,7 +379,7 @@ pub fn request_fetch(
                                 layout_id,
                                 pane_id,
                                 data,
-                                stream,
+                                stream: Box::new(stream),
                             }
                         },
                         move |result| match result {
@@ -372,11 +453,19 @@ pub fn oi_fetch_task(
             ticker_info,
             timeframe,
         } => {
-            let fetch = async move {
-                handles
-                    .fetch_open_interest(ticker_info, timeframe, range)
-                    .await
+            let key = SeriesKey {
+                ticker_info,
+                kind: SeriesKind::OpenInterest(timeframe),
             };
+            let fetch = fetch_cached(key, range, move |range| {
+                let handles = handles.clone();
+                async move {
+                    let oi = handles
+                        .fetch_open_interest(ticker_info, timeframe, range)
+                        .await?;
+                    Ok(oi.into_iter().map(|oi| (oi.time, oi.value)).collect())
+                }
+            });
 
             Task::perform(
                 iced::futures::TryFutureExt::map_err(fetch, |err| {
@@ -384,13 +473,79 @@ pub fn oi_fetch_task(
                     err.ui_message()
                 }),
                 move |result| match result {
-                    Ok(oi) => {
+                    Ok(points) => {
+                        let oi = points
+                            .into_iter()
+                            .map(|(time, value)| OpenInterest { time, value })
+                            .collect();
                         let data = FetchedData::OI { data: oi, req_id };
                         FetchUpdate::Data {
                             layout_id,
                             pane_id,
                             data,
-                            stream,
+                            stream: Box::new(stream),
+                        }
+                    }
+                    Err(err) => FetchUpdate::Error {
+                        pane_id,
+                        error: err,
+                    },
+                },
+            )
+        }
+        _ => Task::none(),
+    };
+
+    update_status.chain(fetch_task)
+}
+
+pub fn funding_fetch_task(
+    handles: AdapterHandles,
+    layout_id: Uuid,
+    pane_id: Uuid,
+    stream: StreamKind,
+    req_id: Option<Uuid>,
+    range: Option<(UnixMs, UnixMs)>,
+) -> Task<FetchUpdate> {
+    let update_status = Task::done(FetchUpdate::Status {
+        pane_id,
+        status: FetchTaskStatus::Loading(InfoKind::FetchingFunding),
+    });
+
+    let fetch_task = match stream {
+        StreamKind::Kline { ticker_info, .. } => {
+            let key = SeriesKey {
+                ticker_info,
+                kind: SeriesKind::FundingRate,
+            };
+            let fetch = fetch_cached(key, range, move |range| {
+                let handles = handles.clone();
+                async move {
+                    let funding = handles.fetch_funding_rate(ticker_info, range).await?;
+                    Ok(funding.into_iter().map(|f| (f.time, f.rate)).collect())
+                }
+            });
+
+            Task::perform(
+                iced::futures::TryFutureExt::map_err(fetch, |err| {
+                    log::error!("Funding rate fetch failed: {err}");
+                    err.ui_message()
+                }),
+                move |result| match result {
+                    Ok(points) => {
+                        let funding = points
+                            .into_iter()
+                            .map(|(time, rate)| FundingRate { time, rate })
+                            .collect();
+                        let data = FetchedData::Funding {
+                            data: funding,
+                            req_id,
+                        };
+                        FetchUpdate::Data {
+                            layout_id,
+                            pane_id,
+                            data,
+                            stream: Box::new(stream),
                         }
                     }
                     Err(err) => FetchUpdate::Error {
@@ -406,6 +561,39 @@ pub fn oi_fetch_task(
     update_status.chain(fetch_task)
 }
 
+/// Serves `range` from the on-disk history cache, fetching only the spans it's missing.
+/// Without a range, or with the cache turned off, this is a plain fetch
+async fn fetch_cached<F, Fut>(
+    key: SeriesKey,
+    range: Option<(UnixMs, UnixMs)>,
+    fetch: F,
+) -> Result<Vec<(UnixMs, f64)>, AdapterError>
+where
+    F: Fn(Option<(UnixMs, UnixMs)>) -> Fut,
+    Fut: Future<Output = Result<Vec<(UnixMs, f64)>, AdapterError>>,
+{
+    let Some((from, to)) = range.filter(|_| is_history_cache_enabled()) else {
+        return fetch(range).await;
+    };
+
+    let mut cache = CachedSeries::load(&key);
+    let gaps = cache.missing(from, to);
+
+    if gaps.is_empty() {
+        return Ok(cache.points(from, to));
+    }
+
+    for gap in gaps {
+        cache.insert(fetch(Some(gap)).await?);
+    }
+
+    if let Err(err) = cache.save(&key) {
+        log::warn!("Failed to write history cache: {err}");
+    }
+
+    Ok(cache.points(from, to))
+}
+
 pub fn kline_fetch_task(
     handles: AdapterHandles,
     layout_id: Uuid,
@@ -441,7 +629,7 @@ pub fn kline_fetch_task(
                             layout_id,
                             pane_id,
                             data,
-                            stream,
+                            stream: Box::new(stream),
                         }
                     }
                     Err(err) => FetchUpdate::Error {
@@ -465,8 +653,23 @@ pub fn fetch_trades_batched(
     data_path: PathBuf,
 ) -> impl Straw<(), Vec<Trade>, AdapterError> {
     sipper(async move |mut progress| {
+        let store = TickStore::default();
         let mut latest_trade_t = from_time;
 
+        if let Some(covered_until) = store.covered_until(&ticker_info, from_time) {
+            let stored_until = covered_until.min(to_time);
+
+            match store.read(&ticker_info, from_time, stored_until) {
+                Ok(trades) => {
+                    for chunk in trades.chunks(STORED_TRADES_CHUNK) {
+                        let () = progress.send(chunk.to_vec()).await;
+                    }
+                    latest_trade_t = stored_until.saturating_add(1);
+                }
+                Err(err) => log::warn!("Failed to read stored trades, refetching: {err}"),
+            }
+        }
+
         while latest_trade_t < to_time {
             match handles
                 .fetch_trades(ticker_info, latest_trade_t, Some(data_path.clone()))
@@ -477,7 +680,22 @@ pub fn fetch_trades_batched(
                         break;
                     }
 
-                    latest_trade_t = batch.last().map_or(latest_trade_t, |trade| trade.time);
+                    let batch_end = batch.last().map_or(latest_trade_t, |trade| trade.time);
+
+                    // Trades sharing the last timestamp may continue in the next batch,
+                    // so that timestamp is only marked as stored once fetched again
+                    if batch_end > latest_trade_t
+                        && let Err(err) = store.insert(
+                            &ticker_info,
+                            latest_trade_t,
+                            UnixMs::new(batch_end.as_u64() - 1),
+                            &batch,
+                        )
+                    {
+                        log::warn!("Failed to store fetched trades: {err}");
+                    }
+
+                    latest_trade_t = batch_end;
 
                     let () = progress.send(batch).await;
                 }
@@ -0,0 +1,107 @@
+use super::loopback;
+
+use data::external::ExternalEvent;
+use exchange::UnixMs;
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream, StreamExt};
+use std::io::{BufRead, BufReader};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Received(ExternalEvent),
+    /// A line that couldn't be parsed, the connection stays open
+    Rejected(String),
+    /// The listener couldn't be started
+    Failed(String),
+}
+
+/// Accepts JSON-lines connections on `127.0.0.1:port` until the stream is dropped. Each
+/// one has to start with a line holding `token`, or it's closed.
+///
+/// Sockets are served on plain threads, so this doesn't depend on the runtime
+/// the subscription happens to run on.
+pub fn listen((port, token): &(u16, String)) -> impl Stream<Item = Event> + use<> {
+    let (port, token) = (*port, Arc::<str>::from(token.as_str()));
+
+    iced::stream::channel(64, async move |mut output| {
+        let (sender, mut receiver) = mpsc::unbounded();
+        std::thread::spawn(move || accept_loop(port, token, sender));
+
+        while let Some(event) = receiver.next().await {
+            if output.send(event).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+fn accept_loop(port: u16, token: Arc<str>, sender: mpsc::UnboundedSender<Event>) {
+    let listener = match loopback::bind(port) {
+        Ok(listener) => listener,
+        Err(err) => {
+            let _ = sender.unbounded_send(Event::Failed(format!(
+                "Failed to listen for external events on port {port}: {err}"
+            )));
+            return;
+        }
+    };
+
+    log::info!("Listening for external events on 127.0.0.1:{port}");
+
+    let is_stopped = || sender.is_closed();
+
+    loopback::accept_loop(
+        &listener,
+        "External event listener",
+        is_stopped,
+        |socket, peer| {
+            let (token, sender) = (Arc::clone(&token), sender.clone());
+            std::thread::spawn(move || read_events(socket, peer, &token, sender));
+        },
+    );
+
+    log::info!("Stopped listening for external events on port {port}");
+}
+
+fn read_events(
+    socket: TcpStream,
+    peer: SocketAddr,
+    token: &str,
+    sender: mpsc::UnboundedSender<Event>,
+) {
+    let mut lines = BufReader::new(socket).lines();
+
+    let authorized = lines
+        .next()
+        .and_then(Result::ok)
+        .is_some_and(|line| !token.is_empty() && line.trim() == token);
+    if !authorized {
+        let _ = sender.unbounded_send(Event::Rejected(format!(
+            "External event source {peer} rejected: missing or wrong token"
+        )));
+        return;
+    }
+    log::info!("External event source connected from {peer}");
+
+    for line in lines {
+        let Ok(line) = line else {
+            break;
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let event = match ExternalEvent::parse(line, UnixMs::now()) {
+            Ok(event) => Event::Received(event),
+            Err(err) => Event::Rejected(format!("Invalid external event: {err}")),
+        };
+
+        if sender.unbounded_send(event).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Plain-thread TCP servers bound to the loopback interface only
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+/// How often the accept loops check whether they should stop
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Listener on `127.0.0.1:port`, non-blocking so [`accept_loop`] can stop
+pub fn bind(port: u16) -> io::Result<TcpListener> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+/// Hands every connection to `on_accept` in blocking mode, until `is_stopped` says so
+pub fn accept_loop(
+    listener: &TcpListener,
+    name: &str,
+    is_stopped: impl Fn() -> bool,
+    mut on_accept: impl FnMut(TcpStream, SocketAddr),
+) {
+    while !is_stopped() {
+        match listener.accept() {
+            // Accepted sockets inherit non-blocking mode on some platforms
+            Ok((socket, peer)) => match socket.set_nonblocking(false) {
+                Ok(()) => on_accept(socket, peer),
+                Err(err) => log::warn!("{name}: {peer}: {err}"),
+            },
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            Err(err) => {
+                log::warn!("{name}: {err}");
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
@@ -1,11 +1,27 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
+use data::config::session::SessionProfile;
 use data::stream::PersistStreamKind;
 use exchange::adapter::StreamKind;
 
 /// Persisted stream resolution to avoid loop retries
 const RESOLVE_RETRY_INTERVAL: Duration = Duration::from_secs(2);
 
+static REMOTE_SESSION: AtomicBool = AtomicBool::new(false);
+
+pub fn set_session_profile(profile: SessionProfile) {
+    REMOTE_SESSION.store(profile.is_remote(), Ordering::Relaxed);
+}
+
+pub fn session_profile() -> SessionProfile {
+    if REMOTE_SESSION.load(Ordering::Relaxed) {
+        SessionProfile::Remote
+    } else {
+        SessionProfile::Full
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResolvedStream {
     /// Streams that are persisted but needs to be resolved for use
@@ -53,7 +69,9 @@ impl ResolvedStream {
 
     pub fn matches_stream(&self, stream: &StreamKind) -> bool {
         match self {
-            ResolvedStream::Ready(existing) => existing.iter().any(|s| s == stream),
+            ResolvedStream::Ready(existing) => existing
+                .iter()
+                .any(|s| s == stream || s.is_composite_member(stream)),
             _ => false,
         }
     }
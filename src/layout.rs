@@ -2,7 +2,10 @@ use crate::modal::layout_manager::LayoutManager;
 use crate::screen::dashboard::{Dashboard, pane};
 use data::{
     UserTimezone,
-    layout::{WindowSpec, pane::Axis},
+    layout::{
+        WindowSpec,
+        pane::{Axis, LinkGroup},
+    },
 };
 
 use iced::widget::pane_grid::{self, Configuration};
@@ -31,6 +34,16 @@ pub struct SavedState {
     pub audio_cfg: data::AudioStream,
     pub volume_size_unit: exchange::SizeUnit,
     pub proxy_cfg: Option<exchange::proxy::Proxy>,
+    pub external_events: data::external::ListenerConfig,
+    pub market_broadcast: data::broadcast::BroadcastConfig,
+    pub accents: data::config::accent::Accents,
+    pub state_backup: data::config::backup::BackupConfig,
+    pub trade_retention: data::tickstore::RetentionPolicy,
+    pub maintenance: data::maintenance::MaintenanceConfig,
+    pub snapshot: data::snapshot::SnapshotConfig,
+    pub alerts: data::alerts::Alerts,
+    pub trading: data::trading::TradingConfig,
+    pub journal: data::trading::journal::JournalConfig,
 }
 
 impl SavedState {
@@ -60,6 +73,16 @@ impl Default for SavedState {
             audio_cfg: data::AudioStream::default(),
             volume_size_unit: exchange::SizeUnit::Base,
             proxy_cfg: None,
+            external_events: data::external::ListenerConfig::default(),
+            market_broadcast: data::broadcast::BroadcastConfig::default(),
+            accents: data::config::accent::Accents::default(),
+            state_backup: data::config::backup::BackupConfig::default(),
+            trade_retention: data::tickstore::RetentionPolicy::default(),
+            maintenance: data::maintenance::MaintenanceConfig::default(),
+            snapshot: data::snapshot::SnapshotConfig::default(),
+            alerts: data::alerts::Alerts::default(),
+            trading: data::trading::TradingConfig::default(),
+            journal: data::trading::journal::JournalConfig::default(),
         }
     }
 }
@@ -103,6 +126,15 @@ impl From<&Dashboard> for data::Dashboard {
                     .map(|(pane, window_spec)| (pane.clone(), *window_spec))
                     .collect()
             },
+            link_options: LinkGroup::ALL
+                .iter()
+                .filter_map(|group| {
+                    dashboard
+                        .link_options
+                        .get(group)
+                        .map(|options| (*group, *options))
+                })
+                .collect(),
         }
     }
 }
@@ -180,6 +212,20 @@ impl From<&pane::State> for data::Pane {
                     link_group: pane.link_group,
                 }
             }
+            pane::Content::Spread(chart) => {
+                let settings = data::layout::pane::Settings {
+                    visual_config: chart
+                        .as_ref()
+                        .map(|c| data::layout::pane::VisualConfig::Spread(c.serializable_config())),
+                    ..pane.settings.clone()
+                };
+
+                data::Pane::SpreadChart {
+                    stream_type: streams,
+                    settings,
+                    link_group: pane.link_group,
+                }
+            }
         }
     }
 }
@@ -279,6 +325,20 @@ pub fn configuration(pane: data::Pane) -> Configuration<pane::State> {
                 link_group,
             ))
         }
+        data::Pane::SpreadChart {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::Spread(None);
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
         data::Pane::TimeAndSales {
             stream_type,
             settings,
@@ -310,26 +370,30 @@ pub fn configuration(pane: data::Pane) -> Configuration<pane::State> {
     }
 }
 
+/// The dashboard of a layout, from its saved form
+pub fn dashboard(ser_dashboard: &data::Dashboard, layout_id: Uuid) -> Dashboard {
+    let popout_windows = ser_dashboard
+        .popout
+        .iter()
+        .map(|(pane, window_spec)| (configuration(pane.clone()), *window_spec))
+        .collect();
+
+    Dashboard::from_config(
+        configuration(ser_dashboard.pane.clone()),
+        popout_windows,
+        &ser_dashboard.link_options,
+        layout_id,
+    )
+}
+
 pub fn load_saved_state() -> SavedState {
     match data::read_from_file(data::SAVED_STATE_PATH) {
         Ok(state) => {
             let mut de_layouts = vec![];
 
             for layout in &state.layout_manager.layouts {
-                let mut popout_windows = Vec::new();
-
-                for (pane, window_spec) in &layout.dashboard.popout {
-                    let configuration = configuration(pane.clone());
-                    popout_windows.push((configuration, *window_spec));
-                }
-
                 let layout_id = Uuid::new_v4();
-
-                let dashboard = Dashboard::from_config(
-                    configuration(layout.dashboard.pane.clone()),
-                    popout_windows,
-                    layout_id,
-                );
+                let dashboard = dashboard(&layout.dashboard, layout_id);
 
                 de_layouts.push((layout.name.clone(), layout_id, dashboard));
             }
@@ -368,6 +432,8 @@ pub fn load_saved_state() -> SavedState {
             };
 
             crate::connector::fetcher::toggle_trade_fetch(state.trade_fetch_enabled);
+            crate::connector::fetcher::toggle_history_cache(state.history_cache.enabled);
+            crate::connector::stream::set_session_profile(state.session_profile);
             exchange::unit::qty::set_preferred_currency(state.size_in_quote_ccy);
 
             // Hydrate proxy auth from keychain (keeps auth out of persisted JSON)
@@ -390,6 +456,16 @@ pub fn load_saved_state() -> SavedState {
                 audio_cfg: state.audio_cfg,
                 volume_size_unit: state.size_in_quote_ccy,
                 proxy_cfg,
+                external_events: state.external_events,
+                market_broadcast: state.market_broadcast,
+                accents: state.accents,
+                state_backup: state.state_backup,
+                trade_retention: state.trade_retention,
+                maintenance: state.maintenance,
+                snapshot: state.snapshot,
+                alerts: state.alerts,
+                trading: state.trading,
+                journal: state.journal,
             }
         }
         Err(e) => {
@@ -15,11 +15,17 @@ mod window;
 
 use data::config::theme::default_theme;
 use data::{layout::WindowSpec, sidebar};
-use layout::{LayoutId, configuration};
+use layout::LayoutId;
 use modal::{
     LayoutManager, ThemeEditor,
+    account::{self, AccountPanel},
+    alerts::{self, AlertsManager},
     audio::AudioStream,
+    connections::{self, Connections},
+    journal::{self, JournalManager},
+    layout_templates::{self, LayoutTemplatesManager},
     network_manager::{self, NetworkManager},
+    trading::{self, TradingManager},
 };
 use modal::{dashboard_modal, main_dialog_modal};
 use notify::Notifications;
@@ -33,11 +39,20 @@ use widget::{
 use iced::{
     Alignment, Element, Subscription, Task, keyboard, padding,
     widget::{
-        button, column, container, pane_grid, pick_list, row, rule, scrollable, text,
+        button, column, container, pane_grid, pick_list, row, rule, scrollable, text, text_input,
         tooltip::Position as TooltipPosition,
     },
 };
-use std::{borrow::Cow, collections::HashMap, vec};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+    vec,
+};
+
+/// How often to check whether the daily state backup is due
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 fn main() {
     logger::install_panic_hook();
@@ -46,8 +61,6 @@ fn main() {
         logger::report_stderr(&format!("Failed to initialize logger: {err}"));
     }
 
-    std::thread::spawn(data::cleanup_old_market_data);
-
     let daemon = iced::daemon(Flowsurface::new, Flowsurface::update, Flowsurface::view)
         .settings(iced::Settings {
             antialiasing: true,
@@ -75,12 +88,32 @@ struct Flowsurface {
     sidebar: dashboard::Sidebar,
     handles: exchange::adapter::AdapterHandles,
     layout_manager: LayoutManager,
+    layout_templates: LayoutTemplatesManager,
     theme_editor: ThemeEditor,
     network: NetworkManager,
     audio_stream: AudioStream,
+    connections: Connections,
+    alerts: AlertsManager,
+    trading: TradingManager,
+    account: AccountPanel,
+    journal: JournalManager,
     confirm_dialog: Option<screen::ConfirmDialog<Message>>,
     volume_size_unit: exchange::SizeUnit,
+    external_events: data::external::ListenerConfig,
+    market_broadcast: data::broadcast::BroadcastConfig,
+    broadcast_server: Option<connector::broadcast::Server>,
+    accents: data::config::accent::Accents,
+    trade_retention: data::tickstore::RetentionPolicy,
+    maintenance: data::maintenance::MaintenanceConfig,
+    idle_scheduler: data::maintenance::IdleScheduler,
+    snapshot: data::snapshot::SnapshotConfig,
+    state_backup: data::config::backup::BackupConfig,
+    /// Found in the backup directory, newest first
+    backups: Vec<data::config::backup::Backup>,
+    last_backup_check: Instant,
     ui_scale_factor: data::ScaleFactor,
+    /// OS reported scale factor per open window
+    window_scales: HashMap<window::Id, f32>,
     timezone: data::UserTimezone,
     theme: data::Theme,
     notifications: Notifications,
@@ -107,13 +140,36 @@ enum Message {
     ScaleFactorChanged(data::ScaleFactor),
     SetTimezone(data::UserTimezone),
     ToggleTradeFetch(bool),
+    SetTradeRetention(data::tickstore::RetentionPolicy),
+    SetMaintenance(data::maintenance::MaintenanceConfig),
+    MaintenanceFinished(Option<data::maintenance::Report>),
+    ToggleHistoryCache(bool),
+    ClearHistoryCache,
+    SetSnapshotScale(data::snapshot::SnapshotConfig),
+    SnapshotCaptured(Option<window::Screenshot>, String),
+    SetSessionProfile(data::config::session::SessionProfile),
+    ToggleExternalEvents(bool),
+    CopyExternalEventsToken,
+    ExternalEvent(connector::ingest::Event),
+    ToggleMarketBroadcast(bool),
+    ToggleStateBackup(bool),
+    SetBackupDir(String),
+    RestoreBackup(data::config::backup::Backup),
     ApplyVolumeSizeUnit(exchange::SizeUnit),
     RemoveNotification(usize),
     ToggleDialogModal(Option<screen::ConfirmDialog<Message>>),
     ThemeEditor(modal::theme_editor::Message),
     NetworkManager(modal::network_manager::Message),
     Layouts(modal::layout_manager::Message),
+    LayoutTemplates(layout_templates::Message),
+    ApplyLayoutTemplate(Box<data::Dashboard>),
     AudioStream(modal::audio::Message),
+    Connections(connections::Message),
+    Alerts(alerts::Message),
+    AlertDelivered(data::alerts::delivery::Channel, Result<(), String>),
+    Trading(trading::Message),
+    Account(account::Message),
+    Journal(journal::Message),
 }
 
 impl Flowsurface {
@@ -131,6 +187,10 @@ impl Flowsurface {
             window::open(config)
         };
 
+        exchange::source::register(Arc::new(exchange::source::local::LocalFiles::new(
+            data::data_path(Some("local-data")),
+        )));
+
         let handles = exchange::adapter::AdapterHandles::spawn_venues(
             exchange::adapter::Venue::ALL,
             saved_state.proxy_cfg.as_ref(),
@@ -143,19 +203,46 @@ impl Flowsurface {
         let mut state = Self {
             main_window: window::Window::new(main_window_id),
             layout_manager: saved_state.layout_manager,
+            layout_templates: LayoutTemplatesManager::new(),
             theme_editor: ThemeEditor::new(saved_state.custom_theme),
             audio_stream,
+            connections: Connections::new(),
+            alerts: AlertsManager::new(saved_state.alerts),
+            trading: TradingManager::new(saved_state.trading),
+            account: AccountPanel::default(),
+            journal: JournalManager::new(saved_state.journal),
             sidebar,
             handles,
             confirm_dialog: None,
             timezone: saved_state.timezone,
             ui_scale_factor: saved_state.scale_factor,
+            window_scales: HashMap::new(),
             volume_size_unit: saved_state.volume_size_unit,
+            external_events: saved_state.external_events,
+            market_broadcast: saved_state.market_broadcast,
+            broadcast_server: None,
+            accents: saved_state.accents,
+            trade_retention: saved_state.trade_retention,
+            maintenance: saved_state.maintenance,
+            idle_scheduler: data::maintenance::IdleScheduler::new(Instant::now()),
+            snapshot: saved_state.snapshot,
+            backups: saved_state.state_backup.list(),
+            state_backup: saved_state.state_backup,
+            last_backup_check: Instant::now(),
             theme: saved_state.theme,
             notifications: Notifications::new(),
             network: NetworkManager::new(saved_state.proxy_cfg),
         };
 
+        if state.market_broadcast.enabled {
+            state.start_market_broadcast();
+        }
+
+        state.run_state_backup();
+
+        let retention = state.trade_retention;
+        std::thread::spawn(move || data::cleanup_old_market_data(retention));
+
         if let Some(err) = audio_init_err {
             state
                 .notifications
@@ -198,14 +285,45 @@ impl Flowsurface {
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::MarketWsEvent(event) => {
+                if let Some(connections::Action::ReportError(err)) = self.connections.record(&event)
+                {
+                    self.notifications.push(Toast::error(err));
+                }
+
+                if let Some(server) = &self.broadcast_server {
+                    server.publish(&event);
+                }
+
+                let alert_delivery = self.fire_alerts(&event);
+                self.sync_alert_levels();
+
+                if let Some(trading::Action::Notify(notice)) = self.trading.on_market_event(&event)
+                {
+                    self.notifications.push(Toast::custom(
+                        "Paper fill",
+                        notice,
+                        toast::Status::Success,
+                    ));
+                    self.log_executions();
+                }
+                self.sync_accounts();
+
                 let main_window_id = self.main_window.id;
                 let dashboard = self.active_dashboard_mut();
 
                 match event {
-                    exchange::Event::Connected(_streams) => {}
+                    exchange::Event::Connected(streams) => {
+                        dashboard.clear_reconnecting(&streams, main_window_id);
+                    }
+                    exchange::Event::Reconnecting(streams, status) => {
+                        dashboard.set_reconnecting(&streams, status, main_window_id);
+                    }
                     exchange::Event::Disconnected(_streams, reason) => {
                         log::info!("a stream disconnected from WS: {reason:?}");
                     }
+                    exchange::Event::Resumed(streams, gap_start) => {
+                        dashboard.backfill_gap(&streams, gap_start, main_window_id);
+                    }
                     exchange::Event::DepthReceived(stream, update_t, depth) => {
                         let task = dashboard
                             .ingest_depth(&stream, update_t, &depth, main_window_id)
@@ -217,40 +335,104 @@ impl Flowsurface {
                         return task;
                     }
                     exchange::Event::TradesReceived(stream, update_t, buffer) => {
-                        let task = dashboard
-                            .ingest_trades(&stream, &buffer, update_t, main_window_id)
-                            .map(move |msg| Message::Dashboard {
-                                layout_id: None,
-                                event: msg,
-                            });
+                        let (task, trade_sound) =
+                            dashboard.ingest_trades(&stream, &buffer, update_t, main_window_id);
+                        let task = task.map(move |msg| Message::Dashboard {
+                            layout_id: None,
+                            event: msg,
+                        });
 
                         if let Some(msg) = self.audio_stream.try_play_sound(&stream, &buffer) {
                             self.notifications.push(Toast::error(msg));
                         }
+                        if let Some(msg) =
+                            trade_sound.and_then(|hit| self.audio_stream.play_trade_sound(hit))
+                        {
+                            self.notifications.push(Toast::error(msg));
+                        }
 
-                        return task;
+                        return Task::batch([task, alert_delivery]);
                     }
                     exchange::Event::KlineReceived(stream, kline) => {
-                        return dashboard
+                        let task = dashboard
                             .update_latest_klines(&stream, &kline, main_window_id)
                             .map(move |msg| Message::Dashboard {
                                 layout_id: None,
                                 event: msg,
                             });
+
+                        return Task::batch([task, alert_delivery]);
                     }
+                    exchange::Event::MarkPriceReceived(stream, mark_price) => {
+                        return dashboard
+                            .update_mark_price(&stream, &mark_price, main_window_id)
+                            .map(move |msg| Message::Dashboard {
+                                layout_id: None,
+                                event: msg,
+                            });
+                    }
+                    // Only the tickers table subscribes to those
+                    exchange::Event::TickerStatsReceived(..) => {}
                 }
             }
             Message::Tick(now) => {
                 let main_window_id = self.main_window.id;
                 let handles = self.handles.clone();
 
-                return self
+                let tick = self
                     .active_dashboard_mut()
                     .tick(&handles, now, main_window_id)
                     .map(move |msg| Message::Dashboard {
                         layout_id: None,
                         event: msg,
                     });
+
+                let traded_tickers = self.active_dashboard().traded_tickers(main_window_id);
+                let refresh = self.trading.refresh_if_due(now, &traded_tickers);
+                // Tickers no longer shown were just dropped from the accounts
+                self.sync_accounts();
+                let tick = if refresh.is_empty() {
+                    tick
+                } else {
+                    Task::batch([tick, self.send_orders(refresh)])
+                };
+
+                let tick = if self.sidebar.is_menu_active(sidebar::Menu::Account)
+                    && self.account.start_refresh_if_due(now)
+                {
+                    Task::batch([tick, self.fetch_balances()])
+                } else {
+                    tick
+                };
+
+                let tick = if self.idle_scheduler.start_if_due(now, &self.maintenance) {
+                    Task::batch([tick, self.run_maintenance()])
+                } else {
+                    tick
+                };
+
+                if now.duration_since(self.last_backup_check) < BACKUP_CHECK_INTERVAL {
+                    return tick;
+                }
+                self.last_backup_check = now;
+
+                if !self.state_backup.is_due() {
+                    return tick;
+                }
+
+                // Backups copy the state file, so bring it up to date first
+                let mut active_windows = self
+                    .active_dashboard()
+                    .popout
+                    .keys()
+                    .copied()
+                    .collect::<Vec<window::Id>>();
+                active_windows.push(main_window_id);
+
+                return Task::batch([
+                    tick,
+                    window::collect_window_specs(active_windows, Message::SaveStateRequested),
+                ]);
             }
             Message::WindowEvent(event) => match event {
                 window::Event::CloseRequested(window) => {
@@ -259,6 +441,7 @@ impl Flowsurface {
 
                     if window != main_window {
                         dashboard.popout.remove(&window);
+                        self.window_scales.remove(&window);
                         return window::close(window);
                     }
 
@@ -271,6 +454,18 @@ impl Flowsurface {
 
                     return window::collect_window_specs(active_windows, Message::ExitRequested);
                 }
+                window::Event::Opened(window) => {
+                    return window::scale_factor(window).map(move |scale_factor| {
+                        Message::WindowEvent(window::Event::Rescaled(window, scale_factor))
+                    });
+                }
+                window::Event::Rescaled(window, scale_factor) => {
+                    self.window_scales.insert(window, scale_factor);
+                    self.sync_scale_factors();
+                }
+                window::Event::UserInput => {
+                    self.idle_scheduler.on_input(Instant::now());
+                }
             },
             Message::ExitRequested(windows) => {
                 self.save_state_to_disk(&windows);
@@ -348,7 +543,7 @@ impl Flowsurface {
                             data,
                             stream,
                         }) => dashboard
-                            .distribute_fetched_data(main_window.id, pane_id, data, stream)
+                            .distribute_fetched_data(main_window.id, pane_id, data, *stream)
                             .map(move |msg| Message::Dashboard {
                                 layout_id: Some(layout_id),
                                 event: msg,
@@ -406,6 +601,36 @@ impl Flowsurface {
                                 }
                             }
                         }
+                        Some(dashboard::Event::SnapshotRequested {
+                            window,
+                            target,
+                            label,
+                        }) => window::capture_widget(window, target)
+                            .map(move |shot| Message::SnapshotCaptured(shot, label.clone())),
+                        Some(dashboard::Event::AccentChanged(target, color)) => {
+                            self.accents.set(target, color);
+                            Task::none()
+                        }
+                        Some(dashboard::Event::AlertLevelEdited(ticker, edit)) => {
+                            if let Some(alerts::Action::LimitReached) =
+                                self.alerts.edit_level(ticker, edit)
+                            {
+                                self.notifications.push(Toast::warn(format!(
+                                    "Can't add more than {} alerts",
+                                    data::alerts::MAX_ALERTS
+                                )));
+                            }
+                            self.sync_alert_levels();
+                            Task::none()
+                        }
+                        Some(dashboard::Event::PlaceOrder(ticker_info, side, price)) => {
+                            let action = self.trading.place(ticker_info, side, price);
+                            self.handle_trading_action(Some(action))
+                        }
+                        Some(dashboard::Event::CancelOrders(ticker_info, ids)) => {
+                            let action = self.trading.cancel(ticker_info, ids);
+                            self.handle_trading_action(action)
+                        }
                         Some(dashboard::Event::RequestPalette) => {
                             let theme = self.theme.0.clone();
 
@@ -434,6 +659,7 @@ impl Flowsurface {
             }
             Message::ScaleFactorChanged(value) => {
                 self.ui_scale_factor = value;
+                self.sync_scale_factors();
             }
             Message::ToggleTradeFetch(checked) => {
                 self.layout_manager
@@ -446,6 +672,155 @@ impl Flowsurface {
                     self.confirm_dialog = None;
                 }
             }
+            Message::SetTradeRetention(retention) => {
+                self.trade_retention = retention;
+
+                std::thread::spawn(move || {
+                    data::tickstore::TickStore::default().compact(retention);
+                });
+            }
+            Message::SetMaintenance(maintenance) => {
+                self.maintenance = maintenance;
+            }
+            Message::MaintenanceFinished(report) => {
+                self.idle_scheduler.finish();
+
+                match report {
+                    Some(report) if !report.is_empty() => {
+                        self.notifications.push(Toast::custom(
+                            "Idle cleanup",
+                            report.to_string(),
+                            toast::Status::Success,
+                        ));
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.notifications
+                            .push(Toast::warn("Idle cleanup stopped unexpectedly"));
+                    }
+                }
+            }
+            Message::ToggleHistoryCache(enabled) => {
+                connector::fetcher::toggle_history_cache(enabled);
+            }
+            Message::ClearHistoryCache => {
+                self.confirm_dialog = None;
+
+                let size = data::history_cache::size_on_disk();
+                match data::history_cache::clear() {
+                    Ok(()) => {
+                        self.notifications.push(Toast::info(format!(
+                            "Cleared {:.1} MB of cached OI & funding history",
+                            size as f64 / (1024.0 * 1024.0)
+                        )));
+                    }
+                    Err(err) => {
+                        let err = format!("Failed to clear history cache: {err}");
+                        log::error!("{err}");
+                        self.notifications.push(Toast::error(err));
+                    }
+                }
+            }
+            Message::SetSnapshotScale(snapshot) => {
+                self.snapshot = snapshot;
+            }
+            Message::SnapshotCaptured(shot, label) => {
+                let Some(shot) = shot else {
+                    self.notifications
+                        .push(Toast::warn("Pane isn't visible, nothing to capture"));
+                    return Task::none();
+                };
+
+                let capture = window::into_capture(shot).upscale(self.snapshot.scale);
+
+                match capture.save_png(&label) {
+                    Ok(path) => {
+                        let path = path.display().to_string();
+                        self.notifications
+                            .push(Toast::info(format!("Snapshot saved to {path}")));
+                        return iced::clipboard::write(path);
+                    }
+                    Err(err) => {
+                        let err = format!("Failed to save snapshot: {err}");
+                        log::error!("{err}");
+                        self.notifications.push(Toast::error(err));
+                    }
+                }
+            }
+            Message::SetSessionProfile(profile) => {
+                connector::stream::set_session_profile(profile);
+
+                let main_window = self.main_window.id;
+                return self
+                    .active_dashboard_mut()
+                    .apply_session_profile(main_window)
+                    .map(move |msg| Message::Dashboard {
+                        layout_id: None,
+                        event: msg,
+                    });
+            }
+            Message::ToggleExternalEvents(enabled) => {
+                self.external_events.enabled = enabled;
+            }
+            Message::CopyExternalEventsToken => {
+                self.notifications
+                    .push(Toast::info("External events token copied to clipboard"));
+                return iced::clipboard::write(self.external_events.token.clone());
+            }
+            Message::ExternalEvent(event) => match event {
+                connector::ingest::Event::Received(event) => {
+                    let main_window = self.main_window.id;
+                    let matched = self.active_dashboard_mut().annotate(&event, main_window);
+
+                    let summary = format!("{}: {}", event.ticker.to_uppercase(), event.summary());
+                    if matched == 0 {
+                        log::info!("External event without a matching chart: {summary}");
+                    }
+                    self.notifications.push(Toast::info(summary));
+                }
+                connector::ingest::Event::Rejected(err) => {
+                    log::warn!("{err}");
+                    self.notifications.push(Toast::warn(err));
+                }
+                connector::ingest::Event::Failed(err) => {
+                    log::error!("{err}");
+                    self.external_events.enabled = false;
+                    self.notifications.push(Toast::error(err));
+                }
+            },
+            Message::ToggleStateBackup(enabled) => {
+                self.state_backup.enabled = enabled;
+
+                if enabled {
+                    self.run_state_backup();
+                }
+            }
+            Message::SetBackupDir(dir) => {
+                let dir = dir.trim();
+                self.state_backup.dir = (!dir.is_empty()).then(|| dir.into());
+                self.backups = self.state_backup.list();
+            }
+            Message::RestoreBackup(backup) => {
+                self.confirm_dialog = None;
+
+                match data::config::backup::restore(&backup) {
+                    Ok(()) => return self.restart(),
+                    Err(err) => {
+                        let err = format!("Failed to restore backup from {}: {err}", backup.date);
+                        log::error!("{err}");
+                        self.notifications.push(Toast::error(err));
+                    }
+                }
+            }
+            Message::ToggleMarketBroadcast(enabled) => {
+                self.market_broadcast.enabled = enabled;
+
+                if enabled {
+                    self.start_market_broadcast();
+                } else {
+                    self.broadcast_server = None;
+                }
+            }
             Message::ToggleDialogModal(dialog) => {
                 self.confirm_dialog = dialog;
             }
@@ -504,25 +879,146 @@ impl Flowsurface {
                                 name: manager.ensure_unique_name(&name, new_uid),
                             };
 
-                            let mut popout_windows = Vec::new();
+                            let dashboard = layout::dashboard(&ser_dashboard, old_id);
 
-                            for (pane, window_spec) in &ser_dashboard.popout {
-                                let configuration = configuration(pane.clone());
-                                popout_windows.push((configuration, *window_spec));
-                            }
+                            manager.insert_layout(new_layout.clone(), dashboard);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            Message::LayoutTemplates(message) => match self.layout_templates.update(message) {
+                Some(layout_templates::Action::Apply(template)) => {
+                    self.confirm_dialog = Some(
+                        screen::ConfirmDialog::new(
+                            format!(
+                                "Replace the panes of the active layout with \"{}\"?",
+                                template.name
+                            ),
+                            Box::new(Message::ApplyLayoutTemplate(Box::new(template.dashboard))),
+                        )
+                        .with_confirm_btn_text("Apply".to_string()),
+                    );
+                }
+                Some(layout_templates::Action::SaveCurrent(name)) => {
+                    let dashboard = data::Dashboard::from(self.active_dashboard());
 
-                            let dashboard = Dashboard::from_config(
-                                configuration(ser_dashboard.pane.clone()),
-                                popout_windows,
-                                old_id,
-                            );
+                    match self.layout_templates.save(name.clone(), dashboard) {
+                        Ok(()) => {
+                            self.notifications
+                                .push(Toast::info(format!("Saved layout template \"{name}\"")));
+                        }
+                        Err(err) => {
+                            log::error!("{err}");
+                            self.notifications.push(Toast::error(err));
+                        }
+                    }
+                }
+                Some(layout_templates::Action::SaveFailed(err)) => {
+                    log::error!("{err}");
+                    self.notifications.push(Toast::error(err));
+                }
+                Some(layout_templates::Action::Exit) => {
+                    self.sidebar.set_menu(Some(sidebar::Menu::Layout));
+                }
+                None => {}
+            },
+            Message::ApplyLayoutTemplate(template) => {
+                self.confirm_dialog = None;
+                self.sidebar.set_menu(None);
 
-                            manager.insert_layout(new_layout.clone(), dashboard);
+                let Some(layout_id) = self
+                    .layout_manager
+                    .active_layout_id()
+                    .map(|layout| layout.unique)
+                else {
+                    return Task::none();
+                };
+
+                let close_windows = Task::batch(
+                    self.active_dashboard()
+                        .popout
+                        .keys()
+                        .copied()
+                        .map(window::close::<window::Id>)
+                        .collect::<Vec<_>>(),
+                )
+                .discard();
+
+                if let Some(layout) = self.layout_manager.get_mut(layout_id) {
+                    layout.dashboard = layout::dashboard(&template, layout_id);
+                }
+
+                return close_windows.chain(self.load_layout(layout_id, self.main_window.id));
+            }
+            Message::Connections(message) => {
+                if let Some(connections::Action::ReportError(err)) =
+                    self.connections.update(message)
+                {
+                    self.notifications.push(Toast::error(err));
+                }
+            }
+            Message::Alerts(message) => {
+                let action = self.alerts.update(message);
+                self.sync_alert_levels();
+
+                match action {
+                    Some(alerts::Action::LimitReached) => {
+                        self.notifications.push(Toast::warn(format!(
+                            "Can't add more than {} alerts",
+                            data::alerts::MAX_ALERTS
+                        )));
+                    }
+                    Some(alerts::Action::Deliver(requests)) => {
+                        return self.deliver_alerts(requests);
+                    }
+                    Some(alerts::Action::SaveFailed(err)) => {
+                        self.notifications.push(Toast::error(err));
+                    }
+                    None => {}
+                }
+            }
+            Message::Trading(message) => {
+                let action = self.trading.update(message);
+                return self.handle_trading_action(action);
+            }
+            Message::Journal(message) => {
+                let action = self.journal.update(message);
+                self.sync_journal_markers();
+
+                match action {
+                    Some(journal::Action::Focus(ticker, time, price)) => {
+                        let main_window = self.main_window.id;
+                        if !self.active_dashboard_mut().focus_journal_entry(
+                            ticker,
+                            time,
+                            price,
+                            main_window,
+                        ) {
+                            self.notifications.push(Toast::warn(format!(
+                                "Open a time based kline chart of {} to see this trade",
+                                ticker.symbol_and_exchange_string()
+                            )));
                         }
                     }
+                    Some(journal::Action::Failed(err)) => {
+                        self.notifications.push(Toast::error(err));
+                    }
                     None => {}
                 }
             }
+            Message::Account(message) => {
+                if let Some(account::Action::Refresh) = self.account.update(message) {
+                    return self.fetch_balances();
+                }
+            }
+            Message::AlertDelivered(channel, result) => {
+                if let Err(err) = result {
+                    self.notifications.push(Toast::error(format!(
+                        "Alert delivery via {channel} failed: {err}"
+                    )));
+                }
+            }
             Message::AudioStream(message) => {
                 if let Some(event) = self.audio_stream.update(message) {
                     match event {
@@ -635,6 +1131,18 @@ impl Flowsurface {
                             event: msg,
                         });
                     }
+                    Some(dashboard::sidebar::Action::PresetSelected(ticker_info, preset)) => {
+                        let main_window_id = self.main_window.id;
+                        let handles = self.handles.clone();
+
+                        return self
+                            .active_dashboard_mut()
+                            .init_focused_preset(&handles, main_window_id, ticker_info, preset)
+                            .map(move |msg| Message::Dashboard {
+                                layout_id: None,
+                                event: msg,
+                            });
+                    }
                     Some(dashboard::sidebar::Action::ErrorOccurred(err)) => {
                         self.notifications.push(Toast::error(err.to_string()));
                     }
@@ -672,7 +1180,13 @@ impl Flowsurface {
                 .map(Message::Sidebar);
 
             let dashboard_view = dashboard
-                .view(&self.main_window, tickers_table, self.timezone)
+                .view(
+                    &self.main_window,
+                    tickers_table,
+                    self.connections.rates(),
+                    self.timezone,
+                    &self.accents,
+                )
                 .map(move |msg| Message::Dashboard {
                     layout_id: None,
                     event: msg,
@@ -718,7 +1232,14 @@ impl Flowsurface {
         } else {
             container(
                 dashboard
-                    .view_window(id, &self.main_window, tickers_table, self.timezone)
+                    .view_window(
+                        id,
+                        &self.main_window,
+                        tickers_table,
+                        self.connections.rates(),
+                        self.timezone,
+                        &self.accents,
+                    )
                     .map(move |msg| Message::Dashboard {
                         layout_id: None,
                         event: msg,
@@ -767,6 +1288,19 @@ impl Flowsurface {
 
         let tick = iced::window::frames().map(Message::Tick);
 
+        let external_events = if self.external_events.enabled {
+            Subscription::run_with(
+                (
+                    self.external_events.port,
+                    self.external_events.token.clone(),
+                ),
+                connector::ingest::listen,
+            )
+            .map(Message::ExternalEvent)
+        } else {
+            Subscription::none()
+        };
+
         let hotkeys = keyboard::listen().filter_map(|event| {
             let keyboard::Event::KeyPressed { key, .. } = event else {
                 return None;
@@ -783,6 +1317,7 @@ impl Flowsurface {
             window_events,
             tick,
             hotkeys,
+            external_events,
         ])
     }
 
@@ -808,6 +1343,91 @@ impl Flowsurface {
             .expect("No active dashboard")
     }
 
+    /// Runs the storage cleanup off the UI thread, reporting back with what it removed
+    fn run_maintenance(&self) -> Task<Message> {
+        let (config, retention) = (self.maintenance, self.trade_retention);
+        let (sender, receiver) = iced::futures::channel::oneshot::channel();
+
+        std::thread::spawn(move || {
+            let _ = sender.send(data::maintenance::run(config, retention));
+        });
+
+        Task::perform(receiver, |report| Message::MaintenanceFinished(report.ok()))
+    }
+
+    /// Takes today's backup of the saved state file if backups are on and it's missing
+    fn run_state_backup(&mut self) {
+        self.last_backup_check = Instant::now();
+
+        match self.state_backup.run_if_due() {
+            Ok(Some(path)) => {
+                log::info!("Backed up saved state to {}", path.display());
+                self.backups = self.state_backup.list();
+            }
+            Ok(None) => {}
+            Err(err) => {
+                let err = format!("Failed to back up saved state: {err}");
+                log::error!("{err}");
+                self.notifications.push(Toast::error(err));
+            }
+        }
+    }
+
+    fn start_market_broadcast(&mut self) {
+        let port = self.market_broadcast.port;
+        let allowed_origins = self.market_broadcast.allowed_origins.clone();
+
+        match connector::broadcast::Server::start(port, allowed_origins) {
+            Ok(server) => self.broadcast_server = Some(server),
+            Err(err) => {
+                let err = format!("Failed to re-broadcast market data on port {port}: {err}");
+                log::error!("{err}");
+                self.market_broadcast.enabled = false;
+                self.notifications.push(Toast::error(err));
+            }
+        }
+    }
+
+    /// Mirrors price cross alerts onto the charts of the active layout, once they changed
+    fn sync_alert_levels(&mut self) {
+        let main_window = self.main_window.id;
+        let levels = self.alerts.price_levels();
+        self.active_dashboard_mut()
+            .set_alert_levels(main_window, levels);
+    }
+
+    /// Mirrors orders and positions onto the panes of the active layout, once they changed
+    fn sync_accounts(&mut self) {
+        let main_window = self.main_window.id;
+        let accounts = self.trading.accounts();
+        self.active_dashboard_mut()
+            .set_accounts(main_window, accounts);
+    }
+
+    /// Marks logged trades on the kline charts of the active layout, once the journal changed
+    fn sync_journal_markers(&mut self) {
+        let main_window = self.main_window.id;
+        let markers = self.journal.markers();
+        self.active_dashboard_mut()
+            .set_journal_markers(main_window, markers);
+    }
+
+    /// Pushes the scale factor of each open window, the UI scale included, to the active layout
+    fn sync_scale_factors(&mut self) {
+        let main_window = self.main_window.id;
+        let ui_scale: f32 = self.ui_scale_factor.into();
+        let scales = self
+            .window_scales
+            .iter()
+            .map(|(window, scale)| (*window, scale * ui_scale))
+            .collect::<Vec<_>>();
+
+        let dashboard = self.active_dashboard_mut();
+        for (window, scale_factor) in scales {
+            dashboard.set_scale_factor(main_window, window, scale_factor);
+        }
+    }
+
     fn load_layout(&mut self, layout_uid: uuid::Uuid, main_window: window::Id) -> Task<Message> {
         if let Err(err) = self.layout_manager.set_active_layout(layout_uid) {
             log::error!("Failed to set active layout: {}", err);
@@ -816,6 +1436,10 @@ impl Flowsurface {
 
         self.layout_manager
             .park_inactive_layouts(layout_uid, main_window);
+        self.sync_scale_factors();
+        self.sync_alert_levels();
+        self.sync_accounts();
+        self.sync_journal_markers();
 
         self.layout_manager
             .get_mut(layout_uid)
@@ -872,12 +1496,48 @@ impl Flowsurface {
                         )),
                     ));
 
+                    let toggle_connections = button(text("Connections")).on_press(
+                        Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(Some(
+                            sidebar::Menu::Connections,
+                        ))),
+                    );
+
+                    let toggle_alerts = button(text("Alerts")).on_press(Message::Sidebar(
+                        dashboard::sidebar::Message::ToggleSidebarMenu(Some(sidebar::Menu::Alerts)),
+                    ));
+
+                    let toggle_trading = button(text("Trading")).on_press(Message::Sidebar(
+                        dashboard::sidebar::Message::ToggleSidebarMenu(Some(
+                            sidebar::Menu::Trading,
+                        )),
+                    ));
+
+                    let toggle_journal = button(text("Journal")).on_press(Message::Sidebar(
+                        dashboard::sidebar::Message::ToggleSidebarMenu(Some(
+                            sidebar::Menu::Journal,
+                        )),
+                    ));
+
                     let timezone_picklist = pick_list(
                         [data::UserTimezone::Utc, data::UserTimezone::Local],
                         Some(self.timezone),
                         Message::SetTimezone,
                     );
 
+                    let session_profile_picklist = {
+                        let picklist = pick_list(
+                            data::config::session::SessionProfile::ALL,
+                            Some(connector::stream::session_profile()),
+                            Message::SetSessionProfile,
+                        );
+
+                        tooltip(
+                            picklist,
+                            Some("Remote subscribes to klines only and pauses depth & trade panes"),
+                            TooltipPosition::Top,
+                        )
+                    };
+
                     let size_in_quote_currency_checkbox = {
                         let is_active = match self.volume_size_unit {
                             exchange::SizeUnit::Quote => true,
@@ -976,6 +1636,211 @@ impl Flowsurface {
                         )
                     };
 
+                    let trade_retention_picklist = {
+                        let picklist = pick_list(
+                            data::tickstore::RetentionPolicy::DAY_OPTIONS
+                                .map(|days| data::tickstore::RetentionPolicy { days }),
+                            Some(self.trade_retention),
+                            Message::SetTradeRetention,
+                        );
+
+                        tooltip(
+                            row![text("Keep fetched trades for"), picklist]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                            Some(
+                                "Fetched trades are stored on disk and reused to backfill footprint charts",
+                            ),
+                            TooltipPosition::Top,
+                        )
+                    };
+
+                    let idle_cleanup = {
+                        use data::maintenance::{MaintenanceConfig, SnapshotAge};
+
+                        let maintenance = self.maintenance;
+
+                        let checkbox = iced::widget::checkbox(maintenance.enabled)
+                            .label("Clean up when idle")
+                            .on_toggle(move |enabled| {
+                                Message::SetMaintenance(MaintenanceConfig {
+                                    enabled,
+                                    ..maintenance
+                                })
+                            });
+
+                        let idle_picklist = pick_list(
+                            MaintenanceConfig::IDLE_OPTIONS,
+                            Some(maintenance.idle_mins),
+                            move |idle_mins| {
+                                Message::SetMaintenance(MaintenanceConfig {
+                                    idle_mins,
+                                    ..maintenance
+                                })
+                            },
+                        );
+
+                        let snapshot_picklist = pick_list(
+                            SnapshotAge::OPTIONS,
+                            Some(SnapshotAge(maintenance.snapshot_max_age_days)),
+                            move |age| {
+                                Message::SetMaintenance(MaintenanceConfig {
+                                    snapshot_max_age_days: age.0,
+                                    ..maintenance
+                                })
+                            },
+                        );
+
+                        column![
+                            tooltip(
+                                checkbox,
+                                Some("Apply the trade retention, evict history of tickers not charted\nfor a month and prune exports after no input for a while"),
+                                TooltipPosition::Top,
+                            ),
+                            row![text("Idle for"), idle_picklist, text("minutes")]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                            row![text("Keep pane snapshots"), snapshot_picklist]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                        ]
+                        .spacing(8)
+                    };
+
+                    let history_cache = {
+                        let checkbox =
+                            iced::widget::checkbox(connector::fetcher::is_history_cache_enabled())
+                                .label("Cache OI & funding history")
+                                .on_toggle(Message::ToggleHistoryCache);
+
+                        let clear_btn =
+                            button(text("Clear cache")).on_press(Message::ToggleDialogModal(Some(
+                                screen::ConfirmDialog::new(
+                                    "Delete all cached open interest and funding rate history?"
+                                        .to_string(),
+                                    Box::new(Message::ClearHistoryCache),
+                                )
+                                .with_confirm_btn_text("Clear".to_string()),
+                            )));
+
+                        column![
+                            tooltip(
+                                checkbox,
+                                Some("Keep fetched history on disk and only fetch what's newer"),
+                                TooltipPosition::Top,
+                            ),
+                            clear_btn,
+                        ]
+                        .spacing(8)
+                    };
+
+                    let snapshot_scale_picklist = {
+                        let picklist = pick_list(
+                            data::snapshot::SnapshotConfig::SCALES
+                                .map(|scale| data::snapshot::SnapshotConfig { scale }),
+                            Some(self.snapshot),
+                            Message::SetSnapshotScale,
+                        );
+
+                        tooltip(
+                            row![text("Pane snapshot scale"), picklist]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                            Some("Upscales images saved with the PNG button of panes"),
+                            TooltipPosition::Top,
+                        )
+                    };
+
+                    let external_events_checkbox = {
+                        let checkbox = iced::widget::checkbox(self.external_events.enabled)
+                            .label(format!(
+                                "Accept external events (:{})",
+                                self.external_events.port
+                            ))
+                            .on_toggle(Message::ToggleExternalEvents);
+
+                        let copy_token =
+                            button(text("Copy token")).on_press(Message::CopyExternalEventsToken);
+
+                        row![
+                            tooltip(
+                                checkbox,
+                                Some(
+                                    "Listen on localhost for JSON-lines events (fills, signals...)\nand mark them on charts of the same ticker",
+                                ),
+                                TooltipPosition::Top,
+                            ),
+                            tooltip(
+                                copy_token,
+                                Some("Senders have to send it as the first line of a connection"),
+                                TooltipPosition::Top,
+                            ),
+                        ]
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                    };
+
+                    let market_broadcast_checkbox = {
+                        let checkbox = iced::widget::checkbox(self.market_broadcast.enabled)
+                            .label(format!(
+                                "Re-broadcast market data (ws://localhost:{})",
+                                self.market_broadcast.port
+                            ))
+                            .on_toggle(Message::ToggleMarketBroadcast);
+
+                        tooltip(
+                            checkbox,
+                            Some(
+                                "Serve trades, depth and klines of open streams as JSON\nover a local websocket for other tools",
+                            ),
+                            TooltipPosition::Top,
+                        )
+                    };
+
+                    let state_backup = {
+                        let checkbox = iced::widget::checkbox(self.state_backup.enabled)
+                            .label("Daily backups")
+                            .on_toggle(Message::ToggleStateBackup);
+
+                        let dir = self
+                            .state_backup
+                            .dir
+                            .as_ref()
+                            .map(|dir| dir.display().to_string())
+                            .unwrap_or_default();
+                        let dir_input = text_input("Backup folder path", &dir)
+                            .on_input(Message::SetBackupDir)
+                            .size(crate::style::text_size::BODY);
+
+                        let restore = pick_list(
+                            self.backups.as_slice(),
+                            None::<&data::config::backup::Backup>,
+                            |backup| {
+                                let dialog = screen::ConfirmDialog::new(
+                                    format!(
+                                        "Restore the state saved on {}? The app restarts with it.",
+                                        backup.date
+                                    ),
+                                    Box::new(Message::RestoreBackup(backup)),
+                                )
+                                .with_confirm_btn_text("Restore".to_string());
+                                Message::ToggleDialogModal(Some(dialog))
+                            },
+                        )
+                        .placeholder("Restore a backup...");
+
+                        column![
+                            tooltip(
+                                checkbox,
+                                Some("Copy the saved state to the folder below once a day,\nkeeping the last week"),
+                                TooltipPosition::Top,
+                            ),
+                            dir_input,
+                            restore,
+                        ]
+                        .spacing(8)
+                    };
+
                     let open_data_folder = {
                         let button =
                             button(text("Open data folder")).on_press(Message::DataFolderRequested);
@@ -1044,11 +1909,14 @@ impl Flowsurface {
                         column![text("Sidebar position").size(crate::style::text_size::SECTION), sidebar_pos_picklist,].spacing(12),
                         column![text("Time zone").size(crate::style::text_size::SECTION), timezone_picklist,].spacing(12),
                         column![text("Market data").size(crate::style::text_size::SECTION), size_in_quote_currency_checkbox,].spacing(12),
+                        column![text("Storage").size(crate::style::text_size::SECTION), trade_retention_picklist, idle_cleanup, history_cache, snapshot_scale_picklist,].spacing(12),
+                        column![text("Session profile").size(crate::style::text_size::SECTION), session_profile_picklist,].spacing(12),
                         column![text("Theme").size(crate::style::text_size::SECTION), theme_picklist,].spacing(12),
                         column![text("Interface scale").size(crate::style::text_size::SECTION), scale_factor,].spacing(12),
+                        column![text("State backups").size(crate::style::text_size::SECTION), state_backup,].spacing(12),
                         column![
                             text("Experimental").size(crate::style::text_size::SECTION),
-                            column![trade_fetch_checkbox, toggle_theme_editor, toggle_network_editor].spacing(8),
+                            column![trade_fetch_checkbox, external_events_checkbox, market_broadcast_checkbox, toggle_theme_editor, toggle_network_editor, toggle_connections, toggle_alerts, toggle_trading, toggle_journal].spacing(8),
                         ]
                         .spacing(12),
                         footer,
@@ -1102,12 +1970,12 @@ impl Flowsurface {
                 let manage_pane = if let Some((window_id, pane_id)) = dashboard.focus {
                     let selected_pane_str =
                         if let Some(state) = dashboard.get_pane(main_window, window_id, pane_id) {
-                            let link_group_name: String =
-                                state.link_group.as_ref().map_or_else(String::new, |g| {
-                                    " - Group ".to_string() + &g.to_string()
-                                });
+                            let link_group_name: String = state
+                                .link_group
+                                .as_ref()
+                                .map_or_else(String::new, |g| format!(" - Group {g}"));
 
-                            state.content.to_string() + &link_group_name
+                            format!("{}{link_group_name}", state.content)
                         } else {
                             "".to_string()
                         };
@@ -1197,7 +2065,14 @@ impl Flowsurface {
                     let col = column![
                         manage_pane,
                         rule::horizontal(1.0).style(style::split_ruler),
-                        self.layout_manager.view().map(Message::Layouts)
+                        self.layout_manager.view().map(Message::Layouts),
+                        button(text("Templates").align_x(Alignment::Center))
+                            .width(iced::Length::Fill)
+                            .on_press(Message::Sidebar(
+                                dashboard::sidebar::Message::ToggleSidebarMenu(Some(
+                                    sidebar::Menu::LayoutTemplates,
+                                )),
+                            )),
                     ];
 
                     container(col.align_x(Alignment::Center).spacing(20))
@@ -1220,6 +2095,34 @@ impl Flowsurface {
                     align_x,
                 )
             }
+            sidebar::Menu::LayoutTemplates => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(40)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(40)),
+                };
+
+                let base_content = dashboard_modal(
+                    base,
+                    self.layout_templates.view().map(Message::LayoutTemplates),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                );
+
+                if let Some(dialog) = &self.confirm_dialog {
+                    let dialog_content =
+                        confirm_dialog_container(dialog.clone(), Message::ToggleDialogModal(None));
+
+                    main_dialog_modal(
+                        base_content,
+                        dialog_content,
+                        Message::ToggleDialogModal(None),
+                    )
+                } else {
+                    base_content
+                }
+            }
             sidebar::Menu::Audio => {
                 let (align_x, padding) = match sidebar_pos {
                     sidebar::Position::Left => (Alignment::Start, padding::left(44).top(76)),
@@ -1256,6 +2159,92 @@ impl Flowsurface {
                     align_x,
                 )
             }
+            sidebar::Menu::Connections => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).bottom(4)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.connections
+                        .view(&dashboard.streams, Instant::now())
+                        .map(Message::Connections),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::End,
+                    align_x,
+                )
+            }
+            sidebar::Menu::Alerts => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).bottom(4)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.alerts.view(&dashboard.streams).map(Message::Alerts),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::End,
+                    align_x,
+                )
+            }
+            sidebar::Menu::Trading => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).bottom(4)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.trading.view(&dashboard.streams).map(Message::Trading),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::End,
+                    align_x,
+                )
+            }
+            sidebar::Menu::Journal => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).bottom(4)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.journal.view(&dashboard.streams).map(Message::Journal),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::End,
+                    align_x,
+                )
+            }
+            sidebar::Menu::Account => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(112)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(112)),
+                };
+
+                let venues = self
+                    .trading
+                    .keyed_venues()
+                    .into_iter()
+                    .map(|(venue, _)| venue)
+                    .collect::<Vec<_>>();
+
+                dashboard_modal(
+                    base,
+                    self.account
+                        .view(&venues, self.trading.paper_pnls())
+                        .map(Message::Account),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                )
+            }
             sidebar::Menu::Network => {
                 let (align_x, padding) = match sidebar_pos {
                     sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
@@ -1299,6 +2288,10 @@ impl Flowsurface {
 
         self.sidebar.sync_tickers_table_settings();
 
+        if let Some(connections::Action::ReportError(err)) = self.connections.flush() {
+            log::error!("{err}");
+        }
+
         let mut ser_layouts = vec![];
         for layout in &self.layout_manager.layouts {
             if let Some(layout) = self.layout_manager.get(layout.id.unique) {
@@ -1328,19 +2321,35 @@ impl Flowsurface {
 
         let proxy_cfg_persisted = self.network.proxy_cfg().map(|p| p.without_auth());
 
-        let state = data::State::from_parts(
-            layouts,
-            self.theme.clone(),
-            self.theme_editor.custom_theme.clone().map(data::Theme),
-            main_window_spec,
-            self.timezone,
-            self.sidebar.state.clone(),
-            self.ui_scale_factor,
-            audio_cfg,
-            connector::fetcher::is_trade_fetch_enabled(),
-            self.volume_size_unit,
-            proxy_cfg_persisted,
-        );
+        let state = data::State {
+            session_profile: connector::stream::session_profile(),
+            external_events: self.external_events.clone(),
+            market_broadcast: self.market_broadcast.clone(),
+            accents: self.accents.clone(),
+            state_backup: self.state_backup.clone(),
+            trade_retention: self.trade_retention,
+            maintenance: self.maintenance,
+            history_cache: data::history_cache::CacheConfig {
+                enabled: connector::fetcher::is_history_cache_enabled(),
+            },
+            snapshot: self.snapshot,
+            alerts: self.alerts.alerts().clone(),
+            trading: self.trading.config().clone(),
+            journal: self.journal.config(),
+            ..data::State::from_parts(
+                layouts,
+                self.theme.clone(),
+                self.theme_editor.custom_theme.clone().map(data::Theme),
+                main_window_spec,
+                self.timezone,
+                self.sidebar.state.clone(),
+                self.ui_scale_factor,
+                audio_cfg,
+                connector::fetcher::is_trade_fetch_enabled(),
+                self.volume_size_unit,
+                proxy_cfg_persisted,
+            )
+        };
 
         match serde_json::to_string(&state) {
             Ok(layout_str) => {
@@ -1349,12 +2358,179 @@ impl Flowsurface {
                     log::error!("Failed to write layout state to file: {}", e);
                 } else {
                     log::info!("Persisted state to {file_name}");
+                    self.run_state_backup();
                 }
             }
             Err(e) => log::error!("Failed to serialize layout: {}", e),
         }
     }
 
+    /// Notifies about the alerts `event` fired, returns the webhook and bot deliveries
+    fn fire_alerts(&mut self, event: &exchange::Event) -> Task<Message> {
+        let mut requests = vec![];
+
+        for fired in self.alerts.evaluate(event) {
+            if self.alerts.os_notifications() {
+                notify::desktop("Flowsurface alert", &fired.message);
+            }
+            requests.extend(self.alerts.deliveries(&fired));
+
+            self.notifications.push(Toast::custom(
+                "Alert",
+                fired.message,
+                toast::Status::Warning,
+            ));
+        }
+
+        self.deliver_alerts(requests)
+    }
+
+    fn deliver_alerts(&self, requests: Vec<data::alerts::delivery::Request>) -> Task<Message> {
+        let proxy_cfg = self.network.proxy_cfg();
+
+        Task::batch(requests.into_iter().map(|request| {
+            let channel = request.channel;
+            let proxy_cfg = proxy_cfg.clone();
+
+            Task::perform(
+                async move {
+                    let label = channel.to_string();
+                    exchange::adapter::post_json(
+                        &request.url,
+                        &label,
+                        &request.body,
+                        proxy_cfg.as_ref(),
+                    )
+                    .await
+                    .map_err(|err| {
+                        log::warn!("Alert delivery via {label} failed: {err}");
+                        err.ui_message()
+                    })
+                },
+                move |result| Message::AlertDelivered(channel, result),
+            )
+        }))
+    }
+
+    fn handle_trading_action(&mut self, action: Option<trading::Action>) -> Task<Message> {
+        self.log_executions();
+        self.sync_accounts();
+
+        match action {
+            Some(trading::Action::Send(requests)) => return self.send_orders(requests),
+            Some(trading::Action::Notify(notice)) => {
+                self.notifications
+                    .push(Toast::custom("Order", notice, toast::Status::Success));
+            }
+            Some(trading::Action::Failed(err)) => {
+                self.notifications.push(Toast::warn(err));
+            }
+            None => {}
+        }
+        Task::none()
+    }
+
+    fn send_orders(&self, requests: Vec<trading::Request>) -> Task<Message> {
+        use exchange::adapter::trading as api;
+
+        let proxy_cfg = self.network.proxy_cfg();
+
+        Task::batch(requests.into_iter().map(|request| {
+            let proxy_cfg = proxy_cfg.clone();
+
+            let message = match request {
+                trading::Request::Place(credentials, order) => Task::perform(
+                    async move {
+                        api::place_limit(&credentials, order, proxy_cfg.as_ref())
+                            .await
+                            .map_err(|err| err.ui_message())
+                    },
+                    trading::Message::Placed,
+                ),
+                trading::Request::Cancel(credentials, ticker_info, id) => Task::perform(
+                    async move {
+                        let result =
+                            api::cancel(&credentials, ticker_info, &id, proxy_cfg.as_ref())
+                                .await
+                                .map_err(|err| err.ui_message());
+                        (id, result)
+                    },
+                    move |(id, result)| trading::Message::Cancelled(ticker_info.ticker, id, result),
+                ),
+                trading::Request::Refresh(credentials, ticker_info) => {
+                    let since = self.journal.import_since(ticker_info.ticker);
+
+                    Task::perform(
+                        async move {
+                            let proxy_cfg = proxy_cfg.as_ref();
+                            let orders = api::open_orders(&credentials, ticker_info, proxy_cfg)
+                                .await
+                                .map_err(|err| err.ui_message())?;
+                            let positions = api::positions(&credentials, ticker_info, proxy_cfg)
+                                .await
+                                .map_err(|err| err.ui_message())?;
+                            // The journal can catch up on the next refresh
+                            let trades =
+                                api::user_trades(&credentials, ticker_info, since, proxy_cfg)
+                                    .await
+                                    .unwrap_or_else(|err| {
+                                        log::warn!("Failed to import executions: {err}");
+                                        vec![]
+                                    });
+
+                            let account = data::trading::Account {
+                                orders,
+                                positions,
+                                fills: vec![],
+                            };
+                            Ok((account, trades))
+                        },
+                        move |result| trading::Message::Refreshed(ticker_info.ticker, result),
+                    )
+                }
+            };
+
+            message.map(Message::Trading)
+        }))
+    }
+
+    fn log_executions(&mut self) {
+        let executions = self.trading.take_executions();
+        if executions.is_empty() {
+            return;
+        }
+        if let Err(err) = self.journal.record(executions) {
+            self.notifications.push(Toast::error(err));
+        }
+        self.sync_journal_markers();
+    }
+
+    fn fetch_balances(&self) -> Task<Message> {
+        let proxy_cfg = self.network.proxy_cfg();
+
+        Task::batch(
+            self.trading
+                .keyed_venues()
+                .into_iter()
+                .map(|(venue, credentials)| {
+                    let proxy_cfg = proxy_cfg.clone();
+
+                    Task::perform(
+                        async move {
+                            exchange::adapter::trading::balances(
+                                &credentials,
+                                venue,
+                                proxy_cfg.as_ref(),
+                            )
+                            .await
+                            .map_err(|err| err.ui_message())
+                        },
+                        move |result| Message::Account(account::Message::Fetched(venue, result)),
+                    )
+                }),
+        )
+    }
+
     fn restart(&mut self) -> Task<Message> {
         let mut windows_to_close: Vec<window::Id> =
             self.active_dashboard().popout.keys().copied().collect();
@@ -1,8 +1,14 @@
+pub mod account;
+pub mod alerts;
 pub mod audio;
+pub mod connections;
+pub mod journal;
 pub mod layout_manager;
+pub mod layout_templates;
 pub mod network_manager;
 pub mod pane;
 pub mod theme_editor;
+pub mod trading;
 
 use iced::widget::{center, container, mouse_area, opaque, stack};
 use iced::{Alignment, Color, Element, Length, padding};
@@ -0,0 +1,233 @@
+use crate::style;
+
+use data::trading::paper::Pnl;
+use data::util::format_with_commas;
+use exchange::Ticker;
+use exchange::adapter::Venue;
+use exchange::adapter::trading::Balance;
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{Alignment, Element, Length, Theme};
+use rustc_hash::FxHashMap;
+use std::time::{Duration, Instant};
+
+/// How often balances are fetched again while the panel is open
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Refresh,
+    Fetched(Venue, Result<Vec<Balance>, String>),
+}
+
+pub enum Action {
+    Refresh,
+}
+
+/// Balances, PnL and margin usage of the venues with a saved API key
+#[derive(Default)]
+pub struct AccountPanel {
+    balances: FxHashMap<Venue, Result<Vec<Balance>, String>>,
+    last_refresh: Option<Instant>,
+}
+
+impl AccountPanel {
+    /// Whether the balances are due for a refresh, marking it as started if so
+    pub fn start_refresh_if_due(&mut self, now: Instant) -> bool {
+        let is_due = self
+            .last_refresh
+            .is_none_or(|last| now.duration_since(last) >= REFRESH_INTERVAL);
+
+        if is_due {
+            self.last_refresh = Some(now);
+        }
+        is_due
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::Refresh => {
+                self.last_refresh = Some(Instant::now());
+                return Some(Action::Refresh);
+            }
+            Message::Fetched(venue, result) => {
+                if let Err(err) = &result {
+                    log::warn!("Failed to fetch {venue} balances: {err}");
+                }
+                self.balances.insert(venue, result);
+            }
+        }
+        None
+    }
+
+    /// `venues` are the ones with a saved key, `paper` the tickers traded on paper if enabled
+    pub fn view<'a>(
+        &'a self,
+        venues: &[Venue],
+        paper: Option<Vec<(Ticker, Pnl)>>,
+    ) -> Element<'a, Message> {
+        let header = row![
+            text("Account").size(style::text_size::SECTION),
+            space::horizontal(),
+            button(text("Refresh"))
+                .on_press_maybe((!venues.is_empty()).then_some(Message::Refresh)),
+        ]
+        .align_y(Alignment::Center);
+
+        let mut content = column![].spacing(12);
+
+        if let Some(paper) = paper {
+            content = content.push(paper_view(paper));
+        }
+
+        if venues.is_empty() {
+            content = content.push(text(
+                "Add an API key in Settings > Trading to see balances here",
+            ));
+        }
+        for &venue in venues {
+            let body: Element<'_, Message> = match self.balances.get(&venue) {
+                None => text("Loading...").into(),
+                Some(Err(err)) => text(err.as_str())
+                    .style(|theme: &Theme| iced::widget::text::Style {
+                        color: Some(theme.extended_palette().danger.base.color),
+                    })
+                    .into(),
+                Some(Ok(balances)) if balances.is_empty() => text("No balances").into(),
+                Some(Ok(balances)) => balances_view(balances),
+            };
+
+            content = content.push(
+                column![
+                    row![
+                        style::icon_text(style::venue_icon(venue), 12),
+                        text(venue.to_string()),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                    container(body).padding(8).style(style::modal_container),
+                ]
+                .spacing(6),
+            );
+        }
+
+        container(
+            column![
+                header,
+                scrollable(content).spacing(4),
+                text("Realized PnL is summed since the start of the UTC day")
+                    .size(style::text_size::SMALL),
+            ]
+            .spacing(12),
+        )
+        .width(360)
+        .max_height(560)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+}
+
+fn balances_view(balances: &[Balance]) -> Element<'_, Message> {
+    let mut list = column![].spacing(8);
+    let mut last_account = None;
+
+    for balance in balances {
+        if last_account != Some(balance.account) {
+            last_account = Some(balance.account);
+            list = list.push(text(balance.account).size(style::text_size::SMALL));
+        }
+
+        let mut details = column![
+            row![
+                text(balance.asset.as_str()),
+                space::horizontal(),
+                text(format_with_commas(balance.wallet)),
+            ],
+            row![
+                text("Available").size(style::text_size::SMALL),
+                space::horizontal(),
+                text(format_with_commas(balance.available)).size(style::text_size::SMALL),
+            ],
+        ]
+        .spacing(2);
+
+        if balance.unrealized_pnl != 0.0 || balance.realized_pnl.is_some() {
+            details = details.push(pnl_row("Unrealized PnL", balance.unrealized_pnl));
+        }
+        if let Some(realized) = balance.realized_pnl {
+            details = details.push(pnl_row("Realized PnL", realized));
+        }
+        if let Some(usage) = balance.margin_usage() {
+            details = details.push(row![
+                text("Margin used").size(style::text_size::SMALL),
+                space::horizontal(),
+                text(format!(
+                    "{} ({usage:.1}%)",
+                    format_with_commas(balance.margin_used)
+                ))
+                .size(style::text_size::SMALL),
+            ]);
+        }
+
+        list = list.push(details);
+    }
+
+    list.width(Length::Fill).into()
+}
+
+fn paper_view<'a>(mut pnls: Vec<(Ticker, Pnl)>) -> Element<'a, Message> {
+    pnls.sort_by_key(|(ticker, _)| ticker.symbol_and_exchange_string());
+
+    let mut list = column![].spacing(8);
+    if pnls.is_empty() {
+        list = list.push(text("No paper trades yet"));
+    }
+
+    for (ticker, pnl) in pnls {
+        list = list.push(
+            column![
+                row![
+                    text(ticker.symbol_and_exchange_string()),
+                    space::horizontal(),
+                    text(if pnl.qty == 0.0 {
+                        "Flat".to_string()
+                    } else {
+                        format!("{} @ {}", pnl.qty, format_with_commas(pnl.avg_price))
+                    }),
+                ],
+                pnl_row("Unrealized PnL", pnl.unrealized),
+                pnl_row("Realized PnL", pnl.realized),
+            ]
+            .spacing(2),
+        );
+    }
+
+    column![
+        text("Paper"),
+        container(list.width(Length::Fill))
+            .padding(8)
+            .style(style::modal_container),
+    ]
+    .spacing(6)
+    .into()
+}
+
+fn pnl_row<'a>(label: &'a str, value: f64) -> Element<'a, Message> {
+    row![
+        text(label).size(style::text_size::SMALL),
+        space::horizontal(),
+        text(format_with_commas(value))
+            .size(style::text_size::SMALL)
+            .style(move |theme: &Theme| {
+                let palette = theme.extended_palette();
+                iced::widget::text::Style {
+                    color: Some(match value {
+                        v if v > 0.0 => palette.success.base.color,
+                        v if v < 0.0 => palette.danger.base.color,
+                        _ => palette.background.base.text,
+                    }),
+                }
+            }),
+    ]
+    .into()
+}
@@ -0,0 +1,452 @@
+use crate::style;
+use crate::widget::tooltip;
+
+use data::alerts::delivery::{self, Channel, Secrets};
+use data::alerts::{self, Alert, Condition, ConditionKind, Fired, LevelEdit};
+use exchange::Ticker;
+use exchange::adapter::UniqueStreams;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Alignment, Element, Theme};
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectTicker(TickerOption),
+    SelectKind(ConditionKind),
+    ValueChanged(String),
+    SelectWindow(WindowOption),
+    Add,
+    ToggleEnabled(u32, bool),
+    ToggleRepeat(u32, bool),
+    Remove(u32),
+    ToggleOsNotifications(bool),
+    ToggleChannel(Channel, bool),
+    WebhookUrlChanged(String),
+    TelegramTokenChanged(String),
+    TelegramChatChanged(String),
+    SaveSecrets,
+    SendTest,
+}
+
+pub enum Action {
+    LimitReached,
+    Deliver(Vec<delivery::Request>),
+    SaveFailed(String),
+}
+
+/// Alerts of every ticker, managed from the sidebar instead of per pane
+pub struct AlertsManager {
+    alerts: alerts::Alerts,
+    ticker: Option<Ticker>,
+    kind: ConditionKind,
+    value: String,
+    window_secs: u32,
+    secrets: Secrets,
+    /// As last stored in the keychain, edits are only used once saved
+    saved_secrets: Secrets,
+    /// Charts sync with these every frame, so they're only rebuilt when alerts change
+    levels: Arc<FxHashMap<Ticker, Vec<(u32, f64)>>>,
+}
+
+impl AlertsManager {
+    pub fn new(alerts: alerts::Alerts) -> Self {
+        let kind = ConditionKind::PriceCross;
+        let (value, window_secs) = draft_for(kind);
+        let secrets = Secrets::load();
+
+        Self {
+            levels: Arc::new(alerts.price_levels()),
+            alerts,
+            ticker: None,
+            kind,
+            value,
+            window_secs,
+            saved_secrets: secrets.clone(),
+            secrets,
+        }
+    }
+
+    pub fn alerts(&self) -> &alerts::Alerts {
+        &self.alerts
+    }
+
+    pub fn os_notifications(&self) -> bool {
+        self.alerts.config.os_notifications
+    }
+
+    /// Levels of the enabled price cross alerts per ticker, drawn on their charts
+    pub fn price_levels(&self) -> Arc<FxHashMap<Ticker, Vec<(u32, f64)>>> {
+        Arc::clone(&self.levels)
+    }
+
+    pub fn evaluate(&mut self, event: &exchange::Event) -> Vec<Fired> {
+        if self.alerts.is_empty() {
+            return vec![];
+        }
+
+        let fired = self.alerts.evaluate(event);
+        if !fired.is_empty() {
+            // One-shot alerts were just disabled
+            self.refresh_levels();
+        }
+        fired
+    }
+
+    /// Requests delivering `fired` to the enabled webhook and Telegram channels
+    pub fn deliveries(&self, fired: &Fired) -> Vec<delivery::Request> {
+        self.alerts
+            .config
+            .delivery
+            .requests(&self.saved_secrets, fired)
+    }
+
+    pub fn edit_level(&mut self, ticker: Ticker, edit: LevelEdit) -> Option<Action> {
+        let added = self.alerts.edit_level(ticker, edit);
+        self.refresh_levels();
+
+        if added {
+            None
+        } else {
+            Some(Action::LimitReached)
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        let action = self.apply(message);
+        self.refresh_levels();
+
+        action
+    }
+
+    /// Replaces the shared levels only when they changed, so charts aren't synced for nothing
+    fn refresh_levels(&mut self) {
+        let levels = self.alerts.price_levels();
+        if *self.levels != levels {
+            self.levels = Arc::new(levels);
+        }
+    }
+
+    fn apply(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::SelectTicker(option) => self.ticker = Some(option.0),
+            Message::SelectKind(kind) => {
+                self.kind = kind;
+                (self.value, self.window_secs) = draft_for(kind);
+            }
+            Message::ValueChanged(value) => self.value = value,
+            Message::SelectWindow(option) => self.window_secs = option.0,
+            Message::Add => {
+                let (Some(ticker), Some(condition)) = (self.ticker, self.draft_condition()) else {
+                    return None;
+                };
+
+                if self.alerts.add(ticker, condition).is_none() {
+                    return Some(Action::LimitReached);
+                }
+                (self.value, self.window_secs) = draft_for(self.kind);
+            }
+            Message::ToggleEnabled(id, enabled) => self.alerts.set_enabled(id, enabled),
+            Message::ToggleRepeat(id, repeat) => self.alerts.set_repeat(id, repeat),
+            Message::Remove(id) => self.alerts.remove(id),
+            Message::ToggleOsNotifications(enabled) => {
+                self.alerts.config.os_notifications = enabled;
+            }
+            Message::ToggleChannel(channel, enabled) => match channel {
+                Channel::Webhook => self.alerts.config.delivery.webhook = enabled,
+                Channel::Telegram => self.alerts.config.delivery.telegram = enabled,
+            },
+            Message::WebhookUrlChanged(url) => self.secrets.webhook_url = url,
+            Message::TelegramTokenChanged(token) => self.secrets.telegram_token = token,
+            Message::TelegramChatChanged(chat_id) => {
+                self.alerts.config.delivery.telegram_chat_id = chat_id;
+            }
+            Message::SaveSecrets => {
+                if let Err(err) = self.secrets.save() {
+                    return Some(Action::SaveFailed(err));
+                }
+                self.saved_secrets = self.secrets.clone();
+            }
+            Message::SendTest => {
+                let ticker = self
+                    .alerts
+                    .iter()
+                    .map(|alert| alert.ticker)
+                    .next()
+                    .or(self.ticker)?;
+
+                let test = Fired {
+                    id: 0,
+                    ticker,
+                    message: "Test alert, delivery is set up".to_string(),
+                };
+                return Some(Action::Deliver(self.deliveries(&test)));
+            }
+        }
+        None
+    }
+
+    fn draft_condition(&self) -> Option<Condition> {
+        let value = self.value.trim().parse::<f64>().ok()?;
+        if !value.is_finite() || value <= 0.0 {
+            return None;
+        }
+
+        Some(match self.kind {
+            ConditionKind::PriceCross => Condition::PriceCross { price: value },
+            ConditionKind::PercentChange => Condition::PercentChange {
+                pct: value as f32,
+                window_secs: self.window_secs,
+            },
+            ConditionKind::VolumeSpike => Condition::VolumeSpike {
+                multiple: value as f32,
+                window_secs: self.window_secs,
+            },
+        })
+    }
+
+    pub fn view<'a>(&'a self, streams: &'a UniqueStreams) -> Element<'a, Message> {
+        let notifications_checkbox = checkbox(self.os_notifications())
+            .label("Desktop notifications")
+            .on_toggle(Message::ToggleOsNotifications);
+
+        let header = row![
+            text("Alerts").size(style::text_size::SECTION),
+            space::horizontal(),
+            notifications_checkbox,
+        ]
+        .align_y(Alignment::Center);
+
+        container(
+            column![
+                header,
+                self.form_view(streams),
+                scrollable(self.list_view(streams)).spacing(4),
+                self.delivery_view(),
+            ]
+            .spacing(12),
+        )
+        .max_width(380)
+        .max_height(520)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+
+    fn form_view(&self, streams: &UniqueStreams) -> Element<'_, Message> {
+        let mut tickers = streams
+            .iter()
+            .map(|stream| TickerOption(stream.ticker_info().ticker))
+            .collect::<Vec<_>>();
+        tickers.sort_by_key(|option| option.0.symbol_and_exchange_string());
+        tickers.dedup();
+
+        let selected = self
+            .ticker
+            .map(TickerOption)
+            .filter(|option| tickers.contains(option));
+
+        let ticker_picklist =
+            pick_list(tickers, selected, Message::SelectTicker).placeholder("Streamed ticker");
+        let kind_picklist = pick_list(ConditionKind::ALL, Some(self.kind), Message::SelectKind);
+
+        let (placeholder, label) = match self.kind {
+            ConditionKind::PriceCross => ("e.g. 65000", "Price"),
+            ConditionKind::PercentChange => ("e.g. 1.5", "Percent"),
+            ConditionKind::VolumeSpike => ("e.g. 3", "x average"),
+        };
+
+        let is_valid = self.value.is_empty() || self.draft_condition().is_some();
+        let on_add = selected.and(self.draft_condition()).map(|_| Message::Add);
+
+        let value_input = text_input(placeholder, &self.value)
+            .on_input(Message::ValueChanged)
+            .on_submit_maybe(on_add.clone())
+            .width(100)
+            .style(move |theme, status| style::validated_text_input(theme, status, is_valid));
+
+        let mut params = row![text(label), value_input]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+        if self.kind != ConditionKind::PriceCross {
+            params = params.push(text("per"));
+            params = params.push(pick_list(
+                alerts::WINDOW_OPTIONS.map(WindowOption),
+                Some(WindowOption(self.window_secs)),
+                Message::SelectWindow,
+            ));
+        }
+
+        let add_button = button(text("Add")).on_press_maybe(on_add);
+
+        let form = column![
+            row![ticker_picklist, kind_picklist]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            row![params, space::horizontal(), add_button].align_y(Alignment::Center),
+        ]
+        .spacing(8);
+
+        container(form)
+            .padding(8)
+            .style(style::modal_container)
+            .into()
+    }
+
+    fn delivery_view(&self) -> Element<'_, Message> {
+        let config = &self.alerts.config.delivery;
+
+        let webhook = column![
+            checkbox(config.webhook)
+                .label("Webhook (JSON POST)")
+                .on_toggle(|enabled| Message::ToggleChannel(Channel::Webhook, enabled)),
+            text_input("https://...", &self.secrets.webhook_url)
+                .on_input(Message::WebhookUrlChanged)
+                .secure(true),
+        ]
+        .spacing(4);
+
+        let telegram = column![
+            checkbox(config.telegram)
+                .label("Telegram bot")
+                .on_toggle(|enabled| Message::ToggleChannel(Channel::Telegram, enabled)),
+            text_input("Bot token", &self.secrets.telegram_token)
+                .on_input(Message::TelegramTokenChanged)
+                .secure(true),
+            text_input(
+                "Chat id, e.g. 123456789 or @channel",
+                &config.telegram_chat_id
+            )
+            .on_input(Message::TelegramChatChanged),
+        ]
+        .spacing(4);
+
+        let has_changes = self.secrets != self.saved_secrets;
+        let can_test = (config.webhook || config.telegram)
+            && !has_changes
+            && (!self.alerts.is_empty() || self.ticker.is_some());
+
+        let buttons = row![
+            space::horizontal(),
+            button(text("Send test")).on_press_maybe(can_test.then_some(Message::SendTest)),
+            button(text("Save")).on_press_maybe(has_changes.then_some(Message::SaveSecrets)),
+        ]
+        .spacing(8);
+
+        column![
+            text("Delivery").size(style::text_size::SECTION),
+            container(column![webhook, telegram, buttons].spacing(12))
+                .padding(8)
+                .style(style::modal_container),
+            text("The webhook URL and bot token are stored in the OS keychain")
+                .size(style::text_size::SMALL),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    fn list_view<'a>(&'a self, streams: &'a UniqueStreams) -> Element<'a, Message> {
+        let mut list = column![].spacing(4);
+
+        if self.alerts.is_empty() {
+            list = list.push(text("No alerts set"));
+        }
+
+        for alert in self.alerts.iter() {
+            list = list.push(alert_card(alert, streams));
+        }
+
+        list.into()
+    }
+}
+
+fn alert_card<'a>(alert: &'a Alert, streams: &UniqueStreams) -> Element<'a, Message> {
+    let id = alert.id;
+
+    let header = row![
+        checkbox(alert.enabled)
+            .label(alert.ticker.symbol_and_exchange_string())
+            .on_toggle(move |enabled| Message::ToggleEnabled(id, enabled)),
+        space::horizontal(),
+        checkbox(alert.repeat)
+            .label("Repeat")
+            .on_toggle(move |repeat| Message::ToggleRepeat(id, repeat)),
+        tooltip(
+            button(style::icon_text(style::Icon::TrashBin, 11))
+                .on_press(Message::Remove(id))
+                .style(|theme, status| style::button::transparent(theme, status, false)),
+            Some("Remove alert"),
+            iced::widget::tooltip::Position::Top,
+        ),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let mut card = column![header, text(alert.condition.to_string())].spacing(4);
+
+    if let Some(fired) = alert.last_fired {
+        card = card.push(
+            text(format!(
+                "Last fired {}",
+                fired
+                    .format_utc("%Y-%m-%d %H:%M:%S UTC")
+                    .unwrap_or_default()
+            ))
+            .size(style::text_size::SMALL),
+        );
+    }
+
+    if alert.enabled && !streams.iter().any(|stream| alert.is_evaluated_by(stream)) {
+        let hint = if alert.condition.needs_trades() {
+            "Not evaluated, open a pane streaming trades of this ticker"
+        } else {
+            "Not evaluated, open a pane streaming this ticker"
+        };
+
+        card = card.push(
+            text(hint)
+                .size(style::text_size::SMALL)
+                .style(|theme: &Theme| iced::widget::text::Style {
+                    color: Some(theme.extended_palette().warning.base.color),
+                }),
+        );
+    }
+
+    container(card)
+        .padding(8)
+        .style(style::modal_container)
+        .into()
+}
+
+/// Form value and window a new alert of `kind` starts with
+fn draft_for(kind: ConditionKind) -> (String, u32) {
+    match Condition::default_for(kind) {
+        Condition::PriceCross { .. } => (String::new(), alerts::WINDOW_OPTIONS[1]),
+        Condition::PercentChange { pct, window_secs } => (pct.to_string(), window_secs),
+        Condition::VolumeSpike {
+            multiple,
+            window_secs,
+        } => (multiple.to_string(), window_secs),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickerOption(Ticker);
+
+impl std::fmt::Display for TickerOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.symbol_and_exchange_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowOption(u32);
+
+impl std::fmt::Display for WindowOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&alerts::format_window(self.0))
+    }
+}
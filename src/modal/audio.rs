@@ -2,7 +2,7 @@ use crate::TooltipPosition;
 use crate::audio::{AudioError, SoundCache, SoundType};
 use crate::style::{self, icon_text};
 use crate::widget::{labeled_slider, tooltip};
-use data::audio::StreamCfg;
+use data::audio::{StreamCfg, TradeSoundHit};
 use exchange::adapter::{Exchange, StreamKind};
 
 use exchange::Trade;
@@ -384,33 +384,49 @@ impl AudioStream {
                     }
                 };
 
-                let play_one = |this: &mut Self, s: SoundType| -> Option<String> {
-                    match this.play(s) {
-                        Ok(()) => None,
-                        Err(err) => {
-                            let msg = err.to_string();
-                            log::error!("Audio play error: {msg}");
-
-                            if this.disable_audio(err) {
-                                Some(format!("Audio disabled: {msg}"))
-                            } else {
-                                None
-                            }
-                        }
-                    }
-                };
-
                 match buy_count.cmp(&sell_count) {
-                    std::cmp::Ordering::Greater => play_one(self, sound(buy_count, false)),
-                    std::cmp::Ordering::Less => play_one(self, sound(sell_count, true)),
-                    std::cmp::Ordering::Equal => play_one(self, sound(buy_count, false))
-                        .or_else(|| play_one(self, sound(sell_count, true))),
+                    std::cmp::Ordering::Greater => self.play_or_disable(sound(buy_count, false)),
+                    std::cmp::Ordering::Less => self.play_or_disable(sound(sell_count, true)),
+                    std::cmp::Ordering::Equal => self
+                        .play_or_disable(sound(buy_count, false))
+                        .or_else(|| self.play_or_disable(sound(sell_count, true))),
                 }
             }
             data::audio::Threshold::Qty(_) => todo!(),
         }
     }
 
+    /// Plays the sound for an outsized trade or burst picked up by a pane.
+    /// Same as [`Self::try_play_sound`], returns an error message if audio got disabled
+    pub fn play_trade_sound(&mut self, hit: TradeSoundHit) -> Option<String> {
+        let sound = match hit {
+            TradeSoundHit::Trade { is_sell: false } => SoundType::Buy,
+            TradeSoundHit::Trade { is_sell: true } => SoundType::Sell,
+            TradeSoundHit::Burst { is_sell: false }
+            | TradeSoundHit::FlowSignal { is_sell: false } => SoundType::HardBuy,
+            TradeSoundHit::Burst { is_sell: true }
+            | TradeSoundHit::FlowSignal { is_sell: true } => SoundType::HardSell,
+        };
+
+        self.play_or_disable(sound)
+    }
+
+    fn play_or_disable(&mut self, sound: SoundType) -> Option<String> {
+        match self.play(sound) {
+            Ok(()) => None,
+            Err(err) => {
+                let msg = err.to_string();
+                log::error!("Audio play error: {msg}");
+
+                if self.disable_audio(err) {
+                    Some(format!("Audio disabled: {msg}"))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
     /// Disables audio and stores the real error. Returns `true` if we just transitioned
     fn disable_audio(&mut self, err: AudioError) -> bool {
         let was_enabled = self.cache.is_some();
@@ -0,0 +1,272 @@
+use crate::style;
+
+use chrono::NaiveDate;
+use data::quality::{self, DailyReport, QualityTracker};
+use data::rates::StreamRates;
+use exchange::UnixMs;
+use exchange::adapter::health::{ConnectionHealth, HealthState};
+use exchange::adapter::{StreamKind, UniqueStreams};
+use iced::widget::{button, column, container, pick_list, row, scrollable, space, text};
+use iced::{Alignment, Element, Theme};
+use std::time::Instant;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleReports,
+    SelectReport(NaiveDate),
+}
+
+pub enum Action {
+    ReportError(String),
+}
+
+/// Connection diagnostics and daily data quality reports for the market streams
+pub struct Connections {
+    health: ConnectionHealth,
+    quality: QualityTracker,
+    rates: StreamRates,
+    show_reports: bool,
+    saved_reports: Vec<NaiveDate>,
+    /// `None` shows the running report of the current day
+    selected_report: Option<DailyReport>,
+}
+
+impl Connections {
+    pub fn new() -> Self {
+        Self {
+            health: ConnectionHealth::default(),
+            quality: QualityTracker::new(UnixMs::now()),
+            rates: StreamRates::load(),
+            show_reports: false,
+            saved_reports: vec![],
+            selected_report: None,
+        }
+    }
+
+    pub fn record(&mut self, event: &exchange::Event) -> Option<Action> {
+        let now = Instant::now();
+        self.health.record(event, now);
+        self.rates.record(event, now);
+
+        let finished = self.quality.record(event, UnixMs::now())?;
+        self.write_report(&finished)
+    }
+
+    pub fn rates(&self) -> &StreamRates {
+        &self.rates
+    }
+
+    /// Persists the running report and measured rates, e.g. before exit
+    pub fn flush(&mut self) -> Option<Action> {
+        if let Err(err) = self.rates.save() {
+            log::warn!("Failed to save stream rates: {err}");
+        }
+
+        let current = self.quality.current().clone();
+        self.write_report(&current)
+    }
+
+    fn write_report(&mut self, report: &DailyReport) -> Option<Action> {
+        if report.tickers.is_empty() {
+            return None;
+        }
+
+        match quality::write_report(report) {
+            Ok(()) => {
+                if !self.saved_reports.contains(&report.date) {
+                    self.saved_reports.insert(0, report.date);
+                }
+                None
+            }
+            Err(err) => Some(Action::ReportError(format!(
+                "Failed to write data quality report for {}: {err}",
+                report.date
+            ))),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::ToggleReports => {
+                self.show_reports = !self.show_reports;
+                self.selected_report = None;
+
+                if self.show_reports {
+                    self.saved_reports = quality::saved_report_dates();
+                }
+            }
+            Message::SelectReport(date) => {
+                if date == self.quality.current().date {
+                    self.selected_report = None;
+                    return None;
+                }
+
+                match quality::read_report(date) {
+                    Ok(report) => self.selected_report = Some(report),
+                    Err(err) => {
+                        return Some(Action::ReportError(format!(
+                            "Failed to read data quality report for {date}: {err}"
+                        )));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    pub fn view<'a>(&'a self, streams: &'a UniqueStreams, now: Instant) -> Element<'a, Message> {
+        let toggle_label = if self.show_reports {
+            "Live streams"
+        } else {
+            "Daily reports"
+        };
+
+        let header = row![
+            text("Connections").size(style::text_size::SECTION),
+            space::horizontal(),
+            button(text(toggle_label)).on_press(Message::ToggleReports),
+        ]
+        .align_y(Alignment::Center);
+
+        let body = if self.show_reports {
+            self.reports_view()
+        } else {
+            self.streams_view(streams, now)
+        };
+
+        container(column![header, scrollable(body).spacing(4)].spacing(12))
+            .max_width(360)
+            .max_height(480)
+            .padding(24)
+            .style(style::dashboard_modal)
+            .into()
+    }
+
+    fn streams_view<'a>(
+        &'a self,
+        streams: &'a UniqueStreams,
+        now: Instant,
+    ) -> Element<'a, Message> {
+        let mut subscribed = streams.iter().collect::<Vec<_>>();
+        subscribed.sort_by_key(|stream| {
+            (
+                stream.ticker_info().ticker.symbol_and_exchange_string(),
+                stream_label(stream),
+            )
+        });
+
+        let mut list = column![].spacing(4);
+
+        if subscribed.is_empty() {
+            list = list.push(text("No active streams"));
+        }
+
+        for stream in subscribed {
+            let state = self.health.state(stream, now);
+            let entry = self.health.get(stream);
+
+            let header = row![
+                text(stream.ticker_info().ticker.symbol_and_exchange_string()),
+                text(stream_label(stream)).size(style::text_size::SMALL),
+                space::horizontal(),
+                text(state.to_string()).style(move |theme: &Theme| {
+                    let palette = theme.extended_palette();
+                    iced::widget::text::Style {
+                        color: Some(match state {
+                            HealthState::Live => palette.success.base.color,
+                            HealthState::Stale => palette.warning.base.color,
+                            HealthState::Disconnected => palette.danger.base.color,
+                            HealthState::Pending => palette.background.strongest.color,
+                        }),
+                    }
+                }),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            let last_message = entry
+                .and_then(|h| h.since_last_message(now))
+                .map_or("-".to_string(), |elapsed| {
+                    format!("{:.1}s ago", elapsed.as_secs_f32())
+                });
+            let latency = entry
+                .and_then(|h| h.latency_ms)
+                .map_or("-".to_string(), |ms| format!("{ms:.0}ms"));
+            let reconnects = entry.map_or(0, |h| h.reconnects);
+
+            let details = row![
+                text(format!("Last msg: {last_message}")),
+                text(format!("Latency: {latency}")),
+                text(format!("Reconnects: {reconnects}")),
+            ]
+            .spacing(12);
+
+            let mut card = column![header, details.wrap()].spacing(4);
+
+            if state == HealthState::Disconnected
+                && let Some(reason) = entry.and_then(|h| h.last_error.as_ref())
+            {
+                card = card.push(text(reason).size(style::text_size::SMALL));
+            }
+
+            list = list.push(container(card).padding(8).style(style::modal_container));
+        }
+
+        list.into()
+    }
+
+    fn reports_view(&self) -> Element<'_, Message> {
+        let today = self.quality.current().date;
+        let report = self
+            .selected_report
+            .as_ref()
+            .unwrap_or_else(|| self.quality.current());
+
+        let mut dates = self.saved_reports.clone();
+        if !dates.contains(&today) {
+            dates.insert(0, today);
+        }
+
+        let date_picklist = row![
+            text("Day (UTC)"),
+            space::horizontal(),
+            pick_list(dates, Some(report.date), Message::SelectReport),
+        ]
+        .align_y(Alignment::Center);
+
+        let mut list = column![date_picklist].spacing(4);
+
+        if report.tickers.is_empty() {
+            list = list.push(text("Nothing recorded for this day"));
+        }
+
+        for (ticker, stats) in &report.tickers {
+            let details = row![
+                text(format!("Messages: {}", stats.messages)),
+                text(format!(
+                    "Gaps: {} (longest {:.0}s)",
+                    stats.gaps,
+                    stats.longest_gap_ms as f32 / 1000.0
+                )),
+                text(format!("Reconnects: {}", stats.reconnects)),
+                text(format!("Max latency: {}ms", stats.max_latency_ms)),
+            ]
+            .spacing(12);
+
+            let card = column![text(ticker.as_str()), details.wrap()].spacing(4);
+            list = list.push(container(card).padding(8).style(style::modal_container));
+        }
+
+        list.into()
+    }
+}
+
+fn stream_label(stream: &StreamKind) -> String {
+    match stream {
+        StreamKind::Kline { timeframe, .. } => format!("Kline {timeframe}"),
+        StreamKind::Depth { .. } => "Depth".to_string(),
+        StreamKind::Trades { .. } => "Trades".to_string(),
+        StreamKind::CompositeDepth { .. } => "Composite depth".to_string(),
+        StreamKind::MarkPrice { .. } => "Mark price".to_string(),
+    }
+}
@@ -0,0 +1,463 @@
+use super::trading::TickerOption;
+use crate::style;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use data::trading::journal::{Entry, Journal, JournalConfig, Marker, Review, Role, Source};
+use data::util::format_with_commas;
+use exchange::adapter::UniqueStreams;
+use exchange::adapter::trading::OrderSide;
+use exchange::unit::Price;
+use exchange::{Ticker, UnixMs};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Alignment, Element, Length, Theme};
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+/// How far back live executions are imported for tickers with none logged yet
+const IMPORT_LOOKBACK_MS: u64 = 24 * 60 * 60 * 1000;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    TogglePlot(bool),
+    FilterTicker(TickerFilter),
+    FromChanged(String),
+    ToChanged(String),
+    Focus(u64),
+    Remove(u64),
+    SelectTicker(TickerOption),
+    SelectSide(OrderSide),
+    PriceChanged(String),
+    QtyChanged(String),
+    TimeChanged(String),
+    NoteChanged(String),
+    Log,
+}
+
+pub enum Action {
+    /// Pan kline charts of the ticker to the entry
+    Focus(Ticker, UnixMs, Price),
+    Failed(String),
+}
+
+/// Review table of the trade journal and the form to log trades by hand
+pub struct JournalManager {
+    journal: Journal,
+    config: JournalConfig,
+    reviews: FxHashMap<u64, Review>,
+    /// Kline charts sync with these every frame, so they're only rebuilt on changes
+    markers: Arc<FxHashMap<Ticker, Vec<Marker>>>,
+    ticker_filter: TickerFilter,
+    from: String,
+    to: String,
+    draft: Draft,
+}
+
+#[derive(Default)]
+struct Draft {
+    ticker: Option<TickerOption>,
+    side: Option<OrderSide>,
+    price: String,
+    qty: String,
+    /// Now if left empty
+    time: String,
+    note: String,
+}
+
+impl JournalManager {
+    pub fn new(config: JournalConfig) -> Self {
+        let mut manager = Self {
+            journal: Journal::load(),
+            config,
+            reviews: FxHashMap::default(),
+            markers: Arc::new(FxHashMap::default()),
+            ticker_filter: TickerFilter(None),
+            from: String::new(),
+            to: String::new(),
+            draft: Draft::default(),
+        };
+        manager.rebuild();
+        manager
+    }
+
+    pub fn config(&self) -> JournalConfig {
+        self.config
+    }
+
+    pub fn markers(&self) -> Arc<FxHashMap<Ticker, Vec<Marker>>> {
+        Arc::clone(&self.markers)
+    }
+
+    /// Where importing the live executions of `ticker` picks up from
+    pub fn import_since(&self, ticker: Ticker) -> UnixMs {
+        self.journal.last_execution(ticker).map_or_else(
+            || UnixMs::new(UnixMs::now().as_u64().saturating_sub(IMPORT_LOOKBACK_MS)),
+            |last| UnixMs::new(last.as_u64() + 1),
+        )
+    }
+
+    /// Logs executions, saving the journal if any of them were new
+    pub fn record(&mut self, entries: Vec<Entry>) -> Result<(), String> {
+        let mut changed = false;
+        for entry in entries {
+            changed |= self.journal.record(entry);
+        }
+
+        if changed {
+            self.rebuild();
+            return self.save();
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), String> {
+        self.journal
+            .save()
+            .map_err(|err| format!("Failed to save the trade journal: {err}"))
+    }
+
+    fn rebuild(&mut self) {
+        self.reviews = self.journal.reviews();
+        self.markers = Arc::new(if self.config.plot_on_charts {
+            self.journal.markers()
+        } else {
+            FxHashMap::default()
+        });
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::TogglePlot(plot) => {
+                self.config.plot_on_charts = plot;
+                self.rebuild();
+            }
+            Message::FilterTicker(filter) => self.ticker_filter = filter,
+            Message::FromChanged(from) => self.from = from,
+            Message::ToChanged(to) => self.to = to,
+            Message::Focus(id) => {
+                let entry = self.journal.entries().iter().find(|entry| entry.id == id)?;
+                return Some(Action::Focus(entry.ticker, entry.time, entry.price));
+            }
+            Message::Remove(id) => {
+                if self.journal.remove(id) {
+                    self.rebuild();
+                    return self.save().err().map(Action::Failed);
+                }
+            }
+            Message::SelectTicker(option) => self.draft.ticker = Some(option),
+            Message::SelectSide(side) => self.draft.side = Some(side),
+            Message::PriceChanged(price) => self.draft.price = price,
+            Message::QtyChanged(qty) => self.draft.qty = qty,
+            Message::TimeChanged(time) => self.draft.time = time,
+            Message::NoteChanged(note) => self.draft.note = note,
+            Message::Log => {
+                let entry = self.draft_entry()?;
+                self.draft = Draft {
+                    ticker: self.draft.ticker,
+                    side: self.draft.side,
+                    ..Draft::default()
+                };
+                return self.record(vec![entry]).err().map(Action::Failed);
+            }
+        }
+        None
+    }
+
+    fn draft_entry(&self) -> Option<Entry> {
+        let draft = &self.draft;
+        let ticker_info = draft.ticker?.0;
+
+        let price = parse_positive(&draft.price)?;
+        let qty = parse_positive(&draft.qty)?;
+        let time = if draft.time.trim().is_empty() {
+            UnixMs::now()
+        } else {
+            parse_time(&draft.time)?
+        };
+
+        Some(Entry {
+            id: 0,
+            ticker: ticker_info.ticker,
+            time,
+            side: draft.side?,
+            price: Price::from_f64(price).round_to_min_tick(ticker_info.min_ticksize),
+            qty,
+            source: Source::Manual,
+            trade_id: None,
+            note: draft.note.trim().to_string(),
+        })
+    }
+
+    /// Entries passing the filters, newest first
+    fn filtered(&self) -> impl Iterator<Item = &Entry> {
+        let from = parse_date(&self.from).map(day_start);
+        let to = parse_date(&self.to).map(|date| day_start(date) + 24 * 60 * 60 * 1000);
+
+        self.journal.entries().iter().rev().filter(move |entry| {
+            let time = entry.time.as_u64();
+
+            self.ticker_filter
+                .0
+                .is_none_or(|ticker| ticker == entry.ticker)
+                && from.is_none_or(|from| time >= from)
+                && to.is_none_or(|to| time < to)
+        })
+    }
+
+    pub fn view<'a>(&'a self, streams: &'a UniqueStreams) -> Element<'a, Message> {
+        let header = row![
+            text("Trade journal").size(style::text_size::SECTION),
+            space::horizontal(),
+            checkbox(self.config.plot_on_charts)
+                .label("Plot on charts")
+                .on_toggle(Message::TogglePlot),
+        ]
+        .align_y(Alignment::Center);
+
+        container(
+            column![
+                header,
+                self.log_view(streams),
+                self.filters_view(),
+                scrollable(self.table_view()).spacing(4),
+            ]
+            .spacing(12),
+        )
+        .width(560)
+        .max_height(640)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+
+    fn log_view<'a>(&'a self, streams: &'a UniqueStreams) -> Element<'a, Message> {
+        let draft = &self.draft;
+
+        let mut tickers = streams
+            .iter()
+            .map(|stream| TickerOption(stream.ticker_info()))
+            .collect::<Vec<_>>();
+        tickers.sort_by_key(|option| option.0.ticker.symbol_and_exchange_string());
+        tickers.dedup();
+
+        let price_valid = draft.price.is_empty() || parse_positive(&draft.price).is_some();
+        let qty_valid = draft.qty.is_empty() || parse_positive(&draft.qty).is_some();
+        let time_valid = draft.time.trim().is_empty() || parse_time(&draft.time).is_some();
+        let on_log = self.draft_entry().map(|_| Message::Log);
+
+        let form = column![
+            row![
+                pick_list(tickers, draft.ticker, Message::SelectTicker)
+                    .placeholder("Streamed ticker"),
+                pick_list(
+                    [OrderSide::Buy, OrderSide::Sell],
+                    draft.side,
+                    Message::SelectSide
+                )
+                .placeholder("Side"),
+                text_input("Price", &draft.price)
+                    .on_input(Message::PriceChanged)
+                    .style(move |theme, status| {
+                        style::validated_text_input(theme, status, price_valid)
+                    }),
+                text_input("Size", &draft.qty)
+                    .on_input(Message::QtyChanged)
+                    .style(move |theme, status| {
+                        style::validated_text_input(theme, status, qty_valid)
+                    }),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+            row![
+                text_input("YYYY-MM-DD HH:MM (UTC, now if empty)", &draft.time)
+                    .on_input(Message::TimeChanged)
+                    .style(move |theme, status| {
+                        style::validated_text_input(theme, status, time_valid)
+                    }),
+                text_input("Note", &draft.note)
+                    .on_input(Message::NoteChanged)
+                    .on_submit_maybe(on_log.clone()),
+                button(text("Log")).on_press_maybe(on_log),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(8);
+
+        column![
+            text("Log a trade"),
+            container(form).padding(8).style(style::modal_container),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    fn filters_view(&self) -> Element<'_, Message> {
+        let mut tickers = self
+            .journal
+            .entries()
+            .iter()
+            .map(|entry| TickerFilter(Some(entry.ticker)))
+            .collect::<Vec<_>>();
+        tickers.sort_by_key(ToString::to_string);
+        tickers.dedup();
+        tickers.insert(0, TickerFilter(None));
+
+        let from_valid = self.from.is_empty() || parse_date(&self.from).is_some();
+        let to_valid = self.to.is_empty() || parse_date(&self.to).is_some();
+
+        row![
+            pick_list(tickers, Some(self.ticker_filter), Message::FilterTicker),
+            space::horizontal(),
+            text_input("From YYYY-MM-DD", &self.from)
+                .on_input(Message::FromChanged)
+                .width(130)
+                .style(move |theme, status| style::validated_text_input(theme, status, from_valid)),
+            text_input("To YYYY-MM-DD", &self.to)
+                .on_input(Message::ToChanged)
+                .width(130)
+                .style(move |theme, status| style::validated_text_input(theme, status, to_valid)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn table_view(&self) -> Element<'_, Message> {
+        let mut table = column![].spacing(4);
+
+        let mut realized = 0.0;
+        let mut count = 0;
+
+        for entry in self.filtered() {
+            let review = self.reviews.get(&entry.id);
+            if let Some(pnl) = review.and_then(|review| review.realized) {
+                realized += pnl;
+            }
+            count += 1;
+
+            let time = DateTime::from_timestamp_millis(entry.time.as_u64() as i64)
+                .map(|time| time.format(TIME_FORMAT).to_string())
+                .unwrap_or_default();
+            let role = review.map_or(String::new(), |review| review.role.to_string());
+
+            let mut subtitle = row![
+                text(format!("{time} UTC, {}", entry.source)).size(style::text_size::SMALL),
+                space::horizontal(),
+            ];
+            if let Some(pnl) = review
+                .filter(|review| review.role == Role::Exit)
+                .and_then(|review| review.realized)
+            {
+                subtitle = subtitle.push(pnl_text(pnl));
+            }
+
+            let mut details = column![
+                row![
+                    text(format!(
+                        "{} {} {} @ {}",
+                        entry.ticker.symbol_and_exchange_string(),
+                        entry.side,
+                        entry.qty,
+                        entry.price.to_f64()
+                    )),
+                    space::horizontal(),
+                    text(role),
+                ]
+                .spacing(8),
+                subtitle,
+            ]
+            .spacing(2)
+            .width(Length::Fill);
+
+            if !entry.note.is_empty() {
+                details = details.push(text(entry.note.as_str()).size(style::text_size::SMALL));
+            }
+
+            table = table.push(
+                container(
+                    row![
+                        details,
+                        button(text("Chart")).on_press(Message::Focus(entry.id)),
+                        button(style::icon_text(style::Icon::TrashBin, 11))
+                            .on_press(Message::Remove(entry.id))
+                            .style(|theme, status| style::button::transparent(
+                                theme, status, false
+                            )),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+                )
+                .padding(8)
+                .style(style::modal_container),
+            );
+        }
+
+        let summary = if count == 0 {
+            row![text("No trades logged for these filters")]
+        } else {
+            row![
+                text(format!("{count} trades, realized")),
+                space::horizontal(),
+                pnl_text(realized),
+            ]
+        };
+
+        column![summary, table].spacing(8).into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickerFilter(Option<Ticker>);
+
+impl std::fmt::Display for TickerFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(ticker) => f.write_str(&ticker.symbol_and_exchange_string()),
+            None => f.write_str("All tickers"),
+        }
+    }
+}
+
+fn pnl_text<'a>(pnl: f64) -> Element<'a, Message> {
+    text(format_with_commas(pnl))
+        .style(move |theme: &Theme| {
+            let palette = theme.extended_palette();
+            iced::widget::text::Style {
+                color: Some(match pnl {
+                    v if v > 0.0 => palette.success.base.color,
+                    v if v < 0.0 => palette.danger.base.color,
+                    _ => palette.background.base.text,
+                }),
+            }
+        })
+        .into()
+}
+
+fn parse_positive(input: &str) -> Option<f64> {
+    input
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+}
+
+fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), DATE_FORMAT).ok()
+}
+
+fn parse_time(input: &str) -> Option<UnixMs> {
+    let time = NaiveDateTime::parse_from_str(input.trim(), TIME_FORMAT).ok()?;
+    u64::try_from(time.and_utc().timestamp_millis())
+        .ok()
+        .map(UnixMs::new)
+}
+
+fn day_start(date: NaiveDate) -> u64 {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|time| u64::try_from(time.and_utc().timestamp_millis()).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,187 @@
+use crate::style::{self, Icon, icon_text};
+
+use data::Dashboard;
+use data::layout::template::{LayoutTemplate, LayoutTemplates};
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input};
+use iced::{Alignment, Element};
+
+const MAX_NAME_CHARS: usize = 40;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ApplyBuiltin(usize),
+    Apply(String),
+    Remove(String),
+    NameChanged(String),
+    SaveCurrent,
+    Exit,
+}
+
+pub enum Action {
+    /// Asks before replacing the panes of the active layout with the template's
+    Apply(Box<LayoutTemplate>),
+    /// The active layout is saved under the name, see [`LayoutTemplatesManager::save`]
+    SaveCurrent(String),
+    SaveFailed(String),
+    Exit,
+}
+
+/// Dashboards the active layout can start over from, shipped ones and those the user saved
+pub struct LayoutTemplatesManager {
+    builtin: Vec<LayoutTemplate>,
+    saved: LayoutTemplates,
+    name: String,
+}
+
+impl LayoutTemplatesManager {
+    pub fn new() -> Self {
+        Self {
+            builtin: LayoutTemplate::builtin(),
+            saved: LayoutTemplates::load(),
+            name: String::new(),
+        }
+    }
+
+    pub fn save(&mut self, name: String, dashboard: Dashboard) -> Result<(), String> {
+        self.saved.insert(LayoutTemplate { name, dashboard });
+        self.saved
+            .save()
+            .map_err(|err| format!("Failed to save layout template: {err}"))?;
+
+        self.name.clear();
+        Ok(())
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::ApplyBuiltin(index) => {
+                return self
+                    .builtin
+                    .get(index)
+                    .cloned()
+                    .map(Box::new)
+                    .map(Action::Apply);
+            }
+            Message::Apply(name) => {
+                return self
+                    .saved
+                    .get(&name)
+                    .cloned()
+                    .map(Box::new)
+                    .map(Action::Apply);
+            }
+            Message::Remove(name) => {
+                self.saved.remove(&name);
+                if let Err(err) = self.saved.save() {
+                    return Some(Action::SaveFailed(format!(
+                        "Failed to save layout templates: {err}"
+                    )));
+                }
+            }
+            Message::NameChanged(name) => {
+                self.name = name.chars().take(MAX_NAME_CHARS).collect();
+            }
+            Message::SaveCurrent => {
+                let name = self.name.trim();
+                if !name.is_empty() {
+                    return Some(Action::SaveCurrent(name.to_string()));
+                }
+            }
+            Message::Exit => return Some(Action::Exit),
+        }
+        None
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let header = row![
+            button(icon_text(Icon::Return, 12))
+                .style(|theme, status| style::button::transparent(theme, status, false))
+                .on_press(Message::Exit),
+            text("Layout templates").size(style::text_size::SECTION),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let mut presets = column![].spacing(4);
+        for (index, template) in self.builtin.iter().enumerate() {
+            presets = presets.push(template_row(
+                &template.name,
+                Message::ApplyBuiltin(index),
+                None,
+            ));
+        }
+
+        let mut saved = column![].spacing(4);
+        if self.saved.templates().is_empty() {
+            saved = saved.push(text("No saved templates").size(style::text_size::SMALL));
+        }
+        for template in self.saved.templates() {
+            saved = saved.push(template_row(
+                &template.name,
+                Message::Apply(template.name.clone()),
+                Some(Message::Remove(template.name.clone())),
+            ));
+        }
+
+        let trimmed = self.name.trim();
+        let save_label = if self.saved.get(trimmed).is_some() {
+            "Replace"
+        } else {
+            "Save"
+        };
+        let on_save = (!trimmed.is_empty()).then_some(Message::SaveCurrent);
+
+        let save_row = row![
+            text_input("Template name", &self.name)
+                .on_input(Message::NameChanged)
+                .on_submit_maybe(on_save.clone()),
+            button(text(save_label)).on_press_maybe(on_save),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        container(
+            column![
+                header,
+                column![text("Presets").size(style::text_size::SECTION), presets].spacing(8),
+                column![
+                    text("Saved").size(style::text_size::SECTION),
+                    container(scrollable(saved).spacing(4)).max_height(240),
+                    save_row,
+                ]
+                .spacing(8),
+                text("Applying a template replaces the panes of the active layout")
+                    .size(style::text_size::SMALL),
+            ]
+            .spacing(16),
+        )
+        .width(300)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+}
+
+fn template_row<'a>(
+    name: &str,
+    on_apply: Message,
+    on_remove: Option<Message>,
+) -> Element<'a, Message> {
+    let mut template_row = row![
+        text(name.to_string()),
+        space::horizontal(),
+        button(text("Apply")).on_press(on_apply),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    if let Some(on_remove) = on_remove {
+        template_row = template_row.push(
+            button(icon_text(Icon::TrashBin, 12))
+                .style(|theme, status| style::button::transparent(theme, status, false))
+                .on_press(on_remove),
+        );
+    }
+
+    template_row.into()
+}
@@ -16,6 +16,9 @@ pub enum Modal {
     Indicators,
     LinkGroup,
     Controls,
+    Drawings,
+    /// Editing the note at the index, among those of the pane's ticker
+    Note(usize),
 }
 
 pub fn stack_modal<'a, Message>(
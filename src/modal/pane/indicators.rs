@@ -1,22 +1,70 @@
+use crate::chart::indicator::kline::script::ScriptOverlay;
 use crate::screen::dashboard::pane::{self, Message};
 use crate::style::{self, Icon, icon_text};
-use crate::widget::{column_drag, dragger_row};
+use crate::widget::{column_drag, dragger_row, labeled_slider};
 
-use data::chart::indicator::{Indicator, UiIndicator};
+use data::chart::indicator::{
+    BandKind, HeatmapIndicator, Indicator, IndicatorSettings, KlineIndicator,
+    KlineIndicatorInstance, MovingAverage, MovingAverageKind, PriceSource, UiIndicator,
+    VolatilityBand,
+};
+use data::chart::oscillator::OscillatorConfig;
+use data::chart::vwap::{VwapAnchor, VwapConfig};
+use data::layout::template::IndicatorTemplates;
 use iced::{
-    Element, Length, padding,
-    widget::{button, column, container, pane_grid, row, space, text},
+    Alignment, Element, Length, padding,
+    widget::{
+        button, checkbox, column, container, pane_grid, pick_list, row, space, text, text_input,
+    },
 };
 
-pub fn view<'a, I>(
+/// Moving averages a single chart can draw
+const MAX_MOVING_AVERAGES: usize = 8;
+/// Periods new moving averages start with, in order
+const DEFAULT_MA_PERIODS: [u16; 4] = [20, 50, 100, 200];
+
+/// An enabled indicator as the modal lists it
+pub trait Enabled: Copy + std::fmt::Display {
+    type Kind: Indicator + Copy + Into<UiIndicator>;
+
+    fn kind(&self) -> Self::Kind;
+
+    /// Sent when its row is clicked
+    fn remove_event(&self) -> pane::Event;
+}
+
+impl Enabled for HeatmapIndicator {
+    type Kind = HeatmapIndicator;
+
+    fn kind(&self) -> Self::Kind {
+        *self
+    }
+
+    fn remove_event(&self) -> pane::Event {
+        pane::Event::ToggleIndicator((*self).into())
+    }
+}
+
+impl Enabled for KlineIndicatorInstance {
+    type Kind = KlineIndicator;
+
+    fn kind(&self) -> Self::Kind {
+        self.kind
+    }
+
+    fn remove_event(&self) -> pane::Event {
+        pane::Event::RemoveIndicator(self.id)
+    }
+}
+
+/// `params` are settings of the selected indicators, shown under the lists
+pub fn view<'a, E: Enabled>(
     pane: pane_grid::Pane,
     state: &'a pane::State,
-    selected: &[I],
+    selected: &[E],
     market_type: Option<exchange::adapter::MarketKind>,
-) -> Element<'a, Message>
-where
-    I: Indicator + Copy + Into<UiIndicator>,
-{
+    params: Option<Element<'a, Message>>,
+) -> Element<'a, Message> {
     let content_allows_dragging = matches!(state.content, pane::Content::Kline { .. });
     let content_row = if let Some(market) = market_type {
         content_row(pane, selected, market, content_allows_dragging)
@@ -24,54 +72,55 @@ where
         column![].spacing(4).into()
     };
 
-    container(content_row)
+    let content = if let Some(params) = params {
+        column![content_row, params].spacing(12).into()
+    } else {
+        content_row
+    };
+
+    container(content)
         .max_width(200)
         .padding(16)
         .style(style::chart_modal)
         .into()
 }
 
-fn build_indicator_row<'a, I>(
-    pane: pane_grid::Pane,
-    indicator: &I,
+fn build_indicator_row<'a>(
+    label: String,
     is_selected: bool,
-) -> Element<'a, Message>
-where
-    I: Indicator + Copy + Into<UiIndicator>,
-{
+    on_press: Message,
+) -> Element<'a, Message> {
     let content = if is_selected {
         row![
-            text(indicator.to_string()),
+            text(label),
             space::horizontal(),
             container(icon_text(Icon::Checkmark, 12)),
         ]
         .width(Length::Fill)
     } else {
-        row![text(indicator.to_string())].width(Length::Fill)
+        row![text(label)].width(Length::Fill)
     };
 
     button(content)
-        .on_press(Message::PaneEvent(
-            pane,
-            pane::Event::ToggleIndicator((*indicator).into()),
-        ))
+        .on_press(on_press)
         .width(Length::Fill)
         .style(move |theme, status| style::button::modifier(theme, status, is_selected))
         .into()
 }
 
-fn selected_list<'a, I>(
+fn selected_list<'a, E: Enabled>(
     pane: pane_grid::Pane,
-    selected: &[I],
+    selected: &[E],
     reorderable: bool,
-) -> Element<'a, Message>
-where
-    I: Indicator + Copy + Into<UiIndicator>,
-{
+) -> Element<'a, Message> {
     let elements: Vec<Element<_>> = selected
         .iter()
         .map(|indicator| {
-            let base = build_indicator_row(pane, indicator, true);
+            let base = build_indicator_row(
+                indicator.to_string(),
+                true,
+                Message::PaneEvent(pane, indicator.remove_event()),
+            );
             dragger_row(base, reorderable)
         })
         .collect();
@@ -98,7 +147,11 @@ where
     let elements: Vec<Element<_>> = available
         .iter()
         .map(|indicator| {
-            let base = build_indicator_row(pane, indicator, false);
+            let base = build_indicator_row(
+                indicator.to_string(),
+                false,
+                Message::PaneEvent(pane, pane::Event::ToggleIndicator((*indicator).into())),
+            );
             dragger_row(base, false)
         })
         .collect();
@@ -108,15 +161,12 @@ where
         .into()
 }
 
-fn content_row<'a, I>(
+fn content_row<'a, E: Enabled>(
     pane: pane_grid::Pane,
-    selected: &[I],
+    selected: &[E],
     market: exchange::adapter::MarketKind,
     allows_drag: bool,
-) -> Element<'a, Message>
-where
-    I: Indicator + Copy + Into<UiIndicator>,
-{
+) -> Element<'a, Message> {
     let reorderable = allows_drag && selected.len() >= 2;
 
     let selected_list = if !selected.is_empty() {
@@ -125,9 +175,10 @@ where
         None
     };
 
-    let available: Vec<I> = I::for_market(market)
+    // Kinds that can be added again stay listed after being enabled
+    let available: Vec<E::Kind> = E::Kind::for_market(market)
         .iter()
-        .filter(|indicator| !selected.contains(indicator))
+        .filter(|kind| kind.allows_multiple() || !selected.iter().any(|s| s.kind() == **kind))
         .cloned()
         .collect();
     let available_list = if !available.is_empty() {
@@ -152,3 +203,584 @@ where
     .spacing(4)
     .into()
 }
+
+/// Settings of a kline chart's indicators: its overlays, each panel's settings and the
+/// saved templates
+pub fn kline_params<'a>(
+    pane: pane_grid::Pane,
+    selected: &[KlineIndicatorInstance],
+    moving_averages: &[MovingAverage],
+    bands: &[VolatilityBand],
+    vwap: Option<VwapConfig>,
+    view_start: Option<exchange::UnixMs>,
+    scripts: &ScriptOverlay,
+    templates: &IndicatorTemplates,
+    template_name: &str,
+) -> Element<'a, Message> {
+    let mut content = column![
+        templates_view(pane, templates, template_name),
+        moving_averages_view(pane, moving_averages),
+        bands_view(pane, bands),
+        vwap_view(pane, vwap, view_start),
+        scripts_view(pane, scripts),
+    ]
+    .spacing(12);
+
+    if let Some(settings) = indicator_settings_view(pane, selected) {
+        content = content.push(settings);
+    }
+    content.into()
+}
+
+fn moving_averages_view<'a>(
+    pane: pane_grid::Pane,
+    lines: &[MovingAverage],
+) -> Element<'a, Message> {
+    let on_change = move |lines: Vec<MovingAverage>| {
+        Message::PaneEvent(pane, pane::Event::MovingAveragesChanged(lines))
+    };
+
+    let add = {
+        let mut added = lines.to_vec();
+        added.push(MovingAverage {
+            kind: MovingAverageKind::Ema,
+            period: DEFAULT_MA_PERIODS[lines.len() % DEFAULT_MA_PERIODS.len()],
+            color: style::PANE_TINTS[lines.len() % style::PANE_TINTS.len()],
+        });
+
+        button(text("Add"))
+            .on_press_maybe((lines.len() < MAX_MOVING_AVERAGES).then(|| on_change(added)))
+    };
+
+    let mut content = column![
+        row![
+            text("Moving averages").size(crate::style::text_size::SECTION),
+            space::horizontal(),
+            add,
+        ]
+        .align_y(Alignment::Center),
+    ]
+    .spacing(8);
+
+    for (idx, line) in lines.iter().enumerate() {
+        let with_line = |edited: MovingAverage| {
+            let mut lines = lines.to_vec();
+            lines[idx] = edited;
+            on_change(lines)
+        };
+
+        let removed = {
+            let mut lines = lines.to_vec();
+            lines.remove(idx);
+            on_change(lines)
+        };
+
+        let kind_picker = {
+            let (lines, line) = (lines.to_vec(), *line);
+            pick_list(MovingAverageKind::ALL, Some(line.kind), move |kind| {
+                let mut lines = lines.clone();
+                lines[idx] = MovingAverage { kind, ..line };
+                on_change(lines)
+            })
+        };
+
+        let period_slider = {
+            let (lines, line) = (lines.to_vec(), *line);
+            labeled_slider(
+                "Period",
+                2..=400,
+                line.period,
+                move |period| {
+                    let mut lines = lines.clone();
+                    lines[idx] = MovingAverage { period, ..line };
+                    on_change(lines)
+                },
+                |value| value.to_string(),
+                Some(1),
+            )
+        };
+
+        let swatches = style::PANE_TINTS
+            .iter()
+            .fold(row![], |row, &color| {
+                let is_selected = line.color == color;
+                row.push(
+                    button(
+                        container(space().width(12).height(12))
+                            .style(move |theme| style::colored_circle_container(theme, color)),
+                    )
+                    .padding(3)
+                    .style(move |theme, status| {
+                        style::button::transparent(theme, status, is_selected)
+                    })
+                    .on_press(with_line(MovingAverage { color, ..*line })),
+                )
+            })
+            .spacing(2)
+            .align_y(Alignment::Center);
+
+        content = content.push(
+            container(
+                column![
+                    row![
+                        kind_picker,
+                        space::horizontal(),
+                        button(icon_text(Icon::TrashBin, 12))
+                            .style(|theme, status| style::button::transparent(theme, status, false))
+                            .on_press(removed),
+                    ]
+                    .align_y(Alignment::Center),
+                    period_slider,
+                    swatches,
+                ]
+                .spacing(4),
+            )
+            .padding(4)
+            .style(style::modal_container),
+        );
+    }
+
+    content.into()
+}
+
+fn bands_view<'a>(pane: pane_grid::Pane, bands: &[VolatilityBand]) -> Element<'a, Message> {
+    let on_change = move |bands: Vec<VolatilityBand>| {
+        Message::PaneEvent(pane, pane::Event::BandsChanged(bands))
+    };
+
+    let mut content =
+        column![text("Volatility bands").size(crate::style::text_size::SECTION)].spacing(8);
+
+    for kind in BandKind::ALL {
+        let current = bands.iter().position(|band| band.kind == kind);
+
+        let toggle = {
+            let bands = bands.to_vec();
+            checkbox(current.is_some())
+                .label(kind.to_string())
+                .on_toggle(move |enabled| {
+                    let mut bands = bands.clone();
+                    if enabled {
+                        bands.push(kind.default_band());
+                    } else {
+                        bands.retain(|band| band.kind != kind);
+                    }
+                    on_change(bands)
+                })
+        };
+
+        let Some(idx) = current else {
+            content = content.push(toggle);
+            continue;
+        };
+        let band = bands[idx];
+
+        let with_band = {
+            let bands = bands.to_vec();
+            move |edited: VolatilityBand| {
+                let mut bands = bands.clone();
+                bands[idx] = edited;
+                on_change(bands)
+            }
+        };
+
+        let period_slider = {
+            let with_band = with_band.clone();
+            labeled_slider(
+                "Period",
+                2..=200,
+                band.period,
+                move |period| with_band(VolatilityBand { period, ..band }),
+                |value| value.to_string(),
+                Some(1),
+            )
+        };
+
+        let multiplier_slider = {
+            let with_band = with_band.clone();
+            labeled_slider(
+                "Multiplier",
+                0.5..=5.0,
+                band.multiplier,
+                move |multiplier| with_band(VolatilityBand { multiplier, ..band }),
+                |value| format!("{value:.1}"),
+                Some(0.1),
+            )
+        };
+
+        let fill = checkbox(band.fill)
+            .label("Fill between bands")
+            .on_toggle(move |fill| with_band(VolatilityBand { fill, ..band }));
+
+        content = content.push(
+            container(column![toggle, period_slider, multiplier_slider, fill].spacing(4))
+                .padding(4)
+                .style(style::modal_container),
+        );
+    }
+
+    content.into()
+}
+
+/// `view_start` is where a newly anchored VWAP starts, the leftmost bar in view
+fn vwap_view<'a>(
+    pane: pane_grid::Pane,
+    vwap: Option<VwapConfig>,
+    view_start: Option<exchange::UnixMs>,
+) -> Element<'a, Message> {
+    let on_change =
+        move |vwap: Option<VwapConfig>| Message::PaneEvent(pane, pane::Event::VwapChanged(vwap));
+
+    let toggle = checkbox(vwap.is_some())
+        .label("VWAP")
+        .on_toggle(move |enabled| on_change(enabled.then(VwapConfig::default)));
+
+    let Some(config) = vwap else {
+        return column![text("VWAP").size(crate::style::text_size::SECTION), toggle,]
+            .spacing(8)
+            .into();
+    };
+
+    let anchored = match config.anchor {
+        VwapAnchor::Anchored(anchor) => Some(VwapAnchor::Anchored(anchor)),
+        _ => view_start.map(VwapAnchor::Anchored),
+    };
+    let anchors = [Some(VwapAnchor::Daily), Some(VwapAnchor::Weekly), anchored]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let anchor_picker = pick_list(anchors, Some(config.anchor), move |anchor| {
+        on_change(Some(VwapConfig { anchor, ..config }))
+    });
+
+    let reanchor = button(text("Anchor to view")).on_press_maybe(
+        view_start
+            .filter(|_| config.anchor.is_anchored())
+            .map(|start| {
+                on_change(Some(VwapConfig {
+                    anchor: VwapAnchor::Anchored(start),
+                    ..config
+                }))
+            }),
+    );
+
+    let bands = checkbox(config.bands)
+        .label("±1σ and ±2σ bands")
+        .on_toggle(move |bands| on_change(Some(VwapConfig { bands, ..config })));
+
+    column![
+        text("VWAP").size(crate::style::text_size::SECTION),
+        container(
+            column![
+                toggle,
+                row![anchor_picker, space::horizontal(), reanchor].align_y(Alignment::Center),
+                bands,
+            ]
+            .spacing(4),
+        )
+        .padding(4)
+        .style(style::modal_container),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Scripts in the scripts folder, toggled like the built-in indicators
+fn scripts_view<'a>(pane: pane_grid::Pane, scripts: &ScriptOverlay) -> Element<'a, Message> {
+    let enabled = scripts.enabled();
+    let errors = scripts.errors();
+
+    let header = row![
+        text("Scripts").size(crate::style::text_size::SECTION),
+        space::horizontal(),
+        button(text("Reload")).on_press(Message::PaneEvent(pane, pane::Event::ReloadScripts)),
+        button(text("Open folder"))
+            .on_press(Message::PaneEvent(pane, pane::Event::OpenScriptsFolder)),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    let mut list = column![].spacing(4);
+
+    if scripts.available().is_empty() {
+        list = list.push(
+            text("Add .rhai files to the folder, then reload").size(crate::style::text_size::SMALL),
+        );
+    }
+
+    for name in scripts.available() {
+        let is_enabled = enabled.contains(name);
+
+        let toggled = if is_enabled {
+            enabled.iter().filter(|s| *s != name).cloned().collect()
+        } else {
+            let mut added = enabled.clone();
+            added.push(name.clone());
+            added
+        };
+
+        let content = if is_enabled {
+            row![
+                text(name.clone()),
+                space::horizontal(),
+                container(icon_text(Icon::Checkmark, 12)),
+            ]
+        } else {
+            row![text(name.clone())]
+        };
+
+        list = list.push(
+            button(content.width(Length::Fill))
+                .on_press(Message::PaneEvent(
+                    pane,
+                    pane::Event::ScriptsChanged(toggled),
+                ))
+                .width(Length::Fill)
+                .style(move |theme, status| style::button::modifier(theme, status, is_enabled)),
+        );
+
+        if let Some((_, err)) = errors.iter().find(|(script, _)| script == name) {
+            list = list.push(
+                text(err.clone())
+                    .size(crate::style::text_size::SMALL)
+                    .style(|theme: &iced::Theme| iced::widget::text::Style {
+                        color: Some(theme.extended_palette().danger.base.color),
+                    }),
+            );
+        }
+    }
+
+    column![header, list].spacing(8).into()
+}
+
+/// Color, price source and periods of each selected indicator, `None` if none has any
+fn indicator_settings_view<'a>(
+    pane: pane_grid::Pane,
+    selected: &[KlineIndicatorInstance],
+) -> Option<Element<'a, Message>> {
+    let sections = selected
+        .iter()
+        .copied()
+        .filter(|instance| {
+            let kind = instance.kind;
+            kind.has_line() || kind.takes_source() || kind.is_oscillator()
+        })
+        .map(|instance| {
+            let (indicator, current) = (instance.kind, instance.settings);
+            let on_change = move |edited: IndicatorSettings| {
+                Message::PaneEvent(
+                    pane,
+                    pane::Event::IndicatorSettingsChanged(instance.id, edited),
+                )
+            };
+
+            let period_slider =
+                move |label: &'static str,
+                      range: std::ops::RangeInclusive<u16>,
+                      value: u16,
+                      apply: fn(OscillatorConfig, u16) -> OscillatorConfig| {
+                    labeled_slider(
+                        label,
+                        range,
+                        value,
+                        move |value| {
+                            on_change(IndicatorSettings {
+                                periods: apply(current.periods, value),
+                                ..current
+                            })
+                        },
+                        |value| value.to_string(),
+                        Some(1),
+                    )
+                };
+
+            let osc = current.periods;
+            let sliders: Vec<Element<'a, Message>> = match indicator {
+                KlineIndicator::Rsi => vec![period_slider(
+                    "Period",
+                    2..=100,
+                    osc.rsi_period,
+                    |osc, v| OscillatorConfig {
+                        rsi_period: v,
+                        ..osc
+                    },
+                )],
+                KlineIndicator::Macd => vec![
+                    period_slider("Fast", 2..=100, osc.macd_fast, |osc, v| OscillatorConfig {
+                        macd_fast: v,
+                        ..osc
+                    }),
+                    period_slider("Slow", 2..=200, osc.macd_slow, |osc, v| OscillatorConfig {
+                        macd_slow: v,
+                        ..osc
+                    }),
+                    period_slider("Signal", 1..=100, osc.macd_signal, |osc, v| {
+                        OscillatorConfig {
+                            macd_signal: v,
+                            ..osc
+                        }
+                    }),
+                ],
+                KlineIndicator::Stochastic => vec![
+                    period_slider("%K", 1..=100, osc.stoch_k, |osc, v| OscillatorConfig {
+                        stoch_k: v,
+                        ..osc
+                    }),
+                    period_slider("Smoothing", 1..=20, osc.stoch_smoothing, |osc, v| {
+                        OscillatorConfig {
+                            stoch_smoothing: v,
+                            ..osc
+                        }
+                    }),
+                    period_slider("%D", 1..=20, osc.stoch_d, |osc, v| OscillatorConfig {
+                        stoch_d: v,
+                        ..osc
+                    }),
+                ],
+                KlineIndicator::Cci => vec![period_slider(
+                    "Period",
+                    2..=100,
+                    osc.cci_period,
+                    |osc, v| OscillatorConfig {
+                        cci_period: v,
+                        ..osc
+                    },
+                )],
+                _ => vec![],
+            };
+
+            let mut section = column![].spacing(4);
+
+            if indicator.takes_source() {
+                section = section.push(
+                    row![
+                        text("Source"),
+                        space::horizontal(),
+                        pick_list(PriceSource::ALL, Some(current.source), move |source| {
+                            on_change(IndicatorSettings { source, ..current })
+                        }),
+                    ]
+                    .align_y(Alignment::Center),
+                );
+            }
+            for slider in sliders {
+                section = section.push(slider);
+            }
+            if indicator.has_line() {
+                section = section.push(color_swatches(current.color, move |color| {
+                    on_change(IndicatorSettings { color, ..current })
+                }));
+            }
+
+            column![
+                text(instance.to_string()),
+                container(section).padding(4).style(style::modal_container),
+            ]
+            .spacing(4)
+            .into()
+        })
+        .collect::<Vec<Element<'a, Message>>>();
+
+    if sections.is_empty() {
+        return None;
+    }
+
+    Some(
+        column![
+            text("Indicator settings").size(crate::style::text_size::SECTION),
+            iced::widget::Column::with_children(sections).spacing(8),
+        ]
+        .spacing(8)
+        .into(),
+    )
+}
+
+/// Pane tints to pick from, led by the theme's own color
+fn color_swatches<'a>(
+    current: Option<iced::Color>,
+    on_pick: impl Fn(Option<iced::Color>) -> Message,
+) -> Element<'a, Message> {
+    let theme_default = button(text("Auto").size(crate::style::text_size::SMALL))
+        .padding([2, 4])
+        .style(move |theme, status| style::button::transparent(theme, status, current.is_none()))
+        .on_press(on_pick(None));
+
+    style::PANE_TINTS
+        .iter()
+        .fold(row![theme_default], |row, &color| {
+            let is_selected = current == Some(color);
+            row.push(
+                button(
+                    container(space().width(12).height(12))
+                        .style(move |theme| style::colored_circle_container(theme, color)),
+                )
+                .padding(3)
+                .style(move |theme, status| style::button::transparent(theme, status, is_selected))
+                .on_press(on_pick(Some(color))),
+            )
+        })
+        .spacing(2)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+/// Saving the pane's indicators under a name, and applying saved ones
+fn templates_view<'a>(
+    pane: pane_grid::Pane,
+    templates: &IndicatorTemplates,
+    name: &str,
+) -> Element<'a, Message> {
+    let trimmed = name.trim();
+    let save_label = if templates.get(trimmed).is_some() {
+        "Replace"
+    } else {
+        "Save"
+    };
+
+    let save_row = row![
+        text_input("Template name", name)
+            .on_input(move |name| Message::PaneEvent(pane, pane::Event::TemplateNameChanged(name)))
+            .on_submit_maybe(
+                (!trimmed.is_empty())
+                    .then_some(Message::PaneEvent(pane, pane::Event::SaveIndicatorTemplate)),
+            ),
+        button(text(save_label)).on_press_maybe(
+            (!trimmed.is_empty())
+                .then_some(Message::PaneEvent(pane, pane::Event::SaveIndicatorTemplate)),
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    let mut list = column![].spacing(4);
+
+    for template in templates.templates() {
+        list = list.push(
+            row![
+                text(template.name.clone()),
+                space::horizontal(),
+                button(text("Apply")).on_press(Message::PaneEvent(
+                    pane,
+                    pane::Event::ApplyIndicatorTemplate(template.name.clone()),
+                )),
+                button(icon_text(Icon::TrashBin, 12))
+                    .style(|theme, status| style::button::transparent(theme, status, false))
+                    .on_press(Message::PaneEvent(
+                        pane,
+                        pane::Event::RemoveIndicatorTemplate(template.name.clone()),
+                    )),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    column![
+        text("Templates").size(crate::style::text_size::SECTION),
+        save_row,
+        list,
+    ]
+    .spacing(8)
+    .into()
+}
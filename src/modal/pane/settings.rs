@@ -2,20 +2,29 @@ use crate::chart::comparison::ComparisonChart;
 use crate::screen::dashboard::pane::{Event, Message};
 use crate::screen::dashboard::panel::timeandsales;
 use crate::split_column;
+use crate::style::{Icon, icon_text};
 use crate::widget::{classic_slider_row, labeled_slider};
 use crate::{style, tooltip, widget::scrollable_content};
 
+use data::audio::TradeSound;
 use data::chart::heatmap::HeatmapStudy;
-use data::chart::kline::FootprintStudy;
+use data::chart::kline::{
+    BigTrades, CandleStyle, FootprintStudy, ProfileRange, ProfileScope, SessionWindow,
+    SummaryFooter, VolumeProfileConfig,
+};
 use data::chart::{
     KlineChartKind,
+    comparison::{self, CORRELATION_WINDOW_RANGE, ComparisonMode, Normalization},
     heatmap::{self, CoalesceKind},
     kline::ClusterKind,
+    spread::SpreadMode,
 };
-use data::layout::pane::VisualConfig;
+use data::config::accent::{AccentTarget, Accents};
+use data::layout::pane::{Appearance, VisualConfig};
 use data::panel::ladder;
-use data::panel::timeandsales::{StackedBar, StackedBarRatio};
-use data::util::format_with_commas;
+use data::panel::timeandsales::{SIZE_TIER_MULTIPLES, StackedBar, StackedBarRatio, TapeColumn};
+use data::snapshot::TimelapseConfig;
+use data::util::{abbr_large_numbers, format_with_commas};
 
 use iced::widget::{checkbox, space};
 use iced::{
@@ -45,6 +54,8 @@ pub fn heatmap_cfg_view<'a>(
     study_config: &'a study::Configurator<HeatmapStudy>,
     studies: &'a [HeatmapStudy],
     basis: data::chart::Basis,
+    recording_timelapse: bool,
+    history_usage: heatmap::HistoryUsage,
 ) -> Element<'a, Message> {
     let trade_size_slider = {
         let filter = cfg.trade_size_filter;
@@ -248,6 +259,32 @@ pub fn heatmap_cfg_view<'a>(
                 )
             });
 
+        let legend_checkbox = checkbox(cfg.show_legend)
+            .label("Show size legend")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config {
+                        show_legend: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let traded_profile_checkbox = checkbox(cfg.traded_profile)
+            .label("Traded volume beside depth")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config {
+                        traded_profile: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
         let mut col = column![
             text("Trade visualization").size(crate::style::text_size::SECTION),
             dyn_checkbox
@@ -256,7 +293,7 @@ pub fn heatmap_cfg_view<'a>(
         if let Some(slider) = circle_scaling_slider {
             col = col.push(slider);
         }
-        col
+        col.push(legend_checkbox).push(traded_profile_checkbox)
     };
 
     let study_cfg = study_config.view(studies, basis).map(move |msg| {
@@ -271,6 +308,16 @@ pub fn heatmap_cfg_view<'a>(
         noise_filters_column,
         trade_viz_column,
         column![text("Studies").size(crate::style::text_size::SECTION), study_cfg].spacing(8),
+        trade_sound_column(cfg.sound, move |sound| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config { sound, ..cfg }),
+                false,
+            )
+        }),
+        depth_history_column(cfg, pane, history_usage),
+        depth_export_column(cfg, pane),
+        timelapse_column(cfg, pane, recording_timelapse),
         row![
             space::horizontal(),
             sync_all_button(pane, VisualConfig::Heatmap(cfg))
@@ -287,6 +334,8 @@ pub fn heatmap_shader_cfg_view<'a>(
     study_config: &'a study::Configurator<HeatmapStudy>,
     studies: &'a [HeatmapStudy],
     basis: data::chart::Basis,
+    recording_timelapse: bool,
+    history_usage: heatmap::HistoryUsage,
 ) -> Element<'a, Message> {
     let trade_size_slider = {
         let filter = cfg.trade_size_filter;
@@ -369,6 +418,18 @@ pub fn heatmap_shader_cfg_view<'a>(
                 )
             });
 
+        let tooltip_picklist = pick_list(
+            heatmap::TooltipKind::ALL,
+            Some(cfg.tooltip),
+            move |tooltip| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config { tooltip, ..cfg }),
+                    false,
+                )
+            },
+        );
+
         let mut col = column![
             text("Trade visualization").size(crate::style::text_size::SECTION),
             dyn_checkbox
@@ -377,7 +438,11 @@ pub fn heatmap_shader_cfg_view<'a>(
         if let Some(slider) = circle_scaling_slider {
             col = col.push(slider);
         }
-        col
+        col.push(
+            row![text("Tooltip"), space::horizontal(), tooltip_picklist]
+                .spacing(8)
+                .align_y(Alignment::Center),
+        )
     };
 
     let study_cfg = study_config.view(studies, basis).map(move |msg| {
@@ -389,8 +454,19 @@ pub fn heatmap_shader_cfg_view<'a>(
 
     let content = split_column![
         size_filters_column,
+        depth_shading_column(cfg, pane),
         trade_viz_column,
         column![text("Studies").size(crate::style::text_size::SECTION), study_cfg].spacing(8),
+        trade_sound_column(cfg.sound, move |sound| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config { sound, ..cfg }),
+                false,
+            )
+        }),
+        depth_history_column(cfg, pane, history_usage),
+        depth_export_column(cfg, pane),
+        timelapse_column(cfg, pane, recording_timelapse),
         row![
             space::horizontal(),
             sync_all_button(pane, VisualConfig::Heatmap(cfg))
@@ -401,6 +477,346 @@ pub fn heatmap_shader_cfg_view<'a>(
     cfg_view_container(360, content)
 }
 
+fn depth_shading_column<'a>(cfg: heatmap::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let on_change =
+        move |cfg| Message::VisualConfigChanged(pane, VisualConfig::Heatmap(cfg), false);
+
+    let color_map_picklist = pick_list(
+        heatmap::ColorMap::options(cfg.color_map),
+        Some(cfg.color_map),
+        move |color_map| on_change(heatmap::Config { color_map, ..cfg }),
+    );
+
+    let intensity_picklist = pick_list(
+        heatmap::IntensityScale::ALL,
+        Some(cfg.intensity),
+        move |intensity| on_change(heatmap::Config { intensity, ..cfg }),
+    );
+
+    let depth_mode_picklist = pick_list(
+        heatmap::DepthMode::ALL,
+        Some(cfg.depth_mode),
+        move |depth_mode| on_change(heatmap::Config { depth_mode, ..cfg }),
+    );
+
+    let mut content = column![
+        text("Depth shading").size(crate::style::text_size::SECTION),
+        row![text("Cells"), space::horizontal(), depth_mode_picklist]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        row![text("Colors"), space::horizontal(), color_map_picklist]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        row![text("Intensity"), space::horizontal(), intensity_picklist]
+            .spacing(8)
+            .align_y(Alignment::Center),
+    ]
+    .spacing(8);
+
+    if let heatmap::ColorMap::Gradient { low, high } = cfg.color_map {
+        let with_gradient = move |low, high| {
+            on_change(heatmap::Config {
+                color_map: heatmap::ColorMap::Gradient { low, high },
+                ..cfg
+            })
+        };
+
+        content = content.extend([
+            text("Thinnest levels")
+                .size(crate::style::text_size::SMALL)
+                .into(),
+            color_swatches(low, move |low| with_gradient(low, high)),
+            text("Deepest levels")
+                .size(crate::style::text_size::SMALL)
+                .into(),
+            color_swatches(high, move |high| with_gradient(low, high)),
+        ]);
+    }
+
+    content.into()
+}
+
+fn color_swatches<'a>(
+    current: iced::Color,
+    on_pick: impl Fn(iced::Color) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    [iced::Color::BLACK, iced::Color::WHITE]
+        .into_iter()
+        .chain(style::PANE_TINTS)
+        .fold(row![], |row, color| {
+            row.push(
+                button(
+                    container(space().width(14).height(14))
+                        .style(move |theme| style::colored_circle_container(theme, color)),
+                )
+                .padding(4)
+                .style(move |theme, status| {
+                    style::button::transparent(theme, status, current == color)
+                })
+                .on_press(on_pick(color)),
+            )
+        })
+        .spacing(4)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+fn depth_history_column<'a>(
+    cfg: heatmap::Config,
+    pane: pane_grid::Pane,
+    usage: heatmap::HistoryUsage,
+) -> Element<'a, Message> {
+    let on_change = move |history| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Heatmap(heatmap::Config { history, ..cfg }),
+            false,
+        )
+    };
+
+    let limit_picklist = pick_list(
+        heatmap::HistoryLimit::options(cfg.history),
+        Some(cfg.history),
+        on_change,
+    );
+
+    let limit_slider = match cfg.history {
+        heatmap::HistoryLimit::Auto => None,
+        heatmap::HistoryLimit::Minutes(minutes) => Some(classic_slider_row(
+            text("Keep"),
+            slider(5..=720, minutes, move |minutes| {
+                on_change(heatmap::HistoryLimit::Minutes(minutes))
+            })
+            .step(5u16)
+            .into(),
+            Some(
+                text(data::util::format_duration_ms(u64::from(minutes) * 60_000))
+                    .size(crate::style::text_size::EMPHASIS),
+            ),
+        )),
+        heatmap::HistoryLimit::Megabytes(megabytes) => Some(classic_slider_row(
+            text("Up to"),
+            slider(16..=2048, megabytes, move |megabytes| {
+                on_change(heatmap::HistoryLimit::Megabytes(megabytes))
+            })
+            .step(16u16)
+            .into(),
+            Some(text(format!("{megabytes} MB")).size(crate::style::text_size::EMPHASIS)),
+        )),
+    };
+
+    let usage_text = text(format!(
+        "Using {:.1} MB for {}",
+        usage.bytes as f64 / (1024.0 * 1024.0),
+        data::util::format_duration_ms(usage.span_ms),
+    ))
+    .size(crate::style::text_size::SMALL);
+
+    let mut col = column![
+        text("Depth history").size(crate::style::text_size::SECTION),
+        row![text("Limit"), space::horizontal(), limit_picklist]
+            .spacing(8)
+            .align_y(Alignment::Center),
+    ]
+    .spacing(8);
+    if let Some(slider) = limit_slider {
+        col = col.push(slider);
+    }
+    col.push(usage_text).into()
+}
+
+fn depth_export_column<'a>(cfg: heatmap::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let export = cfg.export;
+    let on_change = move |export| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Heatmap(heatmap::Config { export, ..cfg }),
+            false,
+        )
+    };
+
+    let window_slider = classic_slider_row(
+        text("Last"),
+        slider(1..=240, export.window_mins, move |window_mins| {
+            on_change(heatmap::DepthExport {
+                window_mins,
+                ..export
+            })
+        })
+        .into(),
+        Some(text(format!("{} min", export.window_mins)).size(crate::style::text_size::EMPHASIS)),
+    );
+
+    let interval_slider = classic_slider_row(
+        text("Sample every"),
+        slider(1..=60, export.interval_secs, move |interval_secs| {
+            on_change(heatmap::DepthExport {
+                interval_secs,
+                ..export
+            })
+        })
+        .into(),
+        Some(text(format!("{}s", export.interval_secs)).size(crate::style::text_size::EMPHASIS)),
+    );
+
+    let export_button = tooltip(
+        button(text("Export CSV")).on_press(Message::PaneEvent(pane, Event::ExportDepth)),
+        Some("Price levels as rows, sample times as columns\nBids positive, asks negative"),
+        TooltipPosition::Top,
+    );
+
+    column![
+        text("Depth export").size(crate::style::text_size::SECTION),
+        window_slider,
+        interval_slider,
+        row![space::horizontal(), export_button],
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn timelapse_column<'a>(
+    cfg: heatmap::Config,
+    pane: pane_grid::Pane,
+    recording: bool,
+) -> Element<'a, Message> {
+    let timelapse = cfg.timelapse;
+    let on_change = move |timelapse| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Heatmap(heatmap::Config { timelapse, ..cfg }),
+            false,
+        )
+    };
+
+    let interval_slider = classic_slider_row(
+        text("Capture every"),
+        slider(1..=60, timelapse.interval_secs, move |interval_secs| {
+            on_change(TimelapseConfig {
+                interval_secs,
+                ..timelapse
+            })
+        })
+        .into(),
+        Some(text(format!("{}s", timelapse.interval_secs)).size(crate::style::text_size::EMPHASIS)),
+    );
+
+    let fps_slider = classic_slider_row(
+        text("Playback"),
+        slider(1..=30, timelapse.fps, move |fps| {
+            on_change(TimelapseConfig { fps, ..timelapse })
+        })
+        .into(),
+        Some(text(format!("{} fps", timelapse.fps)).size(crate::style::text_size::EMPHASIS)),
+    );
+
+    let record_button = tooltip(
+        button(text(if recording { "Stop & save" } else { "Start" }))
+            .on_press(Message::PaneEvent(pane, Event::ToggleTimelapse)),
+        Some("Saved as an animated PNG in the exports folder"),
+        TooltipPosition::Top,
+    );
+
+    column![
+        text("Time-lapse").size(crate::style::text_size::SECTION),
+        interval_slider,
+        fps_slider,
+        row![space::horizontal(), record_button],
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Shared by the Time&Sales and Heatmap settings, `on_change` wraps it in the pane's config
+fn trade_sound_column<'a>(
+    sound: TradeSound,
+    on_change: impl Fn(TradeSound) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enable_checkbox = checkbox(sound.enabled)
+        .label("Play sounds for large trades")
+        .on_toggle(move |enabled| on_change(TradeSound { enabled, ..sound }));
+
+    let mut col = column![
+        row![
+            text("Sound alerts").size(crate::style::text_size::SECTION),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Played at the volume set in the audio settings"),
+                TooltipPosition::Top,
+            )
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        enable_checkbox,
+    ]
+    .spacing(8);
+
+    if !sound.enabled {
+        return col.into();
+    }
+
+    let trade_slider = labeled_slider(
+        "Trade",
+        10_000.0..=2_000_000.0,
+        sound.trade_notional,
+        move |trade_notional| {
+            on_change(TradeSound {
+                trade_notional,
+                ..sound
+            })
+        },
+        |value| format!(">${}", format_with_commas(*value as f64)),
+        Some(10_000.0),
+    );
+    col = col.push(trade_slider);
+
+    let burst_checkbox = checkbox(sound.burst_notional.is_some())
+        .label("Bursts of one-sided flow")
+        .on_toggle(move |value| {
+            on_change(TradeSound {
+                burst_notional: if value {
+                    TradeSound::default().burst_notional
+                } else {
+                    None
+                },
+                ..sound
+            })
+        });
+    col = col.push(
+        row![
+            burst_checkbox,
+            tooltip(
+                button("i").style(style::button::info),
+                Some(
+                    "Liquidations aren't streamed, this plays a harder sound when \
+                 one side takes this much within a second, as a liquidation cluster would"
+                ),
+                TooltipPosition::Top,
+            )
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+    );
+
+    if let Some(burst_notional) = sound.burst_notional {
+        col = col.push(labeled_slider(
+            "Within 1s",
+            100_000.0..=20_000_000.0,
+            burst_notional,
+            move |value| {
+                on_change(TradeSound {
+                    burst_notional: Some(value),
+                    ..sound
+                })
+            },
+            |value| format!(">${}", format_with_commas(*value as f64)),
+            Some(100_000.0),
+        ));
+    }
+
+    col.into()
+}
+
 pub fn timesales_cfg_view<'a>(
     cfg: timeandsales::Config,
     pane: pane_grid::Pane,
@@ -425,11 +841,63 @@ pub fn timesales_cfg_view<'a>(
             Some(500.0),
         );
 
-        column![
+        let tiers_checkbox = checkbox(cfg.size_tiers.is_some())
+            .label("Highlight size tiers")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        size_tiers: value.then_some(timeandsales::Config::DEFAULT_SIZE_TIER),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut col = column![
             text("Size filter").size(crate::style::text_size::SECTION),
-            slider
+            slider,
+            row![
+                tiers_checkbox,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Colors trades past the set size, and stronger again\nat 10x and 100x of it."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4),
         ]
-        .spacing(8)
+        .spacing(8);
+
+        if let Some(smallest) = cfg.size_tiers {
+            col = col.push(labeled_slider(
+                "Tiers",
+                1_000.0..=1_000_000.0,
+                smallest,
+                move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            size_tiers: Some(value),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+                |value| {
+                    SIZE_TIER_MULTIPLES
+                        .iter()
+                        .map(|multiple| {
+                            format!(">${}", abbr_large_numbers(f64::from(*value * multiple)))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                },
+                Some(1_000.0),
+            ));
+        }
+        col
     };
 
     let retention_minutes = (cfg.trade_retention.as_secs_f32() / 60.0).max(1.0);
@@ -468,89 +936,238 @@ pub fn timesales_cfg_view<'a>(
         ]
         .spacing(4)
         .align_y(Alignment::Center),
-        retention_slider
-    ]
-    .spacing(8);
-
-    let stacked_bar: Element<_> = {
-        let is_shown = cfg.stacked_bar.is_some();
-
-        let enable_checkbox = checkbox(is_shown).label("Show stacked bar").on_toggle({
-            move |value| {
-                let current_ratio = cfg.stacked_bar.map(|h| h.ratio()).unwrap_or_default();
-                Message::VisualConfigChanged(
-                    pane,
-                    VisualConfig::TimeAndSales(timeandsales::Config {
-                        stacked_bar: if value {
-                            Some(StackedBar::Compact(current_ratio))
-                        } else {
-                            None
-                        },
-                        ..cfg
-                    }),
-                    false,
-                )
-            }
-        });
-
-        let controls: Option<Element<_>> = cfg.stacked_bar.map(|hist| {
-            let ratio = hist.ratio();
-            let is_compact = matches!(hist, StackedBar::Compact(_));
-
-            let compact = radio("Compact", true, Some(is_compact), {
-                move |_v| {
+        retention_slider,
+        row![
+            checkbox(cfg.pause_on_hover)
+                .label("Pause on hover")
+                .on_toggle(move |value| {
                     Message::VisualConfigChanged(
                         pane,
                         VisualConfig::TimeAndSales(timeandsales::Config {
-                            stacked_bar: Some(StackedBar::Compact(ratio)),
+                            pause_on_hover: value,
                             ..cfg
                         }),
                         false,
                     )
-                }
-            })
-            .spacing(4);
+                }),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("New trades are held back while the cursor is over the tape.\nClicking it pauses as well, click the bar on top to jump back to live."),
+                TooltipPosition::Top,
+            )
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(8);
 
-            let full = radio("Full", false, Some(is_compact), {
-                move |_v| {
+    let columns_column = {
+        let column_checkbox = |column: TapeColumn| {
+            checkbox(cfg.columns.is_shown(column))
+                .label(column.to_string())
+                .on_toggle(move |shown| {
                     Message::VisualConfigChanged(
                         pane,
                         VisualConfig::TimeAndSales(timeandsales::Config {
-                            stacked_bar: Some(StackedBar::Full(ratio)),
+                            columns: cfg.columns.with(column, shown),
                             ..cfg
                         }),
                         false,
                     )
-                }
-            })
+                })
+                .width(Length::Fill)
+        };
+
+        let checkboxes =
+            iced::widget::Column::with_children(TapeColumn::ALL.chunks(2).map(|pair| {
+                iced::widget::Row::with_children(
+                    pair.iter().map(|column| column_checkbox(*column).into()),
+                )
+                .spacing(8)
+                .into()
+            }))
             .spacing(4);
 
-            let metric_picklist = pick_list(StackedBarRatio::ALL, Some(ratio), move |new_ratio| {
-                let new_hist = Some(match cfg.stacked_bar {
-                    Some(StackedBar::Full(_)) => StackedBar::Full(new_ratio),
-                    _ => StackedBar::Compact(new_ratio),
-                });
+        let footer_checkbox = checkbox(cfg.footer_secs.is_some())
+            .label("Show buy/sell footer")
+            .on_toggle(move |value| {
                 Message::VisualConfigChanged(
                     pane,
                     VisualConfig::TimeAndSales(timeandsales::Config {
-                        stacked_bar: new_hist,
+                        footer_secs: value.then_some(timeandsales::Config::DEFAULT_FOOTER_SECS),
                         ..cfg
                     }),
                     false,
                 )
             });
 
-            column![
-                iced::widget::rule::horizontal(1),
-                text("Mode").size(crate::style::text_size::BODY),
-                row![compact, full].spacing(12),
-                text("Metric").size(crate::style::text_size::BODY),
-                metric_picklist,
+        let mut col = column![
+            text("Columns").size(crate::style::text_size::SECTION),
+            checkboxes,
+            row![
+                footer_checkbox,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Buy and sell volume of the last seconds under the tape,\nwith their ratio and the difference between them."),
+                    TooltipPosition::Top,
+                )
             ]
-            .spacing(8)
-            .into()
-        });
-
+            .align_y(Alignment::Center)
+            .spacing(4),
+        ]
+        .spacing(8);
+
+        if let Some(secs) = cfg.footer_secs {
+            col = col.push(classic_slider_row(
+                text("Over the last"),
+                slider(5..=300, secs, move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            footer_secs: Some(value),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                })
+                .step(5u16)
+                .into(),
+                Some(text(format!("{secs}s")).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+        col
+    };
+
+    let aggregation_column = {
+        let aggregation_checkbox = checkbox(cfg.aggregation_ms.is_some())
+            .label("Aggregate trades")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        aggregation_ms: value
+                            .then_some(timeandsales::Config::DEFAULT_AGGREGATION_MS),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut col = column![
+            text("Aggregation").size(crate::style::text_size::SECTION),
+            row![
+                aggregation_checkbox,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Adds trades at the same price and side to the row before\nwhile they come within the window, like an exchange's aggregated tape.\nApplies to trades received from now on."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4),
+        ]
+        .spacing(8);
+
+        if let Some(window_ms) = cfg.aggregation_ms {
+            col = col.push(classic_slider_row(
+                text("Window"),
+                slider(10..=1000, window_ms, move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            aggregation_ms: Some(value),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                })
+                .step(10u16)
+                .into(),
+                Some(text(format!("{window_ms}ms")).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+        col
+    };
+
+    let stacked_bar: Element<_> = {
+        let is_shown = cfg.stacked_bar.is_some();
+
+        let enable_checkbox = checkbox(is_shown).label("Show stacked bar").on_toggle({
+            move |value| {
+                let current_ratio = cfg.stacked_bar.map(|h| h.ratio()).unwrap_or_default();
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        stacked_bar: if value {
+                            Some(StackedBar::Compact(current_ratio))
+                        } else {
+                            None
+                        },
+                        ..cfg
+                    }),
+                    false,
+                )
+            }
+        });
+
+        let controls: Option<Element<_>> = cfg.stacked_bar.map(|hist| {
+            let ratio = hist.ratio();
+            let is_compact = matches!(hist, StackedBar::Compact(_));
+
+            let compact = radio("Compact", true, Some(is_compact), {
+                move |_v| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            stacked_bar: Some(StackedBar::Compact(ratio)),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }
+            })
+            .spacing(4);
+
+            let full = radio("Full", false, Some(is_compact), {
+                move |_v| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            stacked_bar: Some(StackedBar::Full(ratio)),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }
+            })
+            .spacing(4);
+
+            let metric_picklist = pick_list(StackedBarRatio::ALL, Some(ratio), move |new_ratio| {
+                let new_hist = Some(match cfg.stacked_bar {
+                    Some(StackedBar::Full(_)) => StackedBar::Full(new_ratio),
+                    _ => StackedBar::Compact(new_ratio),
+                });
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        stacked_bar: new_hist,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+            column![
+                iced::widget::rule::horizontal(1),
+                text("Mode").size(crate::style::text_size::BODY),
+                row![compact, full].spacing(12),
+                text("Metric").size(crate::style::text_size::BODY),
+                metric_picklist,
+            ]
+            .spacing(8)
+            .into()
+        });
+
         let mut inner = column![enable_checkbox]
             .width(Length::Fill)
             .padding(4)
@@ -566,10 +1183,57 @@ pub fn timesales_cfg_view<'a>(
             .into()
     };
 
+    let filtered_trades_column = {
+        let mark_on_chart = checkbox(cfg.mark_on_chart)
+            .label("Mark on linked charts")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        mark_on_chart: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let export_button = tooltip(
+            button(text("Export CSV")).on_press(Message::PaneEvent(pane, Event::ExportTape)),
+            Some("Trades passing the size filter, oldest first"),
+            TooltipPosition::Top,
+        );
+
+        column![
+            text("Filtered trades").size(crate::style::text_size::SECTION),
+            row![
+                mark_on_chart,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Marks the trades passing the size filter on the kline and heatmap\ncharts in the same link group."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4),
+            row![space::horizontal(), export_button],
+        ]
+        .spacing(8)
+    };
+
     let content = split_column![
         trade_size_column,
+        filtered_trades_column,
+        columns_column,
+        aggregation_column,
         history_column,
         stacked_bar,
+        trade_sound_column(cfg.sound, move |sound| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::TimeAndSales(timeandsales::Config { sound, ..cfg }),
+                false,
+            )
+        }),
         row![space::horizontal(), sync_all_button(pane, VisualConfig::TimeAndSales(cfg))],
         ; spacing = 12, align_x = Alignment::Start
     ];
@@ -583,13 +1247,162 @@ pub fn comparison_cfg_view<'a>(
 ) -> Element<'a, Message> {
     let series = &chart.series;
     let series_editor = &chart.series_editor;
+    let cfg = chart.serializable_config();
+
+    let mode_column = {
+        let modes = ComparisonMode::ALL
+            .iter()
+            .fold(column![].spacing(4), |col, &mode| {
+                let cfg = cfg.clone();
+                col.push(
+                    radio(mode.to_string(), mode, Some(cfg.mode), move |mode| {
+                        Message::VisualConfigChanged(
+                            pane,
+                            VisualConfig::Comparison(comparison::Config {
+                                mode,
+                                ..cfg.clone()
+                            }),
+                            false,
+                        )
+                    })
+                    .spacing(4),
+                )
+            });
 
-    let content = column![series_editor.view(series).map(move |msg| {
-        Message::PaneEvent(
-            pane,
-            Event::ComparisonChartInteraction(crate::chart::comparison::Message::Editor(msg)),
-        )
-    })];
+        let mut col = column![
+            text("Display").size(crate::style::text_size::SECTION),
+            modes
+        ]
+        .spacing(8);
+
+        if cfg.mode == ComparisonMode::Lines {
+            let scales =
+                Normalization::ALL
+                    .iter()
+                    .fold(column![].spacing(4), |col, &normalization| {
+                        let cfg = cfg.clone();
+                        col.push(
+                            radio(
+                                normalization.to_string(),
+                                normalization,
+                                Some(cfg.normalization),
+                                move |normalization| {
+                                    Message::VisualConfigChanged(
+                                        pane,
+                                        VisualConfig::Comparison(comparison::Config {
+                                            normalization,
+                                            ..cfg.clone()
+                                        }),
+                                        false,
+                                    )
+                                },
+                            )
+                            .spacing(4),
+                        )
+                    });
+
+            col = col.push(
+                column![
+                    text("Scale").size(crate::style::text_size::SECTION),
+                    scales,
+                    text("Double click the chart to rebase the lines there, again to reset")
+                        .size(crate::style::text_size::SMALL),
+                ]
+                .spacing(8),
+            );
+        }
+
+        if cfg.mode == ComparisonMode::Correlation {
+            let window = cfg.correlation_window;
+            let cfg = cfg.clone();
+
+            col = col.push(classic_slider_row(
+                text("Window"),
+                slider(CORRELATION_WINDOW_RANGE, window, move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Comparison(comparison::Config {
+                            correlation_window: value,
+                            ..cfg.clone()
+                        }),
+                        false,
+                    )
+                })
+                .step(10u16)
+                .into(),
+                Some(text(format!("{window} bars")).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+
+        let export_button = tooltip(
+            button(text("Export CSV")).on_press(Message::PaneEvent(
+                pane,
+                Event::ComparisonChartInteraction(crate::chart::comparison::Message::ExportCsv),
+            )),
+            Some("Closes of every series and the derived lines, a row per bar"),
+            TooltipPosition::Top,
+        );
+
+        let mut export = column![
+            text("Export").size(crate::style::text_size::SECTION),
+            export_button,
+        ]
+        .spacing(8);
+
+        if cfg.mode == ComparisonMode::Lines {
+            export = export.push(
+                text("Right click the chart to copy the values under the cursor")
+                    .size(crate::style::text_size::SMALL),
+            );
+        }
+
+        col.push(export)
+    };
+
+    let content = split_column![
+        mode_column,
+        series_editor
+            .view(series, chart.derived(), chart.price_axis())
+            .map(move |msg| {
+            Message::PaneEvent(
+                pane,
+                Event::ComparisonChartInteraction(crate::chart::comparison::Message::Editor(msg)),
+            )
+        }),
+        ; spacing = 12, align_x = Alignment::Start
+    ];
+
+    cfg_view_container(320, content)
+}
+
+pub fn spread_cfg_view<'a>(
+    pane: pane_grid::Pane,
+    cfg: data::chart::spread::Config,
+) -> Element<'a, Message> {
+    let mode_column = {
+        let modes = SpreadMode::ALL
+            .iter()
+            .fold(column![].spacing(4), |col, &mode| {
+                col.push(
+                    radio(mode.to_string(), mode, Some(cfg.mode), move |mode| {
+                        Message::VisualConfigChanged(
+                            pane,
+                            VisualConfig::Spread(data::chart::spread::Config { mode }),
+                            false,
+                        )
+                    })
+                    .spacing(4),
+                )
+            });
+
+        column![text("Spread").size(crate::style::text_size::SECTION), modes].spacing(8)
+    };
+
+    let content = split_column![
+        mode_column,
+        row![space::horizontal(), sync_all_button(pane, VisualConfig::Spread(cfg))],
+        ; spacing = 12, align_x = Alignment::Start
+    ];
 
     cfg_view_container(320, content)
 }
@@ -600,6 +1413,8 @@ pub fn kline_cfg_view<'a>(
     kind: &'a KlineChartKind,
     pane: pane_grid::Pane,
     basis: data::chart::Basis,
+    mark_price_supported: bool,
+    profile_ranges: &'a [ProfileRange],
 ) -> Element<'a, Message> {
     let display_readout_section = {
         let data_labels_checkbox = tooltip(
@@ -610,6 +1425,7 @@ pub fn kline_cfg_view<'a>(
                         pane,
                         VisualConfig::Kline(data::chart::kline::Config {
                             data_labels_always_visible: value,
+                            ..cfg
                         }),
                         false,
                     )
@@ -624,10 +1440,365 @@ pub fn kline_cfg_view<'a>(
         .spacing(8)
     };
 
+    let autoscale_section = {
+        let padding_slider = classic_slider_row(
+            text("Padding"),
+            slider(0.0..=25.0, cfg.autoscale_padding, move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Kline(data::chart::kline::Config {
+                        autoscale_padding: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            })
+            .step(1.0)
+            .into(),
+            Some(
+                text(format!("{:.0}%", cfg.autoscale_padding))
+                    .size(crate::style::text_size::EMPHASIS),
+            ),
+        );
+
+        let clip_checkbox = tooltip(
+            checkbox(cfg.clip_outliers)
+                .label("Ignore outlier wicks")
+                .on_toggle(move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config {
+                            clip_outliers: value,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }),
+            Some("Leave the top and bottom 0.5% of wicks out of the fitted range"),
+            TooltipPosition::Top,
+        );
+
+        column![
+            text("Autoscale").size(crate::style::text_size::SECTION),
+            padding_slider,
+            clip_checkbox,
+        ]
+        .spacing(8)
+    };
+
+    let overlays_section = {
+        let mark_price_checkbox = checkbox(cfg.show_mark_price).label("Mark & index price");
+
+        let mark_price_checkbox = if mark_price_supported && basis.is_time() {
+            tooltip(
+                mark_price_checkbox.on_toggle(move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config {
+                            show_mark_price: value,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }),
+                Some("Lines where the venue marks the contract and where its spot index trades"),
+                TooltipPosition::Top,
+            )
+        } else {
+            tooltip(
+                mark_price_checkbox,
+                Some("Only streamed for Binance and Bybit perpetuals on time-based charts"),
+                TooltipPosition::Top,
+            )
+        };
+
+        let profile_checkbox = tooltip(
+            checkbox(cfg.volume_profile.is_some())
+                .label("Volume profile")
+                .on_toggle(move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config {
+                            volume_profile: value.then(Default::default),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }),
+            Some("Volume traded at each price, from the candles' trades"),
+            TooltipPosition::Top,
+        );
+
+        let mut section = column![
+            text("Overlays").size(crate::style::text_size::SECTION),
+            mark_price_checkbox,
+            profile_checkbox,
+        ]
+        .spacing(8);
+
+        if let Some(profile) = cfg.volume_profile {
+            let with_profile = move |profile: VolumeProfileConfig| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Kline(data::chart::kline::Config {
+                        volume_profile: Some(profile),
+                        ..cfg
+                    }),
+                    false,
+                )
+            };
+
+            let session = match profile.scope {
+                ProfileScope::Sessions(window) => window,
+                _ => SessionWindow::default(),
+            };
+            let scope_picklist = pick_list(
+                vec![
+                    ProfileScope::VisibleRange,
+                    ProfileScope::Sessions(session),
+                    ProfileScope::FixedRanges,
+                ],
+                Some(profile.scope),
+                move |scope| with_profile(VolumeProfileConfig { scope, ..profile }),
+            );
+
+            let value_area_slider = classic_slider_row(
+                text("Value area"),
+                slider(
+                    50.0..=95.0,
+                    f32::from(profile.value_area_pct),
+                    move |value| {
+                        with_profile(VolumeProfileConfig {
+                            value_area_pct: value as u8,
+                            ..profile
+                        })
+                    },
+                )
+                .step(1.0)
+                .into(),
+                Some(
+                    text(format!("{}%", profile.value_area_pct))
+                        .size(crate::style::text_size::EMPHASIS),
+                ),
+            );
+
+            section = section
+                .push(
+                    row![text("Scope"), scope_picklist]
+                        .spacing(8)
+                        .align_y(Alignment::Center),
+                )
+                .push(value_area_slider);
+
+            match profile.scope {
+                ProfileScope::VisibleRange => {
+                    let width_slider = classic_slider_row(
+                        text("Width"),
+                        slider(0.1..=0.5, profile.width, move |value| {
+                            with_profile(VolumeProfileConfig {
+                                width: value,
+                                ..profile
+                            })
+                        })
+                        .step(0.05)
+                        .into(),
+                        Some(
+                            text(format!("{:.0}%", profile.width * 100.0))
+                                .size(crate::style::text_size::EMPHASIS),
+                        ),
+                    );
+
+                    section = section.push(width_slider);
+                }
+                ProfileScope::Sessions(window) => {
+                    let minute_slider = |label: &'static str,
+                                         minute: u16,
+                                         on_change: fn(SessionWindow, u16) -> SessionWindow| {
+                        classic_slider_row(
+                            text(label),
+                            slider(0.0..=1425.0, f32::from(minute), move |value| {
+                                with_profile(VolumeProfileConfig {
+                                    scope: ProfileScope::Sessions(on_change(window, value as u16)),
+                                    ..profile
+                                })
+                            })
+                            .step(15.0)
+                            .into(),
+                            Some(
+                                text(format!("{:02}:{:02}", minute / 60, minute % 60))
+                                    .size(crate::style::text_size::EMPHASIS),
+                            ),
+                        )
+                    };
+
+                    let hint = if window.is_utc_day() {
+                        "Whole UTC days"
+                    } else {
+                        "Times in UTC, a start after the end runs past midnight"
+                    };
+
+                    section = section
+                        .push(minute_slider(
+                            "Start",
+                            window.start_minute,
+                            |window, minute| SessionWindow {
+                                start_minute: minute,
+                                ..window
+                            },
+                        ))
+                        .push(minute_slider("End", window.end_minute, |window, minute| {
+                            SessionWindow {
+                                end_minute: minute,
+                                ..window
+                            }
+                        }))
+                        .push(text(hint).size(crate::style::text_size::SMALL));
+                }
+                ProfileScope::FixedRanges => {
+                    let on_ranges = move |ranges: Vec<ProfileRange>| {
+                        Message::PaneEvent(pane, Event::ProfileRangesChanged(ranges))
+                    };
+                    let format_time =
+                        |time: exchange::UnixMs| time.format_utc("%m-%d %H:%M").unwrap_or_default();
+
+                    let mut ranges = column![
+                        text("Press Alt, then click the first and last candle")
+                            .size(crate::style::text_size::SMALL)
+                    ]
+                    .spacing(4);
+
+                    for (index, range) in profile_ranges.iter().enumerate() {
+                        let mut remaining = profile_ranges.to_vec();
+                        remaining.remove(index);
+
+                        ranges = ranges.push(
+                            row![
+                                text(format!(
+                                    "{} → {} UTC",
+                                    format_time(range.from),
+                                    format_time(range.to)
+                                )),
+                                space::horizontal(),
+                                button(icon_text(Icon::TrashBin, 12))
+                                    .style(|theme, status| {
+                                        style::button::transparent(theme, status, false)
+                                    })
+                                    .on_press(on_ranges(remaining)),
+                            ]
+                            .align_y(Alignment::Center),
+                        );
+                    }
+
+                    if !profile_ranges.is_empty() {
+                        ranges = ranges.push(button(text("Clear all")).on_press(on_ranges(vec![])));
+                    }
+
+                    section = section.push(ranges);
+                }
+            }
+        }
+
+        let big_trades_checkbox = tooltip(
+            checkbox(cfg.big_trades.is_some())
+                .label("Big trades")
+                .on_toggle(move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config {
+                            big_trades: value.then(Default::default),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }),
+            Some("Single trades above a notional, hover a dot for its size and time"),
+            TooltipPosition::Top,
+        );
+        section = section.push(big_trades_checkbox);
+
+        if let Some(big_trades) = cfg.big_trades {
+            let with_big_trades = move |big_trades: BigTrades| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Kline(data::chart::kline::Config {
+                        big_trades: Some(big_trades),
+                        ..cfg
+                    }),
+                    false,
+                )
+            };
+
+            let notional_slider = labeled_slider(
+                "Notional",
+                10_000.0..=5_000_000.0,
+                big_trades.min_notional,
+                move |min_notional| {
+                    with_big_trades(BigTrades {
+                        min_notional,
+                        ..big_trades
+                    })
+                },
+                |value| format!(">${}", format_with_commas(*value as f64)),
+                Some(10_000.0),
+            );
+
+            section = section
+                .push(notional_slider)
+                .push(
+                    checkbox(big_trades.size_scaled)
+                        .label("Scale dots by size")
+                        .on_toggle(move |size_scaled| {
+                            with_big_trades(BigTrades {
+                                size_scaled,
+                                ..big_trades
+                            })
+                        }),
+                )
+                .push(
+                    checkbox(big_trades.labels)
+                        .label("Show notional labels")
+                        .on_toggle(move |labels| {
+                            with_big_trades(BigTrades {
+                                labels,
+                                ..big_trades
+                            })
+                        }),
+                );
+        }
+
+        section
+    };
+
     let content = match kind {
         KlineChartKind::Candles => {
+            let style_section = {
+                let style_picklist = pick_list(
+                    CandleStyle::ALL,
+                    Some(cfg.candle_style),
+                    move |candle_style| {
+                        Message::VisualConfigChanged(
+                            pane,
+                            VisualConfig::Kline(data::chart::kline::Config {
+                                candle_style,
+                                ..cfg
+                            }),
+                            false,
+                        )
+                    },
+                );
+
+                column![
+                    text("Style").size(crate::style::text_size::SECTION),
+                    style_picklist,
+                ]
+                .spacing(8)
+            };
+
             split_column![
+                style_section,
                 display_readout_section,
+                autoscale_section,
+                overlays_section,
                 row![
                     space::horizontal(),
                     sync_all_button(pane, VisualConfig::Kline(cfg))
@@ -676,6 +1847,67 @@ pub fn kline_cfg_view<'a>(
                 }
             };
 
+            let footer_section = {
+                let with_footer = move |summary_footer: Option<SummaryFooter>| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config {
+                            summary_footer,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                };
+
+                let mut section = column![
+                    text("Summary footer").size(crate::style::text_size::SECTION),
+                    tooltip(
+                        checkbox(cfg.summary_footer.is_some())
+                            .label("Show under the candles")
+                            .on_toggle(move |value| with_footer(value.then(Default::default))),
+                        Some("Delta, volume and trade count of each candle, along the bottom"),
+                        TooltipPosition::Top,
+                    ),
+                ]
+                .spacing(8);
+
+                if let Some(footer) = cfg.summary_footer {
+                    let row_checkbox =
+                        |label: &'static str,
+                         shown: bool,
+                         set: fn(SummaryFooter, bool) -> SummaryFooter| {
+                            checkbox(shown)
+                                .label(label)
+                                .on_toggle(move |value| with_footer(Some(set(footer, value))))
+                        };
+
+                    section = section.push(
+                        column![
+                            row_checkbox("Delta", footer.delta, |footer, delta| SummaryFooter {
+                                delta,
+                                ..footer
+                            }),
+                            row_checkbox("Delta %", footer.delta_pct, |footer, delta_pct| {
+                                SummaryFooter {
+                                    delta_pct,
+                                    ..footer
+                                }
+                            }),
+                            row_checkbox("Volume", footer.volume, |footer, volume| {
+                                SummaryFooter { volume, ..footer }
+                            }),
+                            row_checkbox("Trade count", footer.trades, |footer, trades| {
+                                SummaryFooter { trades, ..footer }
+                            }),
+                        ]
+                        .spacing(4)
+                        .padding(iced::padding::left(16)),
+                    );
+                }
+
+                section
+            };
+
             let study_cfg = study_config.view(studies, basis).map(move |msg| {
                 Message::PaneEvent(
                     pane,
@@ -685,8 +1917,11 @@ pub fn kline_cfg_view<'a>(
 
             split_column![
                 display_readout_section,
+                autoscale_section,
+                overlays_section,
                 column![text("Cluster type").size(crate::style::text_size::SECTION), cluster_picklist].spacing(8),
                 column![text("Cluster scaling").size(crate::style::text_size::SECTION), scaling].spacing(8),
+                footer_section,
                 column![text("Studies").size(crate::style::text_size::SECTION), study_cfg].spacing(8),
                 row![
                     space::horizontal(),
@@ -728,10 +1963,61 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
                 )
             });
 
+        let cumulative = checkbox(cfg.show_cumulative)
+            .label("Show cumulative size")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_cumulative: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let depth_bars = checkbox(cfg.show_depth_bars)
+            .label("Show depth bars")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_depth_bars: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let session_volume = checkbox(cfg.show_session_volume)
+            .label("Show session volume")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_session_volume: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
         column![
             text("Display Options").size(crate::style::text_size::SECTION),
             column![
                 spread,
+                cumulative,
+                depth_bars,
+                row![
+                    session_volume,
+                    tooltip(
+                        button("i").style(style::button::info),
+                        Some("Volume traded at each level since 00:00 UTC,\nbuys and sells side by side.\nCounted from when the pane was opened."),
+                        TooltipPosition::Top,
+                    )
+                ]
+                .align_y(Alignment::Center)
+                .spacing(4),
                 row![
                     chase_tracker,
                     tooltip(
@@ -774,15 +2060,239 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
         )
     };
 
+    let size_column = {
+        let size_picklist = pick_list(
+            ladder::SizeDisplay::options(cfg.size_display),
+            Some(cfg.size_display),
+            move |size_display| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        size_display,
+                        ..cfg
+                    }),
+                    false,
+                )
+            },
+        );
+
+        let mut size_column = column![
+            text("Order Sizes").size(crate::style::text_size::SECTION),
+            size_picklist,
+        ]
+        .spacing(8);
+
+        if let ladder::SizeDisplay::PercentOfTop(levels) = cfg.size_display {
+            let slider_ui = slider(5.0..=100.0, levels as f32, move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        size_display: ladder::SizeDisplay::PercentOfTop(value as usize),
+                        ..cfg
+                    }),
+                    false,
+                )
+            })
+            .step(5.0);
+
+            size_column = size_column.push(classic_slider_row(
+                text("Levels per side"),
+                slider_ui.into(),
+                Some(text(levels.to_string()).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+
+        size_column
+    };
+
     let history_column = column![
         text("History").size(crate::style::text_size::SECTION),
         retention_slider
     ]
     .spacing(8);
 
+    let viewport_column = {
+        let on_change = move |recenter| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Ladder(ladder::Config { recenter, ..cfg }),
+                false,
+            )
+        };
+
+        let recenter_picklist = pick_list(
+            ladder::RecenterMode::options(cfg.recenter),
+            Some(cfg.recenter),
+            on_change,
+        );
+
+        let mut col = column![
+            text("Viewport").size(crate::style::text_size::SECTION),
+            row![
+                text("When scrolled"),
+                space::horizontal(),
+                recenter_picklist
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(8);
+
+        if let ladder::RecenterMode::Delayed { secs } = cfg.recenter {
+            col = col.push(classic_slider_row(
+                text("Recenter after"),
+                slider(1..=60, secs, move |secs| {
+                    on_change(ladder::RecenterMode::Delayed { secs })
+                })
+                .into(),
+                Some(text(format!("{secs}s")).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+        col
+    };
+
+    let refill_column = {
+        let refill_checkbox = checkbox(cfg.refill_ratio.is_some())
+            .label("Flag refilling levels")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        refill_ratio: value.then_some(ladder::RefillTracker::DEFAULT_RATIO),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut col = column![
+            text("Icebergs").size(crate::style::text_size::SECTION),
+            row![
+                refill_checkbox,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Marks levels that keep showing size after being traded into,\nonce the volume traded there is the set multiple of the most they showed."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4)
+        ]
+        .spacing(8);
+
+        if let Some(ratio) = cfg.refill_ratio {
+            col = col.push(classic_slider_row(
+                text("Traded vs shown"),
+                slider(1.5..=10.0, ratio, move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Ladder(ladder::Config {
+                            refill_ratio: Some(value),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                })
+                .step(0.5)
+                .into(),
+                Some(text(format!("{ratio:.1}x")).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+        col
+    };
+
+    let header_column = {
+        let header_checkbox = checkbox(cfg.header_levels.is_some())
+            .label("Show imbalance & spread header")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        header_levels: value.then_some(ladder::Config::DEFAULT_HEADER_LEVELS),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut col = column![
+            text("Header").size(crate::style::text_size::SECTION),
+            row![
+                header_checkbox,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some(
+                        "Bid vs ask size resting over the closest levels on each side,
+the spread in ticks and basis points, and its average over the last minute."
+                    ),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4)
+        ]
+        .spacing(8);
+
+        if let Some(levels) = cfg.header_levels {
+            col = col.push(classic_slider_row(
+                text("Imbalance levels"),
+                slider(1.0..=50.0, levels as f32, move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Ladder(ladder::Config {
+                            header_levels: Some(value as usize),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                })
+                .step(1.0)
+                .into(),
+                Some(text(levels.to_string()).size(crate::style::text_size::EMPHASIS)),
+            ));
+        }
+        col
+    };
+
+    let order_entry_column = {
+        let order_entry =
+            checkbox(cfg.order_entry)
+                .label("Click to trade")
+                .on_toggle(move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Ladder(ladder::Config {
+                            order_entry: value,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                });
+
+        column![
+            text("Order Entry").size(crate::style::text_size::SECTION),
+            row![
+                order_entry,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Left-click the bid or ask size column to place a limit order,\nright-click a working order to cancel it.\nNeeds an API key and order size set in Settings > Trading."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4)
+        ]
+        .spacing(8)
+    };
+
     let content = split_column![
         display_options,
+        header_column,
+        viewport_column,
+        size_column,
+        refill_column,
         history_column,
+        order_entry_column,
         row![
             space::horizontal(),
             sync_all_button(pane, VisualConfig::Ladder(cfg))
@@ -793,6 +2303,102 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
     cfg_view_container(320, content)
 }
 
+pub fn appearance_cfg_view<'a>(
+    appearance: Appearance,
+    pane: pane_grid::Pane,
+    accents: &Accents,
+    ticker: Option<exchange::Ticker>,
+) -> Element<'a, Message> {
+    let on_change =
+        move |appearance| Message::PaneEvent(pane, Event::AppearanceChanged(appearance));
+
+    let swatch = |tint: Option<iced::Color>| {
+        let is_selected = appearance.tint == tint;
+
+        let content: Element<_> = match tint {
+            Some(color) => container(space().width(14).height(14))
+                .style(move |theme| style::colored_circle_container(theme, color))
+                .into(),
+            None => text("None").size(crate::style::text_size::SMALL).into(),
+        };
+
+        button(content)
+            .padding(4)
+            .style(move |theme, status| style::button::transparent(theme, status, is_selected))
+            .on_press(on_change(Appearance { tint, ..appearance }))
+    };
+
+    let tints = style::PANE_TINTS
+        .iter()
+        .fold(row![swatch(None)], |row, color| {
+            row.push(swatch(Some(*color)))
+        })
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+    let watermark = checkbox(appearance.watermark)
+        .label("Show ticker watermark")
+        .on_toggle(move |watermark| {
+            on_change(Appearance {
+                watermark,
+                ..appearance
+            })
+        });
+
+    let accent_row = |target: AccentTarget| {
+        let current = accents.get(target);
+
+        let swatch = |color: Option<iced::Color>| {
+            let content: Element<_> = match color {
+                Some(color) => container(space().width(14).height(14))
+                    .style(move |theme| style::colored_circle_container(theme, color))
+                    .into(),
+                None => text("None").size(crate::style::text_size::SMALL).into(),
+            };
+
+            button(content)
+                .padding(4)
+                .style(move |theme, status| {
+                    style::button::transparent(theme, status, current == color)
+                })
+                .on_press(Message::SetAccent(target, color))
+        };
+
+        style::PANE_TINTS
+            .iter()
+            .fold(row![swatch(None)], |row, color| {
+                row.push(swatch(Some(*color)))
+            })
+            .spacing(4)
+            .align_y(Alignment::Center)
+    };
+
+    let mut content = column![
+        text("Background").size(crate::style::text_size::SECTION),
+        tints,
+        watermark,
+    ]
+    .spacing(8);
+
+    if let Some(ticker) = ticker {
+        let (symbol, _) = ticker.display_symbol_and_type();
+
+        content = content.extend([
+            text("Accent").size(crate::style::text_size::SECTION).into(),
+            text(format!("All {} panes", ticker.exchange))
+                .size(crate::style::text_size::SMALL)
+                .into(),
+            accent_row(AccentTarget::Exchange(ticker.exchange)).into(),
+            text(format!("{symbol} on {} only", ticker.exchange))
+                .size(crate::style::text_size::SMALL)
+                .into(),
+            accent_row(AccentTarget::Ticker(ticker)).into(),
+        ]);
+    }
+
+    cfg_view_container(320, content)
+}
+
 fn sync_all_button<'a>(pane: pane_grid::Pane, config: VisualConfig) -> Element<'a, Message> {
     tooltip(
         button("Sync all").on_press(Message::VisualConfigChanged(pane, config, true)),
@@ -807,7 +2413,8 @@ pub mod study {
         style::{self, Icon, icon_text},
     };
     use data::chart::heatmap::{CLEANUP_THRESHOLD, HeatmapStudy, ProfileKind};
-    use data::chart::kline::FootprintStudy;
+    use data::chart::kline::{FlowAlertRules, FootprintStudy};
+    use data::chart::liquidation::LEVERAGE_TIERS;
     use iced::{
         Element, padding,
         widget::{button, checkbox, column, container, row, slider, space, text},
@@ -844,18 +2451,39 @@ pub mod study {
             on_change: impl Fn(Self) -> Message<Self> + Copy + 'a,
         ) -> Element<'a, Message<Self>> {
             match *self {
-                FootprintStudy::NPoC { lookback } => {
+                FootprintStudy::NPoC {
+                    lookback,
+                    unfinished_auctions,
+                } => {
                     let slider_ui = slider(10.0..=400.0, lookback as f32, move |new_value| {
                         on_change(FootprintStudy::NPoC {
                             lookback: new_value as usize,
+                            unfinished_auctions,
                         })
                     })
                     .step(10.0);
 
-                    column![text(format!("Lookback: {lookback} datapoints")), slider_ui]
-                        .padding(8)
-                        .spacing(4)
-                        .into()
+                    let lookback_ui =
+                        column![text(format!("Lookback: {lookback} datapoints")), slider_ui]
+                            .padding(8)
+                            .spacing(4);
+
+                    let auctions_ui = column![
+                        checkbox(unfinished_auctions)
+                            .label("Unfinished auctions")
+                            .on_toggle(move |unfinished_auctions| {
+                                on_change(FootprintStudy::NPoC {
+                                    lookback,
+                                    unfinished_auctions,
+                                })
+                            }),
+                        text("Two-sided trading at a candle's high or low, drawn until revisited")
+                            .size(crate::style::text_size::SMALL),
+                    ]
+                    .padding(8)
+                    .spacing(4);
+
+                    split_column![lookback_ui, auctions_ui].padding(4).into()
                 }
                 FootprintStudy::Imbalance {
                     threshold,
@@ -936,6 +2564,98 @@ pub mod study {
                         .padding(4)
                         .into()
                 }
+                FootprintStudy::FlowAlerts(rules) => {
+                    let stacked = {
+                        let levels = rules.stacked_levels.unwrap_or(3);
+
+                        let stacked_checkbox = checkbox(rules.stacked_levels.is_some())
+                            .label("Stacked imbalances")
+                            .on_toggle(move |is_enabled| {
+                                on_change(FootprintStudy::FlowAlerts(FlowAlertRules {
+                                    stacked_levels: is_enabled.then_some(levels),
+                                    ..rules
+                                }))
+                            });
+
+                        let threshold = rules.imbalance_threshold;
+                        let threshold_slider = column![
+                            text(format!("Ask:Bid threshold: {threshold}%")),
+                            slider(100.0..=800.0, threshold as f32, move |new_value| {
+                                on_change(FootprintStudy::FlowAlerts(FlowAlertRules {
+                                    imbalance_threshold: new_value as usize,
+                                    ..rules
+                                }))
+                            })
+                            .step(25.0)
+                        ]
+                        .spacing(2);
+
+                        if rules.stacked_levels.is_some() {
+                            let levels_slider = column![
+                                text(format!("At least {levels} levels in a row")),
+                                slider(2.0..=10.0, levels as f32, move |new_value| {
+                                    on_change(FootprintStudy::FlowAlerts(FlowAlertRules {
+                                        stacked_levels: Some(new_value as usize),
+                                        ..rules
+                                    }))
+                                })
+                                .step(1.0)
+                            ]
+                            .spacing(2);
+
+                            column![stacked_checkbox, levels_slider, threshold_slider]
+                                .padding(8)
+                                .spacing(8)
+                        } else {
+                            column![stacked_checkbox].padding(8)
+                        }
+                    };
+
+                    let patterns = column![
+                        checkbox(rules.delta_divergence)
+                            .label("Delta divergence")
+                            .on_toggle(move |delta_divergence| {
+                                on_change(FootprintStudy::FlowAlerts(FlowAlertRules {
+                                    delta_divergence,
+                                    ..rules
+                                }))
+                            }),
+                        checkbox(rules.unfinished_auction)
+                            .label("Unfinished auctions")
+                            .on_toggle(move |unfinished_auction| {
+                                on_change(FootprintStudy::FlowAlerts(FlowAlertRules {
+                                    unfinished_auction,
+                                    ..rules
+                                }))
+                            }),
+                    ]
+                    .padding(8)
+                    .spacing(8);
+
+                    let sound_checkbox = {
+                        let cbox =
+                            checkbox(rules.sound)
+                                .label("Play sound")
+                                .on_toggle(move |sound| {
+                                    on_change(FootprintStudy::FlowAlerts(FlowAlertRules {
+                                        sound,
+                                        ..rules
+                                    }))
+                                });
+
+                        column![
+                            cbox,
+                            text("Checked as each candle closes")
+                                .size(crate::style::text_size::SMALL),
+                        ]
+                        .padding(8)
+                        .spacing(4)
+                    };
+
+                    split_column![stacked, patterns, sound_checkbox]
+                        .padding(4)
+                        .into()
+                }
             }
         }
     }
@@ -956,7 +2676,7 @@ pub mod study {
         ) -> Element<'a, Message<Self>> {
             let interval_ms = match basis {
                 data::chart::Basis::Time(interval) => interval.to_milliseconds(),
-                data::chart::Basis::Tick(_) => {
+                data::chart::Basis::Tick(_) | data::chart::Basis::Bars(_) => {
                     return iced::widget::center(text(
                         "Heatmap studies are not supported for tick-based charts",
                     ))
@@ -1022,6 +2742,59 @@ pub mod study {
                             .into()
                     }
                 },
+                HeatmapStudy::DevelopingValueArea { value_area_pct } => {
+                    let value_area_pct = *value_area_pct;
+
+                    let slider = slider(50.0..=95.0, value_area_pct as f32, move |new_value| {
+                        on_change(HeatmapStudy::DevelopingValueArea {
+                            value_area_pct: new_value as usize,
+                        })
+                    })
+                    .step(5.0);
+
+                    column![
+                        text(format!(
+                            "Value area: {value_area_pct}% of session volume (resets 00:00 UTC)"
+                        )),
+                        slider,
+                    ]
+                    .padding(8)
+                    .spacing(4)
+                    .into()
+                }
+                HeatmapStudy::LiquidationLevels { max_leverage } => {
+                    let max_leverage = *max_leverage;
+                    let tier = LEVERAGE_TIERS
+                        .iter()
+                        .position(|leverage| *leverage >= max_leverage)
+                        .unwrap_or(LEVERAGE_TIERS.len() - 1);
+
+                    let slider = slider(
+                        0.0..=(LEVERAGE_TIERS.len() - 1) as f32,
+                        tier as f32,
+                        move |new_tier| {
+                            on_change(HeatmapStudy::LiquidationLevels {
+                                max_leverage: LEVERAGE_TIERS[new_tier as usize],
+                            })
+                        },
+                    )
+                    .step(1.0);
+
+                    column![
+                        text(format!(
+                            "Positions opened with up to {max_leverage}x leverage"
+                        )),
+                        slider,
+                        text(
+                            "Estimated from open interest changes while recording, \
+                             on Binance, Bybit and OKX perpetuals"
+                        )
+                        .size(style::text_size::SMALL),
+                    ]
+                    .padding(8)
+                    .spacing(4)
+                    .into()
+                }
             }
         }
     }
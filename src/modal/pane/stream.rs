@@ -4,13 +4,14 @@ use crate::{
 };
 
 use data::chart::Basis;
+use data::rates::{RateKind, StreamRates};
 use exchange::{
     StreamPairKind, TickMultiplier, Timeframe,
     adapter::Exchange,
     unit::{MinTicksize, PriceStep},
 };
 use iced::{
-    Element, Length,
+    Alignment, Element, Length, Theme,
     alignment::Horizontal,
     padding,
     widget::{button, column, container, row, rule, scrollable, text},
@@ -67,6 +68,10 @@ impl NumericInput {
         Self::from_str(&tc.0.to_string())
     }
 
+    pub fn from_timeframe(tf: Timeframe) -> Self {
+        Self::from_str(&tf.to_string())
+    }
+
     pub fn to_display_string(self) -> String {
         if self.len == 0 {
             return String::new();
@@ -97,6 +102,28 @@ impl NumericInput {
             .and_then(|s| s.parse::<u16>().ok())
             .map(data::aggr::TickCount)
     }
+
+    /// Minutes, or hours and days with an `h` or `d` suffix
+    pub fn parse_timeframe(self) -> Option<Timeframe> {
+        if self.len == 0 {
+            return None;
+        }
+        let input = std::str::from_utf8(&self.buffer[..self.len as usize]).ok()?;
+
+        let (digits, unit_minutes) = if let Some(days) = input.strip_suffix('d') {
+            (days, 1440)
+        } else if let Some(hours) = input.strip_suffix('h') {
+            (hours, 60)
+        } else {
+            (input.strip_suffix('m').unwrap_or(input), 1)
+        };
+
+        digits
+            .parse::<u16>()
+            .ok()?
+            .checked_mul(unit_minutes)
+            .map(Timeframe::from_minutes)
+    }
 }
 
 impl Default for NumericInput {
@@ -117,12 +144,17 @@ pub enum ViewMode {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum SelectedTab {
-    Timeframe,
+    Timeframe {
+        raw_input_buf: NumericInput,
+        parsed_input: Option<Timeframe>,
+        is_input_valid: bool,
+    },
     TickCount {
         raw_input_buf: NumericInput,
         parsed_input: Option<data::aggr::TickCount>,
         is_input_valid: bool,
     },
+    Bars,
 }
 
 pub enum Action {
@@ -138,6 +170,7 @@ pub enum Message {
     TicksizeInputChanged(String),
     TicksizeSelected(TickMultiplier),
     TickCountInputChanged(String),
+    TimeframeInputChanged(String),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -230,7 +263,22 @@ impl Modifier {
         match message {
             Message::TabSelected(tab) => Some(Action::TabSelected(tab)),
             Message::BasisSelected(basis) => match basis {
-                Basis::Time(_) => Some(Action::BasisSelected(basis)),
+                Basis::Time(new_tf) => {
+                    if let SelectedTab::Timeframe {
+                        raw_input_buf,
+                        parsed_input,
+                        is_input_valid,
+                    } = &mut self.tab
+                        && *parsed_input != Some(new_tf)
+                    {
+                        *raw_input_buf = NumericInput::default();
+                        *parsed_input = None;
+                        *is_input_valid = true;
+                    }
+
+                    Some(Action::BasisSelected(basis))
+                }
+                Basis::Bars(_) => Some(Action::BasisSelected(basis)),
                 Basis::Tick(new_tc) => {
                     if let SelectedTab::TickCount {
                         raw_input_buf,
@@ -326,12 +374,44 @@ impl Modifier {
                 }
                 None
             }
+            Message::TimeframeInputChanged(value_str) => {
+                if let SelectedTab::Timeframe {
+                    ref mut raw_input_buf,
+                    ref mut parsed_input,
+                    ref mut is_input_valid,
+                } = self.tab
+                {
+                    let timeframe_str: String = value_str
+                        .chars()
+                        .filter(|c| c.is_ascii_digit() || matches!(c, 'm' | 'h' | 'd'))
+                        .collect();
+
+                    *raw_input_buf = NumericInput::from_str(&timeframe_str);
+                    *parsed_input = raw_input_buf.parse_timeframe();
+                    *is_input_valid =
+                        raw_input_buf.is_empty() || parsed_input.is_some_and(in_custom_tf_range);
+                }
+                None
+            }
         }
     }
 
-    pub fn view<'a>(&self, stream_pair: Option<StreamPairKind>) -> Element<'a, Message> {
+    pub fn view<'a>(
+        &self,
+        stream_pair: Option<StreamPairKind>,
+        stream_rates: &StreamRates,
+    ) -> Element<'a, Message> {
         let kind = self.kind;
 
+        let exchange = match &stream_pair {
+            Some(StreamPairKind::SingleSource(ticker_info)) => Some(ticker_info.exchange()),
+            Some(StreamPairKind::MultiSource(ticker_infos)) => ticker_infos
+                .first()
+                .map(|ticker_info| ticker_info.exchange()),
+            None => None,
+        }
+        .or(self.exchange);
+
         let (selected_basis, selected_ticksize) = match kind {
             ModifierKind::Candlestick(basis) | ModifierKind::Comparison(basis) => {
                 (Some(basis), None)
@@ -341,10 +421,10 @@ impl Modifier {
             | ModifierKind::Orderbook(basis, ticksize) => (Some(basis), Some(ticksize)),
         };
 
-        let create_button = |content: iced::widget::text::Text<'a>,
+        let create_button = |content: Element<'a, Message>,
                              msg: Option<Message>,
                              is_selected: bool| {
-            let btn = button(content.align_x(iced::Alignment::Center))
+            let btn = button(container(content).center_x(Length::Fill))
                 .width(Length::Fill)
                 .style(move |theme, status| style::button::menu_body(theme, status, is_selected));
 
@@ -360,18 +440,20 @@ impl Modifier {
                 let mut basis_selection_column =
                     column![].padding(4).spacing(8).align_x(Horizontal::Center);
 
-                let allows_tick_basis = match kind {
-                    ModifierKind::Candlestick(_) | ModifierKind::Footprint(_, _) => true,
-                    ModifierKind::Heatmap(_, _)
-                    | ModifierKind::Orderbook(_, _)
-                    | ModifierKind::Comparison(_) => false,
-                };
+                let is_kline_chart = matches!(
+                    kind,
+                    ModifierKind::Candlestick(_) | ModifierKind::Footprint(_, _)
+                );
+                let allows_tick_basis =
+                    is_kline_chart || matches!(kind, ModifierKind::Comparison(_));
 
                 if selected_basis.is_some() {
                     let (timeframe_tab_is_selected, tick_count_tab_is_selected) = match self.tab {
-                        SelectedTab::Timeframe => (true, false),
+                        SelectedTab::Timeframe { .. } => (true, false),
                         SelectedTab::TickCount { .. } => (false, true),
+                        SelectedTab::Bars => (false, false),
                     };
+                    let bars_tab_is_selected = self.tab == SelectedTab::Bars;
 
                     let tabs_row = {
                         if allows_tick_basis {
@@ -405,13 +487,17 @@ impl Modifier {
                                     }
                                 };
 
-                            row![
+                            let tabs = row![
                                 tab_button(
                                     text("Timeframe"),
                                     if timeframe_tab_is_selected {
                                         None
                                     } else {
-                                        Some(Message::TabSelected(SelectedTab::Timeframe))
+                                        Some(Message::TabSelected(SelectedTab::Timeframe {
+                                            raw_input_buf: NumericInput::default(),
+                                            parsed_input: None,
+                                            is_input_valid: true,
+                                        }))
                                     },
                                     !timeframe_tab_is_selected,
                                     is_timeframe_selected,
@@ -440,16 +526,28 @@ impl Modifier {
                                         Some(Message::TabSelected(tick_count_tab))
                                     },
                                     !tick_count_tab_is_selected,
-                                    !is_timeframe_selected,
+                                    matches!(selected_basis, Some(Basis::Tick(_))),
                                 ),
                             ]
-                            .spacing(4)
+                            .spacing(4);
+
+                            // Range, renko and the like are built per ticker, nothing to line up
+                            if is_kline_chart {
+                                tabs.push(tab_button(
+                                    text("Bars"),
+                                    if bars_tab_is_selected {
+                                        None
+                                    } else {
+                                        Some(Message::TabSelected(SelectedTab::Bars))
+                                    },
+                                    !bars_tab_is_selected,
+                                    matches!(selected_basis, Some(Basis::Bars(_))),
+                                ))
+                            } else {
+                                tabs
+                            }
                         } else {
-                            let text_content = match kind {
-                                ModifierKind::Comparison(_) => "Timeframe",
-                                _ => "Aggregation",
-                            };
-                            row![text(text_content).size(crate::style::text_size::EMPHASIS)]
+                            row![text("Aggregation").size(crate::style::text_size::EMPHASIS)]
                         }
                     };
 
@@ -459,12 +557,40 @@ impl Modifier {
                 }
 
                 match self.tab {
-                    SelectedTab::Timeframe => {
+                    SelectedTab::Timeframe {
+                        raw_input_buf,
+                        parsed_input,
+                        is_input_valid,
+                    } => {
                         let selected_tf = match selected_basis {
                             Some(Basis::Time(tf)) => Some(tf),
                             _ => None,
                         };
 
+                        // Resampled from a lower timeframe, so kline charts only
+                        if is_kline_chart {
+                            let timeframe_to_submit = parsed_input.filter(|tf| {
+                                in_custom_tf_range(*tf)
+                                    && exchange
+                                        .is_none_or(|ex| ex.kline_fetch_timeframe(*tf).is_some())
+                            });
+
+                            let custom_input = numeric_input_box::<_, Message>(
+                                "Custom: ",
+                                &format!(
+                                    "{}m-{}d",
+                                    Timeframe::CUSTOM_MIN_MINUTES,
+                                    Timeframe::CUSTOM_MAX_MINUTES / 1440
+                                ),
+                                &raw_input_buf.to_display_string(),
+                                is_input_valid
+                                    && (parsed_input.is_none() || timeframe_to_submit.is_some()),
+                                Message::TimeframeInputChanged,
+                                timeframe_to_submit.map(|tf| Message::BasisSelected(tf.into())),
+                            );
+                            basis_selection_column = basis_selection_column.push(custom_input);
+                        }
+
                         if let Some((timeframes, items_per_row)) =
                             timeframe_grid_config(kind, stream_pair.as_ref())
                         {
@@ -472,6 +598,7 @@ impl Modifier {
                                 &timeframes,
                                 selected_tf,
                                 |tf| Message::BasisSelected(tf.into()),
+                                |tf| basis_cost(kind, tf.into(), exchange, stream_rates),
                                 &create_button,
                                 items_per_row,
                             );
@@ -492,6 +619,7 @@ impl Modifier {
                             &data::aggr::TickCount::ALL,
                             selected_tick_count,
                             |tc| Message::BasisSelected(Basis::Tick(tc)),
+                            |tc| basis_cost(kind, Basis::Tick(tc), exchange, stream_rates),
                             &create_button,
                             3,
                         );
@@ -513,6 +641,30 @@ impl Modifier {
                         basis_selection_column = basis_selection_column.push(custom_input);
                         basis_selection_column = basis_selection_column.push(tick_count_grid);
                     }
+                    SelectedTab::Bars => {
+                        let selected_kind = match selected_basis {
+                            Some(Basis::Bars(kind)) => Some(kind),
+                            _ => None,
+                        };
+
+                        // Bricks and columns have no footprint to show
+                        let presets = data::aggr::BarKind::PRESETS
+                            .into_iter()
+                            .filter(|bars| {
+                                bars.keeps_candles() || matches!(kind, ModifierKind::Candlestick(_))
+                            })
+                            .collect::<Vec<_>>();
+
+                        let bars_grid = modifiers_grid(
+                            &presets,
+                            selected_kind,
+                            |bars| Message::BasisSelected(Basis::Bars(bars)),
+                            |bars| basis_cost(kind, Basis::Bars(bars), exchange, stream_rates),
+                            &create_button,
+                            2,
+                        );
+                        basis_selection_column = basis_selection_column.push(bars_grid);
+                    }
                 }
 
                 container(scrollable::Scrollable::with_direction(
@@ -559,6 +711,7 @@ impl Modifier {
                         &allowed_tm,
                         Some(ticksize),
                         Message::TicksizeSelected,
+                        |tm| ticksize_cost(kind, Some(exchange), tm),
                         &create_button,
                         3,
                     );
@@ -622,14 +775,24 @@ impl Modifier {
     }
 }
 
+fn in_custom_tf_range(tf: Timeframe) -> bool {
+    (Timeframe::CUSTOM_MIN_MINUTES..=Timeframe::CUSTOM_MAX_MINUTES).contains(&tf.to_minutes())
+}
+
 fn timeframe_grid_config(
     kind: ModifierKind,
     stream_pair: Option<&StreamPairKind>,
 ) -> Option<(Vec<Timeframe>, usize)> {
     match kind {
-        ModifierKind::Candlestick(_)
-        | ModifierKind::Footprint(_, _)
-        | ModifierKind::Comparison(_) => Some((supported_kline_timeframes(stream_pair), 3)),
+        // Seconds are built from trades where the venue has no klines for them
+        ModifierKind::Candlestick(_) | ModifierKind::Footprint(_, _) => Some((
+            Timeframe::SECONDS
+                .into_iter()
+                .chain(supported_kline_timeframes(stream_pair))
+                .collect(),
+            3,
+        )),
+        ModifierKind::Comparison(_) => Some((supported_kline_timeframes(stream_pair), 3)),
         ModifierKind::Heatmap(_, _) => Some((supported_heatmap_timeframes(stream_pair)?, 2)),
         ModifierKind::Orderbook(_, _) => None,
     }
@@ -641,12 +804,13 @@ fn timeframe_grid_config(
 /// If the last row would otherwise contain only one item,
 /// one item is shifted from the previous row so that no row ends up
 /// with a single button.
-fn modifiers_grid<'a, T, FMsg>(
+fn modifiers_grid<'a, T, FMsg, FCost>(
     items_source: &[T],
     selected_value: Option<T>,
     to_message: FMsg,
+    cost_of: FCost,
     create_button_fn: &impl Fn(
-        iced::widget::text::Text<'a>,
+        Element<'a, Message>,
         Option<Message>,
         bool,
     ) -> iced::widget::Button<'a, Message>,
@@ -655,6 +819,7 @@ fn modifiers_grid<'a, T, FMsg>(
 where
     T: Copy + PartialEq + ToString,
     FMsg: Fn(T) -> Message,
+    FCost: Fn(T) -> Option<Cost>,
 {
     let mut grid_column = column![].spacing(4);
     let mut remaining_slice = items_source;
@@ -683,11 +848,15 @@ where
             } else {
                 Some(to_message(item_value))
             };
-            button_row = button_row.push(create_button_fn(
-                text(item_value.to_string()),
-                msg,
-                is_selected,
-            ));
+            let label = text(item_value.to_string());
+            let content = match cost_of(item_value) {
+                Some(cost) => column![label, cost.view()]
+                    .align_x(Alignment::Center)
+                    .into(),
+                None => label.into(),
+            };
+
+            button_row = button_row.push(create_button_fn(content, msg, is_selected));
         }
 
         grid_column = grid_column.push(button_row);
@@ -696,6 +865,144 @@ where
     grid_column
 }
 
+/// Rough load of picking an option, shown under its button
+#[derive(Debug, Clone, Copy)]
+struct Cost {
+    /// Typical messages per second of the stream the option needs, once measured
+    per_sec: Option<f32>,
+    level: CostLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CostLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl CostLevel {
+    fn from_trade_rate(per_sec: f32) -> Self {
+        match per_sec {
+            r if r < 20.0 => CostLevel::Low,
+            r if r < 100.0 => CostLevel::Medium,
+            _ => CostLevel::High,
+        }
+    }
+}
+
+impl Cost {
+    fn view<'a>(self) -> Element<'a, Message> {
+        let dots = match self.level {
+            CostLevel::Low => "●○○",
+            CostLevel::Medium => "●●○",
+            CostLevel::High => "●●●",
+        };
+        let label = match self.per_sec {
+            Some(r) if r >= 1000.0 => format!("{:.1}k/s {dots}", r / 1000.0),
+            Some(r) if r >= 10.0 => format!("{r:.0}/s {dots}"),
+            Some(r) => format!("{r:.1}/s {dots}"),
+            None => dots.to_string(),
+        };
+        let level = self.level;
+
+        text(label)
+            .size(style::text_size::TINY)
+            .style(move |theme: &Theme| {
+                let palette = theme.extended_palette();
+                iced::widget::text::Style {
+                    color: Some(match level {
+                        CostLevel::Low => palette.success.base.color,
+                        CostLevel::Medium => palette.warning.base.color,
+                        CostLevel::High => palette.danger.base.color,
+                    }),
+                }
+            })
+            .into()
+    }
+}
+
+fn basis_cost(
+    kind: ModifierKind,
+    basis: Basis,
+    exchange: Option<Exchange>,
+    rates: &StreamRates,
+) -> Option<Cost> {
+    let typical = |rate_kind| exchange.and_then(|ex| rates.typical(ex, rate_kind));
+
+    match (kind, basis) {
+        (ModifierKind::Candlestick(_), Basis::Time(tf))
+            if exchange.is_none_or(|ex| ex.kline_fetch_timeframe(tf).is_some()) =>
+        {
+            Some(Cost {
+                per_sec: typical(RateKind::Kline),
+                level: CostLevel::Low,
+            })
+        }
+        (ModifierKind::Comparison(_), Basis::Time(_)) => Some(Cost {
+            per_sec: typical(RateKind::Kline),
+            level: CostLevel::Low,
+        }),
+        // Every ticker in the comparison streams its trades
+        (ModifierKind::Comparison(_), Basis::Tick(_)) => {
+            let per_sec = typical(RateKind::Trades);
+
+            Some(Cost {
+                per_sec,
+                level: per_sec.map_or(CostLevel::Medium, CostLevel::from_trade_rate),
+            })
+        }
+        // Tick based candles, footprints and candles the venue has no klines for are built
+        // from every single trade
+        (ModifierKind::Candlestick(_) | ModifierKind::Footprint(..), _) => {
+            let per_sec = typical(RateKind::Trades);
+            let level = per_sec.map_or(CostLevel::Medium, CostLevel::from_trade_rate);
+
+            Some(Cost {
+                per_sec,
+                level: if matches!(kind, ModifierKind::Footprint(..)) {
+                    level.max(CostLevel::Medium)
+                } else {
+                    level
+                },
+            })
+        }
+        // Depth arrives at the same pace either way, shorter buckets mean more columns to render
+        (ModifierKind::Heatmap(..), Basis::Time(timeframe)) => Some(Cost {
+            per_sec: typical(RateKind::Depth),
+            level: match timeframe.to_milliseconds() {
+                0..=200 => CostLevel::High,
+                201..=500 => CostLevel::Medium,
+                _ => CostLevel::Low,
+            },
+        }),
+        _ => None,
+    }
+}
+
+/// Only matters where levels are grouped locally, server-side grouping costs the same
+fn ticksize_cost(
+    kind: ModifierKind,
+    exchange: Option<Exchange>,
+    multiplier: TickMultiplier,
+) -> Option<Cost> {
+    let aggregated_locally = match kind {
+        ModifierKind::Footprint(..) => true,
+        ModifierKind::Heatmap(..) | ModifierKind::Orderbook(..) => {
+            exchange.is_some_and(|ex| ex.is_depth_client_aggr())
+        }
+        ModifierKind::Candlestick(_) | ModifierKind::Comparison(_) => false,
+    };
+
+    aggregated_locally.then_some(Cost {
+        per_sec: None,
+        level: match multiplier.0 {
+            0..=2 => CostLevel::High,
+            3..=10 => CostLevel::Medium,
+            _ => CostLevel::Low,
+        },
+    })
+}
+
 fn supported_kline_timeframes(stream_pair: Option<&StreamPairKind>) -> Vec<Timeframe> {
     let is_supported = |tf| match stream_pair {
         Some(StreamPairKind::SingleSource(ticker_info)) => {
@@ -738,7 +1045,15 @@ impl From<&ModifierKind> for SelectedTab {
             | ModifierKind::Heatmap(basis, _)
             | ModifierKind::Orderbook(basis, _)
             | ModifierKind::Comparison(basis) => match basis {
-                Basis::Time(_) => SelectedTab::Timeframe,
+                Basis::Time(tf) => SelectedTab::Timeframe {
+                    raw_input_buf: if tf.is_custom() {
+                        NumericInput::from_timeframe(*tf)
+                    } else {
+                        NumericInput::default()
+                    },
+                    parsed_input: if tf.is_custom() { Some(*tf) } else { None },
+                    is_input_valid: true,
+                },
                 Basis::Tick(tc) => SelectedTab::TickCount {
                     raw_input_buf: if tc.is_custom() {
                         NumericInput::from_tick_count(*tc)
@@ -748,6 +1063,7 @@ impl From<&ModifierKind> for SelectedTab {
                     parsed_input: if tc.is_custom() { Some(*tc) } else { None },
                     is_input_valid: true,
                 },
+                Basis::Bars(_) => SelectedTab::Bars,
             },
         }
     }
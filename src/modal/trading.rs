@@ -0,0 +1,715 @@
+use crate::style;
+use crate::widget::tooltip;
+
+use data::trading::journal::{self, Source};
+use data::trading::paper::{PaperAccount, Pnl};
+use data::trading::{self as config, Account, Fill, TradingConfig};
+use exchange::adapter::trading::{
+    self, Credentials, LimitOrder, OrderSide, UserTrade, WorkingOrder,
+};
+use exchange::adapter::{UniqueStreams, Venue};
+use exchange::unit::Price;
+use exchange::{Ticker, TickerInfo};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Alignment, Element};
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often open orders and positions of the traded tickers are fetched again
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleEnabled(bool),
+    TogglePaper(bool),
+    ResetPaper,
+    SelectVenue(Venue),
+    ApiKeyChanged(String),
+    SecretChanged(String),
+    SaveCredentials,
+    ClearCredentials,
+    SelectTicker(TickerOption),
+    SizeChanged(String),
+    SetSize,
+    RemoveSize(Ticker),
+    Cancel(TickerInfo, String),
+    Placed(Result<WorkingOrder, String>),
+    Cancelled(Ticker, String, Result<(), String>),
+    Refreshed(Ticker, Result<(Account, Vec<UserTrade>), String>),
+}
+
+pub enum Action {
+    Send(Vec<Request>),
+    Notify(String),
+    Failed(String),
+}
+
+/// An order request to run, signed with the key of its venue
+pub enum Request {
+    Place(Credentials, LimitOrder),
+    Cancel(Credentials, TickerInfo, String),
+    Refresh(Credentials, TickerInfo),
+}
+
+/// API keys, ladder order sizes and the orders and positions of the traded tickers
+pub struct TradingManager {
+    config: TradingConfig,
+    venue: Venue,
+    credentials: FxHashMap<Venue, Credentials>,
+    /// Key inputs of the selected venue, only used once saved
+    draft: Credentials,
+    ticker: Option<TickerInfo>,
+    size: String,
+    /// Ladders and charts sync with these every frame, so they're only rebuilt on changes
+    accounts: Arc<FxHashMap<Ticker, Account>>,
+    last_refresh: Option<Instant>,
+    paper: PaperAccount,
+    /// Live executions and paper fills not handed to the journal yet
+    executions: Vec<journal::Entry>,
+}
+
+impl TradingManager {
+    pub fn new(config: TradingConfig) -> Self {
+        let credentials = trading::SUPPORTED_VENUES
+            .into_iter()
+            .map(|venue| (venue, config::load_credentials(venue)))
+            .collect::<FxHashMap<_, _>>();
+
+        let venue = trading::SUPPORTED_VENUES[0];
+
+        Self {
+            draft: credentials.get(&venue).cloned().unwrap_or_default(),
+            config,
+            venue,
+            credentials,
+            ticker: None,
+            size: String::new(),
+            accounts: Arc::new(FxHashMap::default()),
+            last_refresh: None,
+            paper: PaperAccount::default(),
+            executions: vec![],
+        }
+    }
+
+    pub fn config(&self) -> &TradingConfig {
+        &self.config
+    }
+
+    pub fn accounts(&self) -> Arc<FxHashMap<Ticker, Account>> {
+        Arc::clone(&self.accounts)
+    }
+
+    /// Executions since the last call, for the journal to log
+    pub fn take_executions(&mut self) -> Vec<journal::Entry> {
+        std::mem::take(&mut self.executions)
+    }
+
+    /// Venues with a complete key saved, in the order they're listed
+    pub fn keyed_venues(&self) -> Vec<(Venue, Credentials)> {
+        trading::SUPPORTED_VENUES
+            .into_iter()
+            .filter_map(|venue| {
+                let credentials = self.credentials.get(&venue)?;
+                credentials
+                    .is_complete()
+                    .then(|| (venue, credentials.clone()))
+            })
+            .collect()
+    }
+
+    /// Profit of every ticker traded on paper, `None` outside of paper trading
+    pub fn paper_pnls(&self) -> Option<Vec<(Ticker, Pnl)>> {
+        self.config.paper.then(|| {
+            self.paper
+                .tickers()
+                .filter_map(|ticker| Some((ticker, self.paper.pnl(ticker)?)))
+                .filter(|(_, pnl)| pnl.qty != 0.0 || pnl.realized != 0.0)
+                .collect()
+        })
+    }
+
+    /// A click on a ladder row, sent to the venue or filled by the paper account
+    pub fn place(&mut self, ticker_info: TickerInfo, side: OrderSide, price: Price) -> Action {
+        let ticker = ticker_info.ticker;
+
+        let Some(qty) = self.config.order_size(ticker) else {
+            return Action::Failed(format!(
+                "Set an order size for {} in Trading first",
+                ticker.symbol_and_exchange_string()
+            ));
+        };
+
+        if self.config.paper {
+            return match self.paper.place(ticker, side, price, qty) {
+                Ok(fills) => {
+                    self.sync_paper(ticker);
+                    self.log_paper_fills(ticker, &fills);
+                    match fills.first() {
+                        Some(fill) => Action::Notify(fill_notice(ticker, fill)),
+                        None => Action::Notify(format!(
+                            "Paper {side} {qty} {} @ {}",
+                            ticker.symbol_and_exchange_string(),
+                            price.to_string(ticker_info.min_ticksize)
+                        )),
+                    }
+                }
+                Err(err) => Action::Failed(err),
+            };
+        }
+
+        match self.usable_credentials(ticker_info) {
+            Ok(credentials) => Action::Send(vec![Request::Place(
+                credentials,
+                LimitOrder {
+                    ticker_info,
+                    side,
+                    price,
+                    qty,
+                },
+            )]),
+            Err(err) => Action::Failed(err),
+        }
+    }
+
+    pub fn cancel(&mut self, ticker_info: TickerInfo, ids: Vec<String>) -> Option<Action> {
+        let ticker = ticker_info.ticker;
+
+        if self.config.paper {
+            for id in &ids {
+                self.paper.cancel(ticker, id);
+            }
+            self.sync_paper(ticker);
+            return None;
+        }
+
+        match self.usable_credentials(ticker_info) {
+            Ok(credentials) => Some(Action::Send(
+                ids.into_iter()
+                    .map(|id| Request::Cancel(credentials.clone(), ticker_info, id))
+                    .collect(),
+            )),
+            Err(err) => Some(Action::Failed(err)),
+        }
+    }
+
+    /// Feeds the paper account, returns a notice of the orders it filled
+    pub fn on_market_event(&mut self, event: &exchange::Event) -> Option<Action> {
+        use exchange::adapter::{Event, StreamKind};
+
+        if !self.config.paper {
+            return None;
+        }
+
+        let (ticker, fills) = match event {
+            Event::DepthReceived(StreamKind::Depth { ticker_info, .. }, _, depth) => (
+                ticker_info.ticker,
+                self.paper.on_depth(ticker_info.ticker, depth),
+            ),
+            Event::TradesReceived(StreamKind::Trades { ticker_info }, _, trades) => (
+                ticker_info.ticker,
+                self.paper.on_trades(ticker_info.ticker, trades),
+            ),
+            _ => return None,
+        };
+
+        if fills.is_empty() {
+            return None;
+        }
+        self.sync_paper(ticker);
+        self.log_paper_fills(ticker, &fills);
+
+        let notices = fills
+            .iter()
+            .map(|fill| fill_notice(ticker, fill))
+            .collect::<Vec<_>>();
+        Some(Action::Notify(notices.join("\n")))
+    }
+
+    fn log_paper_fills(&mut self, ticker: Ticker, fills: &[Fill]) {
+        self.executions
+            .extend(fills.iter().map(|fill| journal::Entry {
+                id: 0,
+                ticker,
+                time: fill.time,
+                side: fill.side,
+                price: fill.price,
+                qty: fill.qty.to_f64(),
+                source: Source::Paper,
+                trade_id: None,
+                note: String::new(),
+            }));
+    }
+
+    fn sync_paper(&mut self, ticker: Ticker) {
+        let account = self.paper.account(ticker);
+        let accounts = Arc::make_mut(&mut self.accounts);
+
+        if account == Account::default() {
+            accounts.remove(&ticker);
+        } else {
+            accounts.insert(ticker, account);
+        }
+    }
+
+    /// Refreshes the orders, positions and executions of `tickers` when due, as orders also
+    /// fill or get cancelled outside of the app. Only needs a key, also without order entry
+    /// enabled
+    pub fn refresh_if_due(&mut self, now: Instant, tickers: &[TickerInfo]) -> Vec<Request> {
+        if self.config.paper
+            || self
+                .last_refresh
+                .is_some_and(|last| now.duration_since(last) < REFRESH_INTERVAL)
+        {
+            return vec![];
+        }
+        self.last_refresh = Some(now);
+
+        if self
+            .accounts
+            .keys()
+            .any(|ticker| !tickers.iter().any(|info| info.ticker == *ticker))
+        {
+            Arc::make_mut(&mut self.accounts)
+                .retain(|ticker, _| tickers.iter().any(|info| info.ticker == *ticker));
+        }
+
+        tickers
+            .iter()
+            .filter_map(|&ticker_info| {
+                let credentials = self.credentials_for(ticker_info).ok()?;
+                Some(Request::Refresh(credentials, ticker_info))
+            })
+            .collect()
+    }
+
+    fn usable_credentials(&self, ticker_info: TickerInfo) -> Result<Credentials, String> {
+        if !self.config.enabled {
+            return Err("Trading is disabled, turn it on in Settings > Trading".to_string());
+        }
+        self.credentials_for(ticker_info)
+    }
+
+    fn credentials_for(&self, ticker_info: TickerInfo) -> Result<Credentials, String> {
+        let venue = ticker_info.exchange().venue();
+
+        if !trading::is_supported(ticker_info.exchange()) {
+            return Err(format!("Order entry isn't supported on {venue}"));
+        }
+
+        self.credentials
+            .get(&venue)
+            .filter(|credentials| credentials.is_complete())
+            .cloned()
+            .ok_or_else(|| format!("No {venue} API key set, add one in Settings > Trading"))
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::ToggleEnabled(enabled) => self.config.enabled = enabled,
+            Message::TogglePaper(paper) => {
+                self.config.paper = paper;
+                self.last_refresh = None;
+                self.accounts = Arc::new(FxHashMap::default());
+
+                if paper {
+                    let tickers = self.paper.tickers().collect::<Vec<_>>();
+                    tickers
+                        .into_iter()
+                        .for_each(|ticker| self.sync_paper(ticker));
+                }
+            }
+            Message::ResetPaper => {
+                self.paper.clear();
+                if self.config.paper {
+                    self.accounts = Arc::new(FxHashMap::default());
+                }
+            }
+            Message::SelectVenue(venue) => {
+                self.venue = venue;
+                self.draft = self.credentials.get(&venue).cloned().unwrap_or_default();
+            }
+            Message::ApiKeyChanged(api_key) => self.draft.api_key = api_key,
+            Message::SecretChanged(secret) => self.draft.secret = secret,
+            Message::SaveCredentials => return self.store_credentials(self.draft.clone()),
+            Message::ClearCredentials => {
+                self.draft = Credentials::default();
+                let venue = self.venue;
+                Arc::make_mut(&mut self.accounts)
+                    .retain(|ticker, _| ticker.exchange.venue() != venue);
+
+                return self.store_credentials(Credentials::default());
+            }
+            Message::SelectTicker(option) => {
+                self.ticker = Some(option.0);
+                self.size = self
+                    .config
+                    .order_size(option.0.ticker)
+                    .map(|qty| qty.to_string())
+                    .unwrap_or_default();
+            }
+            Message::SizeChanged(size) => self.size = size,
+            Message::SetSize => {
+                if let (Some(ticker_info), Some(qty)) = (self.ticker, self.draft_size()) {
+                    self.config.set_order_size(ticker_info.ticker, qty);
+                }
+            }
+            Message::RemoveSize(ticker) => self.config.remove_order_size(ticker),
+            Message::Cancel(ticker_info, id) => return self.cancel(ticker_info, vec![id]),
+            Message::Placed(result) => match result {
+                Ok(order) => {
+                    let notice = format!(
+                        "{} {} {} @ {}",
+                        order.side,
+                        order.qty.to_f64(),
+                        order.ticker.symbol_and_exchange_string(),
+                        order.price.to_f64()
+                    );
+
+                    let accounts = Arc::make_mut(&mut self.accounts);
+                    let working = &mut accounts.entry(order.ticker).or_default().orders;
+                    if !working.iter().any(|o| o.id == order.id) {
+                        working.push(order);
+                    }
+                    return Some(Action::Notify(format!("Placed {notice}")));
+                }
+                Err(err) => return Some(Action::Failed(format!("Order rejected: {err}"))),
+            },
+            Message::Cancelled(ticker, id, result) => {
+                if let Err(err) = result {
+                    return Some(Action::Failed(format!("Cancel failed: {err}")));
+                }
+                if let Some(account) = Arc::make_mut(&mut self.accounts).get_mut(&ticker) {
+                    account.orders.retain(|order| order.id != id);
+                }
+            }
+            Message::Refreshed(ticker, result) => match result {
+                Ok((fetched, trades)) => {
+                    self.executions
+                        .extend(trades.into_iter().map(|trade| journal::Entry {
+                            id: 0,
+                            ticker: trade.ticker,
+                            time: trade.time,
+                            side: trade.side,
+                            price: trade.price,
+                            qty: trade.qty.to_f64(),
+                            source: Source::Live,
+                            trade_id: Some(trade.id),
+                            note: String::new(),
+                        }));
+
+                    if self
+                        .accounts
+                        .get(&ticker)
+                        .map_or(fetched.is_empty(), |current| *current == fetched)
+                    {
+                        return None;
+                    }
+                    Arc::make_mut(&mut self.accounts).insert(ticker, fetched);
+                }
+                Err(err) => log::warn!(
+                    "Failed to refresh orders and positions of {}: {err}",
+                    ticker.symbol_and_exchange_string()
+                ),
+            },
+        }
+        None
+    }
+
+    fn store_credentials(&mut self, credentials: Credentials) -> Option<Action> {
+        if let Err(err) = config::save_credentials(self.venue, &credentials) {
+            return Some(Action::Failed(err));
+        }
+        self.credentials.insert(self.venue, credentials);
+        None
+    }
+
+    fn draft_size(&self) -> Option<f64> {
+        self.size
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|qty| qty.is_finite() && *qty > 0.0)
+    }
+
+    pub fn view<'a>(&'a self, streams: &'a UniqueStreams) -> Element<'a, Message> {
+        let enabled_checkbox = checkbox(self.config.enabled)
+            .label("Enable order entry")
+            .on_toggle(Message::ToggleEnabled);
+
+        let header = row![
+            text("Trading").size(style::text_size::SECTION),
+            space::horizontal(),
+            enabled_checkbox,
+        ]
+        .align_y(Alignment::Center);
+
+        let paper = row![
+            checkbox(self.config.paper)
+                .label("Paper trading")
+                .on_toggle(Message::TogglePaper),
+            tooltip(
+                button("i").style(style::button::info),
+                Some(
+                    "Ladder orders are filled against the live order book and trades\n\
+                     instead of being sent to the exchange, no API key needed"
+                ),
+                iced::widget::tooltip::Position::Top,
+            ),
+            space::horizontal(),
+            button(text("Reset")).on_press_maybe(self.config.paper.then_some(Message::ResetPaper)),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        container(
+            column![
+                header,
+                paper,
+                self.keys_view(),
+                self.sizes_view(streams),
+                scrollable(self.orders_view(streams)).spacing(4),
+                text(
+                    "Arm a ladder in its settings, then left-click the bid or ask size column \
+                     to place a limit order and right-click a working order to cancel it"
+                )
+                .size(style::text_size::SMALL),
+            ]
+            .spacing(12),
+        )
+        .max_width(380)
+        .max_height(560)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+
+    fn keys_view(&self) -> Element<'_, Message> {
+        let saved = self
+            .credentials
+            .get(&self.venue)
+            .cloned()
+            .unwrap_or_default();
+        let has_changes = self.draft != saved;
+
+        let venue_picklist = pick_list(
+            trading::SUPPORTED_VENUES,
+            Some(self.venue),
+            Message::SelectVenue,
+        );
+        let status = if saved.is_complete() {
+            "Key saved"
+        } else {
+            "No key"
+        };
+
+        let buttons = row![
+            space::horizontal(),
+            button(text("Clear")).on_press_maybe(
+                (saved != Credentials::default()).then_some(Message::ClearCredentials)
+            ),
+            button(text("Save")).on_press_maybe(
+                (has_changes && self.draft.is_complete()).then_some(Message::SaveCredentials)
+            ),
+        ]
+        .spacing(8);
+
+        column![
+            container(
+                column![
+                    row![venue_picklist, space::horizontal(), text(status)]
+                        .align_y(Alignment::Center),
+                    text_input("API key", &self.draft.api_key)
+                        .on_input(Message::ApiKeyChanged)
+                        .secure(true),
+                    text_input("API secret", &self.draft.secret)
+                        .on_input(Message::SecretChanged)
+                        .secure(true),
+                    buttons,
+                ]
+                .spacing(8)
+            )
+            .padding(8)
+            .style(style::modal_container),
+            text("Keys are stored in the OS keychain, use ones restricted to trading")
+                .size(style::text_size::SMALL),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    fn sizes_view(&self, streams: &UniqueStreams) -> Element<'_, Message> {
+        let mut tickers = streams
+            .iter()
+            .map(|stream| stream.ticker_info())
+            .filter(|info| trading::is_supported(info.exchange()))
+            .map(TickerOption)
+            .collect::<Vec<_>>();
+        tickers.sort_by_key(|option| option.0.ticker.symbol_and_exchange_string());
+        tickers.dedup();
+
+        let selected = self
+            .ticker
+            .map(TickerOption)
+            .filter(|option| tickers.contains(option));
+
+        let is_valid = self.size.is_empty() || self.draft_size().is_some();
+        let on_set = selected.and(self.draft_size()).map(|_| Message::SetSize);
+
+        let form = row![
+            pick_list(tickers, selected, Message::SelectTicker).placeholder("Streamed ticker"),
+            text_input("Size", &self.size)
+                .on_input(Message::SizeChanged)
+                .on_submit_maybe(on_set.clone())
+                .width(90)
+                .style(move |theme, status| style::validated_text_input(theme, status, is_valid)),
+            button(text("Set")).on_press_maybe(on_set),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let mut list = column![form].spacing(4);
+        for (ticker, qty) in self.config.order_sizes() {
+            list = list.push(
+                row![
+                    text(ticker.symbol_and_exchange_string()),
+                    space::horizontal(),
+                    text(qty.to_string()),
+                    tooltip(
+                        button(style::icon_text(style::Icon::TrashBin, 11))
+                            .on_press(Message::RemoveSize(ticker))
+                            .style(|theme, status| style::button::transparent(
+                                theme, status, false
+                            )),
+                        Some("Remove order size"),
+                        iced::widget::tooltip::Position::Top,
+                    ),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        column![
+            text("Order sizes").size(style::text_size::SECTION),
+            container(list).padding(8).style(style::modal_container),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    fn orders_view<'a>(&'a self, streams: &'a UniqueStreams) -> Element<'a, Message> {
+        let mut list = column![].spacing(4);
+
+        let mut positions = self
+            .accounts
+            .values()
+            .flat_map(|account| &account.positions)
+            .collect::<Vec<_>>();
+        positions.sort_by_key(|position| position.ticker.symbol_and_exchange_string());
+
+        if !positions.is_empty() {
+            list = list.push(text("Positions").size(style::text_size::SECTION));
+        }
+        for position in positions {
+            let side = match position.side {
+                OrderSide::Buy => "Long",
+                OrderSide::Sell => "Short",
+            };
+            let mut label = format!(
+                "{} {side} {} @ {}",
+                position.ticker.symbol_and_exchange_string(),
+                position.qty.to_f64(),
+                position.entry_price.to_f64(),
+            );
+            if let Some(liquidation) = position.liquidation_price {
+                label.push_str(&format!(", liq. {}", liquidation.to_f64()));
+            }
+            if self.config.paper
+                && let Some(pnl) = self.paper.pnl(position.ticker)
+            {
+                label.push_str(&format!(
+                    "\nPnL {:.2}, realized {:.2}",
+                    pnl.realized + pnl.unrealized,
+                    pnl.realized
+                ));
+            }
+
+            list = list.push(
+                container(text(label))
+                    .padding(8)
+                    .style(style::modal_container),
+            );
+        }
+
+        list = list.push(text("Working orders").size(style::text_size::SECTION));
+
+        let mut orders = self
+            .accounts
+            .values()
+            .flat_map(|account| &account.orders)
+            .collect::<Vec<_>>();
+        if orders.is_empty() {
+            list = list.push(text("No working orders"));
+        }
+        orders.sort_by_key(|order| order.ticker.symbol_and_exchange_string());
+
+        for order in orders {
+            let ticker_info = streams
+                .iter()
+                .map(|stream| stream.ticker_info())
+                .find(|info| info.ticker == order.ticker);
+
+            let label = format!(
+                "{} {} {}/{} @ {}",
+                order.ticker.symbol_and_exchange_string(),
+                order.side,
+                order.filled.to_f64(),
+                order.qty.to_f64(),
+                ticker_info.map_or_else(
+                    || order.price.to_f64().to_string(),
+                    |info| order.price.to_string(info.min_ticksize)
+                ),
+            );
+
+            list = list.push(
+                container(
+                    row![
+                        text(label),
+                        space::horizontal(),
+                        button(text("Cancel")).on_press_maybe(
+                            ticker_info.map(|info| Message::Cancel(info, order.id.clone()))
+                        ),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+                )
+                .padding(8)
+                .style(style::modal_container),
+            );
+        }
+
+        list.into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickerOption(pub TickerInfo);
+
+impl std::fmt::Display for TickerOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.ticker.symbol_and_exchange_string())
+    }
+}
+
+fn fill_notice(ticker: Ticker, fill: &Fill) -> String {
+    format!(
+        "Paper fill: {} {} {} @ {}",
+        fill.side,
+        fill.qty.to_f64(),
+        ticker.symbol_and_exchange_string(),
+        fill.price.to_f64()
+    )
+}
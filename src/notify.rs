@@ -141,3 +141,64 @@ fn aggregate_toast(base: &Toast, suppressed_count: u32) -> Toast {
         base.status(),
     )
 }
+
+/// Shows a desktop notification through the platform's own tooling, on a separate thread
+/// so a slow or missing notifier doesn't block the UI. Failures are only logged.
+pub fn desktop(title: &str, body: &str) {
+    let (title, body) = (title.to_string(), body.to_string());
+
+    std::thread::spawn(move || {
+        let result = desktop_command(&title, &body).output();
+
+        match result {
+            Ok(output) if !output.status.success() => {
+                log::warn!(
+                    "Desktop notification failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Err(err) => log::warn!("Desktop notification failed: {err}"),
+            Ok(_) => {}
+        }
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn desktop_command(title: &str, body: &str) -> std::process::Command {
+    let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+
+    let mut command = std::process::Command::new("osascript");
+    command.arg("-e").arg(format!(
+        "display notification \"{}\" with title \"{}\"",
+        quote(body),
+        quote(title)
+    ));
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn desktop_command(title: &str, body: &str) -> std::process::Command {
+    let quote = |s: &str| s.replace('\'', "''");
+
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $n = New-Object System.Windows.Forms.NotifyIcon; \
+         $n.Icon = [System.Drawing.SystemIcons]::Information; \
+         $n.Visible = $true; \
+         $n.ShowBalloonTip(5000, '{}', '{}', 'Info'); \
+         Start-Sleep -Seconds 6; $n.Dispose()",
+        quote(title),
+        quote(body)
+    );
+
+    let mut command = std::process::Command::new("powershell");
+    command.args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", &script]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn desktop_command(title: &str, body: &str) -> std::process::Command {
+    let mut command = std::process::Command::new("notify-send");
+    command.args(["--app-name=Flowsurface", title, body]);
+    command
+}
@@ -11,6 +11,7 @@ use crate::{
     connector::{
         ResolvedStream,
         fetcher::{self, FetchedData, InfoKind},
+        stream::session_profile,
     },
     screen::dashboard::tickers_table::TickersTable,
     style,
@@ -19,16 +20,28 @@ use crate::{
 };
 use data::{
     UserTimezone,
-    layout::{WindowSpec, pane::ContentKind},
+    alerts::LevelEdit,
+    audio::TradeSoundHit,
+    chart::Basis,
+    config::accent::{AccentTarget, Accents},
+    external::ExternalEvent,
+    layout::{
+        WindowSpec,
+        pane::{ContentKind, LinkGroup, LinkOptions, PanePreset},
+    },
+    rates::StreamRates,
     stream::PersistStreamKind,
+    trading::{Account, journal::Marker as JournalMarker},
 };
 use exchange::{
-    Kline, PushFrequency, StreamPairKind, TickerInfo, Trade, UnixMs,
+    Kline, MarkPrice, PushFrequency, StreamPairKind, Ticker, TickerInfo, Trade, UnixMs,
     adapter::{
-        AdapterHandles, MAX_KLINE_STREAMS_PER_STREAM, MAX_TRADE_TICKERS_PER_STREAM, StreamConfig,
-        StreamKind, StreamTicksize, UniqueStreams,
+        AdapterHandles, MAX_KLINE_STREAMS_PER_STREAM, MAX_TRADE_TICKERS_PER_STREAM,
+        ReconnectStatus, StreamConfig, StreamKind, StreamTicksize, UniqueStreams,
+        trading::OrderSide,
     },
     depth::Depth,
+    unit::Price,
 };
 
 use iced::{
@@ -38,7 +51,8 @@ use iced::{
         pane_grid::{self, Configuration},
     },
 };
-use std::{collections::HashMap, time::Instant, vec};
+use rustc_hash::FxHashMap;
+use std::{collections::HashMap, sync::Arc, time::Instant, vec};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -50,11 +64,13 @@ pub enum Message {
     DistributeFetchedData {
         layout_id: uuid::Uuid,
         pane_id: uuid::Uuid,
-        stream: StreamKind,
+        stream: Box<StreamKind>,
         data: FetchedData,
     },
     ResolveStreams(uuid::Uuid, Vec<PersistStreamKind>),
     RequestPalette,
+    TimelapseFrame(uuid::Uuid, Option<window::Screenshot>),
+    TimelapseSaved(uuid::Uuid, Toast),
 }
 
 pub struct Dashboard {
@@ -62,7 +78,23 @@ pub struct Dashboard {
     pub focus: Option<(window::Id, pane_grid::Pane)>,
     pub popout: HashMap<window::Id, (pane_grid::State<pane::State>, WindowSpec)>,
     pub streams: UniqueStreams,
+    /// Ladder windows opened from a chart pane, keyed by the chart pane's id
+    paired_ladders: HashMap<uuid::Uuid, window::Id>,
     layout_id: uuid::Uuid,
+    /// Only the groups set to something other than the default
+    pub link_options: HashMap<LinkGroup, LinkOptions>,
+    /// Pane whose crosshair the rest of each group follows
+    crosshair_sources: HashMap<LinkGroup, uuid::Uuid>,
+    /// Of each window, the UI scale included, see [`Dashboard::set_scale_factor`]
+    scale_factors: HashMap<window::Id, f32>,
+    /// Price cross alerts last mirrored onto the charts, see [`Dashboard::set_alert_levels`]
+    alert_levels: Arc<FxHashMap<Ticker, Vec<(u32, f64)>>>,
+    /// Orders and positions last mirrored onto the ladders and charts
+    accounts: Arc<FxHashMap<Ticker, Account>>,
+    /// Logged trades last marked on the kline charts
+    journal: Arc<FxHashMap<Ticker, Vec<JournalMarker>>>,
+    /// Size filter of the tape marking its trades on each group's charts
+    tape_marks: FxHashMap<LinkGroup, f32>,
 }
 
 impl Default for Dashboard {
@@ -72,7 +104,15 @@ impl Default for Dashboard {
             focus: None,
             streams: UniqueStreams::default(),
             popout: HashMap::new(),
+            paired_ladders: HashMap::new(),
             layout_id: uuid::Uuid::new_v4(),
+            link_options: HashMap::new(),
+            crosshair_sources: HashMap::new(),
+            scale_factors: HashMap::new(),
+            alert_levels: Arc::default(),
+            accounts: Arc::default(),
+            journal: Arc::default(),
+            tape_marks: FxHashMap::default(),
         }
     }
 }
@@ -84,13 +124,24 @@ pub enum Event {
         layout_id: uuid::Uuid,
         pane_id: uuid::Uuid,
         data: FetchedData,
-        stream: StreamKind,
+        stream: Box<StreamKind>,
     },
     ResolveStreams {
         pane_id: uuid::Uuid,
         streams: Vec<PersistStreamKind>,
     },
     RequestPalette,
+    AccentChanged(AccentTarget, Option<iced::Color>),
+    /// Price cross alert placed, dragged or removed on a chart
+    AlertLevelEdited(Ticker, LevelEdit),
+    /// Order entry click on a ladder
+    PlaceOrder(TickerInfo, OrderSide, Price),
+    CancelOrders(TickerInfo, Vec<String>),
+    SnapshotRequested {
+        window: window::Id,
+        target: iced::widget::Id,
+        label: String,
+    },
 }
 
 impl Dashboard {
@@ -121,6 +172,7 @@ impl Dashboard {
     pub fn from_config(
         panes: Configuration<pane::State>,
         popout_windows: Vec<(Configuration<pane::State>, WindowSpec)>,
+        link_options: &[(LinkGroup, LinkOptions)],
         layout_id: uuid::Uuid,
     ) -> Self {
         let panes = pane_grid::State::with_configuration(panes);
@@ -139,7 +191,15 @@ impl Dashboard {
             focus: None,
             streams: UniqueStreams::default(),
             popout,
+            paired_ladders: HashMap::new(),
             layout_id,
+            link_options: link_options.iter().copied().collect(),
+            crosshair_sources: HashMap::new(),
+            scale_factors: HashMap::new(),
+            alert_levels: Arc::default(),
+            accounts: Arc::default(),
+            journal: Arc::default(),
+            tape_marks: FxHashMap::default(),
         }
     }
 
@@ -231,8 +291,14 @@ impl Dashboard {
                     }
                 }
                 pane::Message::ClosePane(pane) => {
-                    if let Some((_, sibling)) = self.panes.close(pane) {
+                    if let Some((closed, sibling)) = self.panes.close(pane) {
                         self.focus = Some((window, sibling));
+
+                        if let Some(ladder_window) = self.paired_ladders.remove(&closed.unique_id())
+                            && self.popout.remove(&ladder_window).is_some()
+                        {
+                            return (window::close(ladder_window), None);
+                        }
                     }
                 }
                 pane::Message::MaximizePane(pane) => {
@@ -249,6 +315,8 @@ impl Dashboard {
                     return (self.refresh_streams(main_window.id), None);
                 }
                 pane::Message::VisualConfigChanged(pane, cfg, to_sync) => {
+                    let mut streams_changed = false;
+
                     if to_sync {
                         if let Some(state) = self.get_pane(main_window.id, window, pane) {
                             let studies_cfg = state.content.studies();
@@ -282,6 +350,9 @@ impl Dashboard {
                                             ) | (
                                                 data::layout::pane::VisualConfig::Comparison(_),
                                                 pane::Content::Comparison(_)
+                                            ) | (
+                                                data::layout::pane::VisualConfig::Spread(_),
+                                                pane::Content::Spread(_)
                                             )
                                         ),
                                     };
@@ -289,6 +360,7 @@ impl Dashboard {
                                     if should_apply {
                                         state.settings.visual_config = Some(cfg.clone());
                                         state.content.change_visual_config(cfg.clone());
+                                        streams_changed |= state.sync_mark_price_stream();
 
                                         if let Some(studies) = &studies_cfg {
                                             state.content.update_studies(studies.clone());
@@ -307,12 +379,18 @@ impl Dashboard {
                     } else if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
                         state.settings.visual_config = Some(cfg.clone());
                         state.content.change_visual_config(cfg);
+                        streams_changed = state.sync_mark_price_stream();
+                    }
+
+                    if streams_changed {
+                        return (self.refresh_streams(main_window.id), None);
                     }
                 }
                 pane::Message::SwitchLinkGroup(pane, group) => {
                     if group.is_none() {
                         if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
                             state.link_group = None;
+                            state.request_sync();
                         }
                         return (Task::none(), None);
                     }
@@ -331,6 +409,7 @@ impl Dashboard {
                     if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
                         state.link_group = group;
                         state.modal = None;
+                        state.request_sync();
 
                         if let Some(ticker_info) = maybe_ticker_info
                             && state.stream_pair() != Some(ticker_info)
@@ -361,12 +440,44 @@ impl Dashboard {
                         }
                     }
                 }
+                pane::Message::LinkOptionsChanged(group, options) => {
+                    if options == LinkOptions::default() {
+                        self.link_options.remove(&group);
+                    } else {
+                        self.link_options.insert(group, options);
+                    }
+
+                    self.iter_all_panes_mut(main_window.id)
+                        .filter(|(_, _, state)| state.link_group == Some(group))
+                        .for_each(|(_, _, state)| state.link_options = options);
+                }
                 pane::Message::Popout => {
                     return (self.popout_pane(main_window), None);
                 }
+                pane::Message::PairLadder(pane) => {
+                    return (self.open_paired_ladder(main_window, window, pane), None);
+                }
+                pane::Message::DuplicatePane(pane) => {
+                    return (self.duplicate_pane(main_window, window, pane), None);
+                }
                 pane::Message::Merge => {
                     return (self.merge_pane(main_window), None);
                 }
+                pane::Message::Snapshot(pane) => {
+                    if let Some(state) = self.get_pane(main_window.id, window, pane) {
+                        return (
+                            Task::none(),
+                            Some(Event::SnapshotRequested {
+                                window,
+                                target: state.snapshot_id(),
+                                label: state.snapshot_label(),
+                            }),
+                        );
+                    }
+                }
+                pane::Message::SetAccent(target, color) => {
+                    return (Task::none(), Some(Event::AccentChanged(target, color)));
+                }
                 pane::Message::PaneEvent(pane, local) => {
                     if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
                         let Some(effect) = state.update(local) else {
@@ -407,6 +518,35 @@ impl Dashboard {
                             pane::Effect::FocusWidget(id) => {
                                 return (iced::widget::operation::focus(id), None);
                             }
+                            pane::Effect::EditAlertLevel(ticker, edit) => {
+                                return (Task::none(), Some(Event::AlertLevelEdited(ticker, edit)));
+                            }
+                            pane::Effect::PlaceOrder(ticker_info, side, price) => {
+                                return (
+                                    Task::none(),
+                                    Some(Event::PlaceOrder(ticker_info, side, price)),
+                                );
+                            }
+                            pane::Effect::CancelOrders(ticker_info, ids) => {
+                                return (Task::none(), Some(Event::CancelOrders(ticker_info, ids)));
+                            }
+                            pane::Effect::CopyToClipboard(contents) => {
+                                return (iced::clipboard::write(contents), None);
+                            }
+                            pane::Effect::ShareCrosshair(group, crosshair) => {
+                                let source = state.unique_id();
+                                self.share_crosshair(main_window.id, group, source, crosshair);
+                                return (Task::none(), None);
+                            }
+                            pane::Effect::ShareBasis(group, basis) => {
+                                let source = state.unique_id();
+                                self.share_basis(main_window.id, group, source, basis)
+                            }
+                            pane::Effect::ShareTimespan(group, span) => {
+                                let source = state.unique_id();
+                                self.share_timespan(main_window.id, group, source, span);
+                                return (Task::none(), None);
+                            }
                         };
                         return (task, None);
                     }
@@ -415,6 +555,20 @@ impl Dashboard {
             Message::RequestPalette => {
                 return (Task::none(), Some(Event::RequestPalette));
             }
+            Message::TimelapseFrame(pane_id, shot) => {
+                // Nothing comes back while the pane is hidden, e.g. behind a maximized one
+                if let Some(shot) = shot
+                    && let Some(state) = self.get_mut_pane_state_by_uuid(main_window.id, pane_id)
+                {
+                    state.push_timelapse_frame(window::into_capture(shot));
+                }
+            }
+            Message::TimelapseSaved(pane_id, toast) => {
+                match self.get_mut_pane_state_by_uuid(main_window.id, pane_id) {
+                    Some(state) => state.notifications.push(toast),
+                    None => return (Task::none(), Some(Event::Notification(toast))),
+                }
+            }
             Message::ChangePaneStatus(pane_id, status) => {
                 if let Some(pane_state) = self.get_mut_pane_state_by_uuid(main_window.id, pane_id) {
                     pane_state.status = status;
@@ -508,7 +662,7 @@ impl Dashboard {
 
     fn popout_pane(&mut self, main_window: &Window) -> Task<Message> {
         if let Some((_, id)) = self.focus.take()
-            && let Some((pane, _)) = self.panes.close(id)
+            && let Some((mut pane, _)) = self.panes.close(id)
         {
             let (window, task) = window::open(window::Settings {
                 position: main_window
@@ -520,6 +674,7 @@ impl Dashboard {
                 ..window::settings()
             });
 
+            pane.request_sync();
             let (state, id) = pane_grid::State::new(pane);
             self.popout.insert(window, (state, WindowSpec::default()));
 
@@ -531,13 +686,118 @@ impl Dashboard {
         Task::none()
     }
 
+    /// Opens a slim always-on-top ladder for the pane's ticker. Both panes end up in the
+    /// same link group so switching tickers on either one moves the other along, and the
+    /// ladder subscribes through the shared streams like any other pane
+    fn open_paired_ladder(
+        &mut self,
+        main_window: &Window,
+        window: window::Id,
+        pane: pane_grid::Pane,
+    ) -> Task<Message> {
+        let Some(source) = self.get_pane(main_window.id, window, pane) else {
+            return Task::none();
+        };
+        let Some(ticker_info) = source.stream_pair() else {
+            return Task::done(Message::Notification(Toast::warn(
+                "Select a ticker before opening a paired ladder".to_string(),
+            )));
+        };
+        let source_id = source.unique_id();
+
+        if let Some(&ladder_window) = self.paired_ladders.get(&source_id)
+            && self.popout.contains_key(&ladder_window)
+        {
+            return window::gain_focus(ladder_window);
+        }
+
+        let link_group = source.link_group.or_else(|| {
+            let taken = self
+                .iter_all_panes(main_window.id)
+                .filter_map(|(_, _, state)| state.link_group)
+                .collect::<Vec<_>>();
+
+            LinkGroup::ALL
+                .into_iter()
+                .find(|group| !taken.contains(group))
+        });
+
+        if link_group.is_none() {
+            return Task::done(Message::Notification(Toast::warn(
+                "All link groups are in use, free one up to pair a ladder".to_string(),
+            )));
+        }
+
+        if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
+            state.link_group = link_group;
+        }
+
+        let mut ladder = pane::State::new();
+        ladder.link_group = link_group;
+        let streams = ladder.set_content_and_streams(vec![ticker_info], ContentKind::Ladder);
+        self.streams.extend(streams.iter());
+
+        let (ladder_window, task) = window::open(window::Settings {
+            size: iced::Size::new(280.0, 720.0),
+            position: main_window
+                .position
+                .map(|point| window::Position::Specific(point + Vector::new(40.0, 40.0)))
+                .unwrap_or_default(),
+            level: window::Level::AlwaysOnTop,
+            exit_on_close_request: false,
+            min_size: Some(iced::Size::new(200.0, 300.0)),
+            ..window::settings()
+        });
+
+        let (state, id) = pane_grid::State::new(ladder);
+        self.popout
+            .insert(ladder_window, (state, WindowSpec::default()));
+        self.paired_ladders.insert(source_id, ladder_window);
+
+        task.then(move |window| Task::done(Message::Pane(window, pane::Message::PaneClicked(id))))
+    }
+
+    /// Splits the pane and fills the new slot with a copy of it. The copy is rebuilt from
+    /// the pane's persisted form, so content, tickers, settings, indicators and link group
+    /// match, and shares its streams, which stay subscribed while either pane uses them
+    fn duplicate_pane(
+        &mut self,
+        main_window: &Window,
+        window: window::Id,
+        pane: pane_grid::Pane,
+    ) -> Task<Message> {
+        let Some(source) = self.get_pane(main_window.id, window, pane) else {
+            return Task::none();
+        };
+
+        let Configuration::Pane(mut copy) = crate::layout::configuration(data::Pane::from(source))
+        else {
+            return Task::none();
+        };
+        copy.adopt_loaded(source);
+
+        let panes = if window == main_window.id {
+            &mut self.panes
+        } else if let Some((panes, _)) = self.popout.get_mut(&window) {
+            panes
+        } else {
+            return Task::none();
+        };
+
+        match panes.split(pane_grid::Axis::Vertical, pane, copy) {
+            Some((new_pane, _)) => self.focus_pane(window, new_pane),
+            None => Task::none(),
+        }
+    }
+
     fn merge_pane(&mut self, main_window: &Window) -> Task<Message> {
         if let Some((window, pane)) = self.focus.take()
-            && let Some(pane_state) = self
+            && let Some(mut pane_state) = self
                 .popout
                 .remove(&window)
                 .and_then(|(mut panes, _)| panes.panes.remove(&pane))
         {
+            pane_state.request_sync();
             let task = self.new_pane(pane_grid::Axis::Horizontal, main_window, Some(pane_state));
 
             return Task::batch(vec![window::close(window), task]);
@@ -598,6 +858,113 @@ impl Dashboard {
             }))
     }
 
+    /// Tickers of the panes showing orders or positions, in every window
+    pub fn traded_tickers(&self, main_window: window::Id) -> Vec<TickerInfo> {
+        let mut tickers: Vec<TickerInfo> = vec![];
+
+        for ticker_info in self
+            .iter_all_panes(main_window)
+            .filter_map(|(_, _, state)| state.traded_ticker())
+        {
+            if !tickers.iter().any(|info| info.ticker == ticker_info.ticker) {
+                tickers.push(ticker_info);
+            }
+        }
+        tickers
+    }
+
+    /// Pans the kline charts of `ticker` to a journal entry, returns whether there were any
+    pub fn focus_journal_entry(
+        &mut self,
+        ticker: Ticker,
+        time: UnixMs,
+        price: Price,
+        main_window: window::Id,
+    ) -> bool {
+        let mut found = false;
+        for (_, _, state) in self.iter_all_panes_mut(main_window) {
+            found |= state.focus_journal_entry(ticker, time, price);
+        }
+        found
+    }
+
+    /// Marks the candle hovered on `source` on the other panes of `group`
+    fn share_crosshair(
+        &mut self,
+        main_window: window::Id,
+        group: LinkGroup,
+        source: uuid::Uuid,
+        crosshair: Option<chart::LinkedCrosshair>,
+    ) {
+        match crosshair {
+            Some(_) => {
+                self.crosshair_sources.insert(group, source);
+            }
+            // Left for another pane of the group, which took over already
+            None if self.crosshair_sources.get(&group) != Some(&source) => return,
+            None => {
+                self.crosshair_sources.remove(&group);
+            }
+        }
+
+        let share_price = self
+            .link_options
+            .get(&group)
+            .is_some_and(|options| options.crosshair_price);
+        let crosshair = crosshair.map(|crosshair| chart::LinkedCrosshair {
+            price: crosshair.price.filter(|_| share_price),
+            ..crosshair
+        });
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| {
+                if state.link_group == Some(group) && state.unique_id() != source {
+                    state.sync_linked_crosshair(crosshair);
+                }
+            });
+    }
+
+    /// Applies `basis` to `source` and to every other pane of `group` it suits
+    fn share_basis(
+        &self,
+        main_window: window::Id,
+        group: LinkGroup,
+        source: uuid::Uuid,
+        basis: Basis,
+    ) -> Task<Message> {
+        let tasks = self
+            .iter_all_panes(main_window)
+            .filter(|(_, _, state)| {
+                state.unique_id() == source
+                    || state.link_group == Some(group) && state.accepts_basis(basis)
+            })
+            .map(|(window, pane, _)| {
+                Task::done(Message::Pane(
+                    window,
+                    pane::Message::PaneEvent(pane, pane::Event::LinkedBasis(basis)),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        Task::batch(tasks)
+    }
+
+    /// Shows the time span of `source` on the other panes of `group`
+    fn share_timespan(
+        &mut self,
+        main_window: window::Id,
+        group: LinkGroup,
+        source: uuid::Uuid,
+        span: (UnixMs, UnixMs),
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| {
+                if state.link_group == Some(group) && state.unique_id() != source {
+                    state.sync_linked_timespan(span);
+                }
+            });
+    }
+
     fn iter_all_panes_mut(
         &mut self,
         main_window: window::Id,
@@ -616,7 +983,9 @@ impl Dashboard {
         &'a self,
         main_window: &'a Window,
         tickers_table: &'a TickersTable,
+        stream_rates: &'a StreamRates,
         timezone: UserTimezone,
+        accents: &'a Accents,
     ) -> Element<'a, Message> {
         let pane_grid: Element<_> = PaneGrid::new(&self.panes, |id, pane, maximized| {
             let is_focused = self.focus == Some((main_window.id, id));
@@ -629,6 +998,8 @@ impl Dashboard {
                 main_window,
                 timezone,
                 tickers_table,
+                stream_rates,
+                accents,
             )
         })
         .min_size(240)
@@ -647,7 +1018,9 @@ impl Dashboard {
         window: window::Id,
         main_window: &'a Window,
         tickers_table: &'a TickersTable,
+        stream_rates: &'a StreamRates,
         timezone: UserTimezone,
+        accents: &'a Accents,
     ) -> Element<'a, Message> {
         if let Some((state, _)) = self.popout.get(&window) {
             let content = container(
@@ -662,6 +1035,8 @@ impl Dashboard {
                         main_window,
                         timezone,
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 })
                 .on_click(pane::Message::PaneClicked),
@@ -792,6 +1167,25 @@ impl Dashboard {
         )))
     }
 
+    /// Sets up the focused pane like [`Self::init_focused_pane`], then applies the preset
+    pub fn init_focused_preset(
+        &mut self,
+        handles: &AdapterHandles,
+        main_window: window::Id,
+        ticker_info: TickerInfo,
+        preset: PanePreset,
+    ) -> Task<Message> {
+        let task = self.init_focused_pane(handles, main_window, ticker_info, preset.content_kind());
+
+        if let Some((window, selected_pane)) = self.focus
+            && let Some(state) = self.get_mut_pane(main_window, window, selected_pane)
+        {
+            state.content.apply_preset(preset);
+        }
+
+        task
+    }
+
     pub fn switch_tickers_in_group(
         &mut self,
         handles: &AdapterHandles,
@@ -924,6 +1318,12 @@ impl Dashboard {
                     }
                 }
             }
+            FetchedData::Funding { data, req_id } => {
+                if let Some(pane_state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
+                    pane_state.status = pane::Status::Ready;
+                    pane_state.insert_hist_funding(req_id, &data);
+                }
+            }
         }
 
         Task::none()
@@ -987,11 +1387,18 @@ impl Dashboard {
                 if pane_state.matches_stream(stream) {
                     match &mut pane_state.content {
                         pane::Content::Kline { chart: Some(c), .. } => {
-                            c.update_latest_kline(kline);
+                            if c.overlay_stream() == Some(*stream) {
+                                c.update_overlay_kline(kline);
+                            } else {
+                                c.update_latest_kline(kline);
+                            }
                         }
                         pane::Content::Comparison(Some(c)) => {
                             c.update_latest_kline(&stream.ticker_info(), kline);
                         }
+                        pane::Content::Spread(Some(c)) => {
+                            c.update_latest_kline(&stream.ticker_info(), kline);
+                        }
                         _ => {}
                     }
                     found_match = true;
@@ -1006,6 +1413,117 @@ impl Dashboard {
         }
     }
 
+    pub fn update_mark_price(
+        &mut self,
+        stream: &StreamKind,
+        mark_price: &MarkPrice,
+        main_window: window::Id,
+    ) -> Task<Message> {
+        let mut found_match = false;
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, pane_state)| {
+                if pane_state.matches_stream(stream) {
+                    if let pane::Content::Kline { chart: Some(c), .. } = &mut pane_state.content {
+                        c.insert_mark_price(mark_price);
+                    }
+                    found_match = true;
+                }
+            });
+
+        if found_match {
+            Task::none()
+        } else {
+            log::debug!("{stream:?} stream had no matching panes - dropping");
+            self.refresh_streams(main_window)
+        }
+    }
+
+    pub fn set_reconnecting(
+        &mut self,
+        streams: &[StreamKind],
+        status: ReconnectStatus,
+        main_window: window::Id,
+    ) {
+        let retry_at = Instant::now() + status.delay;
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, pane_state)| {
+                if streams
+                    .iter()
+                    .any(|stream| pane_state.matches_stream(stream))
+                {
+                    pane_state.status = pane::Status::Reconnecting {
+                        retry_at,
+                        secs_left: status.delay.as_secs(),
+                        attempt: status.attempt,
+                        exhausted: status.exhausted,
+                    };
+                }
+            });
+    }
+
+    pub fn clear_reconnecting(&mut self, streams: &[StreamKind], main_window: window::Id) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, pane_state)| {
+                if matches!(pane_state.status, pane::Status::Reconnecting { .. })
+                    && streams
+                        .iter()
+                        .any(|stream| pane_state.matches_stream(stream))
+                {
+                    pane_state.status = pane::Status::Ready;
+                }
+            });
+    }
+
+    /// Lets kline charts fed by `streams` refetch what was missed while disconnected,
+    /// footprints their trades too.
+    ///
+    /// Heatmaps and tapes keep the gap: venues have no order book history to refetch, and
+    /// a tape only lists what was received since it was opened.
+    pub fn backfill_gap(
+        &mut self,
+        streams: &[StreamKind],
+        gap_start: UnixMs,
+        main_window: window::Id,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, pane_state)| {
+                if !streams
+                    .iter()
+                    .any(|stream| pane_state.matches_stream(stream))
+                {
+                    return;
+                }
+
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut pane_state.content {
+                    c.backfill_gap(gap_start);
+                }
+            });
+    }
+
+    /// Adds the event as a marker to every chart of its ticker, returns how many matched
+    pub fn annotate(&mut self, event: &ExternalEvent, main_window: window::Id) -> usize {
+        let mut matched = 0;
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, pane_state)| {
+                if !pane_state
+                    .stream_pair()
+                    .is_some_and(|ticker_info| event.matches(&ticker_info))
+                {
+                    return;
+                }
+
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut pane_state.content {
+                    c.add_annotation(event.clone());
+                    matched += 1;
+                }
+            });
+
+        matched
+    }
+
     pub fn ingest_depth(
         &mut self,
         stream: &StreamKind,
@@ -1018,27 +1536,20 @@ impl Dashboard {
         self.iter_all_panes_mut(main_window)
             .for_each(|(_, _, pane_state)| {
                 if pane_state.matches_stream(stream) {
-                    match &mut pane_state.content {
-                        pane::Content::Heatmap { chart, .. } => {
-                            if let Some(c) = chart {
-                                c.insert_depth(depth, update_t);
-                            }
-                        }
-                        pane::Content::ShaderHeatmap { chart, .. } => {
-                            if let Some(c) = chart {
-                                c.insert_depth(depth, update_t);
-                            }
-                        }
-                        pane::Content::Ladder(panel) => {
-                            if let Some(panel) = panel {
-                                panel.insert_depth(depth, update_t);
-                            }
-                        }
-                        _ => {
-                            log::error!("No chart found for the stream: {stream:?}");
-                        }
-                    }
                     found_match = true;
+                    pane_state.record_depth(stream, update_t, depth);
+
+                    // Live updates would interleave with the recording being played
+                    if pane_state.is_replaying() {
+                        return;
+                    }
+
+                    let composite = pane_state.merge_composite_depth(stream, depth);
+                    let depth = composite.as_deref().unwrap_or(depth);
+
+                    if !pane_state.content.insert_depth(depth, update_t) {
+                        log::error!("No chart found for the stream: {stream:?}");
+                    }
                 }
             });
 
@@ -1055,50 +1566,38 @@ impl Dashboard {
         buffer: &[Trade],
         update_t: UnixMs,
         main_window: window::Id,
-    ) -> Task<Message> {
+    ) -> (Task<Message>, Option<TradeSoundHit>) {
         let mut found_match = false;
+        // Panes on the same stream share one sound, the strongest of them
+        let mut sound = None;
 
         self.iter_all_panes_mut(main_window)
             .for_each(|(_, _, pane_state)| {
                 if pane_state.matches_stream(stream) {
-                    match &mut pane_state.content {
-                        pane::Content::Heatmap { chart, .. } => {
-                            if let Some(c) = chart {
-                                c.insert_trades(buffer, update_t);
-                            }
-                        }
-                        pane::Content::ShaderHeatmap { chart, .. } => {
-                            if let Some(c) = chart {
-                                c.insert_trades(buffer, update_t);
-                            }
-                        }
-                        pane::Content::Kline { chart, .. } => {
-                            if let Some(c) = chart {
-                                c.insert_trades(buffer);
-                            }
-                        }
-                        pane::Content::TimeAndSales(panel) => {
-                            if let Some(p) = panel {
-                                p.insert_buffer(buffer);
-                            }
-                        }
-                        pane::Content::Ladder(panel) => {
-                            if let Some(p) = panel {
-                                p.insert_trades(buffer);
-                            }
-                        }
-                        _ => {
-                            log::error!("No chart found for the stream: {stream:?}");
-                        }
-                    }
                     found_match = true;
+                    pane_state.record_trades(stream, update_t, buffer);
+
+                    if pane_state.is_replaying() {
+                        return;
+                    }
+
+                    if !pane_state
+                        .content
+                        .insert_trades(stream.ticker_info(), buffer, update_t)
+                    {
+                        log::error!("No chart found for the stream: {stream:?}");
+                    }
+
+                    sound = sound
+                        .max(pane_state.trade_sound_hit(stream.ticker_info(), buffer))
+                        .max(pane_state.flow_signal_hit(stream.ticker_info()));
                 }
             });
 
         if found_match {
-            Task::none()
+            (Task::none(), sound)
         } else {
-            self.refresh_streams(main_window)
+            (self.refresh_streams(main_window), sound)
         }
     }
 
@@ -1114,11 +1613,72 @@ impl Dashboard {
             .for_each(|(_, _, state)| state.park_for_inactive_layout());
     }
 
+    /// Pushes the scale factor of `window` to the panes it shows, once it changed
+    pub fn set_scale_factor(
+        &mut self,
+        main_window: window::Id,
+        window: window::Id,
+        scale_factor: f32,
+    ) {
+        if self.scale_factors.insert(window, scale_factor) == Some(scale_factor) {
+            return;
+        }
+
+        self.iter_all_panes_mut(main_window)
+            .filter(|(pane_window, _, _)| *pane_window == window)
+            .for_each(|(_, _, state)| state.sync_scale_factor(scale_factor));
+    }
+
+    /// Mirrors price cross alerts onto the charts, once one was added, moved or removed
+    pub fn set_alert_levels(
+        &mut self,
+        main_window: window::Id,
+        levels: Arc<FxHashMap<Ticker, Vec<(u32, f64)>>>,
+    ) {
+        if Arc::ptr_eq(&self.alert_levels, &levels) {
+            return;
+        }
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| state.sync_alert_levels(&levels));
+        self.alert_levels = levels;
+    }
+
+    /// Mirrors orders and positions onto the ladders and charts, once the trading state changed
+    pub fn set_accounts(
+        &mut self,
+        main_window: window::Id,
+        accounts: Arc<FxHashMap<Ticker, Account>>,
+    ) {
+        if Arc::ptr_eq(&self.accounts, &accounts) {
+            return;
+        }
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| state.sync_account(&accounts));
+        self.accounts = accounts;
+    }
+
+    /// Marks logged trades on the kline charts, once the journal changed
+    pub fn set_journal_markers(
+        &mut self,
+        main_window: window::Id,
+        markers: Arc<FxHashMap<Ticker, Vec<JournalMarker>>>,
+    ) {
+        if Arc::ptr_eq(&self.journal, &markers) {
+            return;
+        }
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| state.sync_journal(&markers));
+        self.journal = markers;
+    }
+
     pub fn tick(
         &mut self,
         handles: &AdapterHandles,
         now: Instant,
-        _main_window: window::Id,
+        main_window: window::Id,
     ) -> Task<Message> {
         let mut tasks = vec![];
 
@@ -1177,22 +1737,80 @@ impl Dashboard {
             None => {}
         };
 
+        let tape_marks = self
+            .iter_all_panes(main_window)
+            .filter_map(|(_, _, state)| state.tape_marks())
+            .collect::<FxHashMap<_, _>>();
+        let marks_changed = tape_marks != self.tape_marks;
+        if marks_changed {
+            self.tape_marks = tape_marks;
+        }
+
         // tick only the maximized pane if there is any, otherwise tick all panes
         let maximized_pane = self.panes.maximized();
+        let scale_factor = |window| self.scale_factors.get(&window).copied().unwrap_or(1.0);
+        let main_scale = scale_factor(main_window);
         for (pane_id, state) in self.panes.iter_mut() {
             if maximized_pane.is_some_and(|maximized| *pane_id != maximized) {
                 continue;
             }
 
+            let flagged = state.take_sync_request();
+            if flagged {
+                state.sync_scale_factor(main_scale);
+                state.sync_alert_levels(&self.alert_levels);
+                state.sync_account(&self.accounts);
+                state.sync_journal(&self.journal);
+                state.sync_drawings();
+                state.link_options = state
+                    .link_group
+                    .and_then(|group| self.link_options.get(&group).copied())
+                    .unwrap_or_default();
+            }
+            if flagged || marks_changed {
+                state.sync_tape_marks(&self.tape_marks);
+            }
             tick_state(state);
         }
 
-        for (popout_state, _) in self.popout.values_mut() {
+        for (window_id, (popout_state, _)) in self.popout.iter_mut() {
+            let popout_scale = scale_factor(*window_id);
             for (_, state) in popout_state.iter_mut() {
+                let flagged = state.take_sync_request();
+                if flagged {
+                    state.sync_scale_factor(popout_scale);
+                    state.sync_alert_levels(&self.alert_levels);
+                    state.sync_account(&self.accounts);
+                    state.sync_journal(&self.journal);
+                    state.sync_drawings();
+                    state.link_options = state
+                        .link_group
+                        .and_then(|group| self.link_options.get(&group).copied())
+                        .unwrap_or_default();
+                }
+                if flagged || marks_changed {
+                    state.sync_tape_marks(&self.tape_marks);
+                }
                 tick_state(state);
             }
         }
 
+        for (window_id, _, state) in self.iter_all_panes_mut(main_window) {
+            if state.timelapse_due(now) {
+                let pane_id = state.unique_id();
+                tasks.push(
+                    window::capture_widget(window_id, state.snapshot_id())
+                        .map(move |shot| Message::TimelapseFrame(pane_id, shot)),
+                );
+            }
+            if let Some(outcome) = state.take_timelapse_outcome() {
+                let pane_id = state.unique_id();
+                tasks.push(Task::perform(outcome, move |outcome| {
+                    Message::TimelapseSaved(pane_id, pane::State::timelapse_toast(outcome.ok()))
+                }));
+            }
+        }
+
         Task::batch(tasks)
     }
 
@@ -1204,6 +1822,7 @@ impl Dashboard {
     ) -> Task<Message> {
         if let Some(state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
             state.streams = ResolvedStream::Ready(streams.clone());
+            state.request_sync();
         }
         self.refresh_streams(main_window)
     }
@@ -1286,6 +1905,28 @@ impl Dashboard {
                     }
                 }
 
+                if !specs.mark_price.is_empty() {
+                    let mark_price_subs = specs
+                        .mark_price
+                        .chunks(MAX_TRADE_TICKERS_PER_STREAM)
+                        .map(|tickers| {
+                            let config = StreamConfig::new(
+                                tickers.to_vec(),
+                                exchange,
+                                None,
+                                PushFrequency::ServerDefault,
+                            );
+
+                            let data = (handles.clone(), config);
+                            Subscription::run_with(data, |data| data.0.mark_price_stream(&data.1))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !mark_price_subs.is_empty() {
+                        subs.push(Subscription::batch(mark_price_subs));
+                    }
+                }
+
                 subs
             })
             .collect::<Vec<Subscription<exchange::Event>>>();
@@ -1300,10 +1941,20 @@ impl Dashboard {
             });
     }
 
+    /// Re-subscribes with whatever the current [`SessionProfile`] still allows
+    ///
+    /// [`SessionProfile`]: data::config::session::SessionProfile
+    pub fn apply_session_profile(&mut self, main_window: window::Id) -> Task<Message> {
+        self.refresh_streams(main_window)
+    }
+
     fn refresh_streams(&mut self, main_window: window::Id) -> Task<Message> {
+        let profile = session_profile();
+
         let all_pane_streams = self
             .iter_all_panes(main_window)
-            .flat_map(|(_, _, pane_state)| pane_state.streams.ready_iter().into_iter().flatten());
+            .flat_map(|(_, _, pane_state)| pane_state.streams.ready_iter().into_iter().flatten())
+            .filter(|stream| profile.allows_stream(stream));
         self.streams = UniqueStreams::from(all_pane_streams);
 
         Task::none()
@@ -1,8 +1,12 @@
 use crate::{
-    chart::{self, comparison::ComparisonChart, heatmap::HeatmapChart, kline::KlineChart},
+    chart::{
+        self, LinkedCrosshair, comparison::ComparisonChart, heatmap::HeatmapChart,
+        kline::KlineChart, spread::SpreadChart,
+    },
     connector::{
         ResolvedStream,
         fetcher::{FetchSpec, InfoKind},
+        stream::session_profile,
     },
     modal::{
         self, ModifierKind,
@@ -11,6 +15,7 @@ use crate::{
             mini_tickers_list::MiniPanel,
             settings::{
                 comparison_cfg_view, heatmap_cfg_view, heatmap_shader_cfg_view, kline_cfg_view,
+                spread_cfg_view,
             },
             stack_modal,
         },
@@ -21,31 +26,69 @@ use crate::{
     },
     style::{self, Icon, icon_text},
     widget::{
-        self, button_with_tooltip, chart::heatmap::HeatmapShader, column_drag, link_group_button,
-        toast::Toast,
+        self, button_with_tooltip,
+        chart::heatmap::HeatmapShader,
+        column_drag, link_group_button,
+        toast::{self, Toast},
     },
     window::{self, Window},
 };
 use data::{
     UserTimezone,
+    alerts::LevelEdit,
+    audio::{BurstTracker, TradeSound, TradeSoundHit},
     chart::{
         Basis, ViewConfig,
+        drawing::{Drawing, MAX_NOTE_CHARS, Marker, Note, Snap, Tool},
         heatmap::HeatmapStudy,
-        indicator::{HeatmapIndicator, Indicator, KlineIndicator, UiIndicator},
+        indicator::{
+            HeatmapIndicator, Indicator, IndicatorSettings, KlineIndicator, KlineIndicatorInstance,
+            MovingAverage, UiIndicator, VolatilityBand,
+        },
+        kline::ProfileRange,
+        vwap::VwapConfig,
+    },
+    config::accent::{AccentTarget, Accents},
+    layout::{
+        pane::{
+            Appearance, ContentKind, LinkGroup, LinkOptions, PanePreset, PaneSetup, Settings,
+            VisualConfig,
+        },
+        template::{IndicatorTemplate, IndicatorTemplates},
     },
-    layout::pane::{ContentKind, LinkGroup, PaneSetup, Settings, VisualConfig},
+    rates::StreamRates,
+    replay::{self, Recorder, Replay},
+    snapshot::{Capture, Recorded, Timelapse, TimelapseConfig},
     stream::PersistStreamKind,
+    trading::{Account, journal::Marker as JournalMarker},
 };
 use exchange::{
-    Kline, OpenInterest, StreamPairKind, TickMultiplier, TickerInfo, Timeframe,
-    adapter::{MarketKind, StreamKind, StreamTicksize},
-    unit::PriceStep,
+    FundingRate, Kline, OpenInterest, SerTicker, StreamPairKind, TickMultiplier, Ticker,
+    TickerInfo, Timeframe, Trade, UnixMs,
+    adapter::{CompositeSources, MarketKind, StreamKind, StreamTicksize, trading::OrderSide},
+    depth::{CompositeDepth, Depth},
+    unit::{Price, PriceStep},
 };
 use iced::{
-    Alignment, Element, Length, Renderer, Theme, padding,
-    widget::{button, center, column, container, pane_grid, pick_list, row, text, tooltip},
+    Alignment, Element, Length, Renderer, Theme,
+    futures::channel::oneshot,
+    padding,
+    widget::{
+        button, center, checkbox, column, container, pane_grid, pick_list, row, rule, slider,
+        space, stack, text, tooltip,
+    },
 };
-use std::time::Instant;
+use rustc_hash::FxHashMap;
+use std::{
+    io,
+    sync::{Arc, mpsc},
+    time::{Duration, Instant},
+};
+
+const NOTE_INPUT_ID: &str = "note_text_input";
+
+/// Outlasts the retries of a single request, see `connector::fetcher`
+const STALLED_STATUS_AFTER: Duration = Duration::from_secs(120);
 
 #[derive(Debug, Clone)]
 pub enum Effect {
@@ -53,6 +96,16 @@ pub enum Effect {
     RequestFetch(Vec<FetchSpec>),
     SwitchTickersInGroup(TickerInfo),
     FocusWidget(iced::widget::Id),
+    EditAlertLevel(Ticker, LevelEdit),
+    PlaceOrder(TickerInfo, OrderSide, Price),
+    CancelOrders(TickerInfo, Vec<String>),
+    CopyToClipboard(String),
+    /// Hovered candle of the pane's chart, for the other panes of its link group
+    ShareCrosshair(LinkGroup, Option<LinkedCrosshair>),
+    /// Basis picked on the pane, applied to every pane of its link group it suits
+    ShareBasis(LinkGroup, Basis),
+    /// Earliest and latest time the pane's chart shows, for the other panes of its link group
+    ShareTimespan(LinkGroup, (UnixMs, UnixMs)),
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -61,6 +114,15 @@ pub enum Status {
     Ready,
     Loading(InfoKind),
     Stale(String),
+    Reconnecting {
+        retry_at: Instant,
+        /// Countdown shown until `retry_at`, advanced by [`State::tick`]
+        secs_left: u64,
+        attempt: u32,
+        exhausted: bool,
+    },
+    /// Stuck loading for too long, waits for the user to retry
+    TimedOut(InfoKind),
 }
 
 pub enum Action {
@@ -82,7 +144,15 @@ pub enum Message {
     ReplacePane(pane_grid::Pane),
     Popout,
     Merge,
+    /// Opens an always-on-top ladder window following the pane's ticker
+    PairLadder(pane_grid::Pane),
+    /// Splits off a copy of the pane with its settings and loaded data
+    DuplicatePane(pane_grid::Pane),
+    /// Captures the pane's content to a PNG
+    Snapshot(pane_grid::Pane),
+    SetAccent(AccentTarget, Option<iced::Color>),
     SwitchLinkGroup(pane_grid::Pane, Option<LinkGroup>),
+    LinkOptionsChanged(LinkGroup, LinkOptions),
     VisualConfigChanged(pane_grid::Pane, VisualConfig, bool),
     PaneEvent(pane_grid::Pane, Event),
 }
@@ -95,15 +165,60 @@ pub enum Event {
     ChartInteraction(super::chart::Message),
     PanelInteraction(super::panel::Message),
     ToggleIndicator(UiIndicator),
+    /// Removes one of the kline chart's indicator panels
+    RemoveIndicator(uuid::Uuid),
     DeleteNotification(usize),
     ReorderIndicator(column_drag::DragEvent),
     ClusterKindSelected(data::chart::kline::ClusterKind),
     ClusterScalingSelected(data::chart::kline::ClusterScaling),
     StudyConfigurator(modal::pane::settings::study::StudyMessage),
     StreamModifierChanged(modal::stream::Message),
+    /// Basis picked on a pane of the link group, see [`LinkOptions::basis`]
+    LinkedBasis(Basis),
     ComparisonChartInteraction(super::chart::comparison::Message),
+    SpreadChartInteraction(super::chart::spread::Message),
     HeatmapShaderInteraction(crate::widget::chart::heatmap::Message),
     MiniTickersListInteraction(modal::pane::mini_tickers_list::Message),
+    AppearanceChanged(Appearance),
+    MovingAveragesChanged(Vec<MovingAverage>),
+    BandsChanged(Vec<VolatilityBand>),
+    VwapChanged(Option<VwapConfig>),
+    ProfileRangesChanged(Vec<ProfileRange>),
+    ScriptsChanged(Vec<String>),
+    ReloadScripts,
+    OpenScriptsFolder,
+    IndicatorSettingsChanged(uuid::Uuid, IndicatorSettings),
+    TemplateNameChanged(String),
+    SaveIndicatorTemplate,
+    ApplyIndicatorTemplate(String),
+    RemoveIndicatorTemplate(String),
+    ExportDepth,
+    ExportTape,
+    ClearDrawings,
+    DrawingSnapChanged(Snap),
+    NoteEdited(usize, NoteEdit),
+    RetryFetch,
+    ToggleTimelapse,
+    ToggleRecording,
+    OpenReplay,
+    Replay(ReplayControl),
+}
+
+#[derive(Debug, Clone)]
+pub enum NoteEdit {
+    Text(String),
+    Marker(Marker),
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReplayControl {
+    TogglePlay,
+    CycleSpeed,
+    /// Seek bar dragged, applied on release
+    Scrub(f32),
+    Seek,
+    Close,
 }
 
 pub struct State {
@@ -115,6 +230,29 @@ pub struct State {
     pub streams: ResolvedStream,
     pub status: Status,
     pub link_group: Option<LinkGroup>,
+    /// Those of `link_group`, mirrored by the dashboard which keeps them
+    pub link_options: LinkOptions,
+    /// Last crosshair shared with the link group, so moves within a candle aren't sent again
+    shared_crosshair: Option<LinkedCrosshair>,
+    composite_depth: Option<CompositeDepth>,
+    recorder: Option<Recorder>,
+    /// While set, live events are dropped and the pane is fed from the recording instead
+    replay: Option<Replay>,
+    /// Frames of the heatmap time-lapse in progress, encoded on a thread of its own, with
+    /// when the last one was requested
+    timelapse: Option<(mpsc::Sender<Capture>, Option<Instant>)>,
+    /// Outcome of the time-lapse thread, until the dashboard waits on it
+    timelapse_outcome: Option<oneshot::Receiver<io::Result<Recorded>>>,
+    /// Loading status last seen by the watchdog, and since when it hasn't changed
+    loading_since: Option<(InfoKind, Instant)>,
+    burst_tracker: BurstTracker,
+    /// Read from disk whenever the indicators modal opens
+    indicator_templates: IndicatorTemplates,
+    /// Name typed for the next template to save
+    template_name: String,
+    /// Set when the content is rebuilt or the pane changes ticker, window or link group, for
+    /// the dashboard to mirror scale, alert levels, orders and the like onto it again
+    needs_sync: bool,
 }
 
 impl State {
@@ -137,14 +275,448 @@ impl State {
         }
     }
 
+    pub fn request_sync(&mut self) {
+        self.needs_sync = true;
+    }
+
+    /// Whether the pane asked to be synced since the last call
+    pub fn take_sync_request(&mut self) -> bool {
+        std::mem::take(&mut self.needs_sync)
+    }
+
     pub fn stream_pair(&self) -> Option<TickerInfo> {
         self.streams.find_ready_map(|stream| match stream {
             StreamKind::Kline { ticker_info, .. } => Some(*ticker_info),
             StreamKind::Depth { ticker_info, .. } => Some(*ticker_info),
             StreamKind::Trades { ticker_info, .. } => Some(*ticker_info),
+            StreamKind::CompositeDepth { sources, .. } => Some(sources.base()),
+            StreamKind::MarkPrice { ticker_info } => Some(*ticker_info),
+        })
+    }
+
+    pub fn composite_sources(&self) -> Option<&CompositeSources> {
+        self.streams.ready_iter()?.find_map(|stream| match stream {
+            StreamKind::CompositeDepth { sources, .. } => Some(sources),
+            _ => None,
         })
     }
 
+    /// Venues the tickers list can add to or remove from a depth-based pane
+    fn composite_selection(&self) -> &[TickerInfo] {
+        self.composite_sources()
+            .map(CompositeSources::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    fn can_replay(&self) -> bool {
+        self.content.supports_replay() && self.composite_sources().is_none()
+    }
+
+    pub fn record_depth(&mut self, stream: &StreamKind, update_t: UnixMs, depth: &Depth) {
+        if let Some(recorder) = &mut self.recorder
+            && recorder.ticker_info() == stream.ticker_info()
+            && let Err(err) = recorder.record_depth(update_t, depth)
+        {
+            self.stop_recording_on(err);
+        }
+    }
+
+    pub fn record_trades(&mut self, stream: &StreamKind, update_t: UnixMs, buffer: &[Trade]) {
+        if let Some(recorder) = &mut self.recorder
+            && recorder.ticker_info() == stream.ticker_info()
+            && let Err(err) = recorder.record_trades(update_t, buffer)
+        {
+            self.stop_recording_on(err);
+        }
+    }
+
+    /// Sound to play for trades just received, if the pane has trade sounds enabled
+    pub fn trade_sound_hit(
+        &mut self,
+        ticker_info: TickerInfo,
+        buffer: &[Trade],
+    ) -> Option<TradeSoundHit> {
+        let cfg = self.content.trade_sound()?;
+        self.burst_tracker
+            .check(&cfg, ticker_info.market_type(), buffer)
+    }
+
+    /// Shows the order flow patterns found by a footprint chart as toasts, with the sound
+    /// of the strongest one if the study has sound on
+    pub fn flow_signal_hit(&mut self, ticker_info: TickerInfo) -> Option<TradeSoundHit> {
+        let Content::Kline {
+            chart: Some(chart), ..
+        } = &mut self.content
+        else {
+            return None;
+        };
+
+        let signals = chart.take_flow_signals();
+        let sound = chart.flow_alert_rules().is_some_and(|rules| rules.sound);
+
+        let mut hit = None;
+        for signal in signals {
+            let is_sell = signal.is_sell();
+
+            self.notifications.push(Toast::custom(
+                "Order flow",
+                signal.describe(ticker_info.min_ticksize),
+                if is_sell {
+                    toast::Status::Danger
+                } else {
+                    toast::Status::Success
+                },
+            ));
+            if sound {
+                hit = hit.max(Some(TradeSoundHit::FlowSignal { is_sell }));
+            }
+        }
+
+        hit
+    }
+
+    fn stop_recording_on(&mut self, err: std::io::Error) {
+        self.recorder = None;
+        self.notifications
+            .push(Toast::error(format!("Recording stopped: {err}")));
+    }
+
+    fn toggle_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            match recorder.finish() {
+                Ok(path) => self.notifications.push(Toast::info(format!(
+                    "Recording saved to {}",
+                    path.display()
+                ))),
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("Failed to save recording: {err}"))),
+            }
+            return;
+        }
+
+        let Some(ticker_info) = self.stream_pair() else {
+            return;
+        };
+
+        match Recorder::start(ticker_info) {
+            Ok(recorder) => {
+                self.notifications.push(Toast::info(format!(
+                    "Recording {} to {}",
+                    ticker_info.ticker,
+                    recorder.path().display()
+                )));
+                self.recorder = Some(recorder);
+            }
+            Err(err) => self
+                .notifications
+                .push(Toast::error(format!("Couldn't start recording: {err}"))),
+        }
+    }
+
+    fn timelapse_config(&self) -> Option<TimelapseConfig> {
+        match &self.content {
+            Content::Heatmap { chart: Some(c), .. } => Some(c.visual_config().timelapse),
+            Content::ShaderHeatmap { chart: Some(c), .. } => Some(c.visual_config().timelapse),
+            _ => None,
+        }
+    }
+
+    /// Hanging up on the recording thread has it save the frames received so far
+    fn toggle_timelapse(&mut self) {
+        if self.timelapse.take().is_some() {
+            return;
+        }
+
+        let Some(config) = self.timelapse_config() else {
+            return;
+        };
+
+        let label = self.snapshot_label();
+        let (frames, receiver) = mpsc::channel();
+        let (sender, outcome) = oneshot::channel();
+
+        std::thread::spawn(move || {
+            let _ = sender.send(Timelapse::record(&label, config, receiver));
+        });
+
+        self.notifications.push(Toast::info(format!(
+            "Recording time-lapse, one frame every {}s",
+            config.interval_secs
+        )));
+        self.timelapse = Some((frames, None));
+        self.timelapse_outcome = Some(outcome);
+    }
+
+    /// Outcome of the last time-lapse, resolved once its thread is done saving
+    pub fn take_timelapse_outcome(&mut self) -> Option<oneshot::Receiver<io::Result<Recorded>>> {
+        self.timelapse_outcome.take()
+    }
+
+    pub fn timelapse_toast(outcome: Option<io::Result<Recorded>>) -> Toast {
+        match outcome {
+            Some(Ok(recorded)) => {
+                let mut msg = format!(
+                    "Time-lapse of {} frames saved to {}",
+                    recorded.frames,
+                    recorded.path.display()
+                );
+                if recorded.skipped > 0 {
+                    msg.push_str(&format!(
+                        ", {} skipped after the pane was resized",
+                        recorded.skipped
+                    ));
+                }
+
+                match recorded.stopped_by {
+                    Some(err) => Toast::warn(format!("Time-lapse stopped: {err}. {msg}")),
+                    None => Toast::info(msg),
+                }
+            }
+            Some(Err(err)) => Toast::error(format!("Failed to save time-lapse: {err}")),
+            None => Toast::error("Time-lapse stopped unexpectedly"),
+        }
+    }
+
+    /// Whether a time-lapse frame should be captured now, restarting the interval if so.
+    /// A recording outlives its heatmap only until the next check, which saves it
+    pub fn timelapse_due(&mut self, now: Instant) -> bool {
+        if self.timelapse.is_none() {
+            return false;
+        }
+        let Some(config) = self.timelapse_config() else {
+            self.toggle_timelapse();
+            return false;
+        };
+        let Some((_, last_capture)) = &mut self.timelapse else {
+            return false;
+        };
+
+        let interval = Duration::from_secs(u64::from(config.interval_secs.max(1)));
+        if last_capture.is_some_and(|last| now.duration_since(last) < interval) {
+            return false;
+        }
+
+        *last_capture = Some(now);
+        true
+    }
+
+    pub fn push_timelapse_frame(&mut self, capture: Capture) {
+        // Only fails once the recording thread gave up, its outcome tells why
+        if let Some((frames, _)) = &self.timelapse
+            && frames.send(capture).is_err()
+        {
+            self.timelapse = None;
+        }
+    }
+
+    fn open_replay(&mut self) {
+        let Some(ticker_info) = self.stream_pair() else {
+            return;
+        };
+
+        let Some(path) = replay::latest_recording(&ticker_info) else {
+            self.notifications.push(Toast::warn(format!(
+                "No recordings of {} yet",
+                ticker_info.ticker
+            )));
+            return;
+        };
+
+        match Replay::load(&path) {
+            Ok(mut replay) => {
+                self.reset_content();
+                replay.play(Instant::now());
+                self.replay = Some(replay);
+            }
+            Err(err) => self.notifications.push(Toast::error(format!(
+                "Couldn't load {}: {err}",
+                path.display()
+            ))),
+        }
+    }
+
+    fn control_replay(&mut self, control: ReplayControl) {
+        let now = Instant::now();
+
+        match control {
+            ReplayControl::TogglePlay => {
+                if let Some(replay) = &mut self.replay {
+                    replay.toggle(now);
+                }
+            }
+            ReplayControl::CycleSpeed => {
+                if let Some(replay) = &mut self.replay {
+                    replay.set_speed(replay.speed().next(), now);
+                }
+            }
+            ReplayControl::Scrub(fraction) => {
+                if let Some(replay) = &mut self.replay {
+                    replay.scrub(fraction);
+                }
+            }
+            ReplayControl::Seek => {
+                if self.replay.is_some() {
+                    self.reset_content();
+                }
+                if let Some(replay) = &mut self.replay {
+                    replay.finish_scrub(now);
+                }
+            }
+            ReplayControl::Close => {
+                if self.replay.take().is_some() {
+                    self.reset_content();
+                }
+            }
+        }
+    }
+
+    /// Feeds the recording events that are due into the content
+    fn advance_replay(&mut self, now: Instant) {
+        let current = self.stream_pair();
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        let ticker_info = replay.ticker_info();
+
+        // The pane was switched to another ticker
+        if current != Some(ticker_info) {
+            self.replay = None;
+            return;
+        }
+
+        for output in replay.advance(now) {
+            match output {
+                replay::Output::Depth(update_t, depth) => {
+                    self.content.insert_depth(&depth, update_t);
+                }
+                replay::Output::Trades(update_t, trades) => {
+                    self.content.insert_trades(ticker_info, &trades, update_t);
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the content empty, keeping its settings and tickers
+    fn reset_content(&mut self) {
+        let Some(base) = self.stream_pair() else {
+            return;
+        };
+
+        let mut tickers = vec![base];
+        if let Some(streams) = self.streams.ready_iter() {
+            for ticker_info in streams.map(StreamKind::ticker_info) {
+                if !tickers.contains(&ticker_info) {
+                    tickers.push(ticker_info);
+                }
+            }
+        }
+
+        self.set_content_and_streams(tickers, self.content.kind());
+    }
+
+    /// Folds a venue's orderbook into the pane's composite book,
+    /// returns `None` if the pane isn't subscribed to a composite depth stream
+    pub fn merge_composite_depth(
+        &mut self,
+        stream: &StreamKind,
+        depth: &Depth,
+    ) -> Option<Arc<Depth>> {
+        let sources = *self.composite_sources()?;
+
+        if self
+            .composite_depth
+            .as_ref()
+            .is_none_or(|book| *book.sources() != sources)
+        {
+            self.composite_depth = Some(CompositeDepth::new(sources));
+        }
+
+        self.composite_depth
+            .as_mut()
+            .map(|book| book.insert(stream.ticker_info(), depth))
+    }
+
+    /// Adds or removes a venue from the pane's depth stream,
+    /// switching between a plain and a composite depth stream as needed
+    fn edit_composite_sources(&mut self, ticker_info: TickerInfo, add: bool) -> Option<Effect> {
+        let depth_stream = self.streams.ready_iter_mut()?.find(|stream| {
+            matches!(
+                stream,
+                StreamKind::Depth { .. } | StreamKind::CompositeDepth { .. }
+            )
+        })?;
+
+        let (sources, depth_aggr, push_freq) = match *depth_stream {
+            StreamKind::Depth {
+                ticker_info,
+                depth_aggr,
+                push_freq,
+            } => (CompositeSources::new(ticker_info), depth_aggr, push_freq),
+            StreamKind::CompositeDepth {
+                sources,
+                depth_aggr,
+                push_freq,
+            } => (sources, depth_aggr, push_freq),
+            _ => return None,
+        };
+
+        let sources = if add {
+            let Some(sources) = sources.with(ticker_info) else {
+                self.notifications.push(Toast::warn(format!(
+                    "Composite book is limited to {} venues",
+                    exchange::adapter::MAX_COMPOSITE_SOURCES
+                )));
+                return None;
+            };
+            sources
+        } else {
+            sources.without(ticker_info)
+        };
+
+        *depth_stream = if sources.count() > 1 {
+            StreamKind::CompositeDepth {
+                sources,
+                depth_aggr,
+                push_freq,
+            }
+        } else {
+            StreamKind::Depth {
+                ticker_info: sources.base(),
+                depth_aggr,
+                push_freq,
+            }
+        };
+
+        Some(Effect::RefreshStreams)
+    }
+
+    /// Overlays `ticker_info` on the kline chart, or drops the overlay with `None`
+    fn set_kline_overlay(&mut self, ticker_info: Option<TickerInfo>) -> Option<Effect> {
+        let Content::Kline { chart: Some(c), .. } = &mut self.content else {
+            return None;
+        };
+
+        if let Some(ti) = ticker_info
+            && let Basis::Time(tf) = c.basis()
+            && ti.exchange().kline_fetch_timeframe(tf).is_none()
+        {
+            self.notifications.push(Toast::warn(format!(
+                "{} has no klines in {tf}",
+                ti.ticker.display_symbol_and_type().0
+            )));
+            return None;
+        }
+
+        c.set_overlay(ticker_info);
+
+        self.sync_overlay_stream().then_some(Effect::RefreshStreams)
+    }
+
     pub fn stream_pair_kind(&self) -> Option<StreamPairKind> {
         let ready_streams = self.streams.ready_iter()?;
         let mut unique = vec![];
@@ -163,6 +735,22 @@ impl State {
         }
     }
 
+    /// Takes over the streams `source` already resolved and, for kline charts, the data
+    /// it loaded. Other content is rebuilt from the streams on the next tick
+    pub fn adopt_loaded(&mut self, source: &State) {
+        self.streams = source.streams.clone();
+
+        if let Content::Kline { chart, .. } = &mut self.content
+            && let Content::Kline {
+                chart: Some(loaded),
+                ..
+            } = &source.content
+        {
+            *chart = Some(Box::new(loaded.duplicate()));
+            self.needs_sync = true;
+        }
+    }
+
     pub fn set_content_and_streams(
         &mut self,
         tickers: Vec<TickerInfo>,
@@ -175,6 +763,11 @@ impl State {
 
         let base_ticker = tickers[0];
         let prev_base_ticker = self.stream_pair();
+        // Restored from the saved streams, otherwise kept across a ticker switch
+        let overlay = tickers.get(1).copied().or_else(|| match &self.content {
+            Content::Kline { chart: Some(c), .. } => c.overlay_ticker(),
+            _ => None,
+        });
 
         let derived_plan = PaneSetup::new(
             kind,
@@ -226,6 +819,7 @@ impl State {
                     let streams = by_basis_default(
                         derived_plan.basis,
                         Timeframe::M5,
+                        derived_plan.ticker_info,
                         |tf| {
                             vec![
                                 trades_stream(&derived_plan),
@@ -265,6 +859,7 @@ impl State {
                     let streams = by_basis_default(
                         derived_plan.basis,
                         Timeframe::M15,
+                        derived_plan.ticker_info,
                         time_basis_stream,
                         tick_basis_stream,
                     );
@@ -277,17 +872,10 @@ impl State {
                         .visual_config
                         .clone()
                         .and_then(|cfg| cfg.time_and_sales());
-                    let content = Content::TimeAndSales(Some(TimeAndSales::new(
-                        config,
-                        derived_plan.ticker_info,
-                    )));
-
-                    let temp = PaneSetup {
-                        push_freq: exchange::PushFrequency::ServerDefault,
-                        ..derived_plan
-                    };
+                    let panel = TimeAndSales::new(config, base_ticker, &tickers[1..]);
+                    let streams = panel.streams_for_all();
 
-                    let streams = vec![trades_stream(&temp)];
+                    let content = Content::TimeAndSales(Some(panel));
 
                     (content, streams)
                 }
@@ -314,22 +902,18 @@ impl State {
                         .clone()
                         .and_then(|cfg| cfg.comparison());
 
-                    let timeframe = {
-                        let supports = |tf| {
-                            tickers
-                                .iter()
-                                .all(|ti| ti.exchange().supports_kline_timeframe(tf))
-                        };
+                    let supports = |tf| {
+                        tickers
+                            .iter()
+                            .all(|ti| ti.exchange().supports_kline_timeframe(tf))
+                    };
 
-                        if let Some(tf) = derived_plan.basis.and_then(|basis| match basis {
-                            Basis::Time(tf) => Some(tf),
-                            Basis::Tick(_) => None,
-                        }) && supports(tf)
-                        {
-                            tf
-                        } else {
+                    let basis = match derived_plan.basis {
+                        Some(Basis::Tick(tick_count)) => Basis::Tick(tick_count),
+                        Some(Basis::Time(tf)) if supports(tf) => Basis::Time(tf),
+                        _ => {
                             let fallback = Timeframe::M15;
-                            if supports(fallback) {
+                            Basis::Time(if supports(fallback) {
                                 fallback
                             } else {
                                 Timeframe::KLINE
@@ -337,20 +921,44 @@ impl State {
                                     .copied()
                                     .find(|tf| supports(*tf))
                                     .unwrap_or(fallback)
-                            }
+                            })
+                        }
+                    };
+
+                    self.settings.selected_basis = Some(basis);
+                    let chart = ComparisonChart::new(basis, &tickers, config);
+
+                    let streams = chart.streams_for_all();
+                    let content = Content::Comparison(Some(chart));
+
+                    (content, streams)
+                }
+                ContentKind::SpreadChart => {
+                    let config = self
+                        .settings
+                        .visual_config
+                        .clone()
+                        .and_then(|cfg| cfg.spread());
+
+                    let legs = &tickers[..tickers.len().min(chart::spread::MAX_LEGS)];
+
+                    let basis = match derived_plan.basis {
+                        Some(Basis::Tick(tick_count)) => Basis::Tick(tick_count),
+                        Some(Basis::Time(tf))
+                            if legs
+                                .iter()
+                                .all(|ti| ti.exchange().supports_kline_timeframe(tf)) =>
+                        {
+                            Basis::Time(tf)
                         }
+                        _ => Basis::Time(Timeframe::M15),
                     };
 
-                    let basis = Basis::Time(timeframe);
                     self.settings.selected_basis = Some(basis);
-                    let content =
-                        Content::Comparison(Some(ComparisonChart::new(basis, &tickers, config)));
+                    let chart = SpreadChart::new(basis, legs, config);
 
-                    let streams = tickers
-                        .iter()
-                        .copied()
-                        .map(|ti| kline_stream(ti, timeframe))
-                        .collect();
+                    let streams = chart.streams_for_all();
+                    let content = Content::Spread(Some(chart));
 
                     (content, streams)
                 }
@@ -408,11 +1016,91 @@ impl State {
         };
 
         self.content = content;
+        self.needs_sync = true;
+
+        let mut streams = streams;
+        if let Some(stream) = self.mark_price_stream(derived_plan.ticker_info) {
+            streams.push(stream);
+        }
+        if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+            c.set_overlay(overlay);
+            streams.extend(c.overlay_stream());
+        }
         self.streams = ResolvedStream::Ready(streams.clone());
 
         streams
     }
 
+    /// The mark price stream the kline chart overlays, if enabled and streamed for `ticker_info`
+    fn mark_price_stream(&self, ticker_info: TickerInfo) -> Option<StreamKind> {
+        let Content::Kline { chart: Some(c), .. } = &self.content else {
+            return None;
+        };
+
+        (c.visual_config().show_mark_price
+            && c.basis().is_time()
+            && ticker_info.exchange().supports_mark_price())
+        .then_some(StreamKind::MarkPrice { ticker_info })
+    }
+
+    /// Adds or drops the mark price stream to match the chart settings.
+    /// Returns whether the pane's streams changed
+    pub fn sync_mark_price_stream(&mut self) -> bool {
+        let wanted = self
+            .stream_pair()
+            .and_then(|ticker_info| self.mark_price_stream(ticker_info));
+
+        let ResolvedStream::Ready(streams) = &mut self.streams else {
+            return false;
+        };
+
+        let before = streams.len();
+        streams.retain(|stream| {
+            !matches!(stream, StreamKind::MarkPrice { .. }) || Some(*stream) == wanted
+        });
+        let mut changed = streams.len() != before;
+
+        if let Some(stream) = wanted
+            && !streams.contains(&stream)
+        {
+            streams.push(stream);
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Adds or drops the kline stream of the ticker overlaid on the chart.
+    /// Returns whether the pane's streams changed
+    pub fn sync_overlay_stream(&mut self) -> bool {
+        let (main, wanted) = match &self.content {
+            Content::Kline { chart: Some(c), .. } => (self.stream_pair(), c.overlay_stream()),
+            _ => return false,
+        };
+
+        let ResolvedStream::Ready(streams) = &mut self.streams else {
+            return false;
+        };
+
+        let before = streams.len();
+        streams.retain(|stream| match stream {
+            StreamKind::Kline { ticker_info, .. } => {
+                Some(*ticker_info) == main || Some(*stream) == wanted
+            }
+            _ => true,
+        });
+        let mut changed = streams.len() != before;
+
+        if let Some(stream) = wanted
+            && !streams.contains(&stream)
+        {
+            streams.push(stream);
+            changed = true;
+        }
+
+        changed
+    }
+
     pub fn insert_hist_oi(&mut self, req_id: Option<uuid::Uuid>, oi: &[OpenInterest]) {
         match &mut self.content {
             Content::Kline { chart, .. } => {
@@ -421,12 +1109,27 @@ impl State {
                 };
                 chart.insert_open_interest(req_id, oi);
             }
+            // Polled for the liquidation levels study
+            Content::Heatmap { chart: Some(c), .. } => c.insert_open_interest(oi),
             _ => {
                 log::error!("pane content not candlestick");
             }
         }
     }
 
+    pub fn insert_hist_funding(&mut self, req_id: Option<uuid::Uuid>, funding: &[FundingRate]) {
+        match &mut self.content {
+            Content::Kline {
+                chart: Some(chart), ..
+            } => {
+                chart.insert_funding_rate(req_id, funding);
+            }
+            _ => {
+                log::error!("pane content not a kline chart");
+            }
+        }
+    }
+
     pub fn insert_hist_klines(
         &mut self,
         req_id: Option<uuid::Uuid>,
@@ -442,24 +1145,53 @@ impl State {
                     panic!("chart wasn't initialized when inserting klines");
                 };
 
-                if let Some(id) = req_id {
-                    if chart.basis() != Basis::Time(timeframe) {
-                        log::warn!(
-                            "Ignoring stale kline fetch for timeframe {:?}; chart basis = {:?}",
-                            timeframe,
-                            chart.basis()
-                        );
-                        return;
+                if chart.overlay_ticker() == Some(ticker_info) {
+                    if let Some(id) = req_id
+                        && chart.overlay_stream()
+                            == Some(StreamKind::Kline {
+                                ticker_info,
+                                timeframe,
+                            })
+                    {
+                        chart.insert_overlay_klines(id, klines);
+                    }
+                    return;
+                }
+
+                // Custom timeframes are fetched in the one they're resampled from
+                let basis = match chart.basis() {
+                    Basis::Time(tf)
+                        if ticker_info.exchange().kline_fetch_timeframe(tf) == Some(timeframe) =>
+                    {
+                        chart.basis()
+                    }
+                    _ => Basis::Time(timeframe),
+                };
+
+                if let Some(id) = req_id {
+                    if chart.basis() != basis {
+                        log::warn!(
+                            "Ignoring stale kline fetch for timeframe {:?}; chart basis = {:?}",
+                            timeframe,
+                            chart.basis()
+                        );
+                        return;
                     }
                     chart.insert_hist_klines(id, klines);
                 } else {
                     let (raw_trades, tick_size) = (chart.raw_trades(), chart.tick_size());
                     let layout = chart.chart_layout();
                     let visual_config = chart.visual_config();
-
-                    *chart = KlineChart::new(
+                    let moving_averages = chart.moving_averages();
+                    let bands = chart.bands();
+                    let vwap = chart.vwap();
+                    let profile_ranges = chart.profile_ranges().to_vec();
+                    let scripts = chart.scripts().enabled();
+                    let overlay = chart.overlay_ticker();
+
+                    **chart = KlineChart::new(
                         layout,
-                        Basis::Time(timeframe),
+                        basis,
                         tick_size,
                         klines,
                         raw_trades,
@@ -468,6 +1200,13 @@ impl State {
                         chart.kind(),
                         Some(visual_config),
                     );
+                    chart.set_moving_averages(&moving_averages);
+                    chart.set_bands(&bands);
+                    chart.set_vwap(vwap);
+                    chart.set_profile_ranges(profile_ranges);
+                    chart.set_scripts(&scripts);
+                    chart.set_overlay(overlay);
+                    self.needs_sync = true;
                 }
             }
             Content::Comparison(chart) => {
@@ -476,11 +1215,11 @@ impl State {
                 };
 
                 if let Some(id) = req_id {
-                    if chart.timeframe != timeframe {
+                    if chart.basis() != Basis::Time(timeframe) {
                         log::warn!(
-                            "Ignoring stale kline fetch for timeframe {:?}; chart timeframe = {:?}",
+                            "Ignoring stale kline fetch for timeframe {:?}; chart basis = {:?}",
                             timeframe,
-                            chart.timeframe
+                            chart.basis()
                         );
                         return;
                     }
@@ -491,6 +1230,31 @@ impl State {
                         &[ticker_info],
                         Some(chart.serializable_config()),
                     );
+                    self.needs_sync = true;
+                }
+            }
+            Content::Spread(chart) => {
+                let Some(chart) = chart else {
+                    panic!("Spread chart wasn't initialized when inserting klines");
+                };
+
+                if let Some(id) = req_id {
+                    if chart.basis() != Basis::Time(timeframe) {
+                        log::warn!(
+                            "Ignoring stale kline fetch for timeframe {:?}; chart basis = {:?}",
+                            timeframe,
+                            chart.basis()
+                        );
+                        return;
+                    }
+                    chart.insert_history(id, ticker_info, klines);
+                } else {
+                    *chart = SpreadChart::new(
+                        Basis::Time(timeframe),
+                        chart.selected_tickers(),
+                        Some(chart.serializable_config()),
+                    );
+                    self.needs_sync = true;
                 }
             }
             _ => {
@@ -516,6 +1280,8 @@ impl State {
         main_window: &'a Window,
         timezone: UserTimezone,
         tickers_table: &'a TickersTable,
+        stream_rates: &'a StreamRates,
+        accents: &'a Accents,
     ) -> pane_grid::Content<'a, Message, Theme, Renderer> {
         let mut top_left_buttons = if Content::Starter == self.content {
             row![]
@@ -530,6 +1296,10 @@ impl State {
                 StreamPairKind::MultiSource(list) => (list[0], list.len().saturating_sub(1)),
                 StreamPairKind::SingleSource(ti) => (ti, 0),
             };
+            let extra = extra
+                + self
+                    .composite_sources()
+                    .map_or(0, |sources| sources.count() - 1);
 
             let exchange_icon = icon_text(style::venue_icon(base_ti.ticker.exchange.venue()), 14);
             let mut label = {
@@ -627,10 +1397,40 @@ impl State {
             }
         };
 
+        let profile = session_profile();
+
         let body = match &self.content {
+            content if !profile.allows_content(content.kind()) => {
+                let base = center(
+                    column![
+                        text(content.kind().to_string()).size(crate::style::text_size::TITLE),
+                        text("Paused in remote session").size(crate::style::text_size::SECTION)
+                    ]
+                    .spacing(8)
+                    .align_x(Alignment::Center),
+                )
+                .into();
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    None,
+                    tickers_table,
+                    stream_rates,
+                    accents,
+                )
+            }
             Content::Starter => {
+                let available_kinds = ContentKind::ALL
+                    .into_iter()
+                    .filter(|kind| profile.allows_content(*kind))
+                    .collect::<Vec<_>>();
+
                 let content_picklist =
-                    pick_list(ContentKind::ALL, Some(ContentKind::Starter), move |kind| {
+                    pick_list(available_kinds, Some(ContentKind::Starter), move |kind| {
                         Message::PaneEvent(id, Event::ContentSelected(kind))
                     });
 
@@ -658,11 +1458,13 @@ impl State {
                     || column![].into(),
                     None,
                     tickers_table,
+                    stream_rates,
+                    accents,
                 )
             }
             Content::Comparison(chart) => {
                 if let Some(c) = chart {
-                    let selected_basis = Basis::Time(c.timeframe);
+                    let selected_basis = c.basis();
                     let kind = ModifierKind::Comparison(selected_basis);
 
                     let modifiers =
@@ -684,6 +1486,8 @@ impl State {
                         settings_modal,
                         Some(c.selected_tickers()),
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::ComparisonChart);
@@ -695,6 +1499,50 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        stream_rates,
+                        accents,
+                    )
+                }
+            }
+            Content::Spread(chart) => {
+                if let Some(c) = chart {
+                    let selected_basis = c.basis();
+                    let kind = ModifierKind::Comparison(selected_basis);
+
+                    let modifiers =
+                        row![basis_modifier(id, selected_basis, modifier, kind),].spacing(4);
+
+                    top_left_buttons = top_left_buttons.push(modifiers);
+
+                    let base = c.view(timezone).map(move |message| {
+                        Message::PaneEvent(id, Event::SpreadChartInteraction(message))
+                    });
+
+                    let settings_modal = || spread_cfg_view(id, c.config);
+
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        settings_modal,
+                        Some(c.selected_tickers()),
+                        tickers_table,
+                        stream_rates,
+                        accents,
+                    )
+                } else {
+                    let base = uninitialized_base(ContentKind::SpreadChart);
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        || column![].into(),
+                        None,
+                        tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 }
             }
@@ -713,8 +1561,10 @@ impl State {
                         None,
                         compact_controls,
                         settings_modal,
-                        None,
+                        Some(panel.sources()),
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::TimeAndSales);
@@ -726,6 +1576,8 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 }
             }
@@ -773,8 +1625,10 @@ impl State {
                         None,
                         compact_controls,
                         settings_modal,
-                        None,
+                        Some(self.composite_selection()),
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::Ladder);
@@ -786,6 +1640,8 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 }
             }
@@ -827,7 +1683,7 @@ impl State {
 
                     top_left_buttons = top_left_buttons.push(modifiers);
 
-                    let base = chart::view(chart, indicators, timezone).map(move |message| {
+                    let base = chart::view(&**chart, indicators, timezone).map(move |message| {
                         Message::PaneEvent(id, Event::ChartInteraction(message))
                     });
                     let settings_modal = || {
@@ -837,6 +1693,8 @@ impl State {
                             chart.study_configurator(),
                             &chart.studies,
                             basis,
+                            self.timelapse.is_some(),
+                            chart.history_usage(),
                         )
                     };
 
@@ -846,6 +1704,7 @@ impl State {
                             self,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
+                            None,
                         ))
                     } else {
                         None
@@ -857,8 +1716,10 @@ impl State {
                         indicator_modal,
                         compact_controls,
                         settings_modal,
-                        None,
+                        Some(self.composite_selection()),
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::HeatmapChart);
@@ -870,6 +1731,8 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 }
             }
@@ -928,7 +1791,7 @@ impl State {
                         }
                     }
 
-                    let base = chart::view(chart, indicators, timezone).map(move |message| {
+                    let base = chart::view(&**chart, indicators, timezone).map(move |message| {
                         Message::PaneEvent(id, Event::ChartInteraction(message))
                     });
                     let settings_modal = || {
@@ -938,6 +1801,9 @@ impl State {
                             chart_kind,
                             id,
                             chart.basis(),
+                            self.stream_pair()
+                                .is_some_and(|ti| ti.exchange().supports_mark_price()),
+                            &self.settings.profile_ranges,
                         )
                     };
 
@@ -947,6 +1813,17 @@ impl State {
                             self,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
+                            Some(modal::indicators::kline_params(
+                                id,
+                                indicators,
+                                &self.settings.moving_averages,
+                                &self.settings.bands,
+                                self.settings.vwap,
+                                chart.visible_start_time(),
+                                chart.scripts(),
+                                &self.indicator_templates,
+                                &self.template_name,
+                            )),
                         ))
                     } else {
                         None
@@ -958,8 +1835,11 @@ impl State {
                         indicator_modal,
                         compact_controls,
                         settings_modal,
-                        None,
+                        // Overlaying another ticker only lines up on time basis
+                        chart.basis().is_time().then_some(chart.overlay_tickers()),
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 } else {
                     let content_kind = match chart_kind {
@@ -977,6 +1857,8 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 }
             }
@@ -1014,6 +1896,8 @@ impl State {
                             chart.study_configurator(),
                             &chart.studies,
                             basis,
+                            self.timelapse.is_some(),
+                            chart.history_usage(),
                         )
                     };
 
@@ -1023,6 +1907,7 @@ impl State {
                             self,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
+                            None,
                         ))
                     } else {
                         None
@@ -1050,8 +1935,10 @@ impl State {
                         indicator_modal,
                         compact_controls,
                         settings_modal,
-                        None,
+                        Some(self.composite_selection()),
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::HeatmapChart);
@@ -1063,28 +1950,59 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        stream_rates,
+                        accents,
                     )
                 }
             }
         };
 
         match &self.status {
-            Status::Loading(InfoKind::FetchingKlines) => {
-                top_left_buttons = top_left_buttons.push(text("Fetching Klines..."));
-            }
             Status::Loading(InfoKind::FetchingTrades(count)) => {
                 top_left_buttons =
                     top_left_buttons.push(text(format!("Fetching Trades... {count} fetched")));
             }
-            Status::Loading(InfoKind::FetchingOI) => {
-                top_left_buttons = top_left_buttons.push(text("Fetching Open Interest..."));
+            Status::Loading(info) => {
+                top_left_buttons = top_left_buttons.push(text(format!("{}...", info_label(*info))));
             }
             Status::Stale(msg) => {
                 top_left_buttons = top_left_buttons.push(text(msg));
             }
+            Status::Reconnecting {
+                secs_left: secs,
+                attempt,
+                exhausted,
+                ..
+            } => {
+                let label = if *exhausted {
+                    format!("Connection lost, retrying in {secs}s")
+                } else {
+                    format!("Reconnecting in {secs}s (attempt {attempt})")
+                };
+                top_left_buttons = top_left_buttons.push(text(label));
+            }
+            Status::TimedOut(info) => {
+                top_left_buttons = top_left_buttons.push(
+                    row![
+                        text(format!("{} timed out", info_label(*info))),
+                        button(text("Retry"))
+                            .style(|theme, status| style::button::modifier(theme, status, true))
+                            .on_press(Message::PaneEvent(id, Event::RetryFetch))
+                            .height(widget::PANE_CONTROL_BTN_HEIGHT),
+                    ]
+                    .spacing(4)
+                    .align_y(Alignment::Center),
+                );
+            }
             Status::Ready => {}
         }
 
+        if let Some(replay) = &self.replay {
+            top_left_buttons = top_left_buttons.push(replay_transport(id, replay));
+        }
+
+        let accent = self.accent(accents);
+
         let content = pane_grid::Content::new(body)
             .style(move |theme| style::pane_background(theme, is_focused));
 
@@ -1125,7 +2043,7 @@ impl State {
                 .height(Length::Fixed(32.0)),
         )
         .controls(top_right_buttons)
-        .style(style::pane_title_bar);
+        .style(move |theme| style::pane_title_bar(theme, accent));
 
         content.title_bar(if self.modal.is_none() {
             title_bar
@@ -1137,13 +2055,142 @@ impl State {
     pub fn update(&mut self, msg: Event) -> Option<Effect> {
         match msg {
             Event::ShowModal(requested_modal) => {
+                if matches!(requested_modal, Modal::Indicators) {
+                    self.indicator_templates = IndicatorTemplates::load();
+                }
                 return self.show_modal_with_focus(requested_modal);
             }
             Event::HideModal => {
                 self.modal = None;
             }
+            Event::AppearanceChanged(appearance) => {
+                self.settings.appearance = appearance;
+            }
+            Event::MovingAveragesChanged(lines) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.set_moving_averages(&lines);
+                }
+                self.settings.moving_averages = lines;
+            }
+            Event::BandsChanged(bands) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.set_bands(&bands);
+                }
+                self.settings.bands = bands;
+            }
+            Event::VwapChanged(vwap) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.set_vwap(vwap);
+                }
+                self.settings.vwap = vwap;
+            }
+            Event::ProfileRangesChanged(ranges) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.set_profile_ranges(ranges.clone());
+                }
+                self.settings.profile_ranges = ranges;
+            }
+            Event::ScriptsChanged(scripts) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.set_scripts(&scripts);
+                }
+                self.settings.scripts = scripts;
+            }
+            Event::ReloadScripts => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.reload_scripts();
+                }
+            }
+            Event::OpenScriptsFolder => {
+                if let Err(err) = data::chart::script::open_scripts_dir() {
+                    self.notifications.push(Toast::error(err.to_string()));
+                }
+            }
+            Event::IndicatorSettingsChanged(id, settings) => {
+                if let Content::Kline {
+                    chart, indicators, ..
+                } = &mut self.content
+                    && let Some(instance) = indicators.iter_mut().find(|i| i.id == id)
+                {
+                    instance.settings = settings;
+                    if let Some(c) = chart {
+                        c.set_indicator_settings(id, settings);
+                    }
+                }
+            }
+            Event::TemplateNameChanged(name) => {
+                self.template_name = name;
+            }
+            Event::SaveIndicatorTemplate => {
+                let name = self.template_name.trim().to_string();
+                if let Content::Kline { indicators, .. } = &self.content
+                    && !name.is_empty()
+                {
+                    self.indicator_templates
+                        .insert(IndicatorTemplate::from_pane(
+                            name,
+                            indicators,
+                            &self.settings,
+                        ));
+                    match self.indicator_templates.save() {
+                        Ok(()) => self.template_name.clear(),
+                        Err(err) => self.notifications.push(Toast::error(format!(
+                            "Failed to save indicator template: {err}"
+                        ))),
+                    }
+                }
+            }
+            Event::ApplyIndicatorTemplate(name) => {
+                if let Some(template) = self.indicator_templates.get(&name).cloned() {
+                    template.apply_to(&mut self.settings);
+                    self.content.apply_indicator_template(&template);
+                }
+            }
+            Event::RemoveIndicatorTemplate(name) => {
+                self.indicator_templates.remove(&name);
+                if let Err(err) = self.indicator_templates.save() {
+                    self.notifications.push(Toast::error(format!(
+                        "Failed to save indicator templates: {err}"
+                    )));
+                }
+            }
+            Event::ExportDepth => {
+                let exported = match &self.content {
+                    Content::Heatmap { chart: Some(c), .. } => Some(c.export_depth()),
+                    Content::ShaderHeatmap { chart: Some(c), .. } => Some(c.export_depth()),
+                    _ => None,
+                };
+
+                match exported {
+                    Some(Ok(path)) => self
+                        .notifications
+                        .push(Toast::info(format!("Depth exported to {}", path.display()))),
+                    Some(Err(err)) => self
+                        .notifications
+                        .push(Toast::error(format!("Depth export failed: {err}"))),
+                    None => {}
+                }
+            }
+            Event::ExportTape => {
+                if let Content::TimeAndSales(Some(panel)) = &self.content {
+                    match panel.export_csv() {
+                        Ok(path) => self
+                            .notifications
+                            .push(Toast::info(format!("Tape exported to {}", path.display()))),
+                        Err(err) => self
+                            .notifications
+                            .push(Toast::error(format!("Tape export failed: {err}"))),
+                    }
+                }
+            }
+            Event::RetryFetch => self.retry_fetch(),
+            Event::ToggleTimelapse => self.toggle_timelapse(),
+            Event::ToggleRecording => self.toggle_recording(),
+            Event::OpenReplay => self.open_replay(),
+            Event::Replay(control) => self.control_replay(control),
             Event::ContentSelected(kind) => {
                 self.content = Content::placeholder(kind);
+                self.needs_sync = true;
 
                 if !matches!(kind, ContentKind::Starter) {
                     self.streams = ResolvedStream::waiting(vec![]);
@@ -1154,15 +2201,136 @@ impl State {
                     }
                 }
             }
-            Event::ChartInteraction(msg) => match &mut self.content {
-                Content::Heatmap { chart: Some(c), .. } => {
-                    super::chart::update(c, &msg);
+            Event::ChartInteraction(super::chart::Message::AlertLevel(edit)) => {
+                return self
+                    .stream_pair()
+                    .map(|ticker_info| Effect::EditAlertLevel(ticker_info.ticker, edit));
+            }
+            Event::ChartInteraction(super::chart::Message::RangeSelected(first, last)) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content
+                    && let (Some(from), Some(to)) = (c.interval_time(first), c.interval_time(last))
+                {
+                    self.settings
+                        .profile_ranges
+                        .push(ProfileRange::new(from, to));
+                    c.set_profile_ranges(self.settings.profile_ranges.clone());
                 }
-                Content::Kline { chart: Some(c), .. } => {
-                    super::chart::update(c, &msg);
+            }
+            Event::ChartInteraction(super::chart::Message::ColumnClicked(interval)) => {
+                if let Content::Heatmap { chart: Some(c), .. } = &mut self.content {
+                    c.toggle_snapshot(interval);
                 }
-                _ => {}
-            },
+            }
+            Event::ChartInteraction(msg) => {
+                let opens_note = matches!(
+                    msg,
+                    super::chart::Message::NotePlaced(_) | super::chart::Message::NoteClicked(_)
+                );
+
+                match msg {
+                    super::chart::Message::DrawingPlaced(drawing) => {
+                        self.edit_drawings(|drawings| drawings.push(drawing));
+                    }
+                    super::chart::Message::DrawingRemoved(index) => {
+                        self.edit_drawings(|drawings| {
+                            if index < drawings.len() {
+                                drawings.remove(index);
+                            }
+                        });
+                    }
+                    // Picked from the palette, which would cover the chart to draw on
+                    super::chart::Message::DrawingToolChanged(Some(_)) => self.modal = None,
+                    super::chart::Message::NotePlaced(at) => {
+                        self.edit_notes(|notes| notes.push(Note::new(at)));
+                        let index = self.notes().len().saturating_sub(1);
+                        self.modal = Some(Modal::Note(index));
+                    }
+                    super::chart::Message::NoteClicked(index) => {
+                        self.modal = Some(Modal::Note(index));
+                    }
+                    _ => {}
+                }
+
+                match &mut self.content {
+                    Content::Heatmap { chart: Some(c), .. } => {
+                        super::chart::update(&mut **c, &msg);
+                    }
+                    Content::Kline { chart: Some(c), .. } => {
+                        super::chart::update(&mut **c, &msg);
+                    }
+                    _ => {}
+                }
+
+                if opens_note {
+                    return Some(Effect::FocusWidget(NOTE_INPUT_ID.into()));
+                }
+                if let super::chart::Message::CrosshairMoved(crosshair) = msg
+                    && let Some(group) = self.link_group
+                    && crosshair != self.shared_crosshair
+                {
+                    self.shared_crosshair = crosshair;
+                    return Some(Effect::ShareCrosshair(group, crosshair));
+                }
+
+                let moves_timespan = matches!(
+                    msg,
+                    super::chart::Message::Translated(_)
+                        | super::chart::Message::Scaled(..)
+                        | super::chart::Message::XScaling(..)
+                        | super::chart::Message::DoubleClick(super::chart::AxisScaleClicked::X)
+                );
+                if moves_timespan
+                    && let Some(group) = self.link_group
+                    && self.link_options.zoom
+                    && let Some(span) = self.visible_timespan()
+                {
+                    return Some(Effect::ShareTimespan(group, span));
+                }
+            }
+            Event::ClearDrawings => {
+                self.edit_drawings(Vec::clear);
+                self.edit_notes(Vec::clear);
+            }
+            Event::DrawingSnapChanged(snap) => {
+                self.settings.drawing_snap = snap;
+                self.sync_drawings();
+            }
+            Event::NoteEdited(index, edit) => {
+                if let NoteEdit::Remove = edit {
+                    self.modal = None;
+                }
+                self.edit_notes(|notes| match edit {
+                    NoteEdit::Remove if index < notes.len() => {
+                        notes.remove(index);
+                    }
+                    NoteEdit::Text(text) => {
+                        if let Some(note) = notes.get_mut(index) {
+                            note.text = text.chars().take(MAX_NOTE_CHARS).collect();
+                        }
+                    }
+                    NoteEdit::Marker(marker) => {
+                        if let Some(note) = notes.get_mut(index) {
+                            note.marker = marker;
+                        }
+                    }
+                    NoteEdit::Remove => {}
+                });
+            }
+            Event::PanelInteraction(super::panel::Message::PlaceOrder(side, price)) => {
+                return self
+                    .stream_pair()
+                    .map(|ticker_info| Effect::PlaceOrder(ticker_info, side, price));
+            }
+            Event::PanelInteraction(super::panel::Message::CancelOrdersAt(price)) => {
+                if let (Content::Ladder(Some(panel)), Some(ticker_info)) =
+                    (&self.content, self.stream_pair())
+                {
+                    let ids = panel.order_ids_at(price);
+                    if !ids.is_empty() {
+                        return Some(Effect::CancelOrders(ticker_info, ids));
+                    }
+                }
+            }
             Event::PanelInteraction(msg) => match &mut self.content {
                 Content::Ladder(Some(p)) => super::panel::update(p, msg),
                 Content::TimeAndSales(Some(p)) => super::panel::update(p, msg),
@@ -1171,6 +2339,9 @@ impl State {
             Event::ToggleIndicator(ind) => {
                 self.content.toggle_indicator(ind);
             }
+            Event::RemoveIndicator(id) => {
+                self.content.remove_indicator(id);
+            }
             Event::DeleteNotification(idx) => {
                 if idx < self.notifications.len() {
                     self.notifications.remove(idx);
@@ -1220,6 +2391,12 @@ impl State {
                     }
                 }
             },
+            Event::LinkedBasis(basis) => {
+                if let Some(Modal::StreamModifier(modifier)) = &mut self.modal {
+                    modifier.update_kind_with_basis(basis);
+                }
+                return self.set_basis(basis);
+            }
             Event::StreamModifierChanged(message) => {
                 if let Some(Modal::StreamModifier(mut modifier)) = self.modal.take() {
                     let mut effect: Option<Effect> = None;
@@ -1276,7 +2453,9 @@ impl State {
 
                                 if let Some(mut it) = self.streams.ready_iter_mut() {
                                     for s in &mut it {
-                                        if let StreamKind::Depth { depth_aggr, .. } = s {
+                                        if let StreamKind::Depth { depth_aggr, .. }
+                                        | StreamKind::CompositeDepth { depth_aggr, .. } = s
+                                        {
                                             *depth_aggr = if is_client {
                                                 StreamTicksize::Client
                                             } else {
@@ -1291,142 +2470,13 @@ impl State {
                             }
                             modal::stream::Action::BasisSelected(new_basis) => {
                                 modifier.update_kind_with_basis(new_basis);
-                                self.settings.selected_basis = Some(new_basis);
-
-                                let base_ticker = self.stream_pair();
-
-                                match &mut self.content {
-                                    Content::Heatmap { chart: Some(c), .. } => {
-                                        c.set_basis(new_basis);
-
-                                        if let Some(stream_type) =
-                                            self.streams.ready_iter_mut().and_then(|mut it| {
-                                                it.find(|s| matches!(s, StreamKind::Depth { .. }))
-                                            })
-                                            && let StreamKind::Depth {
-                                                push_freq,
-                                                ticker_info,
-                                                ..
-                                            } = stream_type
-                                            && ticker_info.exchange().is_custom_push_freq()
-                                        {
-                                            match new_basis {
-                                                Basis::Time(tf) => {
-                                                    *push_freq = exchange::PushFrequency::Custom(tf)
-                                                }
-                                                Basis::Tick(_) => {
-                                                    *push_freq =
-                                                        exchange::PushFrequency::ServerDefault
-                                                }
-                                            }
-                                        }
-
-                                        effect = Some(Effect::RefreshStreams);
-                                    }
-                                    Content::ShaderHeatmap {
-                                        chart: Some(c),
-                                        indicators,
-                                        ..
-                                    } => {
-                                        let saved_config = c.config;
-                                        let saved_studies = c.studies.clone();
-                                        **c = HeatmapShader::new(
-                                            new_basis,
-                                            c.tick_size(),
-                                            c.ticker_info,
-                                            saved_studies,
-                                            indicators.clone(),
-                                            Some(saved_config),
-                                        );
-
-                                        if let Some(stream_type) =
-                                            self.streams.ready_iter_mut().and_then(|mut it| {
-                                                it.find(|s| matches!(s, StreamKind::Depth { .. }))
-                                            })
-                                            && let StreamKind::Depth {
-                                                push_freq,
-                                                ticker_info,
-                                                ..
-                                            } = stream_type
-                                            && ticker_info.exchange().is_custom_push_freq()
-                                        {
-                                            match new_basis {
-                                                Basis::Time(tf) => {
-                                                    *push_freq = exchange::PushFrequency::Custom(tf)
-                                                }
-                                                Basis::Tick(_) => {
-                                                    *push_freq =
-                                                        exchange::PushFrequency::ServerDefault
-                                                }
-                                            }
-                                        }
 
-                                        effect = Some(Effect::RefreshStreams);
+                                effect = match self.link_group {
+                                    Some(group) if self.link_options.basis => {
+                                        Some(Effect::ShareBasis(group, new_basis))
                                     }
-                                    Content::Kline { chart: Some(c), .. } => {
-                                        if let Some(base_ticker) = base_ticker {
-                                            match new_basis {
-                                                Basis::Time(tf) => {
-                                                    let kline_stream = StreamKind::Kline {
-                                                        ticker_info: base_ticker,
-                                                        timeframe: tf,
-                                                    };
-                                                    let mut streams = vec![kline_stream];
-
-                                                    if matches!(
-                                                        c.kind,
-                                                        data::chart::KlineChartKind::Footprint { .. }
-                                                    ) {
-                                                        streams.push(StreamKind::Trades {
-                                                            ticker_info: base_ticker,
-                                                        });
-                                                    }
-
-                                                    self.streams = ResolvedStream::Ready(streams);
-                                                    let action = c.set_basis(new_basis);
-
-                                                    if let Some(chart::Action::RequestFetch(
-                                                        fetch,
-                                                    )) = action
-                                                    {
-                                                        effect = Some(Effect::RequestFetch(fetch));
-                                                    }
-                                                }
-                                                Basis::Tick(_) => {
-                                                    self.streams = ResolvedStream::Ready(vec![
-                                                        StreamKind::Trades {
-                                                            ticker_info: base_ticker,
-                                                        },
-                                                    ]);
-                                                    c.set_basis(new_basis);
-                                                    effect = Some(Effect::RefreshStreams);
-                                                }
-                                            }
-                                        }
-                                    }
-                                    Content::Comparison(Some(c)) => {
-                                        if let Basis::Time(tf) = new_basis {
-                                            let streams: Vec<StreamKind> = c
-                                                .selected_tickers()
-                                                .iter()
-                                                .copied()
-                                                .map(|ti| StreamKind::Kline {
-                                                    ticker_info: ti,
-                                                    timeframe: tf,
-                                                })
-                                                .collect();
-
-                                            self.streams = ResolvedStream::Ready(streams);
-                                            let action = c.set_basis(new_basis);
-
-                                            if let Some(chart::Action::RequestFetch(fetch)) = action
-                                            {
-                                                effect = Some(Effect::RequestFetch(fetch));
-                                            }
-                                        }
-                                    }
-                                    _ => {}
-                                }
+                                    _ => self.set_basis(new_basis),
+                                };
                             }
                         }
                     }
@@ -1459,9 +2509,41 @@ impl State {
 
                             return Some(Effect::RefreshStreams);
                         }
+                        super::chart::comparison::Action::PriceAxisChanged(ticker_info) => {
+                            chart.set_price_axis(ticker_info);
+                        }
+                        super::chart::comparison::Action::AddDerived(legs, op) => {
+                            chart.add_derived(legs, op);
+                        }
+                        super::chart::comparison::Action::DerivedColorChanged(index, color) => {
+                            chart.set_derived_color(index, color);
+                        }
+                        super::chart::comparison::Action::RemoveDerived(index) => {
+                            chart.remove_derived(index);
+                        }
+                        super::chart::comparison::Action::CsvExported(Ok(path)) => {
+                            self.notifications.push(Toast::info(format!(
+                                "Comparison exported to {}",
+                                path.display()
+                            )));
+                        }
+                        super::chart::comparison::Action::CsvExported(Err(err)) => {
+                            self.notifications
+                                .push(Toast::error(format!("Comparison export failed: {err}")));
+                        }
+                        super::chart::comparison::Action::CopyLegendValues(values) => {
+                            self.notifications
+                                .push(Toast::info("Legend values copied to the clipboard"));
+                            return Some(Effect::CopyToClipboard(values));
+                        }
                     }
                 }
             }
+            Event::SpreadChartInteraction(message) => {
+                if let Content::Spread(Some(chart)) = &mut self.content {
+                    chart.update(message);
+                }
+            }
             Event::HeatmapShaderInteraction(message) => {
                 if let Content::ShaderHeatmap { chart: Some(c), .. } = &mut self.content {
                     c.update(message);
@@ -1482,6 +2564,25 @@ impl State {
                                 let rebuilt = c.add_ticker(&ti);
                                 self.streams = ResolvedStream::Ready(rebuilt);
                                 return Some(Effect::RefreshStreams);
+                            } else if let Content::Spread(Some(c)) = &mut self.content {
+                                let Some(rebuilt) = c.add_ticker(&ti) else {
+                                    self.notifications.push(Toast::warn(format!(
+                                        "A spread takes exactly {} tickers, remove one first",
+                                        chart::spread::MAX_LEGS
+                                    )));
+                                    return None;
+                                };
+                                self.streams = ResolvedStream::Ready(rebuilt);
+                                return Some(Effect::RefreshStreams);
+                            } else if let Content::TimeAndSales(Some(panel)) = &mut self.content {
+                                let rebuilt = panel.add_source(&ti);
+                                self.streams = ResolvedStream::Ready(rebuilt);
+                                return Some(Effect::RefreshStreams);
+                            } else if matches!(self.content, Content::Kline { chart: Some(_), .. })
+                            {
+                                return self.set_kline_overlay(Some(ti));
+                            } else if self.content.supports_composite_depth() {
+                                return self.edit_composite_sources(ti, true);
                             }
                         }
                         crate::modal::pane::mini_tickers_list::RowSelection::Remove(ti) => {
@@ -1491,6 +2592,19 @@ impl State {
                                 let rebuilt = c.remove_ticker(&ti);
                                 self.streams = ResolvedStream::Ready(rebuilt);
                                 return Some(Effect::RefreshStreams);
+                            } else if let Content::Spread(Some(c)) = &mut self.content {
+                                let rebuilt = c.remove_ticker(&ti);
+                                self.streams = ResolvedStream::Ready(rebuilt);
+                                return Some(Effect::RefreshStreams);
+                            } else if let Content::TimeAndSales(Some(panel)) = &mut self.content {
+                                let rebuilt = panel.remove_source(&ti);
+                                self.streams = ResolvedStream::Ready(rebuilt);
+                                return Some(Effect::RefreshStreams);
+                            } else if matches!(self.content, Content::Kline { chart: Some(_), .. })
+                            {
+                                return self.set_kline_overlay(None);
+                            } else if self.content.supports_composite_depth() {
+                                return self.edit_composite_sources(ti, false);
                             }
                         }
                         crate::modal::pane::mini_tickers_list::RowSelection::Switch(ti) => {
@@ -1555,6 +2669,78 @@ impl State {
             ));
         }
 
+        if !treat_as_starter
+            && matches!(
+                &self.content,
+                Content::Heatmap { .. } | Content::Kline { .. }
+            )
+        {
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::Edit, 12),
+                show_modal(Modal::Drawings),
+                Some("Drawing tools"),
+                tooltip_pos,
+                modal_btn_style(Modal::Drawings),
+            ));
+        }
+
+        if !treat_as_starter && self.can_replay() {
+            buttons = buttons.push(button_with_tooltip(
+                text("REC").size(crate::style::text_size::TINY),
+                Message::PaneEvent(pane, Event::ToggleRecording),
+                Some(if self.recorder.is_some() {
+                    "Stop recording"
+                } else {
+                    "Record depth and trades"
+                }),
+                tooltip_pos,
+                control_btn_style(self.recorder.is_some()),
+            ));
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::Return, 12),
+                Message::PaneEvent(pane, Event::OpenReplay),
+                Some("Replay latest recording"),
+                tooltip_pos,
+                control_btn_style(self.replay.is_some()),
+            ));
+        }
+
+        if !treat_as_starter {
+            buttons = buttons.push(button_with_tooltip(
+                text("PNG").size(crate::style::text_size::TINY),
+                Message::Snapshot(pane),
+                Some("Save as image, its path is copied to the clipboard"),
+                tooltip_pos,
+                control_btn_style(false),
+            ));
+        }
+
+        if !is_popout
+            && !treat_as_starter
+            && matches!(
+                &self.content,
+                Content::Heatmap { .. } | Content::Kline { .. } | Content::ShaderHeatmap { .. }
+            )
+        {
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::ExternalLink, 12),
+                Message::PairLadder(pane),
+                Some("Trader mode: paired ladder window"),
+                tooltip_pos,
+                control_btn_style(false),
+            ));
+        }
+
+        if !treat_as_starter {
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::Clone, 12),
+                Message::DuplicatePane(pane),
+                Some("Duplicate pane"),
+                tooltip_pos,
+                control_btn_style(false),
+            ));
+        }
+
         if is_popout {
             buttons = buttons.push(button_with_tooltip(
                 icon_text(Icon::Popout, 12),
@@ -1604,6 +2790,48 @@ impl State {
             .into()
     }
 
+    /// Accent assigned to the pane's ticker or its exchange
+    fn accent(&self, accents: &Accents) -> Option<iced::Color> {
+        self.stream_pair()
+            .and_then(|ticker_info| accents.resolve(&ticker_info.ticker))
+    }
+
+    /// Layers the configured tint and ticker watermark behind the pane content
+    fn with_appearance<'a>(
+        &'a self,
+        base: Element<'a, Message>,
+        accent: Option<iced::Color>,
+    ) -> Element<'a, Message> {
+        let Appearance { tint, watermark } = self.settings.appearance;
+
+        if tint.is_none() && !watermark {
+            return base;
+        }
+
+        let mut layers = stack![];
+
+        if let Some(tint) = tint {
+            layers = layers.push(
+                container(space())
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .style(move |_theme| style::pane_tint(tint)),
+            );
+        }
+
+        if watermark && let Some(ticker_info) = self.stream_pair() {
+            let symbol = ticker_info.ticker.display_symbol_and_type().0;
+            layers = layers.push(center(
+                text(symbol)
+                    .size(48)
+                    .font(style::AZERET_MONO)
+                    .style(move |theme| style::pane_watermark(theme, accent)),
+            ));
+        }
+
+        layers.push(base).into()
+    }
+
     fn compose_stack_view<'a, F>(
         &'a self,
         base: Element<'a, Message>,
@@ -1613,10 +2841,17 @@ impl State {
         settings_modal: F,
         selected_tickers: Option<&'a [TickerInfo]>,
         tickers_table: &'a TickersTable,
+        stream_rates: &'a StreamRates,
+        accents: &'a Accents,
     ) -> Element<'a, Message>
     where
         F: FnOnce() -> Element<'a, Message>,
     {
+        let base: Element<'a, Message> =
+            container(self.with_appearance(base, self.accent(accents)))
+                .id(self.snapshot_id())
+                .into();
+
         let base =
             widget::toast::Manager::new(base, &self.notifications, Alignment::End, move |msg| {
                 Message::PaneEvent(pane, Event::DeleteNotification(msg))
@@ -1627,7 +2862,7 @@ impl State {
 
         match &self.modal {
             Some(Modal::LinkGroup) => {
-                let content = link_group_modal(pane, self.link_group);
+                let content = link_group_modal(pane, self.link_group, self.link_options);
 
                 stack_modal(
                     base,
@@ -1639,9 +2874,11 @@ impl State {
             }
             Some(Modal::StreamModifier(modifier)) => stack_modal(
                 base,
-                modifier.view(self.stream_pair_kind()).map(move |message| {
-                    Message::PaneEvent(pane, Event::StreamModifierChanged(message))
-                }),
+                modifier
+                    .view(self.stream_pair_kind(), stream_rates)
+                    .map(move |message| {
+                        Message::PaneEvent(pane, Event::StreamModifierChanged(message))
+                    }),
                 Message::PaneEvent(pane, Event::HideModal),
                 padding::right(12).left(48),
                 Alignment::Start,
@@ -1669,7 +2906,17 @@ impl State {
             }
             Some(Modal::Settings) => stack_modal(
                 base,
-                settings_modal(),
+                column![
+                    settings_modal(),
+                    modal::pane::settings::appearance_cfg_view(
+                        self.settings.appearance,
+                        pane,
+                        accents,
+                        self.stream_pair().map(|ticker_info| ticker_info.ticker),
+                    ),
+                ]
+                .spacing(8)
+                .align_x(Alignment::End),
                 on_blur,
                 padding::right(12).left(12),
                 Alignment::End,
@@ -1681,6 +2928,44 @@ impl State {
                 padding::right(12).left(12),
                 Alignment::End,
             ),
+            Some(Modal::Drawings) => {
+                let content = match self.drawing_tool() {
+                    Some((tool, supported)) => {
+                        let count = self
+                            .stream_pair()
+                            .map(|ticker_info| SerTicker::from_parts(ticker_info.ticker))
+                            .map_or(0, |ticker| {
+                                self.settings.drawings.of(&ticker).len()
+                                    + self.settings.notes.of(&ticker).len()
+                            });
+
+                        drawings_modal(pane, tool, self.settings.drawing_snap, supported, count)
+                    }
+                    None => column![].into(),
+                };
+
+                stack_modal(
+                    base,
+                    content,
+                    on_blur,
+                    padding::right(12).left(12),
+                    Alignment::End,
+                )
+            }
+            Some(Modal::Note(index)) => {
+                let content = match self.notes().get(*index) {
+                    Some(note) => note_editor(pane, *index, note),
+                    None => column![].into(),
+                };
+
+                stack_modal(
+                    base,
+                    content,
+                    on_blur,
+                    padding::right(12).left(12),
+                    Alignment::End,
+                )
+            }
             Some(Modal::Controls) => stack_modal(
                 base,
                 if let Some(controls) = compact_controls {
@@ -1696,54 +2981,428 @@ impl State {
         }
     }
 
-    pub fn matches_stream(&self, stream: &StreamKind) -> bool {
-        self.streams.matches_stream(stream)
+    pub fn matches_stream(&self, stream: &StreamKind) -> bool {
+        self.streams.matches_stream(stream)
+    }
+
+    fn show_modal_with_focus(&mut self, requested_modal: Modal) -> Option<Effect> {
+        let should_toggle_close = match (&self.modal, &requested_modal) {
+            (Some(Modal::StreamModifier(open)), Modal::StreamModifier(req)) => {
+                open.view_mode == req.view_mode
+            }
+            (Some(open), req) => core::mem::discriminant(open) == core::mem::discriminant(req),
+            _ => false,
+        };
+
+        if should_toggle_close {
+            self.modal = None;
+            return None;
+        }
+
+        let focus_widget_id = match &requested_modal {
+            Modal::MiniTickersList(m) => Some(m.search_box_id.clone()),
+            _ => None,
+        };
+
+        self.modal = Some(requested_modal);
+        focus_widget_id.map(Effect::FocusWidget)
+    }
+
+    pub fn invalidate(&mut self, now: Instant) -> Option<Action> {
+        match &mut self.content {
+            Content::Heatmap { chart, .. } => chart
+                .as_mut()
+                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
+            Content::Kline { chart, .. } => chart
+                .as_mut()
+                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
+            Content::TimeAndSales(panel) => panel
+                .as_mut()
+                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
+            Content::Ladder(panel) => panel
+                .as_mut()
+                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
+            Content::Starter => None,
+            Content::Comparison(chart) => chart
+                .as_mut()
+                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
+            Content::Spread(chart) => chart
+                .as_mut()
+                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
+            Content::ShaderHeatmap { chart, .. } => chart
+                .as_mut()
+                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
+        }
+    }
+
+    pub fn sync_scale_factor(&mut self, scale_factor: f32) {
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => {
+                chart::sync_scale_factor(&mut **c, scale_factor)
+            }
+            Content::Kline { chart: Some(c), .. } => {
+                chart::sync_scale_factor(&mut **c, scale_factor)
+            }
+            Content::ShaderHeatmap { chart: Some(c), .. } => c.set_scale_factor(scale_factor),
+            Content::Ladder(Some(panel)) => panel.set_scale_factor(scale_factor),
+            Content::TimeAndSales(Some(panel)) => panel.set_scale_factor(scale_factor),
+            Content::Comparison(Some(c)) => c.set_scale_factor(scale_factor),
+            Content::Spread(Some(c)) => c.set_scale_factor(scale_factor),
+            _ => {}
+        }
+    }
+
+    /// Mirrors the price cross alerts of the pane's ticker onto its chart
+    pub fn sync_alert_levels(&mut self, levels: &FxHashMap<Ticker, Vec<(u32, f64)>>) {
+        let levels = self
+            .stream_pair()
+            .and_then(|ticker_info| levels.get(&ticker_info.ticker))
+            .map_or(&[][..], Vec::as_slice);
+
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => chart::sync_alert_levels(&mut **c, levels),
+            Content::Kline { chart: Some(c), .. } => chart::sync_alert_levels(&mut **c, levels),
+            _ => {}
+        }
+    }
+
+    /// Mirrors the drawings and notes kept for the pane's ticker onto its chart
+    pub fn sync_drawings(&mut self) {
+        let ticker = self
+            .stream_pair()
+            .map(|ticker_info| SerTicker::from_parts(ticker_info.ticker));
+        let drawings = ticker
+            .as_ref()
+            .map_or(&[][..], |ticker| self.settings.drawings.of(ticker));
+        let notes = ticker
+            .as_ref()
+            .map_or(&[][..], |ticker| self.settings.notes.of(ticker));
+        let snap = self.settings.drawing_snap;
+
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => {
+                chart::sync_drawings(&mut **c, drawings, notes, snap);
+            }
+            Content::Kline { chart: Some(c), .. } => {
+                chart::sync_drawings(&mut **c, drawings, notes, snap);
+            }
+            _ => {}
+        }
+    }
+
+    /// Switches the pane to `new_basis`, picked on it or on another pane of its link group
+    fn set_basis(&mut self, new_basis: Basis) -> Option<Effect> {
+        let mut effect = None;
+
+        self.settings.selected_basis = Some(new_basis);
+
+        let base_ticker = self.stream_pair();
+
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => {
+                c.set_basis(new_basis);
+
+                if let Some(stream_type) = self
+                    .streams
+                    .ready_iter_mut()
+                    .and_then(|mut it| it.find(|s| matches!(s, StreamKind::Depth { .. })))
+                    && let StreamKind::Depth {
+                        push_freq,
+                        ticker_info,
+                        ..
+                    } = stream_type
+                    && ticker_info.exchange().is_custom_push_freq()
+                {
+                    match new_basis {
+                        Basis::Time(tf) => *push_freq = exchange::PushFrequency::Custom(tf),
+                        Basis::Tick(_) | Basis::Bars(_) => {
+                            *push_freq = exchange::PushFrequency::ServerDefault
+                        }
+                    }
+                }
+
+                effect = Some(Effect::RefreshStreams);
+            }
+            Content::ShaderHeatmap {
+                chart: Some(c),
+                indicators,
+                ..
+            } => {
+                let saved_config = c.config;
+                let saved_studies = c.studies.clone();
+                **c = HeatmapShader::new(
+                    new_basis,
+                    c.tick_size(),
+                    c.ticker_info,
+                    saved_studies,
+                    indicators.clone(),
+                    Some(saved_config),
+                );
+
+                if let Some(stream_type) = self
+                    .streams
+                    .ready_iter_mut()
+                    .and_then(|mut it| it.find(|s| matches!(s, StreamKind::Depth { .. })))
+                    && let StreamKind::Depth {
+                        push_freq,
+                        ticker_info,
+                        ..
+                    } = stream_type
+                    && ticker_info.exchange().is_custom_push_freq()
+                {
+                    match new_basis {
+                        Basis::Time(tf) => *push_freq = exchange::PushFrequency::Custom(tf),
+                        Basis::Tick(_) | Basis::Bars(_) => {
+                            *push_freq = exchange::PushFrequency::ServerDefault
+                        }
+                    }
+                }
+
+                effect = Some(Effect::RefreshStreams);
+            }
+            Content::Kline { chart: Some(c), .. } => {
+                if let Some(base_ticker) = base_ticker {
+                    let kline_timeframe = match new_basis {
+                        Basis::Time(tf) => base_ticker.exchange().kline_fetch_timeframe(tf),
+                        Basis::Tick(_) | Basis::Bars(_) => None,
+                    };
+
+                    match kline_timeframe {
+                        Some(tf) => {
+                            let kline_stream = StreamKind::Kline {
+                                ticker_info: base_ticker,
+                                timeframe: tf,
+                            };
+                            let mut streams = vec![kline_stream];
+
+                            if matches!(c.kind, data::chart::KlineChartKind::Footprint { .. }) {
+                                streams.push(StreamKind::Trades {
+                                    ticker_info: base_ticker,
+                                });
+                            }
+
+                            self.streams = ResolvedStream::Ready(streams);
+                            let action = c.set_basis(new_basis);
+
+                            if let Some(chart::Action::RequestFetch(fetch)) = action {
+                                effect = Some(Effect::RequestFetch(fetch));
+                            }
+                        }
+                        None => {
+                            self.streams = ResolvedStream::Ready(vec![StreamKind::Trades {
+                                ticker_info: base_ticker,
+                            }]);
+                            c.set_basis(new_basis);
+                            effect = Some(Effect::RefreshStreams);
+                        }
+                    }
+                }
+            }
+            Content::Comparison(Some(c)) => {
+                if let Basis::Time(_) | Basis::Tick(_) = new_basis {
+                    let action = c.set_basis(new_basis);
+                    self.streams = ResolvedStream::Ready(c.streams_for_all());
+
+                    // Tick bars have nothing to fetch, only new streams
+                    effect = match action {
+                        Some(chart::Action::RequestFetch(fetch)) => {
+                            Some(Effect::RequestFetch(fetch))
+                        }
+                        _ => Some(Effect::RefreshStreams),
+                    };
+                }
+            }
+            Content::Spread(Some(c)) => {
+                if let Basis::Time(_) | Basis::Tick(_) = new_basis {
+                    let action = c.set_basis(new_basis);
+                    self.streams = ResolvedStream::Ready(c.streams_for_all());
+
+                    effect = match action {
+                        Some(chart::Action::RequestFetch(fetch)) => {
+                            Some(Effect::RequestFetch(fetch))
+                        }
+                        _ => Some(Effect::RefreshStreams),
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        self.sync_mark_price_stream();
+        self.sync_overlay_stream();
+
+        effect
+    }
+
+    /// Whether a basis picked on another pane of the link group suits the pane's content
+    pub fn accepts_basis(&self, basis: Basis) -> bool {
+        let ticker_info = self.stream_pair();
+
+        match (&self.content, basis) {
+            (Content::Heatmap { .. } | Content::ShaderHeatmap { .. }, Basis::Time(tf)) => {
+                Timeframe::HEATMAP.contains(&tf)
+                    && ticker_info.is_none_or(|ti| ti.exchange().supports_heatmap_timeframe(tf))
+            }
+            // Sub-second timeframes are heatmap only, seconds are built from trades
+            (Content::Kline { .. }, Basis::Time(tf)) => tf.to_milliseconds() >= 1000,
+            (Content::Kline { .. }, Basis::Tick(_)) => true,
+            (Content::Kline { kind, .. }, Basis::Bars(bars)) => {
+                bars.keeps_candles() || matches!(kind, data::chart::KlineChartKind::Candles)
+            }
+            (Content::Comparison(_) | Content::Spread(_), Basis::Time(tf)) => {
+                tf.is_custom() || Timeframe::KLINE.contains(&tf)
+            }
+            (Content::Comparison(_) | Content::Spread(_), Basis::Tick(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Earliest and latest time the pane's chart shows, on time basis only
+    fn visible_timespan(&self) -> Option<(UnixMs, UnixMs)> {
+        match &self.content {
+            Content::Heatmap { chart: Some(c), .. } => chart::visible_timespan(&**c),
+            Content::Kline { chart: Some(c), .. } => chart::visible_timespan(&**c),
+            _ => None,
+        }
     }
 
-    fn show_modal_with_focus(&mut self, requested_modal: Modal) -> Option<Effect> {
-        let should_toggle_close = match (&self.modal, &requested_modal) {
-            (Some(Modal::StreamModifier(open)), Modal::StreamModifier(req)) => {
-                open.view_mode == req.view_mode
+    /// Zooms and scrolls the pane's chart to the time span another pane of the link group shows
+    pub fn sync_linked_timespan(&mut self, span: (UnixMs, UnixMs)) {
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => chart::sync_linked_timespan(&mut **c, span),
+            Content::Kline { chart: Some(c), .. } => chart::sync_linked_timespan(&mut **c, span),
+            _ => {}
+        }
+    }
+
+    /// Marks the candle hovered on another pane of the link group, `None` once it's left
+    pub fn sync_linked_crosshair(&mut self, crosshair: Option<LinkedCrosshair>) {
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => {
+                chart::sync_linked_crosshair(&mut **c, crosshair)
             }
-            (Some(open), req) => core::mem::discriminant(open) == core::mem::discriminant(req),
-            _ => false,
+            Content::Kline { chart: Some(c), .. } => {
+                chart::sync_linked_crosshair(&mut **c, crosshair)
+            }
+            Content::TimeAndSales(Some(panel)) => {
+                panel.set_linked_span(crosshair.map(|c| (c.time, c.span_ms)));
+            }
+            _ => {}
+        }
+    }
+
+    fn notes(&self) -> &[Note] {
+        self.stream_pair()
+            .map(|ticker_info| SerTicker::from_parts(ticker_info.ticker))
+            .map_or(&[][..], |ticker| self.settings.notes.of(&ticker))
+    }
+
+    fn edit_notes(&mut self, f: impl FnOnce(&mut Vec<Note>)) {
+        let Some(ticker_info) = self.stream_pair() else {
+            return;
         };
+        self.settings
+            .notes
+            .edit(SerTicker::from_parts(ticker_info.ticker), f);
+        self.sync_drawings();
+    }
 
-        if should_toggle_close {
-            self.modal = None;
-            return None;
+    fn edit_drawings(&mut self, f: impl FnOnce(&mut Vec<Drawing>)) {
+        let Some(ticker_info) = self.stream_pair() else {
+            return;
+        };
+        self.settings
+            .drawings
+            .edit(SerTicker::from_parts(ticker_info.ticker), f);
+        self.sync_drawings();
+    }
+
+    /// Tool picked on the pane's chart and whether it takes drawings at all
+    fn drawing_tool(&self) -> Option<(Option<Tool>, bool)> {
+        match &self.content {
+            Content::Heatmap { chart: Some(c), .. } => {
+                Some((chart::drawing_tool(&**c), chart::supports_drawings(&**c)))
+            }
+            Content::Kline { chart: Some(c), .. } => {
+                Some((chart::drawing_tool(&**c), chart::supports_drawings(&**c)))
+            }
+            _ => None,
         }
+    }
 
-        let focus_widget_id = match &requested_modal {
-            Modal::MiniTickersList(m) => Some(m.search_box_id.clone()),
+    /// Mirrors the orders and positions of the pane's ticker onto its ladder or chart
+    pub fn sync_account(&mut self, accounts: &FxHashMap<Ticker, Account>) {
+        let account = self
+            .stream_pair()
+            .and_then(|ticker_info| accounts.get(&ticker_info.ticker));
+
+        match &mut self.content {
+            Content::Ladder(Some(panel)) => {
+                panel.set_working_orders(account.map_or(&[][..], |a| a.orders.as_slice()));
+            }
+            Content::Heatmap { chart: Some(c), .. } => chart::sync_chart_levels(&mut **c, account),
+            Content::Kline { chart: Some(c), .. } => chart::sync_chart_levels(&mut **c, account),
+            _ => {}
+        }
+    }
+
+    /// Link group and size filter of a tape marking its trades on the group's charts
+    pub fn tape_marks(&self) -> Option<(LinkGroup, f32)> {
+        match &self.content {
+            Content::TimeAndSales(Some(panel)) if panel.config.mark_on_chart => {
+                Some((self.link_group?, panel.config.trade_size_filter))
+            }
             _ => None,
+        }
+    }
+
+    pub fn sync_tape_marks(&mut self, marks: &FxHashMap<LinkGroup, f32>) {
+        let min_size = self.link_group.and_then(|group| marks.get(&group).copied());
+
+        match &mut self.content {
+            Content::Kline { chart: Some(c), .. } => c.set_tape_marks(min_size),
+            Content::Heatmap { chart: Some(c), .. } => c.set_tape_marks(min_size),
+            _ => {}
+        }
+    }
+
+    pub fn sync_journal(&mut self, markers: &FxHashMap<Ticker, Vec<JournalMarker>>) {
+        let Some(ticker_info) = self.stream_pair() else {
+            return;
         };
 
-        self.modal = Some(requested_modal);
-        focus_widget_id.map(Effect::FocusWidget)
+        if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+            c.set_journal_markers(
+                markers
+                    .get(&ticker_info.ticker)
+                    .map_or(&[][..], Vec::as_slice),
+            );
+        }
     }
 
-    pub fn invalidate(&mut self, now: Instant) -> Option<Action> {
+    /// Pans a kline chart of `ticker` to a journal entry, returns whether this pane is one
+    pub fn focus_journal_entry(&mut self, ticker: Ticker, time: UnixMs, price: Price) -> bool {
+        if self.stream_pair().is_none_or(|info| info.ticker != ticker) {
+            return false;
+        }
+
         match &mut self.content {
-            Content::Heatmap { chart, .. } => chart
-                .as_mut()
-                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
-            Content::Kline { chart, .. } => chart
-                .as_mut()
-                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
-            Content::TimeAndSales(panel) => panel
-                .as_mut()
-                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
-            Content::Ladder(panel) => panel
-                .as_mut()
-                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
-            Content::Starter => None,
-            Content::Comparison(chart) => chart
-                .as_mut()
-                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
-            Content::ShaderHeatmap { chart, .. } => chart
-                .as_mut()
-                .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
+            Content::Kline { chart: Some(c), .. } => {
+                chart::focus_on(&mut **c, time, price);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Ticker of the ladder with order entry or chart the account overlay is drawn on
+    pub fn traded_ticker(&self) -> Option<TickerInfo> {
+        match &self.content {
+            Content::Ladder(Some(panel)) if panel.config.order_entry => self.stream_pair(),
+            Content::Heatmap { chart: Some(_), .. } | Content::Kline { chart: Some(_), .. } => {
+                self.stream_pair()
+            }
+            _ => None,
         }
     }
 
@@ -1756,7 +3415,7 @@ impl State {
 
     pub fn update_interval(&self) -> Option<u64> {
         match &self.content {
-            Content::Kline { .. } | Content::Comparison(_) => Some(1000),
+            Content::Kline { .. } | Content::Comparison(_) | Content::Spread(_) => Some(1000),
             Content::Heatmap { chart, .. } => {
                 if let Some(chart) = chart {
                     chart.basis_interval()
@@ -1775,6 +3434,15 @@ impl State {
     }
 
     pub fn tick(&mut self, now: Instant) -> Option<Action> {
+        if let Status::Reconnecting {
+            retry_at,
+            secs_left,
+            ..
+        } = &mut self.status
+        {
+            *secs_left = retry_at.saturating_duration_since(now).as_secs();
+        }
+
         let invalidate_interval: Option<u64> = self.update_interval();
         let last_tick: Option<Instant> = self.last_tick();
 
@@ -1786,6 +3454,9 @@ impl State {
             return Some(Action::ResolveContent);
         }
 
+        self.advance_replay(now);
+        self.watch_loading_status(now);
+
         match (invalidate_interval, last_tick) {
             (Some(interval_ms), Some(previous_tick_time)) => {
                 if interval_ms > 0 {
@@ -1808,9 +3479,59 @@ impl State {
         None
     }
 
+    /// Times out a loading status that hasn't changed for [`STALLED_STATUS_AFTER`].
+    /// Requests retry on their own well before that, so it only fires once those gave up
+    /// or the task died without reporting back
+    fn watch_loading_status(&mut self, now: Instant) {
+        let Status::Loading(info) = self.status else {
+            self.loading_since = None;
+            return;
+        };
+
+        let since = match self.loading_since {
+            Some((seen, since)) if seen == info => since,
+            _ => {
+                self.loading_since = Some((info, now));
+                return;
+            }
+        };
+
+        if now.duration_since(since) >= STALLED_STATUS_AFTER {
+            log::warn!("Pane {} stuck at {info:?}, timing out", self.id);
+
+            self.loading_since = None;
+            self.status = Status::TimedOut(info);
+            self.notifications.push(Toast::error(format!(
+                "{} timed out, use Retry in the pane's title bar to try again",
+                info_label(info)
+            )));
+        }
+    }
+
+    /// Forgets pending and failed requests so the chart asks for its missing data again
+    fn retry_fetch(&mut self) {
+        match &mut self.content {
+            Content::Kline { chart: Some(c), .. } => c.reset_request_handler(),
+            Content::Comparison(Some(c)) => c.reset_request_handlers(),
+            _ => {}
+        }
+        self.status = Status::Ready;
+    }
+
     pub fn unique_id(&self) -> uuid::Uuid {
         self.id
     }
+
+    /// Widget id of the pane's content, used to locate it for snapshots
+    pub fn snapshot_id(&self) -> iced::widget::Id {
+        iced::widget::Id::from(format!("pane-content-{}", self.id))
+    }
+
+    /// Names snapshot files after the pane's ticker, if it has one
+    pub fn snapshot_label(&self) -> String {
+        self.stream_pair()
+            .map_or_else(|| "pane".to_string(), |ti| ti.ticker.to_string())
+    }
 }
 
 impl Default for State {
@@ -1824,6 +3545,18 @@ impl Default for State {
             notifications: vec![],
             status: Status::Ready,
             link_group: None,
+            link_options: LinkOptions::default(),
+            shared_crosshair: None,
+            composite_depth: None,
+            recorder: None,
+            timelapse: None,
+            timelapse_outcome: None,
+            loading_since: None,
+            replay: None,
+            burst_tracker: BurstTracker::default(),
+            indicator_templates: IndicatorTemplates::default(),
+            template_name: String::new(),
+            needs_sync: true,
         }
     }
 }
@@ -1833,7 +3566,7 @@ pub enum Content {
     #[default]
     Starter,
     Heatmap {
-        chart: Option<HeatmapChart>,
+        chart: Option<Box<HeatmapChart>>,
         indicators: Vec<HeatmapIndicator>,
         layout: data::chart::ViewConfig,
         studies: Vec<data::chart::heatmap::HeatmapStudy>,
@@ -1844,14 +3577,15 @@ pub enum Content {
         studies: Vec<data::chart::heatmap::HeatmapStudy>,
     },
     Kline {
-        chart: Option<KlineChart>,
-        indicators: Vec<KlineIndicator>,
+        chart: Option<Box<KlineChart>>,
+        indicators: Vec<KlineIndicatorInstance>,
         layout: data::chart::ViewConfig,
         kind: data::chart::KlineChartKind,
     },
     TimeAndSales(Option<TimeAndSales>),
     Ladder(Option<Ladder>),
     Comparison(Option<ComparisonChart>),
+    Spread(Option<SpreadChart>),
 }
 
 impl Content {
@@ -1905,7 +3639,7 @@ impl Content {
         );
 
         Content::Heatmap {
-            chart: Some(chart),
+            chart: Some(Box::new(chart)),
             indicators: enabled_indicators,
             layout,
             studies: prev_studies,
@@ -1955,11 +3689,11 @@ impl Content {
         let enabled_indicators = {
             let available = KlineIndicator::for_market(ticker_info.market_type());
             prev_indis.map_or_else(
-                || vec![KlineIndicator::Volume],
+                || vec![KlineIndicatorInstance::new(KlineIndicator::Volume)],
                 |indis| {
                     indis
                         .into_iter()
-                        .filter(|i| available.contains(i))
+                        .filter(|i| available.contains(&i.kind))
                         .collect()
                 },
             )
@@ -1995,7 +3729,7 @@ impl Content {
             });
         let visual_config = settings.visual_config.as_ref().and_then(|cfg| cfg.kline());
 
-        let chart = KlineChart::new(
+        let mut chart = KlineChart::new(
             layout.clone(),
             basis,
             step,
@@ -2006,9 +3740,14 @@ impl Content {
             &determined_chart_kind,
             visual_config,
         );
+        chart.set_moving_averages(&settings.moving_averages);
+        chart.set_bands(&settings.bands);
+        chart.set_vwap(settings.vwap);
+        chart.set_profile_ranges(settings.profile_ranges.clone());
+        chart.set_scripts(&settings.scripts);
 
         Content::Kline {
-            chart: Some(chart),
+            chart: Some(Box::new(chart)),
             indicators: enabled_indicators,
             layout,
             kind: determined_chart_kind,
@@ -2020,7 +3759,7 @@ impl Content {
             ContentKind::Starter => Content::Starter,
             ContentKind::CandlestickChart => Content::Kline {
                 chart: None,
-                indicators: vec![KlineIndicator::Volume],
+                indicators: vec![KlineIndicatorInstance::new(KlineIndicator::Volume)],
                 kind: data::chart::KlineChartKind::Candles,
                 layout: ViewConfig {
                     splits: vec![],
@@ -2029,7 +3768,7 @@ impl Content {
             },
             ContentKind::FootprintChart => Content::Kline {
                 chart: None,
-                indicators: vec![KlineIndicator::Volume],
+                indicators: vec![KlineIndicatorInstance::new(KlineIndicator::Volume)],
                 kind: data::chart::KlineChartKind::Footprint {
                     clusters: data::chart::kline::ClusterKind::default(),
                     scaling: data::chart::kline::ClusterScaling::default(),
@@ -2057,6 +3796,7 @@ impl Content {
                 },
             },
             ContentKind::ComparisonChart => Content::Comparison(None),
+            ContentKind::SpreadChart => Content::Spread(None),
             ContentKind::TimeAndSales => Content::TimeAndSales(None),
             ContentKind::Ladder => Content::Ladder(None),
         }
@@ -2069,6 +3809,7 @@ impl Content {
             Content::TimeAndSales(panel) => Some(panel.as_ref()?.last_update()),
             Content::Ladder(panel) => Some(panel.as_ref()?.last_update()),
             Content::Comparison(chart) => Some(chart.as_ref()?.last_update()),
+            Content::Spread(chart) => Some(chart.as_ref()?.last_update()),
             Content::Starter => None,
             Content::ShaderHeatmap { chart, .. } => Some(chart.as_ref()?.last_tick?),
         }
@@ -2081,6 +3822,51 @@ impl Content {
         }
     }
 
+    /// Applies a preset's indicator panels to a kline chart, dropping any others
+    pub fn apply_preset(&mut self, preset: PanePreset) {
+        let Content::Kline {
+            chart: Some(chart),
+            indicators,
+            layout,
+            ..
+        } = self
+        else {
+            return;
+        };
+
+        let wanted = preset
+            .kline_indicators()
+            .iter()
+            .map(|&kind| KlineIndicatorInstance::new(kind))
+            .collect::<Vec<_>>();
+
+        *indicators = chart.set_indicators(&wanted, preset.main_split());
+        *layout = chart.chart_layout();
+    }
+
+    /// Applies a saved template's panels and overlays to a kline chart, keeping the height
+    /// of the main chart
+    pub fn apply_indicator_template(&mut self, template: &IndicatorTemplate) {
+        let Content::Kline {
+            chart: Some(chart),
+            indicators,
+            layout,
+            ..
+        } = self
+        else {
+            return;
+        };
+
+        chart.set_moving_averages(&template.moving_averages);
+        chart.set_bands(&template.bands);
+        chart.set_vwap(template.vwap);
+        chart.set_scripts(&template.scripts);
+
+        let main_split = layout.splits.first().copied().unwrap_or(0.8);
+        *indicators = chart.set_indicators(&template.indicators, main_split);
+        *layout = chart.chart_layout();
+    }
+
     pub fn toggle_indicator(&mut self, indicator: UiIndicator) {
         match (self, indicator) {
             (
@@ -2110,12 +3896,18 @@ impl Content {
                     return;
                 };
 
-                if indicators.contains(&ind) {
-                    indicators.retain(|i| i != &ind);
+                // Kinds that can be added more than once are removed one panel at a time
+                if !ind.allows_multiple()
+                    && let Some(existing) = indicators.iter().find(|i| i.kind == ind)
+                {
+                    let id = existing.id;
+                    indicators.retain(|i| i.id != id);
+                    chart.remove_indicator(id);
                 } else {
-                    indicators.push(ind);
+                    let instance = KlineIndicatorInstance::new(ind);
+                    indicators.push(instance);
+                    chart.add_indicator(instance);
                 }
-                chart.toggle_indicator(ind);
             }
             (
                 Content::ShaderHeatmap {
@@ -2138,6 +3930,18 @@ impl Content {
         }
     }
 
+    pub fn remove_indicator(&mut self, id: uuid::Uuid) {
+        if let Content::Kline {
+            chart: Some(chart),
+            indicators,
+            ..
+        } = self
+        {
+            indicators.retain(|i| i.id != id);
+            chart.remove_indicator(id);
+        }
+    }
+
     pub fn reorder_indicators(&mut self, event: &column_drag::DragEvent) {
         match self {
             Content::Heatmap { indicators, .. } => column_drag::reorder_vec(indicators, event),
@@ -2146,6 +3950,7 @@ impl Content {
             | Content::Ladder(_)
             | Content::Starter
             | Content::Comparison(_)
+            | Content::Spread(_)
             | Content::ShaderHeatmap { .. } => {
                 panic!("indicator reorder on {} pane", self)
             }
@@ -2164,7 +3969,10 @@ impl Content {
                 c.set_visual_config(cfg);
             }
             (Content::Comparison(Some(chart)), VisualConfig::Comparison(cfg)) => {
-                chart.config = cfg;
+                chart.set_config(cfg);
+            }
+            (Content::Spread(Some(chart)), VisualConfig::Spread(cfg)) => {
+                chart.set_config(cfg);
             }
             (Content::TimeAndSales(Some(panel)), VisualConfig::TimeAndSales(cfg)) => {
                 panel.config = cfg;
@@ -2192,7 +4000,8 @@ impl Content {
             Content::TimeAndSales(_)
             | Content::Ladder(_)
             | Content::Starter
-            | Content::Comparison(_) => None,
+            | Content::Comparison(_)
+            | Content::Spread(_) => None,
         }
     }
 
@@ -2252,6 +4061,7 @@ impl Content {
             Content::TimeAndSales(_) => ContentKind::TimeAndSales,
             Content::Ladder(_) => ContentKind::Ladder,
             Content::Comparison(_) => ContentKind::ComparisonChart,
+            Content::Spread(_) => ContentKind::SpreadChart,
             Content::Starter => ContentKind::Starter,
             Content::ShaderHeatmap { .. } => ContentKind::ShaderHeatmap,
         }
@@ -2263,6 +4073,107 @@ impl Content {
         }
     }
 
+    fn supports_composite_depth(&self) -> bool {
+        matches!(
+            self,
+            Content::Heatmap { .. } | Content::ShaderHeatmap { .. } | Content::Ladder(_)
+        )
+    }
+
+    /// Contents fed by raw depth or trade events, which can be recorded and replayed
+    fn supports_replay(&self) -> bool {
+        match self {
+            Content::Kline { kind, .. } => {
+                matches!(kind, data::chart::KlineChartKind::Footprint { .. })
+            }
+            Content::Heatmap { .. }
+            | Content::ShaderHeatmap { .. }
+            | Content::TimeAndSales(_)
+            | Content::Ladder(_) => true,
+            Content::Comparison(_) | Content::Spread(_) | Content::Starter => false,
+        }
+    }
+
+    /// Returns `false` if the content doesn't take depth updates
+    pub fn insert_depth(&mut self, depth: &Depth, update_t: UnixMs) -> bool {
+        match self {
+            Content::Heatmap { chart, .. } => {
+                if let Some(c) = chart {
+                    c.insert_depth(depth, update_t);
+                }
+            }
+            Content::ShaderHeatmap { chart, .. } => {
+                if let Some(c) = chart {
+                    c.insert_depth(depth, update_t);
+                }
+            }
+            Content::Ladder(panel) => {
+                if let Some(panel) = panel {
+                    panel.insert_depth(depth, update_t);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Returns `false` if the content doesn't take trades
+    pub fn insert_trades(
+        &mut self,
+        ticker_info: TickerInfo,
+        buffer: &[Trade],
+        update_t: UnixMs,
+    ) -> bool {
+        match self {
+            Content::Heatmap { chart, .. } => {
+                if let Some(c) = chart {
+                    c.insert_trades(buffer, update_t);
+                }
+            }
+            Content::ShaderHeatmap { chart, .. } => {
+                if let Some(c) = chart {
+                    c.insert_trades(buffer, update_t);
+                }
+            }
+            Content::Kline { chart, .. } => {
+                if let Some(c) = chart {
+                    c.insert_trades(buffer);
+                }
+            }
+            Content::Comparison(chart) => {
+                if let Some(c) = chart {
+                    c.insert_trades(&ticker_info, buffer);
+                }
+            }
+            Content::Spread(chart) => {
+                if let Some(c) = chart {
+                    c.insert_trades(&ticker_info, buffer);
+                }
+            }
+            Content::TimeAndSales(panel) => {
+                if let Some(p) = panel {
+                    p.insert_buffer(ticker_info, buffer);
+                }
+            }
+            Content::Ladder(panel) => {
+                if let Some(p) = panel {
+                    p.insert_trades(buffer);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn trade_sound(&self) -> Option<TradeSound> {
+        match self {
+            Content::Heatmap { chart: Some(c), .. } => Some(c.visual_config().sound),
+            Content::ShaderHeatmap { chart: Some(c), .. } => Some(c.visual_config().sound),
+            Content::TimeAndSales(Some(panel)) => Some(panel.config.sound),
+            _ => None,
+        }
+    }
+
     fn initialized(&self) -> bool {
         match self {
             Content::Heatmap { chart, .. } => chart.is_some(),
@@ -2271,6 +4182,7 @@ impl Content {
             Content::TimeAndSales(panel) => panel.is_some(),
             Content::Ladder(panel) => panel.is_some(),
             Content::Comparison(chart) => chart.is_some(),
+            Content::Spread(chart) => chart.is_some(),
             Content::Starter => true,
         }
     }
@@ -2298,6 +4210,7 @@ impl PartialEq for Content {
 fn link_group_modal<'a>(
     pane: pane_grid::Pane,
     selected_group: Option<LinkGroup>,
+    options: LinkOptions,
 ) -> Element<'a, Message> {
     let mut grid = column![].spacing(4);
     let rows = LinkGroup::ALL.chunks(3);
@@ -2330,6 +4243,37 @@ fn link_group_modal<'a>(
         grid = grid.push(button_row);
     }
 
+    if let Some(group) = selected_group {
+        let option =
+            move |label: &'static str, is_checked: bool, set: fn(&mut LinkOptions, bool)| {
+                checkbox(is_checked).label(label).on_toggle(move |checked| {
+                    let mut options = options;
+                    set(&mut options, checked);
+                    Message::LinkOptionsChanged(group, options)
+                })
+            };
+
+        grid = grid.push(
+            column![
+                rule::horizontal(1.0).style(style::split_ruler),
+                text("Panes of the group share the ticker and the crosshair's time")
+                    .size(crate::style::text_size::SMALL),
+                option(
+                    "Share crosshair price",
+                    options.crosshair_price,
+                    |options, checked| options.crosshair_price = checked,
+                ),
+                option("Sync timeframe", options.basis, |options, checked| {
+                    options.basis = checked;
+                }),
+                option("Sync zoom and scroll", options.zoom, |options, checked| {
+                    options.zoom = checked;
+                }),
+            ]
+            .spacing(8),
+        );
+    }
+
     container(grid)
         .max_width(240)
         .padding(16)
@@ -2337,6 +4281,100 @@ fn link_group_modal<'a>(
         .into()
 }
 
+fn drawings_modal<'a>(
+    pane: pane_grid::Pane,
+    selected_tool: Option<Tool>,
+    snap: Snap,
+    supported: bool,
+    count: usize,
+) -> Element<'a, Message> {
+    let content: Element<_> = if supported {
+        let tools = Tool::ALL.iter().fold(column![].spacing(4), |col, &tool| {
+            let is_selected = selected_tool == Some(tool);
+            let message = super::chart::Message::DrawingToolChanged((!is_selected).then_some(tool));
+
+            col.push(
+                button(text(tool.to_string()))
+                    .width(Length::Fill)
+                    .on_press(Message::PaneEvent(pane, Event::ChartInteraction(message)))
+                    .style(move |theme, status| {
+                        style::button::menu_body(theme, status, is_selected)
+                    }),
+            )
+        });
+
+        let snaps = Snap::ALL.iter().fold(row![].spacing(4), |row, &option| {
+            let is_selected = snap == option;
+
+            row.push(
+                button(text(option.to_string()))
+                    .on_press(Message::PaneEvent(pane, Event::DrawingSnapChanged(option)))
+                    .style(move |theme, status| {
+                        style::button::menu_body(theme, status, is_selected)
+                    }),
+            )
+        });
+
+        column![
+            text("Drawings").size(crate::style::text_size::SECTION),
+            tools,
+            column![text("Snap to").size(crate::style::text_size::SMALL), snaps].spacing(4),
+            text("Click a drawing to select it, Delete removes it. Click a note to edit it")
+                .size(crate::style::text_size::SMALL),
+            button(text(format!("Clear all ({count})"))).on_press_maybe(
+                (count > 0).then_some(Message::PaneEvent(pane, Event::ClearDrawings))
+            ),
+        ]
+        .spacing(8)
+        .into()
+    } else {
+        text("Drawings are placed on time based charts only")
+            .size(crate::style::text_size::SMALL)
+            .into()
+    };
+
+    container(content)
+        .max_width(200)
+        .padding(16)
+        .style(style::chart_modal)
+        .into()
+}
+
+fn note_editor<'a>(pane: pane_grid::Pane, index: usize, note: &Note) -> Element<'a, Message> {
+    let on_edit = move |edit| Message::PaneEvent(pane, Event::NoteEdited(index, edit));
+
+    let markers = Marker::ALL.iter().fold(row![].spacing(4), |row, &marker| {
+        let is_selected = note.marker == marker;
+
+        row.push(
+            button(text(marker.to_string()))
+                .on_press(on_edit(NoteEdit::Marker(marker)))
+                .style(move |theme, status| style::button::menu_body(theme, status, is_selected)),
+        )
+    });
+
+    let content = column![
+        text("Note").size(crate::style::text_size::SECTION),
+        iced::widget::text_input("Text beside the marker", &note.text)
+            .id(NOTE_INPUT_ID)
+            .on_input(move |text| on_edit(NoteEdit::Text(text)))
+            .on_submit(Message::PaneEvent(pane, Event::HideModal)),
+        markers,
+        row![
+            button(text("Remove")).on_press(on_edit(NoteEdit::Remove)),
+            space::horizontal(),
+            button(text("Done")).on_press(Message::PaneEvent(pane, Event::HideModal)),
+        ],
+    ]
+    .spacing(8);
+
+    container(content)
+        .max_width(280)
+        .padding(16)
+        .style(style::chart_modal)
+        .into()
+}
+
 fn ticksize_modifier<'a>(
     id: pane_grid::Pane,
     price_step: PriceStep,
@@ -2388,14 +4426,80 @@ fn basis_modifier<'a>(
         .into()
 }
 
+/// Play/pause, speed and seek controls shown in the title bar while replaying
+fn info_label(info: InfoKind) -> &'static str {
+    match info {
+        InfoKind::FetchingKlines => "Fetching Klines",
+        InfoKind::FetchingTrades(_) => "Fetching Trades",
+        InfoKind::FetchingOI => "Fetching Open Interest",
+        InfoKind::FetchingFunding => "Fetching Funding Rate",
+    }
+}
+
+fn replay_transport<'a>(id: pane_grid::Pane, replay: &Replay) -> Element<'a, Message> {
+    let on_control = move |control| Message::PaneEvent(id, Event::Replay(control));
+    let btn_style =
+        |theme: &Theme, status: button::Status| style::button::modifier(theme, status, true);
+
+    let fmt_duration = |ms: u64| {
+        let secs = ms / 1000;
+        format!("{}:{:02}", secs / 60, secs % 60)
+    };
+    let (played, total) = replay.elapsed();
+
+    row![
+        text("Replay").size(crate::style::text_size::SMALL),
+        button(text(if replay.is_playing() { "Pause" } else { "Play" }))
+            .style(btn_style)
+            .on_press(on_control(ReplayControl::TogglePlay))
+            .height(widget::PANE_CONTROL_BTN_HEIGHT),
+        button(text(replay.speed().to_string()))
+            .style(btn_style)
+            .on_press(on_control(ReplayControl::CycleSpeed))
+            .height(widget::PANE_CONTROL_BTN_HEIGHT),
+        slider(0.0..=1.0, replay.progress(), move |fraction| {
+            on_control(ReplayControl::Scrub(fraction))
+        })
+        .step(0.001)
+        .on_release(on_control(ReplayControl::Seek))
+        .width(120),
+        text(format!(
+            "{} / {}",
+            fmt_duration(played),
+            fmt_duration(total)
+        ))
+        .size(crate::style::text_size::SMALL),
+        button_with_tooltip(
+            icon_text(Icon::Close, 10),
+            on_control(ReplayControl::Close),
+            Some("Back to live"),
+            tooltip::Position::Bottom,
+            move |theme: &Theme, status: button::Status| {
+                style::button::transparent(theme, status, false)
+            },
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// `on_time` gets the timeframe to stream klines in, those the venue has no klines for take
+/// the trades only, like tick bases do
 fn by_basis_default<T>(
     basis: Option<Basis>,
     default_tf: Timeframe,
+    ticker_info: TickerInfo,
     on_time: impl FnOnce(Timeframe) -> T,
     on_tick: impl FnOnce() -> T,
 ) -> T {
-    match basis.unwrap_or(Basis::Time(default_tf)) {
-        Basis::Time(tf) => on_time(tf),
-        Basis::Tick(_) => on_tick(),
+    let kline_timeframe = match basis.unwrap_or(Basis::Time(default_tf)) {
+        Basis::Time(tf) => ticker_info.exchange().kline_fetch_timeframe(tf),
+        Basis::Tick(_) | Basis::Bars(_) => None,
+    };
+
+    match kline_timeframe {
+        Some(tf) => on_time(tf),
+        None => on_tick(),
     }
 }
@@ -1,6 +1,8 @@
 pub mod ladder;
 pub mod timeandsales;
 
+use exchange::adapter::trading::OrderSide;
+use exchange::unit::Price;
 use iced::{
     Element, padding,
     widget::{canvas, center, container, text},
@@ -12,6 +14,12 @@ pub enum Message {
     Scrolled(f32),
     ResetScroll,
     Invalidate(Option<Instant>),
+    /// Cursor entered or left the panel
+    Hovered(bool),
+    Pause,
+    /// Order entry clicks of the ladder, resolved by its pane
+    PlaceOrder(OrderSide, Price),
+    CancelOrdersAt(Price),
 }
 
 pub enum Action {}
@@ -24,6 +32,11 @@ pub trait Panel: canvas::Program<Message> {
     fn invalidate(&mut self, now: Option<Instant>) -> Option<Action>;
 
     fn is_empty(&self) -> bool;
+
+    /// Panels that don't pause ignore it
+    fn set_hovered(&mut self, _hovered: bool) {}
+
+    fn pause(&mut self) {}
 }
 
 pub fn view<T: Panel>(panel: &'_ T, _timezone: data::UserTimezone) -> Element<'_, Message> {
@@ -51,5 +64,12 @@ pub fn update<T: Panel>(panel: &mut T, message: Message) {
         Message::Invalidate(now) => {
             panel.invalidate(now);
         }
+        Message::Hovered(hovered) => {
+            panel.set_hovered(hovered);
+        }
+        Message::Pause => {
+            panel.pause();
+        }
+        Message::PlaceOrder(..) | Message::CancelOrdersAt(_) => {}
     }
 }
@@ -1,7 +1,11 @@
 use super::Message;
 use crate::style;
-use data::panel::ladder::{ChaseTracker, Config, GroupedDepth, Side, TradeStore};
+use data::panel::ladder::{
+    ChaseTracker, Config, GroupedDepth, RecenterMode, RefillTracker, SessionVolume, Side,
+    SizeDisplay, SpreadStats, TradeStore,
+};
 use exchange::Trade;
+use exchange::adapter::trading::{OrderSide, WorkingOrder};
 use exchange::unit::qty::Qty;
 use exchange::unit::{Price, PriceStep};
 use exchange::{TickerInfo, UnixMs, depth::Depth};
@@ -20,6 +24,16 @@ const ROW_HEIGHT: f32 = 16.0;
 const ORDER_QTY_COLS_WIDTH: f32 = 0.60;
 /// Uses half of the width for each side of the trade quantity columns
 const TRADE_QTY_COLS_WIDTH: f32 = 0.20;
+/// Share of the order size columns given to the cumulative size next to them
+const CUMULATIVE_COL_SHARE: f32 = 0.45;
+const DEPTH_BAR_ALPHA: f32 = 0.08;
+/// Width of the session volume column, as a share of the ladder
+const SESSION_VOLUME_COL_WIDTH: f32 = 0.16;
+const REFILL_MARKER_RADIUS: f32 = 3.5;
+const RECENTER_BUTTON_SIZE: Size = Size::new(72.0, 18.0);
+const RECENTER_BUTTON_BOTTOM_PAD: f32 = 8.0;
+const HEADER_HEIGHT: f32 = 22.0;
+const IMBALANCE_GAUGE_HEIGHT: f32 = 3.0;
 
 const COL_PADDING: f32 = 4.0;
 /// Used for calculating layout with texts inside the price column
@@ -34,11 +48,13 @@ const CHASE_MIN_INTERVAL: Duration = Duration::from_millis(200);
 impl super::Panel for Ladder {
     fn scroll(&mut self, delta: f32) {
         self.scroll_px += delta;
+        self.last_scroll = Some(Instant::now());
         Ladder::invalidate(self, Some(Instant::now()));
     }
 
     fn reset_scroll(&mut self) {
         self.scroll_px = 0.0;
+        self.last_scroll = None;
         Ladder::invalidate(self, Some(Instant::now()));
     }
 
@@ -61,38 +77,68 @@ pub struct Ladder {
     last_tick: Instant,
     pub step: PriceStep,
     scroll_px: f32,
+    /// When the user last scrolled, for the delayed recenter
+    last_scroll: Option<Instant>,
+    /// Best bid the rows were laid out from on the last update, to keep them on their
+    /// prices while scrolled
+    layout_best_bid: Option<Price>,
     last_exchange_ts_ms: Option<UnixMs>,
     orderbook: [GroupedDepth; 2],
     trades: TradeStore,
+    session_volume: SessionVolume,
+    refills: RefillTracker,
     pending_tick_size: Option<PriceStep>,
     raw_price_spread: Option<Price>,
+    /// Midpoint of the raw best bid and ask, for the spread in basis points
+    raw_mid_price: Option<f64>,
+    spread_stats: SpreadStats,
+    scale_factor: f32,
+    /// Own resting orders on this ticker, drawn over the order columns
+    working_orders: Vec<WorkingOrder>,
 }
 
 impl Ladder {
     pub fn new(config: Option<Config>, ticker_info: TickerInfo, step: PriceStep) -> Self {
         Self {
             trades: TradeStore::new(),
+            session_volume: SessionVolume::default(),
+            refills: RefillTracker::default(),
             config: config.unwrap_or_default(),
             ticker_info,
             cache: canvas::Cache::default(),
             last_tick: Instant::now(),
             step,
             scroll_px: 0.0,
+            last_scroll: None,
+            layout_best_bid: None,
             last_exchange_ts_ms: None,
             orderbook: [GroupedDepth::new(), GroupedDepth::new()],
             raw_price_spread: None,
+            raw_mid_price: None,
+            spread_stats: SpreadStats::default(),
             pending_tick_size: None,
+            scale_factor: 1.0,
+            working_orders: vec![],
         }
     }
 
     pub fn insert_trades(&mut self, buffer: &[Trade]) {
         self.trades.insert_trades(buffer, self.step);
+        self.session_volume.insert_trades(buffer, self.step);
+
+        if self.config.refill_ratio.is_some() {
+            self.refills.insert_trades(buffer, self.step);
+        }
     }
 
     pub fn insert_depth(&mut self, depth: &Depth, update_t: UnixMs) {
         if let Some(next) = self.pending_tick_size.take() {
             self.step = next;
             self.trades.rebuild_grouped(self.step);
+            self.session_volume.regroup(self.step);
+            self.refills.clear();
+            self.scroll_px = 0.0;
+            self.layout_best_bid = None;
         }
 
         let raw_best_bid = depth.bids.last_key_value().map(|(p, _)| *p);
@@ -101,6 +147,19 @@ impl Ladder {
             (Some(bid), Some(ask)) => Some(ask - bid),
             _ => None,
         };
+        self.raw_mid_price = match (raw_best_bid, raw_best_ask) {
+            (Some(bid), Some(ask)) => Some((bid.to_f64() + ask.to_f64()) / 2.0),
+            _ => None,
+        };
+
+        match (self.config.header_levels, self.raw_price_spread) {
+            (Some(_), Some(spread)) => {
+                let ticks = self.spread_in_ticks(spread);
+                self.spread_stats.push(update_t, ticks);
+            }
+            (Some(_), None) => {}
+            (None, _) => self.spread_stats.clear(),
+        }
 
         if self.config.show_chase_tracker {
             let max_int = CHASE_MIN_INTERVAL;
@@ -121,9 +180,60 @@ impl Ladder {
         }
 
         self.regroup_from_depth(depth);
+        self.follow_recenter_mode();
+
+        if self.config.refill_ratio.is_some() {
+            let orderbook = &self.orderbook;
+            self.refills.update(
+                &orderbook[Side::Bid.idx()].orders,
+                &orderbook[Side::Ask.idx()].orders,
+            );
+        } else {
+            self.refills.clear();
+        }
         self.last_exchange_ts_ms = Some(update_t);
     }
 
+    /// Shifts the scroll with the spread so the rows stay on their prices, and goes back to
+    /// the spread once the delay since the last scroll is up
+    fn follow_recenter_mode(&mut self) {
+        let best_bid = self.build_price_grid().map(|grid| grid.best_bid);
+
+        if self.config.recenter.pins_prices()
+            && self.scroll_px != 0.0
+            && let (Some(prev), Some(best_bid)) = (self.layout_best_bid, best_bid)
+        {
+            let steps = (best_bid.units - prev.units) / self.step.units.max(1);
+            self.scroll_px += steps as f32 * ROW_HEIGHT;
+        }
+        self.layout_best_bid = best_bid;
+
+        if let RecenterMode::Delayed { secs } = self.config.recenter
+            && self
+                .last_scroll
+                .is_some_and(|at| at.elapsed() >= Duration::from_secs(u64::from(secs)))
+        {
+            self.scroll_px = 0.0;
+            self.last_scroll = None;
+        }
+    }
+
+    /// Where the recenter button goes, shown while scrolled off the spread with the rows
+    /// staying on their prices
+    fn recenter_button(&self, bounds: Rectangle) -> Option<Rectangle> {
+        if !self.config.recenter.pins_prices() || self.scroll_px == 0.0 {
+            return None;
+        }
+
+        Some(Rectangle::new(
+            Point::new(
+                (bounds.width - RECENTER_BUTTON_SIZE.width) / 2.0,
+                bounds.height - RECENTER_BUTTON_SIZE.height - RECENTER_BUTTON_BOTTOM_PAD,
+            ),
+            RECENTER_BUTTON_SIZE,
+        ))
+    }
+
     fn trade_qty_at(&self, price: Price) -> (Qty, Qty) {
         self.trades.trade_qty_at(price)
     }
@@ -161,6 +271,13 @@ impl Ladder {
         self.invalidate(Some(Instant::now()));
     }
 
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if (self.scale_factor - scale_factor).abs() >= f32::EPSILON {
+            self.scale_factor = scale_factor;
+            self.invalidate(None);
+        }
+    }
+
     pub fn set_show_chase_tracker(&mut self, enabled: bool) {
         if self.config.show_chase_tracker != enabled {
             self.config.show_chase_tracker = enabled;
@@ -173,6 +290,28 @@ impl Ladder {
         }
     }
 
+    pub fn set_working_orders(&mut self, orders: &[WorkingOrder]) {
+        if self.working_orders != orders {
+            self.working_orders = orders.to_vec();
+            self.invalidate(None);
+        }
+    }
+
+    /// Ids of the working orders grouped into the row of `price`
+    pub fn order_ids_at(&self, price: Price) -> Vec<String> {
+        self.working_orders
+            .iter()
+            .filter(|order| self.order_row_price(order) == price)
+            .map(|order| order.id.clone())
+            .collect()
+    }
+
+    fn order_row_price(&self, order: &WorkingOrder) -> Price {
+        order
+            .price
+            .round_to_side_step(order.side == OrderSide::Buy, self.step)
+    }
+
     fn regroup_from_depth(&mut self, depth: &Depth) {
         let step = self.step;
 
@@ -196,6 +335,46 @@ impl Ladder {
     fn format_quantity(&self, qty: Qty) -> String {
         data::util::abbr_large_numbers(qty.to_f64())
     }
+
+    fn format_order_quantity(&self, qty: Qty, total: Option<f32>) -> String {
+        match total {
+            Some(total) if total > 0.0 => format!("{:.1}%", qty.to_f32_lossy() / total * 100.0),
+            Some(_) => String::new(),
+            None => self.format_quantity(qty),
+        }
+    }
+
+    fn spread_in_ticks(&self, spread: Price) -> f64 {
+        (spread.to_f64() / f64::from(self.min_tick_size())).round()
+    }
+
+    /// Bid minus ask size over the sum of both, resting on the `levels` closest to the touch
+    fn imbalance(&self, levels: usize) -> Option<f32> {
+        let bids = self.grouped_bids().values().rev().take(levels);
+        let asks = self.grouped_asks().values().take(levels);
+
+        let bids: f32 = bids.map(|qty| qty.to_f32_lossy()).sum();
+        let asks: f32 = asks.map(|qty| qty.to_f32_lossy()).sum();
+
+        let total = bids + asks;
+        (total > 0.0).then(|| (bids - asks) / total)
+    }
+
+    fn header_height(&self) -> f32 {
+        if self.config.header_levels.is_some() {
+            HEADER_HEIGHT
+        } else {
+            0.0
+        }
+    }
+
+    /// Resting size of the `levels` closest to the touch, summed over both sides
+    fn top_levels_qty(&self, levels: usize) -> f32 {
+        let asks = self.grouped_asks().values().take(levels);
+        let bids = self.grouped_bids().values().rev().take(levels);
+
+        asks.chain(bids).map(|qty| qty.to_f32_lossy()).sum()
+    }
 }
 
 impl canvas::Program<Message> for Ladder {
@@ -208,12 +387,35 @@ impl canvas::Program<Message> for Ladder {
         bounds: iced::Rectangle,
         cursor: iced_core::mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
-        let _cursor_position = cursor.position_in(bounds)?;
+        let cursor_position = cursor.position_in(bounds)?;
+
+        // Locked rows only go back to the spread from the button
+        let click_recenters = self.config.recenter != RecenterMode::Locked;
 
         match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                if self
+                    .recenter_button(bounds)
+                    .is_some_and(|button| button.contains(cursor_position)) =>
+            {
+                Some(canvas::Action::publish(Message::ResetScroll).and_capture())
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+                if cursor_position.y < self.header_height() =>
+            {
+                None
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(
+                button @ (mouse::Button::Left | mouse::Button::Right),
+            )) if self.config.order_entry => self
+                .order_entry_click(*button, cursor_position, bounds)
+                .or(click_recenters.then_some(Message::ResetScroll))
+                .map(|message| canvas::Action::publish(message).and_capture()),
             Event::Mouse(mouse::Event::ButtonPressed(
                 mouse::Button::Middle | mouse::Button::Left | mouse::Button::Right,
-            )) => Some(canvas::Action::publish(Message::ResetScroll).and_capture()),
+            )) if click_recenters => {
+                Some(canvas::Action::publish(Message::ResetScroll).and_capture())
+            }
             Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
                 let scroll_amount = match delta {
                     mouse::ScrollDelta::Lines { y, .. } => -(*y) * ROW_HEIGHT,
@@ -254,6 +456,31 @@ impl canvas::Program<Message> for Ladder {
                 let mut best_ask_y: Option<f32> = None;
 
                 for visible_row in visible_rows.iter() {
+                    if let DomRow::Ask { .. } | DomRow::Bid { .. } = visible_row.row {
+                        let is_bid = matches!(visible_row.row, DomRow::Bid { .. });
+                        self.draw_cumulative(
+                            frame,
+                            visible_row.y,
+                            visible_row.cum_qty,
+                            maxima.vis_max_cum_qty,
+                            is_bid,
+                            if is_bid { bid_color } else { ask_color },
+                            text_color,
+                            &cols,
+                            bounds.width,
+                        );
+                        self.draw_session_volume(
+                            frame,
+                            visible_row.y,
+                            visible_row.session_qty,
+                            maxima.vis_max_session_qty,
+                            bid_color,
+                            ask_color,
+                            text_color,
+                            &cols,
+                        );
+                    }
+
                     match visible_row.row {
                         DomRow::Ask { price, .. }
                             if Some(price)
@@ -281,6 +508,7 @@ impl canvas::Program<Message> for Ladder {
                                 ask_color,
                                 text_color,
                                 maxima.vis_max_order_qty,
+                                maxima.order_qty_total,
                                 visible_row.buy_t,
                                 visible_row.sell_t,
                                 maxima.vis_max_trade_qty,
@@ -299,6 +527,7 @@ impl canvas::Program<Message> for Ladder {
                                 bid_color,
                                 text_color,
                                 maxima.vis_max_order_qty,
+                                maxima.order_qty_total,
                                 visible_row.buy_t,
                                 visible_row.sell_t,
                                 maxima.vis_max_trade_qty,
@@ -341,6 +570,16 @@ impl canvas::Program<Message> for Ladder {
                     }
                 }
 
+                for visible_row in visible_rows.iter().filter(|row| row.refilling) {
+                    Self::draw_refill_marker(
+                        frame,
+                        visible_row.y,
+                        matches!(visible_row.row, DomRow::Bid { .. }),
+                        &cols,
+                        palette.warning.base.color,
+                    );
+                }
+
                 if self.config.show_chase_tracker {
                     let left_gap_mid_x = cols.sell.1 + (layout.inside_pad_px + COL_PADDING) * 0.5;
                     let right_gap_mid_x = cols.buy.0 - (layout.inside_pad_px + COL_PADDING) * 0.5;
@@ -367,6 +606,28 @@ impl canvas::Program<Message> for Ladder {
                     );
                 }
 
+                if !self.working_orders.is_empty() {
+                    self.draw_working_orders(frame, &grid, bounds, &cols, palette);
+                }
+
+                if let Some(button) = self.recenter_button(bounds) {
+                    frame.fill_rectangle(
+                        button.position(),
+                        button.size(),
+                        palette.background.weak.color,
+                    );
+                    frame.fill_text(Text {
+                        content: "Recenter".to_string(),
+                        position: button.center(),
+                        color: palette.background.weak.text,
+                        size: style::text_size::TINY.into(),
+                        font: style::AZERET_MONO,
+                        align_x: Alignment::Center.into(),
+                        align_y: Alignment::Center.into(),
+                        ..Default::default()
+                    });
+                }
+
                 // Price column vertical dividers with a gap over the spread row (if visible)
                 let mut draw_vsplit = |x: f32, gap: Option<(f32, f32)>| {
                     let x = x.floor() + 0.5;
@@ -426,6 +687,10 @@ impl canvas::Program<Message> for Ladder {
                     );
                 }
             }
+
+            if let Some(levels) = self.config.header_levels {
+                self.draw_header(frame, bounds.width, levels, palette, divider_color);
+            }
         });
 
         vec![orderbook_visual]
@@ -436,6 +701,11 @@ impl canvas::Program<Message> for Ladder {
 struct Maxima {
     vis_max_order_qty: f32,
     vis_max_trade_qty: f32,
+    /// Deepest cumulative size among the visible rows, on either side
+    vis_max_cum_qty: f32,
+    vis_max_session_qty: f32,
+    /// Denominator for percent-of-book labels, `None` when sizes are shown as-is
+    order_qty_total: Option<f32>,
 }
 
 struct VisibleRow {
@@ -443,14 +713,23 @@ struct VisibleRow {
     y: f32,
     buy_t: Qty,
     sell_t: Qty,
+    /// Size resting from the touch up to and including the row
+    cum_qty: Qty,
+    /// Buy and sell volume traded at the row this session
+    session_qty: (Qty, Qty),
+    /// Keeps showing size after being traded into, see [`RefillTracker`]
+    refilling: bool,
 }
 
 struct ColumnRanges {
+    bid_cum: Option<(f32, f32)>,
     bid_order: (f32, f32),
     sell: (f32, f32),
     price: (f32, f32),
     buy: (f32, f32),
     ask_order: (f32, f32),
+    ask_cum: Option<(f32, f32)>,
+    session_volume: Option<(f32, f32)>,
 }
 
 struct PriceLayout {
@@ -459,8 +738,9 @@ struct PriceLayout {
 }
 
 impl Ladder {
-    // [BidOrderQty][SellQty][ Price ][BuyQty][AskOrderQty]
+    // [BidCumQty][BidOrderQty][SellQty][ Price ][BuyQty][AskOrderQty][AskCumQty]
     const NUMBER_OF_COLUMN_GAPS: f32 = 4.0;
+    const NUMBER_OF_CUMULATIVE_GAPS: f32 = 2.0;
 
     fn price_sample_text(&self, grid: &PriceGrid) -> String {
         let a = self.format_price(grid.best_ask);
@@ -490,8 +770,21 @@ impl Ladder {
     }
 
     fn column_ranges(&self, width: f32, price_px: f32) -> ColumnRanges {
-        let total_gutter_width = COL_PADDING * Self::NUMBER_OF_COLUMN_GAPS;
-        let usable_width = (width - total_gutter_width).max(0.0);
+        let show_cumulative = self.config.show_cumulative;
+        let gaps = if show_cumulative {
+            Self::NUMBER_OF_COLUMN_GAPS + Self::NUMBER_OF_CUMULATIVE_GAPS
+        } else {
+            Self::NUMBER_OF_COLUMN_GAPS
+        };
+        let total_gutter_width = COL_PADDING * gaps;
+        let mut usable_width = (width - total_gutter_width).max(0.0);
+
+        // Set apart on the far right, the other columns share what's left
+        let session_volume_range = self.config.show_session_volume.then(|| {
+            let session_width = usable_width * SESSION_VOLUME_COL_WIDTH;
+            usable_width = (usable_width - session_width - COL_PADDING).max(0.0);
+            (width - session_width, width)
+        });
 
         let price_width = price_px.min(usable_width);
 
@@ -509,13 +802,23 @@ impl Ladder {
             0.0
         };
 
-        let bid_order_width = order_share * 0.5;
+        let cum_width = if show_cumulative {
+            order_share * 0.5 * CUMULATIVE_COL_SHARE
+        } else {
+            0.0
+        };
+        let bid_order_width = order_share * 0.5 - cum_width;
         let sell_trades_width = trade_share * 0.5;
         let buy_trades_width = trade_share * 0.5;
-        let ask_order_width = order_share * 0.5;
+        let ask_order_width = order_share * 0.5 - cum_width;
 
         let mut cursor_x = 0.0;
 
+        let bid_cum_range = show_cumulative.then_some((cursor_x, cursor_x + cum_width));
+        if show_cumulative {
+            cursor_x += cum_width + COL_PADDING;
+        }
+
         let bid_order_end = cursor_x + bid_order_width;
         let bid_order_range = (cursor_x, bid_order_end);
         cursor_x = bid_order_end + COL_PADDING;
@@ -534,16 +837,109 @@ impl Ladder {
 
         let ask_order_end = cursor_x + ask_order_width;
         let ask_order_range = (cursor_x, ask_order_end);
+        cursor_x = ask_order_end + COL_PADDING;
+
+        let ask_cum_range = show_cumulative.then_some((cursor_x, cursor_x + cum_width));
 
         ColumnRanges {
+            bid_cum: bid_cum_range,
             bid_order: bid_order_range,
             sell: sell_trades_range,
             price: price_range,
             buy: buy_trades_range,
             ask_order: ask_order_range,
+            ask_cum: ask_cum_range,
+            session_volume: session_volume_range,
         }
     }
 
+    /// Imbalance gauge and spread stats over the top rows
+    fn draw_header(
+        &self,
+        frame: &mut canvas::Frame,
+        width: f32,
+        levels: usize,
+        palette: &iced::theme::palette::Extended,
+        divider_color: iced::Color,
+    ) {
+        let bid_color = palette.success.base.color;
+        let ask_color = palette.danger.base.color;
+
+        frame.fill_rectangle(
+            Point::ORIGIN,
+            Size::new(width, HEADER_HEIGHT),
+            palette.background.base.color,
+        );
+        frame.fill_rectangle(
+            Point::new(0.0, HEADER_HEIGHT - 1.0),
+            Size::new(width, 1.0),
+            divider_color,
+        );
+
+        let text_y = (HEADER_HEIGHT - IMBALANCE_GAUGE_HEIGHT - 1.0) / 2.0;
+        let imbalance = self.imbalance(levels);
+
+        let (imbalance_text, imbalance_color) = match imbalance {
+            Some(value) => (
+                format!("Imb {:+.0}% ({levels})", value * 100.0),
+                if value >= 0.0 { bid_color } else { ask_color },
+            ),
+            None => ("Imb -".to_string(), palette.background.base.text),
+        };
+        frame.fill_text(Text {
+            content: imbalance_text,
+            position: Point::new(COL_PADDING, text_y),
+            color: imbalance_color,
+            size: style::text_size::TINY.into(),
+            font: style::AZERET_MONO,
+            align_y: Alignment::Center.into(),
+            ..Default::default()
+        });
+
+        let mut spread_text = match (self.raw_price_spread, self.raw_mid_price) {
+            (Some(spread), Some(mid)) if mid > 0.0 => format!(
+                "Spread {}t {:.1}bps",
+                self.spread_in_ticks(spread),
+                spread.to_f64() / mid * 10_000.0,
+            ),
+            _ => "Spread -".to_string(),
+        };
+        if let Some(average) = self.spread_stats.average() {
+            spread_text.push_str(&format!(" · 1m avg {average:.1}t"));
+        }
+        frame.fill_text(Text {
+            content: spread_text,
+            position: Point::new(width - COL_PADDING, text_y),
+            color: palette.secondary.strong.color,
+            size: style::text_size::TINY.into(),
+            font: style::AZERET_MONO,
+            align_x: Alignment::End.into(),
+            align_y: Alignment::Center.into(),
+            ..Default::default()
+        });
+
+        // Bid share from the left, ask share from the right, split at the middle when even
+        let gauge_y = HEADER_HEIGHT - 1.0 - IMBALANCE_GAUGE_HEIGHT;
+        let bid_share = imbalance.map_or(0.5, |value| (value + 1.0) / 2.0);
+        let split_x = width * bid_share;
+
+        frame.fill_rectangle(
+            Point::new(0.0, gauge_y),
+            Size::new(split_x, IMBALANCE_GAUGE_HEIGHT),
+            bid_color.scale_alpha(0.6),
+        );
+        frame.fill_rectangle(
+            Point::new(split_x, gauge_y),
+            Size::new(width - split_x, IMBALANCE_GAUGE_HEIGHT),
+            ask_color.scale_alpha(0.6),
+        );
+        frame.fill_rectangle(
+            Point::new((width / 2.0).floor(), gauge_y),
+            Size::new(1.0, IMBALANCE_GAUGE_HEIGHT),
+            palette.background.base.text,
+        );
+    }
+
     fn draw_row(
         &self,
         frame: &mut iced::widget::canvas::Frame,
@@ -554,6 +950,7 @@ impl Ladder {
         side_color: iced::Color,
         text_color: iced::Color,
         max_order_qty: f32,
+        order_qty_total: Option<f32>,
         trade_buy_qty: Qty,
         trade_sell_qty: Qty,
         max_trade_qty: f32,
@@ -577,9 +974,9 @@ impl Ladder {
                 true,
                 0.20,
             );
-            let qty_txt = self.format_quantity(order_qty);
+            let qty_txt = self.format_order_quantity(order_qty, order_qty_total);
             let x_text = cols.bid_order.0 + 6.0;
-            Self::draw_cell_text(frame, &qty_txt, x_text, y, text_color, Alignment::Start);
+            self.draw_cell_text(frame, &qty_txt, x_text, y, text_color, Alignment::Start);
         } else {
             Self::fill_bar(
                 frame,
@@ -592,9 +989,9 @@ impl Ladder {
                 false,
                 0.20,
             );
-            let qty_txt = self.format_quantity(order_qty);
+            let qty_txt = self.format_order_quantity(order_qty, order_qty_total);
             let x_text = cols.ask_order.1 - 6.0;
-            Self::draw_cell_text(frame, &qty_txt, x_text, y, text_color, Alignment::End);
+            self.draw_cell_text(frame, &qty_txt, x_text, y, text_color, Alignment::End);
         }
 
         // Sell trades (right-to-left)
@@ -614,7 +1011,7 @@ impl Ladder {
         } else {
             "".into()
         };
-        Self::draw_cell_text(
+        self.draw_cell_text(
             frame,
             &sell_txt,
             cols.sell.1 - 6.0,
@@ -640,7 +1037,7 @@ impl Ladder {
         } else {
             "".into()
         };
-        Self::draw_cell_text(
+        self.draw_cell_text(
             frame,
             &buy_txt,
             cols.buy.0 + 6.0,
@@ -652,7 +1049,7 @@ impl Ladder {
         // Price
         let price_text = self.format_price(price);
         let price_x_center = (cols.price.0 + cols.price.1) * 0.5;
-        Self::draw_cell_text(
+        self.draw_cell_text(
             frame,
             &price_text,
             price_x_center,
@@ -662,6 +1059,136 @@ impl Ladder {
         );
     }
 
+    /// Cumulative size of the row in its column, and the depth bar behind the row
+    fn draw_cumulative(
+        &self,
+        frame: &mut iced::widget::canvas::Frame,
+        y: f32,
+        cum_qty: Qty,
+        max_cum_qty: f32,
+        is_bid: bool,
+        side_color: iced::Color,
+        text_color: iced::Color,
+        cols: &ColumnRanges,
+        width: f32,
+    ) {
+        if self.config.show_depth_bars {
+            Self::fill_bar(
+                frame,
+                (0.0, width),
+                y,
+                ROW_HEIGHT,
+                cum_qty.to_f32_lossy(),
+                max_cum_qty,
+                side_color,
+                is_bid,
+                DEPTH_BAR_ALPHA,
+            );
+        }
+
+        let column = if is_bid { cols.bid_cum } else { cols.ask_cum };
+        if let Some((x_start, x_end)) = column
+            && !cum_qty.is_zero()
+        {
+            let (x_text, align) = if is_bid {
+                (x_start + 6.0, Alignment::Start)
+            } else {
+                (x_end - 6.0, Alignment::End)
+            };
+            self.draw_cell_text(
+                frame,
+                &self.format_quantity(cum_qty),
+                x_text,
+                y,
+                text_color.scale_alpha(0.7),
+                align,
+            );
+        }
+    }
+
+    /// Session volume of the row, the buys and sells side by side in one bar
+    fn draw_session_volume(
+        &self,
+        frame: &mut iced::widget::canvas::Frame,
+        y: f32,
+        (buy_qty, sell_qty): (Qty, Qty),
+        max_qty: f32,
+        buy_color: iced::Color,
+        sell_color: iced::Color,
+        text_color: iced::Color,
+        cols: &ColumnRanges,
+    ) {
+        let Some((x_start, x_end)) = cols.session_volume else {
+            return;
+        };
+        let total = buy_qty.to_f32_lossy() + sell_qty.to_f32_lossy();
+        if max_qty <= 0.0 || total <= 0.0 {
+            return;
+        }
+
+        let bar_width = (total / max_qty).min(1.0) * (x_end - x_start);
+        let buy_width = bar_width * buy_qty.to_f32_lossy() / total;
+
+        frame.fill_rectangle(
+            Point::new(x_start, y),
+            Size::new(buy_width, ROW_HEIGHT),
+            buy_color.scale_alpha(0.30),
+        );
+        frame.fill_rectangle(
+            Point::new(x_start + buy_width, y),
+            Size::new(bar_width - buy_width, ROW_HEIGHT),
+            sell_color.scale_alpha(0.30),
+        );
+
+        self.draw_cell_text(
+            frame,
+            &self.format_quantity(buy_qty + sell_qty),
+            x_end - 6.0,
+            y,
+            text_color,
+            Alignment::End,
+        );
+    }
+
+    /// Diamond and outline on the order size cell of a refilling level
+    fn draw_refill_marker(
+        frame: &mut iced::widget::canvas::Frame,
+        y: f32,
+        is_bid: bool,
+        cols: &ColumnRanges,
+        color: iced::Color,
+    ) {
+        let (x_start, x_end) = if is_bid {
+            cols.bid_order
+        } else {
+            cols.ask_order
+        };
+
+        frame.stroke(
+            &Path::rectangle(
+                Point::new(x_start + 0.5, y + 0.5),
+                Size::new((x_end - x_start - 1.0).max(0.0), ROW_HEIGHT - 1.0),
+            ),
+            Stroke::with_color(Stroke::default(), color.scale_alpha(0.6)),
+        );
+
+        let r = REFILL_MARKER_RADIUS;
+        let cx = if is_bid {
+            x_end - r - 4.0
+        } else {
+            x_start + r + 4.0
+        };
+        let cy = y + ROW_HEIGHT / 2.0;
+        let diamond = Path::new(|p| {
+            p.move_to(Point::new(cx, cy - r));
+            p.line_to(Point::new(cx + r, cy));
+            p.line_to(Point::new(cx, cy + r));
+            p.line_to(Point::new(cx - r, cy));
+            p.close();
+        });
+        frame.fill(&diamond, color);
+    }
+
     fn fill_bar(
         frame: &mut iced::widget::canvas::Frame,
         (x_start, x_end): (f32, f32),
@@ -694,6 +1221,7 @@ impl Ladder {
     }
 
     fn draw_cell_text(
+        &self,
         frame: &mut iced::widget::canvas::Frame,
         text: &str,
         x_anchor: f32,
@@ -705,7 +1233,7 @@ impl Ladder {
             content: text.to_string(),
             position: Point::new(x_anchor, y + ROW_HEIGHT / 2.0),
             color,
-            size: TEXT_SIZE.into(),
+            size: style::dpi::text_size(TEXT_SIZE, self.scale_factor).into(),
             font: style::AZERET_MONO,
             align_x: align.into(),
             align_y: Alignment::Center.into(),
@@ -758,6 +1286,99 @@ impl Ladder {
         }
     }
 
+    /// A working order of our own as a tag at the inner edge of its side's order column
+    fn draw_working_orders(
+        &self,
+        frame: &mut iced::widget::canvas::Frame,
+        grid: &PriceGrid,
+        bounds: Rectangle,
+        cols: &ColumnRanges,
+        palette: &iced::theme::palette::Extended,
+    ) {
+        let mut rows: BTreeMap<(Price, bool), Qty> = BTreeMap::new();
+        for order in &self.working_orders {
+            let is_buy = order.side == OrderSide::Buy;
+            *rows
+                .entry((self.order_row_price(order), is_buy))
+                .or_default() += order.remaining();
+        }
+
+        for ((price, is_buy), qty) in rows {
+            let Some(center_y) = self.price_to_screen_y(price, grid, bounds.height) else {
+                continue;
+            };
+            let y = center_y - ROW_HEIGHT / 2.0;
+            if y >= bounds.height || y + ROW_HEIGHT <= 0.0 {
+                continue;
+            }
+
+            let content = format!("● {}", self.format_quantity(qty));
+            let tag_width = Self::mono_text_width_px(content.chars().count()) + 8.0;
+
+            let (color, tag_x, text_x, align) = if is_buy {
+                let x = (cols.bid_order.1 - tag_width).max(cols.bid_order.0);
+                (
+                    palette.success.strong,
+                    x,
+                    cols.bid_order.1 - 4.0,
+                    Alignment::End,
+                )
+            } else {
+                let x = cols.ask_order.0;
+                (
+                    palette.danger.strong,
+                    x,
+                    cols.ask_order.0 + 4.0,
+                    Alignment::Start,
+                )
+            };
+
+            frame.fill_rectangle(
+                Point::new(tag_x, y + 1.0),
+                Size::new(tag_width, ROW_HEIGHT - 2.0),
+                color.color,
+            );
+            self.draw_cell_text(frame, &content, text_x, y, color.text, align);
+        }
+    }
+
+    /// Order to place or cancel for a click at `position`, `None` outside the order columns
+    fn order_entry_click(
+        &self,
+        button: mouse::Button,
+        position: Point,
+        bounds: Rectangle,
+    ) -> Option<Message> {
+        let grid = self.build_price_grid()?;
+        let layout = self.price_layout_for(bounds.width, &grid);
+        let cols = self.column_ranges(bounds.width, layout.price_px);
+
+        let offset = position.y - bounds.height * 0.5 + self.scroll_px;
+        let idx = (offset / ROW_HEIGHT + 0.5).floor() as i32;
+        let price = grid.index_to_price(idx)?;
+
+        let within = |(start, end): (f32, f32)| position.x >= start && position.x <= end;
+        let is_bid_row = idx > 0;
+
+        match button {
+            mouse::Button::Left if is_bid_row && within(cols.bid_order) => {
+                Some(Message::PlaceOrder(OrderSide::Buy, price))
+            }
+            mouse::Button::Left if !is_bid_row && within(cols.ask_order) => {
+                Some(Message::PlaceOrder(OrderSide::Sell, price))
+            }
+            mouse::Button::Right
+                if self
+                    .working_orders
+                    .iter()
+                    .any(|order| self.order_row_price(order) == price) =>
+            {
+                Some(Message::CancelOrdersAt(price))
+            }
+            _ => None,
+        }
+    }
+
     fn build_price_grid(&self) -> Option<PriceGrid> {
         let best_bid = match (self.best_price(Side::Bid), self.best_price(Side::Ask)) {
             (Some(bb), _) => bb,
@@ -783,6 +1404,7 @@ impl Ladder {
 
         let mut visible: Vec<VisibleRow> = Vec::new();
         let mut maxima = Maxima::default();
+        let mut visible_qty_sum = 0.0_f32;
 
         let mid_screen_y = bounds.height * 0.5;
         let scroll = self.scroll_px;
@@ -810,6 +1432,9 @@ impl Ladder {
                         y: top_y_screen,
                         buy_t: Qty::default(),
                         sell_t: Qty::default(),
+                        cum_qty: Qty::default(),
+                        session_qty: Default::default(),
+                        refilling: false,
                     });
                 }
                 continue;
@@ -831,7 +1456,31 @@ impl Ladder {
                 continue;
             }
 
+            let cum_qty = if !self.config.show_cumulative && !self.config.show_depth_bars {
+                Qty::default()
+            } else if is_bid {
+                bids_grouped
+                    .range(price..)
+                    .fold(Qty::ZERO, |sum, (_, qty)| sum + *qty)
+            } else {
+                asks_grouped
+                    .range(..=price)
+                    .fold(Qty::ZERO, |sum, (_, qty)| sum + *qty)
+            };
+            maxima.vis_max_cum_qty = maxima.vis_max_cum_qty.max(cum_qty.to_f32_lossy());
+
+            let refilling = self
+                .config
+                .refill_ratio
+                .is_some_and(|ratio| self.refills.is_refilling(price, is_bid, ratio));
+
+            let session_qty = self.session_volume.qty_at(price);
+            maxima.vis_max_session_qty = maxima
+                .vis_max_session_qty
+                .max((session_qty.0 + session_qty.1).to_f32_lossy());
+
             maxima.vis_max_order_qty = maxima.vis_max_order_qty.max(order_qty.to_f32_lossy());
+            visible_qty_sum += order_qty.to_f32_lossy();
             let (buy_t, sell_t) = self.trade_qty_at(price);
             maxima.vis_max_trade_qty = maxima
                 .vis_max_trade_qty
@@ -854,9 +1503,18 @@ impl Ladder {
                 y: top_y_screen,
                 buy_t,
                 sell_t,
+                cum_qty,
+                session_qty,
+                refilling,
             });
         }
 
+        maxima.order_qty_total = match self.config.size_display {
+            SizeDisplay::Absolute => None,
+            SizeDisplay::PercentOfVisible => Some(visible_qty_sum),
+            SizeDisplay::PercentOfTop(levels) => Some(self.top_levels_qty(levels)),
+        };
+
         visible.sort_by(|a, b| a.y.total_cmp(&b.y));
         (visible, maxima)
     }
@@ -2,7 +2,10 @@ use super::Message;
 use crate::style;
 use data::config::theme::{darken, lighten};
 pub use data::panel::timeandsales::Config;
-use data::panel::timeandsales::{HistAgg, HistAggValues, StackedBar, TradeDisplay, TradeEntry};
+use data::panel::timeandsales::{
+    HistAgg, HistAggValues, StackedBar, TapeColumn, TradeDisplay, TradeEntry,
+};
+use exchange::adapter::{MarketKind, StreamKind};
 use exchange::unit::Qty;
 use exchange::{SizeUnit, TickerInfo, Trade, UnixMs, unit::qty::volume_size_unit};
 
@@ -15,9 +18,17 @@ const TEXT_SIZE: iced::Pixels = iced::Pixels(style::text_size::SMALL);
 const METRICS_HEIGHT_COMPACT: f32 = 8.0;
 const METRICS_HEIGHT_FULL: f32 = 18.0;
 const TRADE_ROW_HEIGHT: f32 = 14.0;
+const FOOTER_HEIGHT: f32 = 18.0;
+/// Space between a column's text and its edge
+const COLUMN_PAD: f32 = 6.0;
+/// Row background opacity of each size tier, see [`Config::size_tier`]
+const SIZE_TIER_ALPHAS: [f32; 3] = [0.45, 0.7, 0.95];
+/// Keeps rows below the tiers from outshining them
+const UNTIERED_MAX_ALPHA: f32 = 0.25;
 
 impl super::Panel for TimeAndSales {
     fn scroll(&mut self, delta: f32) {
+        let was_scrolled_back = self.is_scrolled_back();
         self.scroll_offset -= delta;
 
         let stacked_bar_h = self.stacked_bar_height();
@@ -27,19 +38,16 @@ impl super::Panel for TimeAndSales {
 
         self.scroll_offset = self.scroll_offset.clamp(0.0, max_scroll_offset);
 
-        if self.scroll_offset > stacked_bar_h + TRADE_ROW_HEIGHT {
-            self.is_paused = true;
-        } else if self.is_paused {
-            self.is_paused = false;
-
-            for trade in self.paused_trades_buffer.iter() {
-                self.hist_agg.add(&trade.display);
+        if self.is_scrolled_back() {
+            if self.paused != Some(Paused::Click) {
+                self.paused = Some(Paused::Scroll);
+            }
+        } else if was_scrolled_back && self.paused == Some(Paused::Scroll) {
+            if self.hovered && self.config.pause_on_hover {
+                self.paused = Some(Paused::Hover);
+            } else {
+                self.resume();
             }
-
-            self.recent_trades
-                .extend(self.paused_trades_buffer.drain(..));
-
-            self.prune_by_time(None);
         }
 
         self.invalidate(Some(Instant::now()));
@@ -47,20 +55,28 @@ impl super::Panel for TimeAndSales {
 
     fn reset_scroll(&mut self) {
         self.scroll_offset = 0.0;
-        self.is_paused = false;
+        self.resume();
 
-        for trade in self.paused_trades_buffer.iter() {
-            self.hist_agg.add(&trade.display);
-        }
+        self.invalidate(Some(Instant::now()));
+    }
 
-        self.recent_trades
-            .extend(self.paused_trades_buffer.drain(..));
+    fn set_hovered(&mut self, hovered: bool) {
+        self.hovered = hovered;
 
-        self.prune_by_time(None);
+        if hovered && self.config.pause_on_hover {
+            self.paused.get_or_insert(Paused::Hover);
+        } else if !hovered && self.paused == Some(Paused::Hover) {
+            self.resume();
+        }
 
         self.invalidate(Some(Instant::now()));
     }
 
+    fn pause(&mut self) {
+        self.paused = Some(Paused::Click);
+        self.invalidate(Some(Instant::now()));
+    }
+
     fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
         self.invalidate(now)
     }
@@ -70,49 +86,195 @@ impl super::Panel for TimeAndSales {
     }
 }
 
+/// Why the tape stopped scrolling, which decides what starts it again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Paused {
+    /// Cursor over the tape with [`Config::pause_on_hover`], ends when it leaves
+    Hover,
+    /// Scrolled back through the history, ends back at the top
+    Scroll,
+    /// Clicked, ends only on a resume or jump to live
+    Click,
+}
+
 pub struct TimeAndSales {
     recent_trades: VecDeque<TradeEntry>,
     paused_trades_buffer: VecDeque<TradeEntry>,
     hist_agg: HistAgg,
-    is_paused: bool,
+    paused: Option<Paused>,
+    /// Cursor is over the tape, see [`Config::pause_on_hover`]
+    hovered: bool,
     max_filtered_qty: Qty,
     ticker_info: TickerInfo,
+    /// Every ticker feeding the tape, the first one being `ticker_info`
+    sources: Vec<TickerInfo>,
     pub config: Config,
     cache: canvas::Cache,
     last_tick: Instant,
     scroll_offset: f32,
+    /// Start and end of the candle hovered on a chart of the same link group
+    linked_span: Option<(UnixMs, UnixMs)>,
+    scale_factor: f32,
 }
 
 impl TimeAndSales {
-    pub fn new(config: Option<Config>, ticker_info: TickerInfo) -> Self {
+    /// `others` are merged into the tape of `ticker_info`
+    pub fn new(config: Option<Config>, ticker_info: TickerInfo, others: &[TickerInfo]) -> Self {
+        let mut sources = vec![ticker_info];
+        for ti in others {
+            if !sources.contains(ti) {
+                sources.push(*ti);
+            }
+        }
+
         Self {
             recent_trades: VecDeque::new(),
             paused_trades_buffer: VecDeque::new(),
             hist_agg: HistAgg::default(),
-            is_paused: false,
+            paused: None,
+            hovered: false,
             config: config.unwrap_or_default(),
             max_filtered_qty: Qty::ZERO,
             ticker_info,
+            sources,
             cache: canvas::Cache::default(),
             last_tick: Instant::now(),
             scroll_offset: 0.0,
+            linked_span: None,
+            scale_factor: 1.0,
         }
     }
 
-    pub fn insert_buffer(&mut self, trades_buffer: &[Trade]) {
+    pub fn sources(&self) -> &[TickerInfo] {
+        &self.sources
+    }
+
+    fn is_multi_source(&self) -> bool {
+        self.sources.len() > 1
+    }
+
+    pub fn add_source(&mut self, ticker_info: &TickerInfo) -> Vec<StreamKind> {
+        if !self.sources.contains(ticker_info) {
+            self.sources.push(*ticker_info);
+
+            let base = Some(self.ticker_info);
+            self.recent_trades
+                .iter_mut()
+                .chain(self.paused_trades_buffer.iter_mut())
+                .filter(|entry| entry.source.is_none())
+                .for_each(|entry| entry.source = base);
+        }
+
+        self.streams_for_all()
+    }
+
+    pub fn remove_source(&mut self, ticker_info: &TickerInfo) -> Vec<StreamKind> {
+        if *ticker_info != self.ticker_info {
+            self.sources.retain(|t| t != ticker_info);
+
+            let from_removed = |entry: &TradeEntry| entry.source.as_ref() == Some(ticker_info);
+
+            self.recent_trades.retain(|entry| !from_removed(entry));
+            self.paused_trades_buffer
+                .retain(|entry| !from_removed(entry));
+
+            if !self.is_multi_source() {
+                self.recent_trades
+                    .iter_mut()
+                    .chain(self.paused_trades_buffer.iter_mut())
+                    .for_each(|entry| entry.source = None);
+            }
+
+            self.hist_agg = HistAgg::default();
+            for entry in &self.recent_trades {
+                self.hist_agg.add(&entry.display);
+            }
+            self.max_filtered_qty = self.max_filtered_qty_of_recent();
+        }
+
+        self.streams_for_all()
+    }
+
+    pub fn streams_for_all(&self) -> Vec<StreamKind> {
+        self.sources
+            .iter()
+            .map(|&ticker_info| StreamKind::Trades { ticker_info })
+            .collect()
+    }
+
+    fn market_type_of(&self, entry: &TradeEntry) -> MarketKind {
+        entry.source.unwrap_or(self.ticker_info).market_type()
+    }
+
+    /// Size the filter and tiers compare against, in the configured size unit
+    fn trade_size_value(&self, entry: &TradeEntry) -> f64 {
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+        self.market_type_of(entry).qty_in_quote_value(
+            entry.display.qty,
+            entry.display.price,
+            size_in_quote_ccy,
+        )
+    }
+
+    fn max_filtered_qty_of_recent(&self) -> Qty {
         let size_filter = self.config.trade_size_filter;
 
-        let target_trades = if self.is_paused {
+        self.recent_trades
+            .iter()
+            .filter(|t| self.trade_size_value(t) as f32 >= size_filter)
+            .map(|e| e.display.qty)
+            .fold(Qty::ZERO, Qty::max)
+    }
+
+    pub fn insert_buffer(&mut self, ticker_info: TickerInfo, trades_buffer: &[Trade]) {
+        let size_filter = self.config.trade_size_filter;
+        let aggregation_ms = self.config.aggregation_ms;
+        let source = self.is_multi_source().then_some(ticker_info);
+
+        let is_paused = self.is_paused();
+        let target_trades = if is_paused {
             &mut self.paused_trades_buffer
         } else {
             &mut self.recent_trades
         };
 
-        let market_type = self.ticker_info.market_type();
+        let market_type = ticker_info.market_type();
         let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
 
         for trade in trades_buffer {
             let trade_time = trade.time;
+
+            // Venues deliver on their own latency, so a batch can land
+            // behind trades another source already put on the tape
+            let idx = target_trades.partition_point(|entry| entry.ts_ms <= trade_time);
+
+            if let Some(window_ms) = aggregation_ms
+                && let Some(prev) = idx.checked_sub(1).and_then(|i| target_trades.get_mut(i))
+                && prev.source == source
+                && prev.display.price == trade.price
+                && prev.display.is_sell == trade.is_sell
+                && trade_time.saturating_diff(prev.ts_ms) <= u64::from(window_ms)
+            {
+                if !is_paused {
+                    self.hist_agg.remove(&prev.display);
+                }
+                prev.display.qty += trade.qty;
+                if !is_paused {
+                    self.hist_agg.add(&prev.display);
+                }
+
+                let merged_size_value = market_type.qty_in_quote_value(
+                    prev.display.qty,
+                    trade.price,
+                    size_in_quote_ccy,
+                );
+                if merged_size_value as f32 >= size_filter {
+                    self.max_filtered_qty = self.max_filtered_qty.max(prev.display.qty);
+                }
+                continue;
+            }
+
             if let Some(time_str) = trade_time.format_utc("%M:%S.%3f") {
                 let trade_display = TradeDisplay {
                     time_str,
@@ -131,31 +293,66 @@ impl TimeAndSales {
                     self.max_filtered_qty = self.max_filtered_qty.max(trade_display.qty);
                 }
 
-                target_trades.push_back(TradeEntry {
-                    ts_ms: trade_time,
-                    display: trade_display,
-                });
+                target_trades.insert(
+                    idx,
+                    TradeEntry {
+                        ts_ms: trade_time,
+                        display: trade_display,
+                        source,
+                    },
+                );
 
-                if !self.is_paused
-                    && let Some(last) = target_trades.back()
-                {
-                    self.hist_agg.add(&last.display);
+                if !is_paused {
+                    self.hist_agg.add(&target_trades[idx].display);
                 }
             }
         }
 
-        if !self.is_paused {
+        if !self.is_paused() {
             self.prune_by_time(None);
         }
         self.prune_paused_by_time(None);
     }
 
+    /// Writes the trades passing the size filter to a CSV, held back ones included
+    pub fn export_csv(&self) -> std::io::Result<std::path::PathBuf> {
+        let size_filter = self.config.trade_size_filter;
+        let trades = self
+            .recent_trades
+            .iter()
+            .chain(&self.paused_trades_buffer)
+            .filter(|entry| self.trade_size_value(entry) as f32 >= size_filter);
+
+        data::panel::timeandsales::write_csv(trades, &self.ticker_info)
+    }
+
+    /// Highlights the trades from `start` on, for `span_ms`, `None` to clear
+    pub fn set_linked_span(&mut self, span: Option<(UnixMs, u64)>) {
+        let span = span.map(|(start, span_ms)| (start, UnixMs::new(start.as_u64() + span_ms)));
+
+        if self.linked_span != span {
+            self.linked_span = span;
+            self.cache.clear();
+        }
+    }
+
     pub fn last_update(&self) -> Instant {
         self.last_tick
     }
 
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if (self.scale_factor - scale_factor).abs() >= f32::EPSILON {
+            self.scale_factor = scale_factor;
+            self.cache.clear();
+        }
+    }
+
+    fn text_size(&self) -> iced::Pixels {
+        iced::Pixels(style::dpi::text_size(TEXT_SIZE.0, self.scale_factor))
+    }
+
     pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
-        if !self.is_paused {
+        if !self.is_paused() {
             self.prune_by_time(None);
         }
         self.prune_paused_by_time(None);
@@ -167,6 +364,184 @@ impl TimeAndSales {
         None
     }
 
+    /// Puts the trades held back while paused on the tape
+    fn resume(&mut self) {
+        self.paused = None;
+
+        for trade in self.paused_trades_buffer.iter() {
+            self.hist_agg.add(&trade.display);
+        }
+
+        self.recent_trades
+            .extend(self.paused_trades_buffer.drain(..));
+
+        self.prune_by_time(None);
+    }
+
+    fn is_scrolled_back(&self) -> bool {
+        self.scroll_offset > self.stacked_bar_height() + TRADE_ROW_HEIGHT
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Where the text of each shown column is anchored, and how it's aligned there
+    fn column_layout(&self, width: f32) -> Vec<(TapeColumn, f32, Alignment)> {
+        let shown = self.config.columns.shown().collect::<Vec<_>>();
+        let total_weight = shown
+            .iter()
+            .map(|column| column_weight(*column))
+            .sum::<f32>();
+
+        let mut left = 0.0;
+        shown
+            .into_iter()
+            .map(|column| {
+                let column_width = width * column_weight(column) / total_weight;
+                let anchor = match column {
+                    TapeColumn::Exchange | TapeColumn::Aggressor => {
+                        (left + column_width / 2.0, Alignment::Center)
+                    }
+                    TapeColumn::Time => (left + COLUMN_PAD, Alignment::Start),
+                    TapeColumn::Price | TapeColumn::Size | TapeColumn::Notional => {
+                        (left + column_width - COLUMN_PAD, Alignment::End)
+                    }
+                };
+                left += column_width;
+                (column, anchor.0, anchor.1)
+            })
+            .collect()
+    }
+
+    /// Buy and sell volume of the last `secs` seconds, held back trades included
+    fn rolling_volume(&self, secs: u16) -> (Qty, Qty) {
+        let cutoff = UnixMs::now().saturating_sub(u64::from(secs) * 1_000);
+        let since_cutoff = |entry: &&TradeEntry| entry.ts_ms >= cutoff;
+
+        let recent = self.recent_trades.iter().rev().take_while(since_cutoff);
+        let held_back = self
+            .paused_trades_buffer
+            .iter()
+            .rev()
+            .take_while(since_cutoff);
+
+        recent
+            .chain(held_back)
+            .fold((Qty::ZERO, Qty::ZERO), |(buy, sell), entry| {
+                if entry.display.is_sell {
+                    (buy, sell + entry.display.qty)
+                } else {
+                    (buy + entry.display.qty, sell)
+                }
+            })
+    }
+
+    fn draw_footer(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Rectangle,
+        secs: u16,
+        palette: &iced::theme::palette::Extended,
+    ) {
+        let top = bounds.height - FOOTER_HEIGHT;
+        let center_y = top + FOOTER_HEIGHT / 2.0;
+
+        frame.fill_rectangle(
+            Point { x: 0.0, y: top },
+            Size {
+                width: bounds.width,
+                height: FOOTER_HEIGHT,
+            },
+            palette.background.weak.color,
+        );
+
+        let (buy, sell) = self.rolling_volume(secs);
+        let (buy, sell) = (buy.to_f64(), sell.to_f64());
+
+        if buy + sell <= 0.0 {
+            frame.fill_text(Text {
+                content: format!("No trades in {secs}s"),
+                position: Point {
+                    x: bounds.width / 2.0,
+                    y: center_y,
+                },
+                size: self.text_size(),
+                font: style::AZERET_MONO,
+                color: palette.background.weak.text,
+                align_x: Alignment::Center.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            });
+            return;
+        }
+
+        let buy_width = (bounds.width * (buy / (buy + sell)) as f32).round();
+        frame.fill_rectangle(
+            Point { x: 0.0, y: top },
+            Size {
+                width: buy_width,
+                height: FOOTER_HEIGHT,
+            },
+            palette.success.weak.color,
+        );
+        frame.fill_rectangle(
+            Point {
+                x: buy_width,
+                y: top,
+            },
+            Size {
+                width: bounds.width - buy_width,
+                height: FOOTER_HEIGHT,
+            },
+            palette.danger.weak.color,
+        );
+
+        let delta = buy - sell;
+        let ratio = if sell > 0.0 {
+            format!("{:.2}x", buy / sell)
+        } else {
+            "-".to_string()
+        };
+        let labels = [
+            (
+                data::util::abbr_large_numbers(buy),
+                8.0,
+                Alignment::Start,
+                palette.success.weak.text,
+            ),
+            (
+                format!(
+                    "{ratio} Δ{}{} {secs}s",
+                    if delta > 0.0 { "+" } else { "" },
+                    data::util::abbr_large_numbers(delta),
+                ),
+                bounds.width / 2.0,
+                Alignment::Center,
+                palette.background.base.text,
+            ),
+            (
+                data::util::abbr_large_numbers(sell),
+                bounds.width - 8.0,
+                Alignment::End,
+                palette.danger.weak.text,
+            ),
+        ];
+
+        for (content, x, align_x, color) in labels {
+            frame.fill_text(Text {
+                content,
+                position: Point { x, y: center_y },
+                size: self.text_size(),
+                font: style::AZERET_MONO,
+                color,
+                align_x: align_x.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            });
+        }
+    }
+
     fn stacked_bar_height(&self) -> f32 {
         match &self.config.stacked_bar {
             Some(StackedBar::Compact(_)) => METRICS_HEIGHT_COMPACT,
@@ -200,8 +575,6 @@ impl TimeAndSales {
             return;
         }
 
-        let size_filter = self.config.trade_size_filter;
-
         let mut popped_any = false;
         while let Some(front) = self.recent_trades.front() {
             if front.ts_ms >= low_cutoff {
@@ -213,22 +586,7 @@ impl TimeAndSales {
         }
 
         if popped_any {
-            let market_type = self.ticker_info.market_type();
-            let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
-
-            self.max_filtered_qty = self
-                .recent_trades
-                .iter()
-                .filter(|t| {
-                    let trade_size = market_type.qty_in_quote_value(
-                        t.display.qty,
-                        t.display.price,
-                        size_in_quote_ccy,
-                    );
-                    trade_size as f32 >= size_filter
-                })
-                .map(|e| e.display.qty)
-                .fold(Qty::ZERO, Qty::max);
+            self.max_filtered_qty = self.max_filtered_qty_of_recent();
 
             let stacked_bar_h = self.stacked_bar_height();
             let total_content_height =
@@ -278,7 +636,14 @@ impl canvas::Program<Message> for TimeAndSales {
         bounds: iced::Rectangle,
         cursor: iced_core::mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
-        let cursor_position = cursor.position_in(bounds)?;
+        let Some(cursor_position) = cursor.position_in(bounds) else {
+            let left = matches!(
+                event,
+                Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft)
+            );
+            return (left && self.hovered)
+                .then(|| canvas::Action::publish(Message::Hovered(false)));
+        };
 
         match event {
             Event::Mouse(mouse_event) => match mouse_event {
@@ -295,8 +660,10 @@ impl canvas::Program<Message> for TimeAndSales {
                             height: paused_box_height,
                         };
 
-                        if self.is_paused && paused_box.contains(cursor_position) {
+                        if self.is_paused() && paused_box.contains(cursor_position) {
                             Some(canvas::Action::publish(Message::ResetScroll).and_capture())
+                        } else if self.paused != Some(Paused::Click) {
+                            Some(canvas::Action::publish(Message::Pause).and_capture())
                         } else {
                             None
                         }
@@ -312,7 +679,9 @@ impl canvas::Program<Message> for TimeAndSales {
                     Some(canvas::Action::publish(Message::Scrolled(scroll_amount)).and_capture())
                 }
                 mouse::Event::CursorMoved { .. } => {
-                    if self.is_paused {
+                    if !self.hovered {
+                        Some(canvas::Action::publish(Message::Hovered(true)).and_capture())
+                    } else if self.is_paused() {
                         let now = Some(Instant::now());
                         Some(canvas::Action::publish(Message::Invalidate(now)).and_capture())
                     } else {
@@ -333,10 +702,8 @@ impl canvas::Program<Message> for TimeAndSales {
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
-        let market_type = self.ticker_info.market_type();
-
         let palette = theme.extended_palette();
-        let is_scroll_paused = self.is_paused;
+        let is_scroll_paused = self.is_paused();
         let stacked_bar_h = self.stacked_bar_height();
 
         let content = self.cache.draw(renderer, bounds.size(), |frame| {
@@ -409,7 +776,7 @@ impl canvas::Program<Message> for TimeAndSales {
                                 x: 8.0,
                                 y: center_y,
                             },
-                            size: TEXT_SIZE,
+                            size: self.text_size(),
                             font: style::AZERET_MONO,
                             color: palette.success.weak.text,
                             align_x: Alignment::Start.into(),
@@ -424,7 +791,7 @@ impl canvas::Program<Message> for TimeAndSales {
                                 x: bounds.width - 8.0,
                                 y: center_y,
                             },
-                            size: TEXT_SIZE,
+                            size: self.text_size(),
                             font: style::AZERET_MONO,
                             color: palette.danger.weak.text,
                             align_x: Alignment::End.into(),
@@ -444,20 +811,13 @@ impl canvas::Program<Message> for TimeAndSales {
             let start_index = (row_scroll_offset / row_height).floor() as usize;
             let visible_rows = (bounds.height / row_height).ceil() as usize;
 
-            let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
             let max_filtered_qty_f32 = self.max_filtered_qty.to_f32_lossy();
+            let columns = self.column_layout(row_width);
 
             let trades_to_draw = self
                 .recent_trades
                 .iter()
-                .filter(|t| {
-                    let trade_size = market_type.qty_in_quote_value(
-                        t.display.qty,
-                        t.display.price,
-                        size_in_quote_ccy,
-                    );
-                    trade_size as f32 >= self.config.trade_size_filter
-                })
+                .filter(|t| self.trade_size_value(t) as f32 >= self.config.trade_size_filter)
                 .rev()
                 .skip(start_index)
                 .take(visible_rows + 2);
@@ -466,7 +826,7 @@ impl canvas::Program<Message> for TimeAndSales {
                 |content: String, position: Point, align_x: Alignment, color: iced::Color| Text {
                     content,
                     position,
-                    size: TEXT_SIZE,
+                    size: self.text_size(),
                     font: style::AZERET_MONO,
                     color,
                     align_x: align_x.into(),
@@ -482,22 +842,42 @@ impl canvas::Program<Message> for TimeAndSales {
                     continue;
                 }
 
-                let bg_color = if trade.is_sell {
-                    palette.danger.weak.color
-                } else {
-                    palette.success.weak.color
+                let tier = self.config.size_tier(self.trade_size_value(entry));
+                let tier_pair = tier.map(|tier| match (trade.is_sell, tier) {
+                    (true, 0) => palette.danger.weak,
+                    (true, 1) => palette.danger.base,
+                    (true, _) => palette.danger.strong,
+                    (false, 0) => palette.success.weak,
+                    (false, 1) => palette.success.base,
+                    (false, _) => palette.success.strong,
+                });
+
+                let bg_color = match tier_pair {
+                    Some(pair) => pair.color,
+                    None if trade.is_sell => palette.danger.weak.color,
+                    None => palette.success.weak.color,
                 };
 
-                let bg_color_alpha = if max_filtered_qty_f32 > 0.0 {
-                    (trade.qty.to_f32_lossy() / max_filtered_qty_f32).clamp(0.02, 1.0)
-                } else {
-                    0.02
+                let bg_color_alpha = match tier {
+                    Some(tier) => SIZE_TIER_ALPHAS[tier],
+                    None => {
+                        let alpha = if max_filtered_qty_f32 > 0.0 {
+                            (trade.qty.to_f32_lossy() / max_filtered_qty_f32).clamp(0.02, 1.0)
+                        } else {
+                            0.02
+                        };
+                        if self.config.size_tiers.is_some() {
+                            alpha.min(UNTIERED_MAX_ALPHA)
+                        } else {
+                            alpha
+                        }
+                    }
                 };
 
-                let mut text_color = if palette.is_dark {
-                    lighten(bg_color, bg_color_alpha.max(0.1))
-                } else {
-                    darken(bg_color, (bg_color_alpha * 0.8).max(0.1))
+                let mut text_color = match tier_pair {
+                    Some(pair) => pair.text,
+                    None if palette.is_dark => lighten(bg_color, bg_color_alpha.max(0.1)),
+                    None => darken(bg_color, (bg_color_alpha * 0.8).max(0.1)),
                 };
 
                 if is_scroll_paused
@@ -519,38 +899,62 @@ impl canvas::Program<Message> for TimeAndSales {
                     bg_color.scale_alpha(bg_color_alpha.min(0.9)),
                 );
 
-                let trade_time = create_text(
-                    trade.time_str.clone(),
-                    Point {
-                        x: row_width * 0.1,
-                        y: y_position,
-                    },
-                    Alignment::Start,
-                    text_color,
-                );
-                frame.fill_text(trade_time);
+                if self
+                    .linked_span
+                    .is_some_and(|(start, end)| (start..end).contains(&entry.ts_ms))
+                {
+                    frame.fill_rectangle(
+                        Point {
+                            x: 0.0,
+                            y: y_position,
+                        },
+                        Size {
+                            width: row_width,
+                            height: row_height,
+                        },
+                        palette.primary.weak.color.scale_alpha(0.5),
+                    );
+                }
 
-                let trade_price = create_text(
-                    trade.price.to_string(self.ticker_info.min_ticksize),
-                    Point {
-                        x: row_width * 0.67,
-                        y: y_position,
-                    },
-                    Alignment::End,
-                    text_color,
-                );
-                frame.fill_text(trade_price);
+                let source = entry.source.unwrap_or(self.ticker_info);
+                let (exchange, min_ticksize) = (source.exchange(), source.min_ticksize);
+
+                for &(column, x, align_x) in &columns {
+                    let position = Point { x, y: y_position };
+
+                    let content = match column {
+                        TapeColumn::Exchange => {
+                            frame.fill_text(Text {
+                                content: char::from(style::venue_icon(exchange.venue()))
+                                    .to_string(),
+                                position,
+                                size: self.text_size(),
+                                font: style::ICONS_FONT,
+                                color: text_color,
+                                align_x: align_x.into(),
+                                ..Default::default()
+                            });
+                            continue;
+                        }
+                        TapeColumn::Time => trade.time_str.clone(),
+                        TapeColumn::Price => trade.price.to_string(min_ticksize),
+                        TapeColumn::Size => data::util::abbr_large_numbers(trade.qty.to_f64()),
+                        TapeColumn::Notional => data::util::abbr_large_numbers(
+                            self.market_type_of(entry).qty_in_quote_value(
+                                trade.qty,
+                                trade.price,
+                                true,
+                            ),
+                        ),
+                        TapeColumn::Aggressor if trade.is_sell => "S".to_string(),
+                        TapeColumn::Aggressor => "B".to_string(),
+                    };
+                    frame.fill_text(create_text(content, position, align_x, text_color));
+                }
+            }
 
-                let trade_qty = create_text(
-                    data::util::abbr_large_numbers(trade.qty.to_f64()),
-                    Point {
-                        x: row_width * 0.9,
-                        y: y_position,
-                    },
-                    Alignment::End,
-                    text_color,
-                );
-                frame.fill_text(trade_qty);
+            if let Some(secs) = self.config.footer_secs {
+                self.draw_footer(frame, bounds, secs, palette);
             }
 
             if is_scroll_paused {
@@ -588,13 +992,18 @@ impl canvas::Program<Message> for TimeAndSales {
                     bg_color,
                 );
 
+                let content = match self.paused_trades_buffer.len() {
+                    0 => "Paused · Jump to live".to_string(),
+                    waiting => format!("Paused, {waiting} new · Jump to live"),
+                };
+
                 frame.fill_text(Text {
-                    content: "Paused".to_string(),
+                    content,
                     position: Point {
                         x: frame.width() * 0.5,
                         y: pause_overlay_y + (pause_overlay_height / 2.0),
                     },
-                    size: 12.0.into(),
+                    size: style::dpi::text_size(12.0, self.scale_factor).into(),
                     font: style::AZERET_MONO,
                     color: palette.background.strong.text,
                     align_x: Alignment::Center.into(),
@@ -613,7 +1022,7 @@ impl canvas::Program<Message> for TimeAndSales {
         bounds: iced::Rectangle,
         cursor: iced_core::mouse::Cursor,
     ) -> iced_core::mouse::Interaction {
-        if self.is_paused {
+        if self.is_paused() {
             let stacked_bar_h = self.stacked_bar_height();
             let paused_box = Rectangle {
                 x: bounds.x,
@@ -630,3 +1039,13 @@ impl canvas::Program<Message> for TimeAndSales {
         mouse::Interaction::default()
     }
 }
+
+/// Share of the row each column gets, before scaling to the shown ones
+fn column_weight(column: TapeColumn) -> f32 {
+    match column {
+        TapeColumn::Exchange | TapeColumn::Aggressor => 0.08,
+        TapeColumn::Time => 0.32,
+        TapeColumn::Price => 0.26,
+        TapeColumn::Size | TapeColumn::Notional => 0.18,
+    }
+}
@@ -10,7 +10,7 @@ use data::sidebar;
 use iced::{
     Alignment, Element, Subscription, Task,
     widget::responsive,
-    widget::{column, row, space},
+    widget::{column, row, space, text},
 };
 use rustc_hash::FxHashMap;
 
@@ -31,6 +31,7 @@ pub enum Action {
         exchange::TickerInfo,
         Option<data::layout::pane::ContentKind>,
     ),
+    PresetSelected(exchange::TickerInfo, data::layout::pane::PanePreset),
     ErrorOccurred(data::InternalError),
 }
 
@@ -73,6 +74,12 @@ impl Sidebar {
                             Some(Action::TickerSelected(ticker_info, content)),
                         );
                     }
+                    Some(tickers_table::Action::PresetSelected(ticker_info, preset)) => {
+                        return (
+                            Task::none(),
+                            Some(Action::PresetSelected(ticker_info, preset)),
+                        );
+                    }
                     Some(tickers_table::Action::Fetch(task)) => {
                         return (task.map(Message::TickersTable), None);
                     }
@@ -134,7 +141,11 @@ impl Sidebar {
         let settings_modal_button = {
             let is_active = self.is_menu_active(sidebar::Menu::Settings)
                 || self.is_menu_active(sidebar::Menu::ThemeEditor)
-                || self.is_menu_active(sidebar::Menu::Network);
+                || self.is_menu_active(sidebar::Menu::Network)
+                || self.is_menu_active(sidebar::Menu::Connections)
+                || self.is_menu_active(sidebar::Menu::Alerts)
+                || self.is_menu_active(sidebar::Menu::Trading)
+                || self.is_menu_active(sidebar::Menu::Journal);
 
             button_with_tooltip(
                 icon_text(Icon::Cog, 14)
@@ -148,7 +159,8 @@ impl Sidebar {
         };
 
         let layout_modal_button = {
-            let is_active = self.is_menu_active(sidebar::Menu::Layout);
+            let is_active = self.is_menu_active(sidebar::Menu::Layout)
+                || self.is_menu_active(sidebar::Menu::LayoutTemplates);
 
             button_with_tooltip(
                 icon_text(Icon::Layout, 14)
@@ -193,10 +205,23 @@ impl Sidebar {
             )
         };
 
+        let account_btn = {
+            let is_active = self.is_menu_active(sidebar::Menu::Account);
+
+            button_with_tooltip(
+                text("$").size(14).width(24).align_x(Alignment::Center),
+                Message::ToggleSidebarMenu(Some(sidebar::Menu::Account)),
+                None,
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, is_active),
+            )
+        };
+
         column![
             ticker_search_button,
             layout_modal_button,
             audio_btn,
+            account_btn,
             space::vertical(),
             settings_modal_button,
         ]
@@ -1,32 +1,38 @@
 use crate::{
+    connector::stream::session_profile,
     modal::pane::mini_tickers_list::RowSelection,
     style::{self, Icon, icon_text},
     widget::tooltip_with_delay,
 };
 use data::{
     InternalError,
-    layout::pane::ContentKind,
+    layout::pane::{ContentKind, PanePreset},
     tickers_table::{
-        PriceChange, Settings, SortOptions, TickerDisplayData, TickerRowData, calc_search_rank,
-        compare_ticker_rows_by_sort, compute_display_data, market_suffix,
+        Breadth, MoverMetric, MoverTracker, PriceChange, SPARKLINE_TIMEFRAME, SPARKLINE_WINDOW_MS,
+        ScreenerField, ScreenerFilters, Settings, SortOptions, Sparkline, TickerDisplayData,
+        TickerRowData, VOLUME_AVERAGE_DAYS, average_daily_volume, calc_search_rank,
+        compare_ticker_rows_by_mover, compare_ticker_rows_by_sort, compute_display_data,
+        market_suffix,
     },
 };
 use exchange::{
-    Ticker, TickerInfo, TickerStats,
-    adapter::{AdapterHandles, Exchange, MarketKind, Venue},
+    PushFrequency, Ticker, TickerInfo, TickerStats, Timeframe, UnixMs,
+    adapter::{AdapterHandles, Exchange, MarketKind, StreamConfig, Venue},
+    unit::qty::{SizeUnit, volume_size_unit},
 };
 use iced::{
-    Alignment, Element, Length, Renderer, Size, Subscription, Task, Theme,
+    Alignment, Element, Length, Point, Rectangle, Renderer, Size, Subscription, Task, Theme,
     alignment::{self, Horizontal, Vertical},
-    padding,
+    mouse, padding,
     widget::{
-        Button, Space, button, column, container, row, rule,
+        Button, Space, button, canvas, column, container, row, rule,
         scrollable::{self, AbsoluteOffset},
         space, text, text_input,
     },
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{
+    cmp::Ordering,
     collections::HashMap,
     time::{Duration, Instant},
 };
@@ -35,8 +41,13 @@ use std::{
 const ACTIVE_UPDATE_INTERVAL: u64 = 13;
 
 /// How often to refresh stats while the ticker table is hidden (seconds).
+/// Venues streaming their stats are polled at this pace too, for the fields left out.
 const INACTIVE_UPDATE_INTERVAL: u64 = 300;
 
+/// A venue's stats stream counts as down after this long without a push, and it's
+/// polled like the rest again.
+const STREAMED_STATS_STALE: Duration = Duration::from_secs(10);
+
 /// Wait this long after exchange toggles before firing one merged stats fetch (milliseconds).
 const EXCHANGE_TOGGLE_DEBOUNCE_MS: u64 = 1_000;
 
@@ -51,10 +62,25 @@ const FAVORITES_SEPARATOR_HEIGHT: f32 = 12.0;
 const FAVORITES_EMPTY_HINT_HEIGHT: f32 = 32.0;
 
 const TOP_BAR_HEIGHT: f32 = 40.0;
-const SORT_AND_FILTER_HEIGHT: f32 = 200.0;
+const BREADTH_BAR_HEIGHT: f32 = 32.0;
+const SORT_AND_FILTER_HEIGHT: f32 = 360.0;
 
 const COMPACT_ROW_HEIGHT: f32 = 28.0;
 
+/// How often the cards in view are checked for a missing or stale sparkline (milliseconds)
+const SPARKLINE_POLL_MS: u64 = 500;
+/// A card's sparkline is fetched again after this long
+const SPARKLINE_REFRESH: Duration = Duration::from_secs(10 * 60);
+/// Kline requests started per poll, so a fast scroll doesn't burst the venues
+const SPARKLINE_FETCHES_PER_POLL: usize = 4;
+const SPARKLINE_SIZE: Size = Size::new(56.0, 24.0);
+/// Assumed until the list has been scrolled and reported its height
+const FALLBACK_VIEWPORT_HEIGHT: f32 = 800.0;
+
+/// How often more daily volume averages are fetched while ranking by volume (milliseconds)
+const VOLUME_AVERAGE_POLL_MS: u64 = 1_000;
+const VOLUME_AVERAGE_FETCHES_PER_POLL: usize = 4;
+
 const EXCHANGE_UNAVAILABLE_TOOLTIP: &str = "Metadata unavailable.\nCheck logs for details.";
 
 fn available_markets(venue: Venue) -> &'static [MarketKind] {
@@ -64,11 +90,13 @@ fn available_markets(venue: Venue) -> &'static [MarketKind] {
         // Skip metadata fetch for Mexc spot as it requires protobuf for websocket
         // TODO: include after protobuf implementation and Mexc spot markets ready to stream
         Venue::Mexc => &[MarketKind::LinearPerps, MarketKind::InversePerps],
+        Venue::Plugin => &[MarketKind::Spot, MarketKind::LinearPerps],
     }
 }
 
 pub enum Action {
     TickerSelected(TickerInfo, Option<ContentKind>),
+    PresetSelected(TickerInfo, PanePreset),
     ErrorOccurred(data::InternalError),
     Fetch(Task<Message>),
     FocusWidget(iced::widget::Id),
@@ -80,17 +108,27 @@ pub enum Message {
     ChangeSortOption(SortOptions),
     ShowSortingOptions,
     TickerSelected(Ticker, Option<ContentKind>),
+    PresetSelected(Ticker, PanePreset),
     ExpandTickerCard(Option<Ticker>),
     FavoriteTicker(Ticker),
     Scrolled(scrollable::Viewport),
     ToggleMarketFilter(MarketKind),
     ToggleExchangeFilter(Venue),
+    ScreenerInput(ScreenerField, String),
+    ClearScreener,
     DebounceExchangeFetchTick,
     ToggleTable,
     ToggleFavorites,
+    FilterBreadth(PriceChange),
     FetchStats,
+    FetchSparklines,
+    UpdateSparkline(Ticker, Option<Sparkline>),
+    ToggleMovers(MoverMetric),
+    FetchVolumeAverages,
+    UpdateVolumeAverage(Ticker, Option<f64>),
     UpdateMetadata(Venue, HashMap<Ticker, Option<TickerInfo>>),
     UpdateStats(Venue, HashMap<Ticker, TickerStats>),
+    StatsStreamed(Exchange, HashMap<Ticker, TickerStats>),
     RetryMetadataFetch(Venue),
     MetadataFetchFailed(Venue, data::InternalError),
     StatsFetchFailed(Venue, data::InternalError),
@@ -102,6 +140,9 @@ pub struct TickersTable {
     display_cache: FxHashMap<Ticker, TickerDisplayData>,
     pub expand_ticker_card: Option<Ticker>,
     scroll_offset: AbsoluteOffset,
+    viewport_height: Option<f32>,
+    /// When each ticker's sparkline was last asked for, in flight or done
+    sparkline_requested: FxHashMap<Ticker, Instant>,
     pub is_shown: bool,
     pub tickers_info: FxHashMap<Ticker, Option<TickerInfo>>,
     unavailable_exchanges: FxHashSet<Venue>,
@@ -110,6 +151,15 @@ pub struct TickersTable {
     search_query: String,
     selected_sort_option: SortOptions,
     show_favorites: bool,
+    /// Shows only the favorited tickers that moved this way on the day
+    breadth_filter: Option<PriceChange>,
+    screener: ScreenerFilters,
+    /// Screener thresholds as typed, in the order of [`ScreenerField::ALL`]
+    screener_inputs: [String; 4],
+    movers: Option<MoverMetric>,
+    mover_tracker: MoverTracker,
+    /// Tickers whose daily volume average was asked for, in flight or done
+    volume_average_requested: FxHashSet<Ticker>,
     show_sort_options: bool,
     row_index: FxHashMap<Ticker, usize>,
     metadata_fetch_state: MetadataFetchState,
@@ -143,12 +193,26 @@ impl TickersTable {
                 selected_sort_option: settings.selected_sort_option,
                 expand_ticker_card: None,
                 scroll_offset: AbsoluteOffset::default(),
+                viewport_height: None,
+                sparkline_requested: FxHashMap::default(),
                 is_shown: false,
                 tickers_info: FxHashMap::default(),
                 unavailable_exchanges: FxHashSet::default(),
                 selected_exchanges: settings.selected_exchanges.iter().cloned().collect(),
                 selected_markets: settings.selected_markets.iter().cloned().collect(),
                 show_favorites: settings.show_favorites,
+                breadth_filter: None,
+                screener: settings.screener,
+                movers: settings.movers,
+                mover_tracker: MoverTracker::default(),
+                volume_average_requested: FxHashSet::default(),
+                screener_inputs: ScreenerField::ALL.map(|field| {
+                    settings
+                        .screener
+                        .get(field)
+                        .map(|value| value.to_string())
+                        .unwrap_or_default()
+                }),
                 row_index: FxHashMap::default(),
                 metadata_fetch_state: MetadataFetchState::with_pending(selected_exchanges),
                 stats_fetch_state: StatsFetchState::default(),
@@ -165,6 +229,8 @@ impl TickersTable {
             selected_sort_option: self.selected_sort_option,
             selected_exchanges: self.selected_exchanges.iter().cloned().collect(),
             selected_markets: self.selected_markets.iter().cloned().collect(),
+            screener: self.screener,
+            movers: self.movers,
         }
     }
 
@@ -174,7 +240,12 @@ impl TickersTable {
                 self.search_query = query.to_uppercase();
             }
             Message::ChangeSortOption(option) => {
-                self.change_sort_option(option);
+                if self.movers.take().is_some() {
+                    self.selected_sort_option = option;
+                    self.sort_ticker_rows();
+                } else {
+                    self.change_sort_option(option);
+                }
             }
             Message::ShowSortingOptions => {
                 self.show_sort_options = !self.show_sort_options;
@@ -187,6 +258,7 @@ impl TickersTable {
             }
             Message::Scrolled(viewport) => {
                 self.scroll_offset = viewport.absolute_offset();
+                self.viewport_height = Some(viewport.bounds().height);
             }
             Message::ToggleMarketFilter(market) => {
                 if self.selected_markets.contains(&market) {
@@ -220,6 +292,18 @@ impl TickersTable {
                         .on_exchange_enabled(exch, Instant::now());
                 }
             }
+            Message::ScreenerInput(field, input) => {
+                if input.trim().is_empty() {
+                    self.screener.set(field, None);
+                } else if let Some(value) = ScreenerField::parse(&input) {
+                    self.screener.set(field, Some(value));
+                }
+                self.screener_inputs[field as usize] = input;
+            }
+            Message::ClearScreener => {
+                self.screener = ScreenerFilters::default();
+                self.screener_inputs = Default::default();
+            }
             Message::DebounceExchangeFetchTick => {
                 self.metadata_fetch_state.tick_loading_phase();
                 self.stats_fetch_state.tick_loading_phase();
@@ -237,6 +321,13 @@ impl TickersTable {
             Message::ToggleFavorites => {
                 self.show_favorites = !self.show_favorites;
             }
+            Message::FilterBreadth(direction) => {
+                self.breadth_filter = if self.breadth_filter == Some(direction) {
+                    None
+                } else {
+                    Some(direction)
+                };
+            }
             Message::TickerSelected(ticker, content) => {
                 let ticker_info = self.tickers_info.get(&ticker).cloned().flatten();
 
@@ -249,21 +340,31 @@ impl TickersTable {
                     );
                 }
             }
+            Message::PresetSelected(ticker, preset) => {
+                if let Some(ticker_info) = self.tickers_info.get(&ticker).cloned().flatten() {
+                    return Some(Action::PresetSelected(ticker_info, preset));
+                }
+                log::warn!(
+                    "Ticker info not found for {ticker:?} on {:?}",
+                    ticker.exchange
+                );
+            }
             Message::ToggleTable => {
                 self.is_shown = !self.is_shown;
 
                 if self.is_shown {
-                    self.display_cache.clear();
+                    let mut previous = std::mem::take(&mut self.display_cache);
                     for row in self.ticker_rows.iter_mut() {
                         row.previous_stats = None;
                         let precision = self
                             .tickers_info
                             .get(&row.ticker)
                             .and_then(|info| info.as_ref().map(|ti| ti.min_ticksize));
-                        self.display_cache.insert(
-                            row.ticker,
-                            compute_display_data(&row.ticker, &row.stats, None, precision),
-                        );
+                        let mut display_data =
+                            compute_display_data(&row.ticker, &row.stats, None, precision);
+                        display_data.sparkline =
+                            previous.remove(&row.ticker).and_then(|data| data.sparkline);
+                        self.display_cache.insert(row.ticker, display_data);
                     }
 
                     return Some(Action::FocusWidget("full_ticker_search_box".into()));
@@ -274,6 +375,39 @@ impl TickersTable {
                     return Some(Action::Fetch(task));
                 }
             }
+            Message::FetchSparklines => {
+                if let Some(task) = self.sparkline_fetch_task() {
+                    return Some(Action::Fetch(task));
+                }
+            }
+            Message::UpdateSparkline(ticker, sparkline) => {
+                if let Some(display_data) = self.display_cache.get_mut(&ticker) {
+                    display_data.sparkline = sparkline;
+                }
+            }
+            Message::ToggleMovers(metric) => {
+                self.movers = if self.movers == Some(metric) {
+                    None
+                } else {
+                    Some(metric)
+                };
+                self.sort_ticker_rows();
+            }
+            Message::FetchVolumeAverages => {
+                if let Some(task) = self.volume_average_fetch_task() {
+                    return Some(Action::Fetch(task));
+                }
+            }
+            Message::UpdateVolumeAverage(ticker, average) => {
+                self.mover_tracker.set_daily_average(ticker, average);
+
+                if let Some(&idx) = self.row_index.get(&ticker) {
+                    let row = &mut self.ticker_rows[idx];
+                    row.volume_ratio = self
+                        .mover_tracker
+                        .volume_ratio(&ticker, row.stats.daily_volume.to_f64());
+                }
+            }
             Message::RetryMetadataFetch(venue) => {
                 if self.unavailable_exchanges.contains(&venue)
                     && self.metadata_fetch_state.begin_venue(venue)
@@ -290,6 +424,31 @@ impl TickersTable {
                     self.sort_ticker_rows();
                 }
             }
+            Message::StatsStreamed(exchange, stats) => {
+                let venue = exchange.venue();
+                self.stats_fetch_state.on_streamed(exchange, Instant::now());
+
+                // Open interest, funding and futures spreads only come over REST
+                let stats = stats
+                    .into_iter()
+                    .map(|(ticker, mut new_stats)| {
+                        if let Some(&idx) = self.row_index.get(&ticker) {
+                            let previous = &self.ticker_rows[idx].stats;
+                            new_stats.open_interest =
+                                new_stats.open_interest.or(previous.open_interest);
+                            new_stats.funding_rate =
+                                new_stats.funding_rate.or(previous.funding_rate);
+                            new_stats.spread_bps = new_stats.spread_bps.or(previous.spread_bps);
+                        }
+                        (ticker, new_stats)
+                    })
+                    .collect();
+                self.update_ticker_rows(venue, stats);
+
+                if !self.stats_fetch_state.is_in_flight(venue) {
+                    self.sort_ticker_rows();
+                }
+            }
             Message::StatsFetchFailed(venue, err) => {
                 let can_sort = self.stats_fetch_state.complete_venue(venue);
 
@@ -326,18 +485,146 @@ impl TickersTable {
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        let stats_fetch = iced::time::every(Duration::from_secs(if self.is_shown {
+        let stats_interval = Duration::from_secs(if self.is_shown {
             ACTIVE_UPDATE_INTERVAL
         } else {
             INACTIVE_UPDATE_INTERVAL
-        }))
-        .map(|_| Message::FetchStats);
+        });
+        let stats_fetch = iced::time::every(session_profile().poll_interval(stats_interval))
+            .map(|_| Message::FetchStats);
 
         let debounce_tick =
             iced::time::every(Duration::from_millis(EXCHANGE_TOGGLE_DEBOUNCE_TICK_MS))
                 .map(|_| Message::DebounceExchangeFetchTick);
 
-        Subscription::batch([stats_fetch, debounce_tick])
+        let sparkline_poll = if self.is_shown {
+            iced::time::every(Duration::from_millis(SPARKLINE_POLL_MS))
+                .map(|_| Message::FetchSparklines)
+        } else {
+            Subscription::none()
+        };
+
+        let volume_average_poll = if self.is_shown && self.movers == Some(MoverMetric::VolumeRatio)
+        {
+            iced::time::every(Duration::from_millis(VOLUME_AVERAGE_POLL_MS))
+                .map(|_| Message::FetchVolumeAverages)
+        } else {
+            Subscription::none()
+        };
+
+        let stats_streams = if self.is_shown {
+            Subscription::batch(self.stats_stream_configs().into_iter().map(|config| {
+                Subscription::run_with((self.handles.clone(), config), |data| {
+                    data.0.ticker_stats_stream(&data.1)
+                })
+                .filter_map(streamed_stats_message)
+            }))
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            stats_fetch,
+            stats_streams,
+            debounce_tick,
+            sparkline_poll,
+            volume_average_poll,
+        ])
+    }
+
+    /// One stats stream per market of the selected venues that push them, once their
+    /// tickers are known
+    fn stats_stream_configs(&self) -> Vec<StreamConfig<Vec<TickerInfo>>> {
+        let mut tickers_by_exchange = FxHashMap::<Exchange, Vec<TickerInfo>>::default();
+
+        for ticker_info in self.tickers_info.values().flatten() {
+            let exchange = ticker_info.ticker.exchange;
+            let venue = exchange.venue();
+
+            if exchange.supports_ticker_stats_stream()
+                && self.selected_exchanges.contains(&venue)
+                && !self.unavailable_exchanges.contains(&venue)
+            {
+                tickers_by_exchange
+                    .entry(exchange)
+                    .or_default()
+                    .push(*ticker_info);
+            }
+        }
+
+        tickers_by_exchange
+            .into_iter()
+            .map(|(exchange, tickers)| {
+                StreamConfig::new(tickers, exchange, None, PushFrequency::ServerDefault)
+            })
+            .collect()
+    }
+
+    /// Fetches the daily volume averages still missing, most traded tickers first, so the
+    /// ranking by volume fills in a few tickers at a time
+    fn volume_average_fetch_task(&mut self) -> Option<Task<Message>> {
+        let (fav_rows, rest_rows) = self.listed_rows();
+
+        let mut missing = fav_rows
+            .into_iter()
+            .chain(rest_rows)
+            .filter(|row| {
+                !self.mover_tracker.has_daily_average(&row.ticker)
+                    && !self.volume_average_requested.contains(&row.ticker)
+            })
+            .collect::<Vec<_>>();
+        missing.sort_by_key(|row| std::cmp::Reverse(row.stats.daily_volume));
+
+        let due = missing
+            .into_iter()
+            .filter_map(|row| self.tickers_info.get(&row.ticker).cloned().flatten())
+            .take(VOLUME_AVERAGE_FETCHES_PER_POLL)
+            .collect::<Vec<_>>();
+
+        if due.is_empty() {
+            return None;
+        }
+
+        let tasks = due
+            .into_iter()
+            .map(|ticker_info| {
+                self.volume_average_requested.insert(ticker_info.ticker);
+                fetch_volume_average_task(&self.handles, ticker_info)
+            })
+            .collect::<Vec<_>>();
+
+        Some(Task::batch(tasks))
+    }
+
+    /// Fetches the sparklines of the cards in view that are missing or stale, a few at a time
+    fn sparkline_fetch_task(&mut self) -> Option<Task<Message>> {
+        let now = Instant::now();
+
+        let due = self
+            .visible_tickers()
+            .into_iter()
+            .filter(|ticker| {
+                self.sparkline_requested
+                    .get(ticker)
+                    .is_none_or(|at| now.duration_since(*at) >= SPARKLINE_REFRESH)
+            })
+            .filter_map(|ticker| self.tickers_info.get(&ticker).cloned().flatten())
+            .take(SPARKLINE_FETCHES_PER_POLL)
+            .collect::<Vec<_>>();
+
+        if due.is_empty() {
+            return None;
+        }
+
+        let tasks = due
+            .into_iter()
+            .map(|ticker_info| {
+                self.sparkline_requested.insert(ticker_info.ticker, now);
+                fetch_sparkline_task(&self.handles, ticker_info)
+            })
+            .collect::<Vec<_>>();
+
+        Some(Task::batch(tasks))
     }
 
     fn selected_stats_fetch_task(&mut self) -> Option<Task<Message>> {
@@ -360,11 +647,22 @@ impl TickersTable {
         }
 
         let now = Instant::now();
-        let min_interval = Duration::from_secs(ACTIVE_UPDATE_INTERVAL);
+        let min_interval =
+            session_profile().poll_interval(Duration::from_secs(ACTIVE_UPDATE_INTERVAL));
+        let streamed_interval =
+            session_profile().poll_interval(Duration::from_secs(INACTIVE_UPDATE_INTERVAL));
 
-        let scheduled = self
+        let (streamed, polled): (FxHashSet<_>, FxHashSet<_>) = venues
+            .into_iter()
+            .partition(|venue| self.stats_fetch_state.is_streamed(*venue, now));
+
+        let mut scheduled = self
             .stats_fetch_state
-            .schedule_venues(venues, now, min_interval);
+            .schedule_venues(polled, now, min_interval);
+        scheduled.extend(
+            self.stats_fetch_state
+                .schedule_venues(streamed, now, streamed_interval),
+        );
 
         if scheduled.is_empty() {
             return None;
@@ -380,12 +678,7 @@ impl TickersTable {
 
     fn change_sort_option(&mut self, option: SortOptions) {
         if self.selected_sort_option == option {
-            self.selected_sort_option = match self.selected_sort_option {
-                SortOptions::VolumeDesc => SortOptions::VolumeAsc,
-                SortOptions::VolumeAsc => SortOptions::VolumeDesc,
-                SortOptions::ChangeDesc => SortOptions::ChangeAsc,
-                SortOptions::ChangeAsc => SortOptions::ChangeDesc,
-            };
+            self.selected_sort_option = self.selected_sort_option.reversed();
         } else {
             self.selected_sort_option = option;
         }
@@ -402,11 +695,16 @@ impl TickersTable {
                 self.favorited_tickers.insert(ticker);
             } else {
                 self.favorited_tickers.remove(&ticker);
+
+                if self.favorited_tickers.is_empty() {
+                    self.breadth_filter = None;
+                }
             }
         }
     }
 
     fn update_ticker_rows(&mut self, venue: Venue, stats: HashMap<Ticker, TickerStats>) {
+        let now = UnixMs::now();
         let iter = stats
             .into_iter()
             .filter(|(t, _)| self.tickers_info.contains_key(t) && t.exchange.venue() == venue);
@@ -417,16 +715,28 @@ impl TickersTable {
                 .get(&ticker)
                 .and_then(|info| info.as_ref().map(|ti| ti.min_ticksize));
 
+            self.mover_tracker
+                .record(ticker, now, new_stats.mark_price.to_f64());
+            let mover_change = self.mover_tracker.change(&ticker);
+            let volume_ratio = self
+                .mover_tracker
+                .volume_ratio(&ticker, new_stats.daily_volume.to_f64());
+
             if let Some(&idx) = self.row_index.get(&ticker) {
                 let row = &mut self.ticker_rows[idx];
                 let previous_price = Some(row.stats.mark_price);
                 row.previous_stats = Some(row.stats);
                 row.stats = new_stats;
-
-                self.display_cache.insert(
-                    ticker,
-                    compute_display_data(&ticker, &row.stats, previous_price, precision),
-                );
+                row.mover_change = mover_change;
+                row.volume_ratio = volume_ratio;
+
+                let mut display_data =
+                    compute_display_data(&ticker, &row.stats, previous_price, precision);
+                display_data.sparkline = self
+                    .display_cache
+                    .remove(&ticker)
+                    .and_then(|data| data.sparkline);
+                self.display_cache.insert(ticker, display_data);
             } else {
                 let new_row = TickerRowData {
                     exchange: ticker.exchange,
@@ -434,6 +744,8 @@ impl TickersTable {
                     stats: new_stats,
                     previous_stats: None,
                     is_favorited: self.favorited_tickers.contains(&ticker),
+                    mover_change,
+                    volume_ratio,
                 };
                 self.ticker_rows.push(new_row);
                 let idx = self.ticker_rows.len() - 1;
@@ -448,8 +760,9 @@ impl TickersTable {
     }
 
     fn sort_ticker_rows(&mut self) {
+        let (sort, movers) = (self.selected_sort_option, self.movers);
         self.ticker_rows
-            .sort_unstable_by(|a, b| compare_ticker_rows_by_sort(a, b, self.selected_sort_option));
+            .sort_unstable_by(|a, b| compare_rows(a, b, sort, movers));
         self.rebuild_index();
     }
 
@@ -464,7 +777,7 @@ impl TickersTable {
 impl TickersTable {
     /// Full table view with search, sorting, and filtering options.
     pub fn view(&self, bounds: Size) -> Element<'_, Message> {
-        let (fav_rows, rest_rows) = self.filtered_rows(&self.search_query, None);
+        let (fav_rows, rest_rows) = self.listed_rows();
         let fav_n = fav_rows.len();
         let rest_n = rest_rows.len();
         let has_any_favorites = !self.favorited_tickers.is_empty();
@@ -473,18 +786,7 @@ impl TickersTable {
         let sort_and_filter = self.sort_and_filter_col(fav_n, rest_n);
 
         let sep_block_height = self.separator_height(fav_n);
-        let header_offset = self.header_offset();
-
-        let virtual_list_cfg = VirtualListConfig {
-            row_height: TICKER_CARD_HEIGHT,
-            header_offset,
-            overscan: OVERSCAN_BUFFER as usize,
-            gap: if self.show_favorites {
-                Some((fav_n, sep_block_height))
-            } else {
-                None
-            },
-        };
+        let virtual_list_cfg = self.virtual_list_config(fav_n);
         let total_rows = fav_n + rest_n;
         let win = virtual_list_cfg.window(self.scroll_offset.y, bounds.height, total_rows);
 
@@ -502,6 +804,9 @@ impl TickersTable {
             .padding(padding::right(8))
             .width(Length::Fill);
 
+        if let Some(breadth) = self.favorites_breadth() {
+            content = content.push(self.breadth_bar(breadth));
+        }
         if self.show_sort_options {
             content = content.push(sort_and_filter);
         }
@@ -518,6 +823,54 @@ impl TickersTable {
         .into()
     }
 
+    /// Favorited and other rows as listed, after the search, filters and breadth filter
+    fn listed_rows(&self) -> (Vec<&TickerRowData>, Vec<&TickerRowData>) {
+        let (mut fav_rows, mut rest_rows) = self.filtered_rows(&self.search_query, None);
+        if let Some(direction) = self.breadth_filter {
+            let matches = |row: &&TickerRowData| {
+                row.is_favorited && direction.matches(row.stats.daily_price_chg)
+            };
+            fav_rows.retain(matches);
+            rest_rows.retain(matches);
+        }
+        (fav_rows, rest_rows)
+    }
+
+    fn virtual_list_config(&self, fav_n: usize) -> VirtualListConfig {
+        VirtualListConfig {
+            row_height: TICKER_CARD_HEIGHT,
+            header_offset: self.header_offset(),
+            overscan: OVERSCAN_BUFFER as usize,
+            gap: if self.show_favorites {
+                Some((fav_n, self.separator_height(fav_n)))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Tickers of the cards the full view renders at the current scroll position
+    fn visible_tickers(&self) -> Vec<Ticker> {
+        let (fav_rows, rest_rows) = self.listed_rows();
+        let fav_n = fav_rows.len();
+
+        let vcfg = self.virtual_list_config(fav_n);
+        let win = vcfg.window(
+            self.scroll_offset.y,
+            self.viewport_height.unwrap_or(FALLBACK_VIEWPORT_HEIGHT),
+            fav_n + rest_rows.len(),
+        );
+
+        (win.first..win.last)
+            .filter_map(|idx| match vcfg.virtual_to_item(idx) {
+                VirtualItemIndex::Row(data_idx) if data_idx < fav_n => Some(fav_rows[data_idx]),
+                VirtualItemIndex::Row(data_idx) => rest_rows.get(data_idx - fav_n).copied(),
+                VirtualItemIndex::Gap => None,
+            })
+            .map(|row| row.ticker)
+            .collect()
+    }
+
     fn virtual_list<'a>(
         &'a self,
         vcfg: &VirtualListConfig,
@@ -707,29 +1060,11 @@ impl TickersTable {
     }
 
     fn sort_and_filter_col(&self, fav_n: usize, rest_n: usize) -> Element<'_, Message> {
-        let volume_sort_button = self.sort_btn("Volume", SortOptions::VolumeAsc);
-        let volume_sort = volume_sort_button.style(move |theme, status| {
-            style::button::transparent(
-                theme,
-                status,
-                matches!(
-                    self.selected_sort_option,
-                    SortOptions::VolumeAsc | SortOptions::VolumeDesc
-                ),
-            )
-        });
-
-        let change_sort_button = self.sort_btn("Change", SortOptions::ChangeAsc);
-        let daily_change = change_sort_button.style(move |theme, status| {
-            style::button::transparent(
-                theme,
-                status,
-                matches!(
-                    self.selected_sort_option,
-                    SortOptions::ChangeAsc | SortOptions::ChangeDesc
-                ),
-            )
-        });
+        let volume_sort = self.sort_btn("Volume", SortOptions::VolumeAsc);
+        let daily_change = self.sort_btn("Change", SortOptions::ChangeAsc);
+        let open_interest_sort = self.sort_btn("OI", SortOptions::OpenInterestDesc);
+        let funding_sort = self.sort_btn("Funding", SortOptions::FundingDesc);
+        let spread_sort = self.sort_btn("Spread", SortOptions::SpreadAsc);
 
         let spot_market_button = self.market_filter_btn("Spot", MarketKind::Spot);
         let linear_markets_btn = self.market_filter_btn("Linear", MarketKind::LinearPerps);
@@ -743,6 +1078,52 @@ impl TickersTable {
             col.spacing(4)
         };
 
+        let screener = {
+            let input = |field: ScreenerField| {
+                let value = &self.screener_inputs[field as usize];
+                let is_valid = value.trim().is_empty() || ScreenerField::parse(value).is_some();
+
+                text_input(field.placeholder(), value)
+                    .on_input(move |input| Message::ScreenerInput(field, input))
+                    .style(move |theme, status| {
+                        style::validated_text_input(theme, status, is_valid)
+                    })
+                    .width(Length::Fill)
+            };
+
+            let clear_btn = button(text("Clear"))
+                .on_press_maybe(self.screener.is_active().then_some(Message::ClearScreener))
+                .style(|theme, status| style::button::transparent(theme, status, false));
+
+            column![
+                row![text("Screener"), space::horizontal(), clear_btn].align_y(Vertical::Center),
+                row![
+                    input(ScreenerField::MinVolume),
+                    input(ScreenerField::MinAbsChange)
+                ]
+                .spacing(4),
+                row![
+                    input(ScreenerField::MinPrice),
+                    input(ScreenerField::MaxPrice)
+                ]
+                .spacing(4),
+            ]
+            .spacing(4)
+        };
+
+        let movers = row![text("Movers"), space::horizontal()]
+            .extend(MoverMetric::ALL.into_iter().map(|metric| {
+                let is_selected = self.movers == Some(metric);
+                button(text(metric.to_string()))
+                    .on_press(Message::ToggleMovers(metric))
+                    .style(move |theme, status| {
+                        style::button::transparent(theme, status, is_selected)
+                    })
+                    .into()
+            }))
+            .spacing(4)
+            .align_y(Vertical::Center);
+
         let total = rest_n + fav_n;
 
         column![
@@ -761,6 +1142,15 @@ impl TickersTable {
                     .height(Length::Shrink),
             ]
             .spacing(4),
+            row![
+                Space::new().width(Length::Fill).height(Length::Shrink),
+                open_interest_sort,
+                funding_sort,
+                spread_sort,
+                Space::new().width(Length::Fill).height(Length::Shrink),
+            ]
+            .spacing(4),
+            movers,
             rule::horizontal(1.0).style(style::split_ruler),
             row![
                 spot_market_button.width(Length::Fill),
@@ -771,6 +1161,8 @@ impl TickersTable {
             rule::horizontal(1.0).style(style::split_ruler),
             exchange_filters,
             rule::horizontal(1.0).style(style::split_ruler),
+            screener,
+            rule::horizontal(1.0).style(style::split_ruler),
             text(if total == 0 {
                 "No tickers match filters".to_string()
             } else {
@@ -794,16 +1186,16 @@ impl TickersTable {
         .into()
     }
 
+    /// Sorts by the column of `sort_option`, in its direction when the column isn't the
+    /// selected one already
     fn sort_btn<'a>(
         &'a self,
         label: &'a str,
         sort_option: SortOptions,
     ) -> Button<'a, Message, Theme, Renderer> {
-        let (asc_variant, desc_variant) = match sort_option {
-            SortOptions::VolumeAsc => (SortOptions::VolumeAsc, SortOptions::VolumeDesc),
-            SortOptions::ChangeAsc => (SortOptions::ChangeAsc, SortOptions::ChangeDesc),
-            _ => (sort_option, sort_option), // fallback
-        };
+        let (_, desc_variant) = sort_option.pair();
+        let is_selected =
+            self.movers.is_none() && self.selected_sort_option.same_column(sort_option);
 
         button(
             row![
@@ -820,11 +1212,21 @@ impl TickersTable {
             .spacing(4)
             .align_y(Vertical::Center),
         )
-        .on_press(Message::ChangeSortOption(asc_variant))
+        .on_press(Message::ChangeSortOption(if is_selected {
+            self.selected_sort_option
+        } else {
+            sort_option
+        }))
+        .style(move |theme, status| style::button::transparent(theme, status, is_selected))
     }
 
     fn header_offset(&self) -> f32 {
         TOP_BAR_HEIGHT
+            + if self.favorites_breadth().is_some() {
+                BREADTH_BAR_HEIGHT
+            } else {
+                0.0
+            }
             + if self.show_sort_options {
                 SORT_AND_FILTER_HEIGHT
             } else {
@@ -832,6 +1234,61 @@ impl TickersTable {
             }
     }
 
+    fn favorites_breadth(&self) -> Option<Breadth> {
+        Breadth::from_rows(self.ticker_rows.iter().filter(|row| row.is_favorited))
+    }
+
+    /// Up/down counts, average change and total volume of the favorites, the counts
+    /// filter the table to the favorites that moved that way
+    fn breadth_bar(&self, breadth: Breadth) -> Element<'_, Message> {
+        let direction_btn = |count: usize, direction: PriceChange| {
+            let label = match direction {
+                PriceChange::Increased => format!("{count} up"),
+                PriceChange::Decreased => format!("{count} down"),
+            };
+            let is_active = self.breadth_filter == Some(direction);
+
+            button(text(label).style(move |theme: &Theme| {
+                let palette = theme.extended_palette();
+                iced::widget::text::Style {
+                    color: Some(match direction {
+                        PriceChange::Increased => palette.success.base.color,
+                        PriceChange::Decreased => palette.danger.base.color,
+                    }),
+                }
+            }))
+            .padding(padding::left(6).right(6).top(2).bottom(2))
+            .on_press(Message::FilterBreadth(direction))
+            .style(move |theme, status| style::button::transparent(theme, status, is_active))
+        };
+
+        let summary = tooltip_with_delay(
+            row![
+                text(data::util::pct_change(breadth.avg_change)),
+                text(data::util::currency_abbr(breadth.total_volume as f32)),
+            ]
+            .spacing(8),
+            Some("Average 24h change and total 24h volume of favorites"),
+            iced::widget::tooltip::Position::Bottom,
+            Duration::from_millis(500),
+        );
+
+        container(
+            row![
+                icon_text(Icon::StarFilled, 12),
+                direction_btn(breadth.advancing, PriceChange::Increased),
+                direction_btn(breadth.declining, PriceChange::Decreased),
+                Space::new().width(Length::Fill).height(Length::Shrink),
+                summary,
+            ]
+            .spacing(4)
+            .align_y(Vertical::Center),
+        )
+        .height(Length::Fixed(BREADTH_BAR_HEIGHT - 8.0))
+        .padding(padding::left(4).right(4))
+        .into()
+    }
+
     fn top_bar(&self) -> Element<'_, Message> {
         row![
             text_input("Search for a ticker...", &self.search_query)
@@ -877,8 +1334,10 @@ impl TickersTable {
     ) -> (Vec<&'a TickerRowData>, Vec<&'a TickerRowData>) {
         let matches_market =
             |row: &TickerRowData| self.selected_markets.contains(&row.ticker.market_type());
-        let matches_exchange =
-            |row: &TickerRowData| self.selected_exchanges.contains(&row.exchange.venue());
+        let matches_exchange = |row: &TickerRowData| {
+            self.selected_exchanges.contains(&row.exchange.venue())
+                && self.screener.matches(&row.stats)
+        };
 
         // Collect fav_rows with search ranks
         let mut fav_rows: Vec<_> = if self.show_favorites {
@@ -902,7 +1361,7 @@ impl TickersTable {
         fav_rows.sort_by(|(a, ra), (b, rb)| {
             (ra.bucket, ra.pos)
                 .cmp(&(rb.bucket, rb.pos))
-                .then_with(|| compare_ticker_rows_by_sort(a, b, self.selected_sort_option))
+                .then_with(|| compare_rows(a, b, self.selected_sort_option, self.movers))
                 .then_with(|| ra.len.cmp(&rb.len))
         });
         let fav_rows: Vec<&TickerRowData> = fav_rows.into_iter().map(|(row, _)| row).collect();
@@ -924,7 +1383,7 @@ impl TickersTable {
         rest_rows.sort_by(|(a, ra), (b, rb)| {
             (ra.bucket, ra.pos)
                 .cmp(&(rb.bucket, rb.pos))
-                .then_with(|| compare_ticker_rows_by_sort(a, b, self.selected_sort_option))
+                .then_with(|| compare_rows(a, b, self.selected_sort_option, self.movers))
                 .then_with(|| ra.len.cmp(&rb.len))
         });
         let rest_rows: Vec<&TickerRowData> = rest_rows.into_iter().map(|(row, _)| row).collect();
@@ -939,6 +1398,17 @@ impl TickersTable {
         display_data: &'a TickerDisplayData,
         is_fav: bool,
     ) -> Element<'a, Message> {
+        let value = match self.movers {
+            Some(metric) => self
+                .row_index
+                .get(ticker)
+                .map(|&idx| self.ticker_rows[idx].mover_display(metric))
+                .unwrap_or_default(),
+            None => display_data
+                .sorted_value(self.selected_sort_option)
+                .to_string(),
+        };
+
         if let Some(selected_ticker) = &self.expand_ticker_card {
             let selected_exchange = selected_ticker.exchange;
             if ticker == selected_ticker && exchange == selected_exchange {
@@ -946,16 +1416,17 @@ impl TickersTable {
                     .style(style::ticker_card)
                     .into()
             } else {
-                Self::ticker_card(ticker, display_data)
+                Self::ticker_card(ticker, display_data, value)
             }
         } else {
-            Self::ticker_card(ticker, display_data)
+            Self::ticker_card(ticker, display_data, value)
         }
     }
 
     fn ticker_card<'a>(
         ticker: &Ticker,
         display_data: &'a TickerDisplayData,
+        value: String,
     ) -> Element<'a, Message> {
         let color_column = container(column![])
             .height(Length::Fill)
@@ -1003,29 +1474,38 @@ impl TickersTable {
             }
         };
 
+        let sparkline: Element<'a, Message> = match &display_data.sparkline {
+            Some(sparkline) => canvas(SparklineCanvas { sparkline })
+                .width(Length::Fixed(SPARKLINE_SIZE.width))
+                .height(Length::Fixed(SPARKLINE_SIZE.height))
+                .into(),
+            None => Space::new()
+                .width(Length::Fixed(SPARKLINE_SIZE.width))
+                .height(Length::Fixed(SPARKLINE_SIZE.height))
+                .into(),
+        };
+
         container(
             button(
                 row![
                     color_column,
-                    column![
-                        row![
+                    row![
+                        column![
                             row![icon, text(display_ticker),]
                                 .spacing(2)
                                 .align_y(alignment::Vertical::Center),
-                            Space::new().width(Length::Fill).height(Length::Shrink),
-                            text(&display_data.daily_change_pct),
-                        ]
-                        .spacing(4)
-                        .align_y(alignment::Vertical::Center),
-                        row![
                             price_display,
-                            Space::new().width(Length::Fill).height(Length::Shrink),
-                            text(&display_data.volume_display),
                         ]
                         .spacing(4),
+                        Space::new().width(Length::Fill).height(Length::Shrink),
+                        sparkline,
+                        column![text(&display_data.daily_change_pct), text(value),]
+                            .spacing(4)
+                            .align_x(Horizontal::Right),
                     ]
                     .padding(padding::left(8).right(8).bottom(4).top(4))
-                    .spacing(4),
+                    .spacing(8)
+                    .align_y(Alignment::Center),
                 ]
                 .align_y(Alignment::Center),
             )
@@ -1070,7 +1550,7 @@ impl TickersTable {
                 text(
                     ticker_str
                         + " "
-                        + &market.to_string()
+                        + market.to_string().as_str()
                         + match market {
                             MarketKind::Spot => "",
                             MarketKind::LinearPerps | MarketKind::InversePerps => " Perp",
@@ -1096,6 +1576,25 @@ impl TickersTable {
                         text(&display_data.volume_display),
                     ],
                 ]
+                .extend(
+                    [
+                        ("Open Interest: ", &display_data.open_interest_display),
+                        ("Funding Rate: ", &display_data.funding_display),
+                        ("Spread: ", &display_data.spread_display),
+                    ]
+                    .into_iter()
+                    .filter_map(|(label, value)| {
+                        let value = value.as_deref()?;
+                        Some(
+                            row![
+                                text(label).size(crate::style::text_size::SMALL),
+                                Space::new().width(Length::Fill).height(Length::Shrink),
+                                text(value),
+                            ]
+                            .into(),
+                        )
+                    }),
+                )
                 .spacing(2)
             )
             .style(|theme: &Theme| {
@@ -1111,9 +1610,21 @@ impl TickersTable {
                 init_content_btn(ContentKind::FootprintChart, *ticker, 180.0),
                 init_content_btn(ContentKind::CandlestickChart, *ticker, 180.0),
                 init_content_btn(ContentKind::ComparisonChart, *ticker, 180.0),
+                init_content_btn(ContentKind::SpreadChart, *ticker, 180.0),
                 init_content_btn(ContentKind::TimeAndSales, *ticker, 160.0),
                 init_content_btn(ContentKind::Ladder, *ticker, 160.0),
             ]
+            .extend(
+                PanePreset::ALL
+                    .into_iter()
+                    .filter(|preset| preset.is_available(market))
+                    .map(|preset| {
+                        button(text(preset.to_string()).align_x(Horizontal::Center))
+                            .on_press(Message::PresetSelected(*ticker, preset))
+                            .width(Length::Fixed(180.0))
+                            .into()
+                    }),
+            )
             .width(Length::Fill)
             .spacing(2)
         ]
@@ -1673,6 +2184,138 @@ fn fetch_ticker_stats_task(
     })
 }
 
+fn streamed_stats_message(event: exchange::Event) -> Option<Message> {
+    match event {
+        exchange::Event::TickerStatsReceived(exchange, stats) => {
+            Some(Message::StatsStreamed(exchange, stats))
+        }
+        _ => None,
+    }
+}
+
+/// Ranks by the movers metric while one is picked, the sort option breaking ties
+fn compare_rows(
+    a: &TickerRowData,
+    b: &TickerRowData,
+    sort: SortOptions,
+    movers: Option<MoverMetric>,
+) -> Ordering {
+    match movers {
+        Some(metric) => compare_ticker_rows_by_mover(a, b, metric)
+            .then_with(|| compare_ticker_rows_by_sort(a, b, sort)),
+        None => compare_ticker_rows_by_sort(a, b, sort),
+    }
+}
+
+fn fetch_volume_average_task(handles: &AdapterHandles, ticker_info: TickerInfo) -> Task<Message> {
+    let ticker = ticker_info.ticker;
+    let now = UnixMs::now();
+    // One more for the day that's still forming
+    let range = (
+        now.saturating_sub((VOLUME_AVERAGE_DAYS + 1) * Timeframe::D1.to_milliseconds()),
+        now,
+    );
+
+    let handles = handles.clone();
+    let fetch = async move {
+        handles
+            .fetch_klines(ticker_info, Timeframe::D1, Some(range))
+            .await
+    };
+
+    Task::perform(fetch, move |result| match result {
+        Ok(klines) => Message::UpdateVolumeAverage(
+            ticker,
+            average_daily_volume(
+                &klines,
+                ticker.market_type(),
+                volume_size_unit() == SizeUnit::Quote,
+            ),
+        ),
+        Err(err) => {
+            log::debug!("Daily volume fetch failed for {ticker:?}: {err}");
+            Message::UpdateVolumeAverage(ticker, None)
+        }
+    })
+}
+
+fn fetch_sparkline_task(handles: &AdapterHandles, ticker_info: TickerInfo) -> Task<Message> {
+    let ticker = ticker_info.ticker;
+    let now = UnixMs::now();
+    let range = (now.saturating_sub(SPARKLINE_WINDOW_MS), now);
+
+    let handles = handles.clone();
+    let fetch = async move {
+        handles
+            .fetch_klines(ticker_info, SPARKLINE_TIMEFRAME, Some(range))
+            .await
+    };
+
+    Task::perform(fetch, move |result| match result {
+        Ok(klines) => Message::UpdateSparkline(ticker, Sparkline::from_klines(&klines)),
+        Err(err) => {
+            // Left blank until it's due again, the card works without it
+            log::debug!("Sparkline fetch failed for {ticker:?}: {err}");
+            Message::UpdateSparkline(ticker, None)
+        }
+    })
+}
+
+/// A ticker card's line of the last day's closes, colored by which way they went
+struct SparklineCanvas<'a> {
+    sparkline: &'a Sparkline,
+}
+
+impl canvas::Program<Message> for SparklineCanvas<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let palette = theme.extended_palette();
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        let points = &self.sparkline.points;
+        let step = bounds.width / points.len().saturating_sub(1).max(1) as f32;
+        // Kept off the edges so the stroke isn't clipped at the high and low
+        let (top, height) = (1.0, bounds.height - 2.0);
+
+        let line = canvas::Path::new(|builder| {
+            for (index, value) in points.iter().enumerate() {
+                let point = Point::new(index as f32 * step, top + (1.0 - value) * height);
+                if index == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+
+        let color = match self.sparkline.direction {
+            Some(PriceChange::Increased) => palette.success.base.color,
+            Some(PriceChange::Decreased) => palette.danger.base.color,
+            None => palette.background.strong.color,
+        };
+        frame.stroke(
+            &line,
+            canvas::Stroke::with_color(
+                canvas::Stroke {
+                    width: 1.0,
+                    ..canvas::Stroke::default()
+                },
+                color,
+            ),
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
+
 fn fetch_metadata_task(handles: &AdapterHandles, venue: Venue) -> Task<Message> {
     let markets_to_fetch = available_markets(venue);
     let handles = handles.clone();
@@ -1703,6 +2346,8 @@ struct StatsFetchState {
     in_flight_venues: FxHashSet<Venue>,
     last_started_at: FxHashMap<Venue, Instant>,
     force_refresh_venues: FxHashSet<Venue>,
+    /// Last push from each market's stats stream
+    streamed_at: FxHashMap<Exchange, Instant>,
     loading_phase: u8,
 }
 
@@ -1713,6 +2358,7 @@ impl Default for StatsFetchState {
             in_flight_venues: FxHashSet::default(),
             last_started_at: FxHashMap::default(),
             force_refresh_venues: FxHashSet::default(),
+            streamed_at: FxHashMap::default(),
             loading_phase: 0,
         }
     }
@@ -1792,6 +2438,19 @@ impl StatsFetchState {
         empty
     }
 
+    fn on_streamed(&mut self, exchange: Exchange, now: Instant) {
+        self.streamed_at.insert(exchange, now);
+    }
+
+    /// Returns true when every market of this venue had its stats pushed lately.
+    fn is_streamed(&self, venue: Venue, now: Instant) -> bool {
+        available_markets(venue).iter().all(|market| {
+            Exchange::from_venue_and_market(venue, *market)
+                .and_then(|exchange| self.streamed_at.get(&exchange))
+                .is_some_and(|at| now.duration_since(*at) < STREAMED_STATS_STALE)
+        })
+    }
+
     /// Returns true when this venue currently has a running stats fetch.
     fn is_in_flight(&self, venue: Venue) -> bool {
         self.in_flight_venues.contains(&venue)
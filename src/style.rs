@@ -30,6 +30,40 @@ pub mod text_size {
     pub const EMPHASIS: f32 = BODY + 1.0;
 }
 
+/// Sizing helpers for canvas content drawn at a window's effective scale factor
+/// (OS scale factor times the interface scale).
+///
+/// Canvas geometry is laid out in logical pixels, so on fractional scales a `1.0` stroke or an
+/// 11px label ends up straddling physical pixels. These snap such values to whole device pixels.
+pub mod dpi {
+    /// Smallest area for pointer interactions, in physical pixels
+    const MIN_HIT_TARGET_PX: f32 = 4.0;
+
+    /// Rounds a logical size to the nearest whole number of physical pixels, never below one
+    pub fn snap(logical: f32, scale_factor: f32) -> f32 {
+        if scale_factor <= 0.0 {
+            return logical;
+        }
+        (logical * scale_factor).round().max(1.0) / scale_factor
+    }
+
+    pub fn stroke_width(logical: f32, scale_factor: f32) -> f32 {
+        snap(logical, scale_factor)
+    }
+
+    pub fn text_size(logical: f32, scale_factor: f32) -> f32 {
+        snap(logical, scale_factor)
+    }
+
+    /// Keeps hit areas from shrinking below a usable physical size when the interface is scaled down
+    pub fn hit_target(logical: f32, scale_factor: f32) -> f32 {
+        if scale_factor <= 0.0 {
+            return logical;
+        }
+        logical.max(MIN_HIT_TARGET_PX / scale_factor)
+    }
+}
+
 /// Icon glyph code points for the `icons` font (`assets/fonts/icons.ttf`).
 /// Values represent each glyph's `code` in `assets/fonts/fontello.json`.
 /// Enum variant names may differ from Fontello CSS names.
@@ -88,6 +122,7 @@ pub fn venue_icon(venue: exchange::adapter::Venue) -> Icon {
         exchange::adapter::Venue::Hyperliquid => Icon::HyperliquidLogo,
         exchange::adapter::Venue::Okex => Icon::OkexLogo,
         exchange::adapter::Venue::Mexc => Icon::MexcLogo,
+        exchange::adapter::Venue::Plugin => Icon::ExternalLink,
     }
 }
 
@@ -445,17 +480,27 @@ pub fn pane_grid(theme: &Theme) -> widget::pane_grid::Style {
     }
 }
 
-pub fn pane_title_bar(theme: &Theme) -> Style {
+pub fn pane_title_bar(theme: &Theme, accent: Option<Color>) -> Style {
     let palette = theme.extended_palette();
 
     Style {
         background: {
-            if palette.is_dark {
+            if let Some(accent) = accent {
+                Some(accent.scale_alpha(0.24).into())
+            } else if palette.is_dark {
                 Some(palette.background.weak.color.scale_alpha(0.2).into())
             } else {
                 Some(palette.background.strong.color.scale_alpha(0.2).into())
             }
         },
+        border: match accent {
+            Some(accent) => Border {
+                color: accent.scale_alpha(0.6),
+                width: 1.0,
+                ..Border::default()
+            },
+            None => Border::default(),
+        },
         ..Default::default()
     }
 }
@@ -491,6 +536,33 @@ pub fn pane_background(theme: &Theme, is_focused: bool) -> Style {
     }
 }
 
+pub const PANE_TINTS: [Color; 6] = [
+    Color::from_rgb(0.86, 0.30, 0.30),
+    Color::from_rgb(0.90, 0.62, 0.24),
+    Color::from_rgb(0.86, 0.82, 0.30),
+    Color::from_rgb(0.32, 0.78, 0.48),
+    Color::from_rgb(0.30, 0.56, 0.90),
+    Color::from_rgb(0.66, 0.42, 0.88),
+];
+
+pub fn pane_tint(tint: Color) -> Style {
+    Style {
+        background: Some(tint.scale_alpha(0.06).into()),
+        ..Default::default()
+    }
+}
+
+pub fn pane_watermark(theme: &Theme, accent: Option<Color>) -> iced::widget::text::Style {
+    let palette = theme.extended_palette();
+
+    iced::widget::text::Style {
+        color: Some(match accent {
+            Some(accent) => accent.scale_alpha(0.12),
+            None => palette.background.base.text.scale_alpha(0.06),
+        }),
+    }
+}
+
 // Modals
 pub fn chart_modal(theme: &Theme) -> Style {
     let palette = theme.extended_palette();
@@ -689,6 +761,27 @@ pub fn ticker_card_bar(theme: &Theme, color_alpha: f32) -> Style {
     }
 }
 
+/// Green for positive correlation, red for negative, stronger the closer it is to ±1
+pub fn correlation_cell(theme: &Theme, correlation: Option<f32>) -> Style {
+    let palette = theme.extended_palette();
+
+    let background = match correlation {
+        Some(value) if value >= 0.0 => palette.success.strong.color.scale_alpha(value),
+        Some(value) => palette.danger.strong.color.scale_alpha(-value),
+        None => palette.background.weak.color,
+    };
+
+    Style {
+        text_color: Some(palette.background.base.text),
+        background: Some(background.into()),
+        border: Border {
+            radius: 2.0.into(),
+            ..Border::default()
+        },
+        ..Default::default()
+    }
+}
+
 // Scrollable
 pub fn scroll_bar(theme: &Theme, status: widget::scrollable::Status) -> widget::scrollable::Style {
     let palette = theme.extended_palette();
@@ -44,6 +44,81 @@ pub trait SeriesLike {
     fn points(&self) -> &[(u64, f32)];
     fn color(&self) -> iced::Color;
     fn ticker_info(&self) -> &TickerInfo;
+
+    fn legend_label(&self) -> String {
+        self.ticker_info().ticker.symbol_and_exchange_string()
+    }
+}
+
+/// How series values are mapped onto the y axis
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ValueScale {
+    /// Change relative to the rebase point, in percent
+    #[default]
+    PercentChange,
+    /// Change relative to the rebase point on a log scale, labeled in percent
+    LogChange,
+    /// Standard deviations from the mean of the visible window
+    ZScore,
+    /// Raw series values, for series that are already comparable like spreads
+    Absolute,
+}
+
+/// What a series is measured against within the visible window
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Baseline {
+    /// Value at the rebase point, the left edge of the window unless anchored elsewhere
+    pub base: f32,
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+impl ValueScale {
+    /// Maps `y` against the series' `baseline`, `None` if it can't be expressed
+    fn value(self, y: f32, baseline: Baseline) -> Option<f32> {
+        let base = baseline.base;
+        match self {
+            ValueScale::PercentChange if base == 0.0 => None,
+            ValueScale::PercentChange => Some(((y / base) - 1.0) * 100.0),
+            ValueScale::LogChange if base <= 0.0 || y <= 0.0 => None,
+            ValueScale::LogChange => Some((y / base).ln() * 100.0),
+            ValueScale::ZScore if baseline.std_dev <= f32::EPSILON => None,
+            ValueScale::ZScore => Some((y - baseline.mean) / baseline.std_dev),
+            ValueScale::Absolute => Some(y),
+        }
+    }
+
+    /// The series value `val` was mapped from, the inverse of [`ValueScale::value`]
+    fn invert(self, val: f32, baseline: Baseline) -> Option<f32> {
+        let base = baseline.base;
+        match self {
+            ValueScale::PercentChange => Some(base * (1.0 + val / 100.0)),
+            ValueScale::LogChange if base <= 0.0 => None,
+            ValueScale::LogChange => Some(base * (val / 100.0).exp()),
+            ValueScale::ZScore => Some(baseline.mean + val * baseline.std_dev),
+            ValueScale::Absolute => Some(val),
+        }
+    }
+
+    fn format(self, val: f32, step: f32, show_decimals: bool) -> String {
+        match self {
+            ValueScale::PercentChange => format_pct(val, step, show_decimals),
+            // Evenly spaced in log space, so the labels read as the actual change
+            ValueScale::LogChange => {
+                format_pct(((val / 100.0).exp() - 1.0) * 100.0, step, show_decimals)
+            }
+            ValueScale::ZScore => format!("{}σ", format_value(val, step, show_decimals)),
+            ValueScale::Absolute => format_value(val, step, show_decimals),
+        }
+    }
+
+    /// Scales whose zero line is meaningful and kept in view
+    fn keeps_zero_in_view(self) -> bool {
+        match self {
+            ValueScale::PercentChange | ValueScale::LogChange | ValueScale::ZScore => true,
+            ValueScale::Absolute => false,
+        }
+    }
 }
 
 impl SeriesLike for Series {
@@ -134,6 +209,19 @@ fn format_pct(val: f32, step: f32, show_decimals: bool) -> String {
     }
 }
 
+fn format_value(val: f32, step: f32, show_decimals: bool) -> String {
+    let mut decimals = if step >= 1.0 {
+        0
+    } else {
+        (-step.log10()).ceil() as usize
+    };
+    if show_decimals {
+        decimals += 1;
+    }
+
+    format!("{val:.decimals$}")
+}
+
 fn time_tick_candidates() -> &'static [u64] {
     const S: u64 = 1_000;
     const M: u64 = 60 * S;
@@ -325,7 +413,45 @@ pub mod domain {
         Some((left, right))
     }
 
-    pub fn pct_domain(series: &[&[(u64, f32)]], min_x: u64, max_x: u64) -> Option<(f32, f32)> {
+    /// Baseline of `points` in the window, rebased at `anchor` or the left edge.
+    /// `None` if the series has nothing at or after the rebase point
+    pub fn baseline(
+        points: &[(u64, f32)],
+        min_x: u64,
+        max_x: u64,
+        anchor: Option<u64>,
+    ) -> Option<super::Baseline> {
+        let base = interpolate_y_at(points, anchor.unwrap_or(min_x))?;
+
+        let (mut count, mut sum, mut sum_sq) = (0usize, 0.0f64, 0.0f64);
+        for (_, y) in points.iter().filter(|(x, _)| *x >= min_x && *x <= max_x) {
+            count += 1;
+            sum += f64::from(*y);
+            sum_sq += f64::from(*y) * f64::from(*y);
+        }
+
+        let (mean, std_dev) = if count > 0 {
+            let mean = sum / count as f64;
+            let variance = (sum_sq / count as f64 - mean * mean).max(0.0);
+            (mean as f32, variance.sqrt() as f32)
+        } else {
+            (base, 0.0)
+        };
+
+        Some(super::Baseline {
+            base,
+            mean,
+            std_dev,
+        })
+    }
+
+    pub fn value_domain(
+        series: &[&[(u64, f32)]],
+        min_x: u64,
+        max_x: u64,
+        scale: super::ValueScale,
+        anchor: Option<u64>,
+    ) -> Option<(f32, f32)> {
         let mut min_pct = f32::INFINITY;
         let mut max_pct = f32::NEG_INFINITY;
         let mut any = false;
@@ -335,15 +461,16 @@ pub mod domain {
                 continue;
             }
 
-            let y0 = interpolate_y_at(pts, min_x).unwrap_or(0.0);
-            if y0 == 0.0 {
+            let Some(baseline) = baseline(pts, min_x, max_x, anchor) else {
                 continue;
-            }
+            };
 
             let mut has_visible = false;
             for (_x, y) in pts.iter().filter(|(x, _)| *x >= min_x && *x <= max_x) {
+                let Some(pct) = scale.value(*y, baseline) else {
+                    continue;
+                };
                 has_visible = true;
-                let pct = ((*y / y0) - 1.0) * 100.0;
                 if pct < min_pct {
                     min_pct = pct;
                 }
@@ -354,11 +481,14 @@ pub mod domain {
 
             if has_visible {
                 any = true;
-                if 0.0 < min_pct {
-                    min_pct = 0.0;
-                }
-                if 0.0 > max_pct {
-                    max_pct = 0.0;
+                // Relative scales always keep their zero line in view
+                if scale.keeps_zero_in_view() {
+                    if 0.0 < min_pct {
+                        min_pct = 0.0;
+                    }
+                    if 0.0 > max_pct {
+                        max_pct = 0.0;
+                    }
                 }
             }
         }
@@ -368,8 +498,13 @@ pub mod domain {
         }
 
         if (max_pct - min_pct).abs() < f32::EPSILON {
-            min_pct -= 1.0;
-            max_pct += 1.0;
+            let bump = match scale {
+                super::ValueScale::PercentChange | super::ValueScale::LogChange => 1.0,
+                super::ValueScale::ZScore => 0.5,
+                super::ValueScale::Absolute => (min_pct.abs() * 0.01).max(f32::EPSILON),
+            };
+            min_pct -= bump;
+            max_pct += bump;
         }
 
         let span = (max_pct - min_pct).max(1e-6);
@@ -1,10 +1,13 @@
 use crate::style;
 use crate::widget::chart::SeriesLike;
+use crate::widget::chart::ValueScale;
 use crate::widget::chart::Zoom;
 use crate::widget::chart::domain;
+use crate::widget::chart::{Baseline, domain::baseline};
 
 use data::UserTimezone;
-use exchange::{TickerInfo, Timeframe};
+use exchange::TickerInfo;
+use exchange::unit::Price;
 
 use iced::advanced::widget::tree::{self, Tree};
 use iced::advanced::{self, Clipboard, Layout, Shell, Widget, layout, renderer};
@@ -47,7 +50,14 @@ pub enum LineComparisonEvent {
     PanChanged(f32),
     SeriesCog(TickerInfo),
     SeriesRemove(TickerInfo),
+    /// Legend buttons of the derived series at that index
+    DerivedCog(usize),
+    DerivedRemove(usize),
     XAxisDoubleClick,
+    /// Double click on the plot, series are rebased at that time
+    AnchorChanged(u64),
+    /// Right click on the plot, what the legend reads there as text
+    LegendValues(String),
 }
 
 struct State {
@@ -88,30 +98,58 @@ impl State {
 
 pub struct LineComparison<'a, S> {
     series: &'a [S],
+    /// Lines computed from the series, drawn and listed after them
+    derived: Vec<&'a dyn SeriesLike>,
     stroke_width: f32,
     zoom: Zoom,
     pan: f32,
-    timeframe: Timeframe,
+    /// Expected spacing of the points, a timeframe or the typical length of a tick bar
+    step_ms: u64,
     timezone: UserTimezone,
     version: u64,
+    value_scale: ValueScale,
+    /// Rebase point, the left edge of the window when `None`
+    anchor: Option<u64>,
+    /// Series whose price the y axis is labeled in, the others are still read in the scale
+    price_axis: Option<TickerInfo>,
+    /// Effective window scale factor, used to snap text and lines
+    scale_factor: f32,
 }
 
 impl<'a, S> LineComparison<'a, S>
 where
     S: SeriesLike,
 {
-    pub fn new(series: &'a [S], timeframe: Timeframe) -> Self {
+    pub fn new(series: &'a [S], step_ms: u64) -> Self {
         Self {
             series,
+            derived: Vec::new(),
             stroke_width: 2.0,
             zoom: Zoom::points(DEFAULT_ZOOM_POINTS),
-            timeframe,
+            step_ms,
             pan: 0.0,
             timezone: UserTimezone::Utc,
             version: 0,
+            value_scale: ValueScale::default(),
+            anchor: None,
+            price_axis: None,
+            scale_factor: 1.0,
         }
     }
 
+    pub fn with_derived<D: SeriesLike>(mut self, derived: &'a [D]) -> Self {
+        self.derived = derived.iter().map(|d| d as &dyn SeriesLike).collect();
+        self
+    }
+
+    /// Every line on the chart, the series followed by the derived ones
+    fn plotted(&self) -> impl Iterator<Item = &'a dyn SeriesLike> + '_ {
+        self.series
+            .iter()
+            .map(|s| s as &dyn SeriesLike)
+            .chain(self.derived.iter().copied())
+    }
+
     pub fn with_zoom(mut self, zoom: Zoom) -> Self {
         self.zoom = zoom;
         self
@@ -132,6 +170,65 @@ where
         self
     }
 
+    pub fn with_value_scale(mut self, scale: ValueScale) -> Self {
+        self.value_scale = scale;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: Option<u64>) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn with_price_axis(mut self, ticker_info: Option<TickerInfo>) -> Self {
+        self.price_axis = ticker_info;
+        self
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    fn text_size(&self) -> f32 {
+        style::dpi::text_size(TEXT_SIZE, self.scale_factor)
+    }
+
+    fn baseline(&self, points: &[(u64, f32)], ctx: &PlotContext) -> Option<Baseline> {
+        baseline(points, ctx.min_x, ctx.max_x, self.anchor)
+    }
+
+    /// Only the compared series themselves have a price, derived ones share their first leg
+    fn price_axis(&self, ctx: &PlotContext) -> Option<PriceAxis> {
+        if self.value_scale == ValueScale::Absolute {
+            return None;
+        }
+        let ticker_info = self.price_axis?;
+        let series = self
+            .series
+            .iter()
+            .find(|s| *s.ticker_info() == ticker_info)?;
+
+        Some(PriceAxis {
+            ticker_info,
+            baseline: self.baseline(series.points(), ctx)?,
+            color: series.color(),
+        })
+    }
+
+    /// `val` in the value scale, or as the pinned series' price when there is one
+    fn format_y(
+        &self,
+        price_axis: Option<&PriceAxis>,
+        val: f32,
+        step: f32,
+        show_decimals: bool,
+    ) -> String {
+        price_axis
+            .and_then(|axis| axis.label(self.value_scale, val))
+            .unwrap_or_else(|| self.value_scale.format(val, step, show_decimals))
+    }
+
     fn align_floor(ts: u64, dt: u64) -> u64 {
         if dt == 0 {
             return ts;
@@ -148,11 +245,7 @@ where
     }
 
     fn max_points_available(&self) -> usize {
-        self.series
-            .iter()
-            .map(|s| s.points().len())
-            .max()
-            .unwrap_or(0)
+        self.plotted().map(|s| s.points().len()).max().unwrap_or(0)
     }
 
     fn normalize_zoom(&self, z: Zoom) -> Zoom {
@@ -186,7 +279,7 @@ where
         let mut any = false;
         let mut data_min_x = u64::MAX;
         let mut data_max_x = u64::MIN;
-        for s in self.series {
+        for s in self.plotted() {
             for (x, _) in s.points() {
                 any = true;
                 if *x < data_min_x {
@@ -210,7 +303,7 @@ where
     }
 
     fn dt_ms_est(&self) -> u64 {
-        self.timeframe.to_milliseconds()
+        self.step_ms
     }
 
     fn compute_domains(&self, pan_points: f32) -> Option<((u64, u64), (f32, f32))> {
@@ -219,10 +312,11 @@ where
         }
 
         let dt = self.dt_ms_est().max(1);
-        let all_points: Vec<&[(u64, f32)]> = self.series.iter().map(|s| s.points()).collect();
+        let all_points: Vec<&[(u64, f32)]> = self.plotted().map(|s| s.points()).collect();
 
         let (min_x, max_x) = domain::window(&all_points, self.zoom, pan_points, dt)?;
-        let (min_pct, max_pct) = domain::pct_domain(&all_points, min_x, max_x)?;
+        let (min_pct, max_pct) =
+            domain::value_domain(&all_points, min_x, max_x, self.value_scale, self.anchor)?;
 
         Some(((min_x, max_x), (min_pct, max_pct)))
     }
@@ -250,19 +344,30 @@ where
 
         let total_ticks = (plot.height / TEXT_SIZE / 3.).floor() as usize;
         let (all_ticks, step) = super::ticks(min_pct, max_pct, total_ticks);
-        let mut ticks: Vec<f32> = all_ticks
-            .into_iter()
-            .filter(|t| (*t >= min_pct - f32::EPSILON) && (*t <= max_pct + f32::EPSILON))
-            .collect();
-        if ticks.is_empty() {
-            ticks = vec![min_pct, max_pct];
-        }
-        let labels: Vec<String> = ticks
-            .iter()
-            .map(|t| super::format_pct(*t, step, false))
-            .collect();
+        let price_axis = self.price_axis(&ctx);
+
+        let (ticks, labels) = match price_axis
+            .as_ref()
+            .and_then(|axis| axis.ticks(self.value_scale, min_pct, max_pct, total_ticks))
+        {
+            Some(price_ticks) => price_ticks.into_iter().unzip(),
+            None => {
+                let mut ticks: Vec<f32> = all_ticks
+                    .into_iter()
+                    .filter(|t| (*t >= min_pct - f32::EPSILON) && (*t <= max_pct + f32::EPSILON))
+                    .collect();
+                if ticks.is_empty() {
+                    ticks = vec![min_pct, max_pct];
+                }
+                let labels: Vec<String> = ticks
+                    .iter()
+                    .map(|t| self.value_scale.format(*t, step, false))
+                    .collect();
+                (ticks, labels)
+            }
+        };
 
-        let mut end_labels = self.collect_end_labels(&ctx, step);
+        let mut end_labels = self.collect_end_labels(&ctx, step, price_axis.as_ref());
         let plot_rect = ctx.plot_rect();
 
         resolve_label_overlaps(&mut end_labels, plot_rect);
@@ -367,7 +472,7 @@ where
                 ((ci.y_pct - ctx.min_pct) / (ctx.max_pct - ctx.min_pct).max(1e-6)).clamp(0.0, 1.0);
             let cy_px = plot_rect.y + plot_rect.height - t * plot_rect.height;
 
-            let pct_str = super::format_pct(ci.y_pct, step, true);
+            let pct_str = self.format_y(price_axis.as_ref(), ci.y_pct, step, true);
             let pct_est_w = (pct_str.len() as f32) * (TEXT_SIZE * 0.6) + 10.0;
 
             let gutter_w = ctx.gutter_width();
@@ -396,6 +501,7 @@ where
             cursor: cursor_info,
             reserved_y,
             y_step: step,
+            price_axis,
             legend: legend_layout,
             hovering_legend,
             hovered_icon,
@@ -426,21 +532,19 @@ where
         let mut max_name_chars: usize = 0;
         let mut rows_count: usize = 0;
 
-        for s in self.series.iter() {
+        for s in self.plotted() {
             rows_count += 1;
 
-            let name_len = s.ticker_info().ticker.symbol_and_exchange_string().len();
+            let name_len = s.legend_label().len();
             max_name_chars = max_name_chars.max(name_len);
 
             let pct_len = if include_pct_in_width {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
-                    .and_then(|y0| {
+                self.baseline(s.points(), ctx)
+                    .and_then(|base| {
                         cursor_x.and_then(|cx| {
-                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                let pct = ((yc / y0) - 1.0) * 100.0;
-                                super::format_pct(pct, step, true)
-                            })
+                            domain::interpolate_y_at(s.points(), cx)
+                                .and_then(|yc| self.value_scale.value(yc, base))
+                                .map(|v| self.value_scale.format(v, step, true))
                         })
                     })
                     .map(|s| s.len())
@@ -500,13 +604,13 @@ where
         let mut rows: Vec<LegendRowHit> = Vec::with_capacity(visible_rows);
         let mut row_top = bg.y + padding;
 
-        for (i, s) in self.series.iter().take(visible_rows).enumerate() {
+        for (i, s) in self.plotted().take(visible_rows).enumerate() {
             let y_center = row_top + line_h * 0.5;
 
             // Base ticker (i == 0) cannot be removed
             let has_close = i != 0;
 
-            let name_len = s.ticker_info().ticker.symbol_and_exchange_string().len() as f32;
+            let name_len = s.legend_label().len() as f32;
             let text_end_x = x_left + name_len * CHAR_W;
 
             let (cog, close, row_width) = if include_icons {
@@ -585,7 +689,7 @@ where
             };
 
             rows.push(LegendRowHit {
-                ticker: *s.ticker_info(),
+                index: i,
                 cog,
                 close,
                 y_center,
@@ -598,19 +702,23 @@ where
         Some(LegendLayout { bg, rows })
     }
 
-    fn collect_end_labels(&self, ctx: &PlotContext, step: f32) -> Vec<EndLabel> {
+    fn collect_end_labels(
+        &self,
+        ctx: &PlotContext,
+        step: f32,
+        price_axis: Option<&PriceAxis>,
+    ) -> Vec<EndLabel> {
         let mut end_labels: Vec<EndLabel> = Vec::new();
         let plot_height = ctx.plot_rect().height;
 
-        for s in self.series.iter() {
+        for (i, s) in self.plotted().enumerate() {
             let pts = s.points();
             if pts.is_empty() {
                 continue;
             }
-            let global_base = pts[0].1;
-            if global_base == 0.0 {
+            let Some(base) = self.baseline(pts, ctx) else {
                 continue;
-            }
+            };
 
             let last_vis = pts
                 .iter()
@@ -621,27 +729,9 @@ where
                 None => continue,
             };
 
-            let idx_right = pts.iter().position(|(x, _)| *x >= ctx.min_x);
-            let y0 = match idx_right {
-                Some(0) => pts[0].1,
-                Some(i) => {
-                    let (x0, y0) = pts[i - 1];
-                    let (x2, y2) = pts[i];
-                    let dx = (x2.saturating_sub(x0)) as f32;
-                    if dx > 0.0 {
-                        let t = (ctx.min_x.saturating_sub(x0)) as f32 / dx;
-                        y0 + (y2 - y0) * t.clamp(0.0, 1.0)
-                    } else {
-                        y0
-                    }
-                }
-                None => continue,
-            };
-
-            if y0 == 0.0 {
+            let Some(pct_label) = self.value_scale.value(y1, base) else {
                 continue;
-            }
-            let pct_label = ((y1 / y0) - 1.0) * 100.0;
+            };
 
             let mut py_local = ctx.map_y(pct_label);
             let half_txt = TEXT_SIZE * 0.5;
@@ -655,7 +745,14 @@ where
             };
             let bg_color = s.color();
 
-            let label_text = super::format_pct(pct_label, step, true);
+            // The pinned series reads its price, as the axis does
+            let is_pinned = i < self.series.len()
+                && price_axis.is_some_and(|a| a.ticker_info == *s.ticker_info());
+            let label_text = if is_pinned {
+                self.format_y(price_axis, pct_label, step, true)
+            } else {
+                self.value_scale.format(pct_label, step, true)
+            };
 
             end_labels.push(EndLabel {
                 pos: Point::new(
@@ -672,6 +769,45 @@ where
         end_labels
     }
 
+    /// The crosshair time, then a line per plotted series with its value and its change in
+    /// the current scale, tab separated so it pastes into a spreadsheet
+    fn legend_values(&self, scene: &Scene) -> Option<String> {
+        let cursor = scene.cursor?;
+        let ctx = &scene.ctx;
+
+        let mut lines = vec![Self::format_crosshair_time(cursor.x_domain, self.timezone)];
+
+        for (i, s) in self.plotted().enumerate() {
+            let Some(value) = domain::interpolate_y_at(s.points(), cursor.x_domain) else {
+                continue;
+            };
+
+            let mut line = if i < self.series.len() {
+                let price = Price::from_f64(f64::from(value));
+                format!(
+                    "{}\t{}",
+                    s.legend_label(),
+                    price.to_string(s.ticker_info().min_ticksize)
+                )
+            } else {
+                format!("{}\t{value}", s.legend_label())
+            };
+
+            if self.value_scale != ValueScale::Absolute
+                && let Some(scaled) = self
+                    .baseline(s.points(), ctx)
+                    .and_then(|base| self.value_scale.value(value, base))
+            {
+                line.push('\t');
+                line.push_str(&self.value_scale.format(scaled, scene.y_step, true));
+            }
+
+            lines.push(line);
+        }
+
+        (lines.len() > 1).then(|| lines.join("\n"))
+    }
+
     fn format_crosshair_time(ts_ms: u64, tz: UserTimezone) -> String {
         let ts_i64 = ts_ms as i64;
         match tz {
@@ -822,6 +958,8 @@ where
                         }
                     }
                     mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                        let mut is_double_click = false;
+
                         if let Some(global_pos) = cursor.position() {
                             let new_click = iced_core::mouse::Click::new(
                                 global_pos,
@@ -838,6 +976,8 @@ where
                                 return;
                             }
 
+                            is_double_click =
+                                new_click.kind() == iced_core::mouse::click::Kind::Double;
                             state.previous_click = Some(new_click);
                         } else {
                             state.previous_click = None;
@@ -851,28 +991,60 @@ where
                             && let Some(legend) = scene.legend.as_ref()
                         {
                             for row in &legend.rows {
+                                let derived = row.index.checked_sub(self.series.len());
+
                                 if row.cog.contains(cursor_pos) {
-                                    shell.publish(M::from(LineComparisonEvent::SeriesCog(
-                                        row.ticker,
-                                    )));
+                                    let event = match derived {
+                                        Some(index) => LineComparisonEvent::DerivedCog(index),
+                                        None => LineComparisonEvent::SeriesCog(
+                                            *self.series[row.index].ticker_info(),
+                                        ),
+                                    };
+                                    shell.publish(M::from(event));
                                     state.clear_all_caches();
                                     return;
                                 }
                                 if row.has_close && row.close.contains(cursor_pos) {
-                                    shell.publish(M::from(LineComparisonEvent::SeriesRemove(
-                                        row.ticker,
-                                    )));
+                                    let event = match derived {
+                                        Some(index) => LineComparisonEvent::DerivedRemove(index),
+                                        None => LineComparisonEvent::SeriesRemove(
+                                            *self.series[row.index].ticker_info(),
+                                        ),
+                                    };
+                                    shell.publish(M::from(event));
                                     state.clear_all_caches();
                                     return;
                                 }
                             }
                         }
 
+                        if matches!(zone, HitZone::Plot)
+                            && is_double_click
+                            && let Some(cursor_info) = self
+                                .compute_scene(layout, cursor)
+                                .and_then(|scene| scene.cursor)
+                        {
+                            shell.publish(M::from(LineComparisonEvent::AnchorChanged(
+                                cursor_info.x_domain,
+                            )));
+                            state.clear_all_caches();
+                            return;
+                        }
+
                         if matches!(zone, HitZone::Plot) {
                             state.is_panning = true;
                             state.last_cursor = Some(cursor_pos);
                         }
                     }
+                    mouse::Event::ButtonPressed(mouse::Button::Right) => {
+                        if matches!(zone, HitZone::Plot)
+                            && let Some(values) = self
+                                .compute_scene(layout, cursor)
+                                .and_then(|scene| self.legend_values(&scene))
+                        {
+                            shell.publish(M::from(LineComparisonEvent::LegendValues(values)));
+                        }
+                    }
                     mouse::Event::ButtonReleased(mouse::Button::Left) => {
                         state.is_panning = false;
                         state.last_cursor = None;
@@ -938,6 +1110,7 @@ where
             let plot_rect = scene.ctx.plot_rect();
 
             let plot_geom = state.plot_cache.draw(r, plot_rect.size(), |frame| {
+                self.fill_anchor_line(frame, &scene.ctx, palette);
                 self.fill_main_geometry(frame, &scene.ctx);
             });
 
@@ -976,7 +1149,10 @@ where
                     &scene.ctx,
                     &scene.y_ticks,
                     &scene.y_labels,
-                    palette,
+                    scene
+                        .price_axis
+                        .as_ref()
+                        .map_or(palette.background.base.text, |axis| axis.color),
                 );
             });
 
@@ -1087,9 +1263,22 @@ impl<'a, S> LineComparison<'a, S>
 where
     S: SeriesLike,
 {
+    /// Dashed line at the rebase point, when it is set and in view
+    fn fill_anchor_line(&self, frame: &mut canvas::Frame, ctx: &PlotContext, palette: &Extended) {
+        let Some(anchor) = self.anchor.filter(|x| *x >= ctx.min_x && *x <= ctx.max_x) else {
+            return;
+        };
+
+        let x = ctx.map_x(anchor);
+        frame.stroke(
+            &canvas::Path::line(Point::new(x, 0.0), Point::new(x, ctx.plot_rect().height)),
+            style::dashed_line_from_palette(palette),
+        );
+    }
+
     #[allow(unused_assignments)]
     fn fill_main_geometry(&self, frame: &mut canvas::Frame, ctx: &PlotContext) {
-        for s in self.series.iter() {
+        for s in self.plotted() {
             let pts = s.points();
             if pts.is_empty() {
                 continue;
@@ -1112,9 +1301,12 @@ where
                 None => continue,
             };
 
-            if y0 == 0.0 {
+            let Some(base) = self.baseline(pts, ctx) else {
                 continue;
-            }
+            };
+            let Some(base_value) = self.value_scale.value(y0, base) else {
+                continue;
+            };
 
             let mut builder = canvas::path::Builder::new();
 
@@ -1126,14 +1318,14 @@ where
             match idx_right {
                 Some(ir) if ir > 0 => {
                     let px0 = ctx.map_x(ctx.min_x);
-                    let py0 = ctx.map_y(0.0);
+                    let py0 = ctx.map_y(base_value);
                     builder.move_to(Point::new(px0, py0));
                     prev_x = Some(ctx.min_x);
                 }
                 Some(0) => {
                     let (fx, fy) = pts[0];
                     if fx <= ctx.max_x {
-                        let pct = ((fy / y0) - 1.0) * 100.0;
+                        let pct = self.value_scale.value(fy, base).unwrap_or(base_value);
                         builder.move_to(Point::new(ctx.map_x(fx), ctx.map_y(pct)));
                         prev_x = Some(fx);
                     } else {
@@ -1149,7 +1341,7 @@ where
                 if *x > ctx.max_x {
                     break;
                 }
-                let pct = ((*y / y0) - 1.0) * 100.0;
+                let pct = self.value_scale.value(*y, base).unwrap_or(base_value);
                 let px = ctx.map_x(*x);
                 let py = ctx.map_y(pct);
 
@@ -1169,9 +1361,9 @@ where
             let path = builder.build();
             frame.stroke(
                 &path,
-                canvas::Stroke::default()
-                    .with_color(s.color())
-                    .with_width(self.stroke_width),
+                canvas::Stroke::default().with_color(s.color()).with_width(
+                    style::dpi::stroke_width(self.stroke_width, self.scale_factor),
+                ),
             );
         }
     }
@@ -1220,7 +1412,7 @@ where
                     content: label.pct_change.clone(),
                     position: label.pos - Vector::new(4.0, 0.0),
                     color: label.text_color,
-                    size: TEXT_SIZE.into(),
+                    size: self.text_size().into(),
                     font: style::AZERET_MONO,
                     align_x: iced::Alignment::End.into(),
                     align_y: iced::Alignment::Center.into(),
@@ -1247,7 +1439,7 @@ where
                 content: label.symbol.clone(),
                 position: Point::new(sym_rect.x + sym_rect.width - 4.0, label.pos.y),
                 color: label.text_color,
-                size: TEXT_SIZE.into(),
+                size: self.text_size().into(),
                 font: style::AZERET_MONO,
                 align_x: iced::Alignment::End.into(),
                 align_y: iced::Alignment::Center.into(),
@@ -1262,7 +1454,7 @@ where
         ctx: &PlotContext,
         ticks: &[f32],
         labels: &[String],
-        palette: &Extended,
+        color: Color,
     ) {
         let plot = ctx.plot_rect();
         for (i, tick) in ticks.iter().enumerate() {
@@ -1274,8 +1466,8 @@ where
             frame.fill_text(canvas::Text {
                 content: labels[i].clone(),
                 position: Point::new(right_x, y_local),
-                color: palette.background.base.text,
-                size: TEXT_SIZE.into(),
+                color,
+                size: self.text_size().into(),
                 font: style::AZERET_MONO,
                 align_x: iced::Alignment::End.into(),
                 align_y: iced::Alignment::Center.into(),
@@ -1312,7 +1504,7 @@ where
                 content: label,
                 position: Point::new(x_local, y_center_local),
                 color: palette.background.base.text,
-                size: TEXT_SIZE.into(),
+                size: self.text_size().into(),
                 font: style::AZERET_MONO,
                 align_x: iced::Alignment::Center.into(),
                 align_y: iced::Alignment::Center.into(),
@@ -1352,7 +1544,7 @@ where
 
             let x0 = layout.bg.x + padding;
 
-            for (i, s) in self.series.iter().take(layout.rows.len()).enumerate() {
+            for (i, s) in self.plotted().take(layout.rows.len()).enumerate() {
                 let row = &layout.rows[i];
                 let y = (row.y_center).round() + 0.0;
 
@@ -1369,19 +1561,16 @@ where
                 let pct_str = if hovering_legend {
                     None
                 } else {
-                    domain::interpolate_y_at(s.points(), ctx.min_x)
-                        .filter(|&y0| y0 != 0.0)
-                        .and_then(|y0| {
-                            cursor_x.and_then(|cx| {
-                                domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                    let pct = ((yc / y0) - 1.0) * 100.0;
-                                    super::format_pct(pct, step, true)
-                                })
-                            })
+                    self.baseline(s.points(), ctx).and_then(|base| {
+                        cursor_x.and_then(|cx| {
+                            domain::interpolate_y_at(s.points(), cx)
+                                .and_then(|yc| self.value_scale.value(yc, base))
+                                .map(|v| self.value_scale.format(v, step, true))
                         })
+                    })
                 };
 
-                let symbol_and_exchange = s.ticker_info().ticker.symbol_and_exchange_string();
+                let symbol_and_exchange = s.legend_label();
                 let content = if let Some(pct) = pct_str {
                     format!("{symbol_and_exchange} {pct}")
                 } else {
@@ -1392,7 +1581,7 @@ where
                     content,
                     position: Point::new(x0, y),
                     color: s.color(),
-                    size: TEXT_SIZE.into(),
+                    size: self.text_size().into(),
                     font: style::AZERET_MONO,
                     align_x: iced::Alignment::Start.into(),
                     align_y: iced::Alignment::Center.into(),
@@ -1413,7 +1602,7 @@ where
                             y,
                         },
                         color: cog_col,
-                        size: TEXT_SIZE.into(),
+                        size: self.text_size().into(),
                         font: style::ICONS_FONT,
                         align_x: iced::Alignment::Center.into(),
                         align_y: iced::Alignment::Center.into(),
@@ -1428,7 +1617,7 @@ where
                                 y,
                             },
                             color: close_col,
-                            size: TEXT_SIZE.into(),
+                            size: self.text_size().into(),
                             font: style::ICONS_FONT,
                             align_x: iced::Alignment::Center.into(),
                             align_y: iced::Alignment::Center.into(),
@@ -1443,27 +1632,25 @@ where
         let mut max_chars: usize = 0;
         let mut rows_count: usize = 0;
 
-        for s in self.series.iter() {
+        for s in self.plotted() {
             rows_count += 1;
 
             let pct_len = if hovering_legend {
                 0
             } else {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
-                    .and_then(|y0| {
+                self.baseline(s.points(), ctx)
+                    .and_then(|base| {
                         cursor_x.and_then(|cx| {
-                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                let pct = ((yc / y0) - 1.0) * 100.0;
-                                super::format_pct(pct, step, true)
-                            })
+                            domain::interpolate_y_at(s.points(), cx)
+                                .and_then(|yc| self.value_scale.value(yc, base))
+                                .map(|v| self.value_scale.format(v, step, true))
                         })
                     })
                     .map(|s| s.len())
                     .unwrap_or(0)
             };
 
-            let name_len = s.ticker_info().ticker.symbol_and_exchange_string().len();
+            let name_len = s.legend_label().len();
             let total = if pct_len > 0 {
                 name_len + 1 + pct_len
             } else {
@@ -1494,7 +1681,7 @@ where
         let mut y = plot_rect.y + padding + TEXT_SIZE * 0.5;
         let x0 = plot_rect.x + padding;
 
-        for s in self.series.iter() {
+        for s in self.plotted() {
             if y > plot_rect.y + plot_rect.height - TEXT_SIZE {
                 break;
             }
@@ -1502,19 +1689,16 @@ where
             let pct_str = if hovering_legend {
                 None
             } else {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
-                    .and_then(|y0| {
-                        cursor_x.and_then(|cx| {
-                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                let pct = ((yc / y0) - 1.0) * 100.0;
-                                super::format_pct(pct, step, true)
-                            })
-                        })
+                self.baseline(s.points(), ctx).and_then(|base| {
+                    cursor_x.and_then(|cx| {
+                        domain::interpolate_y_at(s.points(), cx)
+                            .and_then(|yc| self.value_scale.value(yc, base))
+                            .map(|v| self.value_scale.format(v, step, true))
                     })
+                })
             };
 
-            let symbol_and_exchange = s.ticker_info().ticker.symbol_and_exchange_string();
+            let symbol_and_exchange = s.legend_label();
             let content = if let Some(pct) = pct_str {
                 format!("{symbol_and_exchange} {pct}")
             } else {
@@ -1525,7 +1709,7 @@ where
                 content,
                 position: Point::new(x0, y),
                 color: s.color(),
-                size: TEXT_SIZE.into(),
+                size: self.text_size().into(),
                 font: style::AZERET_MONO,
                 align_x: iced::Alignment::Start.into(),
                 align_y: iced::Alignment::Center.into(),
@@ -1589,7 +1773,7 @@ where
             content: time_str,
             position: Point::new(time_x, time_y),
             color: text_col,
-            size: TEXT_SIZE.into(),
+            size: self.text_size().into(),
             font: style::AZERET_MONO,
             align_x: iced::Alignment::Center.into(),
             align_y: iced::Alignment::Center.into(),
@@ -1597,7 +1781,7 @@ where
         });
 
         let gutter = ctx.gutter_width();
-        let pct_str = super::format_pct(ci.y_pct, scene.y_step, true);
+        let pct_str = self.format_y(scene.price_axis.as_ref(), ci.y_pct, scene.y_step, true);
         let label_h = TEXT_SIZE + 6.0;
 
         let split_x = plot_rect.x + plot_rect.width;
@@ -1618,7 +1802,7 @@ where
             content: pct_str,
             position: Point::new(ylbl_x_right - 4.0, ylbl_y),
             color: text_col,
-            size: TEXT_SIZE.into(),
+            size: self.text_size().into(),
             font: style::AZERET_MONO,
             align_x: iced::Alignment::End.into(),
             align_y: iced::Alignment::Center.into(),
@@ -1809,15 +1993,61 @@ struct Scene {
     cursor: Option<CursorInfo>,
     reserved_y: Option<Rectangle>,
     y_step: f32,
+    price_axis: Option<PriceAxis>,
     legend: Option<LegendLayout>,
     hovering_legend: bool,
     hovered_icon: Option<(usize, IconKind)>,
     hovered_row: Option<usize>,
 }
 
+/// Reads the y axis as the price of one series, through its baseline in the window
+#[derive(Debug, Clone, Copy)]
+struct PriceAxis {
+    ticker_info: TickerInfo,
+    baseline: Baseline,
+    color: Color,
+}
+
+impl PriceAxis {
+    fn label(&self, scale: ValueScale, val: f32) -> Option<String> {
+        scale
+            .invert(val, self.baseline)
+            .map(|price| self.format_price(price))
+    }
+
+    fn format_price(&self, price: f32) -> String {
+        Price::from_f64(f64::from(price)).to_string(self.ticker_info.min_ticksize)
+    }
+
+    /// Round prices between `min` and `max` of the value scale, placed where the series
+    /// reaches them. `None` if the range can't be expressed in price
+    fn ticks(
+        &self,
+        scale: ValueScale,
+        min: f32,
+        max: f32,
+        target: usize,
+    ) -> Option<Vec<(f32, String)>> {
+        let (lo, hi) = (
+            scale.invert(min, self.baseline)?,
+            scale.invert(max, self.baseline)?,
+        );
+        let (prices, _) = super::ticks(lo, hi, target);
+
+        let ticks = prices
+            .into_iter()
+            .filter(|p| *p >= lo && *p <= hi)
+            .filter_map(|p| Some((scale.value(p, self.baseline)?, self.format_price(p))))
+            .collect::<Vec<_>>();
+
+        (!ticks.is_empty()).then_some(ticks)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct LegendRowHit {
-    ticker: TickerInfo,
+    /// Position in the plotted lines, derived ones come after the series
+    index: usize,
     cog: Rectangle,
     close: Rectangle,
     y_center: f32,
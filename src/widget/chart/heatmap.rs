@@ -23,7 +23,10 @@ use crate::{
 use data::aggr::time::TimeSeries;
 use data::chart::{
     Basis,
-    heatmap::{HeatmapDataPoint, HeatmapStudy, HistoricalDepth},
+    heatmap::{
+        DevelopingValueArea, HeatmapDataPoint, HeatmapStudy, HistoricalDepth, HistoryLimit,
+        HistoryUsage,
+    },
     indicator::HeatmapIndicator,
 };
 use exchange::depth::Depth;
@@ -106,6 +109,9 @@ pub struct HeatmapShader {
     indicators: Vec<HeatmapIndicator>,
     pub studies: Vec<HeatmapStudy>,
     pub study_configurator: study::Configurator<HeatmapStudy>,
+    value_area: Option<DevelopingValueArea>,
+    /// Effective window scale factor (OS DPI x UI scale), used to snap axis and overlay text
+    scale_factor: f32,
 }
 
 impl HeatmapShader {
@@ -120,6 +126,11 @@ impl HeatmapShader {
         let depth_history = HistoricalDepth::new(ticker_info.min_qty, step, basis);
         let trades = TimeSeries::<HeatmapDataPoint>::new(basis, step);
 
+        let config = config.unwrap_or_default();
+
+        let mut scene = Scene::new();
+        scene.sync_shading(config.color_map, config.intensity);
+
         let qty_scale: f32 = match exchange::unit::qty::volume_size_unit() {
             exchange::SizeUnit::Base => {
                 let min_qty_f: f32 = ticker_info.min_qty.into();
@@ -130,7 +141,7 @@ impl HeatmapShader {
 
         Self {
             last_tick: None,
-            scene: Scene::new(),
+            scene,
             viewport: None,
             palette: None,
             qty_scale,
@@ -152,9 +163,14 @@ impl HeatmapShader {
             rebuild_policy: view::RebuildPolicy::Idle,
             indicators,
             anchor: view::Anchor::default(),
-            config: config.unwrap_or_default(),
+            config,
+            value_area: studies
+                .iter()
+                .find_map(HeatmapStudy::value_area_pct)
+                .map(DevelopingValueArea::new),
             studies,
             study_configurator: study::Configurator::new(),
+            scale_factor: 1.0,
         }
     }
 
@@ -347,6 +363,7 @@ impl HeatmapShader {
             is_x0_visible: self
                 .viewport
                 .map(|vp| self.scene.profile_start_visible_x0(vp.size())),
+            scale_factor: self.scale_factor,
         };
         let y_axis = AxisYLabelCanvas {
             cache: &self.canvas_caches.y_axis,
@@ -357,6 +374,7 @@ impl HeatmapShader {
             step: self.step,
             row_h: self.scene.cell.height_world(),
             label_precision: self.ticker_info.min_ticksize,
+            scale_factor: self.scale_factor,
         };
 
         let overlay = OverlayCanvas {
@@ -366,6 +384,9 @@ impl HeatmapShader {
             step: self.step,
             scroll_ref_bucket,
             qty_scale: self.qty_scale,
+            aggr_time,
+            trades: &self.trades,
+            tooltip: self.config.tooltip,
             tooltip_cache: &self.canvas_caches.overlay,
             scale_labels_cache: &self.canvas_caches.scale_labels,
             geometry: overlay_geometry,
@@ -373,6 +394,7 @@ impl HeatmapShader {
             volume_strip_max_qty: self.instances.volume_strip_scale_max_qty,
             depth_profile_max_qty: self.instances.depth_profile_scale_max_qty,
             volume_profile_max_qty: self.instances.volume_profile_scale_max_qty,
+            scale_factor: self.scale_factor,
         };
 
         let chart = HeatmapShaderWidget::new(&self.scene, x_axis, y_axis, overlay)
@@ -393,6 +415,13 @@ impl HeatmapShader {
         self.step
     }
 
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if (self.scale_factor - scale_factor).abs() >= f32::EPSILON {
+            self.scale_factor = scale_factor;
+            self.canvas_invalidation.mark_all();
+        }
+    }
+
     /// called periodically on every window frame
     /// to update time-based rendering and animate/scroll
     pub fn invalidate(&mut self, now: Option<Instant>) -> Option<Action> {
@@ -491,6 +520,11 @@ impl HeatmapShader {
         self.trades
             .ingest_trades_bucket(rounded_t, buffer, self.step);
 
+        DevelopingValueArea::sync(&mut self.value_area, &self.studies, &self.trades.datapoints);
+        if let Some(value_area) = &mut self.value_area {
+            value_area.update(&self.trades.datapoints);
+        }
+
         self.canvas_invalidation.mark_overlay_tooltip();
         self.canvas_invalidation.mark_overlay_scale_labels();
         self.try_rebuild_instances();
@@ -500,6 +534,16 @@ impl HeatmapShader {
         self.config
     }
 
+    pub fn history_usage(&self) -> HistoryUsage {
+        self.depth_history.usage()
+    }
+
+    pub fn export_depth(&self) -> std::io::Result<std::path::PathBuf> {
+        self.config
+            .export
+            .write_csv(&self.depth_history, &self.ticker_info)
+    }
+
     pub fn study_configurator(&self) -> &study::Configurator<HeatmapStudy> {
         &self.study_configurator
     }
@@ -535,6 +579,8 @@ impl HeatmapShader {
             return;
         }
 
+        DevelopingValueArea::sync(&mut self.value_area, &self.studies, &self.trades.datapoints);
+
         self.canvas_invalidation.mark_overlay_tooltip();
         self.canvas_invalidation.mark_overlay_scale_labels();
         self.try_rebuild_instances();
@@ -557,7 +603,13 @@ impl HeatmapShader {
         self.canvas_invalidation.mark_overlay_tooltip();
         self.canvas_invalidation.mark_overlay_scale_labels();
 
-        if trade_visual_changed || order_filter_changed {
+        if prev.color_map != self.config.color_map || prev.intensity != self.config.intensity {
+            self.scene
+                .sync_shading(self.config.color_map, self.config.intensity);
+        }
+
+        if trade_visual_changed || order_filter_changed || prev.depth_mode != self.config.depth_mode
+        {
             self.try_rebuild_instances();
         }
 
@@ -583,6 +635,18 @@ impl HeatmapShader {
     }
 
     fn cleanup_old_data(&mut self, aggr_time: u64) {
+        // A configured limit takes over from the ring's width
+        if self.config.history != HistoryLimit::Auto {
+            if let Some(cutoff) = self.depth_history.enforce_limit(self.config.history) {
+                self.trades.datapoints = self.trades.datapoints.split_off(&cutoff);
+
+                if let Some(value_area) = &mut self.value_area {
+                    value_area.prune_before(cutoff);
+                }
+            }
+            return;
+        }
+
         // Keep CPU history aligned with what the ring can represent
         let keep_buckets: u64 = (self.depth_grid.tex_w().max(1)) as u64;
 
@@ -605,6 +669,10 @@ impl HeatmapShader {
         // otherwise prune by cutoff directly
         if let Some(oldest_time) = self.trades.datapoints.keys().next().copied() {
             self.depth_history.cleanup_old_price_levels(oldest_time);
+
+            if let Some(value_area) = &mut self.value_area {
+                value_area.prune_before(oldest_time);
+            }
         } else {
             self.depth_history
                 .cleanup_old_price_levels(UnixMs::new(cutoff_rounded));
@@ -672,13 +740,7 @@ impl HeatmapShader {
             }
         }
 
-        let volume_profile = self
-            .studies
-            .iter()
-            .map(|study| match study {
-                HeatmapStudy::VolumeProfile(profile) => profile,
-            })
-            .next();
+        let volume_profile = self.studies.iter().find_map(HeatmapStudy::volume_profile);
 
         let latest_depth = self
             .depth_history
@@ -694,6 +756,7 @@ impl HeatmapShader {
         let built = self.instances.build_instances(
             &effective_window,
             &self.trades,
+            &self.depth_history,
             latest_depth,
             base_price,
             self.step,
@@ -704,6 +767,7 @@ impl HeatmapShader {
             &self.config,
             &self.ticker_info.market_type(),
             volume_profile,
+            self.value_area.as_ref(),
             show_volume_indicator,
         );
 
@@ -935,6 +999,7 @@ impl HeatmapShader {
             self.step,
             &self.ticker_info.market_type(),
             self.config.order_size_filter.max(0.0),
+            self.config.intensity,
             norm_gen,
             now_i,
             is_interacting,
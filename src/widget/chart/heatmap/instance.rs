@@ -5,20 +5,26 @@ use crate::widget::chart::heatmap::scene::pipeline::{DrawItem, DrawLayer, DrawOp
 use crate::widget::chart::heatmap::view::ViewWindow;
 
 use data::aggr::time::TimeSeries;
-use data::chart::heatmap::{Config, HeatmapDataPoint, ProfileKind};
+use data::chart::heatmap::{
+    Config, DepthMode, DevelopingValueArea, HeatmapDataPoint, HistoricalDepth, ProfileKind,
+};
 use exchange::SizeUnit;
 use exchange::UnixMs;
 use exchange::adapter::MarketKind;
 use exchange::unit::{Price, PriceStep, Qty};
+use rustc_hash::FxHashMap;
 
 #[derive(Debug, Clone)]
 pub struct OverlayBuild {
     pub circles: Vec<CircleInstance>,
     pub rects: Vec<RectInstance>,
+    pub depth_mode: DepthMode,
 
     // Ranges into `rects` for typed layering.
+    pub rect_depth_delta: std::ops::Range<u32>,
     pub rect_depth_profile: std::ops::Range<u32>,
     pub rect_volume: std::ops::Range<u32>,
+    pub rect_value_area: std::ops::Range<u32>,
     pub rect_volume_profile: std::ops::Range<u32>,
 }
 
@@ -30,8 +36,20 @@ impl OverlayBuild {
     pub fn draw_list(&self) -> Vec<DrawItem> {
         let mut out = Vec::new();
 
-        // Background
-        out.push(DrawItem::new(DrawLayer::HEATMAP, DrawOp::Heatmap));
+        // Background, the depth deltas stand in for the resting depth
+        match self.depth_mode {
+            DepthMode::Size => out.push(DrawItem::new(DrawLayer::HEATMAP, DrawOp::Heatmap)),
+            DepthMode::Delta if Self::count(&self.rect_depth_delta) > 0 => {
+                out.push(DrawItem::new(
+                    DrawLayer::HEATMAP,
+                    DrawOp::Rects {
+                        start: self.rect_depth_delta.start,
+                        count: Self::count(&self.rect_depth_delta),
+                    },
+                ));
+            }
+            DepthMode::Delta => {}
+        }
 
         // Behind circles
         if Self::count(&self.rect_depth_profile) > 0 {
@@ -66,6 +84,16 @@ impl OverlayBuild {
             ));
         }
 
+        if Self::count(&self.rect_value_area) > 0 {
+            out.push(DrawItem::new(
+                DrawLayer::VALUE_AREA,
+                DrawOp::Rects {
+                    start: self.rect_value_area.start,
+                    count: Self::count(&self.rect_value_area),
+                },
+            ));
+        }
+
         if Self::count(&self.rect_volume_profile) > 0 {
             out.push(DrawItem::new(
                 DrawLayer::VOLUME_PROFILE,
@@ -101,6 +129,7 @@ impl InstanceBuilder {
         &mut self,
         w: &ViewWindow,
         trades: &TimeSeries<HeatmapDataPoint>,
+        depth_history: &HistoricalDepth,
         latest_depth: impl IntoIterator<Item = (Price, Qty, bool)>,
         base_price: Price,
         step: PriceStep,
@@ -111,6 +140,7 @@ impl InstanceBuilder {
         config: &Config,
         market_type: &MarketKind,
         profile_kind: Option<&ProfileKind>,
+        value_area: Option<&DevelopingValueArea>,
         show_volume_strip: bool,
     ) -> OverlayBuild {
         // Reset denoms each rebuild to avoid stale overlay labels
@@ -132,6 +162,23 @@ impl InstanceBuilder {
 
         let mut rects: Vec<RectInstance> = Vec::new();
 
+        let delta_start = rects.len() as u32;
+        if config.depth_mode == DepthMode::Delta {
+            Self::build_depth_delta_rects(
+                w,
+                depth_history,
+                base_price,
+                step,
+                y_anchor,
+                scroll_ref_bucket,
+                palette,
+                config,
+                market_type,
+                &mut rects,
+            );
+        }
+        let delta_end = rects.len() as u32;
+
         let prof_start = rects.len() as u32;
         self.build_depth_profile_rects(
             w,
@@ -150,6 +197,21 @@ impl InstanceBuilder {
         }
         let vol_end = rects.len() as u32;
 
+        let va_start = rects.len() as u32;
+        if let Some(value_area) = value_area {
+            Self::build_value_area_rects(
+                w,
+                value_area,
+                base_price,
+                step,
+                y_anchor,
+                scroll_ref_bucket,
+                palette,
+                &mut rects,
+            );
+        }
+        let va_end = rects.len() as u32;
+
         let tp_start = rects.len() as u32;
         if let Some(kind) = profile_kind {
             self.build_volume_profile_rects(
@@ -169,12 +231,109 @@ impl InstanceBuilder {
         OverlayBuild {
             circles,
             rects,
+            depth_mode: config.depth_mode,
+            rect_depth_delta: delta_start..delta_end,
             rect_depth_profile: prof_start..prof_end,
             rect_volume: vol_start..vol_end,
+            rect_value_area: va_start..va_end,
             rect_volume_profile: tp_start..tp_end,
         }
     }
 
+    /// Stacked and pulled size summed over each cell of the visible window
+    fn build_depth_delta_rects(
+        w: &ViewWindow,
+        depth_history: &HistoricalDepth,
+        base_price: Price,
+        step: PriceStep,
+        y_anchor: Option<Price>,
+        ref_bucket: i64,
+        palette: &HeatmapPalette,
+        config: &Config,
+        market_type: &MarketKind,
+        rects: &mut Vec<RectInstance>,
+    ) {
+        let deltas = depth_history.depth_deltas(
+            UnixMs::new(w.earliest),
+            UnixMs::new(w.latest_vis),
+            w.highest,
+            w.lowest,
+            *market_type,
+            config.order_size_filter.max(0.0),
+        );
+
+        let mut cells: FxHashMap<(i64, i64), f64> = FxHashMap::default();
+        for (price, time, delta) in deltas {
+            let bucket = (time.as_u64() / w.aggr_time) as i64;
+            let rel_y_bin = w.y_bin_for_price_texture_aligned(price, base_price, step, y_anchor);
+            *cells.entry((bucket, rel_y_bin)).or_default() += delta;
+        }
+
+        let max_abs = cells
+            .values()
+            .fold(0.0_f64, |max, delta| max.max(delta.abs()));
+        if max_abs <= 0.0 {
+            return;
+        }
+
+        for ((bucket, rel_y_bin), delta) in cells {
+            if delta == 0.0 {
+                continue;
+            }
+
+            let x0_bin = (bucket - ref_bucket).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+            rects.push(RectInstance::depth_delta_cell(
+                w.y_center_for_bin(rel_y_bin),
+                delta as f32,
+                max_abs as f32,
+                x0_bin,
+                x0_bin.saturating_add(1),
+                w,
+                palette,
+            ));
+        }
+    }
+
+    fn build_value_area_rects(
+        w: &ViewWindow,
+        value_area: &DevelopingValueArea,
+        base_price: Price,
+        step: PriceStep,
+        y_anchor: Option<Price>,
+        ref_bucket: i64,
+        palette: &HeatmapPalette,
+        rects: &mut Vec<RectInstance>,
+    ) {
+        for (time, va) in value_area
+            .levels
+            .range(UnixMs::new(w.earliest)..=UnixMs::new(w.latest_vis))
+        {
+            let bucket = (time.as_u64() / w.aggr_time) as i64;
+            let x0_bin = (bucket - ref_bucket).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+            let x1_bin = x0_bin.saturating_add(1);
+
+            for (price, rgb) in [
+                (va.vah, palette.secondary_rgb),
+                (va.val, palette.secondary_rgb),
+                (va.poc, palette.primary_rgb),
+            ] {
+                if price < w.lowest || price > w.highest {
+                    continue;
+                }
+
+                let rel_y_bin =
+                    w.y_bin_for_price_texture_aligned(price, base_price, step, y_anchor);
+                rects.push(RectInstance::value_area_mark(
+                    w.y_center_for_bin(rel_y_bin),
+                    x0_bin,
+                    x1_bin,
+                    w,
+                    rgb,
+                ));
+            }
+        }
+    }
+
     fn build_volume_profile_rects(
         &mut self,
         w: &ViewWindow,
@@ -6,6 +6,7 @@ mod uniform;
 
 use super::Message;
 use cell::Cell;
+use data::chart::heatmap::{ColorMap, IntensityScale};
 use exchange::unit::{Price, PriceStep};
 use pipeline::Pipeline;
 use pipeline::circle::CircleInstance;
@@ -13,10 +14,10 @@ use pipeline::rectangle::RectInstance;
 use pipeline::{DrawItem, DrawLayer, DrawOp};
 use uniform::ParamsUniform;
 
-use iced::Rectangle;
 use iced::mouse;
 use iced::wgpu;
 use iced::widget::shader::{self, Viewport};
+use iced::{Color, Rectangle};
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -233,6 +234,17 @@ impl Scene {
         }
     }
 
+    pub fn sync_shading(&mut self, color_map: ColorMap, intensity: IntensityScale) {
+        let (low, high) = match color_map {
+            ColorMap::Gradient { low, high } => (low, high),
+            _ => (Color::BLACK, Color::WHITE),
+        };
+        let linear = |c: Color| depth_grid::HeatmapPalette::srgb_to_linear([c.r, c.g, c.b]);
+
+        self.params
+            .set_shading(color_map, intensity, (linear(low), linear(high)));
+    }
+
     pub fn zoom_column_world_keep_screen_anchor(
         &mut self,
         factor: f32,
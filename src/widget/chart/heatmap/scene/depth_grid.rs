@@ -932,6 +932,7 @@ pub struct HeatmapPalette {
     pub buy_rgb: [f32; 3],
     pub sell_rgb: [f32; 3],
     pub secondary_rgb: [f32; 3],
+    pub primary_rgb: [f32; 3],
 }
 
 impl HeatmapPalette {
@@ -952,12 +953,16 @@ impl HeatmapPalette {
         let secondary = palette.secondary.base.color;
         let secondary_linear = Self::srgb_to_linear([secondary.r, secondary.g, secondary.b]);
 
+        let primary = palette.primary.strong.color;
+        let primary_linear = Self::srgb_to_linear([primary.r, primary.g, primary.b]);
+
         Self {
             bid_rgb: bid_linear,
             ask_rgb: ask_linear,
             buy_rgb: buy_linear,
             sell_rgb: sell_linear,
             secondary_rgb: secondary_linear,
+            primary_rgb: primary_linear,
         }
     }
 
@@ -971,7 +976,7 @@ impl HeatmapPalette {
     }
 
     #[inline]
-    fn srgb_to_linear(rgb: [f32; 3]) -> [f32; 3] {
+    pub fn srgb_to_linear(rgb: [f32; 3]) -> [f32; 3] {
         [
             Self::srgb_to_linear_channel(rgb[0]),
             Self::srgb_to_linear_channel(rgb[1]),
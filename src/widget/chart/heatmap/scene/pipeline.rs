@@ -40,7 +40,7 @@ mod bind {
 }
 
 const CAMERA_UNIFORM_BYTES: usize = 2 * 16; // 2 vec4<f32>
-const PARAMS_UNIFORM_BYTES: usize = 8 * 16; // 8 vec4<f32>
+const PARAMS_UNIFORM_BYTES: usize = 11 * 16; // 11 vec4<f32>
 
 // Compile-time guarantees (fail build immediately on mismatch)
 const _: [(); CAMERA_UNIFORM_BYTES] = [(); std::mem::size_of::<CameraUniform>()];
@@ -60,6 +60,7 @@ impl DrawLayer {
     pub const DEPTH_PROFILE: Self = Self(10);
     pub const CIRCLES: Self = Self(20);
     pub const VOLUME: Self = Self(30);
+    pub const VALUE_AREA: Self = Self(35);
     pub const VOLUME_PROFILE: Self = Self(40);
 }
 
@@ -30,6 +30,11 @@ impl RectInstance {
 
     const VOLUME_PROFILE_ALPHA: f32 = 1.0;
 
+    const VALUE_AREA_ALPHA: f32 = 0.9;
+    const VALUE_AREA_LINE_PX: f32 = 1.5;
+
+    const DEPTH_DELTA_ALPHA_MIN: f32 = 0.15;
+
     fn extent_and_subpx_alpha(raw_world: f32, cam_scale: f32) -> (f32, f32) {
         let raw_world = raw_world.max(0.0);
         if raw_world <= 0.0 {
@@ -184,6 +189,59 @@ impl RectInstance {
         }
     }
 
+    /// One column wide mark at a developing value area level
+    pub fn value_area_mark(
+        y_world: f32,
+        x0_bin: i32,
+        x1_bin_excl: i32,
+        w: &ViewWindow,
+        rgb: [f32; 3],
+    ) -> Self {
+        let h = Self::VALUE_AREA_LINE_PX / w.cam_scale.max(1e-6);
+
+        Self {
+            position: [0.0, y_world],
+            size: [0.0, h],
+            color: [rgb[0], rgb[1], rgb[2], Self::VALUE_AREA_ALPHA],
+            x0_bin,
+            x1_bin_excl,
+            x_from_bins: 1,
+            fade_mode: 0,
+            subpx_alpha: 1.0,
+        }
+    }
+
+    /// One cell of change in resting size, its shade growing with the share of `max_abs`
+    pub fn depth_delta_cell(
+        y_world: f32,
+        delta: f32,
+        max_abs: f32,
+        x0_bin: i32,
+        x1_bin_excl: i32,
+        w: &ViewWindow,
+        palette: &HeatmapPalette,
+    ) -> Self {
+        let t = (delta.abs() / max_abs.max(f32::EPSILON)).clamp(0.0, 1.0);
+        let alpha = Self::DEPTH_DELTA_ALPHA_MIN + (1.0 - Self::DEPTH_DELTA_ALPHA_MIN) * t;
+
+        let rgb = if delta > 0.0 {
+            palette.buy_rgb
+        } else {
+            palette.sell_rgb
+        };
+
+        Self {
+            position: [0.0, y_world],
+            size: [0.0, w.y_bin_h_world],
+            color: [rgb[0], rgb[1], rgb[2], alpha],
+            x0_bin,
+            x1_bin_excl,
+            x_from_bins: 1,
+            fade_mode: 0,
+            subpx_alpha: 1.0,
+        }
+    }
+
     #[inline]
     pub fn y_center_for_bin(y_bin: i64, w: &ViewWindow) -> f32 {
         -((y_bin as f32 + 0.5) * w.y_bin_h_world)
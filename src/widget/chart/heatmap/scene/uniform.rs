@@ -1,5 +1,6 @@
 use crate::widget::chart::heatmap::view::ViewWindow;
 use bytemuck::{Pod, Zeroable};
+use data::chart::heatmap::{ColorMap, IntensityScale};
 
 // Shift volume-strip rects left by half a bucket to align with circle centers
 const VOLUME_X_SHIFT_BUCKET: f32 = -0.5;
@@ -8,6 +9,16 @@ const PROFILE_FADE_START_MULT: f32 = 1.5;
 const PROFILE_FADE_ALPHA_MIN: f32 = 0.15;
 const PROFILE_FADE_ALPHA_MAX: f32 = 1.0;
 
+// Must match the branches in `heatmap_tex.wgsl`
+const COLOR_MAP_THEME: f32 = 0.0;
+const COLOR_MAP_VIRIDIS: f32 = 1.0;
+const COLOR_MAP_MAGMA: f32 = 2.0;
+const COLOR_MAP_MONOCHROME: f32 = 3.0;
+const COLOR_MAP_GRADIENT: f32 = 4.0;
+
+const INTENSITY_LINEAR: f32 = 0.0;
+const INTENSITY_LOG: f32 = 1.0;
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct ParamsUniform {
@@ -27,6 +38,12 @@ pub struct ParamsUniform {
     heatmap_tex: [f32; 4],
     /// (x_left_world, width_world, alpha_min, alpha_max)
     fade: [f32; 4],
+    /// (color_map, intensity_scale, *pad*, *pad*), see `COLOR_MAP_*` and `INTENSITY_*`
+    shading: [f32; 4],
+    /// (r, g, b, *pad*) of the custom gradient's thinnest end
+    gradient_low: [f32; 4],
+    /// (r, g, b, *pad*) of the custom gradient's deepest end
+    gradient_high: [f32; 4],
 }
 
 impl Default for ParamsUniform {
@@ -40,6 +57,9 @@ impl Default for ParamsUniform {
             heatmap_map: [0.0, 0.0, 1.0, 0.0],
             heatmap_tex: [0.0, 0.0, 0.0, 0.0],
             fade: [0.0, 0.0, 1.0, 1.0],
+            shading: [COLOR_MAP_THEME, INTENSITY_LINEAR, 0.0, 0.0],
+            gradient_low: [0.0, 0.0, 0.0, 0.0],
+            gradient_high: [1.0, 1.0, 1.0, 0.0],
         }
     }
 }
@@ -94,6 +114,32 @@ impl ParamsUniform {
         self.ask_rgb = [ask_rgb[0], ask_rgb[1], ask_rgb[2], 0.0];
     }
 
+    /// Gradient colors are expected in linear space, like the palette's
+    pub fn set_shading(
+        &mut self,
+        color_map: ColorMap,
+        intensity: IntensityScale,
+        gradient: ([f32; 3], [f32; 3]),
+    ) {
+        let map = match color_map {
+            ColorMap::Theme => COLOR_MAP_THEME,
+            ColorMap::Viridis => COLOR_MAP_VIRIDIS,
+            ColorMap::Magma => COLOR_MAP_MAGMA,
+            ColorMap::Monochrome => COLOR_MAP_MONOCHROME,
+            ColorMap::Gradient { .. } => COLOR_MAP_GRADIENT,
+        };
+        // Percentile only moves the denominator, the shader maps it linearly
+        let scale = match intensity {
+            IntensityScale::Linear | IntensityScale::Percentile => INTENSITY_LINEAR,
+            IntensityScale::Log => INTENSITY_LOG,
+        };
+
+        let (low, high) = gradient;
+        self.shading = [map, scale, 0.0, 0.0];
+        self.gradient_low = [low[0], low[1], low[2], 0.0];
+        self.gradient_high = [high[0], high[1], high[2], 0.0];
+    }
+
     pub fn set_depth_denom(&mut self, denom: f32) {
         self.depth[0] = denom;
     }
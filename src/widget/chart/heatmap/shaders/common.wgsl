@@ -14,6 +14,9 @@ struct Params {
     heatmap_map: vec4<f32>,
     heatmap_tex: vec4<f32>,
     fade: vec4<f32>, // (x_left, width, alpha_min, alpha_max)
+    shading: vec4<f32>, // (color_map, intensity_scale, _, _)
+    gradient_low: vec4<f32>,
+    gradient_high: vec4<f32>,
 };
 @group(0) @binding(1)
 var<uniform> params: Params;
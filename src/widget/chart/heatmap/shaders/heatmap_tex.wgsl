@@ -75,8 +75,23 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     let alpha_min = params.depth.y;
     let alpha_max = params.depth.z;
 
-    let bid_t = clamp(bid_qty / max_depth, 0.0, 1.0);
-    let ask_t = clamp(ask_qty / max_depth, 0.0, 1.0);
+    let bid_t = intensity(bid_qty, max_depth);
+    let ask_t = intensity(ask_qty, max_depth);
+
+    let map_kind = i32(round(params.shading.x));
+    if (map_kind != COLOR_MAP_THEME) {
+        let qty = bid_qty + ask_qty;
+        if (qty <= 0.0) {
+            return vec4<f32>(0.0);
+        }
+
+        let t = intensity(qty, max_depth);
+        let a = MAP_ALPHA_MIN + t * (alpha_max - MAP_ALPHA_MIN);
+        let c = color_map(map_kind, t) * a;
+
+        let fade = fade_factor(world.x);
+        return vec4<f32>(c * fade, a * fade);
+    }
 
     let bid_a = select(0.0, alpha_min + bid_t * (alpha_max - alpha_min), bid_qty > 0.0);
     let ask_a = select(0.0, alpha_min + ask_t * (alpha_max - alpha_min), ask_qty > 0.0);
@@ -89,4 +104,73 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
 
     let fade = fade_factor(world.x);
     return vec4<f32>(c * fade, a * fade);
-}
\ No newline at end of file
+}
+
+// Must match `COLOR_MAP_*` and `INTENSITY_*` in `uniform.rs`
+const COLOR_MAP_THEME: i32 = 0;
+const COLOR_MAP_VIRIDIS: i32 = 1;
+const COLOR_MAP_MAGMA: i32 = 2;
+const COLOR_MAP_MONOCHROME: i32 = 3;
+
+const INTENSITY_LOG: i32 = 1;
+
+// Color maps run from near black, so the thinnest levels can't be left as faint as the theme's
+const MAP_ALPHA_MIN: f32 = 0.35;
+
+// Levels down to 1/255 of the denominator still get a distinct shade on the log scale
+const LOG_RANGE: f32 = 255.0;
+
+fn intensity(qty: f32, denom: f32) -> f32 {
+    let linear = clamp(qty / denom, 0.0, 1.0);
+    if (i32(round(params.shading.y)) == INTENSITY_LOG) {
+        return log2(1.0 + linear * LOG_RANGE) / log2(1.0 + LOG_RANGE);
+    }
+    return linear;
+}
+
+fn color_map(kind: i32, t: f32) -> vec3<f32> {
+    switch kind {
+        case COLOR_MAP_VIRIDIS: {
+            return srgb_to_linear(viridis(t));
+        }
+        case COLOR_MAP_MAGMA: {
+            return srgb_to_linear(magma(t));
+        }
+        case COLOR_MAP_MONOCHROME: {
+            return vec3<f32>(mix(0.02, 1.0, t));
+        }
+        default: {
+            return mix(params.gradient_low.xyz, params.gradient_high.xyz, t);
+        }
+    }
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let c01 = clamp(c, vec3<f32>(0.0), vec3<f32>(1.0));
+    let low = c01 / 12.92;
+    let high = pow((c01 + vec3<f32>(0.055)) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c01 <= vec3<f32>(0.04045));
+}
+
+// Polynomial fits of matplotlib's maps, in sRGB
+fn viridis(t: f32) -> vec3<f32> {
+    let c0 = vec3<f32>(0.2777273272234177, 0.005407344544966578, 0.3340998053353061);
+    let c1 = vec3<f32>(0.1050930431085774, 1.404613529898575, 1.384590162594685);
+    let c2 = vec3<f32>(-0.3308618287255563, 0.214847559468213, 0.09509516302823659);
+    let c3 = vec3<f32>(-4.634230498983486, -5.799100973351585, -19.33244095627987);
+    let c4 = vec3<f32>(6.228269936347081, 14.17993336680509, 56.69055260068105);
+    let c5 = vec3<f32>(4.776384997670288, -13.74514537774601, -65.35303263337234);
+    let c6 = vec3<f32>(-5.435455855934631, 4.645852612178535, 26.3124352495832);
+    return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
+}
+
+fn magma(t: f32) -> vec3<f32> {
+    let c0 = vec3<f32>(-0.002136485053939582, -0.000749655052795221, -0.005386127855323933);
+    let c1 = vec3<f32>(0.2516605407371642, 0.6775232436837668, 2.494026599312351);
+    let c2 = vec3<f32>(8.353717279216625, -3.577719514958484, 0.3144679030132573);
+    let c3 = vec3<f32>(-27.66873308576866, 14.26473078096533, -13.64921318813922);
+    let c4 = vec3<f32>(52.17613981234068, -27.94360607168351, 12.94416944238394);
+    let c5 = vec3<f32>(-50.76852536473588, 29.04658282127291, 4.23415299384598);
+    let c6 = vec3<f32>(18.65570506591883, -11.48977351997711, -5.601961508734096);
+    return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
+}
@@ -1,4 +1,5 @@
 use super::{AxisInteraction, Message};
+use crate::style::dpi;
 use crate::widget::chart::heatmap::{
     scene::camera::Camera,
     ui::{AXIS_TEXT_SIZE, AxisZoomAnchor},
@@ -30,6 +31,7 @@ pub struct AxisXLabelCanvas<'a> {
     pub column_world: f32,
     pub x_phase_bucket: f32,
     pub is_x0_visible: Option<bool>,
+    pub scale_factor: f32,
 }
 
 impl<'a> canvas::Program<Message> for AxisXLabelCanvas<'a> {
@@ -315,7 +317,7 @@ impl<'a> canvas::Program<Message> for AxisXLabelCanvas<'a> {
                             position: iced::Point::new(x_px, y),
                             color: text_color,
                             font: crate::style::AZERET_MONO,
-                            size: AXIS_TEXT_SIZE.into(),
+                            size: dpi::text_size(AXIS_TEXT_SIZE, self.scale_factor).into(),
                             align_x: iced::Alignment::Center.into(),
                             align_y: iced::Alignment::Center.into(),
                             ..Default::default()
@@ -351,7 +353,7 @@ impl<'a> canvas::Program<Message> for AxisXLabelCanvas<'a> {
                     content: cursor_label.text,
                     position: iced::Point::new(cursor_label.x_px, y),
                     color: palette.secondary.base.text,
-                    size: AXIS_TEXT_SIZE.into(),
+                    size: dpi::text_size(AXIS_TEXT_SIZE, self.scale_factor).into(),
                     font: crate::style::AZERET_MONO,
                     align_x: iced::Alignment::Center.into(),
                     align_y: iced::Alignment::Center.into(),
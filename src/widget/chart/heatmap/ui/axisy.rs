@@ -1,4 +1,5 @@
 use super::{AxisInteraction, Message};
+use crate::style::dpi;
 use crate::widget::chart::heatmap::{scene::camera::Camera, ui::AXIS_TEXT_SIZE};
 use exchange::unit::{MinTicksize, Price, PriceStep};
 use iced::{Rectangle, Renderer, Theme, widget::canvas};
@@ -18,6 +19,7 @@ pub struct AxisYLabelCanvas<'a> {
     pub row_h: f32,
     /// Rounds/formats labels to a decade step (e.g. power=-2 => 0.01).
     pub label_precision: MinTicksize,
+    pub scale_factor: f32,
 }
 
 /// Represents a label to be drawn on the Y axis.
@@ -319,7 +321,7 @@ impl canvas::Program<Message> for AxisYLabelCanvas<'_> {
                         content: label.clone(),
                         position: iced::Point::new(x, *y_px),
                         color: text_color,
-                        size: AXIS_TEXT_SIZE.into(),
+                        size: dpi::text_size(AXIS_TEXT_SIZE, self.scale_factor).into(),
                         font: crate::style::AZERET_MONO,
                         align_x: iced::Alignment::Center.into(),
                         align_y: iced::Alignment::Center.into(),
@@ -348,7 +350,7 @@ impl canvas::Program<Message> for AxisYLabelCanvas<'_> {
                         content: label.clone(),
                         position: iced::Point::new(x, y_px_base),
                         color: palette.primary.strong.text,
-                        size: AXIS_TEXT_SIZE.into(),
+                        size: dpi::text_size(AXIS_TEXT_SIZE, self.scale_factor).into(),
                         font: crate::style::AZERET_MONO,
                         align_x: iced::Alignment::Center.into(),
                         align_y: iced::Alignment::Center.into(),
@@ -372,7 +374,7 @@ impl canvas::Program<Message> for AxisYLabelCanvas<'_> {
                     content: label,
                     position: iced::Point::new(x, y_px),
                     color: palette.secondary.base.text,
-                    size: AXIS_TEXT_SIZE.into(),
+                    size: dpi::text_size(AXIS_TEXT_SIZE, self.scale_factor).into(),
                     font: crate::style::AZERET_MONO,
                     align_x: iced::Alignment::Center.into(),
                     align_y: iced::Alignment::Center.into(),
@@ -5,7 +5,10 @@ use crate::widget::chart::heatmap::scene::depth_grid::GridRing;
 use crate::widget::chart::heatmap::ui;
 use crate::widget::chart::heatmap::view;
 
+use data::aggr::time::TimeSeries;
+use data::chart::heatmap::{HeatmapDataPoint, TooltipKind};
 use data::util::abbr_large_numbers;
+use exchange::UnixMs;
 use exchange::unit::Qty;
 use exchange::unit::{Price, PriceStep};
 
@@ -27,6 +30,11 @@ const HIGHLIGHT_CROSSHAIR_GAP_PX: f32 = 1.0;
 const HIGHLIGHT_BORDER_WIDTH_PX: f32 = 1.0;
 const HIGHLIGHT_BORDER_ALPHA: f32 = 0.95;
 
+const CELL_LABEL_TEXT_SIZE: f32 = style::text_size::BODY;
+const CELL_LABEL_CHAR_W: f32 = CELL_LABEL_TEXT_SIZE * 0.64;
+const CELL_LABEL_H: f32 = CELL_LABEL_TEXT_SIZE + 6.0;
+const CELL_LABEL_OFFSET_PX: f32 = 8.0;
+
 const PAUSED_CTRL_TEXT: &str = "Paused";
 const PAUSED_CTRL_ICON_GAP_PX: f32 = 6.0;
 const PAUSED_CTRL_LABEL_TEXT_SIZE: f32 = style::text_size::SMALL;
@@ -145,6 +153,9 @@ pub struct OverlayCanvas<'a> {
     pub step: PriceStep,
     pub scroll_ref_bucket: i64,
     pub qty_scale: f32,
+    pub aggr_time: u64,
+    pub trades: &'a TimeSeries<HeatmapDataPoint>,
+    pub tooltip: TooltipKind,
 
     pub geometry: Option<view::OverlayGeometry>,
 
@@ -156,6 +167,7 @@ pub struct OverlayCanvas<'a> {
     pub volume_profile_max_qty: Option<Qty>,
 
     pub is_paused: bool,
+    pub scale_factor: f32,
 }
 
 impl<'a> canvas::Program<Message> for OverlayCanvas<'a> {
@@ -227,7 +239,10 @@ impl<'a> canvas::Program<Message> for OverlayCanvas<'a> {
                     frame.fill_text(canvas::Text {
                         content: abbr_large_numbers(f64::from(qty)),
                         position: Point::new(x_pos, strip_top_y),
-                        size: iced::Pixels(OVERLAY_SCALE_LABEL_TEXT_SIZE),
+                        size: iced::Pixels(style::dpi::text_size(
+                            OVERLAY_SCALE_LABEL_TEXT_SIZE,
+                            self.scale_factor,
+                        )),
                         color: palette.background.base.text.scale_alpha(0.85),
                         font: style::AZERET_MONO,
                         align_x: Alignment::End.into(),
@@ -261,7 +276,10 @@ impl<'a> canvas::Program<Message> for OverlayCanvas<'a> {
                             frame.fill_text(canvas::Text {
                                 content: abbr_large_numbers(f64::from(qty)),
                                 position: Point::new(tx, ty),
-                                size: iced::Pixels(OVERLAY_SCALE_LABEL_TEXT_SIZE),
+                                size: iced::Pixels(style::dpi::text_size(
+                                    OVERLAY_SCALE_LABEL_TEXT_SIZE,
+                                    self.scale_factor,
+                                )),
                                 color: palette.background.base.text.scale_alpha(0.85),
                                 font: style::AZERET_MONO,
                                 align_x: Alignment::End.into(),
@@ -302,7 +320,10 @@ impl<'a> canvas::Program<Message> for OverlayCanvas<'a> {
                             frame.fill_text(canvas::Text {
                                 content: abbr_large_numbers(f64::from(qty)),
                                 position: Point::new(tx, ty),
-                                size: iced::Pixels(OVERLAY_SCALE_LABEL_TEXT_SIZE),
+                                size: iced::Pixels(style::dpi::text_size(
+                                    OVERLAY_SCALE_LABEL_TEXT_SIZE,
+                                    self.scale_factor,
+                                )),
                                 color: palette.background.base.text.scale_alpha(0.85),
                                 font: style::AZERET_MONO,
                                 align_x: Alignment::Start.into(),
@@ -386,6 +407,20 @@ impl<'a> canvas::Program<Message> for OverlayCanvas<'a> {
 
             let y_start_bin = self.scene.params.heatmap_start_bin();
 
+            if self.tooltip == TooltipKind::Cell {
+                self.draw_full_crosshair(frame, theme, bounds, x, y);
+
+                let resting = self.cell_resting_qty(
+                    tex_w,
+                    tex_h,
+                    base_rel_y_bin - y_start_bin,
+                    base_bucket_abs,
+                );
+                let traded = self.cell_traded_qty(base_rel_y_bin, steps_per_y_bin, base_bucket_abs);
+                self.draw_cell_labels(frame, theme, bounds, Point::new(x, y), resting, traded);
+                return;
+            }
+
             let any_nonzero = self.tooltip_neighborhood_has_data(
                 tex_w,
                 tex_h,
@@ -469,7 +504,10 @@ impl<'a> canvas::Program<Message> for OverlayCanvas<'a> {
                     frame.fill_text(canvas::Text {
                         content: abbr_large_numbers(qty as f64),
                         position: layout.cell_center(row_idx, col_idx),
-                        size: iced::Pixels(crate::style::text_size::TINY),
+                        size: iced::Pixels(style::dpi::text_size(
+                            crate::style::text_size::TINY,
+                            self.scale_factor,
+                        )),
                         color: color.scale_alpha(0.95),
                         align_x: Alignment::Center.into(),
                         align_y: Alignment::Center.into(),
@@ -578,7 +616,10 @@ impl<'a> OverlayCanvas<'a> {
                 icon_rect.x - PAUSED_CTRL_ICON_GAP_PX,
                 control_rect.y + (control_rect.height * 0.5),
             ),
-            size: iced::Pixels(PAUSED_CTRL_LABEL_TEXT_SIZE),
+            size: iced::Pixels(style::dpi::text_size(
+                PAUSED_CTRL_LABEL_TEXT_SIZE,
+                self.scale_factor,
+            )),
             color: palette.background.base.text.scale_alpha(0.82),
             font: style::AZERET_MONO,
             align_x: Alignment::End.into(),
@@ -587,6 +628,130 @@ impl<'a> OverlayCanvas<'a> {
         });
     }
 
+    /// Bid and ask size resting in the texture cell, in display units
+    fn cell_resting_qty(&self, tex_w: i64, tex_h: i64, y_tex: i64, bucket: i64) -> (f32, f32) {
+        if y_tex < 0 || y_tex >= tex_h {
+            return (0.0, 0.0);
+        }
+        let x_ring = self.depth_grid.ring_x_for_bucket(bucket) as i64;
+        if x_ring < 0 || x_ring >= tex_w {
+            return (0.0, 0.0);
+        }
+
+        let idx = (y_tex as usize) * (tex_w as usize) + (x_ring as usize);
+        self.depth_grid
+            .get_pair(idx)
+            .map(|(bid, ask)| (bid as f32 / self.qty_scale, ask as f32 / self.qty_scale))
+            .unwrap_or((0.0, 0.0))
+    }
+
+    /// Buy and sell volume traded within the row's price range during the bucket
+    fn cell_traded_qty(&self, rel_y_bin: i64, steps_per_y_bin: i64, bucket: i64) -> (Qty, Qty) {
+        let (Some(base_price), Ok(bucket)) = (self.base_price, u64::try_from(bucket)) else {
+            return (Qty::ZERO, Qty::ZERO);
+        };
+        let Some(dp) = self
+            .trades
+            .datapoints
+            .get(&UnixMs::new(bucket.saturating_mul(self.aggr_time)))
+        else {
+            return (Qty::ZERO, Qty::ZERO);
+        };
+
+        let steps_per_y_bin = steps_per_y_bin.max(1);
+        let low = base_price.add_steps(rel_y_bin.saturating_mul(steps_per_y_bin), self.step);
+        let high = low.add_steps(steps_per_y_bin - 1, self.step);
+
+        dp.grouped_trades
+            .iter()
+            .filter(|trade| trade.price >= low && trade.price <= high)
+            .fold((Qty::ZERO, Qty::ZERO), |(buys, sells), trade| {
+                if trade.is_sell {
+                    (buys, sells + trade.qty)
+                } else {
+                    (buys + trade.qty, sells)
+                }
+            })
+    }
+
+    /// Resting size and traded volume of the hovered cell, as chips off the crosshair
+    fn draw_cell_labels(
+        &self,
+        frame: &mut canvas::Frame,
+        theme: &Theme,
+        bounds: Rectangle,
+        at: Point,
+        (bid, ask): (f32, f32),
+        (buys, sells): (Qty, Qty),
+    ) {
+        let palette = theme.extended_palette();
+
+        let resting = match (bid > 0.0, ask > 0.0) {
+            (true, true) => format!(
+                "Bid {} · Ask {}",
+                abbr_large_numbers(f64::from(bid)),
+                abbr_large_numbers(f64::from(ask))
+            ),
+            (true, false) => format!("Bid {}", abbr_large_numbers(f64::from(bid))),
+            (false, true) => format!("Ask {}", abbr_large_numbers(f64::from(ask))),
+            (false, false) => "No resting size".to_string(),
+        };
+        let traded = if buys.is_zero() && sells.is_zero() {
+            "No trades".to_string()
+        } else {
+            format!(
+                "Buy {} · Sell {}",
+                abbr_large_numbers(f64::from(buys)),
+                abbr_large_numbers(f64::from(sells))
+            )
+        };
+
+        let lines = [
+            (resting, palette.secondary.base.color),
+            (traded, palette.secondary.weak.color),
+        ];
+
+        let width = lines
+            .iter()
+            .map(|(line, _)| line.chars().count() as f32 * CELL_LABEL_CHAR_W + 12.0)
+            .fold(0.0, f32::max);
+        let height = CELL_LABEL_H * lines.len() as f32;
+
+        // Flipped to the other side of the crosshair where it would run off the chart
+        let x = if at.x + CELL_LABEL_OFFSET_PX + width > bounds.width {
+            at.x - CELL_LABEL_OFFSET_PX - width
+        } else {
+            at.x + CELL_LABEL_OFFSET_PX
+        };
+        let y = if at.y + CELL_LABEL_OFFSET_PX + height > bounds.height {
+            at.y - CELL_LABEL_OFFSET_PX - height
+        } else {
+            at.y + CELL_LABEL_OFFSET_PX
+        };
+
+        for (index, (content, bg)) in lines.into_iter().enumerate() {
+            let top = y + CELL_LABEL_H * index as f32;
+
+            frame.fill_rectangle(
+                Point::new(x.max(0.0), top),
+                iced::Size::new(width, CELL_LABEL_H),
+                bg,
+            );
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(x.max(0.0) + 6.0, top + CELL_LABEL_H / 2.0),
+                size: iced::Pixels(style::dpi::text_size(
+                    CELL_LABEL_TEXT_SIZE,
+                    self.scale_factor,
+                )),
+                color: palette.secondary.base.text,
+                font: style::AZERET_MONO,
+                align_y: Alignment::Center.into(),
+                ..canvas::Text::default()
+            });
+        }
+    }
+
     fn draw_full_crosshair(
         &self,
         frame: &mut canvas::Frame,
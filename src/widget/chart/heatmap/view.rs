@@ -2,7 +2,7 @@ use crate::widget::chart::heatmap::scene::{
     camera::Camera,
     cell::{Cell, MIN_ROW_PX},
 };
-use data::chart::heatmap::HistoricalDepth;
+use data::chart::heatmap::{HistoricalDepth, IntensityScale};
 use exchange::UnixMs;
 use exchange::adapter::MarketKind;
 use exchange::unit::{Price, PriceStep};
@@ -760,10 +760,11 @@ struct NormKey {
     y0_bin: i64,
     y1_bin: i64,
     order_filter_bits: u32,
+    intensity: IntensityScale,
 }
 
 #[derive(Debug)]
-/// Cache for depth normalization denom (max qty) to avoid per-frame scans.
+/// Cache for depth normalization denom (max or percentile qty) to avoid per-frame scans.
 pub struct DepthNormCache {
     key: Option<NormKey>,
     value: f32,
@@ -787,6 +788,7 @@ impl DepthNormCache {
         latest_incl: u64,
         step: PriceStep,
         order_size_filter: f32,
+        intensity: IntensityScale,
     ) -> NormKey {
         let aggr = w.aggr_time.max(1);
         let start_bucket = (w.earliest / aggr) as i64;
@@ -807,6 +809,7 @@ impl DepthNormCache {
             y0_bin,
             y1_bin,
             order_filter_bits: order_size_filter.max(0.0).to_bits(),
+            intensity,
         }
     }
 
@@ -818,11 +821,12 @@ impl DepthNormCache {
         step: PriceStep,
         market_type: &MarketKind,
         order_size_filter: f32,
+        intensity: IntensityScale,
         data_gen: u64,
         now: Instant,
         is_interacting: bool,
     ) -> f32 {
-        let key = self.make_key(w, latest_incl, step, order_size_filter);
+        let key = self.make_key(w, latest_incl, step, order_size_filter, intensity);
         let key_changed = self.key != Some(key);
 
         let throttle_ms = if is_interacting {
@@ -866,7 +870,17 @@ impl DepthNormCache {
             return self.value.max(1e-6);
         }
 
-        let max_qty = if order_size_filter > 0.0 {
+        let max_qty = if key.intensity == IntensityScale::Percentile {
+            hist.percentile_qty_in_range(
+                UnixMs::new(w.earliest),
+                UnixMs::new(latest_incl),
+                w.highest,
+                w.lowest,
+                *market_type,
+                order_size_filter,
+                IntensityScale::PERCENTILE,
+            )
+        } else if order_size_filter > 0.0 {
             hist.max_depth_qty_in_range(
                 UnixMs::new(w.earliest),
                 UnixMs::new(latest_incl),
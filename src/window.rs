@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
 use data::layout::WindowSpec;
-use iced::{Point, Size, Subscription, Task, window};
+use iced::advanced::widget::{self, Operation, operation::Outcome};
+use iced::{Point, Rectangle, Size, Subscription, Task, window};
 
-pub use iced::window::{Id, Position, Settings, close, open};
+pub use iced::window::{
+    Id, Level, Position, Screenshot, Settings, close, gain_focus, open, scale_factor,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Window {
@@ -24,6 +27,11 @@ pub fn default_size() -> Size {
 #[derive(Debug, Clone, Copy)]
 pub enum Event {
     CloseRequested(window::Id),
+    Opened(window::Id),
+    /// The OS scale factor of the window, excluding the UI scale setting
+    Rescaled(window::Id, f32),
+    /// A click, scroll or key press in any window, what keeps the app from counting as idle
+    UserInput,
 }
 
 pub fn events() -> Subscription<Event> {
@@ -39,6 +47,14 @@ fn filtered_events(
         iced::Event::Window(iced::window::Event::CloseRequested) => {
             Some(Event::CloseRequested(window))
         }
+        iced::Event::Window(iced::window::Event::Opened { .. }) => Some(Event::Opened(window)),
+        iced::Event::Window(iced::window::Event::Rescaled(scale_factor)) => {
+            Some(Event::Rescaled(window, *scale_factor))
+        }
+        iced::Event::Mouse(
+            iced::mouse::Event::ButtonPressed(_) | iced::mouse::Event::WheelScrolled { .. },
+        )
+        | iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { .. }) => Some(Event::UserInput),
         _ => None,
     }
 }
@@ -88,6 +104,71 @@ where
         })
 }
 
+/// Screenshot of the part of the window covered by the container with the `target` id.
+/// `None` if no such container is laid out
+pub fn capture_widget(window_id: Id, target: widget::Id) -> Task<Option<Screenshot>> {
+    widget::operate(FindBounds {
+        target,
+        bounds: None,
+    })
+    .then(move |bounds| {
+        let Some(bounds) = bounds else {
+            return Task::done(None);
+        };
+
+        window::screenshot(window_id).map(move |screenshot| {
+            let scale = screenshot.scale_factor;
+            let size = screenshot.size;
+
+            // Widget bounds are logical, the screenshot is in physical pixels
+            let x = ((bounds.x * scale).floor().max(0.0) as u32).min(size.width);
+            let y = ((bounds.y * scale).floor().max(0.0) as u32).min(size.height);
+            let width = ((bounds.width * scale).round() as u32).min(size.width - x);
+            let height = ((bounds.height * scale).round() as u32).min(size.height - y);
+
+            screenshot
+                .crop(Rectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                })
+                .ok()
+        })
+    })
+}
+
+pub fn into_capture(screenshot: Screenshot) -> data::snapshot::Capture {
+    data::snapshot::Capture {
+        width: screenshot.size.width,
+        height: screenshot.size.height,
+        rgba: screenshot.rgba.to_vec(),
+    }
+}
+
+struct FindBounds {
+    target: widget::Id,
+    bounds: Option<Rectangle>,
+}
+
+impl Operation<Option<Rectangle>> for FindBounds {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation<Option<Rectangle>>)) {
+        if self.bounds.is_none() {
+            operate(self);
+        }
+    }
+
+    fn container(&mut self, id: Option<&widget::Id>, bounds: Rectangle) {
+        if id == Some(&self.target) {
+            self.bounds = Some(bounds);
+        }
+    }
+
+    fn finish(&self) -> Outcome<Option<Rectangle>> {
+        Outcome::Some(self.bounds)
+    }
+}
+
 #[cfg(target_os = "linux")]
 pub fn settings() -> Settings {
     Settings {